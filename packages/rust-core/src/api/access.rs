@@ -0,0 +1,500 @@
+//! 访问控制
+//!
+//! 启用认证的 HTTP 服务器在执行端点前，按 `ApiEndpoint::access` 声明的资源和角色
+//! 检查当前用户在资源所属工作区中的角色（见 `types::member`）。
+//! Tauri 命令是本机调用，不做检查。
+//!
+//! - 资源不存在时不拒绝，由端点自己返回 NotFound 或空结果
+//! - 跨工作区列出或同步数据的端点不做整体检查，按 `WorkspaceScope` 过滤到用户所属的工作区
+//! - 影响整个服务器的操作（用户资料、日志、备份、清除数据等）要求管理员
+//!   （登录凭据上的标记，由 `GRAIN_ADMIN_USERNAME` 指定的用户）
+//! - 使用 API 令牌时还要符合令牌的权限范围：只读令牌只能访问需要 viewer 的端点，
+//!   限定工作区的令牌只能访问该工作区，备份令牌只能访问备份
+
+use std::collections::HashSet;
+
+use sea_orm::DatabaseConnection;
+
+use crate::db::{
+    attachment_db_fn, auth_db_fn, node_db_fn, search_db_fn, tag_db_fn, task_db_fn, workspace_db_fn,
+    workspace_member_db_fn, writing_db_fn,
+};
+use crate::types::auth::{ApiTokenGrant, ApiTokenScope, AuthUser};
+use crate::types::member::WorkspaceRole;
+use crate::{AppError, AppResult};
+
+/// 端点需要的访问权限
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Access {
+    /// 任何已登录用户
+    Authenticated,
+
+    /// 登录会话（不能使用 API 令牌，如管理 API 令牌）
+    Session,
+
+    /// 创建新的工作区
+    NewWorkspace,
+
+    /// 服务器上的备份（需要管理员；备份令牌只能访问备份）
+    Backup,
+
+    /// 服务器管理员
+    Admin,
+
+    /// 工作区（按工作区 ID）
+    Workspace(String, WorkspaceRole),
+
+    /// 节点所属的工作区（按节点 ID）
+    Node(String, WorkspaceRole),
+
+    /// 一组节点所属的工作区（按节点 ID，每个节点都要检查）
+    Nodes(Vec<String>, WorkspaceRole),
+
+    /// 标签所属的工作区（按标签 ID）
+    Tag(String, WorkspaceRole),
+
+    /// 附件所属的工作区（按附件 ID，不属于工作区的附件需要管理员）
+    Attachment(String, WorkspaceRole),
+
+    /// 保存的搜索所属的工作区（按搜索 ID）
+    SavedSearch(String, WorkspaceRole),
+
+    /// 任务所属的工作区（按任务 ID）
+    Task(String, WorkspaceRole),
+
+    /// 写作目标所属的工作区（按目标 ID）
+    WritingGoal(String, WorkspaceRole),
+}
+
+impl Access {
+    /// 需要的工作区角色
+    pub fn role(&self) -> Option<WorkspaceRole> {
+        match self {
+            Access::Authenticated
+            | Access::Session
+            | Access::NewWorkspace
+            | Access::Backup
+            | Access::Admin => None,
+            Access::Workspace(_, role)
+            | Access::Node(_, role)
+            | Access::Nodes(_, role)
+            | Access::Tag(_, role)
+            | Access::Attachment(_, role)
+            | Access::SavedSearch(_, role)
+            | Access::Task(_, role)
+            | Access::WritingGoal(_, role) => Some(*role),
+        }
+    }
+}
+
+/// 检查用户是否具有访问权限，没有时返回 Forbidden
+pub async fn authorize(db: &DatabaseConnection, user: &AuthUser, access: &Access) -> AppResult<()> {
+    if let Some(grant) = &user.token {
+        authorize_token(grant, access)?;
+    }
+    let (workspace_id, role) = match access {
+        Access::Authenticated | Access::Session | Access::NewWorkspace => return Ok(()),
+        Access::Backup | Access::Admin => return authorize_admin(db, user).await,
+        Access::Workspace(id, role) => (Some(id.clone()), *role),
+        Access::Node(id, role) => (
+            node_db_fn::find_by_id(db, id)
+                .await?
+                .map(|n| n.workspace_id),
+            *role,
+        ),
+        Access::Nodes(ids, role) => {
+            for id in ids {
+                if let Some(node) = node_db_fn::find_by_id(db, id).await? {
+                    authorize_in(db, user, &node.workspace_id, *role).await?;
+                }
+            }
+            return Ok(());
+        }
+        Access::Tag(id, role) => (
+            tag_db_fn::find_by_id(db, id).await?.map(|t| t.workspace_id),
+            *role,
+        ),
+        Access::Attachment(id, role) => match attachment_db_fn::find_by_id(db, id).await? {
+            Some(attachment) if attachment.project_id.is_none() => {
+                if let Some(grant) = &user.token {
+                    authorize_token(grant, &Access::Admin)?;
+                }
+                return authorize_admin(db, user).await;
+            }
+            attachment => (attachment.and_then(|a| a.project_id), *role),
+        },
+        Access::SavedSearch(id, role) => (
+            search_db_fn::find_by_id(db, id)
+                .await?
+                .map(|s| s.workspace_id),
+            *role,
+        ),
+        Access::Task(id, role) => (
+            task_db_fn::find_by_id(db, id)
+                .await?
+                .map(|t| t.workspace_id),
+            *role,
+        ),
+        Access::WritingGoal(id, role) => (
+            writing_db_fn::find_goal_by_id(db, id)
+                .await?
+                .map(|g| g.workspace_id),
+            *role,
+        ),
+    };
+    match workspace_id {
+        Some(workspace_id) => authorize_in(db, user, &workspace_id, role).await,
+        None => Ok(()),
+    }
+}
+
+/// 检查当前请求是否具有访问权限（没有认证身份时总是允许）
+///
+/// 与 `authorize` 相同，但权限不足时返回 false 而不是 Forbidden。
+pub async fn is_authorized(
+    db: &DatabaseConnection,
+    user: Option<&AuthUser>,
+    access: &Access,
+) -> AppResult<bool> {
+    let Some(user) = user else {
+        return Ok(true);
+    };
+    match authorize(db, user, access).await {
+        Ok(()) => Ok(true),
+        Err(AppError::Forbidden(_)) => Ok(false),
+        Err(e) => Err(e),
+    }
+}
+
+/// 当前请求可以访问的工作区
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WorkspaceScope {
+    /// 不限制（没有认证身份：Tauri 命令或未启用认证的服务器）
+    All,
+
+    /// 只能访问这些工作区
+    Only(HashSet<String>),
+}
+
+impl WorkspaceScope {
+    /// 用户具有 `role` 权限的工作区
+    ///
+    /// 使用 API 令牌时还要符合令牌的权限范围和限定的工作区。
+    pub async fn resolve(
+        db: &DatabaseConnection,
+        user: Option<&AuthUser>,
+        role: WorkspaceRole,
+    ) -> AppResult<Self> {
+        let Some(user) = user else {
+            return Ok(WorkspaceScope::All);
+        };
+        let mut ids = workspace_member_db_fn::find_workspace_ids(db, &user.user_id, role).await?;
+        if let Some(grant) = &user.token {
+            let allowed = match grant.scope {
+                ApiTokenScope::Full => true,
+                ApiTokenScope::ReadOnly => role == WorkspaceRole::Viewer,
+                ApiTokenScope::Backup => false,
+            };
+            ids.retain(|id| allowed && grant.workspace_id.as_ref().is_none_or(|limit| limit == id));
+        }
+        Ok(WorkspaceScope::Only(ids))
+    }
+
+    /// 是否可以访问工作区
+    pub fn contains(&self, workspace_id: &str) -> bool {
+        match self {
+            WorkspaceScope::All => true,
+            WorkspaceScope::Only(ids) => ids.contains(workspace_id),
+        }
+    }
+
+    /// 加入工作区（如用户刚创建的工作区）
+    pub fn insert(&mut self, workspace_id: &str) {
+        if let WorkspaceScope::Only(ids) = self {
+            ids.insert(workspace_id.to_string());
+        }
+    }
+}
+
+/// 检查 API 令牌限定的工作区和用户在工作区中的角色
+async fn authorize_in(
+    db: &DatabaseConnection,
+    user: &AuthUser,
+    workspace_id: &str,
+    role: WorkspaceRole,
+) -> AppResult<()> {
+    let limit = user.token.as_ref().and_then(|t| t.workspace_id.as_deref());
+    if limit.is_some_and(|limit| limit != workspace_id) {
+        return Err(AppError::forbidden("API 令牌限定在其他工作区"));
+    }
+    authorize_workspace(db, user, workspace_id, role).await
+}
+
+/// 检查 API 令牌的权限范围（不涉及用户角色）
+fn authorize_token(grant: &ApiTokenGrant, access: &Access) -> AppResult<()> {
+    let unlimited = grant.workspace_id.is_none();
+    let allowed = match (grant.scope, access) {
+        (_, Access::Session) => false,
+        (ApiTokenScope::Backup, access) => *access == Access::Backup,
+        (ApiTokenScope::ReadOnly, Access::Backup | Access::NewWorkspace | Access::Admin) => false,
+        (_, Access::Authenticated) => true,
+        (ApiTokenScope::Full, Access::Backup | Access::NewWorkspace | Access::Admin) => unlimited,
+        (ApiTokenScope::Full, _) => true,
+        (ApiTokenScope::ReadOnly, access) => access.role() == Some(WorkspaceRole::Viewer),
+    };
+    if allowed {
+        Ok(())
+    } else {
+        Err(AppError::forbidden("超出 API 令牌的权限范围"))
+    }
+}
+
+/// 检查用户是否为管理员
+async fn authorize_admin(db: &DatabaseConnection, user: &AuthUser) -> AppResult<()> {
+    if auth_db_fn::is_admin(db, &user.user_id).await? {
+        Ok(())
+    } else {
+        Err(AppError::forbidden("需要管理员权限"))
+    }
+}
+
+/// 检查用户在工作区中的角色
+///
+/// 工作区不存在时不拒绝；存在但用户不是成员或角色不足时返回 Forbidden。
+pub async fn authorize_workspace(
+    db: &DatabaseConnection,
+    user: &AuthUser,
+    workspace_id: &str,
+    role: WorkspaceRole,
+) -> AppResult<()> {
+    match workspace_member_db_fn::find_role(db, workspace_id, &user.user_id).await? {
+        Some(own) if own.allows(role) => Ok(()),
+        Some(_) => Err(AppError::forbidden(format!(
+            "需要工作区的 {} 权限",
+            role_name(role)
+        ))),
+        None => match workspace_db_fn::find_by_id(db, workspace_id).await? {
+            Some(_) => Err(AppError::forbidden("不是工作区成员")),
+            None => Ok(()),
+        },
+    }
+}
+
+fn role_name(role: WorkspaceRole) -> &'static str {
+    match role {
+        WorkspaceRole::Owner => "owner",
+        WorkspaceRole::Editor => "editor",
+        WorkspaceRole::Viewer => "viewer",
+    }
+}
+
+// ============================================================================
+// 测试
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::test_utils::setup_test_db;
+    use crate::types::node::NodeType;
+
+    #[tokio::test]
+    async fn test_authorize() {
+        let db = setup_test_db().await;
+        let alice = auth_db_fn::ensure_user(&db, "alice", "password-1")
+            .await
+            .unwrap();
+        let bob = auth_db_fn::ensure_user(&db, "bob", "password-1")
+            .await
+            .unwrap();
+        let alice = AuthUser {
+            user_id: alice.id,
+            session_id: String::new(),
+            token: None,
+        };
+        let bob = AuthUser {
+            user_id: bob.id,
+            session_id: String::new(),
+            token: None,
+        };
+        workspace_db_fn::create(&db, "ws".to_string(), "ws".to_string(), None)
+            .await
+            .unwrap();
+        node_db_fn::create(
+            &db,
+            "n1".to_string(),
+            "ws".to_string(),
+            None,
+            "节点".to_string(),
+            NodeType::File,
+            None,
+        )
+        .await
+        .unwrap();
+        workspace_member_db_fn::set_role(&db, "ws", &alice.user_id, WorkspaceRole::Owner)
+            .await
+            .unwrap();
+        workspace_member_db_fn::set_role(&db, "ws", &bob.user_id, WorkspaceRole::Viewer)
+            .await
+            .unwrap();
+
+        let editor = Access::Node("n1".to_string(), WorkspaceRole::Editor);
+        authorize(&db, &alice, &editor).await.unwrap();
+        assert!(matches!(
+            authorize(&db, &bob, &editor).await,
+            Err(AppError::Forbidden(_))
+        ));
+        let viewer = Access::Node("n1".to_string(), WorkspaceRole::Viewer);
+        authorize(&db, &bob, &viewer).await.unwrap();
+
+        // 资源不存在时交给端点处理
+        let missing = Access::Node("missing".to_string(), WorkspaceRole::Owner);
+        authorize(&db, &bob, &missing).await.unwrap();
+
+        // 批量操作检查每个节点
+        let batch = Access::Nodes(
+            vec!["missing".to_string(), "n1".to_string()],
+            WorkspaceRole::Editor,
+        );
+        authorize(&db, &alice, &batch).await.unwrap();
+        assert!(matches!(
+            authorize(&db, &bob, &batch).await,
+            Err(AppError::Forbidden(_))
+        ));
+
+        // 不是成员
+        workspace_member_db_fn::remove(&db, "ws", &bob.user_id)
+            .await
+            .unwrap();
+        assert!(matches!(
+            authorize(&db, &bob, &viewer).await,
+            Err(AppError::Forbidden(_))
+        ));
+        authorize(&db, &bob, &Access::Authenticated).await.unwrap();
+
+        // 管理员是登录凭据上的标记，与工作区角色无关：拥有所有工作区也不能访问备份
+        for access in [&Access::Admin, &Access::Backup] {
+            assert!(matches!(
+                authorize(&db, &alice, access).await,
+                Err(AppError::Forbidden(_))
+            ));
+        }
+        auth_db_fn::set_admin(&db, &bob.user_id, true)
+            .await
+            .unwrap();
+        authorize(&db, &bob, &Access::Admin).await.unwrap();
+        authorize(&db, &bob, &Access::Backup).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_workspace_scope() {
+        let db = setup_test_db().await;
+        let alice = auth_db_fn::ensure_user(&db, "alice", "password-1")
+            .await
+            .unwrap();
+        for id in ["ws1", "ws2", "ws3"] {
+            workspace_db_fn::create(&db, id.to_string(), id.to_string(), None)
+                .await
+                .unwrap();
+        }
+        workspace_member_db_fn::set_role(&db, "ws1", &alice.id, WorkspaceRole::Owner)
+            .await
+            .unwrap();
+        workspace_member_db_fn::set_role(&db, "ws2", &alice.id, WorkspaceRole::Viewer)
+            .await
+            .unwrap();
+        let user = |scope: Option<ApiTokenScope>, workspace_id: Option<&str>| AuthUser {
+            user_id: alice.id.clone(),
+            session_id: String::new(),
+            token: scope.map(|scope| ApiTokenGrant {
+                token_id: "t1".to_string(),
+                scope,
+                workspace_id: workspace_id.map(str::to_string),
+            }),
+        };
+        let ids = |scope: WorkspaceScope| match scope {
+            WorkspaceScope::All => panic!("不应该是全部工作区"),
+            WorkspaceScope::Only(ids) => {
+                let mut ids: Vec<_> = ids.into_iter().collect();
+                ids.sort();
+                ids
+            }
+        };
+
+        // 没有认证身份时不限制
+        let all = WorkspaceScope::resolve(&db, None, WorkspaceRole::Owner)
+            .await
+            .unwrap();
+        assert!(all.contains("ws3"));
+
+        let session = user(None, None);
+        let viewer = WorkspaceScope::resolve(&db, Some(&session), WorkspaceRole::Viewer)
+            .await
+            .unwrap();
+        assert_eq!(ids(viewer), vec!["ws1", "ws2"]);
+        let editor = WorkspaceScope::resolve(&db, Some(&session), WorkspaceRole::Editor)
+            .await
+            .unwrap();
+        assert_eq!(ids(editor), vec!["ws1"]);
+
+        // API 令牌的权限范围和限定的工作区
+        let read_only = user(Some(ApiTokenScope::ReadOnly), None);
+        let scope = WorkspaceScope::resolve(&db, Some(&read_only), WorkspaceRole::Editor)
+            .await
+            .unwrap();
+        assert!(ids(scope).is_empty());
+        let limited = user(Some(ApiTokenScope::Full), Some("ws2"));
+        let scope = WorkspaceScope::resolve(&db, Some(&limited), WorkspaceRole::Viewer)
+            .await
+            .unwrap();
+        assert_eq!(ids(scope), vec!["ws2"]);
+        let backup = user(Some(ApiTokenScope::Backup), None);
+        let scope = WorkspaceScope::resolve(&db, Some(&backup), WorkspaceRole::Viewer)
+            .await
+            .unwrap();
+        assert!(ids(scope).is_empty());
+    }
+
+    #[test]
+    fn test_authorize_token() {
+        let grant = |scope, workspace_id: Option<&str>| ApiTokenGrant {
+            token_id: "t1".to_string(),
+            scope,
+            workspace_id: workspace_id.map(str::to_string),
+        };
+        let read = Access::Workspace("ws".to_string(), WorkspaceRole::Viewer);
+        let write = Access::Node("n1".to_string(), WorkspaceRole::Editor);
+
+        let full = grant(ApiTokenScope::Full, None);
+        for access in [
+            &read,
+            &write,
+            &Access::Backup,
+            &Access::NewWorkspace,
+            &Access::Admin,
+        ] {
+            authorize_token(&full, access).unwrap();
+        }
+        assert!(authorize_token(&full, &Access::Session).is_err());
+
+        let read_only = grant(ApiTokenScope::ReadOnly, None);
+        authorize_token(&read_only, &read).unwrap();
+        authorize_token(&read_only, &Access::Authenticated).unwrap();
+        assert!(authorize_token(&read_only, &write).is_err());
+        assert!(authorize_token(&read_only, &Access::NewWorkspace).is_err());
+        assert!(authorize_token(&read_only, &Access::Admin).is_err());
+
+        // 限定工作区的令牌不能访问跨工作区的操作
+        let limited = grant(ApiTokenScope::Full, Some("ws"));
+        authorize_token(&limited, &write).unwrap();
+        assert!(authorize_token(&limited, &Access::NewWorkspace).is_err());
+        assert!(authorize_token(&limited, &Access::Backup).is_err());
+        assert!(authorize_token(&limited, &Access::Admin).is_err());
+
+        let backup = grant(ApiTokenScope::Backup, None);
+        authorize_token(&backup, &Access::Backup).unwrap();
+        for access in [&read, &write, &Access::Authenticated] {
+            assert!(authorize_token(&backup, access).is_err());
+        }
+    }
+}
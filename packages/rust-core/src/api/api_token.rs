@@ -0,0 +1,251 @@
+//! ApiToken API 端点
+//!
+//! 个人 API 令牌的管理。API 令牌供脚本和集成使用，与登录会话分开：
+//! 不会过期，可以随时撤销，以 `Authorization: Bearer grain_pat_...` 携带。
+//! 令牌的权限范围见 `ApiTokenScope`，实际权限同时受创建者在工作区中的角色限制（见 `access`）。
+//! 管理令牌需要登录会话，不能用 API 令牌调用。这些端点只用于 HTTP 服务器，没有 Tauri 命令。
+//!
+//! ## 端点列表
+//!
+//! | 端点 | 方法 | 路径 | 说明 |
+//! |------|------|------|------|
+//! | GetApiTokens | GET | /api/tokens | 当前用户的 API 令牌 |
+//! | CreateApiToken | POST | /api/tokens | 创建 API 令牌（令牌只返回一次） |
+//! | RevokeApiToken | DELETE | /api/tokens/:id | 撤销 API 令牌 |
+
+use sea_orm::DatabaseConnection;
+
+use super::access::authorize_workspace;
+use super::{Access, ApiEndpoint, AuthWithBodyInput, IdInput, NoOutput};
+use crate::db::{api_token_db_fn, workspace_db_fn};
+use crate::types::auth::{
+    ApiTokenResponse, ApiTokenScope, AuthUser, CreateApiTokenRequest, CreatedApiTokenResponse,
+};
+use crate::types::member::WorkspaceRole;
+use crate::{AppError, AppResult};
+
+// ============================================================================
+// GetApiTokens - 获取 API 令牌列表
+// ============================================================================
+
+/// 获取当前用户的 API 令牌（按创建时间排序）
+///
+/// ## HTTP
+/// - Method: GET
+/// - Path: /api/tokens
+///
+/// ## 参数
+/// - 当前请求的身份（来自访问令牌，需要登录会话）
+///
+/// ## 返回
+/// - 成功: Vec<ApiTokenResponse>
+/// - 失败: Forbidden（使用 API 令牌）, DatabaseError
+pub struct GetApiTokens;
+
+impl ApiEndpoint for GetApiTokens {
+    type Input = AuthUser;
+    type Output = Vec<ApiTokenResponse>;
+    const NAME: &'static str = "get_api_tokens";
+
+    fn access(_input: &Self::Input) -> Access {
+        Access::Session
+    }
+
+    async fn execute(db: &DatabaseConnection, input: Self::Input) -> AppResult<Self::Output> {
+        let tokens = api_token_db_fn::find_by_user(db, &input.user_id).await?;
+        Ok(tokens.into_iter().map(Into::into).collect())
+    }
+}
+
+// ============================================================================
+// CreateApiToken - 创建 API 令牌
+// ============================================================================
+
+/// 创建 API 令牌
+///
+/// 令牌只在响应中返回一次，之后只能看到它的开头部分。
+/// 限定工作区时，当前用户必须是该工作区的成员；备份令牌不能限定工作区。
+///
+/// ## HTTP
+/// - Method: POST
+/// - Path: /api/tokens
+/// - Body: CreateApiTokenRequest
+///
+/// ## 参数
+/// - 当前请求的身份（来自访问令牌，需要登录会话）
+/// - name: 名称
+/// - scope: 权限范围（full / readonly / backup）
+/// - workspace_id: 限定的工作区 ID（可选）
+///
+/// ## 返回
+/// - 成功: CreatedApiTokenResponse
+/// - 失败: ValidationError, NotFound（工作区不存在）, Forbidden, DatabaseError
+pub struct CreateApiToken;
+
+impl ApiEndpoint for CreateApiToken {
+    type Input = AuthWithBodyInput<CreateApiTokenRequest>;
+    type Output = CreatedApiTokenResponse;
+    const NAME: &'static str = "create_api_token";
+
+    fn access(_input: &Self::Input) -> Access {
+        Access::Session
+    }
+
+    async fn execute(db: &DatabaseConnection, input: Self::Input) -> AppResult<Self::Output> {
+        let AuthWithBodyInput { auth, body } = input;
+        let name = body.name.trim();
+        if name.is_empty() {
+            return Err(AppError::validation("API 令牌名称不能为空"));
+        }
+        if let Some(workspace_id) = &body.workspace_id {
+            if body.scope == ApiTokenScope::Backup {
+                return Err(AppError::validation("备份令牌不能限定工作区"));
+            }
+            if workspace_db_fn::find_by_id(db, workspace_id)
+                .await?
+                .is_none()
+            {
+                return Err(AppError::not_found(format!("Workspace {}", workspace_id)));
+            }
+            authorize_workspace(db, &auth, workspace_id, WorkspaceRole::Viewer).await?;
+        }
+
+        let (model, token) = api_token_db_fn::create(
+            db,
+            &auth.user_id,
+            name.to_string(),
+            body.scope,
+            body.workspace_id,
+        )
+        .await?;
+        Ok(CreatedApiTokenResponse {
+            info: model.into(),
+            token,
+        })
+    }
+}
+
+// ============================================================================
+// RevokeApiToken - 撤销 API 令牌
+// ============================================================================
+
+/// 撤销当前用户的 API 令牌，令牌立即失效
+///
+/// ## HTTP
+/// - Method: DELETE
+/// - Path: /api/tokens/:id
+///
+/// ## 参数
+/// - 当前请求的身份（来自访问令牌，需要登录会话）
+/// - id: 令牌 ID
+///
+/// ## 返回
+/// - 成功: ()
+/// - 失败: NotFound（不存在或属于其他用户）, Forbidden, DatabaseError
+pub struct RevokeApiToken;
+
+impl ApiEndpoint for RevokeApiToken {
+    type Input = AuthWithBodyInput<IdInput>;
+    type Output = NoOutput;
+    const NAME: &'static str = "revoke_api_token";
+
+    fn access(_input: &Self::Input) -> Access {
+        Access::Session
+    }
+
+    async fn execute(db: &DatabaseConnection, input: Self::Input) -> AppResult<Self::Output> {
+        if !api_token_db_fn::delete(db, &input.auth.user_id, &input.body.id).await? {
+            return Err(AppError::not_found(format!("API token {}", input.body.id)));
+        }
+        Ok(())
+    }
+}
+
+// ============================================================================
+// 测试
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::test_utils::setup_test_db;
+    use crate::db::{auth_db_fn, workspace_member_db_fn};
+
+    fn auth(user_id: &str) -> AuthUser {
+        AuthUser {
+            user_id: user_id.to_string(),
+            session_id: String::new(),
+            token: None,
+        }
+    }
+
+    fn create_request(
+        user_id: &str,
+        scope: ApiTokenScope,
+        workspace_id: Option<&str>,
+    ) -> AuthWithBodyInput<CreateApiTokenRequest> {
+        AuthWithBodyInput::new(
+            auth(user_id),
+            CreateApiTokenRequest {
+                name: "脚本".to_string(),
+                scope,
+                workspace_id: workspace_id.map(str::to_string),
+            },
+        )
+    }
+
+    #[tokio::test]
+    async fn test_api_tokens() {
+        let db = setup_test_db().await;
+        let alice = auth_db_fn::ensure_user(&db, "alice", "password-1")
+            .await
+            .unwrap();
+        let bob = auth_db_fn::ensure_user(&db, "bob", "password-1")
+            .await
+            .unwrap();
+        workspace_db_fn::create(&db, "ws".to_string(), "ws".to_string(), None)
+            .await
+            .unwrap();
+        workspace_member_db_fn::set_role(&db, "ws", &alice.id, WorkspaceRole::Owner)
+            .await
+            .unwrap();
+
+        let created = CreateApiToken::execute(
+            &db,
+            create_request(&alice.id, ApiTokenScope::ReadOnly, Some("ws")),
+        )
+        .await
+        .unwrap();
+        assert!(created.token.starts_with(&created.info.token_prefix));
+        let tokens = GetApiTokens::execute(&db, auth(&alice.id)).await.unwrap();
+        assert_eq!(tokens.len(), 1);
+        assert_eq!(tokens[0].workspace_id.as_deref(), Some("ws"));
+
+        // 不是成员的工作区、备份令牌限定工作区
+        let not_member = CreateApiToken::execute(
+            &db,
+            create_request(&bob.id, ApiTokenScope::Full, Some("ws")),
+        )
+        .await;
+        assert!(matches!(not_member, Err(AppError::Forbidden(_))));
+        let backup = CreateApiToken::execute(
+            &db,
+            create_request(&alice.id, ApiTokenScope::Backup, Some("ws")),
+        )
+        .await;
+        assert!(matches!(backup, Err(AppError::ValidationError(_))));
+
+        // 不能撤销其他用户的令牌
+        let revoke =
+            |user_id: &str| AuthWithBodyInput::new(auth(user_id), IdInput::new(&created.info.id));
+        let other = RevokeApiToken::execute(&db, revoke(&bob.id)).await;
+        assert!(matches!(other, Err(AppError::NotFound(_))));
+        RevokeApiToken::execute(&db, revoke(&alice.id))
+            .await
+            .unwrap();
+        assert!(GetApiTokens::execute(&db, auth(&alice.id))
+            .await
+            .unwrap()
+            .is_empty());
+    }
+}
@@ -0,0 +1,634 @@
+//! Attachment API 端点
+//!
+//! 附件（图片、音频等文件记录）相关的 API 端点实现。
+//! 附件按项目（工作区）归属，不属于任何项目的附件需要管理员。
+//!
+//! ## 端点列表
+//!
+//! | 端点 | 方法 | 路径 | 说明 |
+//! |------|------|------|------|
+//! | GetAttachments | GET | /api/attachments | 获取所有附件 |
+//! | GetAttachment | GET | /api/attachments/:id | 获取单个附件 |
+//! | GetAttachmentByPath | GET | /api/attachments/by-path/:file_path | 按文件路径获取附件 |
+//! | GetAttachmentsByProject | GET | /api/projects/:project_id/attachments?type= | 获取项目的附件（可按类型筛选） |
+//! | GetAttachmentsByType | - | （仅 Tauri） | 按类型获取项目的附件 |
+//! | GetImagesByProject | - | （仅 Tauri） | 获取项目的图片 |
+//! | GetAudioFilesByProject | - | （仅 Tauri） | 获取项目的音频 |
+//! | CreateAttachment | POST | /api/attachments | 创建附件 |
+//! | UpdateAttachment | PUT | /api/attachments/:id | 更新附件 |
+//! | DeleteAttachment | DELETE | /api/attachments/:id | 删除附件 |
+//! | DeleteAttachmentsByProject | DELETE | /api/projects/:project_id/attachments | 删除项目的所有附件 |
+
+use sea_orm::DatabaseConnection;
+
+use super::{
+    is_authorized, Access, ApiEndpoint, AttachmentsByProjectInput, AttachmentsByTypeInput,
+    FilePathInput, IdInput, IdWithBodyInput, NoOutput, OptionalAuthWithBodyInput, ProjectIdInput,
+    WorkspaceScope,
+};
+use crate::db::attachment_db_fn;
+use crate::types::attachment::{
+    AttachmentModel, AttachmentResponse, CreateAttachmentRequest, UpdateAttachmentRequest,
+};
+use crate::types::auth::AuthUser;
+use crate::types::member::WorkspaceRole;
+use crate::{AppError, AppResult};
+
+// ============================================================================
+// GetAttachments - 获取所有附件
+// ============================================================================
+
+/// 获取所有附件
+///
+/// 启用认证时只返回用户所属工作区的附件，不属于工作区的附件只返回给管理员。
+///
+/// ## HTTP
+/// - Method: GET
+/// - Path: /api/attachments
+///
+/// ## Tauri
+/// - Command: get_attachments
+///
+/// ## 参数
+/// - 当前请求的身份（来自访问令牌，可选）
+///
+/// ## 返回
+/// - 成功: Vec<AttachmentResponse>
+/// - 失败: DatabaseError
+pub struct GetAttachments;
+
+impl ApiEndpoint for GetAttachments {
+    type Input = Option<AuthUser>;
+    type Output = Vec<AttachmentResponse>;
+    const NAME: &'static str = "get_attachments";
+
+    fn access(_input: &Self::Input) -> Access {
+        Access::Authenticated
+    }
+
+    async fn execute(db: &DatabaseConnection, input: Self::Input) -> AppResult<Self::Output> {
+        let visible = VisibleAttachments::resolve(db, input.as_ref()).await?;
+        let attachments = attachment_db_fn::find_all(db).await?;
+        Ok(attachments
+            .into_iter()
+            .filter(|a| visible.contains(a))
+            .map(Into::into)
+            .collect())
+    }
+}
+
+// ============================================================================
+// GetAttachment / GetAttachmentByPath - 获取单个附件
+// ============================================================================
+
+/// 按 ID 获取附件
+///
+/// ## HTTP
+/// - Method: GET
+/// - Path: /api/attachments/:id
+///
+/// ## Tauri
+/// - Command: get_attachment
+///
+/// ## 参数
+/// - id: 附件 ID
+///
+/// ## 返回
+/// - 成功: Option<AttachmentResponse>（附件不存在时为 None）
+/// - 失败: DatabaseError
+pub struct GetAttachment;
+
+impl ApiEndpoint for GetAttachment {
+    type Input = IdInput;
+    type Output = Option<AttachmentResponse>;
+    const NAME: &'static str = "get_attachment";
+
+    fn access(input: &Self::Input) -> Access {
+        Access::Attachment(input.id.clone(), WorkspaceRole::Viewer)
+    }
+
+    async fn execute(db: &DatabaseConnection, input: Self::Input) -> AppResult<Self::Output> {
+        let attachment = attachment_db_fn::find_by_id(db, &input.id).await?;
+        Ok(attachment.map(Into::into))
+    }
+}
+
+/// 按文件路径获取附件
+///
+/// 路径不含工作区信息，启用认证时附件不在用户可以访问的范围内则返回 None（与 `GetAttachments` 相同）。
+///
+/// ## HTTP
+/// - Method: GET
+/// - Path: /api/attachments/by-path/:file_path
+///
+/// ## Tauri
+/// - Command: get_attachment_by_path
+///
+/// ## 参数
+/// - file_path: 附件文件路径（URL 编码）
+/// - 当前请求的身份（来自访问令牌，可选）
+///
+/// ## 返回
+/// - 成功: Option<AttachmentResponse>（附件不存在时为 None）
+/// - 失败: DatabaseError
+pub struct GetAttachmentByPath;
+
+impl ApiEndpoint for GetAttachmentByPath {
+    type Input = OptionalAuthWithBodyInput<FilePathInput>;
+    type Output = Option<AttachmentResponse>;
+    const NAME: &'static str = "get_attachment_by_path";
+
+    fn access(_input: &Self::Input) -> Access {
+        Access::Authenticated
+    }
+
+    async fn execute(db: &DatabaseConnection, input: Self::Input) -> AppResult<Self::Output> {
+        let visible = VisibleAttachments::resolve(db, input.auth.as_ref()).await?;
+        let attachment = attachment_db_fn::find_by_path(db, &input.body.file_path).await?;
+        Ok(attachment.filter(|a| visible.contains(a)).map(Into::into))
+    }
+}
+
+// ============================================================================
+// 按项目查询附件
+// ============================================================================
+
+/// 获取项目的附件，可按类型筛选
+///
+/// ## HTTP
+/// - Method: GET
+/// - Path: /api/projects/:project_id/attachments?type=
+///
+/// ## Tauri
+/// - Command: get_attachments_by_project
+///
+/// ## 参数
+/// - project_id: 项目（工作区）ID
+/// - type: 附件类型（可选）
+///
+/// ## 返回
+/// - 成功: Vec<AttachmentResponse>
+/// - 失败: DatabaseError
+pub struct GetAttachmentsByProject;
+
+impl ApiEndpoint for GetAttachmentsByProject {
+    type Input = AttachmentsByProjectInput;
+    type Output = Vec<AttachmentResponse>;
+    const NAME: &'static str = "get_attachments_by_project";
+
+    fn access(input: &Self::Input) -> Access {
+        Access::Workspace(input.project_id.clone(), WorkspaceRole::Viewer)
+    }
+
+    async fn execute(db: &DatabaseConnection, input: Self::Input) -> AppResult<Self::Output> {
+        let attachments = match input.attachment_type {
+            Some(attachment_type) => {
+                attachment_db_fn::find_by_type(db, &input.project_id, attachment_type).await?
+            }
+            None => attachment_db_fn::find_by_project(db, &input.project_id).await?,
+        };
+        Ok(attachments.into_iter().map(Into::into).collect())
+    }
+}
+
+/// 按类型获取项目的附件
+///
+/// HTTP 使用 `GET /api/projects/:project_id/attachments?type=`（见 GetAttachmentsByProject）
+///
+/// ## Tauri
+/// - Command: get_attachments_by_type
+///
+/// ## 参数
+/// - project_id: 项目（工作区）ID
+/// - attachment_type: 附件类型
+///
+/// ## 返回
+/// - 成功: Vec<AttachmentResponse>
+/// - 失败: DatabaseError
+pub struct GetAttachmentsByType;
+
+impl ApiEndpoint for GetAttachmentsByType {
+    type Input = AttachmentsByTypeInput;
+    type Output = Vec<AttachmentResponse>;
+    const NAME: &'static str = "get_attachments_by_type";
+
+    fn access(input: &Self::Input) -> Access {
+        Access::Workspace(input.project_id.clone(), WorkspaceRole::Viewer)
+    }
+
+    async fn execute(db: &DatabaseConnection, input: Self::Input) -> AppResult<Self::Output> {
+        let attachments =
+            attachment_db_fn::find_by_type(db, &input.project_id, input.attachment_type).await?;
+        Ok(attachments.into_iter().map(Into::into).collect())
+    }
+}
+
+/// 获取项目的图片附件
+///
+/// HTTP 使用 `GET /api/projects/:project_id/attachments?type=image`
+///
+/// ## Tauri
+/// - Command: get_images_by_project
+///
+/// ## 参数
+/// - project_id: 项目（工作区）ID
+///
+/// ## 返回
+/// - 成功: Vec<AttachmentResponse>
+/// - 失败: DatabaseError
+pub struct GetImagesByProject;
+
+impl ApiEndpoint for GetImagesByProject {
+    type Input = ProjectIdInput;
+    type Output = Vec<AttachmentResponse>;
+    const NAME: &'static str = "get_images_by_project";
+
+    fn access(input: &Self::Input) -> Access {
+        Access::Workspace(input.project_id.clone(), WorkspaceRole::Viewer)
+    }
+
+    async fn execute(db: &DatabaseConnection, input: Self::Input) -> AppResult<Self::Output> {
+        let attachments = attachment_db_fn::find_images_by_project(db, &input.project_id).await?;
+        Ok(attachments.into_iter().map(Into::into).collect())
+    }
+}
+
+/// 获取项目的音频附件
+///
+/// HTTP 使用 `GET /api/projects/:project_id/attachments?type=audio`
+///
+/// ## Tauri
+/// - Command: get_audio_files_by_project
+///
+/// ## 参数
+/// - project_id: 项目（工作区）ID
+///
+/// ## 返回
+/// - 成功: Vec<AttachmentResponse>
+/// - 失败: DatabaseError
+pub struct GetAudioFilesByProject;
+
+impl ApiEndpoint for GetAudioFilesByProject {
+    type Input = ProjectIdInput;
+    type Output = Vec<AttachmentResponse>;
+    const NAME: &'static str = "get_audio_files_by_project";
+
+    fn access(input: &Self::Input) -> Access {
+        Access::Workspace(input.project_id.clone(), WorkspaceRole::Viewer)
+    }
+
+    async fn execute(db: &DatabaseConnection, input: Self::Input) -> AppResult<Self::Output> {
+        let attachments = attachment_db_fn::find_audio_by_project(db, &input.project_id).await?;
+        Ok(attachments.into_iter().map(Into::into).collect())
+    }
+}
+
+// ============================================================================
+// CreateAttachment - 创建附件
+// ============================================================================
+
+/// 创建附件记录（文件本身由客户端保存）
+///
+/// ## HTTP
+/// - Method: POST
+/// - Path: /api/attachments
+/// - Body: CreateAttachmentRequest
+///
+/// ## Tauri
+/// - Command: create_attachment
+///
+/// ## 参数
+/// - project_id: 项目（工作区）ID，可选（不属于项目的附件需要管理员）
+/// - attachment_type / file_name / file_path: 附件信息
+/// - size / mime_type: 可选
+///
+/// ## 返回
+/// - 成功: AttachmentResponse
+/// - 失败: DatabaseError
+pub struct CreateAttachment;
+
+impl ApiEndpoint for CreateAttachment {
+    type Input = CreateAttachmentRequest;
+    type Output = AttachmentResponse;
+    const NAME: &'static str = "create_attachment";
+
+    fn access(input: &Self::Input) -> Access {
+        match &input.project_id {
+            Some(project_id) => Access::Workspace(project_id.clone(), WorkspaceRole::Editor),
+            None => Access::Admin,
+        }
+    }
+
+    async fn execute(db: &DatabaseConnection, input: Self::Input) -> AppResult<Self::Output> {
+        let id = uuid::Uuid::new_v4().to_string();
+        let attachment = attachment_db_fn::create(
+            db,
+            id,
+            input.project_id,
+            input.attachment_type,
+            input.file_name,
+            input.file_path,
+            input.size,
+            input.mime_type,
+        )
+        .await?;
+        Ok(attachment.into())
+    }
+}
+
+// ============================================================================
+// UpdateAttachment - 更新附件
+// ============================================================================
+
+/// 更新附件记录
+///
+/// ## HTTP
+/// - Method: PUT
+/// - Path: /api/attachments/:id
+/// - Body: UpdateAttachmentRequest
+///
+/// ## Tauri
+/// - Command: update_attachment
+///
+/// ## 参数
+/// - id: 附件 ID
+/// - file_name / file_path / size / mime_type: 为 None 时不变
+///
+/// ## 返回
+/// - 成功: AttachmentResponse
+/// - 失败: NotFound, DatabaseError
+pub struct UpdateAttachment;
+
+impl ApiEndpoint for UpdateAttachment {
+    type Input = IdWithBodyInput<UpdateAttachmentRequest>;
+    type Output = AttachmentResponse;
+    const NAME: &'static str = "update_attachment";
+
+    fn access(input: &Self::Input) -> Access {
+        Access::Attachment(input.id.clone(), WorkspaceRole::Editor)
+    }
+
+    async fn execute(db: &DatabaseConnection, input: Self::Input) -> AppResult<Self::Output> {
+        find_attachment(db, &input.id).await?;
+        let request = input.body;
+        let attachment = attachment_db_fn::update(
+            db,
+            &input.id,
+            request.file_name,
+            request.file_path,
+            request.size,
+            request.mime_type,
+        )
+        .await?;
+        Ok(attachment.into())
+    }
+}
+
+// ============================================================================
+// DeleteAttachment / DeleteAttachmentsByProject - 删除附件
+// ============================================================================
+
+/// 删除附件记录（附件不存在时不报错）
+///
+/// ## HTTP
+/// - Method: DELETE
+/// - Path: /api/attachments/:id
+///
+/// ## Tauri
+/// - Command: delete_attachment
+///
+/// ## 参数
+/// - id: 附件 ID
+///
+/// ## 返回
+/// - 成功: NoOutput
+/// - 失败: DatabaseError
+pub struct DeleteAttachment;
+
+impl ApiEndpoint for DeleteAttachment {
+    type Input = IdInput;
+    type Output = NoOutput;
+    const NAME: &'static str = "delete_attachment";
+
+    fn access(input: &Self::Input) -> Access {
+        Access::Attachment(input.id.clone(), WorkspaceRole::Editor)
+    }
+
+    async fn execute(db: &DatabaseConnection, input: Self::Input) -> AppResult<Self::Output> {
+        attachment_db_fn::delete(db, &input.id).await?;
+        Ok(())
+    }
+}
+
+/// 删除项目的所有附件记录
+///
+/// ## HTTP
+/// - Method: DELETE
+/// - Path: /api/projects/:project_id/attachments
+///
+/// ## Tauri
+/// - Command: delete_attachments_by_project
+///
+/// ## 参数
+/// - project_id: 项目（工作区）ID
+///
+/// ## 返回
+/// - 成功: u64（删除的附件数）
+/// - 失败: DatabaseError
+pub struct DeleteAttachmentsByProject;
+
+impl ApiEndpoint for DeleteAttachmentsByProject {
+    type Input = ProjectIdInput;
+    type Output = u64;
+    const NAME: &'static str = "delete_attachments_by_project";
+
+    fn access(input: &Self::Input) -> Access {
+        Access::Workspace(input.project_id.clone(), WorkspaceRole::Editor)
+    }
+
+    async fn execute(db: &DatabaseConnection, input: Self::Input) -> AppResult<Self::Output> {
+        Ok(attachment_db_fn::delete_by_project(db, &input.project_id).await?)
+    }
+}
+
+/// 当前请求可以看到的附件
+struct VisibleAttachments {
+    /// 可以访问的工作区
+    scope: WorkspaceScope,
+
+    /// 是否可以访问不属于工作区的附件
+    unowned: bool,
+}
+
+impl VisibleAttachments {
+    async fn resolve(db: &DatabaseConnection, auth: Option<&AuthUser>) -> AppResult<Self> {
+        Ok(Self {
+            scope: WorkspaceScope::resolve(db, auth, WorkspaceRole::Viewer).await?,
+            unowned: is_authorized(db, auth, &Access::Admin).await?,
+        })
+    }
+
+    fn contains(&self, attachment: &AttachmentModel) -> bool {
+        match &attachment.project_id {
+            Some(project_id) => self.scope.contains(project_id),
+            None => self.unowned,
+        }
+    }
+}
+
+/// 按 ID 查找附件，不存在时返回 NotFound
+async fn find_attachment(db: &DatabaseConnection, id: &str) -> AppResult<AttachmentModel> {
+    attachment_db_fn::find_by_id(db, id)
+        .await?
+        .ok_or_else(|| AppError::not_found(format!("Attachment {}", id)))
+}
+
+// ============================================================================
+// 测试
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::test_utils::setup_test_db;
+    use crate::db::{auth_db_fn, workspace_db_fn, workspace_member_db_fn};
+    use crate::types::attachment::AttachmentType;
+
+    #[tokio::test]
+    async fn test_attachment_crud() {
+        let db = setup_test_db().await;
+
+        let image = CreateAttachment::execute(
+            &db,
+            serde_json::from_value(serde_json::json!({
+                "projectId": "ws-1",
+                "attachmentType": "image",
+                "fileName": "cover.png",
+                "filePath": "images/cover.png",
+            }))
+            .unwrap(),
+        )
+        .await
+        .unwrap();
+
+        let images = GetImagesByProject::execute(&db, ProjectIdInput::new("ws-1"))
+            .await
+            .unwrap();
+        assert_eq!(images.len(), 1);
+        let audio = GetAttachmentsByType::execute(
+            &db,
+            AttachmentsByTypeInput::new("ws-1", AttachmentType::Audio),
+        )
+        .await
+        .unwrap();
+        assert!(audio.is_empty());
+        let filtered = GetAttachmentsByProject::execute(
+            &db,
+            AttachmentsByProjectInput::new("ws-1", Some(AttachmentType::Audio)),
+        )
+        .await
+        .unwrap();
+        assert!(filtered.is_empty());
+        let all =
+            GetAttachmentsByProject::execute(&db, AttachmentsByProjectInput::new("ws-1", None))
+                .await
+                .unwrap();
+        assert_eq!(all.len(), 1);
+        let by_path = GetAttachmentByPath::execute(
+            &db,
+            OptionalAuthWithBodyInput::anonymous(FilePathInput::new("images/cover.png")),
+        )
+        .await
+        .unwrap()
+        .unwrap();
+        assert_eq!(by_path.id, image.id);
+
+        let result = UpdateAttachment::execute(
+            &db,
+            IdWithBodyInput::new(
+                "missing",
+                serde_json::from_value(serde_json::json!({})).unwrap(),
+            ),
+        )
+        .await;
+        assert!(matches!(result, Err(AppError::NotFound(_))));
+
+        let deleted = DeleteAttachmentsByProject::execute(&db, ProjectIdInput::new("ws-1"))
+            .await
+            .unwrap();
+        assert_eq!(deleted, 1);
+    }
+
+    #[tokio::test]
+    async fn test_attachments_scoped_to_members() {
+        let db = setup_test_db().await;
+        let alice = auth_db_fn::ensure_user(&db, "alice", "password-1")
+            .await
+            .unwrap();
+        for id in ["ws1", "ws2"] {
+            workspace_db_fn::create(&db, id.to_string(), id.to_string(), None)
+                .await
+                .unwrap();
+        }
+        workspace_member_db_fn::set_role(&db, "ws1", &alice.id, WorkspaceRole::Viewer)
+            .await
+            .unwrap();
+        for (project_id, file_path) in [
+            (Some("ws1"), "ws1.png"),
+            (Some("ws2"), "ws2.png"),
+            (None, "shared.png"),
+        ] {
+            CreateAttachment::execute(
+                &db,
+                serde_json::from_value(serde_json::json!({
+                    "projectId": project_id,
+                    "attachmentType": "image",
+                    "fileName": file_path,
+                    "filePath": file_path,
+                }))
+                .unwrap(),
+            )
+            .await
+            .unwrap();
+        }
+        let auth = AuthUser {
+            user_id: alice.id.clone(),
+            session_id: String::new(),
+            token: None,
+        };
+        let by_path = |file_path: &str| {
+            OptionalAuthWithBodyInput::new(Some(auth.clone()), FilePathInput::new(file_path))
+        };
+
+        // 只能看到所属工作区的附件，不属于工作区的附件只有管理员能看到
+        let visible = GetAttachments::execute(&db, Some(auth.clone()))
+            .await
+            .unwrap();
+        let paths: Vec<_> = visible.iter().map(|a| a.file_path.as_str()).collect();
+        assert_eq!(paths, vec!["ws1.png"]);
+        assert!(GetAttachmentByPath::execute(&db, by_path("ws2.png"))
+            .await
+            .unwrap()
+            .is_none());
+        assert!(GetAttachmentByPath::execute(&db, by_path("shared.png"))
+            .await
+            .unwrap()
+            .is_none());
+
+        auth_db_fn::set_admin(&db, &alice.id, true).await.unwrap();
+        assert_eq!(
+            GetAttachments::execute(&db, Some(auth.clone()))
+                .await
+                .unwrap()
+                .len(),
+            2
+        );
+        assert!(GetAttachmentByPath::execute(&db, by_path("shared.png"))
+            .await
+            .unwrap()
+            .is_some());
+
+        // 没有认证身份（Tauri 命令）时返回全部
+        assert_eq!(GetAttachments::execute(&db, None).await.unwrap().len(), 3);
+    }
+}
@@ -0,0 +1,308 @@
+//! Auth API 端点
+//!
+//! HTTP API 的登录、刷新令牌和登出。
+//! 除健康检查、登录和刷新令牌外，所有 `/api` 请求都需要携带
+//! `Authorization: Bearer <accessToken>`（WebSocket 可用 `?access_token=`），
+//! 也可以用 API 令牌代替访问令牌（见 `api_token`），认证由 `server::routes` 中的过滤器完成。Tauri 命令不经过 HTTP，没有对应命令。
+//!
+//! ## 端点列表
+//!
+//! | 端点 | 方法 | 路径 | 说明 |
+//! |------|------|------|------|
+//! | Login | POST | /api/auth/login | 用户名密码登录，签发令牌 |
+//! | RefreshToken | POST | /api/auth/refresh | 用刷新令牌换取新令牌 |
+//! | Logout | POST | /api/auth/logout | 使当前会话失效 |
+//! | GetCurrentUser | GET | /api/auth/me | 当前登录的用户 |
+
+use std::sync::LazyLock;
+
+use sea_orm::DatabaseConnection;
+
+use super::{Access, ApiEndpoint, NoOutput};
+use crate::db::auth_db_fn::{self, IssuedSession};
+use crate::db::user_db_fn;
+use crate::r#fn::auth::{hash_password, verify_password};
+use crate::types::auth::{
+    AuthTokenResponse, AuthUser, LoginRequest, RefreshTokenRequest, TOKEN_TYPE_BEARER,
+};
+use crate::types::user::UserResponse;
+use crate::{AppError, AppResult};
+
+/// 用户不存在时用于校验的哈希，使响应时间与密码错误时一致
+static DUMMY_PASSWORD_HASH: LazyLock<String> = LazyLock::new(|| hash_password(""));
+
+fn token_response(issued: IssuedSession, user: UserResponse) -> AuthTokenResponse {
+    AuthTokenResponse {
+        access_token: issued.access_token,
+        refresh_token: issued.refresh_token,
+        token_type: TOKEN_TYPE_BEARER.to_string(),
+        expires_at: issued.session.access_expires_at,
+        refresh_expires_at: issued.session.refresh_expires_at,
+        user,
+    }
+}
+
+// ============================================================================
+// Login - 登录
+// ============================================================================
+
+/// 用户名密码登录
+///
+/// 每次登录签发一个新会话，多个设备可以同时登录。
+/// 用户不存在、没有设置密码或密码错误时返回相同的错误。
+///
+/// ## HTTP
+/// - Method: POST
+/// - Path: /api/auth/login
+/// - Body: LoginRequest
+///
+/// ## 参数
+/// - username: 用户名
+/// - password: 密码
+///
+/// ## 返回
+/// - 成功: AuthTokenResponse
+/// - 失败: Unauthorized（用户名或密码错误）, DatabaseError
+pub struct Login;
+
+impl ApiEndpoint for Login {
+    type Input = LoginRequest;
+    type Output = AuthTokenResponse;
+    const NAME: &'static str = "login";
+
+    /// 登录前调用，路由不经过认证
+    fn access(_input: &Self::Input) -> Access {
+        Access::Authenticated
+    }
+
+    async fn execute(db: &DatabaseConnection, input: Self::Input) -> AppResult<Self::Output> {
+        let user = user_db_fn::find_by_username(db, input.username.trim()).await?;
+        let credential = match &user {
+            Some(user) => auth_db_fn::find_credential(db, &user.id).await?,
+            None => None,
+        };
+        let password_hash = credential
+            .as_ref()
+            .map_or(DUMMY_PASSWORD_HASH.as_str(), |c| c.password_hash.as_str());
+        let verified = verify_password(&input.password, password_hash);
+
+        let (Some(user), Some(_), true) = (user, credential, verified) else {
+            return Err(AppError::unauthorized("用户名或密码错误"));
+        };
+
+        auth_db_fn::delete_expired(db).await?;
+        let issued = auth_db_fn::create_session(db, &user.id).await?;
+        let user = user_db_fn::update_last_login(db, &user.id).await?;
+        Ok(token_response(issued, user.into()))
+    }
+}
+
+// ============================================================================
+// RefreshToken - 刷新令牌
+// ============================================================================
+
+/// 用刷新令牌换取新的访问令牌和刷新令牌
+///
+/// 旧的两个令牌立即失效。
+///
+/// ## HTTP
+/// - Method: POST
+/// - Path: /api/auth/refresh
+/// - Body: RefreshTokenRequest
+///
+/// ## 参数
+/// - refresh_token: 刷新令牌
+///
+/// ## 返回
+/// - 成功: AuthTokenResponse
+/// - 失败: Unauthorized（刷新令牌无效或已过期）, DatabaseError
+pub struct RefreshToken;
+
+impl ApiEndpoint for RefreshToken {
+    type Input = RefreshTokenRequest;
+    type Output = AuthTokenResponse;
+    const NAME: &'static str = "refresh_token";
+
+    /// 访问令牌过期后调用，路由不经过认证
+    fn access(_input: &Self::Input) -> Access {
+        Access::Authenticated
+    }
+
+    async fn execute(db: &DatabaseConnection, input: Self::Input) -> AppResult<Self::Output> {
+        let issued = auth_db_fn::refresh_session(db, &input.refresh_token)
+            .await?
+            .ok_or_else(|| AppError::unauthorized("刷新令牌无效或已过期"))?;
+        let user = user_db_fn::find_by_id(db, &issued.session.user_id)
+            .await?
+            .ok_or_else(|| AppError::unauthorized("用户不存在"))?;
+        Ok(token_response(issued, user.into()))
+    }
+}
+
+// ============================================================================
+// Logout - 登出
+// ============================================================================
+
+/// 删除当前会话，使它的访问令牌和刷新令牌失效
+///
+/// API 令牌没有会话，需要通过 RevokeApiToken 撤销。
+///
+/// ## HTTP
+/// - Method: POST
+/// - Path: /api/auth/logout
+///
+/// ## 参数
+/// - 当前请求的身份（来自访问令牌）
+///
+/// ## 返回
+/// - 成功: ()
+/// - 失败: Unauthorized, Forbidden（使用 API 令牌）, DatabaseError
+pub struct Logout;
+
+impl ApiEndpoint for Logout {
+    type Input = AuthUser;
+    type Output = NoOutput;
+    const NAME: &'static str = "logout";
+
+    fn access(_input: &Self::Input) -> Access {
+        Access::Authenticated
+    }
+
+    async fn execute(db: &DatabaseConnection, input: Self::Input) -> AppResult<Self::Output> {
+        if input.token.is_some() {
+            return Err(AppError::forbidden("API 令牌不能登出，请撤销令牌"));
+        }
+        auth_db_fn::delete_session(db, &input.session_id).await
+    }
+}
+
+// ============================================================================
+// GetCurrentUser - 当前用户
+// ============================================================================
+
+/// 获取当前登录的用户
+///
+/// ## HTTP
+/// - Method: GET
+/// - Path: /api/auth/me
+///
+/// ## 参数
+/// - 当前请求的身份（来自访问令牌）
+///
+/// ## 返回
+/// - 成功: UserResponse
+/// - 失败: Unauthorized（用户已被删除）, DatabaseError
+pub struct GetCurrentUser;
+
+impl ApiEndpoint for GetCurrentUser {
+    type Input = AuthUser;
+    type Output = UserResponse;
+    const NAME: &'static str = "get_current_user";
+
+    fn access(_input: &Self::Input) -> Access {
+        Access::Authenticated
+    }
+
+    async fn execute(db: &DatabaseConnection, input: Self::Input) -> AppResult<Self::Output> {
+        let user = user_db_fn::find_by_id(db, &input.user_id)
+            .await?
+            .ok_or_else(|| AppError::unauthorized("用户不存在"))?;
+        Ok(user.into())
+    }
+}
+
+// ============================================================================
+// 测试
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::test_utils::setup_test_db;
+
+    fn login_request(username: &str, password: &str) -> LoginRequest {
+        LoginRequest {
+            username: username.to_string(),
+            password: password.to_string(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_login_and_refresh() {
+        let db = setup_test_db().await;
+        let user = auth_db_fn::ensure_user(&db, "alice", "correct horse")
+            .await
+            .unwrap();
+
+        let tokens = Login::execute(&db, login_request("alice", "correct horse"))
+            .await
+            .unwrap();
+        assert_eq!(tokens.token_type, "Bearer");
+        assert_eq!(tokens.user.id, user.id);
+        assert!(tokens.refresh_expires_at > tokens.expires_at);
+
+        let session = auth_db_fn::authenticate(&db, &tokens.access_token)
+            .await
+            .unwrap()
+            .unwrap();
+        let auth = AuthUser {
+            user_id: session.user_id,
+            session_id: session.id,
+            token: None,
+        };
+        let me = GetCurrentUser::execute(&db, auth.clone()).await.unwrap();
+        assert_eq!(me.username, "alice");
+
+        let refreshed = RefreshToken::execute(
+            &db,
+            RefreshTokenRequest {
+                refresh_token: tokens.refresh_token.clone(),
+            },
+        )
+        .await
+        .unwrap();
+        assert_ne!(refreshed.access_token, tokens.access_token);
+        // 刷新令牌只能使用一次
+        let reused = RefreshToken::execute(
+            &db,
+            RefreshTokenRequest {
+                refresh_token: tokens.refresh_token,
+            },
+        )
+        .await;
+        assert!(matches!(reused, Err(AppError::Unauthorized(_))));
+
+        Logout::execute(&db, auth).await.unwrap();
+        assert!(auth_db_fn::authenticate(&db, &refreshed.access_token)
+            .await
+            .unwrap()
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn test_login_rejects_bad_credentials() {
+        let db = setup_test_db().await;
+        auth_db_fn::ensure_user(&db, "alice", "correct horse")
+            .await
+            .unwrap();
+        // 没有设置密码的用户不能登录
+        user_db_fn::create(
+            &db,
+            "bob".to_string(),
+            "bob".to_string(),
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+        )
+        .await
+        .unwrap();
+
+        for (username, password) in [("alice", "wrong horse"), ("carol", ""), ("bob", "")] {
+            let result = Login::execute(&db, login_request(username, password)).await;
+            assert!(matches!(result, Err(AppError::Unauthorized(_))));
+        }
+    }
+}
@@ -0,0 +1,281 @@
+//! ChangeLog API 端点
+//!
+//! 增量同步使用的变更日志端点。
+//! 所有写操作在其事务中追加一条记录（见 `PendingEvents::record`），序号单调递增；
+//! 查询时压缩过时的记录，压缩规则见 `fn::change_log`。
+//!
+//! ## 端点列表
+//!
+//! | 端点 | 方法 | 路径 | 说明 |
+//! |------|------|------|------|
+//! | GetChangesSince | GET | /api/changes?since=&workspaceId=&limit= | 查询序号之后的变更（已压缩） |
+//! | CompactChangeLog | POST | /api/changes/compact | 删除被取代的变更记录 |
+
+use sea_orm::DatabaseConnection;
+
+use super::{Access, ApiEndpoint, NoInput, OptionalAuthWithBodyInput, WorkspaceScope};
+use crate::db::change_log_db_fn;
+use crate::r#fn::change_log::compact_changes;
+use crate::types::change_log::{
+    ChangeEntity, ChangeLogEntry, ChangeLogModel, ChangeOperation, ChangesSinceQuery,
+    ChangesSinceResponse, CompactChangeLogResponse,
+};
+use crate::types::member::WorkspaceRole;
+use crate::{AppError, AppResult};
+
+/// 默认每次扫描的记录条数
+const DEFAULT_CHANGES_LIMIT: u64 = 500;
+
+/// 每次最多扫描的记录条数
+const MAX_CHANGES_LIMIT: u64 = 5000;
+
+// ============================================================================
+// GetChangesSince - 查询增量变更
+// ============================================================================
+
+/// 查询序号之后的变更
+///
+/// 同一实体只返回最新一条记录；工作区删除或清除数据之前的记录不再返回。
+/// 启用认证时只返回用户所属工作区的变更（见 `is_visible_change`）。
+/// 每次最多扫描 `limit` 条记录，压缩和过滤后返回的变更可能更少（甚至为空）。
+/// 客户端按顺序应用变更后，用 `nextSince` 作为下一次的 `since`，
+/// `hasMore` 为 true 时继续拉取。
+///
+/// ## HTTP
+/// - Method: GET
+/// - Path: /api/changes?since=&workspaceId=&limit=
+///
+/// ## Tauri
+/// - Command: get_changes_since
+///
+/// ## 参数
+/// - since: 上次同步到的序号（可选，默认 0）
+/// - workspace_id: 只返回该工作区的变更，清除数据等全局变更总是返回（可选）
+/// - limit: 最多扫描的记录条数（可选，默认 500，最大 5000）
+/// - 当前请求的身份（来自访问令牌，可选）
+///
+/// ## 返回
+/// - 成功: ChangesSinceResponse
+/// - 失败: ValidationError（since 为负数或 limit 为 0）, DatabaseError
+pub struct GetChangesSince;
+
+impl ApiEndpoint for GetChangesSince {
+    type Input = OptionalAuthWithBodyInput<ChangesSinceQuery>;
+    type Output = ChangesSinceResponse;
+    const NAME: &'static str = "get_changes_since";
+
+    fn access(input: &Self::Input) -> Access {
+        match &input.body.workspace_id {
+            Some(id) => Access::Workspace(id.clone(), WorkspaceRole::Viewer),
+            None => Access::Authenticated,
+        }
+    }
+
+    async fn execute(db: &DatabaseConnection, input: Self::Input) -> AppResult<Self::Output> {
+        let scope = WorkspaceScope::resolve(db, input.auth.as_ref(), WorkspaceRole::Viewer).await?;
+        let input = input.body;
+        let since = input.since.unwrap_or(0);
+        if since < 0 {
+            return Err(AppError::validation("since 不能为负数"));
+        }
+        let limit = match input.limit {
+            Some(0) => return Err(AppError::validation("limit 必须大于 0")),
+            Some(limit) => limit.min(MAX_CHANGES_LIMIT),
+            None => DEFAULT_CHANGES_LIMIT,
+        };
+
+        // 多读一条判断是否还有更多记录
+        let mut entries = change_log_db_fn::find_since(db, since, limit + 1).await?;
+        let has_more = entries.len() as u64 > limit;
+        entries.truncate(limit as usize);
+        let next_since = entries.last().map_or(since, |e| e.seq);
+
+        let changes: Vec<ChangeLogEntry> = compact_changes(entries)
+            .into_iter()
+            .filter(|e| match (&input.workspace_id, &e.workspace_id) {
+                (Some(filter), Some(own)) => filter == own,
+                _ => true,
+            })
+            .filter(|e| is_visible_change(e, &scope))
+            .map(Into::into)
+            .collect();
+
+        Ok(ChangesSinceResponse {
+            changes,
+            next_since,
+            has_more,
+        })
+    }
+}
+
+/// 变更记录是否在可以访问的工作区范围内
+///
+/// 清除数据等全局记录和工作区的删除总是可见：工作区删除后成员关系随之删除，
+/// 原来的成员仍要知道它已被删除。
+pub(crate) fn is_visible_change(entry: &ChangeLogModel, scope: &WorkspaceScope) -> bool {
+    let deleted_workspace =
+        entry.entity_type == ChangeEntity::Workspace && entry.operation == ChangeOperation::Delete;
+    match &entry.workspace_id {
+        Some(workspace_id) => deleted_workspace || scope.contains(workspace_id),
+        None => true,
+    }
+}
+
+// ============================================================================
+// CompactChangeLog - 压缩变更日志
+// ============================================================================
+
+/// 删除被后续记录取代的变更记录
+///
+/// 每个实体的最新记录总是保留，压缩不影响任何客户端的增量同步结果。
+///
+/// ## HTTP
+/// - Method: POST
+/// - Path: /api/changes/compact
+///
+/// ## Tauri
+/// - Command: compact_change_log
+///
+/// ## 返回
+/// - 成功: CompactChangeLogResponse
+/// - 失败: DatabaseError
+pub struct CompactChangeLog;
+
+impl ApiEndpoint for CompactChangeLog {
+    type Input = NoInput;
+    type Output = CompactChangeLogResponse;
+    const NAME: &'static str = "compact_change_log";
+
+    fn access(_input: &Self::Input) -> Access {
+        Access::Admin
+    }
+
+    async fn execute(db: &DatabaseConnection, _: Self::Input) -> AppResult<Self::Output> {
+        change_log_db_fn::compact(db).await
+    }
+}
+
+// ============================================================================
+// 测试
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::{CreateWorkspace, DeleteNode, IdInput, SaveContent};
+    use crate::db::node_db_fn;
+    use crate::db::test_utils::setup_test_db;
+    use crate::types::content::SaveContentRequest;
+    use crate::types::node::NodeType;
+    use crate::types::workspace::CreateWorkspaceRequest;
+
+    fn query(since: i64, limit: Option<u64>) -> OptionalAuthWithBodyInput<ChangesSinceQuery> {
+        OptionalAuthWithBodyInput::anonymous(ChangesSinceQuery {
+            since: Some(since),
+            workspace_id: None,
+            limit,
+        })
+    }
+
+    #[tokio::test]
+    async fn test_get_changes_since() {
+        let db = setup_test_db().await;
+        let workspace = CreateWorkspace::execute(
+            &db,
+            OptionalAuthWithBodyInput::anonymous(CreateWorkspaceRequest {
+                title: "同步".to_string(),
+                author: None,
+                description: None,
+                publisher: None,
+                language: None,
+                members: None,
+                owner: None,
+            }),
+        )
+        .await
+        .unwrap();
+        node_db_fn::create(
+            &db,
+            "doc".to_string(),
+            workspace.id.clone(),
+            None,
+            "文档".to_string(),
+            NodeType::File,
+            None,
+        )
+        .await
+        .unwrap();
+
+        let start = GetChangesSince::execute(&db, query(0, None)).await.unwrap();
+        assert_eq!(start.changes.len(), 1);
+        assert_eq!(start.changes[0].entity_type, ChangeEntity::Workspace);
+
+        for text in ["一", "二", "三"] {
+            SaveContent::execute(
+                &db,
+                SaveContentRequest {
+                    node_id: "doc".to_string(),
+                    content: text.to_string(),
+                    expected_version: None,
+                    content_type: None,
+                },
+            )
+            .await
+            .unwrap();
+        }
+
+        // 三次保存压缩为一条，带最新的版本号
+        let changes = GetChangesSince::execute(&db, query(start.next_since, None))
+            .await
+            .unwrap();
+        assert_eq!(changes.changes.len(), 1);
+        assert_eq!(changes.changes[0].entity_type, ChangeEntity::Content);
+        assert_eq!(changes.changes[0].version, Some(3));
+        assert!(!changes.has_more);
+
+        DeleteNode::execute(&db, IdInput::new("doc")).await.unwrap();
+        let changes = GetChangesSince::execute(&db, query(start.next_since, None))
+            .await
+            .unwrap();
+        assert_eq!(changes.changes.len(), 1);
+        assert_eq!(changes.changes[0].operation, ChangeOperation::Delete);
+
+        // 分页：每页最多扫描 limit 条记录，逐页拉取直到 hasMore 为 false
+        let page = GetChangesSince::execute(&db, query(0, Some(1)))
+            .await
+            .unwrap();
+        assert_eq!(page.changes.len(), 1);
+        assert_eq!(page.changes[0].entity_type, ChangeEntity::Workspace);
+        assert!(page.has_more);
+        let mut since = page.next_since;
+        let mut pulled = Vec::new();
+        loop {
+            let rest = GetChangesSince::execute(&db, query(since, Some(2)))
+                .await
+                .unwrap();
+            assert!(rest.changes.len() <= 2);
+            pulled.extend(rest.changes);
+            since = rest.next_since;
+            if !rest.has_more {
+                break;
+            }
+        }
+        assert_eq!(since, changes.next_since);
+        let last = pulled.last().unwrap();
+        assert_eq!(
+            (last.entity_type, last.operation),
+            (ChangeEntity::Node, ChangeOperation::Delete)
+        );
+
+        // 压缩后结果不变
+        let compacted = CompactChangeLog::execute(&db, ()).await.unwrap();
+        assert_eq!(compacted.remaining, 2);
+        let after = GetChangesSince::execute(&db, query(0, None)).await.unwrap();
+        assert_eq!(after.changes.len(), 2);
+        assert_eq!(after.next_since, changes.next_since);
+
+        assert!(GetChangesSince::execute(&db, query(-1, None))
+            .await
+            .is_err());
+    }
+}
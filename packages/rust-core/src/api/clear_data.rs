@@ -2,10 +2,12 @@
 //!
 //! 提供清除 SQLite 数据库数据的 HTTP API 端点
 
-use sea_orm::DatabaseConnection;
+use sea_orm::{DatabaseConnection, TransactionTrait};
 
-use super::{ApiEndpoint, NoInput};
-use crate::db::{clear_all_data, ClearDataOptions, ClearDataResult};
+use super::{Access, ApiEndpoint, ClearDataInput};
+use crate::db::{clear_all_data_in, ClearDataOptions, ClearDataResult};
+use crate::events::PendingEvents;
+use crate::types::event::ChangeEvent;
 use crate::AppResult;
 
 // ============================================================================
@@ -16,10 +18,14 @@ use crate::AppResult;
 ///
 /// ## HTTP
 /// - Method: DELETE
-/// - Path: /api/data/clear
+/// - Path: /api/data/clear?keepUsers=
 ///
 /// ## Tauri
 /// - Command: clear_sqlite_data
+/// - Command: clear_sqlite_data_keep_users（保留用户）
+///
+/// ## 参数
+/// - keep_users: 保留用户（可选，默认 false）
 ///
 /// ## 返回
 /// - 成功: ClearDataResult
@@ -27,39 +33,27 @@ use crate::AppResult;
 pub struct ClearAllData;
 
 impl ApiEndpoint for ClearAllData {
-    type Input = NoInput;
+    type Input = ClearDataInput;
     type Output = ClearDataResult;
     const NAME: &'static str = "clear_all_data";
 
-    async fn execute(db: &DatabaseConnection, _: Self::Input) -> AppResult<Self::Output> {
-        clear_all_data(db, ClearDataOptions::all()).await
+    fn access(_input: &Self::Input) -> Access {
+        Access::Admin
     }
-}
-
-// ============================================================================
-// ClearDataKeepUsers - 清除数据（保留用户）
-// ============================================================================
-
-/// 清除数据（保留用户）
-///
-/// ## HTTP
-/// - Method: DELETE
-/// - Path: /api/data/clear?keepUsers=true
-///
-/// ## Tauri
-/// - Command: clear_sqlite_data_keep_users
-///
-/// ## 返回
-/// - 成功: ClearDataResult
-/// - 失败: DatabaseError
-pub struct ClearDataKeepUsers;
-
-impl ApiEndpoint for ClearDataKeepUsers {
-    type Input = NoInput;
-    type Output = ClearDataResult;
-    const NAME: &'static str = "clear_data_keep_users";
 
-    async fn execute(db: &DatabaseConnection, _: Self::Input) -> AppResult<Self::Output> {
-        clear_all_data(db, ClearDataOptions::database_only()).await
+    async fn execute(db: &DatabaseConnection, input: Self::Input) -> AppResult<Self::Output> {
+        let options = if input.keep_users {
+            ClearDataOptions::database_only()
+        } else {
+            ClearDataOptions::all()
+        };
+        let txn = db.begin().await?;
+        let mut pending = PendingEvents::new();
+        let result = clear_all_data_in(&txn, options).await?;
+        pending.record(&txn, ChangeEvent::DataCleared).await?;
+        txn.commit().await?;
+
+        pending.publish();
+        Ok(result)
     }
-}
\ No newline at end of file
+}
@@ -7,16 +7,27 @@
 //! | 端点 | 方法 | 路径 | 说明 |
 //! |------|------|------|------|
 //! | GetContent | GET | /api/nodes/:node_id/content | 获取节点内容 |
-//! | SaveContent | POST | /api/content | 保存内容（创建或更新） |
+//! | SaveContent | POST | /api/contents | 保存内容（创建或更新） |
+//! | MergeContent | POST | /api/contents/merge | 基于旧版本保存，版本冲突时三方合并 |
 //! | GetContentVersion | GET | /api/nodes/:node_id/content/version | 获取内容版本号 |
-
-use sea_orm::DatabaseConnection;
-use serde::{Deserialize, Serialize};
-
-use super::{ApiEndpoint, NodeIdInput};
-use crate::db::content_db_fn;
-use crate::types::content::{ContentResponse, SaveContentRequest};
-use crate::AppResult;
+//! | CompactContentUpdates | POST | /api/nodes/:node_id/collab/compact | 压缩协同编辑更新并写入内容 |
+//!
+//! 协同编辑的 WebSocket（`GET /api/nodes/:node_id/collab`）见 `collab` 模块。
+
+use sea_orm::{ConnectionTrait, DatabaseConnection, TransactionTrait};
+
+use super::{Access, ApiEndpoint, NodeIdInput};
+use crate::collab;
+use crate::db::{content_db_fn, node_db_fn, tag_db_fn, task_db_fn, writing_db_fn};
+use crate::events::PendingEvents;
+use crate::r#fn::content::{merge_lexical, LexicalMerge};
+use crate::r#fn::tag::extract_content_tags;
+use crate::types::content::{
+    ContentMergeResponse, ContentMergeStatus, ContentModel, ContentResponse, SaveContentRequest,
+};
+use crate::types::event::ChangeEvent;
+use crate::types::member::WorkspaceRole;
+use crate::{AppError, AppResult};
 
 // ============================================================================
 // GetContent - 获取节点内容
@@ -44,6 +55,10 @@ impl ApiEndpoint for GetContent {
     type Output = Option<ContentResponse>;
     const NAME: &'static str = "get_content";
 
+    fn access(input: &Self::Input) -> Access {
+        Access::Node(input.node_id.clone(), WorkspaceRole::Viewer)
+    }
+
     async fn execute(db: &DatabaseConnection, input: Self::Input) -> AppResult<Self::Output> {
         content_db_fn::find_by_node_id(db, &input.node_id)
             .await
@@ -57,9 +72,18 @@ impl ApiEndpoint for GetContent {
 
 /// 保存内容（创建或更新）
 ///
+/// 保存后从 Lexical 内容中提取 `#[tag]` 和 `#+TAGS:`，
+/// 同步到节点的 tags 字段和标签表，前端无需再手动维护标签计数。
+/// 内容不是 JSON 时不改动节点标签。
+/// 待办节点同时重建任务索引。
+/// 文档节点按本地日期记录字数变化（写作进度），并更新写作统计缓存。
+/// 保存成功后发布 `contentSaved` 事件，标签变化时另外发布 `tagsChanged`。
+/// `expected_version` 不是当前版本时，以该版本的历史记录为基础三方合并，
+/// 没有冲突则保存合并结果；有冲突时报告版本冲突（冲突文档见 `MergeContent`）。
+///
 /// ## HTTP
 /// - Method: POST
-/// - Path: /api/content
+/// - Path: /api/contents
 /// - Body: SaveContentRequest
 ///
 /// ## Tauri
@@ -72,8 +96,8 @@ impl ApiEndpoint for GetContent {
 /// - content_type: 内容类型（可选）
 ///
 /// ## 返回
-/// - 成功: ContentResponse
-/// - 失败: NotFound, ValidationError（版本冲突）, DatabaseError
+/// - 成功: ContentResponse（自动合并时 content 为合并结果）
+/// - 失败: NotFound, ValidationError（版本冲突且无法自动合并）, DatabaseError
 pub struct SaveContent;
 
 impl ApiEndpoint for SaveContent {
@@ -81,42 +105,252 @@ impl ApiEndpoint for SaveContent {
     type Output = ContentResponse;
     const NAME: &'static str = "save_content";
 
+    fn access(input: &Self::Input) -> Access {
+        Access::Node(input.node_id.clone(), WorkspaceRole::Editor)
+    }
+
     async fn execute(db: &DatabaseConnection, input: Self::Input) -> AppResult<Self::Output> {
         // 检查是否已存在内容
         let existing = content_db_fn::find_by_node_id(db, &input.node_id).await?;
 
-        match existing {
-            Some(_) => {
-                // 更新现有内容
-                content_db_fn::update(db, &input.node_id, input.content, input.expected_version)
-                    .await
-                    .map(Into::into)
-            }
-            None => {
-                // 创建新内容
-                let id = uuid::Uuid::new_v4().to_string();
-                content_db_fn::create(db, id, input.node_id, input.content)
-                    .await
-                    .map(Into::into)
+        // 版本冲突时与当前内容三方合并，没有冲突则保存合并结果，否则仍然报告版本冲突
+        let merged = match (&existing, input.expected_version) {
+            (Some(current), Some(expected)) if current.version != expected => {
+                merge_with_current(db, current, &input.content, expected)
+                    .await?
+                    .filter(|merge| merge.conflicts == 0)
+                    .map(|merge| (merge.content, current.version))
             }
+            _ => None,
+        };
+        let (content, expected_version) = match merged {
+            Some((content, version)) => (content, Some(version)),
+            None => (input.content, input.expected_version),
+        };
+
+        persist_content(db, existing, input.node_id, content, expected_version).await
+    }
+}
+
+/// 保存内容并同步标签、任务索引和写作进度，发布变更事件
+pub(crate) async fn persist_content(
+    db: &DatabaseConnection,
+    existing: Option<ContentModel>,
+    node_id: String,
+    content: String,
+    expected_version: Option<i32>,
+) -> AppResult<ContentResponse> {
+    let txn = db.begin().await?;
+    let mut pending = PendingEvents::new();
+    let saved = persist_content_in(
+        &txn,
+        &mut pending,
+        existing,
+        node_id,
+        content,
+        expected_version,
+    )
+    .await?;
+    txn.commit().await?;
+    pending.publish();
+    Ok(saved)
+}
+
+/// 在调用方的事务中保存内容，变更事件记入 `pending`
+pub(crate) async fn persist_content_in<C: ConnectionTrait>(
+    db: &C,
+    pending: &mut PendingEvents,
+    existing: Option<ContentModel>,
+    node_id: String,
+    content: String,
+    expected_version: Option<i32>,
+) -> AppResult<ContentResponse> {
+    let saved = match &existing {
+        Some(_) => {
+            // 更新现有内容
+            content_db_fn::update(db, &node_id, content, expected_version).await?
+        }
+        None => {
+            // 创建新内容
+            let id = uuid::Uuid::new_v4().to_string();
+            content_db_fn::create(db, id, node_id, content).await?
+        }
+    };
+
+    // 同步内容中的标签
+    let tags_changed = match extract_content_tags(&saved.content) {
+        Some(tags) => tag_db_fn::sync_node_tags(db, &saved.node_id, &tags).await?,
+        None => false,
+    };
+
+    // 同步待办节点的任务索引
+    task_db_fn::sync_tasks(db, &saved).await?;
+
+    // 记录写作进度
+    writing_db_fn::record_progress(
+        db,
+        existing.as_ref(),
+        &saved,
+        chrono::Local::now().date_naive(),
+    )
+    .await?;
+
+    if let Some(node) = node_db_fn::find_by_id(db, &saved.node_id).await? {
+        pending
+            .record(
+                db,
+                ChangeEvent::ContentSaved {
+                    workspace_id: node.workspace_id.clone(),
+                    node_id: saved.node_id.clone(),
+                    version: saved.version,
+                },
+            )
+            .await?;
+        if tags_changed {
+            // 节点的 tags 字段随内容改变，节点本身也需要进入变更日志
+            pending
+                .record(
+                    db,
+                    ChangeEvent::NodeUpdated {
+                        workspace_id: node.workspace_id.clone(),
+                        node_id: saved.node_id.clone(),
+                    },
+                )
+                .await?;
+            pending
+                .record(
+                    db,
+                    ChangeEvent::TagsChanged {
+                        workspace_id: node.workspace_id,
+                    },
+                )
+                .await?;
         }
     }
+
+    Ok(saved.into())
+}
+
+/// 以 `expected_version` 的历史版本为基础，三方合并本地内容和当前内容
+///
+/// 没有该历史版本或内容不是 Lexical JSON 时返回 None。
+async fn merge_with_current(
+    db: &DatabaseConnection,
+    current: &ContentModel,
+    content: &str,
+    expected_version: i32,
+) -> AppResult<Option<LexicalMerge>> {
+    let Some(base) = content_db_fn::find_revision(db, &current.node_id, expected_version).await?
+    else {
+        return Ok(None);
+    };
+    Ok(merge_lexical(&base.content, content, &current.content))
 }
 
 // ============================================================================
-// GetContentVersion - 获取内容版本号
+// MergeContent - 合并保存内容
 // ============================================================================
 
-/// 内容版本响应
-#[derive(Debug, Clone, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
-pub struct ContentVersionResponse {
-    /// 节点 ID
-    pub node_id: String,
-    /// 版本号（如果内容不存在则为 None）
-    pub version: Option<i32>,
+/// 基于旧版本保存内容，版本冲突时三方合并
+///
+/// 以 `expectedVersion` 对应的历史版本为基础，结构化合并本地内容和服务器当前内容（Lexical JSON）：
+/// - 版本一致：直接保存（`saved`）
+/// - 没有冲突：保存合并结果（`merged`）
+/// - 存在冲突：不保存，返回并列两个版本的冲突文档（`conflict`），
+///   用户解决后以返回的 `version` 作为 `expectedVersion` 再次保存
+///
+/// ## HTTP
+/// - Method: POST
+/// - Path: /api/contents/merge
+/// - Body: SaveContentRequest
+///
+/// ## Tauri
+/// - Command: merge_content
+///
+/// ## 参数
+/// - node_id: 节点 ID（必填）
+/// - content: 基于 expected_version 修改后的内容（必填）
+/// - expected_version: 修改所基于的版本号（必填）
+///
+/// ## 返回
+/// - 成功: ContentMergeResponse
+/// - 失败: NotFound, ValidationError（缺少 expected_version、历史版本已清理或内容不是 Lexical JSON）, DatabaseError
+pub struct MergeContent;
+
+impl ApiEndpoint for MergeContent {
+    type Input = SaveContentRequest;
+    type Output = ContentMergeResponse;
+    const NAME: &'static str = "merge_content";
+
+    fn access(input: &Self::Input) -> Access {
+        Access::Node(input.node_id.clone(), WorkspaceRole::Editor)
+    }
+
+    async fn execute(db: &DatabaseConnection, input: Self::Input) -> AppResult<Self::Output> {
+        let expected = input
+            .expected_version
+            .ok_or_else(|| AppError::validation("合并需要 expectedVersion"))?;
+        let current = content_db_fn::find_by_node_id(db, &input.node_id)
+            .await?
+            .ok_or_else(|| AppError::not_found(format!("Content for node {}", input.node_id)))?;
+
+        if current.version == expected {
+            let saved = persist_content(
+                db,
+                Some(current),
+                input.node_id,
+                input.content,
+                Some(expected),
+            )
+            .await?;
+            return Ok(ContentMergeResponse {
+                status: ContentMergeStatus::Saved,
+                content: saved.content,
+                conflicts: 0,
+                version: saved.version,
+            });
+        }
+
+        let merge = merge_with_current(db, &current, &input.content, expected)
+            .await?
+            .ok_or_else(|| {
+                AppError::validation(format!(
+                    "无法合并: 版本 {} 的历史记录不存在或内容不是 Lexical JSON",
+                    expected
+                ))
+            })?;
+
+        if merge.conflicts > 0 {
+            return Ok(ContentMergeResponse {
+                status: ContentMergeStatus::Conflict,
+                content: merge.content,
+                conflicts: merge.conflicts as u32,
+                version: current.version,
+            });
+        }
+
+        let version = current.version;
+        let saved = persist_content(
+            db,
+            Some(current),
+            input.node_id,
+            merge.content,
+            Some(version),
+        )
+        .await?;
+        Ok(ContentMergeResponse {
+            status: ContentMergeStatus::Merged,
+            content: saved.content,
+            conflicts: 0,
+            version: saved.version,
+        })
+    }
 }
 
+// ============================================================================
+// GetContentVersion - 获取内容版本号
+// ============================================================================
+
 /// 获取内容版本号
 ///
 /// ## HTTP
@@ -130,22 +364,60 @@ pub struct ContentVersionResponse {
 /// - node_id: 节点 ID
 ///
 /// ## 返回
-/// - 成功: ContentVersionResponse
+/// - 成功: Option<i32>（内容不存在时为 None）
 /// - 失败: DatabaseError
 pub struct GetContentVersion;
 
 impl ApiEndpoint for GetContentVersion {
     type Input = NodeIdInput;
-    type Output = ContentVersionResponse;
+    type Output = Option<i32>;
     const NAME: &'static str = "get_content_version";
 
+    fn access(input: &Self::Input) -> Access {
+        Access::Node(input.node_id.clone(), WorkspaceRole::Viewer)
+    }
+
     async fn execute(db: &DatabaseConnection, input: Self::Input) -> AppResult<Self::Output> {
         let content = content_db_fn::find_by_node_id(db, &input.node_id).await?;
+        Ok(content.map(|c| c.version))
+    }
+}
 
-        Ok(ContentVersionResponse {
-            node_id: input.node_id,
-            version: content.map(|c| c.version),
-        })
+// ============================================================================
+// CompactContentUpdates - 压缩协同编辑更新
+// ============================================================================
+
+/// 压缩节点的协同编辑更新，并把 CRDT 文档的文本写入内容
+///
+/// 协同编辑时更新累积到一定条数、以及最后一个连接断开时会自动压缩。
+/// 内容在文档合入之后被其他途径修改过时不覆盖内容，差异在下次打开文档时合入。
+///
+/// ## HTTP
+/// - Method: POST
+/// - Path: /api/nodes/:node_id/collab/compact
+///
+/// ## Tauri
+/// - Command: compact_content_updates
+///
+/// ## 参数
+/// - node_id: 节点 ID
+///
+/// ## 返回
+/// - 成功: Option<ContentResponse>（没有协同编辑记录时为 None）
+/// - 失败: ValidationError（更新无法解码或版本冲突）, DatabaseError
+pub struct CompactContentUpdates;
+
+impl ApiEndpoint for CompactContentUpdates {
+    type Input = NodeIdInput;
+    type Output = Option<ContentResponse>;
+    const NAME: &'static str = "compact_content_updates";
+
+    fn access(input: &Self::Input) -> Access {
+        Access::Node(input.node_id.clone(), WorkspaceRole::Editor)
+    }
+
+    async fn execute(db: &DatabaseConnection, input: Self::Input) -> AppResult<Self::Output> {
+        collab::compact(db, &input.node_id).await
     }
 }
 
@@ -157,7 +429,8 @@ impl ApiEndpoint for GetContentVersion {
 mod tests {
     use super::*;
     use crate::db::test_utils::setup_test_db;
-    use crate::db::{node_db_fn, workspace_db_fn};
+    use crate::db::{change_log_db_fn, node_db_fn, workspace_db_fn};
+    use crate::types::change_log::ChangeEntity;
     use crate::types::node::NodeType;
 
     async fn create_test_node(db: &DatabaseConnection) -> String {
@@ -315,8 +588,7 @@ mod tests {
         // 内容不存在时
         let input = NodeIdInput::new(&node_id);
         let result = GetContentVersion::execute(&db, input).await.unwrap();
-        assert_eq!(result.node_id, node_id);
-        assert!(result.version.is_none());
+        assert!(result.is_none());
 
         // 创建内容
         let save_input = SaveContentRequest {
@@ -330,7 +602,159 @@ mod tests {
         // 内容存在时
         let input = NodeIdInput::new(&node_id);
         let result = GetContentVersion::execute(&db, input).await.unwrap();
-        assert_eq!(result.node_id, node_id);
-        assert_eq!(result.version, Some(1));
+        assert_eq!(result, Some(1));
+    }
+
+    #[tokio::test]
+    async fn test_save_content_syncs_tags() {
+        let db = setup_test_db().await;
+        let node_id = create_test_node(&db).await;
+        let node = node_db_fn::find_by_id(&db, &node_id)
+            .await
+            .unwrap()
+            .unwrap();
+        let save = |content: &str| SaveContentRequest {
+            node_id: node_id.clone(),
+            content: content.to_string(),
+            expected_version: None,
+            content_type: None,
+        };
+
+        SaveContent::execute(
+            &db,
+            save(r##"{"root":{"children":[{"type":"tag","tagName":"rust","text":"#[rust]"},{"type":"text","text":"#[diary]"}]}}"##),
+        )
+        .await
+        .unwrap();
+        let updated = node_db_fn::find_by_id(&db, &node_id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(updated.tags.as_deref(), Some(r#"["rust","diary"]"#));
+        let rust = tag_db_fn::find_by_name(&db, &node.workspace_id, "rust")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(rust.count, 1);
+        // 节点标签的变化进入变更日志
+        let changes = change_log_db_fn::find_since(&db, 0, 100).await.unwrap();
+        assert!(changes
+            .iter()
+            .any(|e| e.entity_type == ChangeEntity::Node && e.entity_id == node_id));
+
+        // 移除 #[rust] 后计数归零，节点标签随内容更新
+        SaveContent::execute(
+            &db,
+            save(r##"{"root":{"children":[{"type":"text","text":"#[diary]"}]}}"##),
+        )
+        .await
+        .unwrap();
+        let updated = node_db_fn::find_by_id(&db, &node_id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(updated.tags.as_deref(), Some(r#"["diary"]"#));
+        let rust = tag_db_fn::find_by_name(&db, &node.workspace_id, "rust")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(rust.count, 0);
+
+        // 非 JSON 内容不改动标签
+        SaveContent::execute(&db, save("纯文本")).await.unwrap();
+        let updated = node_db_fn::find_by_id(&db, &node_id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(updated.tags.as_deref(), Some(r#"["diary"]"#));
+    }
+
+    fn lexical_doc(paragraphs: &[&str]) -> String {
+        let children: Vec<serde_json::Value> = paragraphs
+            .iter()
+            .map(|text| {
+                serde_json::json!({
+                    "type": "paragraph",
+                    "children": [{ "type": "text", "text": text }]
+                })
+            })
+            .collect();
+        serde_json::json!({ "root": { "type": "root", "children": children } }).to_string()
+    }
+
+    fn save_request(node_id: &str, content: String, expected_version: i32) -> SaveContentRequest {
+        SaveContentRequest {
+            node_id: node_id.to_string(),
+            content,
+            expected_version: Some(expected_version),
+            content_type: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_save_content_merges_stale_version() {
+        let db = setup_test_db().await;
+        let node_id = create_test_node(&db).await;
+
+        let base = SaveContent::execute(&db, save_request(&node_id, lexical_doc(&["a", "b"]), 0))
+            .await
+            .unwrap();
+        // 另一端基于版本 1 修改了第二段
+        SaveContent::execute(
+            &db,
+            save_request(&node_id, lexical_doc(&["a", "b2"]), base.version),
+        )
+        .await
+        .unwrap();
+
+        // 本端同样基于版本 1，修改第一段
+        let saved = SaveContent::execute(
+            &db,
+            save_request(&node_id, lexical_doc(&["a1", "b"]), base.version),
+        )
+        .await
+        .unwrap();
+        assert_eq!(saved.version, 3);
+        assert_eq!(saved.content, lexical_doc(&["a1", "b2"]));
+    }
+
+    #[tokio::test]
+    async fn test_merge_content_reports_conflict() {
+        let db = setup_test_db().await;
+        let node_id = create_test_node(&db).await;
+
+        let base = SaveContent::execute(&db, save_request(&node_id, lexical_doc(&["a"]), 0))
+            .await
+            .unwrap();
+        SaveContent::execute(&db, save_request(&node_id, lexical_doc(&["theirs"]), 1))
+            .await
+            .unwrap();
+
+        // 两端修改了同一段：SaveContent 报告版本冲突，MergeContent 返回冲突文档且不保存
+        let ours = save_request(&node_id, lexical_doc(&["ours"]), base.version);
+        assert!(SaveContent::execute(&db, ours.clone()).await.is_err());
+
+        let merged = MergeContent::execute(&db, ours).await.unwrap();
+        assert_eq!(merged.status, ContentMergeStatus::Conflict);
+        assert_eq!(merged.conflicts, 1);
+        assert_eq!(merged.version, 2);
+        assert!(merged
+            .content
+            .contains(crate::r#fn::content::CONFLICT_OURS_MARKER));
+        let current = content_db_fn::find_by_node_id(&db, &node_id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(current.version, 2);
+
+        // 版本一致时直接保存
+        let resolved = MergeContent::execute(
+            &db,
+            save_request(&node_id, lexical_doc(&["resolved"]), merged.version),
+        )
+        .await
+        .unwrap();
+        assert_eq!(resolved.status, ContentMergeStatus::Saved);
+        assert_eq!(resolved.version, 3);
     }
 }
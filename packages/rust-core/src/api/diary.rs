@@ -0,0 +1,336 @@
+//! Diary API 端点
+//!
+//! 日记服务相关的 API 端点实现。
+//! 日记按 `Diary > year-YYYY-* > month-MM-* > day-DD-*` 文件夹组织。
+//!
+//! ## 端点列表
+//!
+//! | 端点 | 方法 | 路径 | 说明 |
+//! |------|------|------|------|
+//! | GetOrCreateDiaryEntry | POST | /api/workspaces/:id/diary/entries | 获取或创建某天的日记 |
+//! | GetDiaryCalendar | GET | /api/workspaces/:id/diary/calendar?from=&to= | 日期范围内有日记的日期和字数 |
+//! | GetDiaryOnThisDay | GET | /api/workspaces/:id/diary/on-this-day?date= | 往年同日的日记 |
+
+use sea_orm::{DatabaseConnection, TransactionTrait};
+
+use super::{Access, ApiEndpoint, IdWithBodyInput};
+use crate::db::{diary_db_fn, workspace_db_fn};
+use crate::events::PendingEvents;
+use crate::r#fn::diary::{
+    build_diary_calendar, default_diary_template, diary_on_this_day, format_diary_date,
+    parse_diary_date, DEFAULT_DIARY_ROOT_FOLDER,
+};
+use crate::types::diary::{
+    DiaryCalendarDay, DiaryCalendarQuery, DiaryEntryResponse, DiaryOnThisDayEntry,
+    DiaryOnThisDayQuery, GetOrCreateDiaryEntryRequest,
+};
+use crate::types::event::ChangeEvent;
+use crate::types::member::WorkspaceRole;
+use crate::{AppError, AppResult};
+
+// ============================================================================
+// GetOrCreateDiaryEntry - 获取或创建某天的日记
+// ============================================================================
+
+/// 获取或创建指定日期的日记
+///
+/// 幂等：年/月/日文件夹不存在时创建，已存在时复用；
+/// 日文件夹中已有日记时直接返回，不会重复创建。
+/// 新建时为日记节点发布 `nodeCreated` 和 `contentSaved` 事件；
+/// 同时新建的年/月/日文件夹不单独发布，客户端遇到未知的父节点时重新加载节点树。
+///
+/// ## HTTP
+/// - Method: POST
+/// - Path: /api/workspaces/:workspace_id/diary/entries
+/// - Body: GetOrCreateDiaryEntryRequest
+///
+/// ## Tauri
+/// - Command: get_or_create_diary_entry
+///
+/// ## 参数
+/// - id: 工作区 ID
+/// - date: 日期（`YYYY-MM-DD`）
+/// - template: 新建时使用的内容模板（可选）
+/// - root_folder: 日记根文件夹名称（可选，默认 `Diary`）
+///
+/// ## 返回
+/// - 成功: DiaryEntryResponse
+/// - 失败: NotFound（工作区不存在）, ValidationError, DatabaseError
+pub struct GetOrCreateDiaryEntry;
+
+impl ApiEndpoint for GetOrCreateDiaryEntry {
+    type Input = IdWithBodyInput<GetOrCreateDiaryEntryRequest>;
+    type Output = DiaryEntryResponse;
+    const NAME: &'static str = "get_or_create_diary_entry";
+
+    fn access(input: &Self::Input) -> Access {
+        Access::Workspace(input.id.clone(), WorkspaceRole::Editor)
+    }
+
+    async fn execute(db: &DatabaseConnection, input: Self::Input) -> AppResult<Self::Output> {
+        let date = parse_diary_date(&input.body.date).map_err(AppError::ValidationError)?;
+        let root_folder = match input.body.root_folder.as_deref().map(str::trim) {
+            Some("") => return Err(AppError::validation("日记根文件夹名称不能为空")),
+            Some(name) => name.to_string(),
+            None => DEFAULT_DIARY_ROOT_FOLDER.to_string(),
+        };
+        let template = input
+            .body
+            .template
+            .filter(|t| !t.trim().is_empty())
+            .unwrap_or_else(default_diary_template);
+
+        workspace_db_fn::find_by_id(db, &input.id)
+            .await?
+            .ok_or_else(|| AppError::not_found(format!("Workspace {}", input.id)))?;
+
+        let txn = db.begin().await?;
+        let mut pending = PendingEvents::new();
+        let (node, content, created) =
+            diary_db_fn::get_or_create_entry(&txn, &input.id, date, &root_folder, &template)
+                .await?;
+        if created {
+            pending
+                .record(
+                    &txn,
+                    ChangeEvent::NodeCreated {
+                        workspace_id: node.workspace_id.clone(),
+                        node_id: node.id.clone(),
+                        parent_id: node.parent_id.clone(),
+                    },
+                )
+                .await?;
+            if let Some(content) = &content {
+                pending
+                    .record(
+                        &txn,
+                        ChangeEvent::ContentSaved {
+                            workspace_id: node.workspace_id.clone(),
+                            node_id: node.id.clone(),
+                            version: content.version,
+                        },
+                    )
+                    .await?;
+            }
+        }
+        txn.commit().await?;
+
+        pending.publish();
+
+        Ok(DiaryEntryResponse {
+            date: format_diary_date(date),
+            created,
+            node: node.into(),
+            content: content.map(Into::into),
+        })
+    }
+}
+
+// ============================================================================
+// GetDiaryCalendar - 日记日历
+// ============================================================================
+
+/// 获取日期范围内有日记的日期及每天的字数
+///
+/// ## HTTP
+/// - Method: GET
+/// - Path: /api/workspaces/:workspace_id/diary/calendar?from=2026-01-01&to=2026-01-31
+///
+/// ## Tauri
+/// - Command: get_diary_calendar
+///
+/// ## 参数
+/// - id: 工作区 ID
+/// - from: 起始日期（包含）
+/// - to: 结束日期（包含）
+///
+/// ## 返回
+/// - 成功: Vec<DiaryCalendarDay>（按日期升序，只包含有日记的日期）
+/// - 失败: ValidationError, DatabaseError
+pub struct GetDiaryCalendar;
+
+impl ApiEndpoint for GetDiaryCalendar {
+    type Input = IdWithBodyInput<DiaryCalendarQuery>;
+    type Output = Vec<DiaryCalendarDay>;
+    const NAME: &'static str = "get_diary_calendar";
+
+    fn access(input: &Self::Input) -> Access {
+        Access::Workspace(input.id.clone(), WorkspaceRole::Viewer)
+    }
+
+    async fn execute(db: &DatabaseConnection, input: Self::Input) -> AppResult<Self::Output> {
+        let from = parse_diary_date(&input.body.from).map_err(AppError::ValidationError)?;
+        let to = parse_diary_date(&input.body.to).map_err(AppError::ValidationError)?;
+        if from > to {
+            return Err(AppError::validation("起始日期不能晚于结束日期"));
+        }
+
+        let entries: Vec<_> = diary_db_fn::find_entries(db, &input.id)
+            .await?
+            .into_iter()
+            .filter(|(date, _)| *date >= from && *date <= to)
+            .collect();
+        let node_ids = entries.iter().map(|(_, node)| node.id.clone()).collect();
+        let word_counts = diary_db_fn::word_counts(db, node_ids).await?;
+
+        Ok(build_diary_calendar(entries, &word_counts, from, to))
+    }
+}
+
+// ============================================================================
+// GetDiaryOnThisDay - 那年今日
+// ============================================================================
+
+/// 获取往年同月同日的日记
+///
+/// ## HTTP
+/// - Method: GET
+/// - Path: /api/workspaces/:workspace_id/diary/on-this-day?date=2026-01-01
+///
+/// ## Tauri
+/// - Command: get_diary_on_this_day
+///
+/// ## 参数
+/// - id: 工作区 ID
+/// - date: 日期
+///
+/// ## 返回
+/// - 成功: Vec<DiaryOnThisDayEntry>（最近的年份在前）
+/// - 失败: ValidationError, DatabaseError
+pub struct GetDiaryOnThisDay;
+
+impl ApiEndpoint for GetDiaryOnThisDay {
+    type Input = IdWithBodyInput<DiaryOnThisDayQuery>;
+    type Output = Vec<DiaryOnThisDayEntry>;
+    const NAME: &'static str = "get_diary_on_this_day";
+
+    fn access(input: &Self::Input) -> Access {
+        Access::Workspace(input.id.clone(), WorkspaceRole::Viewer)
+    }
+
+    async fn execute(db: &DatabaseConnection, input: Self::Input) -> AppResult<Self::Output> {
+        let date = parse_diary_date(&input.body.date).map_err(AppError::ValidationError)?;
+        let entries = diary_db_fn::find_entries(db, &input.id).await?;
+        Ok(diary_on_this_day(entries, date))
+    }
+}
+
+// ============================================================================
+// 测试
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::test_utils::setup_test_db;
+
+    fn entry_request(date: &str) -> GetOrCreateDiaryEntryRequest {
+        GetOrCreateDiaryEntryRequest {
+            date: date.to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_diary_calendar_and_on_this_day() {
+        let db = setup_test_db().await;
+        let workspace_id = uuid::Uuid::new_v4().to_string();
+        workspace_db_fn::create(&db, workspace_id.clone(), "测试工作区".to_string(), None)
+            .await
+            .unwrap();
+
+        let first = GetOrCreateDiaryEntry::execute(
+            &db,
+            IdWithBodyInput::new(&workspace_id, entry_request("2025-03-07")),
+        )
+        .await
+        .unwrap();
+        assert!(first.created);
+        assert_eq!(first.date, "2025-03-07");
+
+        let custom = GetOrCreateDiaryEntry::execute(
+            &db,
+            IdWithBodyInput::new(
+                &workspace_id,
+                GetOrCreateDiaryEntryRequest {
+                    date: "2026-03-07".to_string(),
+                    template: Some("{{date}} 京都".to_string()),
+                    root_folder: None,
+                },
+            ),
+        )
+        .await
+        .unwrap();
+        assert_eq!(custom.content.unwrap().content, "2026-03-07 京都");
+
+        let again = GetOrCreateDiaryEntry::execute(
+            &db,
+            IdWithBodyInput::new(&workspace_id, entry_request("2026-03-07")),
+        )
+        .await
+        .unwrap();
+        assert!(!again.created);
+        assert_eq!(again.node.id, custom.node.id);
+
+        let calendar = GetDiaryCalendar::execute(
+            &db,
+            IdWithBodyInput::new(
+                &workspace_id,
+                DiaryCalendarQuery {
+                    from: "2026-03-01".to_string(),
+                    to: "2026-03-31".to_string(),
+                },
+            ),
+        )
+        .await
+        .unwrap();
+        assert_eq!(calendar.len(), 1);
+        assert_eq!(calendar[0].date, "2026-03-07");
+        assert_eq!(calendar[0].word_count, 3);
+
+        let on_this_day = GetDiaryOnThisDay::execute(
+            &db,
+            IdWithBodyInput::new(
+                &workspace_id,
+                DiaryOnThisDayQuery {
+                    date: "2026-03-07".to_string(),
+                },
+            ),
+        )
+        .await
+        .unwrap();
+        assert_eq!(on_this_day.len(), 1);
+        assert_eq!(on_this_day[0].node.id, first.node.id);
+        assert_eq!(on_this_day[0].years_ago, 1);
+    }
+
+    #[tokio::test]
+    async fn test_diary_validation() {
+        let db = setup_test_db().await;
+
+        let result =
+            GetOrCreateDiaryEntry::execute(&db, IdWithBodyInput::new("ws", entry_request("bad")))
+                .await;
+        assert!(matches!(result, Err(AppError::ValidationError(_))));
+
+        let result = GetOrCreateDiaryEntry::execute(
+            &db,
+            IdWithBodyInput::new("missing", entry_request("2026-01-01")),
+        )
+        .await;
+        assert!(matches!(result, Err(AppError::NotFound(_))));
+
+        let result = GetDiaryCalendar::execute(
+            &db,
+            IdWithBodyInput::new(
+                "ws",
+                DiaryCalendarQuery {
+                    from: "2026-02-01".to_string(),
+                    to: "2026-01-01".to_string(),
+                },
+            ),
+        )
+        .await;
+        assert!(matches!(result, Err(AppError::ValidationError(_))));
+    }
+}
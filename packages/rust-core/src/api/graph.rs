@@ -0,0 +1,145 @@
+//! Graph API 端点
+//!
+//! 节点知识图谱相关的 API 端点实现。
+//!
+//! ## 端点列表
+//!
+//! | 端点 | 方法 | 路径 | 说明 |
+//! |------|------|------|------|
+//! | GetNodeGraph | GET | /api/workspaces/:id/graph | 获取节点知识图谱 |
+
+use sea_orm::DatabaseConnection;
+
+use super::{Access, ApiEndpoint, IdWithBodyInput};
+use crate::db::{link_db_fn, node_db_fn, tag_db_fn};
+use crate::r#fn::graph::graph_fn;
+use crate::types::graph::{NodeGraphData, NodeGraphQuery};
+use crate::types::member::WorkspaceRole;
+use crate::{AppError, AppResult};
+
+// ============================================================================
+// GetNodeGraph - 获取节点知识图谱
+// ============================================================================
+
+/// 获取工作区的节点知识图谱
+///
+/// 图中的点是文档节点（带类型和度数），边分为三类：
+/// `@` 引用（link）、共同标签（sharedTag）、父子关系（parentChild）。
+///
+/// ## HTTP
+/// - Method: GET
+/// - Path: /api/workspaces/:workspace_id/graph?folderId=&nodeTypes=file,diary&tag=&focusId=&depth=&edgeKinds=link,sharedTag
+///
+/// ## Tauri
+/// - Command: get_node_graph（`query` 可省略，等同于不加过滤）
+///
+/// ## 参数
+/// - id: 工作区 ID
+/// - folder_id: 只包含该文件夹下的节点（可选）
+/// - node_types: 节点类型过滤（可选）
+/// - tag: 标签过滤，包含其子标签，支持别名（可选）
+/// - focus_id: 焦点节点（可选）
+/// - depth: 距焦点节点的最大深度（可选，默认 1）
+/// - edge_kinds: 边类型过滤（可选）
+///
+/// ## 返回
+/// - 成功: NodeGraphData
+/// - 失败: NotFound（文件夹或焦点节点不属于该工作区）, DatabaseError
+pub struct GetNodeGraph;
+
+impl ApiEndpoint for GetNodeGraph {
+    type Input = IdWithBodyInput<NodeGraphQuery>;
+    type Output = NodeGraphData;
+    const NAME: &'static str = "get_node_graph";
+
+    fn access(input: &Self::Input) -> Access {
+        Access::Workspace(input.id.clone(), WorkspaceRole::Viewer)
+    }
+
+    async fn execute(db: &DatabaseConnection, input: Self::Input) -> AppResult<Self::Output> {
+        let nodes = node_db_fn::find_by_workspace(db, &input.id).await?;
+
+        let anchors = [&input.body.folder_id, &input.body.focus_id];
+        for anchor in anchors.into_iter().flatten() {
+            if !nodes.iter().any(|n| &n.id == anchor) {
+                return Err(AppError::not_found(format!("Node {}", anchor)));
+            }
+        }
+
+        let mut query = input.body;
+        if let Some(tag) = query.tag.take() {
+            query.tag = Some(tag_db_fn::resolve_alias(db, &input.id, &tag).await?);
+        }
+
+        let links = link_db_fn::find_by_workspace(db, &input.id).await?;
+        Ok(graph_fn::build_node_graph(nodes, links, &query))
+    }
+}
+
+// ============================================================================
+// 测试
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::test_utils::setup_test_db;
+    use crate::db::{content_db_fn, workspace_db_fn};
+    use crate::types::graph::NodeGraphEdgeKind;
+    use crate::types::node::NodeType;
+
+    #[tokio::test]
+    async fn test_get_node_graph() {
+        let db = setup_test_db().await;
+        let workspace_id = uuid::Uuid::new_v4().to_string();
+        workspace_db_fn::create(&db, workspace_id.clone(), "测试工作区".to_string(), None)
+            .await
+            .unwrap();
+
+        for (id, tags) in [("a", r#"["rust"]"#), ("b", r#"["rust"]"#)] {
+            node_db_fn::create(
+                &db,
+                id.to_string(),
+                workspace_id.clone(),
+                None,
+                id.to_string(),
+                NodeType::File,
+                Some(tags.to_string()),
+            )
+            .await
+            .unwrap();
+        }
+        content_db_fn::create(
+            &db,
+            "content-a".to_string(),
+            "a".to_string(),
+            r#"{"root":{"children":[{"type":"mention","mentionName":"b","roleId":"b"}]}}"#
+                .to_string(),
+        )
+        .await
+        .unwrap();
+
+        let graph = GetNodeGraph::execute(
+            &db,
+            IdWithBodyInput::new(&workspace_id, NodeGraphQuery::default()),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(graph.nodes.len(), 2);
+        assert_eq!(graph.edges.len(), 2);
+        assert!(graph
+            .edges
+            .iter()
+            .any(|e| e.kind == NodeGraphEdgeKind::Link && e.source == "a" && e.target == "b"));
+        assert!(graph.nodes.iter().all(|n| n.degree == 2));
+
+        let missing_focus = NodeGraphQuery {
+            focus_id: Some("missing".to_string()),
+            ..Default::default()
+        };
+        let result =
+            GetNodeGraph::execute(&db, IdWithBodyInput::new(&workspace_id, missing_focus)).await;
+        assert!(matches!(result, Err(AppError::NotFound(_))));
+    }
+}
@@ -9,6 +9,10 @@
 
 use serde::Deserialize;
 
+use crate::types::attachment::AttachmentType;
+use crate::types::auth::AuthUser;
+use crate::types::node::NodeType;
+
 // ============================================================================
 // 单一 ID 输入
 // ============================================================================
@@ -60,6 +64,29 @@ impl WorkspaceIdInput {
     }
 }
 
+/// 工作区成员输入
+///
+/// 用于操作单个成员的端点
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkspaceMemberInput {
+    /// 工作区 ID
+    pub workspace_id: String,
+
+    /// 用户 ID
+    pub user_id: String,
+}
+
+impl WorkspaceMemberInput {
+    /// 创建新的 WorkspaceMemberInput
+    pub fn new(workspace_id: impl Into<String>, user_id: impl Into<String>) -> Self {
+        Self {
+            workspace_id: workspace_id.into(),
+            user_id: user_id.into(),
+        }
+    }
+}
+
 // ============================================================================
 // 节点相关输入
 // ============================================================================
@@ -125,6 +152,243 @@ impl NextSortOrderInput {
     }
 }
 
+/// 获取节点树的输入
+///
+/// 用于 GetNodeTree 端点
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NodeTreeInput {
+    /// 工作区 ID
+    pub workspace_id: String,
+
+    /// 起始节点 ID（null 表示从工作区根级别开始）
+    #[serde(default)]
+    pub root_id: Option<String>,
+
+    /// 最大深度（null 表示不限制，1 表示只返回一层）
+    #[serde(default)]
+    pub max_depth: Option<u32>,
+}
+
+impl NodeTreeInput {
+    /// 创建新的 NodeTreeInput
+    pub fn new(
+        workspace_id: impl Into<String>,
+        root_id: Option<String>,
+        max_depth: Option<u32>,
+    ) -> Self {
+        Self {
+            workspace_id: workspace_id.into(),
+            root_id,
+            max_depth,
+        }
+    }
+}
+
+/// 按父节点获取节点的输入
+///
+/// 用于 GetNodesByParent 端点
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NodesByParentInput {
+    /// 工作区 ID
+    pub workspace_id: String,
+
+    /// 父节点 ID（null 表示根级别）
+    pub parent_id: Option<String>,
+}
+
+impl NodesByParentInput {
+    /// 创建新的 NodesByParentInput
+    pub fn new(workspace_id: impl Into<String>, parent_id: Option<String>) -> Self {
+        Self {
+            workspace_id: workspace_id.into(),
+            parent_id,
+        }
+    }
+}
+
+/// 按类型获取节点的输入
+///
+/// 用于 GetNodesByType 端点
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NodesByTypeInput {
+    /// 工作区 ID
+    pub workspace_id: String,
+
+    /// 节点类型
+    pub node_type: NodeType,
+}
+
+impl NodesByTypeInput {
+    /// 创建新的 NodesByTypeInput
+    pub fn new(workspace_id: impl Into<String>, node_type: NodeType) -> Self {
+        Self {
+            workspace_id: workspace_id.into(),
+            node_type,
+        }
+    }
+}
+
+/// 一组节点 ID 输入
+///
+/// 用于批量操作节点的端点
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NodeIdsInput {
+    /// 节点 ID 列表
+    pub node_ids: Vec<String>,
+}
+
+impl NodeIdsInput {
+    /// 创建新的 NodeIdsInput
+    pub fn new(node_ids: Vec<String>) -> Self {
+        Self { node_ids }
+    }
+}
+
+// ============================================================================
+// 附件相关输入
+// ============================================================================
+
+/// 项目 ID 输入
+///
+/// 用于按项目（工作区）查询附件的端点
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectIdInput {
+    /// 项目（工作区）ID
+    pub project_id: String,
+}
+
+impl ProjectIdInput {
+    /// 创建新的 ProjectIdInput
+    pub fn new(project_id: impl Into<String>) -> Self {
+        Self {
+            project_id: project_id.into(),
+        }
+    }
+}
+
+/// 按项目查询附件输入
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AttachmentsByProjectInput {
+    /// 项目（工作区）ID
+    pub project_id: String,
+
+    /// 只返回该类型的附件（可选）
+    #[serde(default)]
+    pub attachment_type: Option<AttachmentType>,
+}
+
+impl AttachmentsByProjectInput {
+    /// 创建新的 AttachmentsByProjectInput
+    pub fn new(project_id: impl Into<String>, attachment_type: Option<AttachmentType>) -> Self {
+        Self {
+            project_id: project_id.into(),
+            attachment_type,
+        }
+    }
+}
+
+/// 附件文件路径输入
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FilePathInput {
+    /// 附件文件路径
+    pub file_path: String,
+}
+
+impl FilePathInput {
+    /// 创建新的 FilePathInput
+    pub fn new(file_path: impl Into<String>) -> Self {
+        Self {
+            file_path: file_path.into(),
+        }
+    }
+}
+
+/// 按类型查询附件输入
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AttachmentsByTypeInput {
+    /// 项目（工作区）ID
+    pub project_id: String,
+
+    /// 附件类型
+    pub attachment_type: AttachmentType,
+}
+
+impl AttachmentsByTypeInput {
+    /// 创建新的 AttachmentsByTypeInput
+    pub fn new(project_id: impl Into<String>, attachment_type: AttachmentType) -> Self {
+        Self {
+            project_id: project_id.into(),
+            attachment_type,
+        }
+    }
+}
+
+// ============================================================================
+// 用户相关输入
+// ============================================================================
+
+/// 用户名输入
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UsernameInput {
+    /// 用户名
+    pub username: String,
+}
+
+impl UsernameInput {
+    /// 创建新的 UsernameInput
+    pub fn new(username: impl Into<String>) -> Self {
+        Self {
+            username: username.into(),
+        }
+    }
+}
+
+/// 邮箱输入
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EmailInput {
+    /// 邮箱地址
+    pub email: String,
+}
+
+impl EmailInput {
+    /// 创建新的 EmailInput
+    pub fn new(email: impl Into<String>) -> Self {
+        Self {
+            email: email.into(),
+        }
+    }
+}
+
+// ============================================================================
+// 数据相关输入
+// ============================================================================
+
+/// 清除数据输入
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ClearDataInput {
+    /// 保留用户（可选，默认 false）
+    #[serde(default)]
+    pub keep_users: bool,
+}
+
+impl ClearDataInput {
+    /// 创建新的 ClearDataInput
+    pub fn new(keep_users: bool) -> Self {
+        Self { keep_users }
+    }
+}
+
 // ============================================================================
 // 带请求体的输入
 // ============================================================================
@@ -160,6 +424,55 @@ impl<T> IdWithBodyInput<T> {
     }
 }
 
+/// 当前用户 + 请求体输入
+///
+/// 用于需要知道当前用户的 HTTP 端点，身份由服务器的认证过滤器提供
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AuthWithBodyInput<T> {
+    /// 当前用户
+    pub auth: AuthUser,
+
+    /// 请求体（使用 flatten 将字段展开）
+    #[serde(flatten)]
+    pub body: T,
+}
+
+impl<T> AuthWithBodyInput<T> {
+    /// 创建新的 AuthWithBodyInput
+    pub fn new(auth: AuthUser, body: T) -> Self {
+        Self { auth, body }
+    }
+}
+
+/// 可选的当前用户 + 请求体输入
+///
+/// 用于 HTTP 和 Tauri 共用、启用认证时还要知道当前用户的端点；
+/// 未启用认证的服务器和 Tauri 命令中当前用户为 None
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OptionalAuthWithBodyInput<T> {
+    /// 当前用户（可选）
+    #[serde(default)]
+    pub auth: Option<AuthUser>,
+
+    /// 请求体（使用 flatten 将字段展开）
+    #[serde(flatten)]
+    pub body: T,
+}
+
+impl<T> OptionalAuthWithBodyInput<T> {
+    /// 创建新的 OptionalAuthWithBodyInput
+    pub fn new(auth: Option<AuthUser>, body: T) -> Self {
+        Self { auth, body }
+    }
+
+    /// 没有当前用户的输入
+    pub fn anonymous(body: T) -> Self {
+        Self { auth: None, body }
+    }
+}
+
 // ============================================================================
 // 测试
 // ============================================================================
@@ -196,6 +509,22 @@ mod tests {
         assert_eq!(input.node_id, "node-123");
     }
 
+    #[test]
+    fn test_node_tree_input_deserialize() {
+        let json = r#"{"workspaceId": "ws-123", "maxDepth": 2}"#;
+        let input: NodeTreeInput = serde_json::from_str(json).unwrap();
+        assert_eq!(input.workspace_id, "ws-123");
+        assert_eq!(input.root_id, None);
+        assert_eq!(input.max_depth, Some(2));
+    }
+
+    #[test]
+    fn test_node_ids_input_deserialize() {
+        let json = r#"{"nodeIds": ["a", "b"]}"#;
+        let input: NodeIdsInput = serde_json::from_str(json).unwrap();
+        assert_eq!(input.node_ids, vec!["a", "b"]);
+    }
+
     #[test]
     fn test_id_with_body_input_deserialize() {
         #[derive(Debug, Deserialize, PartialEq)]
@@ -0,0 +1,555 @@
+//! Ledger API 端点
+//!
+//! 记账引擎相关的 API 端点实现。
+//! 记账节点内容在读取时解析，账本语法见 `fn::ledger`；有错误的分录不计入余额和报表。
+//!
+//! ## 端点列表
+//!
+//! | 端点 | 方法 | 路径 | 说明 |
+//! |------|------|------|------|
+//! | GetLedgerBalances | GET | /api/workspaces/:id/ledger/balances?nodeId=&to= | 账户余额 |
+//! | GetLedgerReport | GET | /api/workspaces/:id/ledger/report?groupBy=&account=&depth=&from=&to= | 按账户/月份汇总 |
+//! | CheckLedger | GET | /api/nodes/:node_id/ledger | 解析单个记账节点并返回诊断信息 |
+//! | ImportLedgerCsv | POST | /api/nodes/:node_id/ledger/import | 导入银行 CSV 流水 |
+
+use chrono::NaiveDate;
+use sea_orm::DatabaseConnection;
+
+use super::{Access, ApiEndpoint, IdWithBodyInput, NodeIdInput, SaveContent};
+use crate::db::{content_db_fn, ledger_db_fn, node_db_fn};
+use crate::r#fn::ledger::{
+    append_ledger_entries, csv_to_ledger_entries, find_duplicate_transactions, ledger_balances,
+    ledger_report, parse_ledger, LedgerPeriod, ParsedLedger,
+};
+use crate::types::content::SaveContentRequest;
+use crate::types::ledger::{
+    ImportLedgerCsvRequest, LedgerBalancesQuery, LedgerBalancesResponse, LedgerCheckResponse,
+    LedgerImportResponse, LedgerNodeDiagnostic, LedgerReportQuery, LedgerReportResponse,
+};
+use crate::types::member::WorkspaceRole;
+use crate::types::node::{NodeModel, NodeType};
+use crate::{AppError, AppResult};
+
+/// 解析可选的日期参数
+fn parse_date_param(date: Option<&str>) -> AppResult<Option<NaiveDate>> {
+    date.map(|d| {
+        NaiveDate::parse_from_str(d.trim(), "%Y-%m-%d")
+            .map_err(|_| AppError::validation(format!("无效的日期: {}", d)))
+    })
+    .transpose()
+}
+
+/// 查找记账节点，不存在或不是记账类型时返回错误
+async fn find_ledger_node(db: &DatabaseConnection, node_id: &str) -> AppResult<NodeModel> {
+    let node = node_db_fn::find_by_id(db, node_id)
+        .await?
+        .ok_or_else(|| AppError::not_found(format!("Node {}", node_id)))?;
+    if node.node_type != NodeType::Ledger {
+        return Err(AppError::validation(format!(
+            "节点不是记账类型: {}",
+            node.node_type
+        )));
+    }
+    Ok(node)
+}
+
+/// 加载并解析工作区的记账节点，同时收集所有诊断信息
+async fn load_ledgers(
+    db: &DatabaseConnection,
+    workspace_id: &str,
+    node_id: Option<&str>,
+) -> AppResult<(Vec<ParsedLedger>, Vec<LedgerNodeDiagnostic>)> {
+    let mut ledgers = Vec::new();
+    let mut diagnostics = Vec::new();
+    for (node, content) in ledger_db_fn::find_ledgers(db, workspace_id, node_id).await? {
+        let parsed = parse_ledger(&content);
+        diagnostics.extend(parsed.diagnostics.iter().cloned().map(|diagnostic| {
+            LedgerNodeDiagnostic {
+                node_id: node.id.clone(),
+                node_title: node.title.clone(),
+                diagnostic,
+            }
+        }));
+        ledgers.push(parsed);
+    }
+    Ok((ledgers, diagnostics))
+}
+
+// ============================================================================
+// GetLedgerBalances - 账户余额
+// ============================================================================
+
+/// 计算工作区内所有记账节点（或指定节点）的账户余额
+///
+/// 子账户余额同时计入父账户（`Expenses:Food` 计入 `Expenses`），按币种分别统计。
+///
+/// ## HTTP
+/// - Method: GET
+/// - Path: /api/workspaces/:workspace_id/ledger/balances?nodeId=&to=2026-01-31
+///
+/// ## Tauri
+/// - Command: get_ledger_balances
+///
+/// ## 参数
+/// - id: 工作区 ID
+/// - node_id: 只统计指定记账节点（可选）
+/// - to: 截止日期（包含，可选）
+///
+/// ## 返回
+/// - 成功: LedgerBalancesResponse（余额和诊断信息）
+/// - 失败: ValidationError, DatabaseError
+pub struct GetLedgerBalances;
+
+impl ApiEndpoint for GetLedgerBalances {
+    type Input = IdWithBodyInput<LedgerBalancesQuery>;
+    type Output = LedgerBalancesResponse;
+    const NAME: &'static str = "get_ledger_balances";
+
+    fn access(input: &Self::Input) -> Access {
+        Access::Workspace(input.id.clone(), WorkspaceRole::Viewer)
+    }
+
+    async fn execute(db: &DatabaseConnection, input: Self::Input) -> AppResult<Self::Output> {
+        let period = LedgerPeriod {
+            from: None,
+            to: parse_date_param(input.body.to.as_deref())?,
+        };
+        let (ledgers, diagnostics) =
+            load_ledgers(db, &input.id, input.body.node_id.as_deref()).await?;
+
+        Ok(LedgerBalancesResponse {
+            balances: ledger_balances(
+                ledgers.iter().flat_map(ParsedLedger::valid_entries),
+                &period,
+            )
+            .map_err(AppError::validation)?,
+            diagnostics,
+        })
+    }
+}
+
+// ============================================================================
+// GetLedgerReport - 记账报表
+// ============================================================================
+
+/// 按账户或月份汇总日期范围内的过账
+///
+/// ## HTTP
+/// - Method: GET
+/// - Path: /api/workspaces/:workspace_id/ledger/report?groupBy=month&account=Expenses&depth=2
+///
+/// ## Tauri
+/// - Command: get_ledger_report
+///
+/// ## 参数
+/// - id: 工作区 ID
+/// - group_by: `account` / `month`（默认 `account`）
+/// - account: 账户过滤，包含子账户（可选）
+/// - depth: 账户层级深度（可选，至少为 1）
+/// - node_id: 只统计指定记账节点（可选）
+/// - from / to: 日期范围（包含，可选）
+///
+/// ## 返回
+/// - 成功: LedgerReportResponse（报表行和诊断信息）
+/// - 失败: ValidationError, DatabaseError
+pub struct GetLedgerReport;
+
+impl ApiEndpoint for GetLedgerReport {
+    type Input = IdWithBodyInput<LedgerReportQuery>;
+    type Output = LedgerReportResponse;
+    const NAME: &'static str = "get_ledger_report";
+
+    fn access(input: &Self::Input) -> Access {
+        Access::Workspace(input.id.clone(), WorkspaceRole::Viewer)
+    }
+
+    async fn execute(db: &DatabaseConnection, input: Self::Input) -> AppResult<Self::Output> {
+        let query = input.body;
+        if query.depth == Some(0) {
+            return Err(AppError::validation("账户层级深度至少为 1"));
+        }
+        let period = LedgerPeriod {
+            from: parse_date_param(query.from.as_deref())?,
+            to: parse_date_param(query.to.as_deref())?,
+        };
+        if matches!((period.from, period.to), (Some(from), Some(to)) if from > to) {
+            return Err(AppError::validation("起始日期不能晚于结束日期"));
+        }
+
+        let (ledgers, diagnostics) = load_ledgers(db, &input.id, query.node_id.as_deref()).await?;
+        Ok(LedgerReportResponse {
+            rows: ledger_report(
+                ledgers.iter().flat_map(ParsedLedger::valid_entries),
+                &query,
+                &period,
+            )
+            .map_err(AppError::validation)?,
+            diagnostics,
+        })
+    }
+}
+
+// ============================================================================
+// CheckLedger - 解析记账节点
+// ============================================================================
+
+/// 解析单个记账节点，返回所有分录（包含无效分录）和逐条诊断信息
+///
+/// ## HTTP
+/// - Method: GET
+/// - Path: /api/nodes/:node_id/ledger
+///
+/// ## Tauri
+/// - Command: check_ledger
+///
+/// ## 参数
+/// - node_id: 记账节点 ID
+///
+/// ## 返回
+/// - 成功: LedgerCheckResponse
+/// - 失败: NotFound, ValidationError（不是记账节点）, DatabaseError
+pub struct CheckLedger;
+
+impl ApiEndpoint for CheckLedger {
+    type Input = NodeIdInput;
+    type Output = LedgerCheckResponse;
+    const NAME: &'static str = "check_ledger";
+
+    fn access(input: &Self::Input) -> Access {
+        Access::Node(input.node_id.clone(), WorkspaceRole::Viewer)
+    }
+
+    async fn execute(db: &DatabaseConnection, input: Self::Input) -> AppResult<Self::Output> {
+        let node = find_ledger_node(db, &input.node_id).await?;
+        let parsed = content_db_fn::find_by_node_id(db, &node.id)
+            .await?
+            .map(|content| parse_ledger(&content.content))
+            .unwrap_or_default();
+        Ok(LedgerCheckResponse {
+            node_id: node.id,
+            entries: parsed.entries,
+            diagnostics: parsed.diagnostics,
+        })
+    }
+}
+
+// ============================================================================
+// ImportLedgerCsv - 导入银行 CSV
+// ============================================================================
+
+/// 将银行 CSV 流水转换为分录并追加到记账节点内容
+///
+/// 每行生成一笔分录：银行账户和按分类规则确定的对方账户。
+/// 与节点中已有分录日期、金额、币种都相同的交易视为重复，不会再次导入；
+/// 无法解析的行记录在 issues 中。写入经过 SaveContent，同样更新版本号和标签。
+///
+/// ## HTTP
+/// - Method: POST
+/// - Path: /api/nodes/:node_id/ledger/import
+/// - Body: ImportLedgerCsvRequest
+///
+/// ## Tauri
+/// - Command: import_ledger_csv
+///
+/// ## 参数
+/// - id: 记账节点 ID
+/// - body: 导入请求（CSV 文本、银行账户、列映射、日期格式、符号约定、分类规则等）
+///
+/// ## 返回
+/// - 成功: LedgerImportResponse（dry_run 或没有新分录时 content 为 None）
+/// - 失败: NotFound, ValidationError（映射/账户配置错误、不是记账节点、版本冲突）, DatabaseError
+pub struct ImportLedgerCsv;
+
+impl ApiEndpoint for ImportLedgerCsv {
+    type Input = IdWithBodyInput<ImportLedgerCsvRequest>;
+    type Output = LedgerImportResponse;
+    const NAME: &'static str = "import_ledger_csv";
+
+    fn access(input: &Self::Input) -> Access {
+        Access::Node(input.id.clone(), WorkspaceRole::Editor)
+    }
+
+    async fn execute(db: &DatabaseConnection, input: Self::Input) -> AppResult<Self::Output> {
+        let request = input.body;
+        let node = find_ledger_node(db, &input.id).await?;
+        let (transactions, issues) =
+            csv_to_ledger_entries(&request).map_err(AppError::validation)?;
+
+        let existing = content_db_fn::find_by_node_id(db, &node.id).await?;
+        let parsed = existing
+            .as_ref()
+            .map(|content| parse_ledger(&content.content))
+            .unwrap_or_default();
+        let duplicates =
+            find_duplicate_transactions(parsed.valid_entries(), &request.account, &transactions);
+
+        let mut entries = Vec::new();
+        let mut duplicate_rows = Vec::new();
+        for (transaction, duplicate) in transactions.into_iter().zip(duplicates) {
+            if duplicate {
+                duplicate_rows.push(transaction.row);
+            } else {
+                entries.push(transaction.entry);
+            }
+        }
+
+        let content = if request.dry_run || entries.is_empty() {
+            None
+        } else {
+            let saved = SaveContent::execute(
+                db,
+                SaveContentRequest {
+                    node_id: node.id,
+                    content: append_ledger_entries(
+                        existing.as_ref().map(|c| c.content.as_str()),
+                        &entries,
+                    ),
+                    // 未指定版本时以读取时的版本为准，避免覆盖并发修改
+                    expected_version: request
+                        .expected_version
+                        .or(existing.as_ref().map(|c| c.version)),
+                    content_type: None,
+                },
+            )
+            .await?;
+            Some(saved)
+        };
+
+        Ok(LedgerImportResponse {
+            entries,
+            duplicate_rows,
+            issues,
+            content,
+        })
+    }
+}
+
+// ============================================================================
+// 测试
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::test_utils::setup_test_db;
+    use crate::db::workspace_db_fn;
+    use crate::types::ledger::{
+        LedgerCsvColumn, LedgerCsvMapping, LedgerGroupBy, LedgerImportRule,
+    };
+
+    async fn setup_ledgers(db: &DatabaseConnection) -> String {
+        let workspace_id = uuid::Uuid::new_v4().to_string();
+        workspace_db_fn::create(db, workspace_id.clone(), "测试工作区".to_string(), None)
+            .await
+            .unwrap();
+
+        let ledgers = [
+            (
+                "january",
+                "2026-01-05 午饭\n  Expenses:Food 30 CNY\n  Assets:Cash\n\n\
+                 2026-01-09 错账\n  Expenses:Food 10 CNY\n  Assets:Cash -1 CNY\n",
+            ),
+            (
+                "february",
+                "2026-02-02 车票\n  Expenses:Transport 12 CNY\n  Assets:Cash\n",
+            ),
+        ];
+        for (id, text) in ledgers {
+            node_db_fn::create(
+                db,
+                id.to_string(),
+                workspace_id.clone(),
+                None,
+                id.to_string(),
+                NodeType::Ledger,
+                None,
+            )
+            .await
+            .unwrap();
+            content_db_fn::create(db, format!("c-{}", id), id.to_string(), text.to_string())
+                .await
+                .unwrap();
+        }
+        workspace_id
+    }
+
+    #[tokio::test]
+    async fn test_ledger_balances_and_report() {
+        let db = setup_test_db().await;
+        let workspace_id = setup_ledgers(&db).await;
+
+        let result = GetLedgerBalances::execute(
+            &db,
+            IdWithBodyInput::new(&workspace_id, LedgerBalancesQuery::default()),
+        )
+        .await
+        .unwrap();
+        let cash = result
+            .balances
+            .iter()
+            .find(|b| b.account == "Assets:Cash")
+            .unwrap();
+        assert_eq!(cash.amount.to_string(), "-42.00");
+        assert_eq!(cash.commodity, "CNY");
+        assert_eq!(result.diagnostics.len(), 1);
+        assert_eq!(result.diagnostics[0].node_id, "january");
+        assert_eq!(result.diagnostics[0].diagnostic.line, Some(5));
+
+        let result = GetLedgerBalances::execute(
+            &db,
+            IdWithBodyInput::new(
+                &workspace_id,
+                LedgerBalancesQuery {
+                    node_id: Some("february".to_string()),
+                    to: None,
+                },
+            ),
+        )
+        .await
+        .unwrap();
+        assert!(result.diagnostics.is_empty());
+        assert!(result.balances.iter().all(|b| b.account != "Expenses:Food"));
+
+        let report = GetLedgerReport::execute(
+            &db,
+            IdWithBodyInput::new(
+                &workspace_id,
+                LedgerReportQuery {
+                    group_by: LedgerGroupBy::Month,
+                    account: Some("Expenses".to_string()),
+                    ..Default::default()
+                },
+            ),
+        )
+        .await
+        .unwrap();
+        let rows: Vec<(Option<&str>, &str)> = report
+            .rows
+            .iter()
+            .map(|r| (r.period.as_deref(), r.account.as_str()))
+            .collect();
+        assert_eq!(
+            rows,
+            vec![
+                (Some("2026-01"), "Expenses:Food"),
+                (Some("2026-02"), "Expenses:Transport"),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_check_ledger() {
+        let db = setup_test_db().await;
+        let workspace_id = setup_ledgers(&db).await;
+
+        let checked = CheckLedger::execute(&db, NodeIdInput::new("january"))
+            .await
+            .unwrap();
+        assert_eq!(checked.entries.len(), 2);
+        assert_eq!(checked.diagnostics.len(), 1);
+        assert_eq!(checked.diagnostics[0].entry_index, Some(1));
+
+        node_db_fn::create(
+            &db,
+            "note".to_string(),
+            workspace_id,
+            None,
+            "note".to_string(),
+            NodeType::File,
+            None,
+        )
+        .await
+        .unwrap();
+        let result = CheckLedger::execute(&db, NodeIdInput::new("note")).await;
+        assert!(matches!(result, Err(AppError::ValidationError(_))));
+
+        let result = CheckLedger::execute(&db, NodeIdInput::new("missing")).await;
+        assert!(matches!(result, Err(AppError::NotFound(_))));
+
+        let result = ImportLedgerCsv::execute(
+            &db,
+            IdWithBodyInput::new("note", ImportLedgerCsvRequest::default()),
+        )
+        .await;
+        assert!(matches!(result, Err(AppError::ValidationError(_))));
+
+        let result = GetLedgerReport::execute(
+            &db,
+            IdWithBodyInput::new(
+                "ws",
+                LedgerReportQuery {
+                    depth: Some(0),
+                    ..Default::default()
+                },
+            ),
+        )
+        .await;
+        assert!(matches!(result, Err(AppError::ValidationError(_))));
+    }
+
+    #[tokio::test]
+    async fn test_import_ledger_csv() {
+        let db = setup_test_db().await;
+        setup_ledgers(&db).await;
+
+        let request = |dry_run: bool| ImportLedgerCsvRequest {
+            csv: "date,memo,amount\n\
+                  2026-01-05,Lunch,-30\n\
+                  2026-01-20,Metro card,-50\n\
+                  2026-01-21,Refund,12.5\n\
+                  bad,Broken,1\n"
+                .to_string(),
+            account: "Assets:Cash".to_string(),
+            mapping: LedgerCsvMapping {
+                date: Some(LedgerCsvColumn::Name("date".to_string())),
+                description: Some(LedgerCsvColumn::Name("memo".to_string())),
+                amount: Some(LedgerCsvColumn::Name("amount".to_string())),
+                ..Default::default()
+            },
+            commodity: Some("CNY".to_string()),
+            rules: vec![LedgerImportRule {
+                contains: "metro".to_string(),
+                account: "Expenses:Transport".to_string(),
+            }],
+            dry_run,
+            ..Default::default()
+        };
+
+        let preview = ImportLedgerCsv::execute(&db, IdWithBodyInput::new("january", request(true)))
+            .await
+            .unwrap();
+        assert_eq!(preview.duplicate_rows, vec![2]);
+        assert_eq!(preview.entries.len(), 2);
+        assert_eq!(preview.issues.len(), 1);
+        assert!(preview.content.is_none());
+
+        let imported =
+            ImportLedgerCsv::execute(&db, IdWithBodyInput::new("january", request(false)))
+                .await
+                .unwrap();
+        assert_eq!(imported.content.unwrap().version, 2);
+
+        let checked = CheckLedger::execute(&db, NodeIdInput::new("january"))
+            .await
+            .unwrap();
+        assert_eq!(checked.entries.len(), 4);
+        assert_eq!(checked.entries[2].postings[1].account, "Expenses:Transport");
+        assert_eq!(
+            checked.entries[3].postings[1].account,
+            "Income:Uncategorized"
+        );
+
+        // 再次导入时全部视为重复
+        let again = ImportLedgerCsv::execute(&db, IdWithBodyInput::new("january", request(false)))
+            .await
+            .unwrap();
+        assert_eq!(again.duplicate_rows, vec![2, 3, 4]);
+        assert!(again.content.is_none());
+
+        let mut stale = request(false);
+        stale.csv.push_str("2026-01-31,Late,-1\n");
+        stale.expected_version = Some(1);
+        let result = ImportLedgerCsv::execute(&db, IdWithBodyInput::new("january", stale)).await;
+        assert!(matches!(result, Err(AppError::ValidationError(_))));
+    }
+}
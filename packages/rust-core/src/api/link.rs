@@ -0,0 +1,334 @@
+//! Link API 端点
+//!
+//! 节点 `@` 引用链接相关的 API 端点实现。
+//! 链接索引在保存内容时自动更新（见 `content_db_fn`）。
+//!
+//! ## 端点列表
+//!
+//! | 端点 | 方法 | 路径 | 说明 |
+//! |------|------|------|------|
+//! | GetBacklinks | GET | /api/nodes/:node_id/backlinks | 获取引用该节点的节点 |
+//! | GetOutgoingLinks | GET | /api/nodes/:node_id/links | 获取该节点引用的节点 |
+//! | GetBrokenLinks | GET | /api/workspaces/:id/links/broken | 获取工作区内的断链 |
+//! | RebuildLinks | POST | /api/workspaces/:id/links/rebuild | 从内容重建链接索引 |
+
+use sea_orm::DatabaseConnection;
+
+use super::{Access, ApiEndpoint, NodeIdInput, WorkspaceIdInput};
+use crate::db::link_db_fn;
+use crate::types::link::NodeLinkResponse;
+use crate::types::member::WorkspaceRole;
+use crate::types::node::NodeResponse;
+use crate::AppResult;
+
+// ============================================================================
+// GetBacklinks - 获取反向链接
+// ============================================================================
+
+/// 获取引用了指定节点的所有节点（反向链接）
+///
+/// ## HTTP
+/// - Method: GET
+/// - Path: /api/nodes/:node_id/backlinks
+///
+/// ## Tauri
+/// - Command: get_backlinks
+///
+/// ## 参数
+/// - node_id: 被引用的节点 ID
+///
+/// ## 返回
+/// - 成功: Vec<NodeResponse>（按标题排序）
+/// - 失败: DatabaseError
+pub struct GetBacklinks;
+
+impl ApiEndpoint for GetBacklinks {
+    type Input = NodeIdInput;
+    type Output = Vec<NodeResponse>;
+    const NAME: &'static str = "get_backlinks";
+
+    fn access(input: &Self::Input) -> Access {
+        Access::Node(input.node_id.clone(), WorkspaceRole::Viewer)
+    }
+
+    async fn execute(db: &DatabaseConnection, input: Self::Input) -> AppResult<Self::Output> {
+        link_db_fn::find_backlinks(db, &input.node_id)
+            .await
+            .map(|nodes| nodes.into_iter().map(Into::into).collect())
+    }
+}
+
+// ============================================================================
+// GetOutgoingLinks - 获取出链
+// ============================================================================
+
+/// 获取指定节点内容中引用的所有节点
+///
+/// ## HTTP
+/// - Method: GET
+/// - Path: /api/nodes/:node_id/links
+///
+/// ## Tauri
+/// - Command: get_outgoing_links
+///
+/// ## 参数
+/// - node_id: 源节点 ID
+///
+/// ## 返回
+/// - 成功: Vec<NodeResponse>（仅包含仍然存在的目标，按标题排序）
+/// - 失败: DatabaseError
+pub struct GetOutgoingLinks;
+
+impl ApiEndpoint for GetOutgoingLinks {
+    type Input = NodeIdInput;
+    type Output = Vec<NodeResponse>;
+    const NAME: &'static str = "get_outgoing_links";
+
+    fn access(input: &Self::Input) -> Access {
+        Access::Node(input.node_id.clone(), WorkspaceRole::Viewer)
+    }
+
+    async fn execute(db: &DatabaseConnection, input: Self::Input) -> AppResult<Self::Output> {
+        link_db_fn::find_outgoing(db, &input.node_id)
+            .await
+            .map(|nodes| nodes.into_iter().map(Into::into).collect())
+    }
+}
+
+// ============================================================================
+// GetBrokenLinks - 获取断链
+// ============================================================================
+
+/// 获取工作区内目标节点已不存在的链接
+///
+/// ## HTTP
+/// - Method: GET
+/// - Path: /api/workspaces/:workspace_id/links/broken
+///
+/// ## Tauri
+/// - Command: get_broken_links
+///
+/// ## 参数
+/// - workspace_id: 工作区 ID
+///
+/// ## 返回
+/// - 成功: Vec<NodeLinkResponse>
+/// - 失败: DatabaseError
+pub struct GetBrokenLinks;
+
+impl ApiEndpoint for GetBrokenLinks {
+    type Input = WorkspaceIdInput;
+    type Output = Vec<NodeLinkResponse>;
+    const NAME: &'static str = "get_broken_links";
+
+    fn access(input: &Self::Input) -> Access {
+        Access::Workspace(input.workspace_id.clone(), WorkspaceRole::Viewer)
+    }
+
+    async fn execute(db: &DatabaseConnection, input: Self::Input) -> AppResult<Self::Output> {
+        link_db_fn::find_broken(db, &input.workspace_id)
+            .await
+            .map(|links| links.into_iter().map(Into::into).collect())
+    }
+}
+
+// ============================================================================
+// RebuildLinks - 重建链接索引
+// ============================================================================
+
+/// 从内容表重建工作区的链接索引
+///
+/// 恢复旧版本备份（没有链接索引）后调用。
+///
+/// ## HTTP
+/// - Method: POST
+/// - Path: /api/workspaces/:workspace_id/links/rebuild
+///
+/// ## Tauri
+/// - Command: rebuild_links
+///
+/// ## 参数
+/// - workspace_id: 工作区 ID
+///
+/// ## 返回
+/// - 成功: u64（重建后的链接数）
+/// - 失败: DatabaseError
+pub struct RebuildLinks;
+
+impl ApiEndpoint for RebuildLinks {
+    type Input = WorkspaceIdInput;
+    type Output = u64;
+    const NAME: &'static str = "rebuild_links";
+
+    fn access(input: &Self::Input) -> Access {
+        Access::Workspace(input.workspace_id.clone(), WorkspaceRole::Editor)
+    }
+
+    async fn execute(db: &DatabaseConnection, input: Self::Input) -> AppResult<Self::Output> {
+        link_db_fn::rebuild_for_workspace(db, &input.workspace_id).await
+    }
+}
+
+// ============================================================================
+// 测试
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::{DeleteNodeRecursive, IdInput, SaveContent};
+    use crate::db::test_utils::setup_test_db;
+    use crate::db::{node_db_fn, workspace_db_fn};
+    use crate::r#fn::node::node_service_fn;
+    use crate::types::content::SaveContentRequest;
+    use crate::types::node::NodeType;
+
+    async fn create_test_workspace(db: &DatabaseConnection) -> String {
+        let id = uuid::Uuid::new_v4().to_string();
+        workspace_db_fn::create(db, id.clone(), "测试工作区".to_string(), None)
+            .await
+            .unwrap();
+        id
+    }
+
+    async fn create_test_node(db: &DatabaseConnection, workspace_id: &str, id: &str) {
+        node_db_fn::create(
+            db,
+            id.to_string(),
+            workspace_id.to_string(),
+            None,
+            format!("节点 {}", id),
+            NodeType::File,
+            None,
+        )
+        .await
+        .unwrap();
+    }
+
+    fn content_with_mentions(targets: &[&str]) -> String {
+        let mentions: Vec<String> = targets
+            .iter()
+            .map(|id| {
+                format!(
+                    r#"{{"type":"mention","mentionName":"节点 {}","roleId":"{}","text":"@节点 {}"}}"#,
+                    id, id, id
+                )
+            })
+            .collect();
+        format!(
+            r#"{{"root":{{"children":[{{"type":"paragraph","children":[{}]}}]}}}}"#,
+            mentions.join(",")
+        )
+    }
+
+    async fn save(db: &DatabaseConnection, node_id: &str, content: String) {
+        SaveContent::execute(
+            db,
+            SaveContentRequest {
+                node_id: node_id.to_string(),
+                content,
+                expected_version: None,
+                content_type: None,
+            },
+        )
+        .await
+        .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_links_follow_save_content() {
+        let db = setup_test_db().await;
+        let workspace_id = create_test_workspace(&db).await;
+        for id in ["a", "b", "c"] {
+            create_test_node(&db, &workspace_id, id).await;
+        }
+
+        save(&db, "a", content_with_mentions(&["b", "c"])).await;
+        let outgoing = GetOutgoingLinks::execute(&db, NodeIdInput::new("a"))
+            .await
+            .unwrap();
+        assert_eq!(outgoing.len(), 2);
+
+        let backlinks = GetBacklinks::execute(&db, NodeIdInput::new("b"))
+            .await
+            .unwrap();
+        assert_eq!(backlinks.len(), 1);
+        assert_eq!(backlinks[0].id, "a");
+
+        // 再次保存时移除对 b 的引用
+        save(&db, "a", content_with_mentions(&["c"])).await;
+        let backlinks = GetBacklinks::execute(&db, NodeIdInput::new("b"))
+            .await
+            .unwrap();
+        assert!(backlinks.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_links_on_delete_and_restore() {
+        let db = setup_test_db().await;
+        let workspace_id = create_test_workspace(&db).await;
+        for id in ["a", "b"] {
+            create_test_node(&db, &workspace_id, id).await;
+        }
+        save(&db, "a", content_with_mentions(&["b"])).await;
+
+        // 删除目标 -> 断链
+        DeleteNodeRecursive::execute(&db, IdInput::new("b"))
+            .await
+            .unwrap();
+        let broken = GetBrokenLinks::execute(&db, WorkspaceIdInput::new(&workspace_id))
+            .await
+            .unwrap();
+        assert_eq!(broken.len(), 1);
+        assert_eq!(broken[0].target_id, "b");
+        assert_eq!(broken[0].target_title, "节点 b");
+
+        // 以相同 ID 恢复目标 -> 链接重新生效
+        create_test_node(&db, &workspace_id, "b").await;
+        let broken = GetBrokenLinks::execute(&db, WorkspaceIdInput::new(&workspace_id))
+            .await
+            .unwrap();
+        assert!(broken.is_empty());
+        let backlinks = GetBacklinks::execute(&db, NodeIdInput::new("b"))
+            .await
+            .unwrap();
+        assert_eq!(backlinks.len(), 1);
+
+        // 删除源 -> 链接级联删除
+        node_db_fn::delete(&db, "a").await.unwrap();
+        let backlinks = GetBacklinks::execute(&db, NodeIdInput::new("b"))
+            .await
+            .unwrap();
+        assert!(backlinks.is_empty());
+        assert!(link_db_fn::find_by_source(&db, "a")
+            .await
+            .unwrap()
+            .is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_links_on_duplicate_and_rebuild() {
+        let db = setup_test_db().await;
+        let workspace_id = create_test_workspace(&db).await;
+        for id in ["a", "b"] {
+            create_test_node(&db, &workspace_id, id).await;
+        }
+        save(&db, "a", content_with_mentions(&["b"])).await;
+
+        let copy = node_service_fn::duplicate_node(&db, "a", None)
+            .await
+            .unwrap();
+        let backlinks = GetBacklinks::execute(&db, NodeIdInput::new("b"))
+            .await
+            .unwrap();
+        let sources: Vec<&str> = backlinks.iter().map(|n| n.id.as_str()).collect();
+        assert_eq!(sources.len(), 2);
+        assert!(sources.contains(&"a"));
+        assert!(sources.contains(&copy.id.as_str()));
+
+        let rebuilt = RebuildLinks::execute(&db, WorkspaceIdInput::new(&workspace_id))
+            .await
+            .unwrap();
+        assert_eq!(rebuilt, 2);
+    }
+}
@@ -0,0 +1,335 @@
+//! Log API 端点
+//!
+//! 前端日志持久化相关的 API 端点实现。
+//! 任何已登录用户都可以写入日志；查询、统计和清理需要管理员。
+//!
+//! ## 端点列表
+//!
+//! | 端点 | 方法 | 路径 | 说明 |
+//! |------|------|------|------|
+//! | InitLogDatabase | POST | /api/logs/init | 初始化日志表 |
+//! | CheckLogDatabaseExists | GET | /api/logs/exists | 检查日志表是否存在 |
+//! | SaveLogEntry | POST | /api/logs | 保存单个日志条目 |
+//! | SaveLogsBatch | POST | /api/logs/batch | 批量保存日志条目 |
+//! | QueryLogs | POST | /api/logs/query | 查询日志条目 |
+//! | GetLogStats | GET | /api/logs/stats | 获取日志统计 |
+//! | ClearOldLogs | DELETE | /api/logs | 清理旧日志 |
+//! | ClearAllLogs | DELETE | /api/logs/all | 清理所有日志 |
+
+use sea_orm::DatabaseConnection;
+
+use super::{Access, ApiEndpoint, NoInput, NoOutput};
+use crate::db::log_db_fn;
+use crate::types::log::{
+    ClearOldLogsRequest, CreateLogEntryRequest, LogEntryResponse, LogQueryOptions, LogQueryResult,
+    LogStats,
+};
+use crate::AppResult;
+
+/// 初始化、读取和清理日志的访问要求
+fn log_admin_access() -> Access {
+    Access::Admin
+}
+
+// ============================================================================
+// InitLogDatabase / CheckLogDatabaseExists - 日志表
+// ============================================================================
+
+/// 初始化日志表（已存在时不变）
+///
+/// ## HTTP
+/// - Method: POST
+/// - Path: /api/logs/init
+///
+/// ## Tauri
+/// - Command: init_log_database
+///
+/// ## 参数
+/// - 无
+///
+/// ## 返回
+/// - 成功: NoOutput
+/// - 失败: DatabaseError
+pub struct InitLogDatabase;
+
+impl ApiEndpoint for InitLogDatabase {
+    type Input = NoInput;
+    type Output = NoOutput;
+    const NAME: &'static str = "init_log_database";
+
+    fn access(_input: &Self::Input) -> Access {
+        log_admin_access()
+    }
+
+    async fn execute(db: &DatabaseConnection, _input: Self::Input) -> AppResult<Self::Output> {
+        log_db_fn::init_log_database(db).await
+    }
+}
+
+/// 检查日志表是否存在
+///
+/// ## HTTP
+/// - Method: GET
+/// - Path: /api/logs/exists
+///
+/// ## Tauri
+/// - Command: check_log_database_exists
+///
+/// ## 参数
+/// - 无
+///
+/// ## 返回
+/// - 成功: bool
+/// - 失败: DatabaseError
+pub struct CheckLogDatabaseExists;
+
+impl ApiEndpoint for CheckLogDatabaseExists {
+    type Input = NoInput;
+    type Output = bool;
+    const NAME: &'static str = "check_log_database_exists";
+
+    fn access(_input: &Self::Input) -> Access {
+        log_admin_access()
+    }
+
+    async fn execute(db: &DatabaseConnection, _input: Self::Input) -> AppResult<Self::Output> {
+        log_db_fn::check_log_database_exists(db).await
+    }
+}
+
+// ============================================================================
+// SaveLogEntry / SaveLogsBatch - 保存日志
+// ============================================================================
+
+/// 保存单个日志条目
+///
+/// ## HTTP
+/// - Method: POST
+/// - Path: /api/logs
+/// - Body: CreateLogEntryRequest
+///
+/// ## Tauri
+/// - Command: save_log_entry
+///
+/// ## 参数
+/// - entry: 日志条目（uuid 可选，不提供时自动生成）
+///
+/// ## 返回
+/// - 成功: LogEntryResponse
+/// - 失败: DatabaseError
+pub struct SaveLogEntry;
+
+impl ApiEndpoint for SaveLogEntry {
+    type Input = CreateLogEntryRequest;
+    type Output = LogEntryResponse;
+    const NAME: &'static str = "save_log_entry";
+
+    /// 前端记录自己的日志
+    fn access(_input: &Self::Input) -> Access {
+        Access::Authenticated
+    }
+
+    async fn execute(db: &DatabaseConnection, input: Self::Input) -> AppResult<Self::Output> {
+        log_db_fn::save_log_entry(db, input).await
+    }
+}
+
+/// 批量保存日志条目
+///
+/// ## HTTP
+/// - Method: POST
+/// - Path: /api/logs/batch
+/// - Body: Vec<CreateLogEntryRequest>
+///
+/// ## Tauri
+/// - Command: save_logs_batch
+///
+/// ## 参数
+/// - entries: 日志条目列表
+///
+/// ## 返回
+/// - 成功: Vec<LogEntryResponse>
+/// - 失败: DatabaseError
+pub struct SaveLogsBatch;
+
+impl ApiEndpoint for SaveLogsBatch {
+    type Input = Vec<CreateLogEntryRequest>;
+    type Output = Vec<LogEntryResponse>;
+    const NAME: &'static str = "save_logs_batch";
+
+    /// 前端记录自己的日志
+    fn access(_input: &Self::Input) -> Access {
+        Access::Authenticated
+    }
+
+    async fn execute(db: &DatabaseConnection, input: Self::Input) -> AppResult<Self::Output> {
+        log_db_fn::save_logs_batch(db, input).await
+    }
+}
+
+// ============================================================================
+// QueryLogs / GetLogStats - 查询日志
+// ============================================================================
+
+/// 查询日志条目
+///
+/// ## HTTP
+/// - Method: POST
+/// - Path: /api/logs/query
+/// - Body: LogQueryOptions
+///
+/// ## Tauri
+/// - Command: query_logs
+///
+/// ## 参数
+/// - options: 分页、级别、时间范围、来源和关键词过滤
+///
+/// ## 返回
+/// - 成功: LogQueryResult
+/// - 失败: Forbidden, DatabaseError
+pub struct QueryLogs;
+
+impl ApiEndpoint for QueryLogs {
+    type Input = LogQueryOptions;
+    type Output = LogQueryResult;
+    const NAME: &'static str = "query_logs";
+
+    fn access(_input: &Self::Input) -> Access {
+        log_admin_access()
+    }
+
+    async fn execute(db: &DatabaseConnection, input: Self::Input) -> AppResult<Self::Output> {
+        log_db_fn::query_logs(db, input).await
+    }
+}
+
+/// 获取日志统计（总数、各级别数量和时间范围）
+///
+/// ## HTTP
+/// - Method: GET
+/// - Path: /api/logs/stats
+///
+/// ## Tauri
+/// - Command: get_log_stats
+///
+/// ## 参数
+/// - 无
+///
+/// ## 返回
+/// - 成功: LogStats
+/// - 失败: Forbidden, DatabaseError
+pub struct GetLogStats;
+
+impl ApiEndpoint for GetLogStats {
+    type Input = NoInput;
+    type Output = LogStats;
+    const NAME: &'static str = "get_log_stats";
+
+    fn access(_input: &Self::Input) -> Access {
+        log_admin_access()
+    }
+
+    async fn execute(db: &DatabaseConnection, _input: Self::Input) -> AppResult<Self::Output> {
+        log_db_fn::get_log_stats(db).await
+    }
+}
+
+// ============================================================================
+// ClearOldLogs / ClearAllLogs - 清理日志
+// ============================================================================
+
+/// 清理指定日期之前的日志
+///
+/// ## HTTP
+/// - Method: DELETE
+/// - Path: /api/logs?beforeDate=
+///
+/// ## Tauri
+/// - Command: clear_old_logs
+///
+/// ## 参数
+/// - before_date: 日期（ISO 8601 格式）
+///
+/// ## 返回
+/// - 成功: i64（删除的条目数）
+/// - 失败: Forbidden, DatabaseError
+pub struct ClearOldLogs;
+
+impl ApiEndpoint for ClearOldLogs {
+    type Input = ClearOldLogsRequest;
+    type Output = i64;
+    const NAME: &'static str = "clear_old_logs";
+
+    fn access(_input: &Self::Input) -> Access {
+        log_admin_access()
+    }
+
+    async fn execute(db: &DatabaseConnection, input: Self::Input) -> AppResult<Self::Output> {
+        log_db_fn::clear_old_logs(db, &input.before_date).await
+    }
+}
+
+/// 清理所有日志
+///
+/// ## HTTP
+/// - Method: DELETE
+/// - Path: /api/logs/all
+///
+/// ## Tauri
+/// - Command: clear_all_logs
+///
+/// ## 参数
+/// - 无
+///
+/// ## 返回
+/// - 成功: i64（删除的条目数）
+/// - 失败: Forbidden, DatabaseError
+pub struct ClearAllLogs;
+
+impl ApiEndpoint for ClearAllLogs {
+    type Input = NoInput;
+    type Output = i64;
+    const NAME: &'static str = "clear_all_logs";
+
+    fn access(_input: &Self::Input) -> Access {
+        log_admin_access()
+    }
+
+    async fn execute(db: &DatabaseConnection, _input: Self::Input) -> AppResult<Self::Output> {
+        log_db_fn::clear_all_logs(db).await
+    }
+}
+
+// ============================================================================
+// 测试
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::test_utils::setup_test_db;
+
+    #[tokio::test]
+    async fn test_save_and_query_logs() {
+        let db = setup_test_db().await;
+        InitLogDatabase::execute(&db, ()).await.unwrap();
+        assert!(CheckLogDatabaseExists::execute(&db, ()).await.unwrap());
+
+        let entry: CreateLogEntryRequest = serde_json::from_value(serde_json::json!({
+            "timestamp": "2024-01-01T00:00:00Z",
+            "level": "Info",
+            "message": "hello",
+        }))
+        .unwrap();
+        SaveLogEntry::execute(&db, entry.clone()).await.unwrap();
+        SaveLogsBatch::execute(&db, vec![entry.clone(), entry])
+            .await
+            .unwrap();
+
+        let result = QueryLogs::execute(&db, LogQueryOptions::default())
+            .await
+            .unwrap();
+        assert_eq!(result.entries.len(), 3);
+        assert_eq!(ClearAllLogs::execute(&db, ()).await.unwrap(), 3);
+    }
+}
@@ -0,0 +1,279 @@
+//! Member API 端点
+//!
+//! 工作区成员管理。启用认证时按成员身份列出和创建工作区，见 `GetWorkspaces` 和 `CreateWorkspace`。
+//! 角色从低到高为 viewer（只读）、editor（读写内容）、owner（管理工作区和成员），
+//! 每个工作区至少保留一个 owner。
+//!
+//! ## 端点列表
+//!
+//! | 端点 | 方法 | 路径 | 说明 |
+//! |------|------|------|------|
+//! | GetWorkspaceMembers | GET | /api/workspaces/:id/members | 工作区的成员列表 |
+//! | SetWorkspaceMember | PUT | /api/workspaces/:id/members | 添加成员或修改成员角色 |
+//! | RemoveWorkspaceMember | DELETE | /api/workspaces/:id/members/:user_id | 移除成员 |
+
+use sea_orm::DatabaseConnection;
+
+use super::{
+    Access, ApiEndpoint, IdWithBodyInput, NoOutput, WorkspaceIdInput, WorkspaceMemberInput,
+};
+use crate::db::{user_db_fn, workspace_db_fn, workspace_member_db_fn};
+use crate::types::member::{SetWorkspaceMemberRequest, WorkspaceMemberResponse, WorkspaceRole};
+use crate::{AppError, AppResult};
+
+/// 检查修改后工作区仍有 owner
+async fn ensure_other_owner(
+    db: &DatabaseConnection,
+    workspace_id: &str,
+    current: Option<WorkspaceRole>,
+) -> AppResult<()> {
+    if current == Some(WorkspaceRole::Owner)
+        && workspace_member_db_fn::count_owners(db, workspace_id).await? <= 1
+    {
+        return Err(AppError::validation("工作区至少需要一个 owner"));
+    }
+    Ok(())
+}
+
+// ============================================================================
+// GetWorkspaceMembers - 获取成员列表
+// ============================================================================
+
+/// 获取工作区的成员列表（按加入时间排序）
+///
+/// ## HTTP
+/// - Method: GET
+/// - Path: /api/workspaces/:id/members
+///
+/// ## 参数
+/// - workspace_id: 工作区 ID（需要 viewer 权限）
+///
+/// ## 返回
+/// - 成功: Vec<WorkspaceMemberResponse>
+/// - 失败: Forbidden, DatabaseError
+pub struct GetWorkspaceMembers;
+
+impl ApiEndpoint for GetWorkspaceMembers {
+    type Input = WorkspaceIdInput;
+    type Output = Vec<WorkspaceMemberResponse>;
+    const NAME: &'static str = "get_workspace_members";
+
+    fn access(input: &Self::Input) -> Access {
+        Access::Workspace(input.workspace_id.clone(), WorkspaceRole::Viewer)
+    }
+
+    async fn execute(db: &DatabaseConnection, input: Self::Input) -> AppResult<Self::Output> {
+        let members = workspace_member_db_fn::find_by_workspace(db, &input.workspace_id).await?;
+        Ok(members
+            .into_iter()
+            .map(|(member, user)| WorkspaceMemberResponse::new(member, user))
+            .collect())
+    }
+}
+
+// ============================================================================
+// SetWorkspaceMember - 添加成员或修改角色
+// ============================================================================
+
+/// 添加成员或修改成员角色
+///
+/// 不能把唯一的 owner 降级。
+///
+/// ## HTTP
+/// - Method: PUT
+/// - Path: /api/workspaces/:id/members
+/// - Body: SetWorkspaceMemberRequest
+///
+/// ## 参数
+/// - id: 工作区 ID（需要 owner 权限）
+/// - username: 用户名
+/// - role: 角色（owner / editor / viewer）
+///
+/// ## 返回
+/// - 成功: WorkspaceMemberResponse
+/// - 失败: NotFound（工作区或用户不存在）, ValidationError（唯一的 owner 被降级）,
+///   Forbidden, DatabaseError
+pub struct SetWorkspaceMember;
+
+impl ApiEndpoint for SetWorkspaceMember {
+    type Input = IdWithBodyInput<SetWorkspaceMemberRequest>;
+    type Output = WorkspaceMemberResponse;
+    const NAME: &'static str = "set_workspace_member";
+
+    fn access(input: &Self::Input) -> Access {
+        Access::Workspace(input.id.clone(), WorkspaceRole::Owner)
+    }
+
+    async fn execute(db: &DatabaseConnection, input: Self::Input) -> AppResult<Self::Output> {
+        if workspace_db_fn::find_by_id(db, &input.id).await?.is_none() {
+            return Err(AppError::not_found(format!("Workspace {}", input.id)));
+        }
+        let user = user_db_fn::find_by_username(db, input.body.username.trim())
+            .await?
+            .ok_or_else(|| AppError::not_found(format!("User {}", input.body.username)))?;
+
+        let current = workspace_member_db_fn::find_role(db, &input.id, &user.id).await?;
+        if input.body.role != WorkspaceRole::Owner {
+            ensure_other_owner(db, &input.id, current).await?;
+        }
+        let member =
+            workspace_member_db_fn::set_role(db, &input.id, &user.id, input.body.role).await?;
+        Ok(WorkspaceMemberResponse::new(member, user))
+    }
+}
+
+// ============================================================================
+// RemoveWorkspaceMember - 移除成员
+// ============================================================================
+
+/// 移除工作区成员
+///
+/// 不能移除唯一的 owner。
+///
+/// ## HTTP
+/// - Method: DELETE
+/// - Path: /api/workspaces/:id/members/:user_id
+///
+/// ## 参数
+/// - workspace_id: 工作区 ID（需要 owner 权限）
+/// - user_id: 用户 ID
+///
+/// ## 返回
+/// - 成功: ()
+/// - 失败: NotFound（不是成员）, ValidationError（唯一的 owner）, Forbidden, DatabaseError
+pub struct RemoveWorkspaceMember;
+
+impl ApiEndpoint for RemoveWorkspaceMember {
+    type Input = WorkspaceMemberInput;
+    type Output = NoOutput;
+    const NAME: &'static str = "remove_workspace_member";
+
+    fn access(input: &Self::Input) -> Access {
+        Access::Workspace(input.workspace_id.clone(), WorkspaceRole::Owner)
+    }
+
+    async fn execute(db: &DatabaseConnection, input: Self::Input) -> AppResult<Self::Output> {
+        let current =
+            workspace_member_db_fn::find_role(db, &input.workspace_id, &input.user_id).await?;
+        if current.is_none() {
+            return Err(AppError::not_found(format!(
+                "Member {} of workspace {}",
+                input.user_id, input.workspace_id
+            )));
+        }
+        ensure_other_owner(db, &input.workspace_id, current).await?;
+        workspace_member_db_fn::remove(db, &input.workspace_id, &input.user_id).await?;
+        Ok(())
+    }
+}
+
+// ============================================================================
+// 测试
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::{CreateWorkspace, GetWorkspaces, OptionalAuthWithBodyInput};
+    use crate::db::auth_db_fn;
+    use crate::db::test_utils::setup_test_db;
+    use crate::types::auth::AuthUser;
+    use crate::types::workspace::CreateWorkspaceRequest;
+
+    fn auth(user_id: &str) -> AuthUser {
+        AuthUser {
+            user_id: user_id.to_string(),
+            session_id: String::new(),
+            token: None,
+        }
+    }
+
+    fn set_member(
+        workspace_id: &str,
+        username: &str,
+        role: WorkspaceRole,
+    ) -> IdWithBodyInput<SetWorkspaceMemberRequest> {
+        IdWithBodyInput::new(
+            workspace_id,
+            SetWorkspaceMemberRequest {
+                username: username.to_string(),
+                role,
+            },
+        )
+    }
+
+    #[tokio::test]
+    async fn test_workspace_membership() {
+        let db = setup_test_db().await;
+        let alice = auth_db_fn::ensure_user(&db, "alice", "password-1")
+            .await
+            .unwrap();
+        let bob = auth_db_fn::ensure_user(&db, "bob", "password-1")
+            .await
+            .unwrap();
+
+        let workspace = CreateWorkspace::execute(
+            &db,
+            OptionalAuthWithBodyInput::new(
+                Some(auth(&alice.id)),
+                CreateWorkspaceRequest {
+                    title: "共享".to_string(),
+                    author: None,
+                    description: None,
+                    publisher: None,
+                    language: None,
+                    members: None,
+                    owner: None,
+                },
+            ),
+        )
+        .await
+        .unwrap();
+        let listed = GetWorkspaces::execute(&db, Some(auth(&bob.id)))
+            .await
+            .unwrap();
+        assert!(listed.is_empty());
+
+        let member = SetWorkspaceMember::execute(
+            &db,
+            set_member(&workspace.id, "bob", WorkspaceRole::Editor),
+        )
+        .await
+        .unwrap();
+        assert_eq!(member.username, "bob");
+        let listed = GetWorkspaces::execute(&db, Some(auth(&bob.id)))
+            .await
+            .unwrap();
+        assert_eq!(listed.len(), 1);
+
+        let members = GetWorkspaceMembers::execute(&db, WorkspaceIdInput::new(&workspace.id))
+            .await
+            .unwrap();
+        let roles: Vec<_> = members.iter().map(|m| m.role).collect();
+        assert_eq!(roles, vec![WorkspaceRole::Owner, WorkspaceRole::Editor]);
+
+        // 唯一的 owner 不能降级或移除
+        let demote = SetWorkspaceMember::execute(
+            &db,
+            set_member(&workspace.id, "alice", WorkspaceRole::Viewer),
+        )
+        .await;
+        assert!(matches!(demote, Err(AppError::ValidationError(_))));
+        let remove = RemoveWorkspaceMember::execute(
+            &db,
+            WorkspaceMemberInput::new(&workspace.id, &alice.id),
+        )
+        .await;
+        assert!(matches!(remove, Err(AppError::ValidationError(_))));
+
+        RemoveWorkspaceMember::execute(&db, WorkspaceMemberInput::new(&workspace.id, &bob.id))
+            .await
+            .unwrap();
+        let missing = SetWorkspaceMember::execute(
+            &db,
+            set_member(&workspace.id, "carol", WorkspaceRole::Viewer),
+        )
+        .await;
+        assert!(matches!(missing, Err(AppError::NotFound(_))));
+    }
+}
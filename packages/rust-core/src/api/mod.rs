@@ -1,7 +1,7 @@
 //! API 端点定义
 //!
 //! 此模块定义 `ApiEndpoint` trait 和所有 API 端点实现。
-//! Warp 和 Tauri 通过宏使用这些端点定义，实现代码共享。
+//! 端点在 `registry` 的 `api_registry!` 中登记一次，Warp 路由、Tauri 命令和路由文档都由它生成。
 //!
 //! ## 设计原则
 //!
@@ -15,20 +15,36 @@
 //! use rust_core::api::{ApiEndpoint, GetWorkspaces};
 //!
 //! // 直接调用端点
-//! let workspaces = GetWorkspaces::execute(&db, ()).await?;
+//! let workspaces = GetWorkspaces::execute(&db, None).await?;
 //!
-//! // 通过宏生成 Warp 路由
-//! let routes = warp_routes!(db,
-//!     GetWorkspaces => GET "/api/workspaces",
-//! );
+//! // 由端点注册表生成 Warp 路由
+//! let routes = macros::combine_routes(api_registry!(warp_routes, db, auth));
 //! ```
 
+pub mod access;
+pub mod api_token;
+pub mod attachment;
+pub mod auth;
+pub mod change_log;
 pub mod content;
 pub mod clear_data;
+pub mod diary;
+pub mod graph;
 pub mod inputs;
+pub mod ledger;
+pub mod link;
+pub mod log;
+pub mod member;
 pub mod node;
+pub mod registry;
+pub mod search;
+pub mod sync;
+pub mod tag;
+pub mod task;
 pub mod transaction;
+pub mod user;
 pub mod workspace;
+pub mod writing;
 
 use sea_orm::DatabaseConnection;
 use serde::{de::DeserializeOwned, Serialize};
@@ -65,6 +81,7 @@ use crate::AppResult;
 /// - `Input`: 必须实现 `DeserializeOwned + Send + 'static`
 /// - `Output`: 必须实现 `Serialize + Send + 'static`
 /// - `NAME`: 端点名称，用于日志和调试
+/// - `access`: 访问控制（必须声明）
 /// - `execute`: 异步执行函数，返回 `AppResult<Output>`
 pub trait ApiEndpoint: Send + Sync + 'static {
     /// 请求输入类型
@@ -83,6 +100,13 @@ pub trait ApiEndpoint: Send + Sync + 'static {
     /// 例如：`"get_workspaces"`, `"create_node"`
     const NAME: &'static str;
 
+    /// 访问控制
+    ///
+    /// 返回执行前需要检查的资源和角色，每个端点都必须声明（没有默认值，避免遗漏时放行）。
+    /// 启用认证的 HTTP 服务器按返回值检查当前用户的工作区角色（见 `access` 模块），
+    /// Tauri 命令不检查。
+    fn access(input: &Self::Input) -> Access;
+
     /// 执行端点逻辑
     ///
     /// ## 参数
@@ -132,9 +156,26 @@ pub type NoOutput = ();
 // 重新导出
 // ============================================================================
 
+pub use access::*;
+pub use api_token::*;
+pub use attachment::*;
+pub use auth::*;
+pub use change_log::*;
 pub use content::*;
 pub use clear_data::*;
+pub use diary::*;
+pub use graph::*;
 pub use inputs::*;
+pub use ledger::*;
+pub use link::*;
+pub use log::*;
+pub use member::*;
 pub use node::*;
+pub use search::*;
+pub use sync::*;
+pub use tag::*;
+pub use task::*;
 pub use transaction::*;
+pub use user::*;
 pub use workspace::*;
+pub use writing::*;
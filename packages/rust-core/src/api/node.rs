@@ -6,55 +6,89 @@
 //!
 //! | 端点 | 方法 | 路径 | 说明 |
 //! |------|------|------|------|
-//! | GetNodesByWorkspace | GET | /api/workspaces/:id/nodes | 获取工作区所有节点 |
+//! | GetNodesByWorkspace | GET | /api/workspaces/:id/nodes?type=&parentId= | 获取工作区的节点（可按类型或父节点筛选） |
 //! | GetNode | GET | /api/nodes/:id | 获取单个节点 |
 //! | GetRootNodes | GET | /api/workspaces/:id/nodes/root | 获取根节点 |
 //! | GetChildNodes | GET | /api/nodes/:id/children | 获取子节点 |
+//! | GetNodesByParent | - | （仅 Tauri） | 按父节点获取节点（HTTP 见 GetNodesByWorkspace） |
+//! | GetNodesByType | - | （仅 Tauri） | 按类型获取节点（HTTP 见 GetNodesByWorkspace） |
+//! | GetDescendants | GET | /api/nodes/:id/descendants | 获取所有后代节点 |
 //! | GetNextSortOrder | GET | /api/workspaces/:id/nodes/next-sort-order | 获取下一个排序顺序 |
+//! | GetNodeTree | GET | /api/workspaces/:id/nodes/tree | 获取嵌套节点树 |
 //! | CreateNode | POST | /api/nodes | 创建节点 |
 //! | UpdateNode | PUT | /api/nodes/:id | 更新节点 |
 //! | MoveNode | PUT | /api/nodes/:id/move | 移动节点 |
+//! | DuplicateNode | POST | /api/nodes/:id/duplicate | 复制节点（包括内容） |
+//! | ReorderNodes | PUT | /api/nodes/reorder | 按给定顺序重排节点 |
 //! | DeleteNode | DELETE | /api/nodes/:id | 删除节点 |
+//! | DeleteNodesBatch | DELETE | /api/nodes/batch | 批量删除节点 |
 
-use sea_orm::DatabaseConnection;
+use sea_orm::{ConnectionTrait, DatabaseConnection, TransactionTrait};
 
-use super::{ApiEndpoint, IdInput, IdWithBodyInput, NextSortOrderInput, NoOutput, ParentIdInput, WorkspaceIdInput};
-use crate::db::node_db_fn;
+use super::content::persist_content_in;
+use super::{
+    Access, ApiEndpoint, IdInput, IdWithBodyInput, NextSortOrderInput, NoOutput, NodeIdInput,
+    NodeIdsInput, NodeTreeInput, NodesByParentInput, NodesByTypeInput, ParentIdInput,
+    WorkspaceIdInput,
+};
+use crate::db::{content_db_fn, link_db_fn, node_db_fn};
+use crate::events::PendingEvents;
+use crate::r#fn::node::{node_service_fn, node_transform_fn};
+use crate::types::event::ChangeEvent;
+use crate::types::link::LinkRewriteReport;
+use crate::types::member::WorkspaceRole;
 use crate::types::node::{
-    CreateNodeRequest, MoveNodeRequest, NodeResponse, NodeType, UpdateNodeRequest,
+    CreateNodeRequest, DuplicateNodeRequest, MoveNodeRequest, NodeChangeResponse, NodeListQuery,
+    NodeModel, NodeResponse, NodeTreeResponse, NodeType, UpdateNodeRequest,
 };
-use crate::AppResult;
+use crate::{AppError, AppResult};
 
 // ============================================================================
-// GetNodesByWorkspace - 获取工作区所有节点
+// GetNodesByWorkspace - 获取工作区的节点
 // ============================================================================
 
-/// 获取工作区下的所有节点
+/// 获取工作区下的节点，可按类型或父节点筛选
 ///
 /// ## HTTP
 /// - Method: GET
-/// - Path: /api/workspaces/:workspace_id/nodes
+/// - Path: /api/workspaces/:workspace_id/nodes?type=&parentId=
 ///
 /// ## Tauri
 /// - Command: get_nodes_by_workspace
 ///
 /// ## 参数
 /// - workspace_id: 工作区 ID
+/// - type: 节点类型（可选）
+/// - parent_id: 父节点 ID（可选，`null` 表示根级别）
 ///
 /// ## 返回
 /// - 成功: Vec<NodeResponse>
-/// - 失败: DatabaseError
+/// - 失败: ValidationError（未知类型）, DatabaseError
 pub struct GetNodesByWorkspace;
 
 impl ApiEndpoint for GetNodesByWorkspace {
-    type Input = WorkspaceIdInput;
+    type Input = IdWithBodyInput<NodeListQuery>;
     type Output = Vec<NodeResponse>;
     const NAME: &'static str = "get_nodes_by_workspace";
 
+    fn access(input: &Self::Input) -> Access {
+        Access::Workspace(input.id.clone(), WorkspaceRole::Viewer)
+    }
+
     async fn execute(db: &DatabaseConnection, input: Self::Input) -> AppResult<Self::Output> {
-        node_db_fn::find_by_workspace(db, &input.workspace_id)
-            .await
-            .map(|v| v.into_iter().map(Into::into).collect())
+        let node_type = input.body.node_type;
+        let nodes = match input.body.parent_filter() {
+            Some(parent_id) => node_db_fn::find_by_parent(db, &input.id, parent_id)
+                .await?
+                .into_iter()
+                .filter(|node| node_type.is_none_or(|t| node.node_type == t))
+                .collect(),
+            None => match node_type {
+                Some(node_type) => node_db_fn::find_by_type(db, &input.id, node_type).await?,
+                None => node_db_fn::find_by_workspace(db, &input.id).await?,
+            },
+        };
+        Ok(nodes.into_iter().map(Into::into).collect())
     }
 }
 
@@ -84,6 +118,10 @@ impl ApiEndpoint for GetNode {
     type Output = Option<NodeResponse>;
     const NAME: &'static str = "get_node";
 
+    fn access(input: &Self::Input) -> Access {
+        Access::Node(input.id.clone(), WorkspaceRole::Viewer)
+    }
+
     async fn execute(db: &DatabaseConnection, input: Self::Input) -> AppResult<Self::Output> {
         node_db_fn::find_by_id(db, &input.id)
             .await
@@ -117,6 +155,10 @@ impl ApiEndpoint for GetRootNodes {
     type Output = Vec<NodeResponse>;
     const NAME: &'static str = "get_root_nodes";
 
+    fn access(input: &Self::Input) -> Access {
+        Access::Workspace(input.workspace_id.clone(), WorkspaceRole::Viewer)
+    }
+
     async fn execute(db: &DatabaseConnection, input: Self::Input) -> AppResult<Self::Output> {
         node_db_fn::find_root_nodes(db, &input.workspace_id)
             .await
@@ -150,6 +192,10 @@ impl ApiEndpoint for GetChildNodes {
     type Output = Vec<NodeResponse>;
     const NAME: &'static str = "get_child_nodes";
 
+    fn access(input: &Self::Input) -> Access {
+        Access::Node(input.parent_id.clone(), WorkspaceRole::Viewer)
+    }
+
     async fn execute(db: &DatabaseConnection, input: Self::Input) -> AppResult<Self::Output> {
         node_db_fn::find_children(db, &input.parent_id)
             .await
@@ -184,11 +230,70 @@ impl ApiEndpoint for GetNextSortOrder {
     type Output = i32;
     const NAME: &'static str = "get_next_sort_order";
 
+    fn access(input: &Self::Input) -> Access {
+        Access::Workspace(input.workspace_id.clone(), WorkspaceRole::Viewer)
+    }
+
     async fn execute(db: &DatabaseConnection, input: Self::Input) -> AppResult<Self::Output> {
         node_db_fn::get_next_sort_order(db, &input.workspace_id, input.parent_id.as_deref()).await
     }
 }
 
+// ============================================================================
+// GetNodeTree - 获取嵌套节点树
+// ============================================================================
+
+/// 获取嵌套的节点树（递归 CTE 单次查询）
+///
+/// ## HTTP
+/// - Method: GET
+/// - Path: /api/workspaces/:workspace_id/nodes/tree?rootId=xxx&maxDepth=n
+///
+/// ## Tauri
+/// - Command: get_node_tree
+///
+/// ## 参数
+/// - workspace_id: 工作区 ID
+/// - root_id: 起始节点 ID（可选，返回其子树，不含该节点本身）
+/// - max_depth: 最大深度（可选，1 表示只返回直接子节点）
+///
+/// ## 返回
+/// - 成功: Vec<NodeTreeResponse>
+/// - 失败: NotFound（起始节点不存在或不属于该工作区）, DatabaseError
+pub struct GetNodeTree;
+
+impl ApiEndpoint for GetNodeTree {
+    type Input = NodeTreeInput;
+    type Output = Vec<NodeTreeResponse>;
+    const NAME: &'static str = "get_node_tree";
+
+    fn access(input: &Self::Input) -> Access {
+        Access::Workspace(input.workspace_id.clone(), WorkspaceRole::Viewer)
+    }
+
+    async fn execute(db: &DatabaseConnection, input: Self::Input) -> AppResult<Self::Output> {
+        if let Some(root_id) = &input.root_id {
+            let root = node_db_fn::find_by_id(db, root_id).await?;
+            if root.map(|r| r.workspace_id) != Some(input.workspace_id.clone()) {
+                return Err(AppError::not_found(format!("Node {}", root_id)));
+            }
+        }
+
+        let nodes = node_db_fn::find_tree(
+            db,
+            &input.workspace_id,
+            input.root_id.as_deref(),
+            input.max_depth,
+        )
+        .await?;
+
+        Ok(node_transform_fn::build_node_tree(
+            nodes,
+            input.root_id.as_deref(),
+        ))
+    }
+}
+
 // ============================================================================
 // CreateNode - 创建节点
 // ============================================================================
@@ -206,16 +311,16 @@ impl ApiEndpoint for GetNextSortOrder {
 /// ## 参数
 /// - workspace_id: 工作区 ID（必填）
 /// - parent_id: 父节点 ID（可选，null 表示根节点）
-/// - title: 节点标题（必填）
+/// - title: 节点标题（必填，去除首尾空白）
 /// - node_type: 节点类型（可选，默认 "file"）
 /// - sort_order: 排序顺序（可选）
 /// - is_collapsed: 是否折叠（可选）
-/// - tags: 标签数组（可选）
-/// - initial_content: 初始内容（可选）
+/// - tags: 标签数组（可选，提供初始内容时以内容中的标签为准）
+/// - initial_content: 初始内容（可选，文件夹以外的节点默认为 `{}`）
 ///
 /// ## 返回
 /// - 成功: NodeResponse
-/// - 失败: DatabaseError, ValidationError
+/// - 失败: NotFound（父节点不存在）, DatabaseError, ValidationError（父节点属于其他工作区）
 pub struct CreateNode;
 
 impl ApiEndpoint for CreateNode {
@@ -223,7 +328,17 @@ impl ApiEndpoint for CreateNode {
     type Output = NodeResponse;
     const NAME: &'static str = "create_node";
 
+    fn access(input: &Self::Input) -> Access {
+        Access::Workspace(input.workspace_id.clone(), WorkspaceRole::Editor)
+    }
+
     async fn execute(db: &DatabaseConnection, input: Self::Input) -> AppResult<Self::Output> {
+        let txn = db.begin().await?;
+        let mut pending = PendingEvents::new();
+
+        // 父节点必须在同一工作区，工作区的权限即父节点的权限
+        node_db_fn::check_parent(&txn, &input.workspace_id, input.parent_id.as_deref()).await?;
+
         // 生成 UUID
         let id = uuid::Uuid::new_v4().to_string();
 
@@ -231,17 +346,57 @@ impl ApiEndpoint for CreateNode {
         let tags = input.tags.map(|t| serde_json::to_string(&t).unwrap());
 
         // 调用数据库函数创建
-        node_db_fn::create(
-            db,
+        let node = node_db_fn::create(
+            &txn,
             id,
             input.workspace_id,
             input.parent_id,
-            input.title,
+            node_transform_fn::transform_title(&input.title),
             input.node_type.unwrap_or(NodeType::File),
             tags,
         )
-        .await
-        .map(Into::into)
+        .await?;
+
+        pending
+            .record(
+                &txn,
+                ChangeEvent::NodeCreated {
+                    workspace_id: node.workspace_id.clone(),
+                    node_id: node.id.clone(),
+                    parent_id: node.parent_id.clone(),
+                },
+            )
+            .await?;
+        if node.tags.is_some() {
+            pending
+                .record(
+                    &txn,
+                    ChangeEvent::TagsChanged {
+                        workspace_id: node.workspace_id.clone(),
+                    },
+                )
+                .await?;
+        }
+
+        // 文件夹以外的节点创建内容记录
+        let node = match input.initial_content {
+            _ if !node_transform_fn::node_type_needs_content(&node.node_type) => node,
+            None => {
+                let content_id = uuid::Uuid::new_v4().to_string();
+                content_db_fn::create(&txn, content_id, node.id.clone(), "{}".to_string()).await?;
+                node
+            }
+            Some(content) => {
+                // 初始内容按保存内容处理：同步标签、任务和写作进度，内容中的标签写回节点
+                persist_content_in(&txn, &mut pending, None, node.id.clone(), content, None)
+                    .await?;
+                node_db_fn::find_by_id(&txn, &node.id).await?.unwrap_or(node)
+            }
+        };
+
+        txn.commit().await?;
+        pending.publish();
+        Ok(node.into())
     }
 }
 
@@ -265,34 +420,65 @@ impl ApiEndpoint for CreateNode {
 /// - is_collapsed: 是否折叠（可选）
 /// - sort_order: 排序顺序（可选）
 /// - tags: 标签数组（可选）
+/// - rewrite_links: 是否同步改写引用该节点的 `@` 提及（可选，默认 false）
+///
+/// ## 事务保证
+/// - 请求改写引用时，节点更新和所有引用文档的改写在同一事务中完成
 ///
 /// ## 返回
-/// - 成功: NodeResponse
+/// - 成功: NodeChangeResponse（请求改写时附带 linkRewrite 报告）
 /// - 失败: NotFound, DatabaseError
 pub struct UpdateNode;
 
 impl ApiEndpoint for UpdateNode {
     type Input = IdWithBodyInput<UpdateNodeRequest>;
-    type Output = NodeResponse;
+    type Output = NodeChangeResponse;
     const NAME: &'static str = "update_node";
 
+    fn access(input: &Self::Input) -> Access {
+        Access::Node(input.id.clone(), WorkspaceRole::Editor)
+    }
+
     async fn execute(db: &DatabaseConnection, input: Self::Input) -> AppResult<Self::Output> {
         // 序列化 tags
+        let tags_changed = input.body.tags.is_some();
         let tags = input
             .body
             .tags
             .map(|t| Some(serde_json::to_string(&t).unwrap()));
 
-        node_db_fn::update(
-            db,
+        let txn = db.begin().await?;
+        let mut pending = PendingEvents::new();
+        let node = node_db_fn::update(
+            &txn,
             &input.id,
             input.body.title,
             input.body.is_collapsed,
             input.body.sort_order,
             tags,
         )
-        .await
-        .map(Into::into)
+        .await?;
+        let report = if input.body.rewrite_links.unwrap_or(false) {
+            Some(link_db_fn::rewrite_mentions_to(&txn, &node.id, &node.title).await?)
+        } else {
+            None
+        };
+        record_node_updated(
+            &txn,
+            &mut pending,
+            &node.workspace_id,
+            &node.id,
+            tags_changed,
+            report.as_ref(),
+        )
+        .await?;
+        txn.commit().await?;
+        pending.publish();
+
+        Ok(NodeChangeResponse {
+            node: node.into(),
+            link_rewrite: report,
+        })
     }
 }
 
@@ -314,26 +500,52 @@ impl ApiEndpoint for UpdateNode {
 /// - id: 节点 ID（路径参数）
 /// - new_parent_id: 新的父节点 ID（null 表示移动到根级别）
 /// - new_sort_order: 新的排序顺序
+/// - rewrite_links: 是否同步改写引用该节点的 `@` 提及（可选，默认 false）
+///
+/// ## 事务保证
+/// - 请求改写引用时，移动和所有引用文档的改写在同一事务中完成
 ///
 /// ## 返回
-/// - 成功: NodeResponse
-/// - 失败: NotFound, DatabaseError
+/// - 成功: NodeChangeResponse（请求改写时附带 linkRewrite 报告）
+/// - 失败: NotFound, DatabaseError, ValidationError（新的父节点属于其他工作区）
 pub struct MoveNode;
 
 impl ApiEndpoint for MoveNode {
     type Input = IdWithBodyInput<MoveNodeRequest>;
-    type Output = NodeResponse;
+    type Output = NodeChangeResponse;
     const NAME: &'static str = "move_node";
 
+    /// 节点和新的父节点都需要 editor 权限
+    fn access(input: &Self::Input) -> Access {
+        let ids = std::iter::once(input.id.clone())
+            .chain(input.body.new_parent_id.clone())
+            .collect();
+        Access::Nodes(ids, WorkspaceRole::Editor)
+    }
+
     async fn execute(db: &DatabaseConnection, input: Self::Input) -> AppResult<Self::Output> {
-        node_db_fn::move_node(
-            db,
+        let txn = db.begin().await?;
+        let mut pending = PendingEvents::new();
+        let node = node_db_fn::move_node(
+            &txn,
             &input.id,
             input.body.new_parent_id,
             input.body.new_sort_order,
         )
-        .await
-        .map(Into::into)
+        .await?;
+        let report = if input.body.rewrite_links.unwrap_or(false) {
+            Some(link_db_fn::rewrite_mentions_to(&txn, &node.id, &node.title).await?)
+        } else {
+            None
+        };
+        record_node_moved(&txn, &mut pending, &node, report.as_ref()).await?;
+        txn.commit().await?;
+        pending.publish();
+
+        Ok(NodeChangeResponse {
+            node: node.into(),
+            link_rewrite: report,
+        })
     }
 }
 
@@ -363,10 +575,371 @@ impl ApiEndpoint for DeleteNode {
     type Output = NoOutput;
     const NAME: &'static str = "delete_node";
 
+    fn access(input: &Self::Input) -> Access {
+        Access::Node(input.id.clone(), WorkspaceRole::Editor)
+    }
+
     async fn execute(db: &DatabaseConnection, input: Self::Input) -> AppResult<Self::Output> {
+        let txn = db.begin().await?;
+        let mut pending = PendingEvents::new();
+        node_db_fn::find_by_id(&txn, &input.id)
+            .await?
+            .ok_or_else(|| AppError::not_found(format!("Node {}", input.id)))?;
+
         // 使用批量删除以确保级联删除子节点
-        node_db_fn::delete_batch(db, vec![input.id]).await
+        let removed = node_db_fn::delete_batch(&txn, vec![input.id]).await?;
+
+        record_nodes_deleted(&txn, &mut pending, removed).await?;
+        txn.commit().await?;
+        pending.publish();
+        Ok(())
+    }
+}
+
+// ============================================================================
+// GetNodesByParent - 按父节点获取节点
+// ============================================================================
+
+/// 获取工作区中某个父节点下的节点（按排序顺序）
+///
+/// HTTP 使用 `GET /api/workspaces/:workspace_id/nodes?parentId=`（见 GetNodesByWorkspace）
+///
+/// ## Tauri
+/// - Command: get_nodes_by_parent
+///
+/// ## 参数
+/// - workspace_id: 工作区 ID
+/// - parent_id: 父节点 ID（null 表示根级别）
+///
+/// ## 返回
+/// - 成功: Vec<NodeResponse>
+/// - 失败: DatabaseError
+pub struct GetNodesByParent;
+
+impl ApiEndpoint for GetNodesByParent {
+    type Input = NodesByParentInput;
+    type Output = Vec<NodeResponse>;
+    const NAME: &'static str = "get_nodes_by_parent";
+
+    fn access(input: &Self::Input) -> Access {
+        Access::Workspace(input.workspace_id.clone(), WorkspaceRole::Viewer)
+    }
+
+    async fn execute(db: &DatabaseConnection, input: Self::Input) -> AppResult<Self::Output> {
+        let nodes =
+            node_db_fn::find_by_parent(db, &input.workspace_id, input.parent_id.as_deref()).await?;
+        Ok(nodes.into_iter().map(Into::into).collect())
+    }
+}
+
+// ============================================================================
+// GetNodesByType - 按类型获取节点
+// ============================================================================
+
+/// 获取工作区中指定类型的节点
+///
+/// HTTP 使用 `GET /api/workspaces/:workspace_id/nodes?type=`（见 GetNodesByWorkspace）
+///
+/// ## Tauri
+/// - Command: get_nodes_by_type
+///
+/// ## 参数
+/// - workspace_id: 工作区 ID
+/// - node_type: 节点类型
+///
+/// ## 返回
+/// - 成功: Vec<NodeResponse>
+/// - 失败: ValidationError（未知类型）, DatabaseError
+pub struct GetNodesByType;
+
+impl ApiEndpoint for GetNodesByType {
+    type Input = NodesByTypeInput;
+    type Output = Vec<NodeResponse>;
+    const NAME: &'static str = "get_nodes_by_type";
+
+    fn access(input: &Self::Input) -> Access {
+        Access::Workspace(input.workspace_id.clone(), WorkspaceRole::Viewer)
+    }
+
+    async fn execute(db: &DatabaseConnection, input: Self::Input) -> AppResult<Self::Output> {
+        let nodes = node_db_fn::find_by_type(db, &input.workspace_id, input.node_type).await?;
+        Ok(nodes.into_iter().map(Into::into).collect())
+    }
+}
+
+// ============================================================================
+// GetDescendants - 获取所有后代节点
+// ============================================================================
+
+/// 获取节点的所有后代节点（不含节点本身）
+///
+/// ## HTTP
+/// - Method: GET
+/// - Path: /api/nodes/:node_id/descendants
+///
+/// ## Tauri
+/// - Command: get_descendants
+///
+/// ## 参数
+/// - node_id: 节点 ID
+///
+/// ## 返回
+/// - 成功: Vec<NodeResponse>
+/// - 失败: DatabaseError
+pub struct GetDescendants;
+
+impl ApiEndpoint for GetDescendants {
+    type Input = NodeIdInput;
+    type Output = Vec<NodeResponse>;
+    const NAME: &'static str = "get_descendants";
+
+    fn access(input: &Self::Input) -> Access {
+        Access::Node(input.node_id.clone(), WorkspaceRole::Viewer)
+    }
+
+    async fn execute(db: &DatabaseConnection, input: Self::Input) -> AppResult<Self::Output> {
+        let nodes = node_db_fn::find_descendants(db, &input.node_id).await?;
+        Ok(nodes.into_iter().map(Into::into).collect())
+    }
+}
+
+// ============================================================================
+// DuplicateNode - 复制节点
+// ============================================================================
+
+/// 复制节点及其内容，副本与原节点在同一父节点下
+///
+/// ## HTTP
+/// - Method: POST
+/// - Path: /api/nodes/:id/duplicate
+/// - Body: DuplicateNodeRequest
+///
+/// ## Tauri
+/// - Command: duplicate_node
+///
+/// ## 参数
+/// - id: 原节点 ID（路径参数）
+/// - new_title: 副本的标题（可选）
+///
+/// ## 返回
+/// - 成功: NodeResponse（副本）
+/// - 失败: NotFound, DatabaseError
+pub struct DuplicateNode;
+
+impl ApiEndpoint for DuplicateNode {
+    type Input = IdWithBodyInput<DuplicateNodeRequest>;
+    type Output = NodeResponse;
+    const NAME: &'static str = "duplicate_node";
+
+    fn access(input: &Self::Input) -> Access {
+        Access::Node(input.id.clone(), WorkspaceRole::Editor)
+    }
+
+    async fn execute(db: &DatabaseConnection, input: Self::Input) -> AppResult<Self::Output> {
+        let txn = db.begin().await?;
+        let mut pending = PendingEvents::new();
+        let node = node_service_fn::duplicate_node(&txn, &input.id, input.body.new_title).await?;
+        pending
+            .record(
+                &txn,
+                ChangeEvent::NodeCreated {
+                    workspace_id: node.workspace_id.clone(),
+                    node_id: node.id.clone(),
+                    parent_id: node.parent_id.clone(),
+                },
+            )
+            .await?;
+        txn.commit().await?;
+        pending.publish();
+        Ok(node.into())
+    }
+}
+
+// ============================================================================
+// ReorderNodes - 重排节点
+// ============================================================================
+
+/// 按给定顺序重排节点（排序顺序依次设为 0, 1, 2...）
+///
+/// ## HTTP
+/// - Method: PUT
+/// - Path: /api/nodes/reorder
+/// - Body: { nodeIds }
+///
+/// ## Tauri
+/// - Command: reorder_nodes
+///
+/// ## 参数
+/// - node_ids: 按新顺序排列的节点 ID
+///
+/// ## 返回
+/// - 成功: ()
+/// - 失败: NotFound, DatabaseError
+pub struct ReorderNodes;
+
+impl ApiEndpoint for ReorderNodes {
+    type Input = NodeIdsInput;
+    type Output = NoOutput;
+    const NAME: &'static str = "reorder_nodes";
+
+    fn access(input: &Self::Input) -> Access {
+        Access::Nodes(input.node_ids.clone(), WorkspaceRole::Editor)
+    }
+
+    async fn execute(db: &DatabaseConnection, input: Self::Input) -> AppResult<Self::Output> {
+        let txn = db.begin().await?;
+        let mut pending = PendingEvents::new();
+        node_db_fn::reorder_nodes(&txn, input.node_ids.clone()).await?;
+        for node_id in &input.node_ids {
+            if let Some(node) = node_db_fn::find_by_id(&txn, node_id).await? {
+                record_node_moved(&txn, &mut pending, &node, None).await?;
+            }
+        }
+        txn.commit().await?;
+        pending.publish();
+        Ok(())
+    }
+}
+
+// ============================================================================
+// DeleteNodesBatch - 批量删除节点
+// ============================================================================
+
+/// 批量删除节点（级联删除子节点），不存在的节点忽略
+///
+/// ## HTTP
+/// - Method: DELETE
+/// - Path: /api/nodes/batch
+/// - Body: { nodeIds }
+///
+/// ## Tauri
+/// - Command: delete_nodes_batch
+///
+/// ## 参数
+/// - node_ids: 要删除的节点 ID
+///
+/// ## 返回
+/// - 成功: ()
+/// - 失败: DatabaseError
+pub struct DeleteNodesBatch;
+
+impl ApiEndpoint for DeleteNodesBatch {
+    type Input = NodeIdsInput;
+    type Output = NoOutput;
+    const NAME: &'static str = "delete_nodes_batch";
+
+    fn access(input: &Self::Input) -> Access {
+        Access::Nodes(input.node_ids.clone(), WorkspaceRole::Editor)
+    }
+
+    async fn execute(db: &DatabaseConnection, input: Self::Input) -> AppResult<Self::Output> {
+        let txn = db.begin().await?;
+        let mut pending = PendingEvents::new();
+        let removed = node_db_fn::delete_batch(&txn, input.node_ids).await?;
+
+        record_nodes_deleted(&txn, &mut pending, removed).await?;
+        txn.commit().await?;
+        pending.publish();
+        Ok(())
+    }
+}
+
+// ============================================================================
+// 事件记录
+// ============================================================================
+
+/// 记录节点更新事件（标签变化和被改写引用的文档一并记录）
+async fn record_node_updated<C: ConnectionTrait>(
+    db: &C,
+    pending: &mut PendingEvents,
+    workspace_id: &str,
+    node_id: &str,
+    tags_changed: bool,
+    report: Option<&LinkRewriteReport>,
+) -> AppResult<()> {
+    pending
+        .record(
+            db,
+            ChangeEvent::NodeUpdated {
+                workspace_id: workspace_id.to_string(),
+                node_id: node_id.to_string(),
+            },
+        )
+        .await?;
+    if tags_changed {
+        pending
+            .record(
+                db,
+                ChangeEvent::TagsChanged {
+                    workspace_id: workspace_id.to_string(),
+                },
+            )
+            .await?;
     }
+    record_link_rewrite(db, pending, workspace_id, report).await
+}
+
+/// 记录节点移动事件（被改写引用的文档一并记录）
+async fn record_node_moved<C: ConnectionTrait>(
+    db: &C,
+    pending: &mut PendingEvents,
+    node: &NodeModel,
+    report: Option<&LinkRewriteReport>,
+) -> AppResult<()> {
+    pending
+        .record(
+            db,
+            ChangeEvent::NodeMoved {
+                workspace_id: node.workspace_id.clone(),
+                node_id: node.id.clone(),
+                parent_id: node.parent_id.clone(),
+                sort_order: node.sort_order,
+            },
+        )
+        .await?;
+    record_link_rewrite(db, pending, &node.workspace_id, report).await
+}
+
+/// 为改写了引用的文档记录内容保存事件
+async fn record_link_rewrite<C: ConnectionTrait>(
+    db: &C,
+    pending: &mut PendingEvents,
+    workspace_id: &str,
+    report: Option<&LinkRewriteReport>,
+) -> AppResult<()> {
+    for document in report.map(|r| r.documents.as_slice()).unwrap_or_default() {
+        pending
+            .record(
+                db,
+                ChangeEvent::ContentSaved {
+                    workspace_id: workspace_id.to_string(),
+                    node_id: document.node_id.clone(),
+                    version: document.version,
+                },
+            )
+            .await?;
+    }
+    Ok(())
+}
+
+/// 为每个被删除的节点记录删除事件（级联删除的后代也逐个记录）
+pub(crate) async fn record_nodes_deleted<C: ConnectionTrait>(
+    db: &C,
+    pending: &mut PendingEvents,
+    removed: Vec<NodeModel>,
+) -> AppResult<()> {
+    for node in removed {
+        pending
+            .record(
+                db,
+                ChangeEvent::NodeDeleted {
+                    workspace_id: node.workspace_id,
+                    node_id: node.id,
+                },
+            )
+            .await?;
+    }
+    Ok(())
 }
 
 // ============================================================================
@@ -377,7 +950,8 @@ impl ApiEndpoint for DeleteNode {
 mod tests {
     use super::*;
     use crate::db::test_utils::setup_test_db;
-    use crate::db::workspace_db_fn;
+    use crate::db::{change_log_db_fn, workspace_db_fn};
+    use crate::types::change_log::ChangeOperation;
 
     async fn create_test_workspace(db: &DatabaseConnection) -> String {
         let id = uuid::Uuid::new_v4().to_string();
@@ -430,24 +1004,43 @@ mod tests {
         let db = setup_test_db().await;
         let workspace_id = create_test_workspace(&db).await;
 
-        // 创建多个节点
-        for i in 1..=3 {
-            let input = CreateNodeRequest {
-                workspace_id: workspace_id.clone(),
-                parent_id: None,
-                node_type: Some(NodeType::File),
-                title: format!("节点 {}", i),
-                sort_order: None,
-                is_collapsed: None,
-                tags: None,
-                initial_content: None,
-            };
-            CreateNode::execute(&db, input).await.unwrap();
+        let request = |title: &str, node_type, parent_id| CreateNodeRequest {
+            workspace_id: workspace_id.clone(),
+            parent_id,
+            node_type: Some(node_type),
+            title: title.to_string(),
+            sort_order: None,
+            is_collapsed: None,
+            tags: None,
+            initial_content: None,
+        };
+        let folder = CreateNode::execute(&db, request("文件夹", NodeType::Folder, None))
+            .await
+            .unwrap();
+        CreateNode::execute(&db, request("根文件", NodeType::File, None))
+            .await
+            .unwrap();
+        CreateNode::execute(
+            &db,
+            request("子文件", NodeType::File, Some(folder.id.clone())),
+        )
+        .await
+        .unwrap();
+
+        // 按类型、父节点（null 为根级别）筛选，同时指定时取交集
+        let child_query = format!("parentId={}", folder.id);
+        for (query, expected) in [
+            ("", 3),
+            ("type=file", 2),
+            ("parentId=null", 2),
+            (child_query.as_str(), 1),
+            ("parentId=null&type=file", 1),
+        ] {
+            let query: NodeListQuery = serde_urlencoded::from_str(query).unwrap();
+            let input = IdWithBodyInput::new(&workspace_id, query);
+            let result = GetNodesByWorkspace::execute(&db, input).await.unwrap();
+            assert_eq!(result.len(), expected);
         }
-
-        let input = WorkspaceIdInput::new(&workspace_id);
-        let result = GetNodesByWorkspace::execute(&db, input).await.unwrap();
-        assert_eq!(result.len(), 3);
     }
 
     #[tokio::test]
@@ -529,6 +1122,83 @@ mod tests {
         assert_eq!(result.len(), 2);
     }
 
+    #[tokio::test]
+    async fn test_get_node_tree() {
+        let db = setup_test_db().await;
+        let workspace_id = create_test_workspace(&db).await;
+
+        let folder = CreateNode::execute(
+            &db,
+            CreateNodeRequest {
+                workspace_id: workspace_id.clone(),
+                parent_id: None,
+                node_type: Some(NodeType::Folder),
+                title: "文件夹".to_string(),
+                sort_order: None,
+                is_collapsed: None,
+                tags: None,
+                initial_content: None,
+            },
+        )
+        .await
+        .unwrap();
+
+        let sub = CreateNode::execute(
+            &db,
+            CreateNodeRequest {
+                workspace_id: workspace_id.clone(),
+                parent_id: Some(folder.id.clone()),
+                node_type: Some(NodeType::Folder),
+                title: "子文件夹".to_string(),
+                sort_order: None,
+                is_collapsed: None,
+                tags: None,
+                initial_content: None,
+            },
+        )
+        .await
+        .unwrap();
+
+        CreateNode::execute(
+            &db,
+            CreateNodeRequest {
+                workspace_id: workspace_id.clone(),
+                parent_id: Some(sub.id.clone()),
+                node_type: Some(NodeType::File),
+                title: "文件".to_string(),
+                sort_order: None,
+                is_collapsed: None,
+                tags: None,
+                initial_content: None,
+            },
+        )
+        .await
+        .unwrap();
+
+        // 完整树
+        let input = NodeTreeInput::new(&workspace_id, None, None);
+        let tree = GetNodeTree::execute(&db, input).await.unwrap();
+        assert_eq!(tree.len(), 1);
+        assert_eq!(tree[0].node.id, folder.id);
+        assert_eq!(tree[0].children[0].node.id, sub.id);
+        assert_eq!(tree[0].children[0].children[0].node.title, "文件");
+
+        // 限制深度
+        let input = NodeTreeInput::new(&workspace_id, None, Some(2));
+        let tree = GetNodeTree::execute(&db, input).await.unwrap();
+        assert!(tree[0].children[0].children.is_empty());
+
+        // 从子树开始
+        let input = NodeTreeInput::new(&workspace_id, Some(folder.id.clone()), None);
+        let tree = GetNodeTree::execute(&db, input).await.unwrap();
+        assert_eq!(tree.len(), 1);
+        assert_eq!(tree[0].node.id, sub.id);
+
+        // 起始节点不存在
+        let input = NodeTreeInput::new(&workspace_id, Some("missing".to_string()), None);
+        assert!(GetNodeTree::execute(&db, input).await.is_err());
+    }
+
     #[tokio::test]
     async fn test_update_node() {
         let db = setup_test_db().await;
@@ -557,9 +1227,12 @@ mod tests {
                 sort_order: None,
                 is_collapsed: Some(true),
                 tags: Some(vec!["new-tag".to_string()]),
+                rewrite_links: None,
             },
         );
         let updated = UpdateNode::execute(&db, update_input).await.unwrap();
+        assert!(updated.link_rewrite.is_none());
+        let updated = updated.node;
 
         assert_eq!(updated.id, created.id);
         assert_eq!(updated.title, "新标题");
@@ -567,6 +1240,66 @@ mod tests {
         assert_eq!(updated.tags, Some(vec!["new-tag".to_string()]));
     }
 
+    #[tokio::test]
+    async fn test_update_node_rewrites_links() {
+        use crate::db::content_db_fn;
+
+        let db = setup_test_db().await;
+        let workspace_id = create_test_workspace(&db).await;
+
+        let create = |title: &str| CreateNodeRequest {
+            workspace_id: workspace_id.clone(),
+            parent_id: None,
+            node_type: Some(NodeType::File),
+            title: title.to_string(),
+            sort_order: None,
+            is_collapsed: None,
+            tags: None,
+            initial_content: None,
+        };
+        let target = CreateNode::execute(&db, create("旧标题")).await.unwrap();
+        let source = CreateNode::execute(&db, create("引用方")).await.unwrap();
+
+        let content = format!(
+            r#"{{"root":{{"children":[{{"type":"mention","mentionName":"旧标题","roleId":"{}","text":"@旧标题"}}]}}}}"#,
+            target.id
+        );
+        // 创建节点时已有空文档（版本 1），写入引用后为版本 2
+        content_db_fn::update(&db, &source.id, content, None)
+            .await
+            .unwrap();
+
+        let update_input = IdWithBodyInput::new(
+            &target.id,
+            UpdateNodeRequest {
+                parent_id: None,
+                node_type: None,
+                title: Some("新标题".to_string()),
+                sort_order: None,
+                is_collapsed: None,
+                tags: None,
+                rewrite_links: Some(true),
+            },
+        );
+        let result = UpdateNode::execute(&db, update_input).await.unwrap();
+        assert_eq!(result.node.title, "新标题");
+
+        let report = result.link_rewrite.unwrap();
+        assert_eq!(report.documents.len(), 1);
+        assert_eq!(report.documents[0].node_id, source.id);
+        assert_eq!(report.documents[0].title, "引用方");
+        assert_eq!(report.documents[0].mentions, 1);
+        assert_eq!(report.documents[0].version, 3);
+
+        let saved = content_db_fn::find_by_node_id(&db, &source.id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(saved.version, 3);
+        assert!(saved.content.contains("@新标题"));
+        assert!(!saved.content.contains("旧标题"));
+    }
+
     #[tokio::test]
     async fn test_move_node() {
         let db = setup_test_db().await;
@@ -616,12 +1349,56 @@ mod tests {
             MoveNodeRequest {
                 new_parent_id: Some(folder2.id.clone()),
                 new_sort_order: 0,
+                rewrite_links: None,
             },
         );
-        let moved = MoveNode::execute(&db, move_input).await.unwrap();
+        let moved = MoveNode::execute(&db, move_input).await.unwrap().node;
 
         assert_eq!(moved.parent_id, Some(folder2.id));
         assert_eq!(moved.sort_order, 0);
+
+        // 不能移动或创建到其他工作区的节点下
+        let other = CreateNode::execute(
+            &db,
+            CreateNodeRequest {
+                workspace_id: create_test_workspace(&db).await,
+                parent_id: None,
+                node_type: Some(NodeType::Folder),
+                title: "其他工作区".to_string(),
+                sort_order: None,
+                is_collapsed: None,
+                tags: None,
+                initial_content: None,
+            },
+        )
+        .await
+        .unwrap();
+        let move_input = IdWithBodyInput::new(
+            &file.id,
+            MoveNodeRequest {
+                new_parent_id: Some(other.id.clone()),
+                new_sort_order: 0,
+                rewrite_links: None,
+            },
+        );
+        assert!(matches!(
+            MoveNode::execute(&db, move_input).await,
+            Err(AppError::ValidationError(_))
+        ));
+        let create_input = CreateNodeRequest {
+            workspace_id: folder1.workspace_id.clone(),
+            parent_id: Some(other.id),
+            node_type: Some(NodeType::File),
+            title: "文件".to_string(),
+            sort_order: None,
+            is_collapsed: None,
+            tags: None,
+            initial_content: None,
+        };
+        assert!(matches!(
+            CreateNode::execute(&db, create_input).await,
+            Err(AppError::ValidationError(_))
+        ));
     }
 
     #[tokio::test]
@@ -650,6 +1427,59 @@ mod tests {
         let get_input = IdInput::new(&created.id);
         let result = GetNode::execute(&db, get_input).await.unwrap();
         assert!(result.is_none());
+
+        // 再次删除返回 NotFound
+        assert!(matches!(
+            DeleteNode::execute(&db, IdInput::new(&created.id)).await,
+            Err(AppError::NotFound(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_create_node_with_initial_content() {
+        let db = setup_test_db().await;
+        let workspace_id = create_test_workspace(&db).await;
+
+        let request = |node_type, initial_content: Option<&str>| CreateNodeRequest {
+            workspace_id: workspace_id.clone(),
+            parent_id: None,
+            node_type: Some(node_type),
+            title: "  标题  ".to_string(),
+            sort_order: None,
+            is_collapsed: None,
+            tags: None,
+            initial_content: initial_content.map(str::to_string),
+        };
+
+        // 初始内容按保存内容处理，内容中的标签写回节点
+        let content = r##"{"root":{"children":[{"type":"paragraph","children":[{"type":"text","text":"#[初始]"}]}]}}"##;
+        let node = CreateNode::execute(&db, request(NodeType::File, Some(content)))
+            .await
+            .unwrap();
+        assert_eq!(node.title, "标题");
+        assert_eq!(node.tags, Some(vec!["初始".to_string()]));
+        let saved = content_db_fn::find_by_node_id(&db, &node.id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(saved.content, content);
+
+        // 没有初始内容时为空文档，文件夹没有内容
+        let empty = CreateNode::execute(&db, request(NodeType::File, None))
+            .await
+            .unwrap();
+        let saved = content_db_fn::find_by_node_id(&db, &empty.id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(saved.content, "{}");
+        let folder = CreateNode::execute(&db, request(NodeType::Folder, None))
+            .await
+            .unwrap();
+        assert!(content_db_fn::find_by_node_id(&db, &folder.id)
+            .await
+            .unwrap()
+            .is_none());
     }
 
     #[tokio::test]
@@ -694,5 +1524,168 @@ mod tests {
         // 验证子节点也已删除
         let get_child = IdInput::new(&child.id);
         assert!(GetNode::execute(&db, get_child).await.unwrap().is_none());
+
+        // 子节点和父节点各记录一条删除，子节点在前
+        let deleted: Vec<String> = change_log_db_fn::find_since(&db, 0, 100)
+            .await
+            .unwrap()
+            .into_iter()
+            .filter(|e| e.operation == ChangeOperation::Delete)
+            .map(|e| e.entity_id)
+            .collect();
+        assert_eq!(deleted, vec![child.id, parent.id]);
+    }
+
+    #[tokio::test]
+    async fn test_duplicate_reorder_and_batch_delete() {
+        let db = setup_test_db().await;
+        let workspace_id = create_test_workspace(&db).await;
+        let create = |title: &str| CreateNodeRequest {
+            workspace_id: workspace_id.clone(),
+            parent_id: None,
+            node_type: Some(NodeType::File),
+            title: title.to_string(),
+            sort_order: None,
+            is_collapsed: None,
+            tags: None,
+            initial_content: None,
+        };
+        let a = CreateNode::execute(&db, create("a")).await.unwrap();
+        let b = CreateNode::execute(&db, create("b")).await.unwrap();
+
+        let copy = DuplicateNode::execute(
+            &db,
+            IdWithBodyInput::new(&a.id, DuplicateNodeRequest::default()),
+        )
+        .await
+        .unwrap();
+        assert_eq!(copy.title, "a (副本)");
+        let files =
+            GetNodesByType::execute(&db, NodesByTypeInput::new(&workspace_id, NodeType::File))
+                .await
+                .unwrap();
+        assert_eq!(files.len(), 3);
+
+        ReorderNodes::execute(
+            &db,
+            NodeIdsInput::new(vec![b.id.clone(), copy.id.clone(), a.id.clone()]),
+        )
+        .await
+        .unwrap();
+        let roots = GetNodesByParent::execute(&db, NodesByParentInput::new(&workspace_id, None))
+            .await
+            .unwrap();
+        let titles: Vec<_> = roots.iter().map(|n| n.title.as_str()).collect();
+        assert_eq!(titles, vec!["b", "a (副本)", "a"]);
+
+        // 不存在的节点忽略
+        DeleteNodesBatch::execute(
+            &db,
+            NodeIdsInput::new(vec![a.id.clone(), copy.id.clone(), "missing".to_string()]),
+        )
+        .await
+        .unwrap();
+        let roots = GetNodesByParent::execute(&db, NodesByParentInput::new(&workspace_id, None))
+            .await
+            .unwrap();
+        assert_eq!(roots.len(), 1);
+        assert_eq!(roots[0].id, b.id);
+    }
+
+    /// 写操作成功后按顺序发布变更事件
+    #[tokio::test]
+    async fn test_node_writes_publish_events() {
+        let db = setup_test_db().await;
+        let workspace_id = create_test_workspace(&db).await;
+        // 全局总线被并行测试共享，只看本工作区的事件
+        let mut subscription = crate::events::subscribe();
+
+        let node = CreateNode::execute(
+            &db,
+            CreateNodeRequest {
+                workspace_id: workspace_id.clone(),
+                parent_id: None,
+                node_type: Some(NodeType::File),
+                title: "事件".to_string(),
+                sort_order: None,
+                is_collapsed: None,
+                tags: None,
+                initial_content: None,
+            },
+        )
+        .await
+        .unwrap();
+        UpdateNode::execute(
+            &db,
+            IdWithBodyInput::new(
+                &node.id,
+                UpdateNodeRequest {
+                    parent_id: None,
+                    node_type: None,
+                    title: None,
+                    sort_order: None,
+                    is_collapsed: None,
+                    tags: Some(vec!["a".to_string()]),
+                    rewrite_links: None,
+                },
+            ),
+        )
+        .await
+        .unwrap();
+        MoveNode::execute(
+            &db,
+            IdWithBodyInput::new(
+                &node.id,
+                MoveNodeRequest {
+                    new_parent_id: None,
+                    new_sort_order: 3,
+                    rewrite_links: None,
+                },
+            ),
+        )
+        .await
+        .unwrap();
+        DeleteNode::execute(&db, IdInput::new(&node.id))
+            .await
+            .unwrap();
+
+        let mut received = Vec::new();
+        while received.len() < 5 {
+            let event =
+                tokio::time::timeout(std::time::Duration::from_secs(5), subscription.recv())
+                    .await
+                    .unwrap()
+                    .unwrap();
+            if event.workspace_id() == Some(workspace_id.as_str()) {
+                received.push(event);
+            }
+        }
+
+        let ws = || workspace_id.clone();
+        assert_eq!(
+            received,
+            vec![
+                ChangeEvent::NodeCreated {
+                    workspace_id: ws(),
+                    node_id: node.id.clone(),
+                    parent_id: None,
+                },
+                ChangeEvent::NodeUpdated {
+                    workspace_id: ws(),
+                    node_id: node.id.clone(),
+                },
+                ChangeEvent::TagsChanged { workspace_id: ws() },
+                ChangeEvent::NodeMoved {
+                    workspace_id: ws(),
+                    node_id: node.id.clone(),
+                    parent_id: None,
+                    sort_order: 3,
+                },
+                ChangeEvent::NodeDeleted {
+                    workspace_id: ws(),
+                    node_id: node.id.clone(),
+                },
+            ]
+        );
     }
 }
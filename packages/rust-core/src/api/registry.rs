@@ -0,0 +1,438 @@
+//! 端点注册表
+//!
+//! 所有对外提供的端点在 `api_registry!` 中登记一次，由它生成：
+//!
+//! - Warp 路由（`macros::warp_routes!`）
+//! - Tauri 命令和 `generate_handler!` 列表（`macros::tauri_commands!` / `macros::tauri_handler!`）
+//! - OpenAPI 文档和 `/api` 路由列表（`macros::registry_docs!`）
+//!
+//! ## 条目语法
+//!
+//! ```text
+//! "说明" 端点类型: 方法 "路径" => 命令名(来源 参数名: 类型, ...) -> 构造 Input 的表达式;
+//! "说明" 端点类型 => 命令名(来源 参数名: 类型, ...) -> 构造 Input 的表达式;
+//! ```
+//!
+//! - 参数同时是 Tauri 命令的参数（前端以 camelCase 传入）和 HTTP 请求中取值的位置，
+//!   来源见 `ArgSource`；`Auth` 来源的参数是请求的认证身份，不是 Tauri 命令的参数
+//! - HTTP 中的键与参数名不同时写作 `来源("键") 参数名: 类型`，如 `Param("q") query: String`
+//! - 路径参数写作 `:name`，`name` 与 `Path` 来源的参数名相同
+//! - 省略方法和路径的条目只生成 Tauri 命令
+//! - 条目中的类型在 `prelude` 中解析
+
+/// 参数在 HTTP 请求中的来源
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArgSource {
+    /// 路径参数（百分号解码）
+    Path,
+    /// 整个查询字符串（反序列化为查询结构体，`Option<T>` 总是 Some）
+    Query,
+    /// 单个查询参数，键为参数名的 camelCase（缺省、空串和 `null` 视为 null）
+    Param,
+    /// 整个 JSON 请求体
+    Body,
+    /// JSON 请求体对象的一个字段，键为参数名的 camelCase
+    Field,
+    /// 请求的认证身份（`Option<AuthUser>`，未启用认证时为 None）；Tauri 命令没有这个参数，总是 None
+    Auth,
+}
+
+/// 参数在查询字符串或请求体中的键，如 `parent_id` 为 `parentId`
+pub fn arg_key(name: &str) -> String {
+    let mut key = String::with_capacity(name.len());
+    let mut upper = false;
+    for c in name.chars() {
+        if c == '_' {
+            upper = true;
+        } else if upper {
+            key.extend(c.to_uppercase());
+            upper = false;
+        } else {
+            key.push(c);
+        }
+    }
+    key
+}
+
+/// 注册表条目中使用的类型
+///
+/// 生成代码的宏在自己的作用域中引入；生成 Tauri 命令的模块需要 `use` 它。
+pub mod prelude {
+    pub use crate::api::*;
+    pub use crate::types::log::ClearOldLogsRequest;
+    pub use crate::{
+        AttachmentType, AuthUser, ChangesSinceQuery, CreateAttachmentRequest,
+        CreateLogEntryRequest, CreateNodeRequest, CreateSavedSearchRequest, CreateTagRequest,
+        CreateUserRequest, CreateWorkspaceRequest, DiaryCalendarQuery, DiaryOnThisDayQuery,
+        DuplicateNodeRequest, GetOrCreateDiaryEntryRequest, GetOrCreateTagRequest,
+        ImportLedgerCsvRequest, LedgerBalancesQuery, LedgerReportQuery, LogQueryOptions,
+        MergeTagsRequest, MoveNodeRequest, NodeGraphQuery, NodeListQuery, NodeType,
+        RenameTagRequest, ResolveTagQuery, SaveContentRequest, SearchNodesQuery, SearchTagsQuery,
+        SetWorkspaceMemberRequest, SetWritingGoalRequest, SyncPullQuery, SyncPushRequest,
+        SyncRequest, TagNodesQuery, TaskQuery, ToggleTaskRequest, TopTagsQuery,
+        UpdateAttachmentRequest, UpdateNodeRequest, UpdateSavedSearchRequest,
+        UpdateTagMetadataRequest, UpdateTagRequest, UpdateUserRequest, UpdateWorkspaceRequest,
+        WritingProgressQuery, WritingStatsQuery,
+    };
+}
+
+/// 端点注册表
+///
+/// 以条目列表调用 `$callback`（`macros` 中的生成宏），附加参数原样放在列表前的 `[]` 中：
+///
+/// ```rust,ignore
+/// let routes = api_registry!(warp_routes, db, auth);
+/// api_registry!(tauri_commands);
+/// ```
+#[macro_export]
+macro_rules! api_registry {
+    ($callback:ident $(, $($extra:tt)*)?) => {
+        $crate::$callback! {
+            [$($($extra)*)?]
+
+            // ---------------------------------------------------------------- 工作区
+            "获取工作区（启用认证时只返回当前用户所属的工作区）" GetWorkspaces: GET "/api/workspaces"
+                => get_workspaces(Auth auth: Option<AuthUser>) -> auth;
+            "获取单个工作区" GetWorkspace: GET "/api/workspaces/:id"
+                => get_workspace(Path id: String) -> IdInput::new(id);
+            "创建工作区（启用认证时当前用户成为 owner）" CreateWorkspace: POST "/api/workspaces"
+                => create_workspace(Auth auth: Option<AuthUser>, Body request: CreateWorkspaceRequest)
+                -> OptionalAuthWithBodyInput::new(auth, request);
+            "更新工作区" UpdateWorkspace: PUT "/api/workspaces/:id"
+                => update_workspace(Path id: String, Body request: UpdateWorkspaceRequest)
+                -> IdWithBodyInput::new(id, request);
+            "删除工作区" DeleteWorkspace: DELETE "/api/workspaces/:id"
+                => delete_workspace(Path id: String) -> IdInput::new(id);
+
+            // ---------------------------------------------------------------- 成员
+            "工作区的成员列表" GetWorkspaceMembers: GET "/api/workspaces/:workspace_id/members"
+                => get_workspace_members(Path workspace_id: String)
+                -> WorkspaceIdInput::new(workspace_id);
+            "添加成员或修改成员角色" SetWorkspaceMember: PUT "/api/workspaces/:workspace_id/members"
+                => set_workspace_member(
+                    Path workspace_id: String,
+                    Body request: SetWorkspaceMemberRequest
+                ) -> IdWithBodyInput::new(workspace_id, request);
+            "移除成员" RemoveWorkspaceMember: DELETE "/api/workspaces/:workspace_id/members/:user_id"
+                => remove_workspace_member(Path workspace_id: String, Path user_id: String)
+                -> WorkspaceMemberInput::new(workspace_id, user_id);
+
+            // ---------------------------------------------------------------- 节点
+            "获取工作区的节点（可按类型或父节点筛选）" GetNodesByWorkspace: GET "/api/workspaces/:workspace_id/nodes"
+                => get_nodes_by_workspace(Path workspace_id: String, Query query: Option<NodeListQuery>)
+                -> IdWithBodyInput::new(workspace_id, query.unwrap_or_default());
+            "获取根节点" GetRootNodes: GET "/api/workspaces/:workspace_id/nodes/root"
+                => get_root_nodes(Path workspace_id: String) -> WorkspaceIdInput::new(workspace_id);
+            "按父节点获取节点" GetNodesByParent
+                => get_nodes_by_parent(Path workspace_id: String, Param parent_id: Option<String>)
+                -> NodesByParentInput::new(workspace_id, parent_id);
+            "按类型获取节点" GetNodesByType
+                => get_nodes_by_type(Path workspace_id: String, Param node_type: NodeType)
+                -> NodesByTypeInput::new(workspace_id, node_type);
+            "获取下一个排序顺序" GetNextSortOrder: GET "/api/workspaces/:workspace_id/nodes/next-sort-order"
+                => get_next_sort_order(Path workspace_id: String, Param parent_id: Option<String>)
+                -> NextSortOrderInput::new(workspace_id, parent_id);
+            "获取嵌套节点树" GetNodeTree: GET "/api/workspaces/:workspace_id/nodes/tree"
+                => get_node_tree(
+                    Path workspace_id: String,
+                    Param root_id: Option<String>,
+                    Param max_depth: Option<u32>
+                ) -> NodeTreeInput::new(workspace_id, root_id, max_depth);
+            "获取单个节点" GetNode: GET "/api/nodes/:id"
+                => get_node(Path id: String) -> IdInput::new(id);
+            "获取子节点" GetChildNodes: GET "/api/nodes/:parent_id/children"
+                => get_child_nodes(Path parent_id: String) -> ParentIdInput::new(parent_id);
+            "获取所有后代节点" GetDescendants: GET "/api/nodes/:node_id/descendants"
+                => get_descendants(Path node_id: String) -> NodeIdInput::new(node_id);
+            "创建节点" CreateNode: POST "/api/nodes"
+                => create_node(Body request: CreateNodeRequest) -> request;
+            "更新节点" UpdateNode: PUT "/api/nodes/:id"
+                => update_node(Path id: String, Body request: UpdateNodeRequest)
+                -> IdWithBodyInput::new(id, request);
+            "移动节点" MoveNode: PUT "/api/nodes/:id/move"
+                => move_node(Path id: String, Body request: MoveNodeRequest)
+                -> IdWithBodyInput::new(id, request);
+            "复制节点（包括内容）" DuplicateNode: POST "/api/nodes/:id/duplicate"
+                => duplicate_node(Path id: String, Field new_title: Option<String>)
+                -> IdWithBodyInput::new(id, DuplicateNodeRequest { new_title });
+            "按给定顺序重排节点" ReorderNodes: PUT "/api/nodes/reorder"
+                => reorder_nodes(Field node_ids: Vec<String>) -> NodeIdsInput::new(node_ids);
+            "删除节点" DeleteNode: DELETE "/api/nodes/:id"
+                => delete_node(Path id: String) -> IdInput::new(id);
+            "批量删除节点" DeleteNodesBatch: DELETE "/api/nodes/batch"
+                => delete_nodes_batch(Field node_ids: Vec<String>) -> NodeIdsInput::new(node_ids);
+            "事务创建节点和内容" CreateNodeWithContent: POST "/api/nodes/with-content"
+                => create_node_with_content(Body request: CreateNodeWithContentRequest) -> request;
+            "事务删除节点及后代" DeleteNodeRecursive: DELETE "/api/nodes/:id/recursive"
+                => delete_node_recursive(Path id: String) -> IdInput::new(id);
+
+            // ---------------------------------------------------------------- 内容
+            "获取节点内容" GetContent: GET "/api/nodes/:node_id/content"
+                => get_content(Path node_id: String) -> NodeIdInput::new(node_id);
+            "获取内容版本号" GetContentVersion: GET "/api/nodes/:node_id/content/version"
+                => get_content_version(Path node_id: String) -> NodeIdInput::new(node_id);
+            "保存内容（创建或更新）" SaveContent: POST "/api/contents"
+                => save_content(Body request: SaveContentRequest) -> request;
+            "基于旧版本保存，版本冲突时三方合并" MergeContent: POST "/api/contents/merge"
+                => merge_content(Body request: SaveContentRequest) -> request;
+            "压缩协同编辑更新并写入内容" CompactContentUpdates: POST "/api/nodes/:node_id/collab/compact"
+                => compact_content_updates(Path node_id: String) -> NodeIdInput::new(node_id);
+
+            // ---------------------------------------------------------------- 链接
+            "获取引用该节点的节点" GetBacklinks: GET "/api/nodes/:node_id/backlinks"
+                => get_backlinks(Path node_id: String) -> NodeIdInput::new(node_id);
+            "获取该节点引用的节点" GetOutgoingLinks: GET "/api/nodes/:node_id/links"
+                => get_outgoing_links(Path node_id: String) -> NodeIdInput::new(node_id);
+            "获取工作区内的断链" GetBrokenLinks: GET "/api/workspaces/:workspace_id/links/broken"
+                => get_broken_links(Path workspace_id: String) -> WorkspaceIdInput::new(workspace_id);
+            "从内容重建链接索引" RebuildLinks: POST "/api/workspaces/:workspace_id/links/rebuild"
+                => rebuild_links(Path workspace_id: String) -> WorkspaceIdInput::new(workspace_id);
+
+            // ---------------------------------------------------------------- 图谱
+            "获取节点知识图谱" GetNodeGraph: GET "/api/workspaces/:workspace_id/graph"
+                => get_node_graph(Path workspace_id: String, Query query: Option<NodeGraphQuery>)
+                -> IdWithBodyInput::new(workspace_id, query.unwrap_or_default());
+
+            // ---------------------------------------------------------------- 标签
+            "获取工作区所有标签" GetTags: GET "/api/workspaces/:workspace_id/tags"
+                => get_tags_by_workspace(Path workspace_id: String)
+                -> WorkspaceIdInput::new(workspace_id);
+            "获取层级标签树" GetTagTree: GET "/api/workspaces/:workspace_id/tags/tree"
+                => get_tag_tree(Path workspace_id: String) -> WorkspaceIdInput::new(workspace_id);
+            "按名称或别名查找标签" ResolveTag: GET "/api/workspaces/:workspace_id/tags/resolve"
+                => resolve_tag(Path workspace_id: String, Param name: String)
+                -> IdWithBodyInput::new(workspace_id, ResolveTagQuery { name });
+            "更新标签颜色、图标、描述和别名" UpdateTagMetadata: PUT "/api/workspaces/:workspace_id/tags/metadata"
+                => update_tag_metadata(
+                    Path workspace_id: String,
+                    Body request: UpdateTagMetadataRequest
+                ) -> IdWithBodyInput::new(workspace_id, request);
+            "重命名标签" RenameTag: POST "/api/workspaces/:workspace_id/tags/rename"
+                => rename_tag(Path workspace_id: String, Body request: RenameTagRequest)
+                -> IdWithBodyInput::new(workspace_id, request);
+            "合并标签" MergeTags: POST "/api/workspaces/:workspace_id/tags/merge"
+                => merge_tags(Path workspace_id: String, Body request: MergeTagsRequest)
+                -> IdWithBodyInput::new(workspace_id, request);
+            "按 ID 获取标签" GetTag: GET "/api/tags/:id"
+                => get_tag(Path id: String) -> IdInput::new(id);
+            "按名称获取标签" GetTagByName: GET "/api/workspaces/:workspace_id/tags/by-name/:name"
+                => get_tag_by_name(Path workspace_id: String, Path name: String)
+                -> IdWithBodyInput::new(workspace_id, ResolveTagQuery { name });
+            "获取最常用标签" GetTopTags: GET "/api/workspaces/:workspace_id/tags/top"
+                => get_top_tags(Path workspace_id: String, Param limit: u64)
+                -> IdWithBodyInput::new(workspace_id, TopTagsQuery { limit });
+            "搜索标签" SearchTags: GET "/api/workspaces/:workspace_id/tags/search"
+                => search_tags(Path workspace_id: String, Param("q") query: String)
+                -> IdWithBodyInput::new(workspace_id, SearchTagsQuery { query });
+            "获取带有标签的节点" GetNodesByTag: GET "/api/workspaces/:workspace_id/tags/:tag_name/nodes"
+                => get_nodes_by_tag(Path workspace_id: String, Path tag_name: String)
+                -> IdWithBodyInput::new(workspace_id, TagNodesQuery { tag_name });
+            "获取标签关系图" GetTagGraphData: GET "/api/workspaces/:workspace_id/tags/graph"
+                => get_tag_graph_data(Path workspace_id: String)
+                -> WorkspaceIdInput::new(workspace_id);
+            "创建标签" CreateTag: POST "/api/tags"
+                => create_tag(Body request: CreateTagRequest) -> request;
+            "获取或创建标签" GetOrCreateTag: POST "/api/workspaces/:workspace_id/tags/get-or-create"
+                => get_or_create_tag(Path workspace_id: String, Field name: String)
+                -> IdWithBodyInput::new(workspace_id, GetOrCreateTagRequest { name });
+            "更新标签" UpdateTag: PUT "/api/tags/:id"
+                => update_tag(Path id: String, Body request: UpdateTagRequest)
+                -> IdWithBodyInput::new(id, request);
+            "标签计数加一" IncrementTagCount: POST "/api/tags/:id/increment"
+                => increment_tag_count(Path id: String) -> IdInput::new(id);
+            "标签计数减一" DecrementTagCount: POST "/api/tags/:id/decrement"
+                => decrement_tag_count(Path id: String) -> IdInput::new(id);
+            "删除标签" DeleteTag: DELETE "/api/tags/:id"
+                => delete_tag(Path id: String) -> IdInput::new(id);
+            "删除工作区所有标签" DeleteTagsByWorkspace: DELETE "/api/workspaces/:workspace_id/tags"
+                => delete_tags_by_workspace(Path workspace_id: String)
+                -> WorkspaceIdInput::new(workspace_id);
+            "从节点同步标签表" SyncTagCache: POST "/api/workspaces/:workspace_id/tags/sync"
+                => sync_tag_cache(Path workspace_id: String) -> WorkspaceIdInput::new(workspace_id);
+            "重建标签表" RebuildTagCache: POST "/api/workspaces/:workspace_id/tags/rebuild"
+                => rebuild_tag_cache(Path workspace_id: String)
+                -> WorkspaceIdInput::new(workspace_id);
+            "重新计算标签计数" RecalculateTagCounts: POST "/api/workspaces/:workspace_id/tags/recalculate"
+                => recalculate_tag_counts(Path workspace_id: String)
+                -> WorkspaceIdInput::new(workspace_id);
+
+            // ---------------------------------------------------------------- 搜索
+            "获取工作区的保存的搜索" GetSavedSearches: GET "/api/workspaces/:workspace_id/saved-searches"
+                => get_saved_searches(Path workspace_id: String)
+                -> WorkspaceIdInput::new(workspace_id);
+            "创建保存的搜索" CreateSavedSearch: POST "/api/saved-searches"
+                => create_saved_search(Body request: CreateSavedSearchRequest) -> request;
+            "更新保存的搜索" UpdateSavedSearch: PUT "/api/saved-searches/:id"
+                => update_saved_search(Path id: String, Body request: UpdateSavedSearchRequest)
+                -> IdWithBodyInput::new(id, request);
+            "删除保存的搜索" DeleteSavedSearch: DELETE "/api/saved-searches/:id"
+                => delete_saved_search(Path id: String) -> IdInput::new(id);
+            "执行保存的搜索" RunSavedQuery: GET "/api/saved-searches/:id/run"
+                => run_saved_query(Path id: String) -> IdInput::new(id);
+            "执行临时查询" SearchNodes: GET "/api/workspaces/:workspace_id/search"
+                => search_nodes(Path workspace_id: String, Param q: String)
+                -> IdWithBodyInput::new(workspace_id, SearchNodesQuery { q });
+
+            // ---------------------------------------------------------------- 日记
+            "获取或创建某天的日记" GetOrCreateDiaryEntry: POST "/api/workspaces/:workspace_id/diary/entries"
+                => get_or_create_diary_entry(
+                    Path workspace_id: String,
+                    Body request: GetOrCreateDiaryEntryRequest
+                ) -> IdWithBodyInput::new(workspace_id, request);
+            "日期范围内有日记的日期和字数" GetDiaryCalendar: GET "/api/workspaces/:workspace_id/diary/calendar"
+                => get_diary_calendar(Path workspace_id: String, Param from: String, Param to: String)
+                -> IdWithBodyInput::new(workspace_id, DiaryCalendarQuery { from, to });
+            "往年同日的日记" GetDiaryOnThisDay: GET "/api/workspaces/:workspace_id/diary/on-this-day"
+                => get_diary_on_this_day(Path workspace_id: String, Param date: String)
+                -> IdWithBodyInput::new(workspace_id, DiaryOnThisDayQuery { date });
+
+            // ---------------------------------------------------------------- 任务
+            "查询工作区的任务" QueryTasks: GET "/api/workspaces/:workspace_id/tasks"
+                => query_tasks(Path workspace_id: String, Query query: TaskQuery)
+                -> IdWithBodyInput::new(workspace_id, query);
+            "切换任务勾选状态" ToggleTask: POST "/api/tasks/:id/toggle"
+                => toggle_task(Path id: String, Body request: ToggleTaskRequest)
+                -> IdWithBodyInput::new(id, request);
+            "从内容重建任务索引" RebuildTasks: POST "/api/workspaces/:workspace_id/tasks/rebuild"
+                => rebuild_tasks(Path workspace_id: String) -> WorkspaceIdInput::new(workspace_id);
+
+            // ---------------------------------------------------------------- 记账
+            "账户余额" GetLedgerBalances: GET "/api/workspaces/:workspace_id/ledger/balances"
+                => get_ledger_balances(Path workspace_id: String, Query query: LedgerBalancesQuery)
+                -> IdWithBodyInput::new(workspace_id, query);
+            "按账户/月份汇总" GetLedgerReport: GET "/api/workspaces/:workspace_id/ledger/report"
+                => get_ledger_report(Path workspace_id: String, Query query: LedgerReportQuery)
+                -> IdWithBodyInput::new(workspace_id, query);
+            "解析单个记账节点并返回诊断信息" CheckLedger: GET "/api/nodes/:node_id/ledger"
+                => check_ledger(Path node_id: String) -> NodeIdInput::new(node_id);
+            "导入银行 CSV 流水" ImportLedgerCsv: POST "/api/nodes/:node_id/ledger/import"
+                => import_ledger_csv(Path node_id: String, Body request: ImportLedgerCsvRequest)
+                -> IdWithBodyInput::new(node_id, request);
+
+            // ---------------------------------------------------------------- 写作
+            "按文档、文件夹和工作区汇总写作统计" GetWritingStats: GET "/api/workspaces/:workspace_id/writing/stats"
+                => get_writing_stats(Path workspace_id: String, Query query: WritingStatsQuery)
+                -> IdWithBodyInput::new(workspace_id, query);
+            "目标进度、连续天数和热力图" GetWritingProgress: GET "/api/workspaces/:workspace_id/writing/progress"
+                => get_writing_progress(Path workspace_id: String, Query query: WritingProgressQuery)
+                -> IdWithBodyInput::new(workspace_id, query);
+            "设置工作区或节点的写作目标" SetWritingGoal: PUT "/api/workspaces/:workspace_id/writing/goals"
+                => set_writing_goal(Path workspace_id: String, Body request: SetWritingGoalRequest)
+                -> IdWithBodyInput::new(workspace_id, request);
+            "删除写作目标" DeleteWritingGoal: DELETE "/api/writing/goals/:id"
+                => delete_writing_goal(Path id: String) -> IdInput::new(id);
+
+            // ---------------------------------------------------------------- 变更和同步
+            "查询序号之后的变更（已压缩）" GetChangesSince: GET "/api/changes"
+                => get_changes_since(Auth auth: Option<AuthUser>, Query query: ChangesSinceQuery)
+                -> OptionalAuthWithBodyInput::new(auth, query);
+            "删除被取代的变更记录" CompactChangeLog: POST "/api/changes/compact"
+                => compact_change_log() -> ();
+            "拉取序号之后的变更（含数据）" SyncPull: GET "/api/sync/pull"
+                => sync_pull(Auth auth: Option<AuthUser>, Query query: SyncPullQuery)
+                -> OptionalAuthWithBodyInput::new(auth, query);
+            "推送本地变更，返回每条的结果（启用认证时新建的工作区归推送者所有）" SyncPush: POST "/api/sync/push"
+                => sync_push(Auth auth: Option<AuthUser>, Body request: SyncPushRequest)
+                -> OptionalAuthWithBodyInput::new(auth, request);
+            "与远程服务器双向同步" SyncWithServer
+                => sync_with_server(Body request: SyncRequest) -> request;
+
+            // ---------------------------------------------------------------- 用户
+            "获取所有用户" GetUsers: GET "/api/users"
+                => get_users() -> ();
+            "获取当前用户" GetCurrentLocalUser: GET "/api/users/current"
+                => get_current_user() -> ();
+            "获取单个用户" GetUser: GET "/api/users/:id"
+                => get_user(Path id: String) -> IdInput::new(id);
+            "按用户名获取用户" GetUserByUsername: GET "/api/users/by-username/:username"
+                => get_user_by_username(Path username: String) -> UsernameInput::new(username);
+            "按邮箱获取用户" GetUserByEmail: GET "/api/users/by-email/:email"
+                => get_user_by_email(Path email: String) -> EmailInput::new(email);
+            "创建用户" CreateUser: POST "/api/users"
+                => create_user(Body request: CreateUserRequest) -> request;
+            "更新用户" UpdateUser: PUT "/api/users/:id"
+                => update_user(Path id: String, Body request: UpdateUserRequest)
+                -> IdWithBodyInput::new(id, request);
+            "更新最后登录时间" UpdateUserLastLogin: PUT "/api/users/:id/last-login"
+                => update_user_last_login(Path id: String) -> IdInput::new(id);
+            "删除用户" DeleteUser: DELETE "/api/users/:id"
+                => delete_user(Path id: String) -> IdInput::new(id);
+
+            // ---------------------------------------------------------------- 附件
+            "获取所有附件" GetAttachments: GET "/api/attachments"
+                => get_attachments(Auth auth: Option<AuthUser>) -> auth;
+            "获取单个附件" GetAttachment: GET "/api/attachments/:id"
+                => get_attachment(Path id: String) -> IdInput::new(id);
+            "按文件路径获取附件" GetAttachmentByPath: GET "/api/attachments/by-path/:file_path"
+                => get_attachment_by_path(Auth auth: Option<AuthUser>, Path file_path: String)
+                -> OptionalAuthWithBodyInput::new(auth, FilePathInput::new(file_path));
+            "获取项目的附件（可按类型筛选）" GetAttachmentsByProject: GET "/api/projects/:project_id/attachments"
+                => get_attachments_by_project(
+                    Path project_id: String,
+                    Param("type") attachment_type: Option<AttachmentType>
+                ) -> AttachmentsByProjectInput::new(project_id, attachment_type);
+            "按类型获取项目的附件" GetAttachmentsByType
+                => get_attachments_by_type(
+                    Path project_id: String,
+                    Param attachment_type: AttachmentType
+                ) -> AttachmentsByTypeInput::new(project_id, attachment_type);
+            "获取项目的图片" GetImagesByProject
+                => get_images_by_project(Path project_id: String) -> ProjectIdInput::new(project_id);
+            "获取项目的音频" GetAudioFilesByProject
+                => get_audio_files_by_project(Path project_id: String)
+                -> ProjectIdInput::new(project_id);
+            "创建附件" CreateAttachment: POST "/api/attachments"
+                => create_attachment(Body request: CreateAttachmentRequest) -> request;
+            "更新附件" UpdateAttachment: PUT "/api/attachments/:id"
+                => update_attachment(Path id: String, Body request: UpdateAttachmentRequest)
+                -> IdWithBodyInput::new(id, request);
+            "删除附件" DeleteAttachment: DELETE "/api/attachments/:id"
+                => delete_attachment(Path id: String) -> IdInput::new(id);
+            "删除项目的所有附件" DeleteAttachmentsByProject: DELETE "/api/projects/:project_id/attachments"
+                => delete_attachments_by_project(Path project_id: String)
+                -> ProjectIdInput::new(project_id);
+
+            // ---------------------------------------------------------------- 日志
+            "初始化日志表" InitLogDatabase: POST "/api/logs/init"
+                => init_log_database() -> ();
+            "检查日志表是否存在" CheckLogDatabaseExists: GET "/api/logs/exists"
+                => check_log_database_exists() -> ();
+            "保存单个日志条目" SaveLogEntry: POST "/api/logs"
+                => save_log_entry(Body entry: CreateLogEntryRequest) -> entry;
+            "批量保存日志条目" SaveLogsBatch: POST "/api/logs/batch"
+                => save_logs_batch(Body entries: Vec<CreateLogEntryRequest>) -> entries;
+            "查询日志条目" QueryLogs: POST "/api/logs/query"
+                => query_logs(Body options: LogQueryOptions) -> options;
+            "获取日志统计" GetLogStats: GET "/api/logs/stats"
+                => get_log_stats() -> ();
+            "清理指定日期之前的日志" ClearOldLogs: DELETE "/api/logs"
+                => clear_old_logs(Param before_date: String)
+                -> ClearOldLogsRequest { before_date };
+            "清理所有日志" ClearAllLogs: DELETE "/api/logs/all"
+                => clear_all_logs() -> ();
+
+            // ---------------------------------------------------------------- 清除数据
+            "清除所有数据（keepUsers 为 true 时保留用户）" ClearAllData: DELETE "/api/data/clear"
+                => clear_sqlite_data(Param keep_users: Option<bool>)
+                -> ClearDataInput::new(keep_users.unwrap_or_default());
+            "清除数据但保留用户" ClearAllData
+                => clear_sqlite_data_keep_users() -> ClearDataInput::new(true);
+        }
+    };
+}
+
+// ============================================================================
+// 测试
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_arg_key() {
+        assert_eq!(arg_key("id"), "id");
+        assert_eq!(arg_key("parent_id"), "parentId");
+        assert_eq!(arg_key("before_date"), "beforeDate");
+    }
+}
@@ -0,0 +1,408 @@
+//! Search API 端点
+//!
+//! 搜索查询语言与保存的搜索（虚拟文件夹）相关的 API 端点实现。
+//! 查询语法见 `fn::search`。
+//!
+//! ## 端点列表
+//!
+//! | 端点 | 方法 | 路径 | 说明 |
+//! |------|------|------|------|
+//! | GetSavedSearches | GET | /api/workspaces/:id/saved-searches | 获取工作区的保存的搜索 |
+//! | CreateSavedSearch | POST | /api/saved-searches | 创建保存的搜索 |
+//! | UpdateSavedSearch | PUT | /api/saved-searches/:id | 更新保存的搜索 |
+//! | DeleteSavedSearch | DELETE | /api/saved-searches/:id | 删除保存的搜索 |
+//! | RunSavedQuery | GET | /api/saved-searches/:id/run | 执行保存的搜索 |
+//! | SearchNodes | GET | /api/workspaces/:id/search?q= | 执行临时查询 |
+
+use sea_orm::DatabaseConnection;
+
+use super::{Access, ApiEndpoint, IdInput, IdWithBodyInput, NoOutput, WorkspaceIdInput};
+use crate::db::{search_db_fn, workspace_db_fn};
+use crate::r#fn::search::{parse_search_query, SearchQuery};
+use crate::types::member::WorkspaceRole;
+use crate::types::node::NodeResponse;
+use crate::types::search::{
+    CreateSavedSearchRequest, SavedSearchResponse, SearchNodesQuery, UpdateSavedSearchRequest,
+};
+use crate::{AppError, AppResult};
+
+/// 解析查询语句，语法错误转为 ValidationError
+fn parse_query(query: &str) -> AppResult<SearchQuery> {
+    parse_search_query(query).map_err(|e| AppError::validation(format!("查询语句无效: {}", e)))
+}
+
+/// 校验并规范化名称
+fn validate_name(name: &str) -> AppResult<String> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err(AppError::validation("搜索名称不能为空"));
+    }
+    Ok(name.to_string())
+}
+
+// ============================================================================
+// GetSavedSearches - 获取工作区的保存的搜索
+// ============================================================================
+
+/// 获取工作区的所有保存的搜索（按排序顺序）
+///
+/// ## HTTP
+/// - Method: GET
+/// - Path: /api/workspaces/:workspace_id/saved-searches
+///
+/// ## Tauri
+/// - Command: get_saved_searches
+///
+/// ## 参数
+/// - workspace_id: 工作区 ID
+///
+/// ## 返回
+/// - 成功: Vec<SavedSearchResponse>
+/// - 失败: DatabaseError
+pub struct GetSavedSearches;
+
+impl ApiEndpoint for GetSavedSearches {
+    type Input = WorkspaceIdInput;
+    type Output = Vec<SavedSearchResponse>;
+    const NAME: &'static str = "get_saved_searches";
+
+    fn access(input: &Self::Input) -> Access {
+        Access::Workspace(input.workspace_id.clone(), WorkspaceRole::Viewer)
+    }
+
+    async fn execute(db: &DatabaseConnection, input: Self::Input) -> AppResult<Self::Output> {
+        search_db_fn::find_by_workspace(db, &input.workspace_id)
+            .await
+            .map(|searches| searches.into_iter().map(Into::into).collect())
+    }
+}
+
+// ============================================================================
+// CreateSavedSearch - 创建保存的搜索
+// ============================================================================
+
+/// 创建保存的搜索
+///
+/// ## HTTP
+/// - Method: POST
+/// - Path: /api/saved-searches
+/// - Body: CreateSavedSearchRequest
+///
+/// ## Tauri
+/// - Command: create_saved_search
+///
+/// ## 参数
+/// - workspace_id: 所属工作区 ID
+/// - name: 显示名称（必填）
+/// - query: 查询语句（必须能解析）
+///
+/// ## 返回
+/// - 成功: SavedSearchResponse
+/// - 失败: NotFound（工作区不存在）, ValidationError, DatabaseError
+pub struct CreateSavedSearch;
+
+impl ApiEndpoint for CreateSavedSearch {
+    type Input = CreateSavedSearchRequest;
+    type Output = SavedSearchResponse;
+    const NAME: &'static str = "create_saved_search";
+
+    fn access(input: &Self::Input) -> Access {
+        Access::Workspace(input.workspace_id.clone(), WorkspaceRole::Editor)
+    }
+
+    async fn execute(db: &DatabaseConnection, input: Self::Input) -> AppResult<Self::Output> {
+        let name = validate_name(&input.name)?;
+        parse_query(&input.query)?;
+        workspace_db_fn::find_by_id(db, &input.workspace_id)
+            .await?
+            .ok_or_else(|| AppError::not_found(format!("Workspace {}", input.workspace_id)))?;
+
+        let id = uuid::Uuid::new_v4().to_string();
+        search_db_fn::create(
+            db,
+            id,
+            input.workspace_id,
+            name,
+            input.query.trim().to_string(),
+        )
+        .await
+        .map(Into::into)
+    }
+}
+
+// ============================================================================
+// UpdateSavedSearch - 更新保存的搜索
+// ============================================================================
+
+/// 更新保存的搜索
+///
+/// ## HTTP
+/// - Method: PUT
+/// - Path: /api/saved-searches/:id
+/// - Body: UpdateSavedSearchRequest
+///
+/// ## Tauri
+/// - Command: update_saved_search
+///
+/// ## 参数
+/// - id: 搜索 ID（路径参数）
+/// - name: 新名称（可选）
+/// - query: 新查询语句（可选，必须能解析）
+/// - sort_order: 新排序顺序（可选）
+///
+/// ## 返回
+/// - 成功: SavedSearchResponse
+/// - 失败: NotFound, ValidationError, DatabaseError
+pub struct UpdateSavedSearch;
+
+impl ApiEndpoint for UpdateSavedSearch {
+    type Input = IdWithBodyInput<UpdateSavedSearchRequest>;
+    type Output = SavedSearchResponse;
+    const NAME: &'static str = "update_saved_search";
+
+    fn access(input: &Self::Input) -> Access {
+        Access::SavedSearch(input.id.clone(), WorkspaceRole::Editor)
+    }
+
+    async fn execute(db: &DatabaseConnection, input: Self::Input) -> AppResult<Self::Output> {
+        let body = input.body;
+        let name = body.name.as_deref().map(validate_name).transpose()?;
+        let query = match body.query {
+            Some(query) => {
+                parse_query(&query)?;
+                Some(query.trim().to_string())
+            }
+            None => None,
+        };
+
+        search_db_fn::update(db, &input.id, name, query, body.sort_order)
+            .await
+            .map(Into::into)
+    }
+}
+
+// ============================================================================
+// DeleteSavedSearch - 删除保存的搜索
+// ============================================================================
+
+/// 删除保存的搜索
+///
+/// ## HTTP
+/// - Method: DELETE
+/// - Path: /api/saved-searches/:id
+///
+/// ## Tauri
+/// - Command: delete_saved_search
+///
+/// ## 参数
+/// - id: 搜索 ID
+///
+/// ## 返回
+/// - 成功: ()
+/// - 失败: NotFound, DatabaseError
+pub struct DeleteSavedSearch;
+
+impl ApiEndpoint for DeleteSavedSearch {
+    type Input = IdInput;
+    type Output = NoOutput;
+    const NAME: &'static str = "delete_saved_search";
+
+    fn access(input: &Self::Input) -> Access {
+        Access::SavedSearch(input.id.clone(), WorkspaceRole::Editor)
+    }
+
+    async fn execute(db: &DatabaseConnection, input: Self::Input) -> AppResult<Self::Output> {
+        search_db_fn::delete(db, &input.id).await
+    }
+}
+
+// ============================================================================
+// RunSavedQuery - 执行保存的搜索
+// ============================================================================
+
+/// 执行保存的搜索，返回虚拟文件夹中的节点
+///
+/// ## HTTP
+/// - Method: GET
+/// - Path: /api/saved-searches/:id/run
+///
+/// ## Tauri
+/// - Command: run_saved_query
+///
+/// ## 参数
+/// - id: 搜索 ID
+///
+/// ## 返回
+/// - 成功: Vec<NodeResponse>（按更新时间倒序）
+/// - 失败: NotFound, ValidationError（保存的查询已无法解析）, DatabaseError
+pub struct RunSavedQuery;
+
+impl ApiEndpoint for RunSavedQuery {
+    type Input = IdInput;
+    type Output = Vec<NodeResponse>;
+    const NAME: &'static str = "run_saved_query";
+
+    fn access(input: &Self::Input) -> Access {
+        Access::SavedSearch(input.id.clone(), WorkspaceRole::Viewer)
+    }
+
+    async fn execute(db: &DatabaseConnection, input: Self::Input) -> AppResult<Self::Output> {
+        let saved = search_db_fn::find_by_id(db, &input.id)
+            .await?
+            .ok_or_else(|| AppError::not_found(format!("SavedSearch {}", input.id)))?;
+
+        let query = parse_query(&saved.query)?;
+        search_db_fn::run_query(db, &saved.workspace_id, query)
+            .await
+            .map(|nodes| nodes.into_iter().map(Into::into).collect())
+    }
+}
+
+// ============================================================================
+// SearchNodes - 执行临时查询
+// ============================================================================
+
+/// 在工作区内执行临时查询（不保存）
+///
+/// ## HTTP
+/// - Method: GET
+/// - Path: /api/workspaces/:workspace_id/search?q=
+///
+/// ## Tauri
+/// - Command: search_nodes
+///
+/// ## 参数
+/// - id: 工作区 ID
+/// - q: 查询语句
+///
+/// ## 返回
+/// - 成功: Vec<NodeResponse>（按更新时间倒序）
+/// - 失败: ValidationError, DatabaseError
+pub struct SearchNodes;
+
+impl ApiEndpoint for SearchNodes {
+    type Input = IdWithBodyInput<SearchNodesQuery>;
+    type Output = Vec<NodeResponse>;
+    const NAME: &'static str = "search_nodes";
+
+    fn access(input: &Self::Input) -> Access {
+        Access::Workspace(input.id.clone(), WorkspaceRole::Viewer)
+    }
+
+    async fn execute(db: &DatabaseConnection, input: Self::Input) -> AppResult<Self::Output> {
+        let query = parse_query(&input.body.q)?;
+        search_db_fn::run_query(db, &input.id, query)
+            .await
+            .map(|nodes| nodes.into_iter().map(Into::into).collect())
+    }
+}
+
+// ============================================================================
+// 测试
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::node_db_fn;
+    use crate::db::test_utils::setup_test_db;
+    use crate::types::node::NodeType;
+
+    #[tokio::test]
+    async fn test_saved_search_round_trip() {
+        let db = setup_test_db().await;
+        let workspace_id = uuid::Uuid::new_v4().to_string();
+        workspace_db_fn::create(&db, workspace_id.clone(), "测试工作区".to_string(), None)
+            .await
+            .unwrap();
+
+        for (id, node_type) in [("trip", NodeType::Diary), ("plan", NodeType::File)] {
+            node_db_fn::create(
+                &db,
+                id.to_string(),
+                workspace_id.clone(),
+                None,
+                id.to_string(),
+                node_type,
+                Some(r#"["travel"]"#.to_string()),
+            )
+            .await
+            .unwrap();
+        }
+
+        let saved = CreateSavedSearch::execute(
+            &db,
+            CreateSavedSearchRequest {
+                workspace_id: workspace_id.clone(),
+                name: " 旅行日记 ".to_string(),
+                query: "type:diary tag:travel".to_string(),
+            },
+        )
+        .await
+        .unwrap();
+        assert_eq!(saved.name, "旅行日记");
+
+        let nodes = RunSavedQuery::execute(&db, IdInput::new(&saved.id))
+            .await
+            .unwrap();
+        assert_eq!(nodes.len(), 1);
+        assert_eq!(nodes[0].id, "trip");
+
+        let invalid = UpdateSavedSearch::execute(
+            &db,
+            IdWithBodyInput::new(
+                &saved.id,
+                UpdateSavedSearchRequest {
+                    query: Some("updated:>tomorrow".to_string()),
+                    ..Default::default()
+                },
+            ),
+        )
+        .await;
+        assert!(matches!(invalid, Err(AppError::ValidationError(_))));
+
+        let ad_hoc = SearchNodes::execute(
+            &db,
+            IdWithBodyInput::new(
+                &workspace_id,
+                SearchNodesQuery {
+                    q: "tag:travel -type:diary".to_string(),
+                },
+            ),
+        )
+        .await
+        .unwrap();
+        assert_eq!(ad_hoc.len(), 1);
+        assert_eq!(ad_hoc[0].id, "plan");
+
+        let listed = GetSavedSearches::execute(&db, WorkspaceIdInput::new(&workspace_id))
+            .await
+            .unwrap();
+        assert_eq!(listed.len(), 1);
+
+        DeleteSavedSearch::execute(&db, IdInput::new(&saved.id))
+            .await
+            .unwrap();
+        let missing = RunSavedQuery::execute(&db, IdInput::new(&saved.id)).await;
+        assert!(matches!(missing, Err(AppError::NotFound(_))));
+    }
+
+    #[tokio::test]
+    async fn test_create_saved_search_validation() {
+        let db = setup_test_db().await;
+        let request = |workspace_id: &str, name: &str, query: &str| CreateSavedSearchRequest {
+            workspace_id: workspace_id.to_string(),
+            name: name.to_string(),
+            query: query.to_string(),
+        };
+
+        let result = CreateSavedSearch::execute(&db, request("ws", " ", "tag:a")).await;
+        assert!(matches!(result, Err(AppError::ValidationError(_))));
+
+        let result = CreateSavedSearch::execute(&db, request("ws", "a", r#""open"#)).await;
+        assert!(matches!(result, Err(AppError::ValidationError(_))));
+
+        let result = CreateSavedSearch::execute(&db, request("missing", "a", "tag:a")).await;
+        assert!(matches!(result, Err(AppError::NotFound(_))));
+    }
+}
@@ -0,0 +1,454 @@
+//! Sync API 端点
+//!
+//! 桌面端与 `grain-api` 服务器之间的推送/拉取同步。
+//! 服务器端提供拉取和推送两个端点，客户端的同步流程见 `sync` 模块，由 `SyncWithServer` 发起
+//! （只有 Tauri 命令：服务器不代替请求方访问任意地址）。
+//!
+//! 每个实体的版本是它在服务器变更日志中最新一条记录的序号。
+//! 推送时携带本地副本所基于的版本，服务器上的版本更新且数据不同即为冲突。
+//! 启用认证时拉取只返回用户所属工作区的变更，推送只接受用户可以编辑的工作区的变更。
+//!
+//! ## 端点列表
+//!
+//! | 端点 | 方法 | 路径 | 说明 |
+//! |------|------|------|------|
+//! | SyncPull | GET | /api/sync/pull?since=&limit= | 拉取序号之后的变更（含数据） |
+//! | SyncPush | POST | /api/sync/push | 推送本地变更，返回每条的结果 |
+//! | SyncWithServer | - | （仅 Tauri） | 与服务器完成一次拉取和推送 |
+
+use sea_orm::{DatabaseConnection, TransactionTrait};
+
+use super::change_log::is_visible_change;
+use super::{is_authorized, Access, ApiEndpoint, OptionalAuthWithBodyInput, WorkspaceScope};
+use crate::db::{change_log_db_fn, sync_db_fn, workspace_db_fn, workspace_member_db_fn};
+use crate::events;
+use crate::r#fn::change_log::compact_changes;
+use crate::r#fn::sync::{is_syncable, normalize_server_url};
+use crate::sync::{self as sync_client, HttpSyncTransport};
+use crate::types::change_log::{ChangeEntity, ChangeOperation};
+use crate::types::member::WorkspaceRole;
+use crate::types::sync::{
+    SyncChange, SyncPullQuery, SyncPullResponse, SyncPushRequest, SyncPushResponse, SyncPushResult,
+    SyncPushStatus, SyncReport, SyncRequest,
+};
+use crate::{AppError, AppResult};
+
+/// 默认每次拉取时扫描的记录条数
+const DEFAULT_PULL_LIMIT: u64 = 500;
+
+/// 每次拉取时最多扫描的记录条数
+const MAX_PULL_LIMIT: u64 = 5000;
+
+// ============================================================================
+// SyncPull - 拉取变更
+// ============================================================================
+
+/// 拉取服务器序号之后的变更
+///
+/// 同一实体只返回最新一条，附带实体的当前数据；
+/// 实体已被删除（包括随父节点级联删除）时返回删除。
+/// 每次最多扫描 `limit` 条记录，压缩和过滤后返回的变更可能更少（甚至为空）。
+/// 客户端应用后用 `nextSince` 作为下一次的 `since`，`hasMore` 为 true 时继续拉取。
+/// 启用认证时只返回用户所属工作区的变更（见 `is_visible_change`）。
+///
+/// ## HTTP
+/// - Method: GET
+/// - Path: /api/sync/pull?since=&limit=
+///
+/// ## 参数
+/// - since: 已拉取到的序号（可选，默认 0）
+/// - limit: 最多扫描的记录条数（可选，默认 500，最大 5000）
+/// - 当前请求的身份（来自访问令牌，可选）
+///
+/// ## 返回
+/// - 成功: SyncPullResponse
+/// - 失败: ValidationError（since 为负数或 limit 为 0）, DatabaseError
+pub struct SyncPull;
+
+impl ApiEndpoint for SyncPull {
+    type Input = OptionalAuthWithBodyInput<SyncPullQuery>;
+    type Output = SyncPullResponse;
+    const NAME: &'static str = "sync_pull";
+
+    fn access(_input: &Self::Input) -> Access {
+        Access::Authenticated
+    }
+
+    async fn execute(db: &DatabaseConnection, input: Self::Input) -> AppResult<Self::Output> {
+        let scope = WorkspaceScope::resolve(db, input.auth.as_ref(), WorkspaceRole::Viewer).await?;
+        let input = input.body;
+        let since = input.since.unwrap_or(0);
+        if since < 0 {
+            return Err(AppError::validation("since 不能为负数"));
+        }
+        let limit = match input.limit {
+            Some(0) => return Err(AppError::validation("limit 必须大于 0")),
+            Some(limit) => limit.min(MAX_PULL_LIMIT),
+            None => DEFAULT_PULL_LIMIT,
+        };
+
+        sync_db_fn::record_baseline(db).await?;
+
+        // 多读一条判断是否还有更多记录
+        let mut entries = change_log_db_fn::find_since(db, since, limit + 1).await?;
+        let has_more = entries.len() as u64 > limit;
+        entries.truncate(limit as usize);
+        let next_since = entries.last().map_or(since, |e| e.seq);
+
+        let entries: Vec<_> = compact_changes(entries)
+            .into_iter()
+            .filter(|e| is_syncable(e.entity_type) && is_visible_change(e, &scope))
+            .collect();
+
+        let mut changes = Vec::with_capacity(entries.len());
+        for entry in &entries {
+            changes.push(sync_db_fn::change_from_entry(db, entry).await?);
+        }
+
+        Ok(SyncPullResponse {
+            changes,
+            next_since,
+            has_more,
+        })
+    }
+}
+
+// ============================================================================
+// SyncPush - 推送变更
+// ============================================================================
+
+/// 推送本地变更
+///
+/// 按请求中的顺序逐条处理，每条变更单独提交：
+/// - 服务器版本比 `version` 新且数据不同：`conflict`，附带服务器当前状态（`force` 时直接覆盖）
+/// - 涉及用户不能编辑的工作区：`rejected`
+/// - 依赖的工作区或父节点不存在：`rejected`
+/// - 否则应用并记入变更日志：`applied`，返回新的版本
+///
+/// 启用认证时推送新建的工作区归推送者所有。
+///
+/// ## HTTP
+/// - Method: POST
+/// - Path: /api/sync/push
+///
+/// ## Tauri
+/// - Command: sync_push
+///
+/// ## 参数
+/// - changes: 本地变更（父节点在子节点之前）
+/// - force: 忽略冲突强制覆盖（可选，默认 false）
+/// - 当前请求的身份（来自访问令牌，可选）
+///
+/// ## 返回
+/// - 成功: SyncPushResponse（与 changes 一一对应）
+/// - 失败: DatabaseError
+pub struct SyncPush;
+
+impl ApiEndpoint for SyncPush {
+    type Input = OptionalAuthWithBodyInput<SyncPushRequest>;
+    type Output = SyncPushResponse;
+    const NAME: &'static str = "sync_push";
+
+    fn access(_input: &Self::Input) -> Access {
+        Access::Authenticated
+    }
+
+    async fn execute(db: &DatabaseConnection, input: Self::Input) -> AppResult<Self::Output> {
+        sync_db_fn::record_baseline(db).await?;
+
+        let auth = input.auth.as_ref();
+        let mut writable = Writable {
+            scope: WorkspaceScope::resolve(db, auth, WorkspaceRole::Editor).await?,
+            create: is_authorized(db, auth, &Access::NewWorkspace).await?,
+        };
+        let request = input.body;
+        let mut results = Vec::with_capacity(request.changes.len());
+        for change in request.changes {
+            let creates_workspace = change.entity_type == ChangeEntity::Workspace
+                && change.operation == ChangeOperation::Upsert
+                && !writable.scope.contains(&change.entity_id);
+            let result = push_change(db, change, request.force, &writable).await?;
+            if let Some(auth) = auth {
+                if creates_workspace && result.status == SyncPushStatus::Applied {
+                    workspace_member_db_fn::set_role(
+                        db,
+                        &result.entity_id,
+                        &auth.user_id,
+                        WorkspaceRole::Owner,
+                    )
+                    .await?;
+                    writable.scope.insert(&result.entity_id);
+                }
+            }
+            results.push(result);
+        }
+        Ok(SyncPushResponse { results })
+    }
+}
+
+/// 推送者可以写入的范围
+struct Writable {
+    /// 可以编辑的工作区
+    scope: WorkspaceScope,
+
+    /// 能否新建工作区
+    create: bool,
+}
+
+impl Writable {
+    /// 检查能否写入变更涉及的工作区，返回拒绝原因
+    ///
+    /// 不存在的工作区不拒绝（有新建权限时），引用它的变更由依赖检查拒绝。
+    async fn forbidden(
+        &self,
+        db: &DatabaseConnection,
+        change: &SyncChange,
+    ) -> AppResult<Option<String>> {
+        if self.scope == WorkspaceScope::All {
+            return Ok(None);
+        }
+        for id in sync_db_fn::affected_workspaces(db, change).await? {
+            if self.scope.contains(&id) {
+                continue;
+            }
+            if workspace_db_fn::find_by_id(db, &id).await?.is_some() {
+                return Ok(Some(format!("没有工作区 {} 的 editor 权限", id)));
+            }
+            if !self.create {
+                return Ok(Some("没有新建工作区的权限".to_string()));
+            }
+        }
+        Ok(None)
+    }
+}
+
+/// 处理一条推送的变更
+async fn push_change(
+    db: &DatabaseConnection,
+    change: SyncChange,
+    force: bool,
+    writable: &Writable,
+) -> AppResult<SyncPushResult> {
+    let result = |status, version, remote, message: Option<String>| SyncPushResult {
+        entity_type: change.entity_type,
+        entity_id: change.entity_id.clone(),
+        status,
+        version,
+        remote,
+        message,
+    };
+
+    if !is_syncable(change.entity_type) {
+        return Ok(result(
+            SyncPushStatus::Rejected,
+            0,
+            None,
+            Some("不支持同步的实体类型".to_string()),
+        ));
+    }
+    if let Some(reason) = writable.forbidden(db, &change).await? {
+        return Ok(result(SyncPushStatus::Rejected, 0, None, Some(reason)));
+    }
+
+    let latest = change_log_db_fn::latest_seq(db, change.entity_type, &change.entity_id).await?;
+    if latest > change.version {
+        let current = sync_db_fn::current_change(
+            db,
+            change.entity_type,
+            &change.entity_id,
+            change.workspace_id.clone(),
+            latest,
+        )
+        .await?;
+        let same = match (&current.data, &change.data) {
+            (Some(remote), Some(local)) => remote.same_state(local),
+            (None, None) => true,
+            _ => false,
+        };
+        if same {
+            return Ok(result(SyncPushStatus::Applied, latest, None, None));
+        }
+        if !force {
+            return Ok(result(
+                SyncPushStatus::Conflict,
+                latest,
+                Some(current),
+                None,
+            ));
+        }
+    }
+
+    if let Some(reason) = sync_db_fn::missing_dependency(db, &change).await? {
+        return Ok(result(SyncPushStatus::Rejected, latest, None, Some(reason)));
+    }
+    if change.operation == ChangeOperation::Upsert && change.data.is_none() {
+        return Ok(result(
+            SyncPushStatus::Rejected,
+            latest,
+            None,
+            Some("缺少变更数据".to_string()),
+        ));
+    }
+
+    let txn = db.begin().await?;
+    let event = sync_db_fn::apply_change(&txn, &change).await?;
+    let version = match &event {
+        Some(event) => change_log_db_fn::append(&txn, event)
+            .await?
+            .map_or(latest, |e| e.seq),
+        None => latest,
+    };
+    txn.commit().await?;
+
+    if let Some(event) = event {
+        events::publish(event);
+    }
+    Ok(result(SyncPushStatus::Applied, version, None, None))
+}
+
+// ============================================================================
+// SyncWithServer - 与服务器同步（客户端）
+// ============================================================================
+
+/// 与服务器完成一次同步：先拉取再推送
+///
+/// 同步进度按服务器保存，中断后再次调用从中断处继续。
+/// 本地和服务器都修改过的实体按 `strategy` 处理，默认只报告冲突、两边都不修改。
+///
+/// ## Tauri
+/// - Command: sync_with_server
+///
+/// ## 参数
+/// - server_url: 服务器地址
+/// - token: 访问令牌（可选）
+/// - strategy: 冲突处理策略（可选，默认 report）
+/// - batch_size: 每批拉取/推送的条数（可选，默认 200）
+///
+/// ## 返回
+/// - 成功: SyncReport
+/// - 失败: ValidationError（地址无效）, Unauthorized, InternalError（网络错误）, DatabaseError
+pub struct SyncWithServer;
+
+impl ApiEndpoint for SyncWithServer {
+    type Input = SyncRequest;
+    type Output = SyncReport;
+    const NAME: &'static str = "sync_with_server";
+
+    /// 只有 Tauri 命令；拉取的修改写入所有工作区
+    fn access(_input: &Self::Input) -> Access {
+        Access::Admin
+    }
+
+    async fn execute(db: &DatabaseConnection, input: Self::Input) -> AppResult<Self::Output> {
+        let server_url = normalize_server_url(&input.server_url)?;
+        let transport = HttpSyncTransport::new(&server_url, input.token)?;
+        sync_client::run(
+            db,
+            &transport,
+            &server_url,
+            input.strategy.unwrap_or_default(),
+            input.batch_size,
+        )
+        .await
+    }
+}
+
+// ============================================================================
+// 测试
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::test_utils::setup_test_db;
+    use crate::db::{auth_db_fn, node_db_fn};
+    use crate::r#fn::sync::order_changes;
+    use crate::types::auth::AuthUser;
+    use crate::types::node::NodeType;
+
+    async fn create_workspace(db: &DatabaseConnection, workspace_id: &str, node_id: &str) {
+        workspace_db_fn::create(db, workspace_id.to_string(), workspace_id.to_string(), None)
+            .await
+            .unwrap();
+        node_db_fn::create(
+            db,
+            node_id.to_string(),
+            workspace_id.to_string(),
+            None,
+            "节点".to_string(),
+            NodeType::File,
+            None,
+        )
+        .await
+        .unwrap();
+    }
+
+    async fn pull(db: &DatabaseConnection, auth: Option<AuthUser>) -> Vec<SyncChange> {
+        let input = OptionalAuthWithBodyInput::new(auth, SyncPullQuery::default());
+        SyncPull::execute(db, input).await.unwrap().changes
+    }
+
+    async fn push(
+        db: &DatabaseConnection,
+        auth: &AuthUser,
+        changes: Vec<SyncChange>,
+    ) -> Vec<SyncPushResult> {
+        let request = SyncPushRequest {
+            changes: order_changes(changes),
+            force: true,
+        };
+        let input = OptionalAuthWithBodyInput::new(Some(auth.clone()), request);
+        SyncPush::execute(db, input).await.unwrap().results
+    }
+
+    #[tokio::test]
+    async fn test_sync_scoped_to_members() {
+        let db = setup_test_db().await;
+        let alice = auth_db_fn::ensure_user(&db, "alice", "password-1")
+            .await
+            .unwrap();
+        create_workspace(&db, "ws1", "n1").await;
+        create_workspace(&db, "ws2", "n2").await;
+        workspace_member_db_fn::set_role(&db, "ws1", &alice.id, WorkspaceRole::Editor)
+            .await
+            .unwrap();
+        let auth = AuthUser {
+            user_id: alice.id.clone(),
+            session_id: String::new(),
+            token: None,
+        };
+
+        // 拉取只返回所属工作区的变更
+        let own = pull(&db, Some(auth.clone())).await;
+        assert!(!own.is_empty());
+        assert!(own.iter().all(|c| c.workspace_id.as_deref() == Some("ws1")));
+        let all = pull(&db, None).await;
+        assert!(all.iter().any(|c| c.workspace_id.as_deref() == Some("ws2")));
+
+        // 推送不能写入不是成员的工作区（不看推送方给出的 workspace_id）
+        let mut forged = all.clone();
+        for change in &mut forged {
+            change.workspace_id = Some("ws1".to_string());
+        }
+        for result in push(&db, &auth, forged).await {
+            let expected = if ["ws2", "n2"].contains(&result.entity_id.as_str()) {
+                SyncPushStatus::Rejected
+            } else {
+                SyncPushStatus::Applied
+            };
+            assert_eq!(result.status, expected, "{}", result.entity_id);
+        }
+
+        // 推送新建的工作区归推送者所有
+        let source = setup_test_db().await;
+        create_workspace(&source, "ws3", "n3").await;
+        let results = push(&db, &auth, pull(&source, None).await).await;
+        assert!(results.iter().all(|r| r.status == SyncPushStatus::Applied));
+        assert_eq!(
+            workspace_member_db_fn::find_role(&db, "ws3", &alice.id)
+                .await
+                .unwrap(),
+            Some(WorkspaceRole::Owner)
+        );
+    }
+}
@@ -0,0 +1,1470 @@
+//! Tag API 端点
+//!
+//! 标签相关的 API 端点实现。
+//! 标签名使用 `/` 分隔层级，如 `project/alpha` 是 `project` 的子标签。
+//!
+//! ## 端点列表
+//!
+//! | 端点 | 方法 | 路径 | 说明 |
+//! |------|------|------|------|
+//! | GetTags | GET | /api/workspaces/:id/tags | 获取工作区所有标签 |
+//! | GetTagTree | GET | /api/workspaces/:id/tags/tree | 获取层级标签树 |
+//! | ResolveTag | GET | /api/workspaces/:id/tags/resolve | 按名称或别名查找标签 |
+//! | UpdateTagMetadata | PUT | /api/workspaces/:id/tags/metadata | 更新标签颜色、图标、描述和别名 |
+//! | RenameTag | POST | /api/workspaces/:id/tags/rename | 重命名标签 |
+//! | MergeTags | POST | /api/workspaces/:id/tags/merge | 合并标签 |
+//! | GetTag | GET | /api/tags/:id | 按 ID 获取标签 |
+//! | GetTagByName | GET | /api/workspaces/:id/tags/by-name/:name | 按名称获取标签 |
+//! | GetTopTags | GET | /api/workspaces/:id/tags/top | 获取最常用标签 |
+//! | SearchTags | GET | /api/workspaces/:id/tags/search | 搜索标签 |
+//! | GetNodesByTag | GET | /api/workspaces/:id/tags/:tag_name/nodes | 获取带有标签的节点 |
+//! | GetTagGraphData | GET | /api/workspaces/:id/tags/graph | 获取标签关系图 |
+//! | CreateTag | POST | /api/tags | 创建标签 |
+//! | GetOrCreateTag | POST | /api/workspaces/:id/tags/get-or-create | 获取或创建标签 |
+//! | UpdateTag | PUT | /api/tags/:id | 更新标签 |
+//! | IncrementTagCount | POST | /api/tags/:id/increment | 标签计数加一 |
+//! | DecrementTagCount | POST | /api/tags/:id/decrement | 标签计数减一 |
+//! | DeleteTag | DELETE | /api/tags/:id | 删除标签 |
+//! | DeleteTagsByWorkspace | DELETE | /api/workspaces/:id/tags | 删除工作区所有标签 |
+//! | SyncTagCache | POST | /api/workspaces/:id/tags/sync | 从节点同步标签表 |
+//! | RebuildTagCache | POST | /api/workspaces/:id/tags/rebuild | 重建标签表 |
+//! | RecalculateTagCounts | POST | /api/workspaces/:id/tags/recalculate | 重新计算标签计数 |
+
+use sea_orm::{ConnectionTrait, DatabaseConnection, TransactionTrait};
+
+use super::{Access, ApiEndpoint, IdInput, IdWithBodyInput, NoOutput, WorkspaceIdInput};
+use crate::db::tag_db_fn;
+use crate::events::PendingEvents;
+use crate::r#fn::tag::{normalize_tag_path, tag_matches, TagRename};
+use crate::types::event::ChangeEvent;
+use crate::types::member::WorkspaceRole;
+use crate::types::tag::{
+    CreateTagRequest, GetOrCreateTagRequest, MergeTagsRequest, RenameTagRequest, ResolveTagQuery,
+    SearchTagsQuery, TagGraphData, TagModel, TagNodesQuery, TagResponse, TagRewriteReport,
+    TagTreeNode, TopTagsQuery, UpdateTagMetadataRequest, UpdateTagRequest,
+};
+use crate::{AppError, AppResult};
+
+// ============================================================================
+// GetTags - 获取工作区所有标签
+// ============================================================================
+
+/// 获取工作区所有标签（含颜色、图标、描述和别名）
+///
+/// ## HTTP
+/// - Method: GET
+/// - Path: /api/workspaces/:workspace_id/tags
+///
+/// ## Tauri
+/// - Command: get_tags_by_workspace
+///
+/// ## 参数
+/// - workspace_id: 工作区 ID
+///
+/// ## 返回
+/// - 成功: Vec<TagResponse>（按使用次数降序）
+/// - 失败: DatabaseError
+pub struct GetTags;
+
+impl ApiEndpoint for GetTags {
+    type Input = WorkspaceIdInput;
+    type Output = Vec<TagResponse>;
+    const NAME: &'static str = "get_tags";
+
+    fn access(input: &Self::Input) -> Access {
+        Access::Workspace(input.workspace_id.clone(), WorkspaceRole::Viewer)
+    }
+
+    async fn execute(db: &DatabaseConnection, input: Self::Input) -> AppResult<Self::Output> {
+        let tags = tag_db_fn::find_by_workspace(db, &input.workspace_id).await?;
+        Ok(tags.into_iter().map(Into::into).collect())
+    }
+}
+
+// ============================================================================
+// GetTagTree - 获取层级标签树
+// ============================================================================
+
+/// 获取工作区的层级标签树
+///
+/// 每个层级包含直接计数（count）和包含所有子标签的汇总计数（rollupCount）。
+///
+/// ## HTTP
+/// - Method: GET
+/// - Path: /api/workspaces/:workspace_id/tags/tree
+///
+/// ## Tauri
+/// - Command: get_tag_tree
+///
+/// ## 参数
+/// - workspace_id: 工作区 ID
+///
+/// ## 返回
+/// - 成功: Vec<TagTreeNode>（顶层标签，按名称排序）
+/// - 失败: DatabaseError
+pub struct GetTagTree;
+
+impl ApiEndpoint for GetTagTree {
+    type Input = WorkspaceIdInput;
+    type Output = Vec<TagTreeNode>;
+    const NAME: &'static str = "get_tag_tree";
+
+    fn access(input: &Self::Input) -> Access {
+        Access::Workspace(input.workspace_id.clone(), WorkspaceRole::Viewer)
+    }
+
+    async fn execute(db: &DatabaseConnection, input: Self::Input) -> AppResult<Self::Output> {
+        Ok(tag_db_fn::get_tag_tree(db, &input.workspace_id).await?)
+    }
+}
+
+// ============================================================================
+// ResolveTag - 按名称或别名查找标签
+// ============================================================================
+
+/// 按名称或别名查找标签
+///
+/// 打标签前调用，将用户输入的别名解析为规范的标签。
+///
+/// ## HTTP
+/// - Method: GET
+/// - Path: /api/workspaces/:workspace_id/tags/resolve?name=
+///
+/// ## Tauri
+/// - Command: resolve_tag
+///
+/// ## 参数
+/// - id: 工作区 ID
+/// - name: 标签名称或别名
+///
+/// ## 返回
+/// - 成功: Option<TagResponse>（标签不存在时为 None）
+/// - 失败: DatabaseError
+pub struct ResolveTag;
+
+impl ApiEndpoint for ResolveTag {
+    type Input = IdWithBodyInput<ResolveTagQuery>;
+    type Output = Option<TagResponse>;
+    const NAME: &'static str = "resolve_tag";
+
+    fn access(input: &Self::Input) -> Access {
+        Access::Workspace(input.id.clone(), WorkspaceRole::Viewer)
+    }
+
+    async fn execute(db: &DatabaseConnection, input: Self::Input) -> AppResult<Self::Output> {
+        let name = tag_db_fn::resolve_alias(db, &input.id, &input.body.name).await?;
+        let tag = tag_db_fn::find_by_name(db, &input.id, &name).await?;
+        Ok(tag.map(Into::into))
+    }
+}
+
+// ============================================================================
+// UpdateTagMetadata - 更新标签元数据
+// ============================================================================
+
+/// 更新标签的颜色、图标、描述和别名
+///
+/// 别名在工作区内唯一，且不能与已有标签同名。
+///
+/// ## HTTP
+/// - Method: PUT
+/// - Path: /api/workspaces/:workspace_id/tags/metadata
+/// - Body: UpdateTagMetadataRequest
+///
+/// ## Tauri
+/// - Command: update_tag_metadata
+///
+/// ## 参数
+/// - id: 工作区 ID
+/// - name: 标签名称
+/// - color / icon / description: 为 None 时不变，空字符串时清除
+/// - aliases: 别名列表（整体替换）
+///
+/// ## 返回
+/// - 成功: TagResponse
+/// - 失败: NotFound（标签不存在）, ValidationError（别名冲突）, DatabaseError
+pub struct UpdateTagMetadata;
+
+impl ApiEndpoint for UpdateTagMetadata {
+    type Input = IdWithBodyInput<UpdateTagMetadataRequest>;
+    type Output = TagResponse;
+    const NAME: &'static str = "update_tag_metadata";
+
+    fn access(input: &Self::Input) -> Access {
+        Access::Workspace(input.id.clone(), WorkspaceRole::Editor)
+    }
+
+    async fn execute(db: &DatabaseConnection, input: Self::Input) -> AppResult<Self::Output> {
+        let name = normalize_tag_path(&input.body.name);
+        let tags = tag_db_fn::find_by_workspace(db, &input.id).await?;
+        let tag = tags
+            .iter()
+            .find(|t| t.name == name)
+            .cloned()
+            .ok_or_else(|| AppError::not_found(format!("Tag {}", name)))?;
+
+        for alias in input.body.aliases.iter().flatten() {
+            let alias = normalize_tag_path(alias);
+            if alias.is_empty() || alias == tag.name {
+                continue;
+            }
+            if tags.iter().any(|t| t.name == alias) {
+                return Err(AppError::validation(format!(
+                    "别名 {} 与已有标签同名",
+                    alias
+                )));
+            }
+            if let Some(owner) = tags
+                .iter()
+                .find(|t| t.id != tag.id && t.alias_list().contains(&alias))
+            {
+                return Err(AppError::validation(format!(
+                    "别名 {} 已被标签 {} 使用",
+                    alias, owner.name
+                )));
+            }
+        }
+
+        let txn = db.begin().await?;
+        let mut pending = PendingEvents::new();
+        let updated = tag_db_fn::update_metadata(&txn, tag, input.body).await?;
+        record_workspace_tags_changed(&txn, &mut pending, input.id).await?;
+        txn.commit().await?;
+
+        pending.publish();
+        Ok(updated.into())
+    }
+}
+
+// ============================================================================
+// RenameTag - 重命名标签
+// ============================================================================
+
+/// 重命名标签
+///
+/// 在一个事务中改写所有节点的 tags、内容中的 `#[tag]`，
+/// 并将标签记录换成新 ID 后重新计数。子标签一同改名。
+/// 完成后为每个被改写的节点和内容记录变更事件，最后记录 `tagsChanged`。
+///
+/// ## HTTP
+/// - Method: POST
+/// - Path: /api/workspaces/:workspace_id/tags/rename
+/// - Body: RenameTagRequest
+///
+/// ## Tauri
+/// - Command: rename_tag
+///
+/// ## 参数
+/// - id: 工作区 ID
+/// - from: 原标签名称
+/// - to: 新标签名称
+///
+/// ## 返回
+/// - 成功: TagRewriteReport
+/// - 失败: NotFound（原标签不存在）, ValidationError（名称为空、新标签已存在或位于原标签之下）, DatabaseError
+pub struct RenameTag;
+
+impl ApiEndpoint for RenameTag {
+    type Input = IdWithBodyInput<RenameTagRequest>;
+    type Output = TagRewriteReport;
+    const NAME: &'static str = "rename_tag";
+
+    fn access(input: &Self::Input) -> Access {
+        Access::Workspace(input.id.clone(), WorkspaceRole::Editor)
+    }
+
+    async fn execute(db: &DatabaseConnection, input: Self::Input) -> AppResult<Self::Output> {
+        let from = normalize_tag_path(&input.body.from);
+        let to = normalize_tag_path(&input.body.to);
+        if from.is_empty() || to.is_empty() {
+            return Err(AppError::validation("标签名称不能为空"));
+        }
+        if tag_matches(&to, &from) {
+            return Err(AppError::validation(format!(
+                "不能将标签 {} 重命名为 {}",
+                from, to
+            )));
+        }
+
+        let tags = tag_db_fn::find_by_workspace(db, &input.id).await?;
+        ensure_tag_exists(&tags, &from)?;
+        if tags.iter().any(|t| t.name == to) {
+            return Err(AppError::validation(format!(
+                "标签 {} 已存在，请使用合并",
+                to
+            )));
+        }
+
+        let txn = db.begin().await?;
+        let mut pending = PendingEvents::new();
+        let report = tag_db_fn::rewrite_tags(&txn, &input.id, &[TagRename { from, to }]).await?;
+        record_tag_rewrite(&txn, &mut pending, input.id, &report).await?;
+        txn.commit().await?;
+
+        pending.publish();
+        Ok(report)
+    }
+}
+
+// ============================================================================
+// MergeTags - 合并标签
+// ============================================================================
+
+/// 合并标签
+///
+/// 在一个事务中将所有源标签（及其子标签）改为目标标签，
+/// 同时改写节点 tags 和内容中的 `#[tag]`，并重新计数。
+/// 与重命名一样记录被改写的节点和内容的变更事件。
+///
+/// ## HTTP
+/// - Method: POST
+/// - Path: /api/workspaces/:workspace_id/tags/merge
+/// - Body: MergeTagsRequest
+///
+/// ## Tauri
+/// - Command: merge_tags
+///
+/// ## 参数
+/// - id: 工作区 ID
+/// - sources: 源标签名称列表
+/// - target: 目标标签名称（可以不存在）
+///
+/// ## 返回
+/// - 成功: TagRewriteReport
+/// - 失败: NotFound（源标签不存在）, ValidationError（没有有效的源标签或目标位于源标签之下）, DatabaseError
+pub struct MergeTags;
+
+impl ApiEndpoint for MergeTags {
+    type Input = IdWithBodyInput<MergeTagsRequest>;
+    type Output = TagRewriteReport;
+    const NAME: &'static str = "merge_tags";
+
+    fn access(input: &Self::Input) -> Access {
+        Access::Workspace(input.id.clone(), WorkspaceRole::Editor)
+    }
+
+    async fn execute(db: &DatabaseConnection, input: Self::Input) -> AppResult<Self::Output> {
+        let target = normalize_tag_path(&input.body.target);
+        if target.is_empty() {
+            return Err(AppError::validation("目标标签名称不能为空"));
+        }
+
+        let mut sources: Vec<String> = Vec::new();
+        for source in input.body.sources.iter().map(|s| normalize_tag_path(s)) {
+            if !source.is_empty() && source != target && !sources.contains(&source) {
+                sources.push(source);
+            }
+        }
+        if sources.is_empty() {
+            return Err(AppError::validation("没有需要合并的源标签"));
+        }
+
+        let tags = tag_db_fn::find_by_workspace(db, &input.id).await?;
+        for source in &sources {
+            if tag_matches(&target, source) {
+                return Err(AppError::validation(format!(
+                    "不能将标签 {} 合并到其子标签 {}",
+                    source, target
+                )));
+            }
+            ensure_tag_exists(&tags, source)?;
+        }
+
+        let renames: Vec<TagRename> = sources
+            .into_iter()
+            .map(|from| TagRename {
+                from,
+                to: target.clone(),
+            })
+            .collect();
+
+        let txn = db.begin().await?;
+        let mut pending = PendingEvents::new();
+        let report = tag_db_fn::rewrite_tags(&txn, &input.id, &renames).await?;
+        record_tag_rewrite(&txn, &mut pending, input.id, &report).await?;
+        txn.commit().await?;
+
+        pending.publish();
+        Ok(report)
+    }
+}
+
+// ============================================================================
+// GetTag - 按 ID 获取标签
+// ============================================================================
+
+/// 按 ID 获取标签
+///
+/// ## HTTP
+/// - Method: GET
+/// - Path: /api/tags/:id
+///
+/// ## Tauri
+/// - Command: get_tag
+///
+/// ## 参数
+/// - id: 标签 ID
+///
+/// ## 返回
+/// - 成功: Option<TagResponse>（标签不存在时为 None）
+/// - 失败: DatabaseError
+pub struct GetTag;
+
+impl ApiEndpoint for GetTag {
+    type Input = IdInput;
+    type Output = Option<TagResponse>;
+    const NAME: &'static str = "get_tag";
+
+    fn access(input: &Self::Input) -> Access {
+        Access::Tag(input.id.clone(), WorkspaceRole::Viewer)
+    }
+
+    async fn execute(db: &DatabaseConnection, input: Self::Input) -> AppResult<Self::Output> {
+        let tag = tag_db_fn::find_by_id(db, &input.id).await?;
+        Ok(tag.map(Into::into))
+    }
+}
+
+// ============================================================================
+// GetTagByName - 按名称获取标签
+// ============================================================================
+
+/// 按名称获取标签（不解析别名，需要别名时使用 ResolveTag）
+///
+/// ## HTTP
+/// - Method: GET
+/// - Path: /api/workspaces/:workspace_id/tags/by-name/:name
+///
+/// ## Tauri
+/// - Command: get_tag_by_name
+///
+/// ## 参数
+/// - id: 工作区 ID
+/// - name: 标签名称
+///
+/// ## 返回
+/// - 成功: Option<TagResponse>（标签不存在时为 None）
+/// - 失败: DatabaseError
+pub struct GetTagByName;
+
+impl ApiEndpoint for GetTagByName {
+    type Input = IdWithBodyInput<ResolveTagQuery>;
+    type Output = Option<TagResponse>;
+    const NAME: &'static str = "get_tag_by_name";
+
+    fn access(input: &Self::Input) -> Access {
+        Access::Workspace(input.id.clone(), WorkspaceRole::Viewer)
+    }
+
+    async fn execute(db: &DatabaseConnection, input: Self::Input) -> AppResult<Self::Output> {
+        let tag = tag_db_fn::find_by_name(db, &input.id, &input.body.name).await?;
+        Ok(tag.map(Into::into))
+    }
+}
+
+// ============================================================================
+// GetTopTags - 获取最常用标签
+// ============================================================================
+
+/// 获取工作区使用次数最多的标签
+///
+/// ## HTTP
+/// - Method: GET
+/// - Path: /api/workspaces/:workspace_id/tags/top?limit=
+///
+/// ## Tauri
+/// - Command: get_top_tags
+///
+/// ## 参数
+/// - id: 工作区 ID
+/// - limit: 返回数量上限
+///
+/// ## 返回
+/// - 成功: Vec<TagResponse>（按使用次数降序）
+/// - 失败: DatabaseError
+pub struct GetTopTags;
+
+impl ApiEndpoint for GetTopTags {
+    type Input = IdWithBodyInput<TopTagsQuery>;
+    type Output = Vec<TagResponse>;
+    const NAME: &'static str = "get_top_tags";
+
+    fn access(input: &Self::Input) -> Access {
+        Access::Workspace(input.id.clone(), WorkspaceRole::Viewer)
+    }
+
+    async fn execute(db: &DatabaseConnection, input: Self::Input) -> AppResult<Self::Output> {
+        let tags = tag_db_fn::find_top_tags(db, &input.id, input.body.limit).await?;
+        Ok(tags.into_iter().map(Into::into).collect())
+    }
+}
+
+// ============================================================================
+// SearchTags - 搜索标签
+// ============================================================================
+
+/// 按名称搜索标签
+///
+/// ## HTTP
+/// - Method: GET
+/// - Path: /api/workspaces/:workspace_id/tags/search?query=
+///
+/// ## Tauri
+/// - Command: search_tags
+///
+/// ## 参数
+/// - id: 工作区 ID
+/// - query: 搜索关键词
+///
+/// ## 返回
+/// - 成功: Vec<TagResponse>
+/// - 失败: DatabaseError
+pub struct SearchTags;
+
+impl ApiEndpoint for SearchTags {
+    type Input = IdWithBodyInput<SearchTagsQuery>;
+    type Output = Vec<TagResponse>;
+    const NAME: &'static str = "search_tags";
+
+    fn access(input: &Self::Input) -> Access {
+        Access::Workspace(input.id.clone(), WorkspaceRole::Viewer)
+    }
+
+    async fn execute(db: &DatabaseConnection, input: Self::Input) -> AppResult<Self::Output> {
+        let tags = tag_db_fn::search_tags(db, &input.id, &input.body.query).await?;
+        Ok(tags.into_iter().map(Into::into).collect())
+    }
+}
+
+// ============================================================================
+// GetNodesByTag - 获取带有标签的节点
+// ============================================================================
+
+/// 获取带有指定标签的节点 ID
+///
+/// ## HTTP
+/// - Method: GET
+/// - Path: /api/workspaces/:workspace_id/tags/:tag_name/nodes
+///
+/// ## Tauri
+/// - Command: get_nodes_by_tag
+///
+/// ## 参数
+/// - id: 工作区 ID
+/// - tag_name: 标签名称
+///
+/// ## 返回
+/// - 成功: Vec<String>（节点 ID）
+/// - 失败: DatabaseError
+pub struct GetNodesByTag;
+
+impl ApiEndpoint for GetNodesByTag {
+    type Input = IdWithBodyInput<TagNodesQuery>;
+    type Output = Vec<String>;
+    const NAME: &'static str = "get_nodes_by_tag";
+
+    fn access(input: &Self::Input) -> Access {
+        Access::Workspace(input.id.clone(), WorkspaceRole::Viewer)
+    }
+
+    async fn execute(db: &DatabaseConnection, input: Self::Input) -> AppResult<Self::Output> {
+        Ok(tag_db_fn::get_nodes_by_tag(db, &input.id, &input.body.tag_name).await?)
+    }
+}
+
+// ============================================================================
+// GetTagGraphData - 获取标签关系图
+// ============================================================================
+
+/// 获取标签与节点的关系图数据
+///
+/// ## HTTP
+/// - Method: GET
+/// - Path: /api/workspaces/:workspace_id/tags/graph
+///
+/// ## Tauri
+/// - Command: get_tag_graph_data
+///
+/// ## 参数
+/// - workspace_id: 工作区 ID
+///
+/// ## 返回
+/// - 成功: TagGraphData
+/// - 失败: DatabaseError
+pub struct GetTagGraphData;
+
+impl ApiEndpoint for GetTagGraphData {
+    type Input = WorkspaceIdInput;
+    type Output = TagGraphData;
+    const NAME: &'static str = "get_tag_graph_data";
+
+    fn access(input: &Self::Input) -> Access {
+        Access::Workspace(input.workspace_id.clone(), WorkspaceRole::Viewer)
+    }
+
+    async fn execute(db: &DatabaseConnection, input: Self::Input) -> AppResult<Self::Output> {
+        Ok(tag_db_fn::get_tag_graph_data(db, &input.workspace_id).await?)
+    }
+}
+
+// ============================================================================
+// CreateTag - 创建标签
+// ============================================================================
+
+/// 创建标签，ID 为 `工作区 ID:标签名称`
+///
+/// ## HTTP
+/// - Method: POST
+/// - Path: /api/tags
+/// - Body: CreateTagRequest
+///
+/// ## Tauri
+/// - Command: create_tag
+///
+/// ## 参数
+/// - name: 标签名称
+/// - workspace_id: 工作区 ID
+///
+/// ## 返回
+/// - 成功: TagResponse
+/// - 失败: DatabaseError（标签已存在）
+pub struct CreateTag;
+
+impl ApiEndpoint for CreateTag {
+    type Input = CreateTagRequest;
+    type Output = TagResponse;
+    const NAME: &'static str = "create_tag";
+
+    fn access(input: &Self::Input) -> Access {
+        Access::Workspace(input.workspace_id.clone(), WorkspaceRole::Editor)
+    }
+
+    async fn execute(db: &DatabaseConnection, input: Self::Input) -> AppResult<Self::Output> {
+        let id = format!("{}:{}", input.workspace_id, input.name);
+        let txn = db.begin().await?;
+        let mut pending = PendingEvents::new();
+        let tag = tag_db_fn::create(&txn, id, input.name, input.workspace_id).await?;
+
+        record_tags_changed(&txn, &mut pending, &tag).await?;
+        txn.commit().await?;
+
+        pending.publish();
+        Ok(tag.into())
+    }
+}
+
+// ============================================================================
+// GetOrCreateTag - 获取或创建标签
+// ============================================================================
+
+/// 按名称获取标签，不存在时创建
+///
+/// ## HTTP
+/// - Method: POST
+/// - Path: /api/workspaces/:workspace_id/tags/get-or-create
+/// - Body: GetOrCreateTagRequest
+///
+/// ## Tauri
+/// - Command: get_or_create_tag
+///
+/// ## 参数
+/// - id: 工作区 ID
+/// - name: 标签名称
+///
+/// ## 返回
+/// - 成功: TagResponse
+/// - 失败: DatabaseError
+pub struct GetOrCreateTag;
+
+impl ApiEndpoint for GetOrCreateTag {
+    type Input = IdWithBodyInput<GetOrCreateTagRequest>;
+    type Output = TagResponse;
+    const NAME: &'static str = "get_or_create_tag";
+
+    fn access(input: &Self::Input) -> Access {
+        Access::Workspace(input.id.clone(), WorkspaceRole::Editor)
+    }
+
+    async fn execute(db: &DatabaseConnection, input: Self::Input) -> AppResult<Self::Output> {
+        let txn = db.begin().await?;
+        let mut pending = PendingEvents::new();
+        let tag = tag_db_fn::get_or_create(&txn, &input.id, &input.body.name).await?;
+
+        record_tags_changed(&txn, &mut pending, &tag).await?;
+        txn.commit().await?;
+
+        pending.publish();
+        Ok(tag.into())
+    }
+}
+
+// ============================================================================
+// UpdateTag - 更新标签
+// ============================================================================
+
+/// 更新标签名称、计数和最后使用时间
+///
+/// 只修改标签记录本身，重命名并改写节点请使用 RenameTag。
+///
+/// ## HTTP
+/// - Method: PUT
+/// - Path: /api/tags/:id
+/// - Body: UpdateTagRequest
+///
+/// ## Tauri
+/// - Command: update_tag
+///
+/// ## 参数
+/// - id: 标签 ID
+/// - name / count / last_used: 为 None 时不变
+///
+/// ## 返回
+/// - 成功: TagResponse
+/// - 失败: NotFound, DatabaseError
+pub struct UpdateTag;
+
+impl ApiEndpoint for UpdateTag {
+    type Input = IdWithBodyInput<UpdateTagRequest>;
+    type Output = TagResponse;
+    const NAME: &'static str = "update_tag";
+
+    fn access(input: &Self::Input) -> Access {
+        Access::Tag(input.id.clone(), WorkspaceRole::Editor)
+    }
+
+    async fn execute(db: &DatabaseConnection, input: Self::Input) -> AppResult<Self::Output> {
+        find_tag(db, &input.id).await?;
+        let request = input.body;
+        let txn = db.begin().await?;
+        let mut pending = PendingEvents::new();
+        let tag = tag_db_fn::update(
+            &txn,
+            &input.id,
+            request.name,
+            request.count,
+            request.last_used,
+        )
+        .await?;
+
+        record_tags_changed(&txn, &mut pending, &tag).await?;
+        txn.commit().await?;
+
+        pending.publish();
+        Ok(tag.into())
+    }
+}
+
+// ============================================================================
+// IncrementTagCount / DecrementTagCount - 调整标签计数
+// ============================================================================
+
+/// 标签使用计数加一，并更新最后使用时间
+///
+/// ## HTTP
+/// - Method: POST
+/// - Path: /api/tags/:id/increment
+///
+/// ## Tauri
+/// - Command: increment_tag_count
+///
+/// ## 参数
+/// - id: 标签 ID
+///
+/// ## 返回
+/// - 成功: TagResponse
+/// - 失败: NotFound, DatabaseError
+pub struct IncrementTagCount;
+
+impl ApiEndpoint for IncrementTagCount {
+    type Input = IdInput;
+    type Output = TagResponse;
+    const NAME: &'static str = "increment_tag_count";
+
+    fn access(input: &Self::Input) -> Access {
+        Access::Tag(input.id.clone(), WorkspaceRole::Editor)
+    }
+
+    async fn execute(db: &DatabaseConnection, input: Self::Input) -> AppResult<Self::Output> {
+        find_tag(db, &input.id).await?;
+        let txn = db.begin().await?;
+        let mut pending = PendingEvents::new();
+        let tag = tag_db_fn::increment_count(&txn, &input.id).await?;
+
+        record_tags_changed(&txn, &mut pending, &tag).await?;
+        txn.commit().await?;
+
+        pending.publish();
+        Ok(tag.into())
+    }
+}
+
+/// 标签使用计数减一
+///
+/// ## HTTP
+/// - Method: POST
+/// - Path: /api/tags/:id/decrement
+///
+/// ## Tauri
+/// - Command: decrement_tag_count
+///
+/// ## 参数
+/// - id: 标签 ID
+///
+/// ## 返回
+/// - 成功: TagResponse
+/// - 失败: NotFound, DatabaseError
+pub struct DecrementTagCount;
+
+impl ApiEndpoint for DecrementTagCount {
+    type Input = IdInput;
+    type Output = TagResponse;
+    const NAME: &'static str = "decrement_tag_count";
+
+    fn access(input: &Self::Input) -> Access {
+        Access::Tag(input.id.clone(), WorkspaceRole::Editor)
+    }
+
+    async fn execute(db: &DatabaseConnection, input: Self::Input) -> AppResult<Self::Output> {
+        find_tag(db, &input.id).await?;
+        let txn = db.begin().await?;
+        let mut pending = PendingEvents::new();
+        let tag = tag_db_fn::decrement_count(&txn, &input.id).await?;
+
+        record_tags_changed(&txn, &mut pending, &tag).await?;
+        txn.commit().await?;
+
+        pending.publish();
+        Ok(tag.into())
+    }
+}
+
+// ============================================================================
+// DeleteTag / DeleteTagsByWorkspace - 删除标签
+// ============================================================================
+
+/// 删除标签记录（节点上的标签不变，标签不存在时不报错）
+///
+/// ## HTTP
+/// - Method: DELETE
+/// - Path: /api/tags/:id
+///
+/// ## Tauri
+/// - Command: delete_tag
+///
+/// ## 参数
+/// - id: 标签 ID
+///
+/// ## 返回
+/// - 成功: NoOutput
+/// - 失败: DatabaseError
+pub struct DeleteTag;
+
+impl ApiEndpoint for DeleteTag {
+    type Input = IdInput;
+    type Output = NoOutput;
+    const NAME: &'static str = "delete_tag";
+
+    fn access(input: &Self::Input) -> Access {
+        Access::Tag(input.id.clone(), WorkspaceRole::Editor)
+    }
+
+    async fn execute(db: &DatabaseConnection, input: Self::Input) -> AppResult<Self::Output> {
+        let Some(tag) = tag_db_fn::find_by_id(db, &input.id).await? else {
+            return Ok(());
+        };
+        let txn = db.begin().await?;
+        let mut pending = PendingEvents::new();
+        tag_db_fn::delete(&txn, &input.id).await?;
+
+        record_tags_changed(&txn, &mut pending, &tag).await?;
+        txn.commit().await?;
+
+        pending.publish();
+        Ok(())
+    }
+}
+
+/// 删除工作区所有标签记录
+///
+/// ## HTTP
+/// - Method: DELETE
+/// - Path: /api/workspaces/:workspace_id/tags
+///
+/// ## Tauri
+/// - Command: delete_tags_by_workspace
+///
+/// ## 参数
+/// - workspace_id: 工作区 ID
+///
+/// ## 返回
+/// - 成功: u64（删除的标签数）
+/// - 失败: DatabaseError
+pub struct DeleteTagsByWorkspace;
+
+impl ApiEndpoint for DeleteTagsByWorkspace {
+    type Input = WorkspaceIdInput;
+    type Output = u64;
+    const NAME: &'static str = "delete_tags_by_workspace";
+
+    fn access(input: &Self::Input) -> Access {
+        Access::Workspace(input.workspace_id.clone(), WorkspaceRole::Editor)
+    }
+
+    async fn execute(db: &DatabaseConnection, input: Self::Input) -> AppResult<Self::Output> {
+        let txn = db.begin().await?;
+        let mut pending = PendingEvents::new();
+        let deleted = tag_db_fn::delete_by_workspace(&txn, &input.workspace_id).await?;
+        record_workspace_tags_changed(&txn, &mut pending, input.workspace_id).await?;
+        txn.commit().await?;
+
+        pending.publish();
+        Ok(deleted)
+    }
+}
+
+// ============================================================================
+// SyncTagCache / RebuildTagCache / RecalculateTagCounts - 标签缓存维护
+// ============================================================================
+
+/// 从节点的 tags 字段同步标签表（只添加缺失的标签）
+///
+/// ## HTTP
+/// - Method: POST
+/// - Path: /api/workspaces/:workspace_id/tags/sync
+///
+/// ## Tauri
+/// - Command: sync_tag_cache
+///
+/// ## 参数
+/// - workspace_id: 工作区 ID
+///
+/// ## 返回
+/// - 成功: NoOutput
+/// - 失败: DatabaseError
+pub struct SyncTagCache;
+
+impl ApiEndpoint for SyncTagCache {
+    type Input = WorkspaceIdInput;
+    type Output = NoOutput;
+    const NAME: &'static str = "sync_tag_cache";
+
+    fn access(input: &Self::Input) -> Access {
+        Access::Workspace(input.workspace_id.clone(), WorkspaceRole::Editor)
+    }
+
+    async fn execute(db: &DatabaseConnection, input: Self::Input) -> AppResult<Self::Output> {
+        let txn = db.begin().await?;
+        let mut pending = PendingEvents::new();
+        tag_db_fn::sync_tag_cache(&txn, &input.workspace_id).await?;
+        record_workspace_tags_changed(&txn, &mut pending, input.workspace_id).await?;
+        txn.commit().await?;
+
+        pending.publish();
+        Ok(())
+    }
+}
+
+/// 清空并从节点的 tags 字段重建标签表
+///
+/// ## HTTP
+/// - Method: POST
+/// - Path: /api/workspaces/:workspace_id/tags/rebuild
+///
+/// ## Tauri
+/// - Command: rebuild_tag_cache
+///
+/// ## 参数
+/// - workspace_id: 工作区 ID
+///
+/// ## 返回
+/// - 成功: NoOutput
+/// - 失败: DatabaseError
+pub struct RebuildTagCache;
+
+impl ApiEndpoint for RebuildTagCache {
+    type Input = WorkspaceIdInput;
+    type Output = NoOutput;
+    const NAME: &'static str = "rebuild_tag_cache";
+
+    fn access(input: &Self::Input) -> Access {
+        Access::Workspace(input.workspace_id.clone(), WorkspaceRole::Editor)
+    }
+
+    async fn execute(db: &DatabaseConnection, input: Self::Input) -> AppResult<Self::Output> {
+        let txn = db.begin().await?;
+        let mut pending = PendingEvents::new();
+        tag_db_fn::rebuild_tag_cache(&txn, &input.workspace_id).await?;
+        record_workspace_tags_changed(&txn, &mut pending, input.workspace_id).await?;
+        txn.commit().await?;
+
+        pending.publish();
+        Ok(())
+    }
+}
+
+/// 按节点重新计算所有标签的使用次数
+///
+/// ## HTTP
+/// - Method: POST
+/// - Path: /api/workspaces/:workspace_id/tags/recalculate
+///
+/// ## Tauri
+/// - Command: recalculate_tag_counts
+///
+/// ## 参数
+/// - workspace_id: 工作区 ID
+///
+/// ## 返回
+/// - 成功: NoOutput
+/// - 失败: DatabaseError
+pub struct RecalculateTagCounts;
+
+impl ApiEndpoint for RecalculateTagCounts {
+    type Input = WorkspaceIdInput;
+    type Output = NoOutput;
+    const NAME: &'static str = "recalculate_tag_counts";
+
+    fn access(input: &Self::Input) -> Access {
+        Access::Workspace(input.workspace_id.clone(), WorkspaceRole::Editor)
+    }
+
+    async fn execute(db: &DatabaseConnection, input: Self::Input) -> AppResult<Self::Output> {
+        let txn = db.begin().await?;
+        let mut pending = PendingEvents::new();
+        tag_db_fn::recalculate_tag_counts(&txn, &input.workspace_id).await?;
+        record_workspace_tags_changed(&txn, &mut pending, input.workspace_id).await?;
+        txn.commit().await?;
+
+        pending.publish();
+        Ok(())
+    }
+}
+
+/// 检查标签（或其子标签）存在
+fn ensure_tag_exists(tags: &[TagModel], name: &str) -> AppResult<()> {
+    if tags.iter().any(|t| tag_matches(&t.name, name)) {
+        Ok(())
+    } else {
+        Err(AppError::not_found(format!("Tag {}", name)))
+    }
+}
+
+/// 按 ID 查找标签，不存在时返回 NotFound
+async fn find_tag(db: &DatabaseConnection, id: &str) -> AppResult<TagModel> {
+    tag_db_fn::find_by_id(db, id)
+        .await?
+        .ok_or_else(|| AppError::not_found(format!("Tag {}", id)))
+}
+
+/// 记录标签所在工作区的 `tagsChanged` 事件
+async fn record_tags_changed<C: ConnectionTrait>(
+    db: &C,
+    pending: &mut PendingEvents,
+    tag: &TagModel,
+) -> AppResult<()> {
+    record_workspace_tags_changed(db, pending, tag.workspace_id.clone()).await
+}
+
+/// 记录标签改写的事件：被改写的节点和内容逐个记录，使其进入变更日志和同步
+async fn record_tag_rewrite<C: ConnectionTrait>(
+    db: &C,
+    pending: &mut PendingEvents,
+    workspace_id: String,
+    report: &TagRewriteReport,
+) -> AppResult<()> {
+    for node_id in &report.updated_node_ids {
+        pending
+            .record(
+                db,
+                ChangeEvent::NodeUpdated {
+                    workspace_id: workspace_id.clone(),
+                    node_id: node_id.clone(),
+                },
+            )
+            .await?;
+    }
+    for content in &report.updated_contents {
+        pending
+            .record(
+                db,
+                ChangeEvent::ContentSaved {
+                    workspace_id: workspace_id.clone(),
+                    node_id: content.node_id.clone(),
+                    version: content.version,
+                },
+            )
+            .await?;
+    }
+    record_workspace_tags_changed(db, pending, workspace_id).await
+}
+
+/// 记录工作区的 `tagsChanged` 事件
+async fn record_workspace_tags_changed<C: ConnectionTrait>(
+    db: &C,
+    pending: &mut PendingEvents,
+    workspace_id: String,
+) -> AppResult<()> {
+    pending
+        .record(db, ChangeEvent::TagsChanged { workspace_id })
+        .await
+}
+
+// ============================================================================
+// 测试
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::test_utils::setup_test_db;
+    use crate::db::{change_log_db_fn, content_db_fn, node_db_fn, workspace_db_fn};
+    use crate::types::change_log::ChangeEntity;
+    use crate::types::node::NodeType;
+
+    async fn create_tagged_nodes(db: &DatabaseConnection, nodes: &[(&str, &str)]) -> String {
+        let workspace_id = uuid::Uuid::new_v4().to_string();
+        workspace_db_fn::create(db, workspace_id.clone(), "测试工作区".to_string(), None)
+            .await
+            .unwrap();
+        for (id, tags) in nodes {
+            node_db_fn::create(
+                db,
+                id.to_string(),
+                workspace_id.clone(),
+                None,
+                id.to_string(),
+                NodeType::File,
+                Some(tags.to_string()),
+            )
+            .await
+            .unwrap();
+        }
+        tag_db_fn::rebuild_tag_cache(db, &workspace_id)
+            .await
+            .unwrap();
+        workspace_id
+    }
+
+    #[tokio::test]
+    async fn test_get_tag_tree() {
+        let db = setup_test_db().await;
+        let workspace_id = create_tagged_nodes(
+            &db,
+            &[
+                ("a", r#"["project"]"#),
+                ("b", r#"["project/alpha"]"#),
+                ("c", r#"["project/alpha", "project/beta"]"#),
+                ("d", r#"["projects"]"#),
+            ],
+        )
+        .await;
+
+        let tree = GetTagTree::execute(&db, WorkspaceIdInput::new(&workspace_id))
+            .await
+            .unwrap();
+        assert_eq!(tree.len(), 2);
+        let project = &tree[0];
+        assert_eq!(project.name, "project");
+        assert_eq!(project.count, 1);
+        assert_eq!(project.rollup_count, 3);
+        assert_eq!(project.children[0].rollup_count, 2);
+        assert_eq!(tree[1].rollup_count, 1);
+
+        // 按父标签查询时包含子标签，但不包含同前缀的其他标签
+        let mut ids = tag_db_fn::get_nodes_by_tag(&db, &workspace_id, "project")
+            .await
+            .unwrap();
+        ids.sort();
+        assert_eq!(ids, vec!["a", "b", "c"]);
+    }
+
+    #[tokio::test]
+    async fn test_rename_tag() {
+        let db = setup_test_db().await;
+        let workspace_id = create_tagged_nodes(
+            &db,
+            &[
+                ("a", r#"["project", "rust"]"#),
+                ("b", r#"["project/alpha"]"#),
+            ],
+        )
+        .await;
+        content_db_fn::create(
+            &db,
+            "content-a".to_string(),
+            "a".to_string(),
+            r##"{"root":{"children":[{"type":"tag","tagName":"project","text":"#[project]"}]}}"##
+                .to_string(),
+        )
+        .await
+        .unwrap();
+
+        let report = RenameTag::execute(
+            &db,
+            IdWithBodyInput::new(
+                &workspace_id,
+                RenameTagRequest {
+                    from: "project".to_string(),
+                    to: "work".to_string(),
+                },
+            ),
+        )
+        .await
+        .unwrap();
+        assert_eq!(report.nodes_updated, 2);
+        assert_eq!(report.contents_updated, 1);
+        let mut node_ids = report.updated_node_ids.clone();
+        node_ids.sort();
+        assert_eq!(node_ids, vec!["a", "b"]);
+        assert_eq!(report.updated_contents[0].node_id, "a");
+        assert_eq!(report.updated_contents[0].version, 2);
+
+        // 被改写的节点和内容进入变更日志
+        let changes: Vec<(ChangeEntity, String)> = change_log_db_fn::find_since(&db, 0, 100)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|e| (e.entity_type, e.entity_id))
+            .collect();
+        for node_id in ["a", "b"] {
+            assert!(changes.contains(&(ChangeEntity::Node, node_id.to_string())));
+        }
+        assert!(changes.contains(&(ChangeEntity::Content, "a".to_string())));
+        let names: Vec<&str> = report.tags.iter().map(|t| t.name.as_str()).collect();
+        assert_eq!(names, vec!["work", "work/alpha"]);
+        assert_eq!(report.tags[0].id, format!("{}:work", workspace_id));
+
+        let node = node_db_fn::find_by_id(&db, "a").await.unwrap().unwrap();
+        assert_eq!(node.tags.as_deref(), Some(r#"["work","rust"]"#));
+        let content = content_db_fn::find_by_node_id(&db, "a")
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(content.content.contains("#[work]"));
+        assert_eq!(content.version, 2);
+        assert!(tag_db_fn::find_by_name(&db, &workspace_id, "project")
+            .await
+            .unwrap()
+            .is_none());
+
+        // 新名称已存在时要求使用合并
+        let result = RenameTag::execute(
+            &db,
+            IdWithBodyInput::new(
+                &workspace_id,
+                RenameTagRequest {
+                    from: "work".to_string(),
+                    to: "rust".to_string(),
+                },
+            ),
+        )
+        .await;
+        assert!(matches!(result, Err(AppError::ValidationError(_))));
+    }
+
+    #[tokio::test]
+    async fn test_merge_tags() {
+        let db = setup_test_db().await;
+        let workspace_id = create_tagged_nodes(
+            &db,
+            &[
+                ("a", r#"["draft", "wip"]"#),
+                ("b", r#"["todo"]"#),
+                ("c", r#"["wip"]"#),
+            ],
+        )
+        .await;
+
+        let report = MergeTags::execute(
+            &db,
+            IdWithBodyInput::new(
+                &workspace_id,
+                MergeTagsRequest {
+                    sources: vec!["draft".to_string(), "todo".to_string()],
+                    target: "wip".to_string(),
+                },
+            ),
+        )
+        .await
+        .unwrap();
+        assert_eq!(report.nodes_updated, 2);
+        assert_eq!(report.tags.len(), 1);
+        assert_eq!(report.tags[0].count, 3);
+
+        let tags = tag_db_fn::find_by_workspace(&db, &workspace_id)
+            .await
+            .unwrap();
+        assert_eq!(tags.len(), 1);
+        let node = node_db_fn::find_by_id(&db, "a").await.unwrap().unwrap();
+        assert_eq!(node.tags.as_deref(), Some(r#"["wip"]"#));
+
+        let result = MergeTags::execute(
+            &db,
+            IdWithBodyInput::new(
+                &workspace_id,
+                MergeTagsRequest {
+                    sources: vec!["missing".to_string()],
+                    target: "wip".to_string(),
+                },
+            ),
+        )
+        .await;
+        assert!(matches!(result, Err(AppError::NotFound(_))));
+    }
+
+    #[tokio::test]
+    async fn test_tag_metadata_and_aliases() {
+        let db = setup_test_db().await;
+        let workspace_id = create_tagged_nodes(
+            &db,
+            &[("a", r#"["lang/javascript"]"#), ("b", r#"["rust"]"#)],
+        )
+        .await;
+
+        let updated = UpdateTagMetadata::execute(
+            &db,
+            IdWithBodyInput::new(
+                &workspace_id,
+                UpdateTagMetadataRequest {
+                    name: "lang/javascript".to_string(),
+                    color: Some("#f7df1e".to_string()),
+                    description: Some("前端".to_string()),
+                    aliases: Some(vec!["js".to_string(), "ecmascript".to_string()]),
+                    ..Default::default()
+                },
+            ),
+        )
+        .await
+        .unwrap();
+        assert_eq!(updated.color.as_deref(), Some("#f7df1e"));
+        assert_eq!(updated.aliases, vec!["js", "ecmascript"]);
+
+        // 别名解析：查找、按标签查询节点、搜索
+        let resolved = ResolveTag::execute(
+            &db,
+            IdWithBodyInput::new(
+                &workspace_id,
+                ResolveTagQuery {
+                    name: "js".to_string(),
+                },
+            ),
+        )
+        .await
+        .unwrap()
+        .unwrap();
+        assert_eq!(resolved.name, "lang/javascript");
+        let ids = tag_db_fn::get_nodes_by_tag(&db, &workspace_id, "js")
+            .await
+            .unwrap();
+        assert_eq!(ids, vec!["a"]);
+        let found = tag_db_fn::search_tags(&db, &workspace_id, "ecma")
+            .await
+            .unwrap();
+        assert_eq!(found.len(), 1);
+
+        // 打标签时别名解析为标签名
+        let tagged = tag_db_fn::get_or_create(&db, &workspace_id, "js")
+            .await
+            .unwrap();
+        assert_eq!(tagged.name, "lang/javascript");
+
+        // 别名冲突
+        let result = UpdateTagMetadata::execute(
+            &db,
+            IdWithBodyInput::new(
+                &workspace_id,
+                UpdateTagMetadataRequest {
+                    name: "rust".to_string(),
+                    aliases: Some(vec!["js".to_string()]),
+                    ..Default::default()
+                },
+            ),
+        )
+        .await;
+        assert!(matches!(result, Err(AppError::ValidationError(_))));
+
+        // 重建缓存和重命名都保留元数据
+        tag_db_fn::rebuild_tag_cache(&db, &workspace_id)
+            .await
+            .unwrap();
+        let report = RenameTag::execute(
+            &db,
+            IdWithBodyInput::new(
+                &workspace_id,
+                RenameTagRequest {
+                    from: "lang".to_string(),
+                    to: "code".to_string(),
+                },
+            ),
+        )
+        .await
+        .unwrap();
+        assert_eq!(report.tags[0].name, "code/javascript");
+        assert_eq!(report.tags[0].color.as_deref(), Some("#f7df1e"));
+        assert_eq!(report.tags[0].aliases, vec!["js", "ecmascript"]);
+    }
+
+    #[tokio::test]
+    async fn test_tag_crud() {
+        let db = setup_test_db().await;
+        let workspace_id = create_tagged_nodes(&db, &[("a", r#"["rust"]"#)]).await;
+
+        let created = CreateTag::execute(
+            &db,
+            CreateTagRequest {
+                name: "go".to_string(),
+                workspace_id: workspace_id.clone(),
+            },
+        )
+        .await
+        .unwrap();
+        assert_eq!(created.id, format!("{}:go", workspace_id));
+
+        let incremented = IncrementTagCount::execute(&db, IdInput::new(&created.id))
+            .await
+            .unwrap();
+        assert_eq!(incremented.count, created.count + 1);
+        let updated = UpdateTag::execute(
+            &db,
+            IdWithBodyInput::new(
+                &created.id,
+                UpdateTagRequest {
+                    name: None,
+                    count: Some(5),
+                    last_used: None,
+                },
+            ),
+        )
+        .await
+        .unwrap();
+        assert_eq!(updated.count, 5);
+
+        let top = GetTopTags::execute(
+            &db,
+            IdWithBodyInput::new(&workspace_id, TopTagsQuery { limit: 1 }),
+        )
+        .await
+        .unwrap();
+        assert_eq!(top[0].name, "go");
+
+        // 不存在的标签
+        let result = DecrementTagCount::execute(&db, IdInput::new("missing")).await;
+        assert!(matches!(result, Err(AppError::NotFound(_))));
+
+        DeleteTag::execute(&db, IdInput::new(&created.id))
+            .await
+            .unwrap();
+        DeleteTag::execute(&db, IdInput::new(&created.id))
+            .await
+            .unwrap();
+        assert!(GetTag::execute(&db, IdInput::new(&created.id))
+            .await
+            .unwrap()
+            .is_none());
+
+        let deleted = DeleteTagsByWorkspace::execute(&db, WorkspaceIdInput::new(&workspace_id))
+            .await
+            .unwrap();
+        assert_eq!(deleted, 1);
+    }
+}
@@ -0,0 +1,314 @@
+//! Task API 端点
+//!
+//! 任务索引相关的 API 端点实现。
+//! 任务是待办（todo）节点内容中的勾选列表项，保存内容时自动提取；标记语法见 `fn::task`。
+//!
+//! ## 端点列表
+//!
+//! | 端点 | 方法 | 路径 | 说明 |
+//! |------|------|------|------|
+//! | QueryTasks | GET | /api/workspaces/:id/tasks?status=&due=&tag=&nodeId=&today= | 查询工作区的任务 |
+//! | ToggleTask | POST | /api/tasks/:id/toggle | 切换任务勾选状态 |
+//! | RebuildTasks | POST | /api/workspaces/:id/tasks/rebuild | 从内容重建任务索引 |
+
+use chrono::NaiveDate;
+use sea_orm::{DatabaseConnection, TransactionTrait};
+
+use super::{Access, ApiEndpoint, IdWithBodyInput, WorkspaceIdInput};
+use crate::db::{tag_db_fn, task_db_fn};
+use crate::events::PendingEvents;
+use crate::r#fn::task::filter_tasks;
+use crate::types::event::ChangeEvent;
+use crate::types::member::WorkspaceRole;
+use crate::types::task::{TaskQuery, TaskResponse, ToggleTaskRequest};
+use crate::{AppError, AppResult};
+
+// ============================================================================
+// QueryTasks - 查询工作区的任务
+// ============================================================================
+
+/// 按状态、截止日期和标签查询工作区的任务
+///
+/// 标签条件先解析别名，匹配任务中的 `#[tag]` 或所在待办节点的标签（包含子标签）。
+///
+/// ## HTTP
+/// - Method: GET
+/// - Path: /api/workspaces/:workspace_id/tasks?status=open&due=thisWeek&tag=work
+///
+/// ## Tauri
+/// - Command: query_tasks
+///
+/// ## 参数
+/// - id: 工作区 ID
+/// - status: `open` / `done`（可选）
+/// - due: `overdue` / `today` / `thisWeek`（可选）
+/// - tag: 标签（可选）
+/// - node_id: 只查询指定节点（可选）
+/// - today: 作为"今天"的日期 `YYYY-MM-DD`（可选，默认 UTC 当天）
+///
+/// ## 返回
+/// - 成功: Vec<TaskResponse>（按截止日期升序，无截止日期在后）
+/// - 失败: ValidationError, DatabaseError
+pub struct QueryTasks;
+
+impl ApiEndpoint for QueryTasks {
+    type Input = IdWithBodyInput<TaskQuery>;
+    type Output = Vec<TaskResponse>;
+    const NAME: &'static str = "query_tasks";
+
+    fn access(input: &Self::Input) -> Access {
+        Access::Workspace(input.id.clone(), WorkspaceRole::Viewer)
+    }
+
+    async fn execute(db: &DatabaseConnection, input: Self::Input) -> AppResult<Self::Output> {
+        let mut query = input.body;
+        let today = match query.today.as_deref() {
+            Some(date) => NaiveDate::parse_from_str(date.trim(), "%Y-%m-%d")
+                .map_err(|_| AppError::validation(format!("无效的日期: {}", date)))?,
+            None => chrono::Utc::now().date_naive(),
+        };
+        if let Some(tag) = query.tag.take() {
+            query.tag = Some(tag_db_fn::resolve_alias(db, &input.id, &tag).await?);
+        }
+
+        let tasks = task_db_fn::find_by_workspace(db, &input.id).await?;
+        let node_tags = task_db_fn::node_tags(db, &input.id).await?;
+        Ok(filter_tasks(tasks, &node_tags, &query, today)
+            .into_iter()
+            .map(Into::into)
+            .collect())
+    }
+}
+
+// ============================================================================
+// ToggleTask - 切换任务勾选状态
+// ============================================================================
+
+/// 切换任务的勾选状态
+///
+/// 直接改写待办内容中的勾选列表项并递增内容版本号，
+/// 前端打开的编辑器需要按新版本重新加载内容。
+///
+/// ## HTTP
+/// - Method: POST
+/// - Path: /api/tasks/:id/toggle
+/// - Body: ToggleTaskRequest
+///
+/// ## Tauri
+/// - Command: toggle_task
+///
+/// ## 参数
+/// - id: 任务 ID
+/// - checked: 目标状态（可选，为空时取反）
+/// - expected_version: 期望的内容版本号（可选，用于乐观锁）
+///
+/// ## 返回
+/// - 成功: TaskResponse（更新后的任务）
+/// - 失败: NotFound, ValidationError（版本冲突）, DatabaseError
+pub struct ToggleTask;
+
+impl ApiEndpoint for ToggleTask {
+    type Input = IdWithBodyInput<ToggleTaskRequest>;
+    type Output = TaskResponse;
+    const NAME: &'static str = "toggle_task";
+
+    fn access(input: &Self::Input) -> Access {
+        Access::Task(input.id.clone(), WorkspaceRole::Editor)
+    }
+
+    async fn execute(db: &DatabaseConnection, input: Self::Input) -> AppResult<Self::Output> {
+        let txn = db.begin().await?;
+        let mut pending = PendingEvents::new();
+        let task = task_db_fn::toggle(
+            &txn,
+            &input.id,
+            input.body.checked,
+            input.body.expected_version,
+        )
+        .await?;
+        pending
+            .record(
+                &txn,
+                ChangeEvent::ContentSaved {
+                    workspace_id: task.workspace_id.clone(),
+                    node_id: task.node_id.clone(),
+                    version: task.content_version,
+                },
+            )
+            .await?;
+        txn.commit().await?;
+
+        pending.publish();
+        Ok(task.into())
+    }
+}
+
+// ============================================================================
+// RebuildTasks - 重建任务索引
+// ============================================================================
+
+/// 从内容表重建工作区的任务索引
+///
+/// 恢复旧版本备份（没有任务索引）后调用。
+///
+/// ## HTTP
+/// - Method: POST
+/// - Path: /api/workspaces/:workspace_id/tasks/rebuild
+///
+/// ## Tauri
+/// - Command: rebuild_tasks
+///
+/// ## 参数
+/// - workspace_id: 工作区 ID
+///
+/// ## 返回
+/// - 成功: u64（重建后的任务数）
+/// - 失败: DatabaseError
+pub struct RebuildTasks;
+
+impl ApiEndpoint for RebuildTasks {
+    type Input = WorkspaceIdInput;
+    type Output = u64;
+    const NAME: &'static str = "rebuild_tasks";
+
+    fn access(input: &Self::Input) -> Access {
+        Access::Workspace(input.workspace_id.clone(), WorkspaceRole::Editor)
+    }
+
+    async fn execute(db: &DatabaseConnection, input: Self::Input) -> AppResult<Self::Output> {
+        task_db_fn::rebuild_for_workspace(db, &input.workspace_id).await
+    }
+}
+
+// ============================================================================
+// 测试
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::SaveContent;
+    use crate::db::test_utils::setup_test_db;
+    use crate::db::{node_db_fn, workspace_db_fn};
+    use crate::types::content::SaveContentRequest;
+    use crate::types::node::NodeType;
+    use crate::types::task::{TaskDueFilter, TaskStatus};
+
+    const CONTENT: &str = r##"{"root":{"children":[{"type":"list","listType":"check","children":[
+        {"type":"listitem","checked":false,"children":[{"type":"text","text":"交房租 due:2026-01-02"}]},
+        {"type":"listitem","checked":false,"children":[{"type":"text","text":"准备演示 !high due:2026-01-08 "},{"type":"tag","tagName":"work","text":"#[work]"}]},
+        {"type":"listitem","checked":true,"children":[{"type":"text","text":"整理照片"}]}
+    ]}]}}"##;
+
+    fn query(status: Option<TaskStatus>, due: Option<TaskDueFilter>) -> TaskQuery {
+        TaskQuery {
+            status,
+            due,
+            today: Some("2026-01-07".to_string()),
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_query_and_toggle_tasks() {
+        let db = setup_test_db().await;
+        let workspace_id = uuid::Uuid::new_v4().to_string();
+        workspace_db_fn::create(&db, workspace_id.clone(), "测试工作区".to_string(), None)
+            .await
+            .unwrap();
+        node_db_fn::create(
+            &db,
+            "todo".to_string(),
+            workspace_id.clone(),
+            None,
+            "待办".to_string(),
+            NodeType::Todo,
+            None,
+        )
+        .await
+        .unwrap();
+
+        // 保存内容时自动提取任务
+        SaveContent::execute(
+            &db,
+            SaveContentRequest {
+                node_id: "todo".to_string(),
+                content: CONTENT.to_string(),
+                expected_version: None,
+                content_type: None,
+            },
+        )
+        .await
+        .unwrap();
+
+        let open = QueryTasks::execute(
+            &db,
+            IdWithBodyInput::new(&workspace_id, query(Some(TaskStatus::Open), None)),
+        )
+        .await
+        .unwrap();
+        assert_eq!(open.len(), 2);
+
+        let overdue = QueryTasks::execute(
+            &db,
+            IdWithBodyInput::new(&workspace_id, query(None, Some(TaskDueFilter::Overdue))),
+        )
+        .await
+        .unwrap();
+        assert_eq!(overdue.len(), 1);
+        assert_eq!(overdue[0].text, "交房租 due:2026-01-02");
+
+        let mut by_tag = query(None, Some(TaskDueFilter::ThisWeek));
+        by_tag.tag = Some("work".to_string());
+        let work = QueryTasks::execute(&db, IdWithBodyInput::new(&workspace_id, by_tag))
+            .await
+            .unwrap();
+        assert_eq!(work.len(), 1);
+        assert_eq!(work[0].tags, vec!["work"]);
+
+        let toggled = ToggleTask::execute(
+            &db,
+            IdWithBodyInput::new(
+                &overdue[0].id,
+                ToggleTaskRequest {
+                    checked: None,
+                    expected_version: Some(1),
+                },
+            ),
+        )
+        .await
+        .unwrap();
+        assert!(toggled.checked);
+        assert_eq!(toggled.content_version, 2);
+
+        let overdue = QueryTasks::execute(
+            &db,
+            IdWithBodyInput::new(&workspace_id, query(None, Some(TaskDueFilter::Overdue))),
+        )
+        .await
+        .unwrap();
+        assert!(overdue.is_empty());
+
+        let rebuilt = RebuildTasks::execute(&db, WorkspaceIdInput::new(&workspace_id))
+            .await
+            .unwrap();
+        assert_eq!(rebuilt, 3);
+    }
+
+    #[tokio::test]
+    async fn test_query_tasks_invalid_today() {
+        let db = setup_test_db().await;
+        let result = QueryTasks::execute(
+            &db,
+            IdWithBodyInput::new(
+                "ws",
+                TaskQuery {
+                    today: Some("tomorrow".to_string()),
+                    ..Default::default()
+                },
+            ),
+        )
+        .await;
+        assert!(matches!(result, Err(AppError::ValidationError(_))));
+    }
+}
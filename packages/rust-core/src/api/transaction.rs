@@ -9,14 +9,21 @@
 //! | CreateNodeWithContent | POST | /api/nodes/with-content | 事务创建节点和内容 |
 //! | DeleteNodeRecursive | DELETE | /api/nodes/:id/recursive | 事务删除节点及后代 |
 
+use schemars::JsonSchema;
 use sea_orm::{
-    ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, QueryOrder, Set,
-    TransactionTrait,
+    ActiveModelTrait, ColumnTrait, ConnectionTrait, DatabaseConnection, EntityTrait, QueryFilter,
+    QueryOrder, Set, TransactionTrait,
 };
 use serde::{Deserialize, Serialize};
 
-use super::{ApiEndpoint, IdInput, NoOutput};
+use super::node::record_nodes_deleted;
+use super::{Access, ApiEndpoint, IdInput, NoOutput};
+use crate::db::node_db_fn::{subtree_statement, SubtreeAnchor};
+use crate::db::{content_db_fn, link_db_fn};
+use crate::events::PendingEvents;
 use crate::types::content::{content_entity as content, ContentEntity as Content, ContentResponse};
+use crate::types::event::ChangeEvent;
+use crate::types::member::WorkspaceRole;
 use crate::types::node::{node_entity as node, NodeEntity as Node, NodeResponse, NodeType};
 use crate::AppError;
 use crate::AppResult;
@@ -26,7 +33,7 @@ use crate::AppResult;
 // ============================================================================
 
 /// 创建节点和内容的请求
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct CreateNodeWithContentRequest {
     /// 所属工作区 ID
@@ -56,7 +63,7 @@ pub struct CreateNodeWithContentRequest {
 }
 
 /// 创建节点和内容的响应
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct CreateNodeWithContentResponse {
     /// 创建的节点
@@ -96,6 +103,10 @@ impl ApiEndpoint for CreateNodeWithContent {
     type Output = CreateNodeWithContentResponse;
     const NAME: &'static str = "create_node_with_content";
 
+    fn access(input: &Self::Input) -> Access {
+        Access::Workspace(input.workspace_id.clone(), WorkspaceRole::Editor)
+    }
+
     async fn execute(db: &DatabaseConnection, input: Self::Input) -> AppResult<Self::Output> {
         // 开启事务
         let txn = db.begin().await?;
@@ -155,9 +166,34 @@ impl ApiEndpoint for CreateNodeWithContent {
             updated_at: Set(now),
         };
         let content_result = content_model.insert(&txn).await?;
+        link_db_fn::sync_links(&txn, &content_result.node_id, &content_result.content).await?;
+        content_db_fn::record_revision(&txn, &content_result).await?;
+
+        let mut pending = PendingEvents::new();
+        pending
+            .record(
+                &txn,
+                ChangeEvent::NodeCreated {
+                    workspace_id: node_result.workspace_id.clone(),
+                    node_id: node_result.id.clone(),
+                    parent_id: node_result.parent_id.clone(),
+                },
+            )
+            .await?;
+        pending
+            .record(
+                &txn,
+                ChangeEvent::ContentSaved {
+                    workspace_id: node_result.workspace_id.clone(),
+                    node_id: node_result.id.clone(),
+                    version: content_result.version,
+                },
+            )
+            .await?;
 
         // 3. 提交事务
         txn.commit().await?;
+        pending.publish();
 
         Ok(CreateNodeWithContentResponse {
             node: node_result.into(),
@@ -196,24 +232,29 @@ impl ApiEndpoint for DeleteNodeRecursive {
     type Output = NoOutput;
     const NAME: &'static str = "delete_node_recursive";
 
+    fn access(input: &Self::Input) -> Access {
+        Access::Node(input.id.clone(), WorkspaceRole::Editor)
+    }
+
     async fn execute(db: &DatabaseConnection, input: Self::Input) -> AppResult<Self::Output> {
         // 开启事务
         let txn = db.begin().await?;
 
-        // 获取所有后代节点（递归）
-        let mut descendants = Vec::new();
-        let mut stack = vec![input.id.clone()];
-
-        while let Some(current_id) = stack.pop() {
-            let children = Node::find()
-                .filter(node::Column::ParentId.eq(&current_id))
-                .all(&txn)
-                .await?;
-            for child in children {
-                stack.push(child.id.clone());
-                descendants.push(child);
-            }
-        }
+        let target = Node::find_by_id(&input.id)
+            .one(&txn)
+            .await?
+            .ok_or_else(|| AppError::not_found(format!("Node {}", input.id)))?;
+
+        // 获取所有后代节点（递归 CTE，按深度排序）
+        let root_ids = [input.id.clone()];
+        let descendants = Node::find()
+            .from_raw_sql(subtree_statement(
+                txn.get_database_backend(),
+                SubtreeAnchor::Nodes(&root_ids),
+                None,
+            ))
+            .all(&txn)
+            .await?;
 
         // 删除所有后代节点的内容
         for descendant in &descendants {
@@ -241,9 +282,14 @@ impl ApiEndpoint for DeleteNodeRecursive {
             return Err(AppError::not_found(format!("Node {}", input.id)));
         }
 
+        // 后代从叶子节点开始逐个记录，最后记录目标节点
+        let mut pending = PendingEvents::new();
+        let removed = descendants.into_iter().rev().chain([target]).collect();
+        record_nodes_deleted(&txn, &mut pending, removed).await?;
+
         // 提交事务
         txn.commit().await?;
-
+        pending.publish();
         Ok(())
     }
 }
@@ -0,0 +1,445 @@
+//! User API 端点
+//!
+//! 本地用户资料（计划、功能开关、界面状态和设置）相关的 API 端点实现。
+//! 与登录账号（`auth` 模块）无关；通过 HTTP 访问时要求是服务器管理员。
+//!
+//! ## 端点列表
+//!
+//! | 端点 | 方法 | 路径 | 说明 |
+//! |------|------|------|------|
+//! | GetUsers | GET | /api/users | 获取所有用户 |
+//! | GetCurrentLocalUser | GET | /api/users/current | 获取当前用户 |
+//! | GetUser | GET | /api/users/:id | 获取单个用户 |
+//! | GetUserByUsername | GET | /api/users/by-username/:username | 按用户名获取用户 |
+//! | GetUserByEmail | GET | /api/users/by-email/:email | 按邮箱获取用户 |
+//! | CreateUser | POST | /api/users | 创建用户 |
+//! | UpdateUser | PUT | /api/users/:id | 更新用户 |
+//! | UpdateUserLastLogin | PUT | /api/users/:id/last-login | 更新最后登录时间 |
+//! | DeleteUser | DELETE | /api/users/:id | 删除用户 |
+
+use sea_orm::DatabaseConnection;
+
+use super::{
+    Access, ApiEndpoint, EmailInput, IdInput, IdWithBodyInput, NoInput, NoOutput, UsernameInput,
+};
+use crate::db::user_db_fn;
+use crate::types::user::{CreateUserRequest, UpdateUserRequest, UserModel, UserResponse};
+use crate::{AppError, AppResult};
+
+/// 读取用户的访问要求
+fn user_access() -> Access {
+    Access::Admin
+}
+
+// ============================================================================
+// GetUsers - 获取所有用户
+// ============================================================================
+
+/// 获取所有用户
+///
+/// ## HTTP
+/// - Method: GET
+/// - Path: /api/users
+///
+/// ## Tauri
+/// - Command: get_users
+///
+/// ## 参数
+/// - 无
+///
+/// ## 返回
+/// - 成功: Vec<UserResponse>
+/// - 失败: DatabaseError
+pub struct GetUsers;
+
+impl ApiEndpoint for GetUsers {
+    type Input = NoInput;
+    type Output = Vec<UserResponse>;
+    const NAME: &'static str = "get_users";
+
+    fn access(_input: &Self::Input) -> Access {
+        user_access()
+    }
+
+    async fn execute(db: &DatabaseConnection, _input: Self::Input) -> AppResult<Self::Output> {
+        let users = user_db_fn::find_all(db).await?;
+        Ok(users.into_iter().map(Into::into).collect())
+    }
+}
+
+// ============================================================================
+// GetCurrentLocalUser - 获取当前用户
+// ============================================================================
+
+/// 获取当前用户（最近登录的用户）
+///
+/// ## HTTP
+/// - Method: GET
+/// - Path: /api/users/current
+///
+/// ## Tauri
+/// - Command: get_current_user
+///
+/// ## 参数
+/// - 无
+///
+/// ## 返回
+/// - 成功: Option<UserResponse>（没有用户时为 None）
+/// - 失败: DatabaseError
+pub struct GetCurrentLocalUser;
+
+impl ApiEndpoint for GetCurrentLocalUser {
+    type Input = NoInput;
+    type Output = Option<UserResponse>;
+    const NAME: &'static str = "get_current_local_user";
+
+    fn access(_input: &Self::Input) -> Access {
+        user_access()
+    }
+
+    async fn execute(db: &DatabaseConnection, _input: Self::Input) -> AppResult<Self::Output> {
+        let user = user_db_fn::find_current(db).await?;
+        Ok(user.map(Into::into))
+    }
+}
+
+// ============================================================================
+// GetUser / GetUserByUsername / GetUserByEmail - 获取单个用户
+// ============================================================================
+
+/// 按 ID 获取用户
+///
+/// ## HTTP
+/// - Method: GET
+/// - Path: /api/users/:id
+///
+/// ## Tauri
+/// - Command: get_user
+///
+/// ## 参数
+/// - id: 用户 ID
+///
+/// ## 返回
+/// - 成功: Option<UserResponse>（用户不存在时为 None）
+/// - 失败: DatabaseError
+pub struct GetUser;
+
+impl ApiEndpoint for GetUser {
+    type Input = IdInput;
+    type Output = Option<UserResponse>;
+    const NAME: &'static str = "get_user";
+
+    fn access(_input: &Self::Input) -> Access {
+        user_access()
+    }
+
+    async fn execute(db: &DatabaseConnection, input: Self::Input) -> AppResult<Self::Output> {
+        let user = user_db_fn::find_by_id(db, &input.id).await?;
+        Ok(user.map(Into::into))
+    }
+}
+
+/// 按用户名获取用户
+///
+/// ## HTTP
+/// - Method: GET
+/// - Path: /api/users/by-username/:username
+///
+/// ## Tauri
+/// - Command: get_user_by_username
+///
+/// ## 参数
+/// - username: 用户名
+///
+/// ## 返回
+/// - 成功: Option<UserResponse>（用户不存在时为 None）
+/// - 失败: DatabaseError
+pub struct GetUserByUsername;
+
+impl ApiEndpoint for GetUserByUsername {
+    type Input = UsernameInput;
+    type Output = Option<UserResponse>;
+    const NAME: &'static str = "get_user_by_username";
+
+    fn access(_input: &Self::Input) -> Access {
+        user_access()
+    }
+
+    async fn execute(db: &DatabaseConnection, input: Self::Input) -> AppResult<Self::Output> {
+        let user = user_db_fn::find_by_username(db, &input.username).await?;
+        Ok(user.map(Into::into))
+    }
+}
+
+/// 按邮箱获取用户
+///
+/// ## HTTP
+/// - Method: GET
+/// - Path: /api/users/by-email/:email
+///
+/// ## Tauri
+/// - Command: get_user_by_email
+///
+/// ## 参数
+/// - email: 邮箱地址
+///
+/// ## 返回
+/// - 成功: Option<UserResponse>（用户不存在时为 None）
+/// - 失败: DatabaseError
+pub struct GetUserByEmail;
+
+impl ApiEndpoint for GetUserByEmail {
+    type Input = EmailInput;
+    type Output = Option<UserResponse>;
+    const NAME: &'static str = "get_user_by_email";
+
+    fn access(_input: &Self::Input) -> Access {
+        user_access()
+    }
+
+    async fn execute(db: &DatabaseConnection, input: Self::Input) -> AppResult<Self::Output> {
+        let user = user_db_fn::find_by_email(db, &input.email).await?;
+        Ok(user.map(Into::into))
+    }
+}
+
+// ============================================================================
+// CreateUser - 创建用户
+// ============================================================================
+
+/// 创建用户
+///
+/// ## HTTP
+/// - Method: POST
+/// - Path: /api/users
+/// - Body: CreateUserRequest
+///
+/// ## Tauri
+/// - Command: create_user
+///
+/// ## 参数
+/// - username: 用户名
+/// - display_name / avatar / email: 可选资料
+/// - plan / features / settings: 可选，默认为免费计划
+///
+/// ## 返回
+/// - 成功: UserResponse
+/// - 失败: DatabaseError（用户名已存在）
+pub struct CreateUser;
+
+impl ApiEndpoint for CreateUser {
+    type Input = CreateUserRequest;
+    type Output = UserResponse;
+    const NAME: &'static str = "create_user";
+
+    fn access(_input: &Self::Input) -> Access {
+        Access::Admin
+    }
+
+    async fn execute(db: &DatabaseConnection, input: Self::Input) -> AppResult<Self::Output> {
+        let id = uuid::Uuid::new_v4().to_string();
+        let user = user_db_fn::create(
+            db,
+            id,
+            input.username,
+            input.display_name,
+            input.avatar,
+            input.email,
+            input.plan,
+            input.features,
+            input.settings,
+        )
+        .await?;
+        Ok(user.into())
+    }
+}
+
+// ============================================================================
+// UpdateUser - 更新用户
+// ============================================================================
+
+/// 更新用户
+///
+/// ## HTTP
+/// - Method: PUT
+/// - Path: /api/users/:id
+/// - Body: UpdateUserRequest
+///
+/// ## Tauri
+/// - Command: update_user
+///
+/// ## 参数
+/// - id: 用户 ID
+/// - 其余字段为 None 时不变，可清空的字段传 null 时清除
+///
+/// ## 返回
+/// - 成功: UserResponse
+/// - 失败: NotFound, DatabaseError
+pub struct UpdateUser;
+
+impl ApiEndpoint for UpdateUser {
+    type Input = IdWithBodyInput<UpdateUserRequest>;
+    type Output = UserResponse;
+    const NAME: &'static str = "update_user";
+
+    fn access(_input: &Self::Input) -> Access {
+        Access::Admin
+    }
+
+    async fn execute(db: &DatabaseConnection, input: Self::Input) -> AppResult<Self::Output> {
+        find_user(db, &input.id).await?;
+        let request = input.body;
+        let user = user_db_fn::update(
+            db,
+            &input.id,
+            request.username,
+            request.display_name,
+            request.avatar,
+            request.email,
+            request.last_login,
+            request.plan,
+            request.plan_start_date,
+            request.plan_expires_at,
+            request.trial_expires_at,
+            request.token,
+            request.server_message,
+            request.features,
+            request.state,
+            request.settings,
+        )
+        .await?;
+        Ok(user.into())
+    }
+}
+
+/// 将用户的最后登录时间更新为当前时间
+///
+/// ## HTTP
+/// - Method: PUT
+/// - Path: /api/users/:id/last-login
+///
+/// ## Tauri
+/// - Command: update_user_last_login
+///
+/// ## 参数
+/// - id: 用户 ID
+///
+/// ## 返回
+/// - 成功: UserResponse
+/// - 失败: NotFound, DatabaseError
+pub struct UpdateUserLastLogin;
+
+impl ApiEndpoint for UpdateUserLastLogin {
+    type Input = IdInput;
+    type Output = UserResponse;
+    const NAME: &'static str = "update_user_last_login";
+
+    fn access(_input: &Self::Input) -> Access {
+        Access::Admin
+    }
+
+    async fn execute(db: &DatabaseConnection, input: Self::Input) -> AppResult<Self::Output> {
+        find_user(db, &input.id).await?;
+        let user = user_db_fn::update_last_login(db, &input.id).await?;
+        Ok(user.into())
+    }
+}
+
+// ============================================================================
+// DeleteUser - 删除用户
+// ============================================================================
+
+/// 删除用户（用户不存在时不报错）
+///
+/// ## HTTP
+/// - Method: DELETE
+/// - Path: /api/users/:id
+///
+/// ## Tauri
+/// - Command: delete_user
+///
+/// ## 参数
+/// - id: 用户 ID
+///
+/// ## 返回
+/// - 成功: NoOutput
+/// - 失败: DatabaseError
+pub struct DeleteUser;
+
+impl ApiEndpoint for DeleteUser {
+    type Input = IdInput;
+    type Output = NoOutput;
+    const NAME: &'static str = "delete_user";
+
+    fn access(_input: &Self::Input) -> Access {
+        Access::Admin
+    }
+
+    async fn execute(db: &DatabaseConnection, input: Self::Input) -> AppResult<Self::Output> {
+        user_db_fn::delete(db, &input.id).await?;
+        Ok(())
+    }
+}
+
+/// 按 ID 查找用户，不存在时返回 NotFound
+async fn find_user(db: &DatabaseConnection, id: &str) -> AppResult<UserModel> {
+    user_db_fn::find_by_id(db, id)
+        .await?
+        .ok_or_else(|| AppError::not_found(format!("User {}", id)))
+}
+
+// ============================================================================
+// 测试
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::test_utils::setup_test_db;
+
+    #[tokio::test]
+    async fn test_user_crud() {
+        let db = setup_test_db().await;
+
+        let created = CreateUser::execute(
+            &db,
+            serde_json::from_value(serde_json::json!({
+                "username": "alice",
+                "email": "alice@example.com",
+            }))
+            .unwrap(),
+        )
+        .await
+        .unwrap();
+
+        let by_email = GetUserByEmail::execute(&db, EmailInput::new("alice@example.com"))
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(by_email.id, created.id);
+        let current = GetCurrentLocalUser::execute(&db, ())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(current.username, "alice");
+
+        let updated = UpdateUser::execute(
+            &db,
+            IdWithBodyInput::new(
+                &created.id,
+                serde_json::from_value(serde_json::json!({ "displayName": "Alice" })).unwrap(),
+            ),
+        )
+        .await
+        .unwrap();
+        assert_eq!(updated.display_name.as_deref(), Some("Alice"));
+
+        let result = UpdateUserLastLogin::execute(&db, IdInput::new("missing")).await;
+        assert!(matches!(result, Err(AppError::NotFound(_))));
+
+        DeleteUser::execute(&db, IdInput::new(&created.id))
+            .await
+            .unwrap();
+        assert!(GetUsers::execute(&db, ()).await.unwrap().is_empty());
+    }
+}
@@ -6,16 +6,24 @@
 //!
 //! | 端点 | 方法 | 路径 | 说明 |
 //! |------|------|------|------|
-//! | GetWorkspaces | GET | /api/workspaces | 获取所有工作区 |
+//! | GetWorkspaces | GET | /api/workspaces | 获取工作区（启用认证时只返回所属的工作区） |
 //! | GetWorkspace | GET | /api/workspaces/:id | 获取单个工作区 |
-//! | CreateWorkspace | POST | /api/workspaces | 创建工作区 |
+//! | CreateWorkspace | POST | /api/workspaces | 创建工作区（启用认证时当前用户成为 owner） |
 //! | UpdateWorkspace | PUT | /api/workspaces/:id | 更新工作区 |
 //! | DeleteWorkspace | DELETE | /api/workspaces/:id | 删除工作区 |
 
-use sea_orm::DatabaseConnection;
-
-use super::{ApiEndpoint, IdInput, IdWithBodyInput, NoInput, NoOutput};
-use crate::db::workspace_db_fn;
+use sea_orm::{DatabaseConnection, TransactionTrait};
+
+use super::node::record_nodes_deleted;
+use super::{
+    Access, ApiEndpoint, IdInput, IdWithBodyInput, NoOutput, OptionalAuthWithBodyInput,
+    WorkspaceScope,
+};
+use crate::db::{node_db_fn, workspace_db_fn, workspace_member_db_fn};
+use crate::events::PendingEvents;
+use crate::types::auth::AuthUser;
+use crate::types::event::ChangeEvent;
+use crate::types::member::WorkspaceRole;
 use crate::types::workspace::{CreateWorkspaceRequest, UpdateWorkspaceRequest, WorkspaceResponse};
 use crate::AppResult;
 
@@ -23,7 +31,10 @@ use crate::AppResult;
 // GetWorkspaces - 获取所有工作区
 // ============================================================================
 
-/// 获取所有工作区
+/// 获取工作区
+///
+/// 启用认证时只返回当前用户所属的工作区，使用限定工作区的 API 令牌时只返回该工作区；
+/// 未启用认证的服务器和 Tauri 返回所有工作区。
 ///
 /// ## HTTP
 /// - Method: GET
@@ -32,20 +43,32 @@ use crate::AppResult;
 /// ## Tauri
 /// - Command: get_workspaces
 ///
+/// ## 参数
+/// - 当前请求的身份（来自访问令牌，可选）
+///
 /// ## 返回
 /// - 成功: Vec<WorkspaceResponse>
 /// - 失败: DatabaseError
 pub struct GetWorkspaces;
 
 impl ApiEndpoint for GetWorkspaces {
-    type Input = NoInput;
+    type Input = Option<AuthUser>;
     type Output = Vec<WorkspaceResponse>;
     const NAME: &'static str = "get_workspaces";
 
-    async fn execute(db: &DatabaseConnection, _: Self::Input) -> AppResult<Self::Output> {
-        workspace_db_fn::find_all(db)
-            .await
-            .map(|v| v.into_iter().map(Into::into).collect())
+    /// 只返回用户所属的工作区
+    fn access(_input: &Self::Input) -> Access {
+        Access::Authenticated
+    }
+
+    async fn execute(db: &DatabaseConnection, input: Self::Input) -> AppResult<Self::Output> {
+        let scope = WorkspaceScope::resolve(db, input.as_ref(), WorkspaceRole::Viewer).await?;
+        let workspaces = workspace_db_fn::find_all(db).await?;
+        Ok(workspaces
+            .into_iter()
+            .filter(|w| scope.contains(&w.id))
+            .map(Into::into)
+            .collect())
     }
 }
 
@@ -75,6 +98,10 @@ impl ApiEndpoint for GetWorkspace {
     type Output = Option<WorkspaceResponse>;
     const NAME: &'static str = "get_workspace";
 
+    fn access(input: &Self::Input) -> Access {
+        Access::Workspace(input.id.clone(), WorkspaceRole::Viewer)
+    }
+
     async fn execute(db: &DatabaseConnection, input: Self::Input) -> AppResult<Self::Output> {
         workspace_db_fn::find_by_id(db, &input.id)
             .await
@@ -88,6 +115,8 @@ impl ApiEndpoint for GetWorkspace {
 
 /// 创建工作区
 ///
+/// 启用认证时当前用户成为工作区的 owner。
+///
 /// ## HTTP
 /// - Method: POST
 /// - Path: /api/workspaces
@@ -104,6 +133,7 @@ impl ApiEndpoint for GetWorkspace {
 /// - language: 语言（可选）
 /// - members: 成员列表（可选）
 /// - owner: 所有者（可选）
+/// - 当前请求的身份（来自访问令牌，可选）
 ///
 /// ## 返回
 /// - 成功: WorkspaceResponse
@@ -111,18 +141,45 @@ impl ApiEndpoint for GetWorkspace {
 pub struct CreateWorkspace;
 
 impl ApiEndpoint for CreateWorkspace {
-    type Input = CreateWorkspaceRequest;
+    type Input = OptionalAuthWithBodyInput<CreateWorkspaceRequest>;
     type Output = WorkspaceResponse;
     const NAME: &'static str = "create_workspace";
 
+    fn access(_input: &Self::Input) -> Access {
+        Access::NewWorkspace
+    }
+
     async fn execute(db: &DatabaseConnection, input: Self::Input) -> AppResult<Self::Output> {
         // 生成 UUID
         let id = uuid::Uuid::new_v4().to_string();
 
         // 调用数据库函数创建
-        workspace_db_fn::create(db, id, input.title, input.description)
-            .await
-            .map(Into::into)
+        let request = input.body;
+        let txn = db.begin().await?;
+        let mut pending = PendingEvents::new();
+        let workspace =
+            workspace_db_fn::create(&txn, id, request.title, request.description).await?;
+        if let Some(auth) = input.auth {
+            workspace_member_db_fn::set_role(
+                &txn,
+                &workspace.id,
+                &auth.user_id,
+                WorkspaceRole::Owner,
+            )
+            .await?;
+        }
+
+        pending
+            .record(
+                &txn,
+                ChangeEvent::WorkspaceCreated {
+                    workspace_id: workspace.id.clone(),
+                },
+            )
+            .await?;
+        txn.commit().await?;
+        pending.publish();
+        Ok(workspace.into())
     }
 }
 
@@ -157,10 +214,28 @@ impl ApiEndpoint for UpdateWorkspace {
     type Output = WorkspaceResponse;
     const NAME: &'static str = "update_workspace";
 
+    fn access(input: &Self::Input) -> Access {
+        Access::Workspace(input.id.clone(), WorkspaceRole::Owner)
+    }
+
     async fn execute(db: &DatabaseConnection, input: Self::Input) -> AppResult<Self::Output> {
-        workspace_db_fn::update(db, &input.id, input.body.title, input.body.description)
-            .await
-            .map(Into::into)
+        let txn = db.begin().await?;
+        let mut pending = PendingEvents::new();
+        let workspace =
+            workspace_db_fn::update(&txn, &input.id, input.body.title, input.body.description)
+                .await?;
+
+        pending
+            .record(
+                &txn,
+                ChangeEvent::WorkspaceUpdated {
+                    workspace_id: workspace.id.clone(),
+                },
+            )
+            .await?;
+        txn.commit().await?;
+        pending.publish();
+        Ok(workspace.into())
     }
 }
 
@@ -190,8 +265,29 @@ impl ApiEndpoint for DeleteWorkspace {
     type Output = NoOutput;
     const NAME: &'static str = "delete_workspace";
 
+    fn access(input: &Self::Input) -> Access {
+        Access::Workspace(input.id.clone(), WorkspaceRole::Owner)
+    }
+
     async fn execute(db: &DatabaseConnection, input: Self::Input) -> AppResult<Self::Output> {
-        workspace_db_fn::delete(db, &input.id).await
+        let txn = db.begin().await?;
+        let mut pending = PendingEvents::new();
+        // 节点随工作区级联删除，逐个记录后再记录工作区删除
+        let nodes = node_db_fn::find_by_workspace(&txn, &input.id).await?;
+        workspace_db_fn::delete(&txn, &input.id).await?;
+
+        record_nodes_deleted(&txn, &mut pending, nodes).await?;
+        pending
+            .record(
+                &txn,
+                ChangeEvent::WorkspaceDeleted {
+                    workspace_id: input.id,
+                },
+            )
+            .await?;
+        txn.commit().await?;
+        pending.publish();
+        Ok(())
     }
 }
 
@@ -202,7 +298,10 @@ impl ApiEndpoint for DeleteWorkspace {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::db::change_log_db_fn;
     use crate::db::test_utils::setup_test_db;
+    use crate::types::change_log::{ChangeEntity, ChangeOperation};
+    use crate::types::node::NodeType;
 
     /// Property 1: CRUD Round-Trip Consistency (Workspace)
     /// Feature: rust-api-macro, Property 1
@@ -223,7 +322,10 @@ mod tests {
             owner: None,
         };
 
-        let created = CreateWorkspace::execute(&db, create_input).await.unwrap();
+        let created =
+            CreateWorkspace::execute(&db, OptionalAuthWithBodyInput::anonymous(create_input))
+                .await
+                .unwrap();
         assert_eq!(created.title, "测试工作区");
         assert_eq!(created.description, "测试描述");
 
@@ -242,7 +344,7 @@ mod tests {
     async fn test_get_workspaces_empty() {
         let db = setup_test_db().await;
 
-        let result = GetWorkspaces::execute(&db, ()).await.unwrap();
+        let result = GetWorkspaces::execute(&db, None).await.unwrap();
         assert!(result.is_empty());
     }
 
@@ -261,10 +363,12 @@ mod tests {
                 members: None,
                 owner: None,
             };
-            CreateWorkspace::execute(&db, input).await.unwrap();
+            CreateWorkspace::execute(&db, OptionalAuthWithBodyInput::anonymous(input))
+                .await
+                .unwrap();
         }
 
-        let result = GetWorkspaces::execute(&db, ()).await.unwrap();
+        let result = GetWorkspaces::execute(&db, None).await.unwrap();
         assert_eq!(result.len(), 3);
     }
 
@@ -292,7 +396,10 @@ mod tests {
             members: None,
             owner: None,
         };
-        let created = CreateWorkspace::execute(&db, create_input).await.unwrap();
+        let created =
+            CreateWorkspace::execute(&db, OptionalAuthWithBodyInput::anonymous(create_input))
+                .await
+                .unwrap();
 
         // 2. 更新
         let update_input = IdWithBodyInput::new(
@@ -351,7 +458,22 @@ mod tests {
             members: None,
             owner: None,
         };
-        let created = CreateWorkspace::execute(&db, create_input).await.unwrap();
+        let created =
+            CreateWorkspace::execute(&db, OptionalAuthWithBodyInput::anonymous(create_input))
+                .await
+                .unwrap();
+
+        node_db_fn::create(
+            &db,
+            "n1".to_string(),
+            created.id.clone(),
+            None,
+            "节点".to_string(),
+            NodeType::File,
+            None,
+        )
+        .await
+        .unwrap();
 
         // 2. 删除
         let delete_input = IdInput::new(&created.id);
@@ -361,6 +483,22 @@ mod tests {
         let get_input = IdInput::new(&created.id);
         let result = GetWorkspace::execute(&db, get_input).await.unwrap();
         assert!(result.is_none());
+
+        // 4. 级联删除的节点和工作区都有删除记录
+        let deleted: Vec<(ChangeEntity, String)> = change_log_db_fn::find_since(&db, 0, 100)
+            .await
+            .unwrap()
+            .into_iter()
+            .filter(|e| e.operation == ChangeOperation::Delete)
+            .map(|e| (e.entity_type, e.entity_id))
+            .collect();
+        assert_eq!(
+            deleted,
+            vec![
+                (ChangeEntity::Node, "n1".to_string()),
+                (ChangeEntity::Workspace, created.id),
+            ]
+        );
     }
 
     #[tokio::test]
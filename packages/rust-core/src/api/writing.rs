@@ -0,0 +1,468 @@
+//! Writing API 端点
+//!
+//! 写作统计和写作目标相关的 API 端点实现。
+//! 中日韩文字按字计数、其他文字按词计数；统计按内容版本号缓存，计数规则见 `fn::writing`。
+//! 字数变化在保存内容（SaveContent）时按天记录。
+//!
+//! ## 端点列表
+//!
+//! | 端点 | 方法 | 路径 | 说明 |
+//! |------|------|------|------|
+//! | GetWritingStats | GET | /api/workspaces/:id/writing/stats?nodeId= | 按文档、文件夹和工作区汇总写作统计 |
+//! | GetWritingProgress | GET | /api/workspaces/:id/writing/progress?nodeId=&from=&to=&today= | 目标进度、连续天数和热力图 |
+//! | SetWritingGoal | PUT | /api/workspaces/:id/writing/goals | 设置工作区或节点的写作目标 |
+//! | DeleteWritingGoal | DELETE | /api/writing/goals/:id | 删除写作目标 |
+
+use std::collections::HashSet;
+
+use chrono::{Duration, NaiveDate};
+use sea_orm::DatabaseConnection;
+
+use super::{Access, ApiEndpoint, IdInput, IdWithBodyInput, NoOutput};
+use crate::db::{node_db_fn, writing_db_fn};
+use crate::r#fn::writing::{
+    aggregate_writing_stats, daily_writing, subtree_ids, writing_goal_progress, writing_heatmap,
+    writing_streak,
+};
+use crate::types::member::WorkspaceRole;
+use crate::types::node::NodeModel;
+use crate::types::writing::{
+    SetWritingGoalRequest, WritingGoalKind, WritingGoalResponse, WritingProgressQuery,
+    WritingProgressResponse, WritingStatsQuery, WritingStatsResponse,
+};
+use crate::{AppError, AppResult};
+
+/// 解析可选的日期参数
+fn parse_date_param(date: Option<&str>) -> AppResult<Option<NaiveDate>> {
+    date.map(|d| {
+        NaiveDate::parse_from_str(d.trim(), "%Y-%m-%d")
+            .map_err(|_| AppError::validation(format!("无效的日期: {}", d)))
+    })
+    .transpose()
+}
+
+/// 检查节点属于工作区
+fn ensure_node_in_workspace(nodes: &[NodeModel], node_id: &str) -> AppResult<()> {
+    if nodes.iter().any(|n| n.id == node_id) {
+        Ok(())
+    } else {
+        Err(AppError::not_found(format!("Node {}", node_id)))
+    }
+}
+
+// ============================================================================
+// GetWritingStats - 写作统计
+// ============================================================================
+
+/// 统计工作区（或指定文件夹）内文档的字数、段落数和阅读时间
+///
+/// 只统计文件、日记、Wiki、待办和笔记节点；文件夹的统计为其所有子孙文档的合计。
+///
+/// ## HTTP
+/// - Method: GET
+/// - Path: /api/workspaces/:workspace_id/writing/stats?nodeId=
+///
+/// ## Tauri
+/// - Command: get_writing_stats
+///
+/// ## 参数
+/// - id: 工作区 ID
+/// - node_id: 只统计该节点及其子孙节点（可选）
+///
+/// ## 返回
+/// - 成功: WritingStatsResponse（合计、文件夹和文档统计）
+/// - 失败: NotFound（节点不在工作区内）, DatabaseError
+pub struct GetWritingStats;
+
+impl ApiEndpoint for GetWritingStats {
+    type Input = IdWithBodyInput<WritingStatsQuery>;
+    type Output = WritingStatsResponse;
+    const NAME: &'static str = "get_writing_stats";
+
+    fn access(input: &Self::Input) -> Access {
+        Access::Workspace(input.id.clone(), WorkspaceRole::Viewer)
+    }
+
+    async fn execute(db: &DatabaseConnection, input: Self::Input) -> AppResult<Self::Output> {
+        let (nodes, stats) = writing_db_fn::workspace_stats(db, &input.id).await?;
+
+        let scope = input.body.node_id.as_deref();
+        if let Some(node_id) = scope {
+            ensure_node_in_workspace(&nodes, node_id)?;
+        }
+        Ok(aggregate_writing_stats(&nodes, &stats, scope))
+    }
+}
+
+// ============================================================================
+// GetWritingProgress - 写作进度
+// ============================================================================
+
+/// 查询写作目标的完成情况、连续写作天数和热力图
+///
+/// 总字数目标按目标范围内的当前字数计算，每日目标按今天的净增字数计算。
+/// 连续天数和热力图按查询范围统计：同一范围有每日目标时以达成目标计，否则以有净增字数计。
+///
+/// ## HTTP
+/// - Method: GET
+/// - Path: /api/workspaces/:workspace_id/writing/progress?nodeId=&from=2026-01-01&to=2026-12-31
+///
+/// ## Tauri
+/// - Command: get_writing_progress
+///
+/// ## 参数
+/// - id: 工作区 ID
+/// - node_id: 连续天数和热力图只统计该节点及其子孙节点（可选）
+/// - from / to: 热力图日期范围（可选，默认最近一年）
+/// - today: 作为"今天"的日期 `YYYY-MM-DD`（可选，默认本地当天）
+///
+/// ## 返回
+/// - 成功: WritingProgressResponse
+/// - 失败: NotFound（节点不在工作区内）, ValidationError（日期无效）, DatabaseError
+pub struct GetWritingProgress;
+
+impl ApiEndpoint for GetWritingProgress {
+    type Input = IdWithBodyInput<WritingProgressQuery>;
+    type Output = WritingProgressResponse;
+    const NAME: &'static str = "get_writing_progress";
+
+    fn access(input: &Self::Input) -> Access {
+        Access::Workspace(input.id.clone(), WorkspaceRole::Viewer)
+    }
+
+    async fn execute(db: &DatabaseConnection, input: Self::Input) -> AppResult<Self::Output> {
+        let query = input.body;
+        let today = parse_date_param(query.today.as_deref())?
+            .unwrap_or_else(|| chrono::Local::now().date_naive());
+        let to = parse_date_param(query.to.as_deref())?.unwrap_or(today);
+        let from = parse_date_param(query.from.as_deref())?.unwrap_or(to - Duration::days(364));
+        if from > to {
+            return Err(AppError::validation("起始日期不能晚于结束日期"));
+        }
+
+        let (nodes, stats) = writing_db_fn::workspace_stats(db, &input.id).await?;
+        if let Some(node_id) = query.node_id.as_deref() {
+            ensure_node_in_workspace(&nodes, node_id)?;
+        }
+        let rows = writing_db_fn::find_progress(db, &input.id).await?;
+        let goals = writing_db_fn::find_goals(db, &input.id).await?;
+
+        let scope_ids = |node_id: Option<&str>| -> Option<HashSet<String>> {
+            node_id.map(|id| subtree_ids(&nodes, id))
+        };
+        let today_net = |node_id: Option<&str>| {
+            daily_writing(&rows, scope_ids(node_id).as_ref())
+                .get(&today)
+                .map_or(0, |(added, removed)| added - removed)
+        };
+
+        let goal_progress = goals
+            .into_iter()
+            .map(|goal| {
+                let current = match goal.kind {
+                    WritingGoalKind::Total => {
+                        aggregate_writing_stats(&nodes, &stats, goal.node_id.as_deref())
+                            .total
+                            .words as i64
+                    }
+                    WritingGoalKind::Daily => today_net(goal.node_id.as_deref()),
+                };
+                writing_goal_progress(goal.into(), current, today)
+            })
+            .collect::<Vec<_>>();
+
+        let daily_target = goal_progress
+            .iter()
+            .find(|p| p.goal.kind == WritingGoalKind::Daily && p.goal.node_id == query.node_id)
+            .map(|p| p.goal.target);
+        let days = daily_writing(&rows, scope_ids(query.node_id.as_deref()).as_ref());
+
+        Ok(WritingProgressResponse {
+            today: today.format("%Y-%m-%d").to_string(),
+            today_words: days
+                .get(&today)
+                .map_or(0, |(added, removed)| added - removed),
+            streak: writing_streak(&days, daily_target, today),
+            goals: goal_progress,
+            heatmap: writing_heatmap(&days, daily_target, from, to),
+        })
+    }
+}
+
+// ============================================================================
+// SetWritingGoal - 设置写作目标
+// ============================================================================
+
+/// 设置工作区或节点的写作目标
+///
+/// 同一范围的同类目标只有一个，再次设置时覆盖目标字数和截止日期。
+///
+/// ## HTTP
+/// - Method: PUT
+/// - Path: /api/workspaces/:workspace_id/writing/goals
+/// - Body: SetWritingGoalRequest
+///
+/// ## Tauri
+/// - Command: set_writing_goal
+///
+/// ## 参数
+/// - id: 工作区 ID
+/// - node_id: 目标节点 ID（可选，默认整个工作区）
+/// - kind: `total`（总字数）/ `daily`（每日字数）
+/// - target: 目标字数（大于 0）
+/// - deadline: 截止日期 `YYYY-MM-DD`（可选）
+///
+/// ## 返回
+/// - 成功: WritingGoalResponse
+/// - 失败: NotFound（节点不在工作区内）, ValidationError, DatabaseError
+pub struct SetWritingGoal;
+
+impl ApiEndpoint for SetWritingGoal {
+    type Input = IdWithBodyInput<SetWritingGoalRequest>;
+    type Output = WritingGoalResponse;
+    const NAME: &'static str = "set_writing_goal";
+
+    fn access(input: &Self::Input) -> Access {
+        Access::Workspace(input.id.clone(), WorkspaceRole::Editor)
+    }
+
+    async fn execute(db: &DatabaseConnection, input: Self::Input) -> AppResult<Self::Output> {
+        let request = input.body;
+        if request.target <= 0 {
+            return Err(AppError::validation("目标字数必须大于 0"));
+        }
+        parse_date_param(request.deadline.as_deref())?;
+        if let Some(node_id) = request.node_id.as_deref() {
+            node_db_fn::find_by_id(db, node_id)
+                .await?
+                .filter(|node| node.workspace_id == input.id)
+                .ok_or_else(|| AppError::not_found(format!("Node {}", node_id)))?;
+        }
+
+        writing_db_fn::set_goal(db, &input.id, request)
+            .await
+            .map(Into::into)
+    }
+}
+
+// ============================================================================
+// DeleteWritingGoal - 删除写作目标
+// ============================================================================
+
+/// 删除写作目标
+///
+/// ## HTTP
+/// - Method: DELETE
+/// - Path: /api/writing/goals/:id
+///
+/// ## Tauri
+/// - Command: delete_writing_goal
+///
+/// ## 参数
+/// - id: 目标 ID
+///
+/// ## 返回
+/// - 成功: ()
+/// - 失败: NotFound, DatabaseError
+pub struct DeleteWritingGoal;
+
+impl ApiEndpoint for DeleteWritingGoal {
+    type Input = IdInput;
+    type Output = NoOutput;
+    const NAME: &'static str = "delete_writing_goal";
+
+    fn access(input: &Self::Input) -> Access {
+        Access::WritingGoal(input.id.clone(), WorkspaceRole::Editor)
+    }
+
+    async fn execute(db: &DatabaseConnection, input: Self::Input) -> AppResult<Self::Output> {
+        writing_db_fn::delete_goal(db, &input.id).await
+    }
+}
+
+// ============================================================================
+// 测试
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::SaveContent;
+    use crate::db::test_utils::setup_test_db;
+    use crate::db::{content_db_fn, workspace_db_fn};
+    use crate::types::content::SaveContentRequest;
+    use crate::types::node::NodeType;
+
+    #[tokio::test]
+    async fn test_get_writing_stats() {
+        let db = setup_test_db().await;
+        let workspace_id = uuid::Uuid::new_v4().to_string();
+        workspace_db_fn::create(&db, workspace_id.clone(), "测试工作区".to_string(), None)
+            .await
+            .unwrap();
+
+        let nodes = [
+            ("novel", None, NodeType::Folder, None),
+            (
+                "chapter",
+                Some("novel"),
+                NodeType::File,
+                Some(
+                    r#"{"root":{"children":[{"type":"paragraph","children":[{"type":"text","text":"春眠不觉晓"}]},{"type":"paragraph","children":[{"type":"text","text":"Spring dawn"}]}]}}"#,
+                ),
+            ),
+            ("notes", None, NodeType::Note, Some("随手记 memo")),
+        ];
+        for (id, parent, node_type, text) in nodes {
+            node_db_fn::create(
+                &db,
+                id.to_string(),
+                workspace_id.clone(),
+                parent.map(str::to_string),
+                id.to_string(),
+                node_type,
+                None,
+            )
+            .await
+            .unwrap();
+            if let Some(text) = text {
+                content_db_fn::create(&db, format!("c-{}", id), id.to_string(), text.to_string())
+                    .await
+                    .unwrap();
+            }
+        }
+
+        let input = |node_id: Option<&str>| {
+            IdWithBodyInput::new(
+                &workspace_id,
+                WritingStatsQuery {
+                    node_id: node_id.map(str::to_string),
+                },
+            )
+        };
+
+        let all = GetWritingStats::execute(&db, input(None)).await.unwrap();
+        assert_eq!(all.total.documents, 2);
+        assert_eq!(all.total.cjk_characters, 8);
+        assert_eq!(all.total.latin_words, 3);
+        assert_eq!(all.total.paragraphs, 3);
+        assert_eq!(all.folders.len(), 1);
+        assert_eq!(all.folders[0].stats.words, 7);
+
+        let novel = GetWritingStats::execute(&db, input(Some("novel")))
+            .await
+            .unwrap();
+        assert_eq!(novel.total.words, 7);
+        assert_eq!(novel.documents[0].node_id, "chapter");
+
+        let result = GetWritingStats::execute(&db, input(Some("missing"))).await;
+        assert!(matches!(result, Err(AppError::NotFound(_))));
+    }
+
+    #[tokio::test]
+    async fn test_writing_goals_and_progress() {
+        let db = setup_test_db().await;
+        let workspace_id = uuid::Uuid::new_v4().to_string();
+        workspace_db_fn::create(&db, workspace_id.clone(), "测试工作区".to_string(), None)
+            .await
+            .unwrap();
+        for (id, parent, node_type) in [
+            ("novel", None, NodeType::Folder),
+            ("chapter", Some("novel"), NodeType::File),
+            ("journal", None, NodeType::Diary),
+        ] {
+            node_db_fn::create(
+                &db,
+                id.to_string(),
+                workspace_id.clone(),
+                parent.map(str::to_string),
+                id.to_string(),
+                node_type,
+                None,
+            )
+            .await
+            .unwrap();
+        }
+
+        let save = |node_id: &str, text: &str| {
+            SaveContent::execute(
+                &db,
+                SaveContentRequest {
+                    node_id: node_id.to_string(),
+                    content: text.to_string(),
+                    expected_version: None,
+                    content_type: None,
+                },
+            )
+        };
+        save("chapter", "一二三四五六七八九十").await.unwrap();
+        save("chapter", "一二三四五六七八").await.unwrap();
+        save("journal", "今天 wrote a lot").await.unwrap();
+
+        let goal = |node_id: Option<&str>, kind, target| {
+            SetWritingGoal::execute(
+                &db,
+                IdWithBodyInput::new(
+                    &workspace_id,
+                    SetWritingGoalRequest {
+                        node_id: node_id.map(str::to_string),
+                        kind,
+                        target,
+                        deadline: None,
+                    },
+                ),
+            )
+        };
+        goal(Some("novel"), WritingGoalKind::Total, 16)
+            .await
+            .unwrap();
+        goal(None, WritingGoalKind::Daily, 5).await.unwrap();
+        // 同一范围的同类目标被覆盖
+        let daily = goal(None, WritingGoalKind::Daily, 10).await.unwrap();
+        assert!(matches!(
+            goal(None, WritingGoalKind::Daily, 0).await,
+            Err(AppError::ValidationError(_))
+        ));
+        assert!(matches!(
+            goal(Some("missing"), WritingGoalKind::Total, 1).await,
+            Err(AppError::NotFound(_))
+        ));
+
+        let progress = GetWritingProgress::execute(
+            &db,
+            IdWithBodyInput::new(&workspace_id, WritingProgressQuery::default()),
+        )
+        .await
+        .unwrap();
+        assert_eq!(progress.goals.len(), 2);
+        let total = &progress.goals[0];
+        assert_eq!(total.goal.kind, WritingGoalKind::Total);
+        assert_eq!((total.current, total.percent), (8, 50));
+        // 10 - 2 + 5 = 13 字
+        assert_eq!(progress.today_words, 13);
+        assert!(progress.goals[1].achieved);
+        assert_eq!(progress.streak.current, 1);
+        assert_eq!(progress.heatmap.len(), 1);
+        assert_eq!(progress.heatmap[0].words_removed, 2);
+
+        let chapter = GetWritingProgress::execute(
+            &db,
+            IdWithBodyInput::new(
+                &workspace_id,
+                WritingProgressQuery {
+                    node_id: Some("novel".to_string()),
+                    ..Default::default()
+                },
+            ),
+        )
+        .await
+        .unwrap();
+        assert_eq!(chapter.today_words, 8);
+
+        DeleteWritingGoal::execute(&db, IdInput::new(&daily.id))
+            .await
+            .unwrap();
+        let result = DeleteWritingGoal::execute(&db, IdInput::new(&daily.id)).await;
+        assert!(matches!(result, Err(AppError::NotFound(_))));
+    }
+}
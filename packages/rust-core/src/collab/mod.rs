@@ -0,0 +1,665 @@
+//! 协同编辑
+//!
+//! 多人同时编辑同一节点的内容：客户端通过 WebSocket（`/api/nodes/:node_id/collab`）
+//! 按 y-protocols 同步协议交换兼容 Yjs 的 CRDT 更新（文档结构见 `r#fn::content::content_crdt_fn`，
+//! Lexical 内容与 `@lexical/yjs` 相同）。
+//!
+//! - 更新追加到 `content_updates` 表；同一节点的连接组成一个房间，房间在内存中缓存合并后的文档
+//! - 更新和 awareness（光标、选区等）转发给房间内的其他连接
+//! - 更新累积到一定条数、以及最后一个连接断开时压缩：合并为一条，并把内容写入 `contents.content`；
+//!   合并结果不是有效内容时不压缩
+//! - 内容被协同编辑之外的途径修改（保存、标签重命名等）后，下次同步时把差异作为一条更新合入
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, OnceLock};
+
+use futures_util::{SinkExt, StreamExt};
+use sea_orm::{DatabaseConnection, TransactionTrait};
+use tokio::sync::broadcast::{self, error::RecvError};
+use tracing::{debug, warn};
+use warp::ws::{Message as WsMessage, WebSocket};
+use yrs::sync::{Message, MessageReader, SyncMessage};
+use yrs::updates::decoder::DecoderV1;
+use yrs::updates::encoder::Encode;
+
+use crate::api::content::persist_content;
+use crate::db::{content_db_fn, content_update_db_fn};
+use crate::r#fn::content::CrdtDocument;
+use crate::types::content::{ContentModel, ContentResponse};
+use crate::{AppError, AppResult};
+
+/// 更新累积到该条数时压缩
+const COMPACT_THRESHOLD: u64 = 200;
+
+/// 每个连接最多缓存的待转发消息数量
+const ROOM_CAPACITY: usize = 256;
+
+// ============================================================================
+// 房间
+// ============================================================================
+
+/// 转发给房间内其他连接的消息：(发送者, 编码后的消息)
+type Relay = (u64, Vec<u8>);
+
+/// 节点的文档（第一次使用时加载）；锁同时串行化文档的初始化、更新和压缩
+type DocumentSlot = Arc<tokio::sync::Mutex<Option<LoadedDocument>>>;
+
+/// 同一节点的连接
+struct Room {
+    sender: broadcast::Sender<Relay>,
+    peers: usize,
+    document: DocumentSlot,
+}
+
+/// 已加载的文档
+struct LoadedDocument {
+    document: CrdtDocument,
+    /// 文档已合入的内容版本号
+    synced: i32,
+}
+
+/// 全部房间（节点 ID -> 房间）
+fn rooms() -> &'static Mutex<HashMap<String, Room>> {
+    static ROOMS: OnceLock<Mutex<HashMap<String, Room>>> = OnceLock::new();
+    ROOMS.get_or_init(Default::default)
+}
+
+/// 加入房间，返回连接 ID 和消息通道
+fn join(node_id: &str) -> (u64, broadcast::Sender<Relay>, broadcast::Receiver<Relay>) {
+    static NEXT_PEER: AtomicU64 = AtomicU64::new(1);
+    let peer = NEXT_PEER.fetch_add(1, Ordering::Relaxed);
+
+    let mut rooms = rooms().lock().unwrap();
+    let room = rooms.entry(node_id.to_string()).or_insert_with(|| Room {
+        sender: broadcast::channel(ROOM_CAPACITY).0,
+        peers: 0,
+        document: DocumentSlot::default(),
+    });
+    room.peers += 1;
+    (peer, room.sender.clone(), room.sender.subscribe())
+}
+
+/// 离开房间，返回是否是最后一个连接（房间在压缩后由 `close` 移除）
+fn leave(node_id: &str) -> bool {
+    let mut rooms = rooms().lock().unwrap();
+    let Some(room) = rooms.get_mut(node_id) else {
+        return true;
+    };
+    room.peers -= 1;
+    room.peers == 0
+}
+
+/// 移除没有连接的房间，释放缓存的文档
+fn close(node_id: &str) {
+    let mut rooms = rooms().lock().unwrap();
+    if rooms.get(node_id).is_some_and(|room| room.peers == 0) {
+        rooms.remove(node_id);
+    }
+}
+
+/// 向房间内的连接转发消息（发送者为 0 时所有连接都会收到）
+fn relay(node_id: &str, from: u64, message: &Message) {
+    if let Some(room) = rooms().lock().unwrap().get(node_id) {
+        // 没有订阅者时返回错误，忽略即可
+        let _ = room.sender.send((from, message.encode_v1()));
+    }
+}
+
+/// 节点的文档槽：有房间时为房间缓存的文档，否则为只在本次使用的空槽
+fn document_slot(node_id: &str) -> DocumentSlot {
+    rooms()
+        .lock()
+        .unwrap()
+        .get(node_id)
+        .map(|room| room.document.clone())
+        .unwrap_or_default()
+}
+
+// ============================================================================
+// 文档
+// ============================================================================
+
+/// 从数据库加载文档；第一次协同编辑时以当前内容创建文档
+async fn load_document(
+    db: &DatabaseConnection,
+    node_id: &str,
+    content: Option<&ContentModel>,
+) -> AppResult<LoadedDocument> {
+    let updates = content_update_db_fn::find_by_node_id(db, node_id).await?;
+    if updates.is_empty() {
+        let (text, version) = content.map_or(("", 0), |c| (c.content.as_str(), c.version));
+        let document = CrdtDocument::from_content(text);
+        content_update_db_fn::append(db, node_id, document.state(), version).await?;
+        return Ok(LoadedDocument {
+            document,
+            synced: version,
+        });
+    }
+
+    let synced = updates.iter().map(|u| u.content_version).max().unwrap_or(0);
+    let document =
+        CrdtDocument::from_updates(&updates.iter().map(|u| &u.data).collect::<Vec<_>>())?;
+    Ok(LoadedDocument { document, synced })
+}
+
+/// 取得节点的文档，并合入其他途径对内容的修改
+///
+/// 房间缓存了文档时只查询内容版本；内容在文档合入之后被修改过时，
+/// 把差异作为一条更新写入并转发给在线的连接。
+async fn refresh<'a>(
+    db: &DatabaseConnection,
+    node_id: &str,
+    slot: &'a mut Option<LoadedDocument>,
+) -> AppResult<&'a mut LoadedDocument> {
+    let content = content_db_fn::find_by_node_id(db, node_id).await?;
+    let loaded = match slot {
+        Some(loaded) => loaded,
+        None => slot.insert(load_document(db, node_id, content.as_ref()).await?),
+    };
+
+    if let Some(content) = content.filter(|c| c.version > loaded.synced) {
+        if let Some(update) = loaded.document.replace_content(&content.content) {
+            debug!(
+                "合入外部修改: node_id={}, version={}",
+                node_id, content.version
+            );
+            relay(
+                node_id,
+                0,
+                &Message::Sync(SyncMessage::Update(update.clone())),
+            );
+            content_update_db_fn::append(db, node_id, update, content.version).await?;
+        }
+        loaded.synced = content.version;
+    }
+    Ok(loaded)
+}
+
+/// 加载节点的 CRDT 文档状态
+pub async fn load_state(db: &DatabaseConnection, node_id: &str) -> AppResult<Vec<u8>> {
+    let slot = document_slot(node_id);
+    let mut slot = slot.lock().await;
+    Ok(refresh(db, node_id, &mut slot).await?.document.state())
+}
+
+/// 保存客户端的更新，累积到一定条数时压缩
+pub async fn apply_update(
+    db: &DatabaseConnection,
+    node_id: &str,
+    update: Vec<u8>,
+) -> AppResult<()> {
+    {
+        let slot = document_slot(node_id);
+        let slot = slot.lock().await;
+        let synced = match slot.as_ref() {
+            Some(loaded) => {
+                loaded.document.apply_update(&update)?;
+                loaded.synced
+            }
+            None => {
+                // 单独应用能解码即可，依赖的更新可能还没有到达
+                CrdtDocument::from_updates(&[&update])?;
+                content_update_db_fn::synced_version(db, node_id)
+                    .await?
+                    .unwrap_or(0)
+            }
+        };
+        content_update_db_fn::append(db, node_id, update, synced).await?;
+    }
+
+    if content_update_db_fn::count_by_node_id(db, node_id).await? >= COMPACT_THRESHOLD {
+        compact(db, node_id).await?;
+    }
+    Ok(())
+}
+
+/// 压缩节点的 CRDT 更新，并把文档内容写入内容快照
+///
+/// 没有协同编辑记录时返回 None。原内容是 JSON 而合并结果不是有效的 JSON 时返回
+/// ValidationError，更新保持原样。内容在文档合入之后被其他途径修改过时不覆盖快照，
+/// 差异在下次同步时合入。
+pub async fn compact(db: &DatabaseConnection, node_id: &str) -> AppResult<Option<ContentResponse>> {
+    let slot = document_slot(node_id);
+    let mut slot = slot.lock().await;
+    let updates = content_update_db_fn::find_by_node_id(db, node_id).await?;
+    let Some(last) = updates.last() else {
+        return Ok(None);
+    };
+
+    let mut synced = updates.iter().map(|u| u.content_version).max().unwrap_or(0);
+    let state = match slot.as_mut() {
+        Some(loaded) => {
+            synced = synced.max(loaded.synced);
+            loaded.document.state()
+        }
+        None => {
+            let updates: Vec<&Vec<u8>> = updates.iter().map(|u| &u.data).collect();
+            CrdtDocument::from_updates(&updates)?.state()
+        }
+    };
+    let text = CrdtDocument::from_updates(&[&state])?.content();
+
+    let current = content_db_fn::find_by_node_id(db, node_id).await?;
+    if !is_valid_snapshot(current.as_ref(), &text) {
+        warn!("协同编辑内容不是有效的 JSON，未压缩: node_id={}", node_id);
+        return Err(AppError::validation(
+            "协同编辑的内容不是有效的 JSON，未写入内容快照",
+        ));
+    }
+
+    let merged = if updates.len() > 1 {
+        let txn = db.begin().await?;
+        let merged = content_update_db_fn::replace(&txn, node_id, last.id, state, synced).await?;
+        txn.commit().await?;
+        merged
+    } else {
+        last.clone()
+    };
+
+    match &current {
+        Some(content) if content.version > synced || content.content == text => {
+            return Ok(current.map(ContentResponse::from));
+        }
+        _ => {}
+    }
+
+    let expected_version = current.as_ref().map(|c| c.version);
+    let saved = persist_content(db, current, node_id.to_string(), text, expected_version).await?;
+    content_update_db_fn::set_content_version(db, merged.id, saved.version).await?;
+    if let Some(loaded) = slot.as_mut() {
+        loaded.synced = saved.version;
+    }
+    Ok(Some(saved))
+}
+
+/// 内容快照原本是 JSON 时，合并后的内容也必须是有效的 JSON
+fn is_valid_snapshot(current: Option<&ContentModel>, text: &str) -> bool {
+    let is_json = |text: &str| serde_json::from_str::<serde_json::Value>(text).is_ok();
+    current.is_none_or(|c| !is_json(&c.content)) || is_json(text)
+}
+
+// ============================================================================
+// 连接
+// ============================================================================
+
+/// 处理一个 WebSocket 连接，直到连接关闭
+///
+/// 连接建立后服务器先发送自己的状态向量（SyncStep1），客户端回复服务器缺少的更新；
+/// 客户端发送 SyncStep1 时服务器回复客户端缺少的更新（SyncStep2）。
+pub async fn serve(db: Arc<DatabaseConnection>, node_id: String, socket: WebSocket) {
+    let (peer, sender, mut receiver) = join(&node_id);
+    debug!("协同编辑连接: node_id={}, peer={}", node_id, peer);
+
+    if let Err(e) = session(&db, &node_id, peer, &sender, &mut receiver, socket).await {
+        warn!("协同编辑连接出错: node_id={}, {}", node_id, e);
+    }
+
+    if leave(&node_id) {
+        if let Err(e) = compact(&db, &node_id).await {
+            warn!("压缩 CRDT 更新失败: node_id={}, {}", node_id, e);
+        }
+        close(&node_id);
+    }
+}
+
+async fn session(
+    db: &DatabaseConnection,
+    node_id: &str,
+    peer: u64,
+    sender: &broadcast::Sender<Relay>,
+    receiver: &mut broadcast::Receiver<Relay>,
+    socket: WebSocket,
+) -> AppResult<()> {
+    let (mut sink, mut stream) = socket.split();
+    let send_error = |e: warp::Error| AppError::internal(format!("发送失败: {}", e));
+
+    let state_vector = {
+        let slot = document_slot(node_id);
+        let mut slot = slot.lock().await;
+        refresh(db, node_id, &mut slot)
+            .await?
+            .document
+            .state_vector()
+    };
+    let step1 = Message::Sync(SyncMessage::SyncStep1(state_vector));
+    sink.send(WsMessage::binary(step1.encode_v1()))
+        .await
+        .map_err(send_error)?;
+
+    loop {
+        tokio::select! {
+            incoming = stream.next() => {
+                let Some(incoming) = incoming else {
+                    return Ok(());
+                };
+                let incoming = incoming.map_err(|e| AppError::internal(format!("接收失败: {}", e)))?;
+                if incoming.is_close() {
+                    return Ok(());
+                }
+                if !incoming.is_binary() {
+                    continue;
+                }
+                for reply in handle_message(db, node_id, peer, sender, incoming.as_bytes()).await? {
+                    sink.send(WsMessage::binary(reply.encode_v1()))
+                        .await
+                        .map_err(send_error)?;
+                }
+            }
+            relayed = receiver.recv() => match relayed {
+                Ok((from, data)) if from != peer => {
+                    sink.send(WsMessage::binary(data)).await.map_err(send_error)?;
+                }
+                Ok(_) => {}
+                Err(RecvError::Lagged(skipped)) => {
+                    // 丢失了转发的更新：发送完整的文档状态，客户端重复应用没有影响
+                    warn!("协同编辑连接落后，丢失 {} 条消息: node_id={}", skipped, node_id);
+                    let state = load_state(db, node_id).await?;
+                    let full = Message::Sync(SyncMessage::Update(state));
+                    sink.send(WsMessage::binary(full.encode_v1()))
+                        .await
+                        .map_err(send_error)?;
+                }
+                Err(RecvError::Closed) => return Ok(()),
+            },
+        }
+    }
+}
+
+/// 处理客户端的一条 WebSocket 消息（可能包含多条协议消息），返回需要回复的消息
+async fn handle_message(
+    db: &DatabaseConnection,
+    node_id: &str,
+    peer: u64,
+    sender: &broadcast::Sender<Relay>,
+    data: &[u8],
+) -> AppResult<Vec<Message>> {
+    let mut decoder = DecoderV1::from(data);
+    let mut replies = Vec::new();
+    for message in MessageReader::new(&mut decoder) {
+        let message =
+            message.map_err(|e| AppError::validation(format!("无效的协同编辑消息: {}", e)))?;
+        match message {
+            Message::Sync(SyncMessage::SyncStep1(state_vector)) => {
+                let slot = document_slot(node_id);
+                let mut slot = slot.lock().await;
+                let loaded = refresh(db, node_id, &mut slot).await?;
+                let diff = loaded.document.diff(&state_vector.encode_v1())?;
+                replies.push(Message::Sync(SyncMessage::SyncStep2(diff)));
+            }
+            Message::Sync(SyncMessage::SyncStep2(update) | SyncMessage::Update(update)) => {
+                apply_update(db, node_id, update.clone()).await?;
+                let message = Message::Sync(SyncMessage::Update(update));
+                // 没有其他连接时返回错误，忽略即可
+                let _ = sender.send((peer, message.encode_v1()));
+            }
+            Message::Awareness(update) => {
+                let _ = sender.send((peer, Message::Awareness(update).encode_v1()));
+            }
+            // 服务器不保存 awareness 状态，也不使用认证消息
+            Message::AwarenessQuery | Message::Auth(_) | Message::Custom(..) => {}
+        }
+    }
+    Ok(replies)
+}
+
+// ============================================================================
+// 测试
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use yrs::types::text::YChange;
+    use yrs::updates::decoder::Decode;
+    use yrs::{Doc, GetString, Out, ReadTxn, Text, Transact, TransactionMut, Update, XmlTextRef};
+
+    use crate::api::{ApiEndpoint, CreateWorkspace, OptionalAuthWithBodyInput, SaveContent};
+    use crate::db::node_db_fn;
+    use crate::db::test_utils::setup_test_db;
+    use crate::server::build_routes;
+    use crate::types::config::AppConfig;
+    use crate::types::content::SaveContentRequest;
+    use crate::types::node::NodeType;
+    use crate::types::workspace::CreateWorkspaceRequest;
+
+    async fn create_node(db: &DatabaseConnection, text: &str) -> String {
+        let workspace = CreateWorkspace::execute(
+            db,
+            OptionalAuthWithBodyInput::anonymous(CreateWorkspaceRequest {
+                title: "协同".to_string(),
+                author: None,
+                description: None,
+                publisher: None,
+                language: None,
+                members: None,
+                owner: None,
+            }),
+        )
+        .await
+        .unwrap();
+        let node_id = uuid::Uuid::new_v4().to_string();
+        node_db_fn::create(
+            db,
+            node_id.clone(),
+            workspace.id,
+            None,
+            "文档".to_string(),
+            NodeType::File,
+            None,
+        )
+        .await
+        .unwrap();
+        save(db, &node_id, text).await;
+        node_id
+    }
+
+    async fn save(db: &DatabaseConnection, node_id: &str, text: &str) {
+        SaveContent::execute(
+            db,
+            SaveContentRequest {
+                node_id: node_id.to_string(),
+                content: text.to_string(),
+                expected_version: None,
+                content_type: None,
+            },
+        )
+        .await
+        .unwrap();
+    }
+
+    /// 测试用的 Yjs 客户端
+    struct Client {
+        ws: warp::test::WsClient,
+        doc: Doc,
+    }
+
+    impl Client {
+        async fn connect(db: &DatabaseConnection, node_id: &str) -> Self {
+            let routes = build_routes(
+                Arc::new(db.clone()),
+                Arc::new(AppConfig {
+                    auth_enabled: false,
+                    ..Default::default()
+                }),
+            );
+            let mut ws = warp::test::ws()
+                .path(&format!("/api/nodes/{}/collab", node_id))
+                .handshake(routes)
+                .await
+                .unwrap();
+            let doc = Doc::with_options(yrs::Options {
+                offset_kind: yrs::OffsetKind::Utf16,
+                ..Default::default()
+            });
+
+            // 服务器先发送状态向量，客户端请求服务器的全部内容
+            assert!(matches!(
+                Self::decode(ws.recv().await.unwrap()),
+                Message::Sync(SyncMessage::SyncStep1(_))
+            ));
+            let step1 = Message::Sync(SyncMessage::SyncStep1(doc.transact().state_vector()));
+            ws.send(WsMessage::binary(step1.encode_v1())).await;
+            let mut client = Self { ws, doc };
+            client.receive().await;
+            client
+        }
+
+        fn decode(message: WsMessage) -> Message {
+            Message::decode_v1(message.as_bytes()).unwrap()
+        }
+
+        /// 接收一条同步消息并应用到本地文档
+        async fn receive(&mut self) {
+            match Self::decode(self.ws.recv().await.unwrap()) {
+                Message::Sync(SyncMessage::SyncStep2(update) | SyncMessage::Update(update)) => {
+                    let update = Update::decode_v1(&update).unwrap();
+                    self.doc.transact_mut().apply_update(update).unwrap();
+                }
+                other => panic!("意外的消息: {:?}", other),
+            }
+        }
+
+        /// 在本地编辑并把更新发送给服务器
+        async fn edit(&mut self, f: impl FnOnce(&mut TransactionMut)) {
+            let before = self.doc.transact().state_vector();
+            f(&mut self.doc.transact_mut());
+            let update = self.doc.transact().encode_diff_v1(&before);
+            let message = Message::Sync(SyncMessage::Update(update));
+            self.ws.send(WsMessage::binary(message.encode_v1())).await;
+        }
+
+        /// 在本地插入文本并把更新发送给服务器
+        async fn insert(&mut self, index: u32, chunk: &str) {
+            let text = self.doc.get_or_insert_text("content");
+            self.edit(|txn| text.insert(txn, index, chunk)).await;
+        }
+
+        fn text(&self) -> String {
+            let text = self.doc.get_or_insert_text("content");
+            let value = text.get_string(&self.doc.transact());
+            value
+        }
+    }
+
+    #[tokio::test]
+    async fn test_collaborative_editing() {
+        let db = setup_test_db().await;
+        let node_id = create_node(&db, "你好").await;
+
+        let mut alice = Client::connect(&db, &node_id).await;
+        let mut bob = Client::connect(&db, &node_id).await;
+        assert_eq!(alice.text(), "你好");
+        assert_eq!(bob.text(), "你好");
+
+        // 两人的更新互相转发
+        alice.insert(2, "世界").await;
+        bob.receive().await;
+        bob.insert(0, "👋").await;
+        alice.receive().await;
+        assert_eq!(alice.text(), "👋你好世界");
+        assert_eq!(bob.text(), "👋你好世界");
+
+        // 压缩后写入内容快照
+        let saved = compact(&db, &node_id).await.unwrap().unwrap();
+        assert_eq!(saved.content, "👋你好世界");
+        assert_eq!(
+            content_update_db_fn::count_by_node_id(&db, &node_id)
+                .await
+                .unwrap(),
+            1
+        );
+
+        // 其他途径修改内容后，差异合入文档并转发给在线的连接
+        save(&db, &node_id, "👋你好世界！").await;
+        let state = load_state(&db, &node_id).await.unwrap();
+        assert_eq!(
+            CrdtDocument::from_updates(&[state]).unwrap().content(),
+            "👋你好世界！"
+        );
+        alice.receive().await;
+        assert_eq!(alice.text(), "👋你好世界！");
+
+        // 文本没有变化时压缩不产生新版本
+        let version = saved.version + 1;
+        let saved = compact(&db, &node_id).await.unwrap().unwrap();
+        assert_eq!(saved.version, version);
+    }
+
+    #[tokio::test]
+    async fn test_collaborative_lexical_editing() {
+        let db = setup_test_db().await;
+        let content = r#"{"root":{"children":[{"children":[{"detail":0,"format":0,"mode":"normal","style":"","text":"你好","type":"text","version":1}],"direction":"ltr","format":"","indent":0,"type":"paragraph","version":1}],"direction":"ltr","format":"","indent":0,"type":"root","version":1}}"#;
+        let node_id = create_node(&db, content).await;
+
+        // 客户端按 @lexical/yjs 的结构编辑：在第一个段落的文本末尾追加
+        let mut alice = Client::connect(&db, &node_id).await;
+        let mut bob = Client::connect(&db, &node_id).await;
+        let text = alice.doc.get_or_insert_text("root");
+        let root: &XmlTextRef = text.as_ref();
+        alice
+            .edit(|txn| {
+                let Some(Out::YXmlText(paragraph)) = root
+                    .diff(txn, YChange::identity)
+                    .into_iter()
+                    .next()
+                    .map(|diff| diff.insert)
+                else {
+                    panic!("缺少段落");
+                };
+                let end = paragraph.len(txn);
+                paragraph.insert(txn, end, "世界");
+            })
+            .await;
+        bob.receive().await;
+
+        let saved = compact(&db, &node_id).await.unwrap().unwrap();
+        let value: serde_json::Value = serde_json::from_str(&saved.content).unwrap();
+        assert_eq!(
+            value["root"]["children"][0]["children"][0]["text"],
+            "你好世界"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_compact_rejects_invalid_json() {
+        let db = setup_test_db().await;
+        let node_id = create_node(&db, r#"{"a":1}"#).await;
+
+        // 不是 Lexical 文档的 JSON 按纯文本协同，编辑后可能不再是有效的 JSON
+        let mut alice = Client::connect(&db, &node_id).await;
+        let mut bob = Client::connect(&db, &node_id).await;
+        alice.insert(0, "x").await;
+        bob.receive().await;
+
+        assert!(compact(&db, &node_id).await.is_err());
+        let content = content_db_fn::find_by_node_id(&db, &node_id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(content.content, r#"{"a":1}"#);
+        assert_eq!(
+            content_update_db_fn::count_by_node_id(&db, &node_id)
+                .await
+                .unwrap(),
+            2
+        );
+    }
+
+    #[tokio::test]
+    async fn test_collab_unknown_node() {
+        let db = setup_test_db().await;
+        let routes = build_routes(
+            Arc::new(db),
+            Arc::new(AppConfig {
+                auth_enabled: false,
+                ..Default::default()
+            }),
+        );
+        let result = warp::test::ws()
+            .path("/api/nodes/missing/collab")
+            .handshake(routes)
+            .await;
+        assert!(result.is_err());
+    }
+}
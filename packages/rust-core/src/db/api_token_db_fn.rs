@@ -0,0 +1,164 @@
+//! ApiToken 数据库函数
+//!
+//! 维护个人 API 令牌（`api_tokens` 表）
+//!
+//! - 数据库只保存令牌的哈希和开头部分，原始令牌只在创建时返回一次
+//! - 令牌不会过期，撤销即删除
+
+use crate::r#fn::auth::{api_token_display_prefix, generate_api_token, hash_token};
+use crate::types::auth::{
+    ApiTokenActiveModel, ApiTokenColumn, ApiTokenEntity, ApiTokenModel, ApiTokenScope,
+};
+use crate::types::error::AppResult;
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, ConnectionTrait, EntityTrait, QueryFilter, QueryOrder, Set,
+};
+use tracing::info;
+
+/// 最近使用时间的记录精度 (毫秒)，避免每个请求都写数据库
+const LAST_USED_RESOLUTION_MS: i64 = 60 * 1000;
+
+// ============================================================================
+// 查询函数
+// ============================================================================
+
+/// 查询用户的全部 API 令牌（按创建时间升序）
+pub async fn find_by_user<C: ConnectionTrait>(
+    db: &C,
+    user_id: &str,
+) -> AppResult<Vec<ApiTokenModel>> {
+    let tokens = ApiTokenEntity::find()
+        .filter(ApiTokenColumn::UserId.eq(user_id))
+        .order_by_asc(ApiTokenColumn::CreatedAt)
+        .all(db)
+        .await?;
+    Ok(tokens)
+}
+
+/// 按令牌查找 API 令牌，并记录使用时间
+pub async fn authenticate<C: ConnectionTrait>(
+    db: &C,
+    token: &str,
+) -> AppResult<Option<ApiTokenModel>> {
+    let Some(model) = ApiTokenEntity::find()
+        .filter(ApiTokenColumn::TokenHash.eq(hash_token(token)))
+        .one(db)
+        .await?
+    else {
+        return Ok(None);
+    };
+
+    let now = chrono::Utc::now().timestamp_millis();
+    if model
+        .last_used_at
+        .is_some_and(|last_used_at| now - last_used_at < LAST_USED_RESOLUTION_MS)
+    {
+        return Ok(Some(model));
+    }
+    let model = ApiTokenActiveModel {
+        id: Set(model.id),
+        last_used_at: Set(Some(now)),
+        ..Default::default()
+    }
+    .update(db)
+    .await?;
+    Ok(Some(model))
+}
+
+// ============================================================================
+// 写入函数
+// ============================================================================
+
+/// 创建 API 令牌，返回令牌记录和原始令牌
+pub async fn create<C: ConnectionTrait>(
+    db: &C,
+    user_id: &str,
+    name: String,
+    scope: ApiTokenScope,
+    workspace_id: Option<String>,
+) -> AppResult<(ApiTokenModel, String)> {
+    let token = generate_api_token();
+    let model = ApiTokenActiveModel {
+        id: Set(uuid::Uuid::new_v4().to_string()),
+        user_id: Set(user_id.to_string()),
+        name: Set(name),
+        token_prefix: Set(api_token_display_prefix(&token)),
+        token_hash: Set(hash_token(&token)),
+        scope: Set(scope),
+        workspace_id: Set(workspace_id),
+        created_at: Set(chrono::Utc::now().timestamp_millis()),
+        last_used_at: Set(None),
+    }
+    .insert(db)
+    .await?;
+    info!(
+        "创建 API 令牌: id={}, user_id={}, scope={:?}",
+        model.id, user_id, scope
+    );
+    Ok((model, token))
+}
+
+/// 撤销用户的 API 令牌，返回是否存在该令牌
+pub async fn delete<C: ConnectionTrait>(db: &C, user_id: &str, id: &str) -> AppResult<bool> {
+    let deleted = ApiTokenEntity::delete_many()
+        .filter(ApiTokenColumn::Id.eq(id))
+        .filter(ApiTokenColumn::UserId.eq(user_id))
+        .exec(db)
+        .await?
+        .rows_affected;
+    if deleted > 0 {
+        info!("撤销 API 令牌: id={}, user_id={}", id, user_id);
+    }
+    Ok(deleted > 0)
+}
+
+// ============================================================================
+// 测试
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::auth_db_fn;
+    use crate::db::test_utils::setup_test_db;
+
+    #[tokio::test]
+    async fn test_api_token_lifecycle() {
+        let db = setup_test_db().await;
+        let alice = auth_db_fn::ensure_user(&db, "alice", "password-1")
+            .await
+            .unwrap();
+        let bob = auth_db_fn::ensure_user(&db, "bob", "password-1")
+            .await
+            .unwrap();
+
+        let (model, token) = create(
+            &db,
+            &alice.id,
+            "导出脚本".to_string(),
+            ApiTokenScope::ReadOnly,
+            None,
+        )
+        .await
+        .unwrap();
+        // 数据库中不保存原始令牌
+        assert_ne!(model.token_hash, token);
+        assert!(token.starts_with(&model.token_prefix));
+        assert_eq!(model.last_used_at, None);
+
+        let used = authenticate(&db, &token).await.unwrap().unwrap();
+        assert_eq!(used.id, model.id);
+        assert!(used.last_used_at.is_some());
+        assert!(authenticate(&db, "grain_pat_invalid")
+            .await
+            .unwrap()
+            .is_none());
+        assert_eq!(find_by_user(&db, &alice.id).await.unwrap().len(), 1);
+
+        // 只能撤销自己的令牌
+        assert!(!delete(&db, &bob.id, &model.id).await.unwrap());
+        assert!(delete(&db, &alice.id, &model.id).await.unwrap());
+        assert!(authenticate(&db, &token).await.unwrap().is_none());
+        assert!(find_by_user(&db, &alice.id).await.unwrap().is_empty());
+    }
+}
@@ -0,0 +1,415 @@
+//! Auth 数据库函数
+//!
+//! 维护登录凭据（`user_credentials` 表）和登录会话（`auth_sessions` 表）
+//!
+//! - 数据库只保存密码和令牌的哈希，原始令牌只在签发时返回一次
+//! - 访问令牌 1 小时过期，刷新令牌 30 天过期；刷新时两个令牌一起轮换
+
+use crate::db::user_db_fn;
+use crate::r#fn::auth::{generate_token, hash_password, hash_token, MIN_PASSWORD_LEN};
+use crate::types::auth::{
+    AuthSessionActiveModel, AuthSessionColumn, AuthSessionEntity, AuthSessionModel,
+    UserCredentialActiveModel, UserCredentialColumn, UserCredentialEntity, UserCredentialModel,
+};
+use crate::types::error::{AppError, AppResult};
+use crate::types::UserModel;
+use sea_orm::sea_query::Expr;
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, ConnectionTrait, DatabaseConnection, EntityTrait,
+    PaginatorTrait, QueryFilter, Set,
+};
+use tracing::info;
+
+/// 访问令牌有效期 (毫秒)
+pub const ACCESS_TOKEN_TTL_MS: i64 = 60 * 60 * 1000;
+
+/// 刷新令牌有效期 (毫秒)
+pub const REFRESH_TOKEN_TTL_MS: i64 = 30 * 24 * 60 * 60 * 1000;
+
+/// 最近使用时间的记录精度 (毫秒)，避免每个请求都写数据库
+const LAST_USED_RESOLUTION_MS: i64 = 60 * 1000;
+
+/// 新签发的会话（包含原始令牌）
+#[derive(Debug, Clone)]
+pub struct IssuedSession {
+    /// 会话记录
+    pub session: AuthSessionModel,
+    /// 访问令牌
+    pub access_token: String,
+    /// 刷新令牌
+    pub refresh_token: String,
+}
+
+// ============================================================================
+// 凭据
+// ============================================================================
+
+/// 设置用户密码（已有凭据时覆盖）
+///
+/// 密码短于 `MIN_PASSWORD_LEN` 时返回 ValidationError。
+pub async fn set_password<C: ConnectionTrait>(
+    db: &C,
+    user_id: &str,
+    password: &str,
+) -> AppResult<()> {
+    if password.chars().count() < MIN_PASSWORD_LEN {
+        return Err(AppError::validation(format!(
+            "密码至少需要 {} 个字符",
+            MIN_PASSWORD_LEN
+        )));
+    }
+
+    let mut credential = UserCredentialActiveModel {
+        user_id: Set(user_id.to_string()),
+        password_hash: Set(hash_password(password)),
+        updated_at: Set(chrono::Utc::now().timestamp_millis()),
+        ..Default::default()
+    };
+    if find_credential(db, user_id).await?.is_some() {
+        credential.update(db).await?;
+    } else {
+        credential.is_admin = Set(false);
+        credential.insert(db).await?;
+    }
+    info!("设置用户密码: user_id={}", user_id);
+    Ok(())
+}
+
+/// 查询用户的登录凭据
+pub async fn find_credential<C: ConnectionTrait>(
+    db: &C,
+    user_id: &str,
+) -> AppResult<Option<UserCredentialModel>> {
+    let credential = UserCredentialEntity::find_by_id(user_id).one(db).await?;
+    Ok(credential)
+}
+
+/// 设置用户是否为管理员
+///
+/// 用户没有登录凭据时返回 NotFound。
+pub async fn set_admin<C: ConnectionTrait>(db: &C, user_id: &str, is_admin: bool) -> AppResult<()> {
+    let result = UserCredentialEntity::update_many()
+        .col_expr(UserCredentialColumn::IsAdmin, Expr::value(is_admin))
+        .filter(UserCredentialColumn::UserId.eq(user_id))
+        .exec(db)
+        .await?;
+    if result.rows_affected == 0 {
+        return Err(AppError::not_found(format!("User credential {}", user_id)));
+    }
+    info!("设置管理员: user_id={}, is_admin={}", user_id, is_admin);
+    Ok(())
+}
+
+/// 用户是否为管理员（没有登录凭据的用户不是）
+pub async fn is_admin<C: ConnectionTrait>(db: &C, user_id: &str) -> AppResult<bool> {
+    Ok(find_credential(db, user_id)
+        .await?
+        .is_some_and(|credential| credential.is_admin))
+}
+
+/// 统计设置了密码的用户数
+pub async fn count_credentials<C: ConnectionTrait>(db: &C) -> AppResult<u64> {
+    let count = UserCredentialEntity::find().count(db).await?;
+    Ok(count)
+}
+
+/// 确保存在指定用户名的用户并设置密码（用于服务器首次启动时创建管理员）
+pub async fn ensure_user(
+    db: &DatabaseConnection,
+    username: &str,
+    password: &str,
+) -> AppResult<UserModel> {
+    let user = match user_db_fn::find_by_username(db, username).await? {
+        Some(user) => user,
+        None => {
+            user_db_fn::create(
+                db,
+                uuid::Uuid::new_v4().to_string(),
+                username.to_string(),
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+            )
+            .await?
+        }
+    };
+    set_password(db, &user.id, password).await?;
+    Ok(user)
+}
+
+// ============================================================================
+// 会话
+// ============================================================================
+
+/// 为用户签发新会话
+pub async fn create_session<C: ConnectionTrait>(db: &C, user_id: &str) -> AppResult<IssuedSession> {
+    let now = chrono::Utc::now().timestamp_millis();
+    let access_token = generate_token();
+    let refresh_token = generate_token();
+
+    let session = AuthSessionActiveModel {
+        id: Set(uuid::Uuid::new_v4().to_string()),
+        user_id: Set(user_id.to_string()),
+        access_token_hash: Set(hash_token(&access_token)),
+        refresh_token_hash: Set(hash_token(&refresh_token)),
+        access_expires_at: Set(now + ACCESS_TOKEN_TTL_MS),
+        refresh_expires_at: Set(now + REFRESH_TOKEN_TTL_MS),
+        created_at: Set(now),
+        last_used_at: Set(now),
+    }
+    .insert(db)
+    .await?;
+
+    Ok(IssuedSession {
+        session,
+        access_token,
+        refresh_token,
+    })
+}
+
+/// 按访问令牌查找未过期的会话，并记录使用时间
+pub async fn authenticate<C: ConnectionTrait>(
+    db: &C,
+    access_token: &str,
+) -> AppResult<Option<AuthSessionModel>> {
+    let now = chrono::Utc::now().timestamp_millis();
+    let Some(session) = AuthSessionEntity::find()
+        .filter(AuthSessionColumn::AccessTokenHash.eq(hash_token(access_token)))
+        .filter(AuthSessionColumn::AccessExpiresAt.gt(now))
+        .one(db)
+        .await?
+    else {
+        return Ok(None);
+    };
+
+    if now - session.last_used_at < LAST_USED_RESOLUTION_MS {
+        return Ok(Some(session));
+    }
+    let session = AuthSessionActiveModel {
+        id: Set(session.id),
+        last_used_at: Set(now),
+        ..Default::default()
+    }
+    .update(db)
+    .await?;
+    Ok(Some(session))
+}
+
+/// 用刷新令牌轮换会话的两个令牌（刷新令牌无效或已过期时返回 None）
+///
+/// 轮换是一条带条件的 UPDATE：同一个刷新令牌被并发使用时只有一次成功。
+pub async fn refresh_session<C: ConnectionTrait>(
+    db: &C,
+    refresh_token: &str,
+) -> AppResult<Option<IssuedSession>> {
+    let now = chrono::Utc::now().timestamp_millis();
+    let access_token = generate_token();
+    let new_refresh_token = generate_token();
+    let new_refresh_hash = hash_token(&new_refresh_token);
+
+    let rotated = AuthSessionEntity::update_many()
+        .col_expr(
+            AuthSessionColumn::AccessTokenHash,
+            Expr::value(hash_token(&access_token)),
+        )
+        .col_expr(
+            AuthSessionColumn::RefreshTokenHash,
+            Expr::value(new_refresh_hash.clone()),
+        )
+        .col_expr(
+            AuthSessionColumn::AccessExpiresAt,
+            Expr::value(now + ACCESS_TOKEN_TTL_MS),
+        )
+        .col_expr(
+            AuthSessionColumn::RefreshExpiresAt,
+            Expr::value(now + REFRESH_TOKEN_TTL_MS),
+        )
+        .col_expr(AuthSessionColumn::LastUsedAt, Expr::value(now))
+        .filter(AuthSessionColumn::RefreshTokenHash.eq(hash_token(refresh_token)))
+        .filter(AuthSessionColumn::RefreshExpiresAt.gt(now))
+        .exec(db)
+        .await?
+        .rows_affected;
+    if rotated != 1 {
+        return Ok(None);
+    }
+
+    let Some(session) = AuthSessionEntity::find()
+        .filter(AuthSessionColumn::RefreshTokenHash.eq(new_refresh_hash))
+        .one(db)
+        .await?
+    else {
+        return Ok(None);
+    };
+
+    Ok(Some(IssuedSession {
+        session,
+        access_token,
+        refresh_token: new_refresh_token,
+    }))
+}
+
+/// 删除会话（登出）
+pub async fn delete_session<C: ConnectionTrait>(db: &C, id: &str) -> AppResult<()> {
+    AuthSessionEntity::delete_by_id(id).exec(db).await?;
+    Ok(())
+}
+
+/// 删除刷新令牌已过期的会话，返回删除条数
+pub async fn delete_expired<C: ConnectionTrait>(db: &C) -> AppResult<u64> {
+    let now = chrono::Utc::now().timestamp_millis();
+    let deleted = AuthSessionEntity::delete_many()
+        .filter(AuthSessionColumn::RefreshExpiresAt.lte(now))
+        .exec(db)
+        .await?
+        .rows_affected;
+    Ok(deleted)
+}
+
+// ============================================================================
+// 测试
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::test_utils::setup_test_db;
+    use crate::r#fn::auth::verify_password;
+
+    #[tokio::test]
+    async fn test_set_password() {
+        let db = setup_test_db().await;
+        let user = ensure_user(&db, "alice", "password-1").await.unwrap();
+        let credential = find_credential(&db, &user.id).await.unwrap().unwrap();
+        assert!(verify_password("password-1", &credential.password_hash));
+
+        // 同名用户只更新密码
+        let again = ensure_user(&db, "alice", "password-2").await.unwrap();
+        assert_eq!(again.id, user.id);
+        let credential = find_credential(&db, &user.id).await.unwrap().unwrap();
+        assert!(verify_password("password-2", &credential.password_hash));
+        assert_eq!(count_credentials(&db).await.unwrap(), 1);
+
+        assert!(matches!(
+            set_password(&db, &user.id, "short").await,
+            Err(AppError::ValidationError(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_admin_flag() {
+        let db = setup_test_db().await;
+        let user = ensure_user(&db, "alice", "password-1").await.unwrap();
+        assert!(!is_admin(&db, &user.id).await.unwrap());
+
+        // 重置密码不改变管理员标记
+        set_admin(&db, &user.id, true).await.unwrap();
+        ensure_user(&db, "alice", "password-2").await.unwrap();
+        assert!(is_admin(&db, &user.id).await.unwrap());
+
+        assert!(!is_admin(&db, "missing").await.unwrap());
+        assert!(matches!(
+            set_admin(&db, "missing", true).await,
+            Err(AppError::NotFound(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_session_lifecycle() {
+        let db = setup_test_db().await;
+        let user = ensure_user(&db, "alice", "password-1").await.unwrap();
+
+        let issued = create_session(&db, &user.id).await.unwrap();
+        // 数据库中不保存原始令牌
+        assert_ne!(issued.session.access_token_hash, issued.access_token);
+        let session = authenticate(&db, &issued.access_token).await.unwrap();
+        assert_eq!(session.map(|s| s.user_id), Some(user.id.clone()));
+        assert!(authenticate(&db, &issued.refresh_token)
+            .await
+            .unwrap()
+            .is_none());
+
+        // 刷新后旧令牌全部失效
+        let refreshed = refresh_session(&db, &issued.refresh_token)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(refreshed.session.id, issued.session.id);
+        assert!(authenticate(&db, &issued.access_token)
+            .await
+            .unwrap()
+            .is_none());
+        assert!(refresh_session(&db, &issued.refresh_token)
+            .await
+            .unwrap()
+            .is_none());
+        assert!(authenticate(&db, &refreshed.access_token)
+            .await
+            .unwrap()
+            .is_some());
+
+        delete_session(&db, &refreshed.session.id).await.unwrap();
+        assert!(authenticate(&db, &refreshed.access_token)
+            .await
+            .unwrap()
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn test_refresh_token_reuse() {
+        let db = setup_test_db().await;
+        let user = ensure_user(&db, "alice", "password-1").await.unwrap();
+        let issued = create_session(&db, &user.id).await.unwrap();
+
+        // 同一个刷新令牌并发使用时只有一次成功
+        let (first, second) = tokio::join!(
+            refresh_session(&db, &issued.refresh_token),
+            refresh_session(&db, &issued.refresh_token)
+        );
+        let rotated: Vec<IssuedSession> = [first.unwrap(), second.unwrap()]
+            .into_iter()
+            .flatten()
+            .collect();
+        assert_eq!(rotated.len(), 1);
+
+        // 旧令牌再次使用失败，新令牌不受影响
+        assert!(refresh_session(&db, &issued.refresh_token)
+            .await
+            .unwrap()
+            .is_none());
+        assert!(refresh_session(&db, &rotated[0].refresh_token)
+            .await
+            .unwrap()
+            .is_some());
+    }
+
+    #[tokio::test]
+    async fn test_expired_session() {
+        let db = setup_test_db().await;
+        let user = ensure_user(&db, "alice", "password-1").await.unwrap();
+        let issued = create_session(&db, &user.id).await.unwrap();
+
+        AuthSessionActiveModel {
+            id: Set(issued.session.id.clone()),
+            access_expires_at: Set(0),
+            refresh_expires_at: Set(0),
+            ..Default::default()
+        }
+        .update(&db)
+        .await
+        .unwrap();
+
+        assert!(authenticate(&db, &issued.access_token)
+            .await
+            .unwrap()
+            .is_none());
+        assert!(refresh_session(&db, &issued.refresh_token)
+            .await
+            .unwrap()
+            .is_none());
+        assert_eq!(delete_expired(&db).await.unwrap(), 1);
+    }
+}
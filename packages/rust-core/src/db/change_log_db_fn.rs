@@ -0,0 +1,190 @@
+//! ChangeLog 数据库函数
+//!
+//! 维护只追加的变更日志（`change_log` 表）
+//!
+//! - 写操作在其事务中由 `PendingEvents::record` 追加一条记录，序号由 SQLite 自增分配
+//! - 查询时按序号分页返回某个序号之后的记录，由调用方压缩
+//! - 压缩从新到旧逐页扫描，只删除被取代的记录，每个实体的最新记录总是保留
+
+use crate::r#fn::change_log::{change_target, ChangeCompactor};
+use crate::types::change_log::{
+    change_log_entity as change_log, ChangeEntity, ChangeLogEntity, CompactChangeLogResponse,
+};
+use crate::types::error::AppResult;
+use crate::types::event::ChangeEvent;
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, ConnectionTrait, DatabaseConnection, EntityTrait,
+    PaginatorTrait, QueryFilter, QueryOrder, QuerySelect, Set,
+};
+use tracing::info;
+
+/// 压缩时每页读取的记录数
+const COMPACT_PAGE_SIZE: u64 = 500;
+
+/// 追加变更记录（不需要记录的事件返回 None）
+pub async fn append<C: ConnectionTrait>(
+    db: &C,
+    event: &ChangeEvent,
+) -> AppResult<Option<change_log::Model>> {
+    let Some(target) = change_target(event) else {
+        return Ok(None);
+    };
+
+    let entry = change_log::ActiveModel {
+        workspace_id: Set(target.workspace_id),
+        entity_type: Set(target.entity_type),
+        entity_id: Set(target.entity_id),
+        operation: Set(target.operation),
+        version: Set(target.version),
+        created_at: Set(chrono::Utc::now().timestamp_millis()),
+        ..Default::default()
+    }
+    .insert(db)
+    .await?;
+
+    Ok(Some(entry))
+}
+
+/// 查询序号之后的记录（按序号升序，最多 `limit` 条）
+pub async fn find_since<C: ConnectionTrait>(
+    db: &C,
+    since: i64,
+    limit: u64,
+) -> AppResult<Vec<change_log::Model>> {
+    let entries = ChangeLogEntity::find()
+        .filter(change_log::Column::Seq.gt(since))
+        .order_by_asc(change_log::Column::Seq)
+        .limit(limit)
+        .all(db)
+        .await?;
+    Ok(entries)
+}
+
+/// 查询实体最新一条记录的序号（没有记录时为 0）
+pub async fn latest_seq<C: ConnectionTrait>(
+    db: &C,
+    entity_type: ChangeEntity,
+    entity_id: &str,
+) -> AppResult<i64> {
+    let latest = ChangeLogEntity::find()
+        .filter(change_log::Column::EntityType.eq(entity_type))
+        .filter(change_log::Column::EntityId.eq(entity_id))
+        .order_by_desc(change_log::Column::Seq)
+        .one(db)
+        .await?;
+    Ok(latest.map_or(0, |e| e.seq))
+}
+
+/// 压缩变更日志，删除被取代的记录
+///
+/// 从最新的记录开始逐页向前扫描，每页删除其中被取代的记录，
+/// 不会一次读入整个日志。
+pub async fn compact(db: &DatabaseConnection) -> AppResult<CompactChangeLogResponse> {
+    let mut compactor = ChangeCompactor::new();
+    let mut before = i64::MAX;
+    let mut removed = 0u64;
+
+    loop {
+        let page = ChangeLogEntity::find()
+            .filter(change_log::Column::Seq.lt(before))
+            .order_by_desc(change_log::Column::Seq)
+            .limit(COMPACT_PAGE_SIZE)
+            .all(db)
+            .await?;
+        let Some(last) = page.last() else {
+            break;
+        };
+        before = last.seq;
+
+        let superseded: Vec<i64> = page
+            .iter()
+            .filter(|e| compactor.is_superseded(e))
+            .map(|e| e.seq)
+            .collect();
+        if !superseded.is_empty() {
+            removed += superseded.len() as u64;
+            ChangeLogEntity::delete_many()
+                .filter(change_log::Column::Seq.is_in(superseded))
+                .exec(db)
+                .await?;
+        }
+    }
+
+    let remaining = ChangeLogEntity::find().count(db).await?;
+    info!("压缩变更日志: 删除 {} 条, 保留 {} 条", removed, remaining);
+    Ok(CompactChangeLogResponse { removed, remaining })
+}
+
+// ============================================================================
+// 测试
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::test_utils::setup_test_db;
+    use crate::types::change_log::ChangeOperation;
+
+    fn node_updated(node_id: &str) -> ChangeEvent {
+        ChangeEvent::NodeUpdated {
+            workspace_id: "ws".to_string(),
+            node_id: node_id.to_string(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_append_and_compact() {
+        let db = setup_test_db().await;
+
+        let first = append(&db, &node_updated("n1")).await.unwrap().unwrap();
+        append(&db, &node_updated("n2")).await.unwrap();
+        let last = append(&db, &node_updated("n1")).await.unwrap().unwrap();
+        assert!(append(&db, &ChangeEvent::Resync).await.unwrap().is_none());
+        assert!(last.seq > first.seq);
+        assert_eq!(last.operation, ChangeOperation::Upsert);
+
+        assert_eq!(find_since(&db, first.seq, 100).await.unwrap().len(), 2);
+        let page = find_since(&db, 0, 2).await.unwrap();
+        assert_eq!(page.len(), 2);
+        assert_eq!(page[0].seq, first.seq);
+        assert_eq!(
+            latest_seq(&db, ChangeEntity::Node, "n1").await.unwrap(),
+            last.seq
+        );
+        assert_eq!(latest_seq(&db, ChangeEntity::Node, "n9").await.unwrap(), 0);
+
+        let result = compact(&db).await.unwrap();
+        assert_eq!((result.removed, result.remaining), (1, 2));
+        let seqs: Vec<i64> = find_since(&db, 0, 100)
+            .await
+            .unwrap()
+            .iter()
+            .map(|e| e.seq)
+            .collect();
+        assert!(!seqs.contains(&first.seq));
+        assert!(seqs.contains(&last.seq));
+
+        // 删除后序号不会复用
+        let next = append(&db, &node_updated("n3")).await.unwrap().unwrap();
+        assert!(next.seq > last.seq);
+    }
+
+    #[tokio::test]
+    async fn test_compact_across_pages() {
+        let db = setup_test_db().await;
+
+        let total = COMPACT_PAGE_SIZE * 2 + 10;
+        for i in 0..total {
+            append(&db, &node_updated(&format!("n{}", i % 10)))
+                .await
+                .unwrap();
+        }
+
+        // 被取代的记录分布在多页中，每个节点只保留最新一条
+        let result = compact(&db).await.unwrap();
+        assert_eq!((result.removed, result.remaining), (total - 10, 10));
+        let entries = find_since(&db, 0, 100).await.unwrap();
+        let expected: Vec<i64> = ((total - 9)..=total).map(|seq| seq as i64).collect();
+        assert_eq!(entries.iter().map(|e| e.seq).collect::<Vec<_>>(), expected);
+    }
+}
@@ -7,11 +7,12 @@ use crate::types::{
     attachment::AttachmentEntity as Attachment,
     content::ContentEntity as Content,
     node::NodeEntity as Node,
+    sync::{SyncRemoteEntity as SyncRemote, SyncVersionEntity as SyncVersion},
     tag::TagEntity as Tag,
     user::UserEntity as User,
     workspace::WorkspaceEntity as Workspace,
 };
-use sea_orm::{DatabaseConnection, EntityTrait, TransactionTrait};
+use sea_orm::{ConnectionTrait, DatabaseConnection, EntityTrait, TransactionTrait};
 use tracing::info;
 
 /// 清除所有数据的选项
@@ -64,23 +65,38 @@ impl ClearDataOptions {
 /// 2. 附件（依赖工作区）
 /// 3. 节点（依赖工作区）
 /// 4. 标签（依赖工作区）
-/// 5. 工作区
+/// 5. 工作区（连同同步进度）
 /// 6. 用户
 pub async fn clear_all_data(
     db: &DatabaseConnection,
     options: ClearDataOptions,
 ) -> AppResult<ClearDataResult> {
-    info!("开始清除 SQLite 数据...");
-
     let txn = db.begin().await.map_err(|e| {
         AppError::DatabaseError(format!("开启事务失败: {}", e))
     })?;
 
+    let result = clear_all_data_in(&txn, options).await?;
+
+    txn.commit().await.map_err(|e| {
+        AppError::DatabaseError(format!("提交事务失败: {}", e))
+    })?;
+
+    info!("SQLite 数据清除完成: {:?}", result);
+    Ok(result)
+}
+
+/// 在调用方的事务中清除所有数据，删除顺序同 [`clear_all_data`]
+pub async fn clear_all_data_in<C: ConnectionTrait>(
+    txn: &C,
+    options: ClearDataOptions,
+) -> AppResult<ClearDataResult> {
+    info!("开始清除 SQLite 数据...");
+
     let mut result = ClearDataResult::default();
 
     // 1. 清除内容（依赖节点）
     if options.clear_contents {
-        let deleted = Content::delete_many().exec(&txn).await.map_err(|e| {
+        let deleted = Content::delete_many().exec(txn).await.map_err(|e| {
             AppError::DatabaseError(format!("清除内容失败: {}", e))
         })?;
         result.contents_deleted = deleted.rows_affected;
@@ -89,7 +105,7 @@ pub async fn clear_all_data(
 
     // 2. 清除附件（依赖工作区）
     if options.clear_attachments {
-        let deleted = Attachment::delete_many().exec(&txn).await.map_err(|e| {
+        let deleted = Attachment::delete_many().exec(txn).await.map_err(|e| {
             AppError::DatabaseError(format!("清除附件失败: {}", e))
         })?;
         result.attachments_deleted = deleted.rows_affected;
@@ -98,7 +114,7 @@ pub async fn clear_all_data(
 
     // 3. 清除节点（依赖工作区）
     if options.clear_nodes {
-        let deleted = Node::delete_many().exec(&txn).await.map_err(|e| {
+        let deleted = Node::delete_many().exec(txn).await.map_err(|e| {
             AppError::DatabaseError(format!("清除节点失败: {}", e))
         })?;
         result.nodes_deleted = deleted.rows_affected;
@@ -107,7 +123,7 @@ pub async fn clear_all_data(
 
     // 4. 清除标签（依赖工作区）
     if options.clear_tags {
-        let deleted = Tag::delete_many().exec(&txn).await.map_err(|e| {
+        let deleted = Tag::delete_many().exec(txn).await.map_err(|e| {
             AppError::DatabaseError(format!("清除标签失败: {}", e))
         })?;
         result.tags_deleted = deleted.rows_affected;
@@ -116,32 +132,37 @@ pub async fn clear_all_data(
 
     // 5. 清除工作区
     if options.clear_workspaces {
-        let deleted = Workspace::delete_many().exec(&txn).await.map_err(|e| {
+        let deleted = Workspace::delete_many().exec(txn).await.map_err(|e| {
             AppError::DatabaseError(format!("清除工作区失败: {}", e))
         })?;
         result.workspaces_deleted = deleted.rows_affected;
         info!("已删除 {} 条工作区记录", result.workspaces_deleted);
+
+        // 同步进度指向的数据已不存在，一并清除，下次同步从头拉取
+        SyncVersion::delete_many()
+            .exec(txn)
+            .await
+            .map_err(|e| AppError::DatabaseError(format!("清除同步版本失败: {}", e)))?;
+        SyncRemote::delete_many()
+            .exec(txn)
+            .await
+            .map_err(|e| AppError::DatabaseError(format!("清除同步进度失败: {}", e)))?;
     }
 
     // 6. 清除用户
     if options.clear_users {
-        let deleted = User::delete_many().exec(&txn).await.map_err(|e| {
+        let deleted = User::delete_many().exec(txn).await.map_err(|e| {
             AppError::DatabaseError(format!("清除用户失败: {}", e))
         })?;
         result.users_deleted = deleted.rows_affected;
         info!("已删除 {} 条用户记录", result.users_deleted);
     }
 
-    txn.commit().await.map_err(|e| {
-        AppError::DatabaseError(format!("提交事务失败: {}", e))
-    })?;
-
-    info!("SQLite 数据清除完成: {:?}", result);
     Ok(result)
 }
 
 /// 清除数据结果
-#[derive(Debug, Clone, Default, serde::Serialize)]
+#[derive(Debug, Clone, Default, serde::Serialize, schemars::JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct ClearDataResult {
     /// 删除的用户数
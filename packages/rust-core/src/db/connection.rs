@@ -126,6 +126,84 @@ impl DbConnection {
         ))
         .await?;
 
+        // 创建 content_revisions 表（内容历史版本，用于三方合并）
+        db.execute(Statement::from_string(
+            db.get_database_backend(),
+            r#"
+            CREATE TABLE IF NOT EXISTS content_revisions (
+                id TEXT PRIMARY KEY NOT NULL,
+                node_id TEXT NOT NULL,
+                version INTEGER NOT NULL,
+                content TEXT NOT NULL,
+                created_at INTEGER NOT NULL,
+                FOREIGN KEY (node_id) REFERENCES nodes(id) ON DELETE CASCADE
+            )
+            "#
+            .to_string(),
+        ))
+        .await?;
+
+        db.execute(Statement::from_string(
+            db.get_database_backend(),
+            "CREATE INDEX IF NOT EXISTS idx_content_revisions_node ON content_revisions(node_id, version)"
+                .to_string(),
+        ))
+        .await?;
+
+        // 创建 content_updates 表（协同编辑的 CRDT 更新）
+        db.execute(Statement::from_string(
+            db.get_database_backend(),
+            r#"
+            CREATE TABLE IF NOT EXISTS content_updates (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                node_id TEXT NOT NULL,
+                data BLOB NOT NULL,
+                content_version INTEGER NOT NULL,
+                created_at INTEGER NOT NULL,
+                FOREIGN KEY (node_id) REFERENCES nodes(id) ON DELETE CASCADE
+            )
+            "#
+            .to_string(),
+        ))
+        .await?;
+
+        db.execute(Statement::from_string(
+            db.get_database_backend(),
+            "CREATE INDEX IF NOT EXISTS idx_content_updates_node ON content_updates(node_id, id)"
+                .to_string(),
+        ))
+        .await?;
+
+        // 创建 node_links 表（目标节点不加外键，删除后保留为断链）
+        db.execute(Statement::from_string(
+            db.get_database_backend(),
+            r#"
+            CREATE TABLE IF NOT EXISTS node_links (
+                id TEXT PRIMARY KEY NOT NULL,
+                source_id TEXT NOT NULL,
+                target_id TEXT NOT NULL,
+                target_title TEXT NOT NULL,
+                created_at INTEGER NOT NULL,
+                FOREIGN KEY (source_id) REFERENCES nodes(id) ON DELETE CASCADE
+            )
+            "#
+            .to_string(),
+        ))
+        .await?;
+
+        // 创建 node_links 表索引
+        db.execute(Statement::from_string(
+            db.get_database_backend(),
+            "CREATE INDEX IF NOT EXISTS idx_node_links_source ON node_links(source_id)".to_string(),
+        ))
+        .await?;
+
+        db.execute(Statement::from_string(
+            db.get_database_backend(),
+            "CREATE INDEX IF NOT EXISTS idx_node_links_target ON node_links(target_id)".to_string(),
+        ))
+        .await?;
+
         // 创建 tags 表
         db.execute(Statement::from_string(
             db.get_database_backend(),
@@ -137,6 +215,10 @@ impl DbConnection {
                 count INTEGER NOT NULL DEFAULT 1,
                 last_used INTEGER NOT NULL,
                 created_at INTEGER NOT NULL,
+                color TEXT,
+                icon TEXT,
+                description TEXT,
+                aliases TEXT,
                 FOREIGN KEY (workspace_id) REFERENCES workspaces(id) ON DELETE CASCADE
             )
             "#
@@ -144,6 +226,11 @@ impl DbConnection {
         ))
         .await?;
 
+        // 旧版本 tags 表没有元数据列
+        for column in ["color", "icon", "description", "aliases"] {
+            Self::add_column_if_missing(db, "tags", column, "TEXT").await?;
+        }
+
         // 创建 tags 表索引
         db.execute(Statement::from_string(
             db.get_database_backend(),
@@ -151,6 +238,208 @@ impl DbConnection {
         ))
         .await?;
 
+        // 创建 saved_searches 表（保存的搜索，作为虚拟文件夹展示）
+        db.execute(Statement::from_string(
+            db.get_database_backend(),
+            r#"
+            CREATE TABLE IF NOT EXISTS saved_searches (
+                id TEXT PRIMARY KEY NOT NULL,
+                workspace_id TEXT NOT NULL,
+                name TEXT NOT NULL,
+                query TEXT NOT NULL,
+                sort_order INTEGER NOT NULL DEFAULT 0,
+                created_at INTEGER NOT NULL,
+                updated_at INTEGER NOT NULL,
+                FOREIGN KEY (workspace_id) REFERENCES workspaces(id) ON DELETE CASCADE
+            )
+            "#
+            .to_string(),
+        ))
+        .await?;
+
+        db.execute(Statement::from_string(
+            db.get_database_backend(),
+            "CREATE INDEX IF NOT EXISTS idx_saved_searches_workspace ON saved_searches(workspace_id)"
+                .to_string(),
+        ))
+        .await?;
+
+        // 创建 tasks 表（从待办内容中提取的任务索引）
+        db.execute(Statement::from_string(
+            db.get_database_backend(),
+            r#"
+            CREATE TABLE IF NOT EXISTS tasks (
+                id TEXT PRIMARY KEY NOT NULL,
+                node_id TEXT NOT NULL,
+                workspace_id TEXT NOT NULL,
+                position INTEGER NOT NULL,
+                text TEXT NOT NULL,
+                checked INTEGER NOT NULL DEFAULT 0,
+                due_date TEXT,
+                priority TEXT,
+                tags TEXT,
+                content_version INTEGER NOT NULL,
+                updated_at INTEGER NOT NULL,
+                FOREIGN KEY (node_id) REFERENCES nodes(id) ON DELETE CASCADE
+            )
+            "#
+            .to_string(),
+        ))
+        .await?;
+
+        db.execute(Statement::from_string(
+            db.get_database_backend(),
+            "CREATE INDEX IF NOT EXISTS idx_tasks_workspace ON tasks(workspace_id)".to_string(),
+        ))
+        .await?;
+
+        db.execute(Statement::from_string(
+            db.get_database_backend(),
+            "CREATE INDEX IF NOT EXISTS idx_tasks_node ON tasks(node_id)".to_string(),
+        ))
+        .await?;
+
+        // 创建 writing_stats 表（写作统计缓存，按内容版本号失效）
+        db.execute(Statement::from_string(
+            db.get_database_backend(),
+            r#"
+            CREATE TABLE IF NOT EXISTS writing_stats (
+                node_id TEXT PRIMARY KEY NOT NULL,
+                workspace_id TEXT NOT NULL,
+                content_version INTEGER NOT NULL,
+                cjk_characters INTEGER NOT NULL DEFAULT 0,
+                latin_words INTEGER NOT NULL DEFAULT 0,
+                characters INTEGER NOT NULL DEFAULT 0,
+                paragraphs INTEGER NOT NULL DEFAULT 0,
+                updated_at INTEGER NOT NULL,
+                FOREIGN KEY (node_id) REFERENCES nodes(id) ON DELETE CASCADE
+            )
+            "#
+            .to_string(),
+        ))
+        .await?;
+
+        db.execute(Statement::from_string(
+            db.get_database_backend(),
+            "CREATE INDEX IF NOT EXISTS idx_writing_stats_workspace ON writing_stats(workspace_id)"
+                .to_string(),
+        ))
+        .await?;
+
+        // 创建 writing_goals 表（工作区或节点的写作目标）
+        db.execute(Statement::from_string(
+            db.get_database_backend(),
+            r#"
+            CREATE TABLE IF NOT EXISTS writing_goals (
+                id TEXT PRIMARY KEY NOT NULL,
+                workspace_id TEXT NOT NULL,
+                node_id TEXT,
+                kind TEXT NOT NULL,
+                target INTEGER NOT NULL,
+                deadline TEXT,
+                created_at INTEGER NOT NULL,
+                updated_at INTEGER NOT NULL,
+                FOREIGN KEY (workspace_id) REFERENCES workspaces(id) ON DELETE CASCADE,
+                FOREIGN KEY (node_id) REFERENCES nodes(id) ON DELETE CASCADE
+            )
+            "#
+            .to_string(),
+        ))
+        .await?;
+
+        db.execute(Statement::from_string(
+            db.get_database_backend(),
+            "CREATE INDEX IF NOT EXISTS idx_writing_goals_workspace ON writing_goals(workspace_id)"
+                .to_string(),
+        ))
+        .await?;
+
+        // 创建 writing_progress 表（每个节点每天的字数变化，节点删除后保留）
+        db.execute(Statement::from_string(
+            db.get_database_backend(),
+            r#"
+            CREATE TABLE IF NOT EXISTS writing_progress (
+                id TEXT PRIMARY KEY NOT NULL,
+                workspace_id TEXT NOT NULL,
+                node_id TEXT NOT NULL,
+                date TEXT NOT NULL,
+                words_added INTEGER NOT NULL DEFAULT 0,
+                words_removed INTEGER NOT NULL DEFAULT 0,
+                updated_at INTEGER NOT NULL,
+                FOREIGN KEY (workspace_id) REFERENCES workspaces(id) ON DELETE CASCADE
+            )
+            "#
+            .to_string(),
+        ))
+        .await?;
+
+        db.execute(Statement::from_string(
+            db.get_database_backend(),
+            "CREATE INDEX IF NOT EXISTS idx_writing_progress_workspace ON writing_progress(workspace_id, date)"
+                .to_string(),
+        ))
+        .await?;
+
+        // 创建 change_log 表（只追加的变更日志，不设外键，实体删除后保留）
+        db.execute(Statement::from_string(
+            db.get_database_backend(),
+            r#"
+            CREATE TABLE IF NOT EXISTS change_log (
+                seq INTEGER PRIMARY KEY AUTOINCREMENT,
+                workspace_id TEXT,
+                entity_type TEXT NOT NULL,
+                entity_id TEXT NOT NULL,
+                operation TEXT NOT NULL,
+                version INTEGER,
+                created_at INTEGER NOT NULL
+            )
+            "#
+            .to_string(),
+        ))
+        .await?;
+
+        db.execute(Statement::from_string(
+            db.get_database_backend(),
+            "CREATE INDEX IF NOT EXISTS idx_change_log_entity ON change_log(entity_type, entity_id)"
+                .to_string(),
+        ))
+        .await?;
+
+        // 创建 sync_remotes 表（客户端与每个同步服务器的同步游标）
+        db.execute(Statement::from_string(
+            db.get_database_backend(),
+            r#"
+            CREATE TABLE IF NOT EXISTS sync_remotes (
+                id TEXT PRIMARY KEY NOT NULL,
+                server_url TEXT NOT NULL,
+                pull_seq INTEGER NOT NULL DEFAULT 0,
+                push_seq INTEGER NOT NULL DEFAULT 0,
+                last_synced_at INTEGER,
+                updated_at INTEGER NOT NULL
+            )
+            "#
+            .to_string(),
+        ))
+        .await?;
+
+        // 创建 sync_versions 表（每个实体在服务器上的版本）
+        db.execute(Statement::from_string(
+            db.get_database_backend(),
+            r#"
+            CREATE TABLE IF NOT EXISTS sync_versions (
+                id TEXT PRIMARY KEY NOT NULL,
+                remote_id TEXT NOT NULL,
+                entity_type TEXT NOT NULL,
+                entity_id TEXT NOT NULL,
+                version INTEGER NOT NULL,
+                local_seq INTEGER NOT NULL,
+                updated_at INTEGER NOT NULL
+            )
+            "#
+            .to_string(),
+        ))
+        .await?;
+
         // 创建 users 表
         db.execute(Statement::from_string(
             db.get_database_backend(),
@@ -185,6 +474,111 @@ impl DbConnection {
         ))
         .await?;
 
+        // 创建 user_credentials 表（登录凭据，只保存密码哈希）
+        db.execute(Statement::from_string(
+            db.get_database_backend(),
+            r#"
+            CREATE TABLE IF NOT EXISTS user_credentials (
+                user_id TEXT PRIMARY KEY NOT NULL,
+                password_hash TEXT NOT NULL,
+                is_admin INTEGER NOT NULL DEFAULT 0,
+                updated_at INTEGER NOT NULL,
+                FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE
+            )
+            "#
+            .to_string(),
+        ))
+        .await?;
+
+        // 旧版本 user_credentials 表没有管理员标记
+        Self::add_column_if_missing(
+            db,
+            "user_credentials",
+            "is_admin",
+            "INTEGER NOT NULL DEFAULT 0",
+        )
+        .await?;
+
+        // 创建 auth_sessions 表（登录会话，只保存令牌哈希）
+        db.execute(Statement::from_string(
+            db.get_database_backend(),
+            r#"
+            CREATE TABLE IF NOT EXISTS auth_sessions (
+                id TEXT PRIMARY KEY NOT NULL,
+                user_id TEXT NOT NULL,
+                access_token_hash TEXT NOT NULL UNIQUE,
+                refresh_token_hash TEXT NOT NULL UNIQUE,
+                access_expires_at INTEGER NOT NULL,
+                refresh_expires_at INTEGER NOT NULL,
+                created_at INTEGER NOT NULL,
+                last_used_at INTEGER NOT NULL,
+                FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE
+            )
+            "#
+            .to_string(),
+        ))
+        .await?;
+
+        db.execute(Statement::from_string(
+            db.get_database_backend(),
+            "CREATE INDEX IF NOT EXISTS idx_auth_sessions_user ON auth_sessions(user_id)"
+                .to_string(),
+        ))
+        .await?;
+
+        // 创建 api_tokens 表（个人 API 令牌）
+        db.execute(Statement::from_string(
+            db.get_database_backend(),
+            r#"
+            CREATE TABLE IF NOT EXISTS api_tokens (
+                id TEXT PRIMARY KEY NOT NULL,
+                user_id TEXT NOT NULL,
+                name TEXT NOT NULL,
+                token_prefix TEXT NOT NULL,
+                token_hash TEXT NOT NULL UNIQUE,
+                scope TEXT NOT NULL,
+                workspace_id TEXT,
+                created_at INTEGER NOT NULL,
+                last_used_at INTEGER,
+                FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE,
+                FOREIGN KEY (workspace_id) REFERENCES workspaces(id) ON DELETE CASCADE
+            )
+            "#
+            .to_string(),
+        ))
+        .await?;
+
+        db.execute(Statement::from_string(
+            db.get_database_backend(),
+            "CREATE INDEX IF NOT EXISTS idx_api_tokens_user ON api_tokens(user_id)".to_string(),
+        ))
+        .await?;
+
+        // 创建 workspace_members 表（工作区成员及角色）
+        db.execute(Statement::from_string(
+            db.get_database_backend(),
+            r#"
+            CREATE TABLE IF NOT EXISTS workspace_members (
+                workspace_id TEXT NOT NULL,
+                user_id TEXT NOT NULL,
+                role TEXT NOT NULL,
+                created_at INTEGER NOT NULL,
+                PRIMARY KEY (workspace_id, user_id),
+                FOREIGN KEY (workspace_id) REFERENCES workspaces(id) ON DELETE CASCADE,
+                FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE
+            )
+            "#
+            .to_string(),
+        ))
+        .await?;
+
+        db.execute(Statement::from_string(
+            db.get_database_backend(),
+            "CREATE INDEX IF NOT EXISTS idx_workspace_members_user ON workspace_members(user_id)"
+                .to_string(),
+        ))
+        .await?;
+
         db.execute(Statement::from_string(
             db.get_database_backend(),
             "CREATE INDEX IF NOT EXISTS idx_users_email ON users(email)".to_string(),
@@ -229,6 +623,36 @@ impl DbConnection {
         Ok(())
     }
 
+    /// 为已存在的表补充新增的列
+    ///
+    /// `CREATE TABLE IF NOT EXISTS` 不会修改旧数据库中的表结构，
+    /// 新增的可空列或有默认值的列需要通过 `ALTER TABLE` 补上。
+    async fn add_column_if_missing(
+        db: &DatabaseConnection,
+        table: &str,
+        column: &str,
+        definition: &str,
+    ) -> AppResult<()> {
+        let exists = db
+            .query_one(Statement::from_sql_and_values(
+                db.get_database_backend(),
+                "SELECT 1 FROM pragma_table_info(?) WHERE name = ?",
+                [table.into(), column.into()],
+            ))
+            .await?
+            .is_some();
+
+        if !exists {
+            info!("补充列: {}.{}", table, column);
+            db.execute(Statement::from_string(
+                db.get_database_backend(),
+                format!("ALTER TABLE {} ADD COLUMN {} {}", table, column, definition),
+            ))
+            .await?;
+        }
+        Ok(())
+    }
+
     /// 构建数据库连接 URL
     fn build_connection_url(db_path: &PathBuf, _enable_encryption: bool) -> AppResult<String> {
         // 基础 SQLite 连接 URL
@@ -265,10 +689,45 @@ mod tests {
             db_filename: "test.db".to_string(),
             backup_dirname: "backups".to_string(),
             enable_encryption: false,
+            ..Default::default()
         };
 
         let result = DbConnection::connect(&config).await;
         assert!(result.is_ok());
         assert!(config.db_path().exists());
     }
+
+    #[tokio::test]
+    async fn test_connect_migrates_old_tags_table() {
+        let temp_dir = tempdir().unwrap();
+        let config = AppConfig {
+            data_dir: temp_dir.path().to_path_buf(),
+            db_filename: "old.db".to_string(),
+            backup_dirname: "backups".to_string(),
+            enable_encryption: false,
+            ..Default::default()
+        };
+
+        // 模拟旧版本的 tags 表
+        config.init().unwrap();
+        let url = DbConnection::build_connection_url(&config.db_path(), false).unwrap();
+        let old = Database::connect(url).await.unwrap();
+        old.execute(Statement::from_string(
+            old.get_database_backend(),
+            "CREATE TABLE tags (id TEXT PRIMARY KEY NOT NULL, name TEXT NOT NULL, workspace_id TEXT NOT NULL, count INTEGER NOT NULL DEFAULT 1, last_used INTEGER NOT NULL, created_at INTEGER NOT NULL)".to_string(),
+        ))
+        .await
+        .unwrap();
+        old.close().await.unwrap();
+
+        let db = DbConnection::connect(&config).await.unwrap();
+        let columns = db
+            .query_all(Statement::from_string(
+                db.get_database_backend(),
+                "SELECT name FROM pragma_table_info('tags')".to_string(),
+            ))
+            .await
+            .unwrap();
+        assert_eq!(columns.len(), 10);
+    }
 }
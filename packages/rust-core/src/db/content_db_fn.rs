@@ -2,11 +2,21 @@
 //!
 //! 封装内容相关的数据库操作
 
-use crate::types::content::{content_entity as content, ContentEntity as Content};
+use crate::db::link_db_fn;
+use crate::types::content::{
+    content_entity as content, content_revision_entity as revision, ContentEntity as Content,
+    ContentRevisionEntity as ContentRevision,
+};
 use crate::types::error::{AppError, AppResult};
-use sea_orm::{ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, Set};
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, ConnectionTrait, DatabaseConnection, EntityTrait, QueryFilter,
+    Set,
+};
 use tracing::info;
 
+/// 每个节点保留的历史版本数量
+const MAX_REVISIONS_PER_NODE: i32 = 50;
+
 // ============================================================================
 // 查询函数
 // ============================================================================
@@ -18,8 +28,8 @@ pub async fn find_by_id(db: &DatabaseConnection, id: &str) -> AppResult<Option<c
 }
 
 /// 根据节点 ID 查询内容
-pub async fn find_by_node_id(
-    db: &DatabaseConnection,
+pub async fn find_by_node_id<C: ConnectionTrait>(
+    db: &C,
     node_id: &str,
 ) -> AppResult<Option<content::Model>> {
     let content = Content::find()
@@ -34,8 +44,10 @@ pub async fn find_by_node_id(
 // ============================================================================
 
 /// 创建内容
-pub async fn create(
-    db: &DatabaseConnection,
+///
+/// 同时根据内容中的 `@` 引用更新链接索引
+pub async fn create<C: ConnectionTrait>(
+    db: &C,
     id: String,
     node_id: String,
     content_text: String,
@@ -52,6 +64,8 @@ pub async fn create(
     };
 
     let content = model.insert(db).await?;
+    link_db_fn::sync_links(db, &content.node_id, &content.content).await?;
+    record_revision(db, &content).await?;
     info!("创建内容: node_id={}", node_id);
     Ok(content)
 }
@@ -61,8 +75,10 @@ pub async fn create(
 // ============================================================================
 
 /// 更新内容（带乐观锁）
-pub async fn update(
-    db: &DatabaseConnection,
+///
+/// 同时根据内容中的 `@` 引用更新链接索引
+pub async fn update<C: ConnectionTrait>(
+    db: &C,
     node_id: &str,
     content_text: String,
     expected_version: Option<i32>,
@@ -90,6 +106,8 @@ pub async fn update(
     model.updated_at = Set(now);
 
     let content = model.update(db).await?;
+    link_db_fn::sync_links(db, node_id, &content.content).await?;
+    record_revision(db, &content).await?;
     info!("更新内容: node_id={}, version={}", node_id, new_version);
     Ok(content)
 }
@@ -107,6 +125,51 @@ pub async fn upsert(
     }
 }
 
+// ============================================================================
+// 历史版本
+// ============================================================================
+
+/// 记录内容的当前版本，并删除超出保留数量的旧版本
+///
+/// 接受任意连接类型，可在事务中调用。
+pub async fn record_revision<C: ConnectionTrait>(
+    db: &C,
+    content: &content::Model,
+) -> AppResult<()> {
+    let id = format!("{}:{}", content.node_id, content.version);
+    let model = revision::ActiveModel {
+        id: Set(id.clone()),
+        node_id: Set(content.node_id.clone()),
+        version: Set(content.version),
+        content: Set(content.content.clone()),
+        created_at: Set(chrono::Utc::now().timestamp_millis()),
+    };
+    if ContentRevision::find_by_id(id).one(db).await?.is_some() {
+        model.update(db).await?;
+    } else {
+        model.insert(db).await?;
+    }
+
+    ContentRevision::delete_many()
+        .filter(revision::Column::NodeId.eq(&content.node_id))
+        .filter(revision::Column::Version.lte(content.version - MAX_REVISIONS_PER_NODE))
+        .exec(db)
+        .await?;
+    Ok(())
+}
+
+/// 查询节点内容的某个历史版本
+pub async fn find_revision(
+    db: &DatabaseConnection,
+    node_id: &str,
+    version: i32,
+) -> AppResult<Option<revision::Model>> {
+    let revision = ContentRevision::find_by_id(format!("{}:{}", node_id, version))
+        .one(db)
+        .await?;
+    Ok(revision)
+}
+
 // ============================================================================
 // 删除函数
 // ============================================================================
@@ -287,4 +350,33 @@ mod tests {
         let found = find_by_id(&db, "content-1").await.unwrap();
         assert!(found.is_none());
     }
+
+    #[tokio::test]
+    async fn test_revisions_are_recorded_and_pruned() {
+        let db = setup_test_db().await;
+        let node_id = create_test_node(&db).await;
+
+        create(
+            &db,
+            "content-1".to_string(),
+            node_id.clone(),
+            "v1".to_string(),
+        )
+        .await
+        .unwrap();
+        for i in 2..=(MAX_REVISIONS_PER_NODE + 2) {
+            update(&db, &node_id, format!("v{}", i), None)
+                .await
+                .unwrap();
+        }
+
+        let revision = find_revision(&db, &node_id, 10).await.unwrap().unwrap();
+        assert_eq!(revision.content, "v10");
+        let latest = MAX_REVISIONS_PER_NODE + 2;
+        assert!(find_revision(&db, &node_id, latest)
+            .await
+            .unwrap()
+            .is_some());
+        assert!(find_revision(&db, &node_id, 2).await.unwrap().is_none());
+    }
 }
@@ -0,0 +1,152 @@
+//! ContentUpdate 数据库函数
+//!
+//! 维护协同编辑的 CRDT 更新（`content_updates` 表）
+//!
+//! - 客户端的每条更新原样追加，序号由 SQLite 自增分配
+//! - 压缩时把某个序号及之前的更新替换为合并后的一条，之后追加的更新不受影响
+
+use crate::types::content::content_update_entity as content_update;
+use crate::types::content::ContentUpdateEntity;
+use crate::types::error::AppResult;
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, ConnectionTrait, EntityTrait, PaginatorTrait, QueryFilter,
+    QueryOrder, Set,
+};
+use tracing::info;
+
+/// 追加一条更新
+pub async fn append<C: ConnectionTrait>(
+    db: &C,
+    node_id: &str,
+    data: Vec<u8>,
+    content_version: i32,
+) -> AppResult<content_update::Model> {
+    let update = content_update::ActiveModel {
+        node_id: Set(node_id.to_string()),
+        data: Set(data),
+        content_version: Set(content_version),
+        created_at: Set(chrono::Utc::now().timestamp_millis()),
+        ..Default::default()
+    }
+    .insert(db)
+    .await?;
+    Ok(update)
+}
+
+/// 查询节点的全部更新（按序号升序）
+pub async fn find_by_node_id<C: ConnectionTrait>(
+    db: &C,
+    node_id: &str,
+) -> AppResult<Vec<content_update::Model>> {
+    let updates = ContentUpdateEntity::find()
+        .filter(content_update::Column::NodeId.eq(node_id))
+        .order_by_asc(content_update::Column::Id)
+        .all(db)
+        .await?;
+    Ok(updates)
+}
+
+/// 统计节点的更新条数
+pub async fn count_by_node_id<C: ConnectionTrait>(db: &C, node_id: &str) -> AppResult<u64> {
+    let count = ContentUpdateEntity::find()
+        .filter(content_update::Column::NodeId.eq(node_id))
+        .count(db)
+        .await?;
+    Ok(count)
+}
+
+/// 查询 CRDT 文档已合入的最新内容版本号（没有更新时为 None）
+pub async fn synced_version<C: ConnectionTrait>(db: &C, node_id: &str) -> AppResult<Option<i32>> {
+    let latest = ContentUpdateEntity::find()
+        .filter(content_update::Column::NodeId.eq(node_id))
+        .order_by_desc(content_update::Column::ContentVersion)
+        .one(db)
+        .await?;
+    Ok(latest.map(|u| u.content_version))
+}
+
+/// 把序号 `up_to` 及之前的更新替换为合并后的一条
+pub async fn replace<C: ConnectionTrait>(
+    db: &C,
+    node_id: &str,
+    up_to: i64,
+    data: Vec<u8>,
+    content_version: i32,
+) -> AppResult<content_update::Model> {
+    let removed = ContentUpdateEntity::delete_many()
+        .filter(content_update::Column::NodeId.eq(node_id))
+        .filter(content_update::Column::Id.lte(up_to))
+        .exec(db)
+        .await?
+        .rows_affected;
+    let merged = append(db, node_id, data, content_version).await?;
+    info!("压缩 CRDT 更新: node_id={}, 合并 {} 条", node_id, removed);
+    Ok(merged)
+}
+
+/// 更新记录对应的内容版本号
+pub async fn set_content_version<C: ConnectionTrait>(
+    db: &C,
+    id: i64,
+    content_version: i32,
+) -> AppResult<()> {
+    content_update::ActiveModel {
+        id: Set(id),
+        content_version: Set(content_version),
+        ..Default::default()
+    }
+    .update(db)
+    .await?;
+    Ok(())
+}
+
+// ============================================================================
+// 测试
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::test_utils::setup_test_db;
+    use crate::db::{node_db_fn, workspace_db_fn};
+    use crate::types::node::NodeType;
+
+    #[tokio::test]
+    async fn test_append_and_replace() {
+        let db = setup_test_db().await;
+        workspace_db_fn::create(&db, "ws".to_string(), "测试工作区".to_string(), None)
+            .await
+            .unwrap();
+        node_db_fn::create(
+            &db,
+            "n1".to_string(),
+            "ws".to_string(),
+            None,
+            "测试节点".to_string(),
+            NodeType::File,
+            None,
+        )
+        .await
+        .unwrap();
+
+        let first = append(&db, "n1", vec![1], 1).await.unwrap();
+        let second = append(&db, "n1", vec![2], 1).await.unwrap();
+        append(&db, "n1", vec![3], 2).await.unwrap();
+        assert!(second.id > first.id);
+        assert_eq!(count_by_node_id(&db, "n1").await.unwrap(), 3);
+
+        // 替换前两条，第三条保留
+        let merged = replace(&db, "n1", second.id, vec![1, 2], 1).await.unwrap();
+        set_content_version(&db, merged.id, 5).await.unwrap();
+        let updates = find_by_node_id(&db, "n1").await.unwrap();
+        let data: Vec<_> = updates.iter().map(|u| u.data.clone()).collect();
+        assert_eq!(data, vec![vec![3], vec![1, 2]]);
+        assert_eq!(updates[1].content_version, 5);
+        assert_eq!(synced_version(&db, "n1").await.unwrap(), Some(5));
+
+        // 删除节点时级联删除
+        node_db_fn::delete(&db, "n1").await.unwrap();
+        assert_eq!(count_by_node_id(&db, "n1").await.unwrap(), 0);
+        assert_eq!(synced_version(&db, "n1").await.unwrap(), None);
+    }
+}
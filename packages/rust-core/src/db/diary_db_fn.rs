@@ -0,0 +1,266 @@
+//! Diary 数据库函数
+//!
+//! 维护日记的年/月/日文件夹结构，并为日历查询收集日记节点
+
+use std::collections::HashMap;
+
+use chrono::NaiveDate;
+
+use crate::db::{content_db_fn, link_db_fn, tag_db_fn};
+use crate::r#fn::diary::{
+    diary_entry_date, diary_entry_title, diary_folders, render_diary_template,
+};
+use crate::r#fn::search::content_plain_text;
+use crate::r#fn::tag::extract_content_tags;
+use crate::r#fn::writing::count_words;
+use crate::types::content::{content_entity as content, ContentEntity as Content};
+use crate::types::error::AppResult;
+use crate::types::node::{node_entity as node, NodeEntity as Node, NodeType};
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, ConnectionTrait, DatabaseConnection, EntityTrait, QueryFilter,
+    QueryOrder, QuerySelect, Set,
+};
+use tracing::info;
+
+// ============================================================================
+// 查询函数
+// ============================================================================
+
+/// 查询工作区内所有日记节点及其日期
+pub async fn find_entries(
+    db: &DatabaseConnection,
+    workspace_id: &str,
+) -> AppResult<Vec<(NaiveDate, node::Model)>> {
+    let nodes = Node::find()
+        .filter(node::Column::WorkspaceId.eq(workspace_id))
+        .all(db)
+        .await?;
+
+    let by_id: HashMap<&str, &node::Model> = nodes.iter().map(|n| (n.id.as_str(), n)).collect();
+    let entries = nodes
+        .iter()
+        .filter(|n| n.node_type == NodeType::Diary)
+        .map(|n| (diary_entry_date(n, &by_id), n.clone()))
+        .collect();
+    Ok(entries)
+}
+
+/// 统计指定节点内容的字数
+pub async fn word_counts(
+    db: &DatabaseConnection,
+    node_ids: Vec<String>,
+) -> AppResult<HashMap<String, u64>> {
+    let rows: Vec<(String, String)> = Content::find()
+        .select_only()
+        .column(content::Column::NodeId)
+        .column(content::Column::Content)
+        .filter(content::Column::NodeId.is_in(node_ids))
+        .into_tuple()
+        .all(db)
+        .await?;
+
+    Ok(rows
+        .into_iter()
+        .map(|(node_id, text)| (node_id, count_words(&content_plain_text(&text))))
+        .collect())
+}
+
+// ============================================================================
+// 创建函数
+// ============================================================================
+
+/// 获取或创建指定日期的日记
+///
+/// 依次查找或创建根文件夹、年、月、日文件夹；日文件夹中已有日记时直接返回最早的一篇，
+/// 否则用模板创建新日记，并从内容中同步标签和链接。
+/// 返回（日记节点，内容，是否新建）。应在事务中调用。
+pub async fn get_or_create_entry<C: ConnectionTrait>(
+    db: &C,
+    workspace_id: &str,
+    date: NaiveDate,
+    root_folder: &str,
+    template: &str,
+) -> AppResult<(node::Model, Option<content::Model>, bool)> {
+    let folders = diary_folders(date);
+    let root = ensure_folder(db, workspace_id, None, root_folder).await?;
+    let year = ensure_folder(db, workspace_id, Some(&root.id), &folders.year).await?;
+    let month = ensure_folder(db, workspace_id, Some(&year.id), &folders.month).await?;
+    let day = ensure_folder(db, workspace_id, Some(&month.id), &folders.day).await?;
+
+    let existing = Node::find()
+        .filter(node::Column::ParentId.eq(&day.id))
+        .filter(node::Column::NodeType.eq(NodeType::Diary))
+        .order_by_asc(node::Column::CreatedAt)
+        .one(db)
+        .await?;
+    if let Some(entry) = existing {
+        let content = Content::find()
+            .filter(content::Column::NodeId.eq(&entry.id))
+            .one(db)
+            .await?;
+        return Ok((entry, content, false));
+    }
+
+    let now = chrono::Utc::now().timestamp_millis();
+    let entry = node::ActiveModel {
+        id: Set(uuid::Uuid::new_v4().to_string()),
+        workspace_id: Set(workspace_id.to_string()),
+        parent_id: Set(Some(day.id.clone())),
+        title: Set(diary_entry_title(date)),
+        node_type: Set(NodeType::Diary),
+        is_collapsed: Set(false),
+        sort_order: Set(next_sort_order(db, workspace_id, Some(&day.id)).await?),
+        tags: Set(None),
+        created_at: Set(now),
+        updated_at: Set(now),
+    }
+    .insert(db)
+    .await?;
+
+    let content = content::ActiveModel {
+        id: Set(uuid::Uuid::new_v4().to_string()),
+        node_id: Set(entry.id.clone()),
+        content: Set(render_diary_template(template, date)),
+        version: Set(1),
+        created_at: Set(now),
+        updated_at: Set(now),
+    }
+    .insert(db)
+    .await?;
+    link_db_fn::sync_links(db, &entry.id, &content.content).await?;
+    content_db_fn::record_revision(db, &content).await?;
+
+    let entry = match extract_content_tags(&content.content) {
+        Some(tags) if tag_db_fn::sync_node_tags(db, &entry.id, &tags).await? => {
+            Node::find_by_id(&entry.id).one(db).await?.unwrap_or(entry)
+        }
+        _ => entry,
+    };
+
+    info!("创建日记: {} ({})", entry.title, entry.id);
+    Ok((entry, Some(content), true))
+}
+
+/// 查找或创建文件夹（按父节点和标题匹配）
+async fn ensure_folder<C: ConnectionTrait>(
+    db: &C,
+    workspace_id: &str,
+    parent_id: Option<&str>,
+    title: &str,
+) -> AppResult<node::Model> {
+    let parent_filter = match parent_id {
+        Some(pid) => node::Column::ParentId.eq(pid),
+        None => node::Column::ParentId.is_null(),
+    };
+    let existing = Node::find()
+        .filter(node::Column::WorkspaceId.eq(workspace_id))
+        .filter(parent_filter)
+        .filter(node::Column::Title.eq(title))
+        .filter(node::Column::NodeType.eq(NodeType::Folder))
+        .order_by_asc(node::Column::SortOrder)
+        .one(db)
+        .await?;
+    if let Some(folder) = existing {
+        return Ok(folder);
+    }
+
+    let now = chrono::Utc::now().timestamp_millis();
+    let folder = node::ActiveModel {
+        id: Set(uuid::Uuid::new_v4().to_string()),
+        workspace_id: Set(workspace_id.to_string()),
+        parent_id: Set(parent_id.map(str::to_string)),
+        title: Set(title.to_string()),
+        node_type: Set(NodeType::Folder),
+        is_collapsed: Set(true),
+        sort_order: Set(next_sort_order(db, workspace_id, parent_id).await?),
+        tags: Set(None),
+        created_at: Set(now),
+        updated_at: Set(now),
+    }
+    .insert(db)
+    .await?;
+    Ok(folder)
+}
+
+/// 同级节点中的下一个排序顺序
+async fn next_sort_order<C: ConnectionTrait>(
+    db: &C,
+    workspace_id: &str,
+    parent_id: Option<&str>,
+) -> AppResult<i32> {
+    let parent_filter = match parent_id {
+        Some(pid) => node::Column::ParentId.eq(pid),
+        None => node::Column::ParentId.is_null(),
+    };
+    let max: Option<i32> = Node::find()
+        .select_only()
+        .column_as(node::Column::SortOrder.max(), "sort_order")
+        .filter(node::Column::WorkspaceId.eq(workspace_id))
+        .filter(parent_filter)
+        .into_tuple()
+        .one(db)
+        .await?
+        .flatten();
+    Ok(max.map_or(0, |n| n + 1))
+}
+
+// ============================================================================
+// 测试
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::test_utils::setup_test_db;
+    use crate::db::workspace_db_fn;
+    use crate::r#fn::diary::{default_diary_template, parse_diary_date};
+
+    #[tokio::test]
+    async fn test_get_or_create_entry_is_idempotent() {
+        let db = setup_test_db().await;
+        let workspace_id = uuid::Uuid::new_v4().to_string();
+        workspace_db_fn::create(&db, workspace_id.clone(), "测试工作区".to_string(), None)
+            .await
+            .unwrap();
+
+        let date = parse_diary_date("2026-01-01").unwrap();
+        let template = default_diary_template();
+        let (entry, content, created) =
+            get_or_create_entry(&db, &workspace_id, date, "Diary", &template)
+                .await
+                .unwrap();
+        assert!(created);
+        assert!(content.is_some());
+        assert_eq!(entry.title, "diary-2026-01-01.grain");
+        assert_eq!(entry.tags.as_deref(), Some(r#"["diary","notes"]"#));
+
+        let (again, _, created) = get_or_create_entry(&db, &workspace_id, date, "Diary", &template)
+            .await
+            .unwrap();
+        assert!(!created);
+        assert_eq!(again.id, entry.id);
+
+        // 同月另一天复用年、月文件夹
+        let next = parse_diary_date("2026-01-02").unwrap();
+        get_or_create_entry(&db, &workspace_id, next, "Diary", &template)
+            .await
+            .unwrap();
+
+        let folders: Vec<node::Model> = Node::find()
+            .filter(node::Column::WorkspaceId.eq(&workspace_id))
+            .filter(node::Column::NodeType.eq(NodeType::Folder))
+            .all(&db)
+            .await
+            .unwrap();
+        assert_eq!(folders.len(), 5);
+
+        let entries = find_entries(&db, &workspace_id).await.unwrap();
+        let mut dates: Vec<NaiveDate> = entries.iter().map(|(d, _)| *d).collect();
+        dates.sort();
+        assert_eq!(dates, vec![date, next]);
+
+        let counts = word_counts(&db, vec![entry.id.clone()]).await.unwrap();
+        // "#[diary] #[notes]" 2 词 + "2026-01-01 Thursday" 2 词
+        assert_eq!(counts.get(&entry.id), Some(&4));
+    }
+}
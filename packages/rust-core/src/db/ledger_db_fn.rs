@@ -0,0 +1,97 @@
+//! Ledger 数据库函数
+//!
+//! 加载记账节点及其内容，供记账引擎解析
+
+use crate::types::content::{content_entity as content, ContentEntity as Content};
+use crate::types::error::AppResult;
+use crate::types::node::{node_entity as node, NodeEntity as Node, NodeType};
+use sea_orm::{ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, QueryOrder};
+
+// ============================================================================
+// 查询函数
+// ============================================================================
+
+/// 查询工作区内的记账节点及其内容（按标题排序）
+///
+/// 指定 `node_id` 时只返回该节点（不是记账节点时返回空）。
+/// 没有内容的记账节点不包含在结果中。
+pub async fn find_ledgers(
+    db: &DatabaseConnection,
+    workspace_id: &str,
+    node_id: Option<&str>,
+) -> AppResult<Vec<(node::Model, String)>> {
+    let mut query = Node::find()
+        .find_also_related(Content)
+        .filter(node::Column::WorkspaceId.eq(workspace_id))
+        .filter(node::Column::NodeType.eq(NodeType::Ledger));
+    if let Some(node_id) = node_id {
+        query = query.filter(node::Column::Id.eq(node_id));
+    }
+
+    let rows = query
+        .order_by_asc(node::Column::Title)
+        .order_by_asc(node::Column::Id)
+        .all(db)
+        .await?;
+    Ok(rows
+        .into_iter()
+        .filter_map(|(node, content)| content.map(|c: content::Model| (node, c.content)))
+        .collect())
+}
+
+// ============================================================================
+// 测试
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::test_utils::setup_test_db;
+    use crate::db::{content_db_fn, node_db_fn, workspace_db_fn};
+
+    #[tokio::test]
+    async fn test_find_ledgers() {
+        let db = setup_test_db().await;
+        let workspace_id = uuid::Uuid::new_v4().to_string();
+        workspace_db_fn::create(&db, workspace_id.clone(), "测试工作区".to_string(), None)
+            .await
+            .unwrap();
+
+        for (id, node_type) in [
+            ("b-ledger", NodeType::Ledger),
+            ("a-ledger", NodeType::Ledger),
+            ("empty", NodeType::Ledger),
+            ("note", NodeType::File),
+        ] {
+            node_db_fn::create(
+                &db,
+                id.to_string(),
+                workspace_id.clone(),
+                None,
+                id.to_string(),
+                node_type,
+                None,
+            )
+            .await
+            .unwrap();
+            if id != "empty" {
+                content_db_fn::create(&db, format!("c-{}", id), id.to_string(), "x".to_string())
+                    .await
+                    .unwrap();
+            }
+        }
+
+        let ledgers = find_ledgers(&db, &workspace_id, None).await.unwrap();
+        let ids: Vec<&str> = ledgers.iter().map(|(n, _)| n.id.as_str()).collect();
+        assert_eq!(ids, vec!["a-ledger", "b-ledger"]);
+
+        let one = find_ledgers(&db, &workspace_id, Some("b-ledger"))
+            .await
+            .unwrap();
+        assert_eq!(one.len(), 1);
+        assert!(find_ledgers(&db, &workspace_id, Some("note"))
+            .await
+            .unwrap()
+            .is_empty());
+    }
+}
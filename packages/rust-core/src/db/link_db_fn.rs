@@ -0,0 +1,237 @@
+//! NodeLink 数据库函数
+//!
+//! 维护从内容中解析出的 `@` 引用索引（`node_links` 表）
+//!
+//! - 源节点删除时，其链接通过外键级联删除
+//! - 目标节点删除时，链接保留为断链；目标以相同 ID 恢复后自动重新生效
+
+use crate::db::content_db_fn;
+use crate::r#fn::link::{extract_mentions, rewrite_mentions};
+use crate::types::content::{content_entity as content, ContentEntity as Content};
+use crate::types::error::AppResult;
+use crate::types::link::{
+    link_entity as link, LinkRewriteReport, NodeLinkEntity as NodeLink, RewrittenDocument,
+};
+use crate::types::node::{node_entity as node, NodeEntity as Node};
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, ConnectionTrait, DatabaseConnection, EntityTrait, QueryFilter,
+    QueryOrder, QuerySelect, Set, Statement,
+};
+use tracing::info;
+
+// ============================================================================
+// 查询函数
+// ============================================================================
+
+/// 查询节点的所有出链（包含断链）
+pub async fn find_by_source(
+    db: &DatabaseConnection,
+    source_id: &str,
+) -> AppResult<Vec<link::Model>> {
+    let links = NodeLink::find()
+        .filter(link::Column::SourceId.eq(source_id))
+        .order_by_asc(link::Column::TargetTitle)
+        .all(db)
+        .await?;
+    Ok(links)
+}
+
+/// 查询工作区内的所有链接（按源节点所属工作区）
+pub async fn find_by_workspace(
+    db: &DatabaseConnection,
+    workspace_id: &str,
+) -> AppResult<Vec<link::Model>> {
+    let links = NodeLink::find()
+        .inner_join(Node)
+        .filter(node::Column::WorkspaceId.eq(workspace_id))
+        .order_by_asc(link::Column::Id)
+        .all(db)
+        .await?;
+    Ok(links)
+}
+
+/// 查询引用了指定节点的所有源节点（反向链接）
+pub async fn find_backlinks(
+    db: &DatabaseConnection,
+    target_id: &str,
+) -> AppResult<Vec<node::Model>> {
+    let source_ids: Vec<String> = NodeLink::find()
+        .select_only()
+        .column(link::Column::SourceId)
+        .filter(link::Column::TargetId.eq(target_id))
+        .into_tuple()
+        .all(db)
+        .await?;
+
+    let nodes = Node::find()
+        .filter(node::Column::Id.is_in(source_ids))
+        .order_by_asc(node::Column::Title)
+        .all(db)
+        .await?;
+    Ok(nodes)
+}
+
+/// 查询节点引用的所有仍然存在的目标节点
+pub async fn find_outgoing(
+    db: &DatabaseConnection,
+    source_id: &str,
+) -> AppResult<Vec<node::Model>> {
+    let target_ids: Vec<String> = NodeLink::find()
+        .select_only()
+        .column(link::Column::TargetId)
+        .filter(link::Column::SourceId.eq(source_id))
+        .into_tuple()
+        .all(db)
+        .await?;
+
+    let nodes = Node::find()
+        .filter(node::Column::Id.is_in(target_ids))
+        .order_by_asc(node::Column::Title)
+        .all(db)
+        .await?;
+    Ok(nodes)
+}
+
+/// 查询工作区内所有断链（目标节点已不存在）
+pub async fn find_broken(
+    db: &DatabaseConnection,
+    workspace_id: &str,
+) -> AppResult<Vec<link::Model>> {
+    let links = NodeLink::find()
+        .from_raw_sql(Statement::from_sql_and_values(
+            db.get_database_backend(),
+            r#"
+            SELECT node_links.*
+            FROM node_links
+            JOIN nodes source ON source.id = node_links.source_id
+            LEFT JOIN nodes target ON target.id = node_links.target_id
+            WHERE source.workspace_id = ? AND target.id IS NULL
+            ORDER BY node_links.source_id, node_links.target_title
+            "#,
+            [workspace_id.into()],
+        ))
+        .all(db)
+        .await?;
+    Ok(links)
+}
+
+// ============================================================================
+// 更新函数
+// ============================================================================
+
+/// 根据内容重建源节点的出链
+///
+/// 删除旧链接后写入内容中当前的所有 `@` 引用，忽略指向自身的引用。
+/// 接受任意连接类型，可在事务中调用。
+pub async fn sync_links<C: ConnectionTrait>(
+    db: &C,
+    source_id: &str,
+    content_text: &str,
+) -> AppResult<usize> {
+    NodeLink::delete_many()
+        .filter(link::Column::SourceId.eq(source_id))
+        .exec(db)
+        .await?;
+
+    let now = chrono::Utc::now().timestamp_millis();
+    let models: Vec<link::ActiveModel> = extract_mentions(content_text)
+        .into_iter()
+        .filter(|mention| mention.node_id != source_id)
+        .map(|mention| link::ActiveModel {
+            id: Set(format!("{}:{}", source_id, mention.node_id)),
+            source_id: Set(source_id.to_string()),
+            target_id: Set(mention.node_id),
+            target_title: Set(mention.name),
+            created_at: Set(now),
+        })
+        .collect();
+
+    let count = models.len();
+    if count > 0 {
+        NodeLink::insert_many(models).exec(db).await?;
+    }
+    Ok(count)
+}
+
+/// 将所有引用目标节点的 `@` 提及改写为新名称
+///
+/// 通过链接索引找到引用方内容，改写 mention 节点并递增内容版本号。
+/// 应在事务中调用，与节点本身的重命名/移动一起提交。
+pub async fn rewrite_mentions_to<C: ConnectionTrait>(
+    db: &C,
+    target_id: &str,
+    new_title: &str,
+) -> AppResult<LinkRewriteReport> {
+    let source_ids: Vec<String> = NodeLink::find()
+        .select_only()
+        .column(link::Column::SourceId)
+        .filter(link::Column::TargetId.eq(target_id))
+        .into_tuple()
+        .all(db)
+        .await?;
+
+    let contents = Content::find()
+        .filter(content::Column::NodeId.is_in(source_ids))
+        .find_also_related(Node)
+        .order_by_asc(content::Column::NodeId)
+        .all(db)
+        .await?;
+
+    let now = chrono::Utc::now().timestamp_millis();
+    let mut documents = Vec::new();
+    for (existing, source) in contents {
+        let Some((new_content, mentions)) =
+            rewrite_mentions(&existing.content, target_id, new_title)
+        else {
+            continue;
+        };
+
+        let node_id = existing.node_id.clone();
+        let new_version = existing.version + 1;
+        let mut model: content::ActiveModel = existing.into();
+        model.content = Set(new_content);
+        model.version = Set(new_version);
+        model.updated_at = Set(now);
+        let updated = model.update(db).await?;
+        sync_links(db, &node_id, &updated.content).await?;
+        content_db_fn::record_revision(db, &updated).await?;
+
+        documents.push(RewrittenDocument {
+            node_id,
+            title: source.map(|n| n.title).unwrap_or_default(),
+            mentions,
+            version: updated.version,
+        });
+    }
+
+    info!(
+        "改写引用: target={}, documents={}",
+        target_id,
+        documents.len()
+    );
+    Ok(LinkRewriteReport {
+        target_id: target_id.to_string(),
+        new_title: new_title.to_string(),
+        documents,
+    })
+}
+
+/// 从内容表重建工作区的链接索引
+///
+/// 用于恢复旧备份（无链接索引）或索引与内容不一致时。
+/// 返回重建后的链接总数。
+pub async fn rebuild_for_workspace(db: &DatabaseConnection, workspace_id: &str) -> AppResult<u64> {
+    let contents = Content::find()
+        .inner_join(Node)
+        .filter(node::Column::WorkspaceId.eq(workspace_id))
+        .all(db)
+        .await?;
+
+    let mut total = 0u64;
+    for item in &contents {
+        total += sync_links(db, &item.node_id, &item.content).await? as u64;
+    }
+
+    info!("重建链接索引: workspace={}, links={}", workspace_id, total);
+    Ok(total)
+}
@@ -2,18 +2,30 @@
 //!
 //! 包含数据库连接管理和各实体的 CRUD 操作函数。
 
+pub mod api_token_db_fn;
 pub mod attachment_db_fn;
+pub mod auth_db_fn;
+pub mod change_log_db_fn;
 pub mod clear_data_db_fn;
 pub mod connection;
 pub mod content_db_fn;
+pub mod content_update_db_fn;
+pub mod diary_db_fn;
+pub mod ledger_db_fn;
+pub mod link_db_fn;
 pub mod log_db_fn;
 pub mod node_db_fn;
+pub mod search_db_fn;
+pub mod sync_db_fn;
 pub mod tag_db_fn;
+pub mod task_db_fn;
 pub mod user_db_fn;
 pub mod workspace_db_fn;
+pub mod workspace_member_db_fn;
+pub mod writing_db_fn;
 
 #[cfg(test)]
 pub mod test_utils;
 
-pub use clear_data_db_fn::{clear_all_data, ClearDataOptions, ClearDataResult};
+pub use clear_data_db_fn::{clear_all_data, clear_all_data_in, ClearDataOptions, ClearDataResult};
 pub use connection::DbConnection;
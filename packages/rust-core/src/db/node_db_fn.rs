@@ -2,26 +2,100 @@
 //!
 //! 封装节点相关的数据库操作
 
+use std::collections::HashSet;
+
 use crate::types::error::{AppError, AppResult};
 use crate::types::node::{node_entity as node, NodeEntity as Node, NodeType};
 use sea_orm::{
-    ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, QueryOrder, Set,
+    ActiveModelTrait, ColumnTrait, ConnectionTrait, DatabaseConnection, DbBackend, EntityTrait,
+    QueryFilter, QueryOrder, Set, Statement, Value,
 };
 use tracing::info;
 
+/// 未指定最大深度时的递归上限
+///
+/// 防止数据中意外出现的父子环导致递归 CTE 无限展开
+const MAX_TREE_DEPTH: u32 = 1024;
+
+// ============================================================================
+// 递归 CTE
+// ============================================================================
+
+/// 子树查询的起点
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum SubtreeAnchor<'a> {
+    /// 从指定节点开始（结果不包含这些节点本身）
+    Nodes(&'a [String]),
+    /// 从工作区的根节点开始（结果包含根节点）
+    WorkspaceRoots(&'a str),
+}
+
+/// 构建子树查询语句（递归 CTE）
+///
+/// 一次查询返回起点下的所有后代节点，按深度和排序顺序排列，
+/// 替代逐层调用 `find_children` 的 N+1 查询。
+///
+/// `max_depth` 以起点的直接子节点为第 1 层（工作区根节点为第 1 层）。
+pub(crate) fn subtree_statement(
+    backend: DbBackend,
+    anchor: SubtreeAnchor<'_>,
+    max_depth: Option<u32>,
+) -> Statement {
+    let (anchor_sql, mut values): (String, Vec<Value>) = match anchor {
+        SubtreeAnchor::Nodes(ids) => {
+            let placeholders = vec!["?"; ids.len()].join(", ");
+            (
+                format!("SELECT id, 0 FROM nodes WHERE id IN ({})", placeholders),
+                ids.iter().map(|id| id.clone().into()).collect(),
+            )
+        }
+        SubtreeAnchor::WorkspaceRoots(workspace_id) => (
+            "SELECT id, 1 FROM nodes WHERE workspace_id = ? AND parent_id IS NULL".to_string(),
+            vec![workspace_id.into()],
+        ),
+    };
+    values.push(
+        max_depth
+            .unwrap_or(MAX_TREE_DEPTH)
+            .min(MAX_TREE_DEPTH)
+            .into(),
+    );
+
+    let sql = format!(
+        r#"
+        WITH RECURSIVE subtree(id, depth) AS (
+            {}
+            UNION ALL
+            SELECT n.id, s.depth + 1
+            FROM nodes n
+            JOIN subtree s ON n.parent_id = s.id
+            WHERE s.depth < ?
+        )
+        SELECT nodes.*
+        FROM nodes
+        JOIN subtree ON nodes.id = subtree.id
+        WHERE subtree.depth > 0
+        ORDER BY subtree.depth, nodes.sort_order
+        "#,
+        anchor_sql
+    );
+
+    Statement::from_sql_and_values(backend, sql, values)
+}
+
 // ============================================================================
 // 查询函数
 // ============================================================================
 
 /// 根据 ID 查询节点
-pub async fn find_by_id(db: &DatabaseConnection, id: &str) -> AppResult<Option<node::Model>> {
+pub async fn find_by_id<C: ConnectionTrait>(db: &C, id: &str) -> AppResult<Option<node::Model>> {
     let node = Node::find_by_id(id).one(db).await?;
     Ok(node)
 }
 
 /// 查询工作区下的所有节点
-pub async fn find_by_workspace(
-    db: &DatabaseConnection,
+pub async fn find_by_workspace<C: ConnectionTrait>(
+    db: &C,
     workspace_id: &str,
 ) -> AppResult<Vec<node::Model>> {
     let nodes = Node::find()
@@ -33,8 +107,8 @@ pub async fn find_by_workspace(
 }
 
 /// 查询子节点
-pub async fn find_children(
-    db: &DatabaseConnection,
+pub async fn find_children<C: ConnectionTrait>(
+    db: &C,
     parent_id: &str,
 ) -> AppResult<Vec<node::Model>> {
     let nodes = Node::find()
@@ -46,8 +120,8 @@ pub async fn find_children(
 }
 
 /// 查询根节点（没有父节点的节点）
-pub async fn find_root_nodes(
-    db: &DatabaseConnection,
+pub async fn find_root_nodes<C: ConnectionTrait>(
+    db: &C,
     workspace_id: &str,
 ) -> AppResult<Vec<node::Model>> {
     let nodes = Node::find()
@@ -101,23 +175,49 @@ pub async fn find_by_type(
     Ok(nodes)
 }
 
-/// 获取节点的所有后代（递归）
-pub async fn find_descendants(
-    db: &DatabaseConnection,
+/// 获取节点的所有后代（递归 CTE，按深度排序）
+pub async fn find_descendants<C: ConnectionTrait>(
+    db: &C,
     node_id: &str,
 ) -> AppResult<Vec<node::Model>> {
-    let mut descendants = Vec::new();
-    let mut stack = vec![node_id.to_string()];
-
-    while let Some(current_id) = stack.pop() {
-        let children = find_children(db, &current_id).await?;
-        for child in children {
-            stack.push(child.id.clone());
-            descendants.push(child);
-        }
-    }
+    let ids = [node_id.to_string()];
+    let nodes = Node::find()
+        .from_raw_sql(subtree_statement(
+            db.get_database_backend(),
+            SubtreeAnchor::Nodes(&ids),
+            None,
+        ))
+        .all(db)
+        .await?;
+    Ok(nodes)
+}
 
-    Ok(descendants)
+/// 查询节点树（递归 CTE，按深度排序）
+///
+/// - `root_id` 为 None 时从工作区根节点开始，结果包含根节点
+/// - `root_id` 为 Some 时返回该节点的后代，不包含该节点本身
+/// - `max_depth` 限制返回的层数（None 表示不限制）
+pub async fn find_tree(
+    db: &DatabaseConnection,
+    workspace_id: &str,
+    root_id: Option<&str>,
+    max_depth: Option<u32>,
+) -> AppResult<Vec<node::Model>> {
+    let root_ids: Vec<String> = root_id.map(|id| vec![id.to_string()]).unwrap_or_default();
+    let anchor = match root_id {
+        Some(_) => SubtreeAnchor::Nodes(&root_ids),
+        None => SubtreeAnchor::WorkspaceRoots(workspace_id),
+    };
+
+    let nodes = Node::find()
+        .from_raw_sql(subtree_statement(
+            db.get_database_backend(),
+            anchor,
+            max_depth,
+        ))
+        .all(db)
+        .await?;
+    Ok(nodes)
 }
 
 // ============================================================================
@@ -125,8 +225,8 @@ pub async fn find_descendants(
 // ============================================================================
 
 /// 创建节点
-pub async fn create(
-    db: &DatabaseConnection,
+pub async fn create<C: ConnectionTrait>(
+    db: &C,
     id: String,
     workspace_id: String,
     parent_id: Option<String>,
@@ -158,8 +258,8 @@ pub async fn create(
 }
 
 /// 获取下一个排序顺序
-pub async fn get_next_sort_order(
-    db: &DatabaseConnection,
+pub async fn get_next_sort_order<C: ConnectionTrait>(
+    db: &C,
     workspace_id: &str,
     parent_id: Option<&str>,
 ) -> AppResult<i32> {
@@ -177,15 +277,18 @@ pub async fn get_next_sort_order(
 // ============================================================================
 
 /// 更新节点
-pub async fn update(
-    db: &DatabaseConnection,
+///
+/// 接受任意连接类型，可在事务中调用
+pub async fn update<C: ConnectionTrait>(
+    db: &C,
     id: &str,
     title: Option<String>,
     is_collapsed: Option<bool>,
     sort_order: Option<i32>,
     tags: Option<Option<String>>,
 ) -> AppResult<node::Model> {
-    let existing = find_by_id(db, id)
+    let existing = Node::find_by_id(id)
+        .one(db)
         .await?
         .ok_or_else(|| AppError::not_found(format!("Node {}", id)))?;
 
@@ -212,16 +315,41 @@ pub async fn update(
     Ok(node)
 }
 
+/// 检查父节点存在且属于指定的工作区（没有父节点时不检查）
+///
+/// 父节点不存在时返回 NotFound，属于其他工作区时返回 ValidationError。
+pub async fn check_parent<C: ConnectionTrait>(
+    db: &C,
+    workspace_id: &str,
+    parent_id: Option<&str>,
+) -> AppResult<()> {
+    let Some(parent_id) = parent_id else {
+        return Ok(());
+    };
+    let parent = Node::find_by_id(parent_id)
+        .one(db)
+        .await?
+        .ok_or_else(|| AppError::not_found(format!("Node {}", parent_id)))?;
+    if parent.workspace_id != workspace_id {
+        return Err(AppError::validation("父节点属于其他工作区"));
+    }
+    Ok(())
+}
+
 /// 移动节点到新的父节点
-pub async fn move_node(
-    db: &DatabaseConnection,
+///
+/// 接受任意连接类型，可在事务中调用。新的父节点必须属于同一工作区。
+pub async fn move_node<C: ConnectionTrait>(
+    db: &C,
     id: &str,
     new_parent_id: Option<String>,
     new_sort_order: i32,
 ) -> AppResult<node::Model> {
-    let existing = find_by_id(db, id)
+    let existing = Node::find_by_id(id)
+        .one(db)
         .await?
         .ok_or_else(|| AppError::not_found(format!("Node {}", id)))?;
+    check_parent(db, &existing.workspace_id, new_parent_id.as_deref()).await?;
 
     let now = chrono::Utc::now().timestamp_millis();
 
@@ -236,7 +364,7 @@ pub async fn move_node(
 }
 
 /// 批量重排序节点
-pub async fn reorder_nodes(db: &DatabaseConnection, node_ids: Vec<String>) -> AppResult<()> {
+pub async fn reorder_nodes<C: ConnectionTrait>(db: &C, node_ids: Vec<String>) -> AppResult<()> {
     let now = chrono::Utc::now().timestamp_millis();
 
     for (index, node_id) in node_ids.iter().enumerate() {
@@ -271,19 +399,46 @@ pub async fn delete(db: &DatabaseConnection, id: &str) -> AppResult<()> {
 }
 
 /// 批量删除节点（含级联删除子节点和内容）
-pub async fn delete_batch(db: &DatabaseConnection, node_ids: Vec<String>) -> AppResult<()> {
-    for node_id in &node_ids {
-        // 先递归删除所有子节点
-        let descendants = find_descendants(db, node_id).await?;
-        for descendant in descendants {
-            Node::delete_by_id(&descendant.id).exec(db).await?;
-        }
-        // 删除节点本身
-        Node::delete_by_id(node_id).exec(db).await?;
+///
+/// 通过一次递归 CTE 查询收集所有后代，再一次性删除。
+/// 返回实际删除的节点，后代排在祖先之前，供调用方逐个记录删除事件
+pub async fn delete_batch<C: ConnectionTrait>(
+    db: &C,
+    node_ids: Vec<String>,
+) -> AppResult<Vec<node::Model>> {
+    if node_ids.is_empty() {
+        return Ok(Vec::new());
     }
 
-    info!("批量删除 {} 个节点", node_ids.len());
-    Ok(())
+    let roots = Node::find()
+        .filter(node::Column::Id.is_in(node_ids.iter().cloned()))
+        .all(db)
+        .await?;
+    let descendants = Node::find()
+        .from_raw_sql(subtree_statement(
+            db.get_database_backend(),
+            SubtreeAnchor::Nodes(&node_ids),
+            None,
+        ))
+        .all(db)
+        .await?;
+
+    // 后代按深度升序返回，倒序后叶子节点在前；起点互为祖先时只保留一次
+    let mut seen = HashSet::new();
+    let removed: Vec<node::Model> = descendants
+        .into_iter()
+        .rev()
+        .chain(roots)
+        .filter(|n| seen.insert(n.id.clone()))
+        .collect();
+
+    Node::delete_many()
+        .filter(node::Column::Id.is_in(removed.iter().map(|n| n.id.clone())))
+        .exec(db)
+        .await?;
+
+    info!("批量删除 {} 个节点", removed.len());
+    Ok(removed)
 }
 
 // ============================================================================
@@ -423,6 +578,86 @@ mod tests {
         assert_eq!(children.len(), 2);
     }
 
+    #[tokio::test]
+    async fn test_find_descendants_and_tree() {
+        let db = setup_test_db().await;
+        let workspace_id = create_test_workspace(&db).await;
+
+        // root -> a -> a1 -> a1x, root -> b
+        for (id, parent) in [
+            ("root", None),
+            ("a", Some("root")),
+            ("b", Some("root")),
+            ("a1", Some("a")),
+            ("a1x", Some("a1")),
+        ] {
+            create(
+                &db,
+                id.to_string(),
+                workspace_id.clone(),
+                parent.map(str::to_string),
+                id.to_string(),
+                NodeType::Folder,
+                None,
+            )
+            .await
+            .unwrap();
+        }
+
+        let descendants = find_descendants(&db, "root").await.unwrap();
+        let ids: Vec<&str> = descendants.iter().map(|n| n.id.as_str()).collect();
+        assert_eq!(ids, vec!["a", "b", "a1", "a1x"]);
+
+        let tree = find_tree(&db, &workspace_id, None, None).await.unwrap();
+        assert_eq!(tree.len(), 5);
+
+        let shallow = find_tree(&db, &workspace_id, None, Some(2)).await.unwrap();
+        assert_eq!(shallow.len(), 3);
+
+        let subtree = find_tree(&db, &workspace_id, Some("a"), Some(1))
+            .await
+            .unwrap();
+        assert_eq!(subtree.len(), 1);
+        assert_eq!(subtree[0].id, "a1");
+    }
+
+    #[tokio::test]
+    async fn test_delete_batch_removes_descendants() {
+        let db = setup_test_db().await;
+        let workspace_id = create_test_workspace(&db).await;
+
+        for (id, parent) in [
+            ("p", None),
+            ("c", Some("p")),
+            ("g", Some("c")),
+            ("other", None),
+        ] {
+            create(
+                &db,
+                id.to_string(),
+                workspace_id.clone(),
+                parent.map(str::to_string),
+                id.to_string(),
+                NodeType::Folder,
+                None,
+            )
+            .await
+            .unwrap();
+        }
+
+        // 子节点和起点重复传入时只删除一次，后代排在祖先之前
+        let removed = delete_batch(&db, vec!["c".to_string(), "p".to_string()])
+            .await
+            .unwrap();
+        let removed: Vec<&str> = removed.iter().map(|n| n.id.as_str()).collect();
+        assert_eq!(removed, vec!["g", "c", "p"]);
+
+        assert!(find_by_id(&db, "p").await.unwrap().is_none());
+        assert!(find_by_id(&db, "c").await.unwrap().is_none());
+        assert!(find_by_id(&db, "g").await.unwrap().is_none());
+        assert!(find_by_id(&db, "other").await.unwrap().is_some());
+    }
+
     #[tokio::test]
     async fn test_update_node() {
         let db = setup_test_db().await;
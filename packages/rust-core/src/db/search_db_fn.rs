@@ -0,0 +1,274 @@
+//! SavedSearch 数据库函数
+//!
+//! 封装保存的搜索（虚拟文件夹）的 CRUD，以及在工作区内执行查询
+
+use std::collections::HashMap;
+
+use crate::db::tag_db_fn;
+use crate::r#fn::search::{filter_nodes, SearchFilter, SearchQuery};
+use crate::types::content::{content_entity as content, ContentEntity as Content};
+use crate::types::error::{AppError, AppResult};
+use crate::types::node::{node_entity as node, NodeEntity as Node};
+use crate::types::search::{search_entity as search, SavedSearchEntity as SavedSearch};
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, QueryOrder,
+    QuerySelect, Set,
+};
+use tracing::info;
+
+// ============================================================================
+// 查询函数
+// ============================================================================
+
+/// 根据 ID 查询保存的搜索
+pub async fn find_by_id(db: &DatabaseConnection, id: &str) -> AppResult<Option<search::Model>> {
+    let saved = SavedSearch::find_by_id(id).one(db).await?;
+    Ok(saved)
+}
+
+/// 查询工作区的所有保存的搜索（按排序顺序）
+pub async fn find_by_workspace(
+    db: &DatabaseConnection,
+    workspace_id: &str,
+) -> AppResult<Vec<search::Model>> {
+    let searches = SavedSearch::find()
+        .filter(search::Column::WorkspaceId.eq(workspace_id))
+        .order_by_asc(search::Column::SortOrder)
+        .order_by_asc(search::Column::CreatedAt)
+        .all(db)
+        .await?;
+    Ok(searches)
+}
+
+/// 在工作区内执行查询
+///
+/// 标签条件先解析别名；只有包含正文条件时才加载内容。
+/// 结果按更新时间倒序。
+pub async fn run_query(
+    db: &DatabaseConnection,
+    workspace_id: &str,
+    mut query: SearchQuery,
+) -> AppResult<Vec<node::Model>> {
+    for term in &mut query.terms {
+        if let SearchFilter::Tag(tag) = &mut term.filter {
+            *tag = tag_db_fn::resolve_alias(db, workspace_id, tag).await?;
+        }
+    }
+
+    let nodes = Node::find()
+        .filter(node::Column::WorkspaceId.eq(workspace_id))
+        .all(db)
+        .await?;
+
+    let contents: HashMap<String, String> = if query.needs_content() {
+        Content::find()
+            .select_only()
+            .column(content::Column::NodeId)
+            .column(content::Column::Content)
+            .inner_join(Node)
+            .filter(node::Column::WorkspaceId.eq(workspace_id))
+            .into_tuple()
+            .all(db)
+            .await?
+            .into_iter()
+            .collect()
+    } else {
+        HashMap::new()
+    };
+
+    Ok(filter_nodes(&query, nodes, &contents))
+}
+
+// ============================================================================
+// 创建函数
+// ============================================================================
+
+/// 创建保存的搜索（排在工作区现有搜索之后）
+pub async fn create(
+    db: &DatabaseConnection,
+    id: String,
+    workspace_id: String,
+    name: String,
+    query: String,
+) -> AppResult<search::Model> {
+    let last: Option<i32> = SavedSearch::find()
+        .select_only()
+        .column_as(search::Column::SortOrder.max(), "sort_order")
+        .filter(search::Column::WorkspaceId.eq(&workspace_id))
+        .into_tuple()
+        .one(db)
+        .await?
+        .flatten();
+
+    let now = chrono::Utc::now().timestamp_millis();
+    let model = search::ActiveModel {
+        id: Set(id),
+        workspace_id: Set(workspace_id),
+        name: Set(name),
+        query: Set(query),
+        sort_order: Set(last.map_or(0, |n| n + 1)),
+        created_at: Set(now),
+        updated_at: Set(now),
+    };
+
+    let saved = model.insert(db).await?;
+    info!("创建保存的搜索: {} ({})", saved.name, saved.id);
+    Ok(saved)
+}
+
+// ============================================================================
+// 更新函数
+// ============================================================================
+
+/// 更新保存的搜索
+pub async fn update(
+    db: &DatabaseConnection,
+    id: &str,
+    name: Option<String>,
+    query: Option<String>,
+    sort_order: Option<i32>,
+) -> AppResult<search::Model> {
+    let existing = find_by_id(db, id)
+        .await?
+        .ok_or_else(|| AppError::not_found(format!("SavedSearch {}", id)))?;
+
+    let now = chrono::Utc::now().timestamp_millis();
+    let mut model: search::ActiveModel = existing.into();
+    model.updated_at = Set(now);
+
+    if let Some(name) = name {
+        model.name = Set(name);
+    }
+    if let Some(query) = query {
+        model.query = Set(query);
+    }
+    if let Some(sort_order) = sort_order {
+        model.sort_order = Set(sort_order);
+    }
+
+    let saved = model.update(db).await?;
+    info!("更新保存的搜索: {} ({})", saved.name, saved.id);
+    Ok(saved)
+}
+
+// ============================================================================
+// 删除函数
+// ============================================================================
+
+/// 删除保存的搜索
+pub async fn delete(db: &DatabaseConnection, id: &str) -> AppResult<()> {
+    let result = SavedSearch::delete_by_id(id).exec(db).await?;
+    if result.rows_affected == 0 {
+        return Err(AppError::not_found(format!("SavedSearch {}", id)));
+    }
+    info!("删除保存的搜索: {}", id);
+    Ok(())
+}
+
+// ============================================================================
+// 测试
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::test_utils::setup_test_db;
+    use crate::db::{content_db_fn, node_db_fn, workspace_db_fn};
+    use crate::r#fn::search::parse_search_query;
+    use crate::types::node::NodeType;
+
+    async fn setup_workspace(db: &DatabaseConnection) -> String {
+        let workspace_id = uuid::Uuid::new_v4().to_string();
+        workspace_db_fn::create(db, workspace_id.clone(), "测试工作区".to_string(), None)
+            .await
+            .unwrap();
+        workspace_id
+    }
+
+    #[tokio::test]
+    async fn test_saved_search_crud() {
+        let db = setup_test_db().await;
+        let workspace_id = setup_workspace(&db).await;
+
+        let first = create(
+            &db,
+            "s1".to_string(),
+            workspace_id.clone(),
+            "日记".to_string(),
+            "type:diary".to_string(),
+        )
+        .await
+        .unwrap();
+        let second = create(
+            &db,
+            "s2".to_string(),
+            workspace_id.clone(),
+            "草稿".to_string(),
+            "tag:draft".to_string(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(first.sort_order, 0);
+        assert_eq!(second.sort_order, 1);
+
+        let updated = update(&db, "s2", None, Some("-tag:draft".to_string()), Some(-1))
+            .await
+            .unwrap();
+        assert_eq!(updated.query, "-tag:draft");
+        assert_eq!(updated.name, "草稿");
+
+        let all = find_by_workspace(&db, &workspace_id).await.unwrap();
+        let ids: Vec<&str> = all.iter().map(|s| s.id.as_str()).collect();
+        assert_eq!(ids, vec!["s2", "s1"]);
+
+        delete(&db, "s1").await.unwrap();
+        assert!(find_by_id(&db, "s1").await.unwrap().is_none());
+        assert!(matches!(
+            delete(&db, "s1").await,
+            Err(AppError::NotFound(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_run_query() {
+        let db = setup_test_db().await;
+        let workspace_id = setup_workspace(&db).await;
+
+        for (id, node_type, tags) in [
+            ("kyoto", NodeType::Diary, Some(r#"["travel/japan"]"#)),
+            ("draft", NodeType::Diary, Some(r#"["travel", "draft"]"#)),
+            ("notes", NodeType::File, None),
+        ] {
+            node_db_fn::create(
+                &db,
+                id.to_string(),
+                workspace_id.clone(),
+                None,
+                id.to_string(),
+                node_type,
+                tags.map(str::to_string),
+            )
+            .await
+            .unwrap();
+        }
+        content_db_fn::create(
+            &db,
+            "content-notes".to_string(),
+            "notes".to_string(),
+            r#"{"root":{"children":[{"type":"paragraph","children":[{"type":"text","text":"Packing list for Kyoto"}]}]}}"#
+                .to_string(),
+        )
+        .await
+        .unwrap();
+
+        let query = parse_search_query("type:diary tag:travel -tag:draft").unwrap();
+        let result = run_query(&db, &workspace_id, query).await.unwrap();
+        let ids: Vec<&str> = result.iter().map(|n| n.id.as_str()).collect();
+        assert_eq!(ids, vec!["kyoto"]);
+
+        let query = parse_search_query(r#""packing list""#).unwrap();
+        let result = run_query(&db, &workspace_id, query).await.unwrap();
+        let ids: Vec<&str> = result.iter().map(|n| n.id.as_str()).collect();
+        assert_eq!(ids, vec!["notes"]);
+    }
+}
@@ -0,0 +1,634 @@
+//! Sync 数据库函数
+//!
+//! 推送/拉取同步的数据库操作，服务器和客户端共用：
+//! - 为变更日志出现之前就存在的数据补记基线记录
+//! - 读取实体的当前状态、检查依赖、应用对端的变更
+//! - 客户端的同步游标（`sync_remotes`）和实体版本（`sync_versions`）
+
+use std::collections::HashSet;
+
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, ConnectionTrait, DatabaseConnection, EntityTrait,
+    IntoActiveModel, QueryFilter, QueryOrder, QuerySelect, Set, TransactionTrait,
+};
+use tracing::info;
+
+use crate::db::{change_log_db_fn, content_db_fn, link_db_fn, task_db_fn};
+use crate::r#fn::sync::sync_version_id;
+use crate::types::change_log::{
+    ChangeEntity, ChangeLogColumn, ChangeLogEntity, ChangeLogModel, ChangeOperation,
+};
+use crate::types::content::{ContentActiveModel, ContentColumn, ContentEntity};
+use crate::types::error::{AppError, AppResult};
+use crate::types::event::ChangeEvent;
+use crate::types::node::{NodeColumn, NodeEntity};
+use crate::types::sync::{
+    sync_remote_entity as sync_remote, sync_version_entity as sync_version, SyncChange,
+    SyncEntityData, SyncRemoteEntity, SyncVersionEntity,
+};
+use crate::types::tag::{TagActiveModel, TagColumn, TagEntity};
+use crate::types::workspace::WorkspaceEntity;
+
+// ============================================================================
+// 基线
+// ============================================================================
+
+/// 为没有变更记录的实体补记一条 upsert 记录
+///
+/// 变更日志出现之前创建的数据没有记录，补记后才能被拉取，
+/// 也才能在推送时检测到冲突。返回补记的条数。
+pub async fn record_baseline(db: &DatabaseConnection) -> AppResult<u64> {
+    let logged: HashSet<(ChangeEntity, String)> = ChangeLogEntity::find()
+        .select_only()
+        .column(ChangeLogColumn::EntityType)
+        .column(ChangeLogColumn::EntityId)
+        .into_tuple()
+        .all(db)
+        .await?
+        .into_iter()
+        .collect();
+    let missing =
+        |entity_type: ChangeEntity, id: &str| !logged.contains(&(entity_type, id.to_string()));
+
+    let mut events = Vec::new();
+
+    let workspace_ids: Vec<String> = WorkspaceEntity::find()
+        .select_only()
+        .column(crate::types::workspace::WorkspaceColumn::Id)
+        .into_tuple()
+        .all(db)
+        .await?;
+    for workspace_id in workspace_ids {
+        if missing(ChangeEntity::Workspace, &workspace_id) {
+            events.push(ChangeEvent::WorkspaceUpdated { workspace_id });
+        }
+    }
+
+    let nodes: Vec<(String, String)> = NodeEntity::find()
+        .select_only()
+        .column(NodeColumn::Id)
+        .column(NodeColumn::WorkspaceId)
+        .into_tuple()
+        .all(db)
+        .await?;
+    if !nodes.is_empty() {
+        let contents: Vec<(String, i32)> = ContentEntity::find()
+            .select_only()
+            .column(ContentColumn::NodeId)
+            .column(ContentColumn::Version)
+            .into_tuple()
+            .all(db)
+            .await?;
+        let workspace_of: std::collections::HashMap<&str, &str> = nodes
+            .iter()
+            .map(|(id, ws)| (id.as_str(), ws.as_str()))
+            .collect();
+
+        for (node_id, workspace_id) in &nodes {
+            if missing(ChangeEntity::Node, node_id) {
+                events.push(ChangeEvent::NodeUpdated {
+                    workspace_id: workspace_id.clone(),
+                    node_id: node_id.clone(),
+                });
+            }
+        }
+        for (node_id, version) in contents {
+            if let Some(workspace_id) = workspace_of.get(node_id.as_str()) {
+                if missing(ChangeEntity::Content, &node_id) {
+                    events.push(ChangeEvent::ContentSaved {
+                        workspace_id: workspace_id.to_string(),
+                        node_id,
+                        version,
+                    });
+                }
+            }
+        }
+    }
+
+    let tag_workspaces: Vec<String> = TagEntity::find()
+        .select_only()
+        .column(TagColumn::WorkspaceId)
+        .distinct()
+        .into_tuple()
+        .all(db)
+        .await?;
+    for workspace_id in tag_workspaces {
+        if missing(ChangeEntity::Tags, &workspace_id) {
+            events.push(ChangeEvent::TagsChanged { workspace_id });
+        }
+    }
+
+    if events.is_empty() {
+        return Ok(0);
+    }
+
+    let txn = db.begin().await?;
+    for event in &events {
+        change_log_db_fn::append(&txn, event).await?;
+    }
+    txn.commit().await?;
+
+    info!("补记同步基线: {} 条", events.len());
+    Ok(events.len() as u64)
+}
+
+// ============================================================================
+// 实体状态
+// ============================================================================
+
+/// 读取实体的当前数据（不存在时返回 None）
+pub async fn load_entity<C: ConnectionTrait>(
+    db: &C,
+    entity_type: ChangeEntity,
+    entity_id: &str,
+) -> AppResult<Option<SyncEntityData>> {
+    let data = match entity_type {
+        ChangeEntity::Workspace => WorkspaceEntity::find_by_id(entity_id)
+            .one(db)
+            .await?
+            .map(SyncEntityData::Workspace),
+        ChangeEntity::Node => NodeEntity::find_by_id(entity_id)
+            .one(db)
+            .await?
+            .map(SyncEntityData::Node),
+        ChangeEntity::Content => ContentEntity::find()
+            .filter(ContentColumn::NodeId.eq(entity_id))
+            .one(db)
+            .await?
+            .map(SyncEntityData::Content),
+        ChangeEntity::Tags => {
+            if WorkspaceEntity::find_by_id(entity_id)
+                .one(db)
+                .await?
+                .is_none()
+            {
+                return Ok(None);
+            }
+            let tags = TagEntity::find()
+                .filter(TagColumn::WorkspaceId.eq(entity_id))
+                .order_by_asc(TagColumn::Name)
+                .all(db)
+                .await?;
+            Some(SyncEntityData::Tags(tags))
+        }
+        ChangeEntity::Database => None,
+    };
+    Ok(data)
+}
+
+/// 构造实体当前状态的同步变更（实体不存在时为删除）
+pub async fn current_change<C: ConnectionTrait>(
+    db: &C,
+    entity_type: ChangeEntity,
+    entity_id: &str,
+    workspace_id: Option<String>,
+    version: i64,
+) -> AppResult<SyncChange> {
+    let data = load_entity(db, entity_type, entity_id).await?;
+    Ok(SyncChange {
+        entity_type,
+        entity_id: entity_id.to_string(),
+        workspace_id,
+        operation: if data.is_some() {
+            ChangeOperation::Upsert
+        } else {
+            ChangeOperation::Delete
+        },
+        version,
+        data,
+    })
+}
+
+/// 把变更日志记录转换为同步变更，版本为记录的序号
+pub async fn change_from_entry(
+    db: &DatabaseConnection,
+    entry: &ChangeLogModel,
+) -> AppResult<SyncChange> {
+    current_change(
+        db,
+        entry.entity_type,
+        &entry.entity_id,
+        entry.workspace_id.clone(),
+        entry.seq,
+    )
+    .await
+}
+
+/// 检查变更依赖的实体是否存在，返回缺失原因
+pub async fn missing_dependency<C: ConnectionTrait>(
+    db: &C,
+    change: &SyncChange,
+) -> AppResult<Option<String>> {
+    if change.operation != ChangeOperation::Upsert {
+        return Ok(None);
+    }
+
+    let missing = match &change.data {
+        Some(SyncEntityData::Node(node)) => {
+            if WorkspaceEntity::find_by_id(&node.workspace_id)
+                .one(db)
+                .await?
+                .is_none()
+            {
+                Some(format!("工作区 {} 不存在", node.workspace_id))
+            } else {
+                match &node.parent_id {
+                    Some(parent_id)
+                        if NodeEntity::find_by_id(parent_id).one(db).await?.is_none() =>
+                    {
+                        Some(format!("父节点 {} 不存在", parent_id))
+                    }
+                    _ => None,
+                }
+            }
+        }
+        Some(SyncEntityData::Content(content)) => NodeEntity::find_by_id(&content.node_id)
+            .one(db)
+            .await?
+            .is_none()
+            .then(|| format!("节点 {} 不存在", content.node_id)),
+        Some(SyncEntityData::Tags(_)) => WorkspaceEntity::find_by_id(&change.entity_id)
+            .one(db)
+            .await?
+            .is_none()
+            .then(|| format!("工作区 {} 不存在", change.entity_id)),
+        _ => None,
+    };
+    Ok(missing)
+}
+
+/// 变更会写入的全部工作区 ID（服务器上的现状和变更数据中的都算）
+///
+/// 推送方给出的 `workspace_id` 不可信，检查权限时按实体和数据本身确定。
+pub async fn affected_workspaces<C: ConnectionTrait>(
+    db: &C,
+    change: &SyncChange,
+) -> AppResult<HashSet<String>> {
+    let mut ids = HashSet::new();
+    let mut node_ids = Vec::new();
+    match change.entity_type {
+        ChangeEntity::Workspace | ChangeEntity::Tags => {
+            ids.insert(change.entity_id.clone());
+        }
+        ChangeEntity::Node | ChangeEntity::Content => node_ids.push(change.entity_id.clone()),
+        ChangeEntity::Database => {}
+    }
+    match &change.data {
+        Some(SyncEntityData::Workspace(workspace)) => {
+            ids.insert(workspace.id.clone());
+        }
+        Some(SyncEntityData::Node(node)) => {
+            ids.insert(node.workspace_id.clone());
+            node_ids.push(node.id.clone());
+        }
+        Some(SyncEntityData::Content(content)) => node_ids.push(content.node_id.clone()),
+        Some(SyncEntityData::Tags(tags)) => {
+            ids.extend(tags.iter().map(|t| t.workspace_id.clone()));
+        }
+        None => {}
+    }
+    for node_id in node_ids {
+        if let Some(node) = NodeEntity::find_by_id(&node_id).one(db).await? {
+            ids.insert(node.workspace_id);
+        }
+    }
+    Ok(ids)
+}
+
+/// 应用对端的变更
+///
+/// 不存在则插入，存在则覆盖；删除依赖外键级联删除子节点和内容。
+/// 返回需要记入本地变更日志的事件（删除不存在的实体时为 None），由调用方在同一事务中追加。
+pub async fn apply_change<C: ConnectionTrait>(
+    db: &C,
+    change: &SyncChange,
+) -> AppResult<Option<ChangeEvent>> {
+    let event = match (change.operation, &change.data) {
+        (ChangeOperation::Upsert, Some(SyncEntityData::Workspace(workspace))) => {
+            let exists = WorkspaceEntity::find_by_id(&workspace.id)
+                .one(db)
+                .await?
+                .is_some();
+            let active = workspace.clone().into_active_model().reset_all();
+            if exists {
+                active.update(db).await?;
+                ChangeEvent::WorkspaceUpdated {
+                    workspace_id: workspace.id.clone(),
+                }
+            } else {
+                active.insert(db).await?;
+                ChangeEvent::WorkspaceCreated {
+                    workspace_id: workspace.id.clone(),
+                }
+            }
+        }
+        (ChangeOperation::Upsert, Some(SyncEntityData::Node(node))) => {
+            let exists = NodeEntity::find_by_id(&node.id).one(db).await?.is_some();
+            let active = node.clone().into_active_model().reset_all();
+            if exists {
+                active.update(db).await?;
+                ChangeEvent::NodeUpdated {
+                    workspace_id: node.workspace_id.clone(),
+                    node_id: node.id.clone(),
+                }
+            } else {
+                active.insert(db).await?;
+                ChangeEvent::NodeCreated {
+                    workspace_id: node.workspace_id.clone(),
+                    node_id: node.id.clone(),
+                    parent_id: node.parent_id.clone(),
+                }
+            }
+        }
+        (ChangeOperation::Upsert, Some(SyncEntityData::Content(content))) => {
+            // 两端内容记录的 ID 可能不同，按节点 ID 覆盖
+            let existing = ContentEntity::find()
+                .filter(ContentColumn::NodeId.eq(&content.node_id))
+                .one(db)
+                .await?;
+            let saved = match existing {
+                Some(existing) => {
+                    ContentActiveModel {
+                        id: Set(existing.id),
+                        node_id: Set(content.node_id.clone()),
+                        content: Set(content.content.clone()),
+                        version: Set(content.version),
+                        created_at: Set(content.created_at),
+                        updated_at: Set(content.updated_at),
+                    }
+                    .update(db)
+                    .await?
+                }
+                None => {
+                    content
+                        .clone()
+                        .into_active_model()
+                        .reset_all()
+                        .insert(db)
+                        .await?
+                }
+            };
+            link_db_fn::sync_links(db, &saved.node_id, &saved.content).await?;
+            content_db_fn::record_revision(db, &saved).await?;
+            task_db_fn::sync_tasks(db, &saved).await?;
+
+            let node = NodeEntity::find_by_id(&saved.node_id)
+                .one(db)
+                .await?
+                .ok_or_else(|| AppError::not_found(format!("节点 {}", saved.node_id)))?;
+            ChangeEvent::ContentSaved {
+                workspace_id: node.workspace_id,
+                node_id: saved.node_id,
+                version: saved.version,
+            }
+        }
+        (ChangeOperation::Upsert, Some(SyncEntityData::Tags(tags))) => {
+            replace_tags(db, &change.entity_id, tags).await?;
+            ChangeEvent::TagsChanged {
+                workspace_id: change.entity_id.clone(),
+            }
+        }
+        (ChangeOperation::Delete, _) => match change.entity_type {
+            ChangeEntity::Workspace => {
+                let result = WorkspaceEntity::delete_by_id(&change.entity_id)
+                    .exec(db)
+                    .await?;
+                if result.rows_affected == 0 {
+                    return Ok(None);
+                }
+                ChangeEvent::WorkspaceDeleted {
+                    workspace_id: change.entity_id.clone(),
+                }
+            }
+            ChangeEntity::Node => {
+                let Some(node) = NodeEntity::find_by_id(&change.entity_id).one(db).await? else {
+                    return Ok(None);
+                };
+                NodeEntity::delete_by_id(&node.id).exec(db).await?;
+                ChangeEvent::NodeDeleted {
+                    workspace_id: node.workspace_id,
+                    node_id: node.id,
+                }
+            }
+            ChangeEntity::Content => {
+                // 内容只随节点删除，没有对应的事件
+                ContentEntity::delete_many()
+                    .filter(ContentColumn::NodeId.eq(&change.entity_id))
+                    .exec(db)
+                    .await?;
+                return Ok(None);
+            }
+            ChangeEntity::Tags => {
+                replace_tags(db, &change.entity_id, &[]).await?;
+                ChangeEvent::TagsChanged {
+                    workspace_id: change.entity_id.clone(),
+                }
+            }
+            ChangeEntity::Database => {
+                return Err(AppError::validation("不支持同步清除数据"));
+            }
+        },
+        _ => {
+            return Err(AppError::validation(format!(
+                "变更数据与实体类型不符: {:?} {}",
+                change.entity_type, change.entity_id
+            )))
+        }
+    };
+    Ok(Some(event))
+}
+
+/// 用给定的标签列表替换工作区的全部标签
+async fn replace_tags<C: ConnectionTrait>(
+    db: &C,
+    workspace_id: &str,
+    tags: &[crate::types::tag::TagModel],
+) -> AppResult<()> {
+    TagEntity::delete_many()
+        .filter(TagColumn::WorkspaceId.eq(workspace_id))
+        .exec(db)
+        .await?;
+    for tag in tags {
+        TagActiveModel::from(tag.clone())
+            .reset_all()
+            .insert(db)
+            .await?;
+    }
+    Ok(())
+}
+
+// ============================================================================
+// 同步游标和实体版本（客户端）
+// ============================================================================
+
+/// 查询服务器的同步游标
+pub async fn find_remote(
+    db: &DatabaseConnection,
+    remote_id: &str,
+) -> AppResult<Option<sync_remote::Model>> {
+    Ok(SyncRemoteEntity::find_by_id(remote_id).one(db).await?)
+}
+
+/// 保存服务器的同步游标
+pub async fn save_remote(
+    db: &DatabaseConnection,
+    remote_id: &str,
+    pull_seq: i64,
+    push_seq: i64,
+    last_synced_at: Option<i64>,
+) -> AppResult<sync_remote::Model> {
+    let now = chrono::Utc::now().timestamp_millis();
+    let existing = find_remote(db, remote_id).await?;
+    let model = sync_remote::ActiveModel {
+        id: Set(remote_id.to_string()),
+        server_url: Set(remote_id.to_string()),
+        pull_seq: Set(pull_seq),
+        push_seq: Set(push_seq),
+        last_synced_at: Set(last_synced_at.or(existing.as_ref().and_then(|r| r.last_synced_at))),
+        updated_at: Set(now),
+    };
+    let saved = match existing {
+        Some(_) => model.update(db).await?,
+        None => model.insert(db).await?,
+    };
+    Ok(saved)
+}
+
+/// 查询实体的同步版本
+pub async fn find_version<C: ConnectionTrait>(
+    db: &C,
+    remote_id: &str,
+    entity_type: ChangeEntity,
+    entity_id: &str,
+) -> AppResult<Option<sync_version::Model>> {
+    let id = sync_version_id(remote_id, entity_type, entity_id);
+    Ok(SyncVersionEntity::find_by_id(id).one(db).await?)
+}
+
+/// 保存实体的同步版本
+pub async fn save_version<C: ConnectionTrait>(
+    db: &C,
+    remote_id: &str,
+    entity_type: ChangeEntity,
+    entity_id: &str,
+    version: i64,
+    local_seq: i64,
+) -> AppResult<()> {
+    let id = sync_version_id(remote_id, entity_type, entity_id);
+    let exists = SyncVersionEntity::find_by_id(id.clone())
+        .one(db)
+        .await?
+        .is_some();
+    let model = sync_version::ActiveModel {
+        id: Set(id),
+        remote_id: Set(remote_id.to_string()),
+        entity_type: Set(entity_type),
+        entity_id: Set(entity_id.to_string()),
+        version: Set(version),
+        local_seq: Set(local_seq),
+        updated_at: Set(chrono::Utc::now().timestamp_millis()),
+    };
+    if exists {
+        model.update(db).await?;
+    } else {
+        model.insert(db).await?;
+    }
+    Ok(())
+}
+
+// ============================================================================
+// 测试
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::test_utils::setup_test_db;
+    use crate::db::{node_db_fn, workspace_db_fn};
+    use crate::types::node::NodeType;
+
+    #[tokio::test]
+    async fn test_record_baseline_and_apply() {
+        let source = setup_test_db().await;
+        let target = setup_test_db().await;
+
+        let workspace =
+            workspace_db_fn::create(&source, "ws".to_string(), "工作区".to_string(), None)
+                .await
+                .unwrap();
+        node_db_fn::create(
+            &source,
+            "n1".to_string(),
+            workspace.id.clone(),
+            None,
+            "节点".to_string(),
+            NodeType::File,
+            None,
+        )
+        .await
+        .unwrap();
+
+        // 直接写库的数据没有变更记录，补记后每个实体一条
+        assert_eq!(record_baseline(&source).await.unwrap(), 2);
+        assert_eq!(record_baseline(&source).await.unwrap(), 0);
+
+        let entries = change_log_db_fn::find_since(&source, 0, 100).await.unwrap();
+        let mut changes = Vec::new();
+        for entry in &entries {
+            changes.push(change_from_entry(&source, entry).await.unwrap());
+        }
+        let node_change = changes
+            .iter()
+            .find(|c| c.entity_type == ChangeEntity::Node)
+            .unwrap();
+
+        // 工作区不存在时节点缺少依赖
+        assert!(missing_dependency(&target, node_change)
+            .await
+            .unwrap()
+            .is_some());
+
+        for change in crate::r#fn::sync::order_changes(changes.clone()) {
+            assert!(apply_change(&target, &change).await.unwrap().is_some());
+        }
+        let copied = load_entity(&target, ChangeEntity::Node, "n1")
+            .await
+            .unwrap();
+        assert_eq!(copied, node_change.data);
+
+        // 删除不存在的实体不产生事件
+        let deleted = SyncChange {
+            operation: ChangeOperation::Delete,
+            data: None,
+            ..node_change.clone()
+        };
+        assert!(apply_change(&target, &deleted).await.unwrap().is_some());
+        assert!(apply_change(&target, &deleted).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_save_remote_and_version() {
+        let db = setup_test_db().await;
+
+        save_remote(&db, "https://a", 3, 0, None).await.unwrap();
+        let remote = save_remote(&db, "https://a", 5, 7, Some(1)).await.unwrap();
+        assert_eq!((remote.pull_seq, remote.push_seq), (5, 7));
+        assert_eq!(remote.last_synced_at, Some(1));
+
+        save_version(&db, "https://a", ChangeEntity::Node, "n1", 4, 2)
+            .await
+            .unwrap();
+        save_version(&db, "https://a", ChangeEntity::Node, "n1", 9, 6)
+            .await
+            .unwrap();
+        let version = find_version(&db, "https://a", ChangeEntity::Node, "n1")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!((version.version, version.local_seq), (9, 6));
+        assert!(find_version(&db, "https://b", ChangeEntity::Node, "n1")
+            .await
+            .unwrap()
+            .is_none());
+    }
+}
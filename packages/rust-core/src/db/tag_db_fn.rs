@@ -2,19 +2,26 @@
 //!
 //! 标签相关的 CRUD 操作
 
+use crate::db::content_db_fn;
+use crate::r#fn::node::serialize_tags;
+use crate::r#fn::tag::{
+    alias_renames, build_tag_tree, merge_tag_metadata, normalize_tag_path, rename_tag_path,
+    rewrite_content_tags, rewrite_tag_list, set_tag_aliases, tag_matches, TagRename,
+};
 use crate::types::{
     TagActiveModel, TagColumn, TagEntity, TagGraphData, TagGraphEdge, TagGraphNode, TagModel,
+    TagResponse, TagRewriteContent, TagRewriteReport, TagTreeNode, UpdateTagMetadataRequest,
 };
 use sea_orm::*;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
 // ============================================================================
 // 查询操作
 // ============================================================================
 
 /// 获取工作区所有标签
-pub async fn find_by_workspace(
-    db: &DatabaseConnection,
+pub async fn find_by_workspace<C: ConnectionTrait>(
+    db: &C,
     workspace_id: &str,
 ) -> Result<Vec<TagModel>, DbErr> {
     TagEntity::find()
@@ -25,7 +32,7 @@ pub async fn find_by_workspace(
 }
 
 /// 按 ID 获取标签
-pub async fn find_by_id(db: &DatabaseConnection, id: &str) -> Result<Option<TagModel>, DbErr> {
+pub async fn find_by_id<C: ConnectionTrait>(db: &C, id: &str) -> Result<Option<TagModel>, DbErr> {
     TagEntity::find_by_id(id).one(db).await
 }
 
@@ -56,7 +63,7 @@ pub async fn find_top_tags(
         .await
 }
 
-/// 搜索标签（模糊匹配）
+/// 搜索标签（模糊匹配名称或别名）
 pub async fn search_tags(
     db: &DatabaseConnection,
     workspace_id: &str,
@@ -64,15 +71,20 @@ pub async fn search_tags(
 ) -> Result<Vec<TagModel>, DbErr> {
     TagEntity::find()
         .filter(TagColumn::WorkspaceId.eq(workspace_id))
-        .filter(TagColumn::Name.contains(query))
+        .filter(
+            Condition::any()
+                .add(TagColumn::Name.contains(query))
+                .add(TagColumn::Aliases.contains(query)),
+        )
         .order_by_desc(TagColumn::Count)
         .all(db)
         .await
 }
 
-/// 获取包含指定标签的节点 ID 列表
+/// 获取包含指定标签（或其子标签）的节点 ID 列表
 ///
-/// 通过查询 nodes 表的 tags JSON 字段来查找
+/// 通过查询 nodes 表的 tags JSON 字段来查找，
+/// `project` 同时匹配 `project/alpha` 等层级子标签。标签别名会先解析为标签名。
 pub async fn get_nodes_by_tag(
     db: &DatabaseConnection,
     workspace_id: &str,
@@ -81,6 +93,9 @@ pub async fn get_nodes_by_tag(
     use crate::types::node::node_entity as node;
     use crate::types::NodeEntity as Node;
 
+    let tag_name = resolve_alias(db, workspace_id, tag_name).await?;
+    let tag_name = tag_name.as_str();
+
     // 查询所有节点，然后在内存中过滤
     // 因为 SQLite 的 JSON 查询支持有限
     let nodes = Node::find()
@@ -95,7 +110,7 @@ pub async fn get_nodes_by_tag(
             if let Some(ref tags_json) = node.tags {
                 // 解析 JSON 数组
                 if let Ok(tags) = serde_json::from_str::<Vec<String>>(tags_json) {
-                    return tags.contains(&tag_name.to_string());
+                    return tags.iter().any(|tag| tag_matches(tag, tag_name));
                 }
             }
             false
@@ -106,6 +121,31 @@ pub async fn get_nodes_by_tag(
     Ok(node_ids)
 }
 
+/// 获取层级标签树
+///
+/// 标签名按 `/` 分隔为层级，返回每个层级的直接计数与汇总计数
+pub async fn get_tag_tree(
+    db: &DatabaseConnection,
+    workspace_id: &str,
+) -> Result<Vec<TagTreeNode>, DbErr> {
+    use crate::types::node::node_entity as node;
+    use crate::types::NodeEntity as Node;
+
+    let tags = find_by_workspace(db, workspace_id).await?;
+
+    let nodes = Node::find()
+        .filter(node::Column::WorkspaceId.eq(workspace_id))
+        .filter(node::Column::Tags.is_not_null())
+        .all(db)
+        .await?;
+    let node_tags: Vec<Vec<String>> = nodes
+        .iter()
+        .filter_map(|node| node.tags.as_deref())
+        .filter_map(|tags_json| serde_json::from_str(tags_json).ok())
+        .collect();
+
+    Ok(build_tag_tree(tags, &node_tags))
+}
 
 /// 获取标签图形数据
 ///
@@ -121,11 +161,7 @@ pub async fn get_tag_graph_data(
     let tags = find_by_workspace(db, workspace_id).await?;
     let nodes: Vec<TagGraphNode> = tags
         .into_iter()
-        .map(|tag| TagGraphNode {
-            id: tag.id,
-            name: tag.name,
-            count: tag.count,
-        })
+        .map(|tag| TagGraphNode::from(TagResponse::from(tag)))
         .collect();
 
     // 计算标签之间的共现关系（边）
@@ -178,13 +214,30 @@ pub async fn get_tag_graph_data(
     Ok(TagGraphData { nodes, edges })
 }
 
+/// 将标签别名解析为标签名
+///
+/// 名称不是任何标签的别名时原样返回（经过规范化）
+pub async fn resolve_alias<C: ConnectionTrait>(
+    db: &C,
+    workspace_id: &str,
+    name: &str,
+) -> Result<String, DbErr> {
+    let name = normalize_tag_path(name);
+    let tags = TagEntity::find()
+        .filter(TagColumn::WorkspaceId.eq(workspace_id))
+        .filter(TagColumn::Aliases.is_not_null())
+        .all(db)
+        .await?;
+    Ok(rename_tag_path(&name, &alias_renames(&tags)).unwrap_or(name))
+}
+
 // ============================================================================
 // 写入操作
 // ============================================================================
 
 /// 创建标签
-pub async fn create(
-    db: &DatabaseConnection,
+pub async fn create<C: ConnectionTrait>(
+    db: &C,
     id: String,
     name: String,
     workspace_id: String,
@@ -198,14 +251,15 @@ pub async fn create(
         count: Set(1),
         last_used: Set(now),
         created_at: Set(now),
+        ..Default::default()
     };
 
     model.insert(db).await
 }
 
 /// 更新标签
-pub async fn update(
-    db: &DatabaseConnection,
+pub async fn update<C: ConnectionTrait>(
+    db: &C,
     id: &str,
     name: Option<String>,
     count: Option<i32>,
@@ -232,7 +286,7 @@ pub async fn update(
 }
 
 /// 增加标签使用计数
-pub async fn increment_count(db: &DatabaseConnection, id: &str) -> Result<TagModel, DbErr> {
+pub async fn increment_count<C: ConnectionTrait>(db: &C, id: &str) -> Result<TagModel, DbErr> {
     let tag = TagEntity::find_by_id(id)
         .one(db)
         .await?
@@ -247,7 +301,7 @@ pub async fn increment_count(db: &DatabaseConnection, id: &str) -> Result<TagMod
 }
 
 /// 减少标签使用计数
-pub async fn decrement_count(db: &DatabaseConnection, id: &str) -> Result<TagModel, DbErr> {
+pub async fn decrement_count<C: ConnectionTrait>(db: &C, id: &str) -> Result<TagModel, DbErr> {
     let tag = TagEntity::find_by_id(id)
         .one(db)
         .await?
@@ -261,14 +315,14 @@ pub async fn decrement_count(db: &DatabaseConnection, id: &str) -> Result<TagMod
 }
 
 /// 删除标签
-pub async fn delete(db: &DatabaseConnection, id: &str) -> Result<(), DbErr> {
+pub async fn delete<C: ConnectionTrait>(db: &C, id: &str) -> Result<(), DbErr> {
     TagEntity::delete_by_id(id).exec(db).await?;
     Ok(())
 }
 
 /// 删除工作区所有标签
-pub async fn delete_by_workspace(
-    db: &DatabaseConnection,
+pub async fn delete_by_workspace<C: ConnectionTrait>(
+    db: &C,
     workspace_id: &str,
 ) -> Result<u64, DbErr> {
     let result = TagEntity::delete_many()
@@ -279,11 +333,15 @@ pub async fn delete_by_workspace(
 }
 
 /// 获取或创建标签
-pub async fn get_or_create(
-    db: &DatabaseConnection,
+///
+/// 名称是已有标签的别名时使用该标签
+pub async fn get_or_create<C: ConnectionTrait>(
+    db: &C,
     workspace_id: &str,
     name: &str,
 ) -> Result<TagModel, DbErr> {
+    let name = resolve_alias(db, workspace_id, name).await?;
+    let name = name.as_str();
     let id = format!("{}:{}", workspace_id, name);
 
     match find_by_id(db, &id).await? {
@@ -298,6 +356,273 @@ pub async fn get_or_create(
     }
 }
 
+/// 更新标签元数据（颜色、图标、描述、别名）
+///
+/// 字段为 None 时保持不变，颜色、图标、描述为空字符串时清除。
+/// 别名是否与其他标签冲突由调用方检查。
+pub async fn update_metadata<C: ConnectionTrait>(
+    db: &C,
+    tag: TagModel,
+    request: UpdateTagMetadataRequest,
+) -> Result<TagModel, DbErr> {
+    let clear_empty = |value: String| {
+        let value = value.trim().to_string();
+        (!value.is_empty()).then_some(value)
+    };
+
+    let mut tag = tag;
+    if let Some(color) = request.color {
+        tag.color = clear_empty(color);
+    }
+    if let Some(icon) = request.icon {
+        tag.icon = clear_empty(icon);
+    }
+    if let Some(description) = request.description {
+        tag.description = clear_empty(description);
+    }
+    if let Some(aliases) = request.aliases {
+        set_tag_aliases(&mut tag, aliases);
+    }
+
+    TagActiveModel::from(tag).reset_all().update(db).await
+}
+
+/// 将节点的标签设置为从内容中提取的标签，并更新标签表
+///
+/// 标签别名会先解析为标签名。标签没有变化时不写入。新增或移除的标签按工作区内的节点重新计数，
+/// 不存在的标签记录会自动创建，计数归零的记录保留。
+/// 返回节点标签是否发生了变化。
+pub async fn sync_node_tags<C: ConnectionTrait>(
+    db: &C,
+    node_id: &str,
+    tags: &[String],
+) -> Result<bool, DbErr> {
+    use crate::types::node::node_entity as node;
+    use crate::types::NodeEntity as Node;
+
+    let Some(existing) = Node::find_by_id(node_id).one(db).await? else {
+        return Ok(false);
+    };
+    let workspace_id = existing.workspace_id.clone();
+
+    let workspace_tags = TagEntity::find()
+        .filter(TagColumn::WorkspaceId.eq(workspace_id.as_str()))
+        .filter(TagColumn::Aliases.is_not_null())
+        .all(db)
+        .await?;
+    let resolved = rewrite_tag_list(tags, &alias_renames(&workspace_tags));
+    let tags = resolved.as_deref().unwrap_or(tags);
+
+    let old_tags: Vec<String> = existing
+        .tags
+        .as_deref()
+        .and_then(|t| serde_json::from_str(t).ok())
+        .unwrap_or_default();
+    if old_tags == tags {
+        return Ok(false);
+    }
+
+    let now = chrono::Utc::now().timestamp_millis();
+    let mut model: node::ActiveModel = existing.into();
+    model.tags = Set(serialize_tags(tags));
+    model.updated_at = Set(now);
+    model.update(db).await?;
+
+    let changed: Vec<&String> = tags
+        .iter()
+        .filter(|t| !old_tags.contains(t))
+        .chain(old_tags.iter().filter(|t| !tags.contains(t)))
+        .collect();
+    if changed.is_empty() {
+        // 只有顺序变化
+        return Ok(true);
+    }
+
+    let nodes = Node::find()
+        .filter(node::Column::WorkspaceId.eq(workspace_id.as_str()))
+        .filter(node::Column::Tags.is_not_null())
+        .all(db)
+        .await?;
+    let mut tag_counts: HashMap<String, i32> = HashMap::new();
+    for tags in nodes
+        .iter()
+        .filter_map(|n| n.tags.as_deref())
+        .filter_map(|t| serde_json::from_str::<Vec<String>>(t).ok())
+    {
+        for tag in tags {
+            *tag_counts.entry(tag).or_insert(0) += 1;
+        }
+    }
+
+    for name in changed {
+        let id = format!("{}:{}", workspace_id, name);
+        let count = tag_counts.get(name).copied().unwrap_or(0);
+        let added = tags.contains(name);
+        match TagEntity::find_by_id(id.as_str()).one(db).await? {
+            Some(tag) => {
+                let mut model: TagActiveModel = tag.into();
+                model.count = Set(count);
+                if added {
+                    model.last_used = Set(now);
+                }
+                model.update(db).await?;
+            }
+            None if added => {
+                TagActiveModel {
+                    id: Set(id),
+                    name: Set(name.clone()),
+                    workspace_id: Set(workspace_id.clone()),
+                    count: Set(count),
+                    last_used: Set(now),
+                    created_at: Set(now),
+                    ..Default::default()
+                }
+                .insert(db)
+                .await?;
+            }
+            None => {}
+        }
+    }
+
+    Ok(true)
+}
+
+// ============================================================================
+// 改名操作
+// ============================================================================
+
+/// 按改名规则重命名/合并工作区内的标签
+///
+/// 1. 改写所有节点的 tags 字段
+/// 2. 改写所有内容中的 `#[tag]`（内容版本号递增）
+/// 3. 标签记录换成新 ID（`workspace_id:新名称`），与已有记录合并
+/// 4. 根据节点标签重新计算改名后标签的计数
+///
+/// 应在事务中调用，保证上述步骤同时生效。
+pub async fn rewrite_tags<C: ConnectionTrait>(
+    db: &C,
+    workspace_id: &str,
+    renames: &[TagRename],
+) -> Result<TagRewriteReport, DbErr> {
+    use crate::types::content::{content_entity as content, ContentEntity as Content};
+    use crate::types::node::node_entity as node;
+    use crate::types::NodeEntity as Node;
+
+    let now = chrono::Utc::now().timestamp_millis();
+
+    // 1. 节点 tags 字段
+    let nodes = Node::find()
+        .filter(node::Column::WorkspaceId.eq(workspace_id))
+        .filter(node::Column::Tags.is_not_null())
+        .all(db)
+        .await?;
+
+    let mut tag_counts: HashMap<String, i32> = HashMap::new();
+    let mut updated_node_ids = Vec::new();
+    for existing in nodes {
+        let Some(tags) = existing
+            .tags
+            .as_deref()
+            .and_then(|t| serde_json::from_str::<Vec<String>>(t).ok())
+        else {
+            continue;
+        };
+
+        let tags = match rewrite_tag_list(&tags, renames) {
+            Some(new_tags) => {
+                updated_node_ids.push(existing.id.clone());
+                let mut model: node::ActiveModel = existing.into();
+                model.tags = Set(serialize_tags(&new_tags));
+                model.updated_at = Set(now);
+                model.update(db).await?;
+                new_tags
+            }
+            None => tags,
+        };
+        for tag in tags {
+            *tag_counts.entry(tag).or_insert(0) += 1;
+        }
+    }
+
+    // 2. 内容中的 #[tag]
+    let contents = Content::find()
+        .inner_join(Node)
+        .filter(node::Column::WorkspaceId.eq(workspace_id))
+        .all(db)
+        .await?;
+
+    let mut updated_contents = Vec::new();
+    for existing in contents {
+        let Some((new_content, _)) = rewrite_content_tags(&existing.content, renames) else {
+            continue;
+        };
+        let new_version = existing.version + 1;
+        let mut model: content::ActiveModel = existing.into();
+        model.content = Set(new_content);
+        model.version = Set(new_version);
+        model.updated_at = Set(now);
+        let updated = model.update(db).await?;
+        content_db_fn::record_revision(db, &updated)
+            .await
+            .map_err(|e| DbErr::Custom(e.to_string()))?;
+        updated_contents.push(TagRewriteContent {
+            node_id: updated.node_id,
+            version: updated.version,
+        });
+    }
+
+    // 3. 标签记录换 ID（同名的多条记录合并元数据）
+    let tags = TagEntity::find()
+        .filter(TagColumn::WorkspaceId.eq(workspace_id))
+        .all(db)
+        .await?;
+
+    let mut renamed: BTreeMap<String, TagModel> = BTreeMap::new();
+    for tag in tags {
+        let Some(new_name) = rename_tag_path(&tag.name, renames) else {
+            continue;
+        };
+        TagEntity::delete_by_id(tag.id.as_str()).exec(db).await?;
+        match renamed.get_mut(&new_name) {
+            Some(merged) => merge_tag_metadata(merged, &tag),
+            None => {
+                renamed.insert(new_name, tag);
+            }
+        }
+    }
+
+    // 4. 写入新记录并重新计数
+    let mut results = Vec::with_capacity(renamed.len());
+    for (name, source) in renamed {
+        let id = format!("{}:{}", workspace_id, name);
+        let count = tag_counts.get(&name).copied().unwrap_or(0);
+        let model = match TagEntity::find_by_id(id.as_str()).one(db).await? {
+            Some(mut target) => {
+                merge_tag_metadata(&mut target, &source);
+                target.count = count;
+                TagActiveModel::from(target).reset_all().update(db).await?
+            }
+            None => {
+                let mut tag = source;
+                tag.id = id;
+                tag.name = name;
+                tag.count = count;
+                let aliases = tag.alias_list();
+                set_tag_aliases(&mut tag, aliases);
+                TagActiveModel::from(tag).reset_all().insert(db).await?
+            }
+        };
+        results.push(TagResponse::from(model));
+    }
+
+    Ok(TagRewriteReport {
+        tags: results,
+        nodes_updated: updated_node_ids.len() as u64,
+        contents_updated: updated_contents.len() as u64,
+        updated_node_ids,
+        updated_contents,
+    })
+}
 
 // ============================================================================
 // 同步操作
@@ -307,7 +632,7 @@ pub async fn get_or_create(
 ///
 /// 从 nodes 表的 tags 字段同步到 tags 表
 /// 只更新已存在的标签的计数
-pub async fn sync_tag_cache(db: &DatabaseConnection, workspace_id: &str) -> Result<(), DbErr> {
+pub async fn sync_tag_cache<C: ConnectionTrait>(db: &C, workspace_id: &str) -> Result<(), DbErr> {
     use crate::types::node::node_entity as node;
     use crate::types::NodeEntity as Node;
 
@@ -348,11 +673,19 @@ pub async fn sync_tag_cache(db: &DatabaseConnection, workspace_id: &str) -> Resu
 
 /// 重建标签缓存
 ///
-/// 删除所有标签并从 nodes 表重新构建
-pub async fn rebuild_tag_cache(db: &DatabaseConnection, workspace_id: &str) -> Result<(), DbErr> {
+/// 删除所有标签并从 nodes 表重新构建。
+/// 已有标签的颜色、图标、描述和别名会保留，带元数据但未被使用的标签以计数 0 保留。
+pub async fn rebuild_tag_cache<C: ConnectionTrait>(db: &C, workspace_id: &str) -> Result<(), DbErr> {
     use crate::types::node::node_entity as node;
     use crate::types::NodeEntity as Node;
 
+    // 保留已有标签的元数据
+    let mut previous: HashMap<String, TagModel> = find_by_workspace(db, workspace_id)
+        .await?
+        .into_iter()
+        .map(|tag| (tag.name.clone(), tag))
+        .collect();
+
     // 删除工作区所有标签
     delete_by_workspace(db, workspace_id).await?;
 
@@ -380,25 +713,42 @@ pub async fn rebuild_tag_cache(db: &DatabaseConnection, workspace_id: &str) -> R
     let now = chrono::Utc::now().timestamp_millis();
     for (tag_name, count) in tag_counts {
         let tag_id = format!("{}:{}", workspace_id, tag_name);
+        let old = previous.remove(&tag_name);
         let model = TagActiveModel {
             id: Set(tag_id),
             name: Set(tag_name),
             workspace_id: Set(workspace_id.to_string()),
             count: Set(count),
             last_used: Set(now),
-            created_at: Set(now),
+            created_at: Set(old.as_ref().map_or(now, |t| t.created_at)),
+            color: Set(old.as_ref().and_then(|t| t.color.clone())),
+            icon: Set(old.as_ref().and_then(|t| t.icon.clone())),
+            description: Set(old.as_ref().and_then(|t| t.description.clone())),
+            aliases: Set(old.and_then(|t| t.aliases)),
         };
         model.insert(db).await?;
     }
 
+    // 未被使用但带元数据的标签
+    for mut tag in previous.into_values() {
+        let has_metadata = tag.color.is_some()
+            || tag.icon.is_some()
+            || tag.description.is_some()
+            || tag.aliases.is_some();
+        if has_metadata {
+            tag.count = 0;
+            TagActiveModel::from(tag).reset_all().insert(db).await?;
+        }
+    }
+
     Ok(())
 }
 
 /// 重新计算标签计数
 ///
 /// 只更新计数，不创建或删除标签
-pub async fn recalculate_tag_counts(
-    db: &DatabaseConnection,
+pub async fn recalculate_tag_counts<C: ConnectionTrait>(
+    db: &C,
     workspace_id: &str,
 ) -> Result<(), DbErr> {
     use crate::types::node::node_entity as node;
@@ -0,0 +1,294 @@
+//! Task 数据库函数
+//!
+//! 维护从待办（todo）节点内容中提取的任务索引（`tasks` 表）
+//!
+//! - 保存内容时整体重建该节点的任务
+//! - 节点删除时，其任务通过外键级联删除
+
+use std::collections::HashMap;
+
+use crate::db::{content_db_fn, link_db_fn};
+use crate::r#fn::node::{extract_tags, serialize_tags};
+use crate::r#fn::task::{extract_tasks, toggle_task_in_content};
+use crate::types::content::{content_entity as content, ContentEntity as Content};
+use crate::types::error::{AppError, AppResult};
+use crate::types::node::{node_entity as node, NodeEntity as Node, NodeType};
+use crate::types::task::{task_entity as task, TaskEntity as Task};
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, ConnectionTrait, DatabaseConnection, EntityTrait, QueryFilter,
+    QueryOrder, Set,
+};
+use tracing::info;
+
+// ============================================================================
+// 查询函数
+// ============================================================================
+
+/// 根据 ID 查询任务
+pub async fn find_by_id<C: ConnectionTrait>(db: &C, id: &str) -> AppResult<Option<task::Model>> {
+    let task = Task::find_by_id(id).one(db).await?;
+    Ok(task)
+}
+
+/// 查询工作区的所有任务（按节点和位置排序）
+pub async fn find_by_workspace(
+    db: &DatabaseConnection,
+    workspace_id: &str,
+) -> AppResult<Vec<task::Model>> {
+    let tasks = Task::find()
+        .filter(task::Column::WorkspaceId.eq(workspace_id))
+        .order_by_asc(task::Column::NodeId)
+        .order_by_asc(task::Column::Position)
+        .all(db)
+        .await?;
+    Ok(tasks)
+}
+
+/// 查询工作区内包含任务的节点的标签（节点 ID -> 标签列表）
+pub async fn node_tags(
+    db: &DatabaseConnection,
+    workspace_id: &str,
+) -> AppResult<HashMap<String, Vec<String>>> {
+    let nodes = Node::find()
+        .filter(node::Column::WorkspaceId.eq(workspace_id))
+        .filter(node::Column::NodeType.eq(NodeType::Todo))
+        .all(db)
+        .await?;
+    Ok(nodes
+        .into_iter()
+        .map(|n| {
+            let tags = extract_tags(&n).unwrap_or_default();
+            (n.id, tags)
+        })
+        .collect())
+}
+
+// ============================================================================
+// 同步函数
+// ============================================================================
+
+/// 根据内容重建节点的任务索引
+///
+/// 只索引待办节点；其他类型的节点或内容不是 JSON 时清空该节点的任务。
+/// 接受任意连接类型，可在事务中调用。返回索引的任务数量。
+pub async fn sync_tasks<C: ConnectionTrait>(db: &C, content: &content::Model) -> AppResult<usize> {
+    Task::delete_many()
+        .filter(task::Column::NodeId.eq(&content.node_id))
+        .exec(db)
+        .await?;
+
+    let Some(node) = Node::find_by_id(&content.node_id).one(db).await? else {
+        return Ok(0);
+    };
+    if node.node_type != NodeType::Todo {
+        return Ok(0);
+    }
+    let Some(extracted) = extract_tasks(&content.content) else {
+        return Ok(0);
+    };
+
+    let now = chrono::Utc::now().timestamp_millis();
+    let models: Vec<task::ActiveModel> = extracted
+        .into_iter()
+        .map(|t| task::ActiveModel {
+            id: Set(format!("{}:{}", node.id, t.position)),
+            node_id: Set(node.id.clone()),
+            workspace_id: Set(node.workspace_id.clone()),
+            position: Set(t.position),
+            text: Set(t.text),
+            checked: Set(t.checked),
+            due_date: Set(t.due_date),
+            priority: Set(t.priority),
+            tags: Set(serialize_tags(&t.tags)),
+            content_version: Set(content.version),
+            updated_at: Set(now),
+        })
+        .collect();
+
+    let count = models.len();
+    if count > 0 {
+        Task::insert_many(models).exec(db).await?;
+    }
+    Ok(count)
+}
+
+/// 从内容表重建工作区的任务索引
+///
+/// 用于恢复旧备份（无任务索引）或索引与内容不一致时。
+/// 返回重建后的任务总数。
+pub async fn rebuild_for_workspace(db: &DatabaseConnection, workspace_id: &str) -> AppResult<u64> {
+    let contents = Content::find()
+        .inner_join(Node)
+        .filter(node::Column::WorkspaceId.eq(workspace_id))
+        .filter(node::Column::NodeType.eq(NodeType::Todo))
+        .all(db)
+        .await?;
+
+    let mut total = 0u64;
+    for item in &contents {
+        total += sync_tasks(db, item).await? as u64;
+    }
+
+    info!("重建任务索引: workspace={}, tasks={}", workspace_id, total);
+    Ok(total)
+}
+
+// ============================================================================
+// 更新函数
+// ============================================================================
+
+/// 切换任务的勾选状态
+///
+/// 改写内容中对应的勾选列表项并递增内容版本号，然后重建该节点的任务索引。
+/// `expected_version` 用于乐观锁。应在事务中调用。返回更新后的任务。
+pub async fn toggle<C: ConnectionTrait>(
+    db: &C,
+    task_id: &str,
+    checked: Option<bool>,
+    expected_version: Option<i32>,
+) -> AppResult<task::Model> {
+    let existing = find_by_id(db, task_id)
+        .await?
+        .ok_or_else(|| AppError::not_found(format!("Task {}", task_id)))?;
+    let current = Content::find()
+        .filter(content::Column::NodeId.eq(&existing.node_id))
+        .one(db)
+        .await?
+        .ok_or_else(|| AppError::not_found(format!("Content for node {}", existing.node_id)))?;
+
+    // 乐观锁检查
+    if let Some(expected) = expected_version {
+        if current.version != expected {
+            return Err(AppError::ValidationError(format!(
+                "版本冲突: 期望版本 {}, 实际版本 {}",
+                expected, current.version
+            )));
+        }
+    }
+
+    let (text, checked) = toggle_task_in_content(&current.content, existing.position, checked)
+        .ok_or_else(|| AppError::not_found(format!("Task {}", task_id)))?;
+
+    let now = chrono::Utc::now().timestamp_millis();
+    let new_version = current.version + 1;
+    let mut model: content::ActiveModel = current.into();
+    model.content = Set(text);
+    model.version = Set(new_version);
+    model.updated_at = Set(now);
+    let saved = model.update(db).await?;
+
+    link_db_fn::sync_links(db, &saved.node_id, &saved.content).await?;
+    content_db_fn::record_revision(db, &saved).await?;
+    sync_tasks(db, &saved).await?;
+
+    info!(
+        "切换任务: {} -> {}, version={}",
+        task_id,
+        if checked { "done" } else { "open" },
+        new_version
+    );
+    find_by_id(db, task_id)
+        .await?
+        .ok_or_else(|| AppError::not_found(format!("Task {}", task_id)))
+}
+
+// ============================================================================
+// 测试
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::test_utils::setup_test_db;
+    use crate::db::{content_db_fn, node_db_fn, workspace_db_fn};
+
+    const TODO_CONTENT: &str = r#"{"root":{"children":[{"type":"list","listType":"check","children":[
+        {"type":"listitem","checked":false,"children":[{"type":"text","text":"写周报 !high due:2026-01-09"}]},
+        {"type":"listitem","checked":true,"children":[{"type":"text","text":"订机票"}]}
+    ]}]}}"#;
+
+    async fn setup_todo(db: &DatabaseConnection, node_type: NodeType) -> (String, content::Model) {
+        let workspace_id = uuid::Uuid::new_v4().to_string();
+        workspace_db_fn::create(db, workspace_id.clone(), "测试工作区".to_string(), None)
+            .await
+            .unwrap();
+        node_db_fn::create(
+            db,
+            "todo".to_string(),
+            workspace_id.clone(),
+            None,
+            "待办".to_string(),
+            node_type,
+            None,
+        )
+        .await
+        .unwrap();
+        let content = content_db_fn::create(
+            db,
+            "content-todo".to_string(),
+            "todo".to_string(),
+            TODO_CONTENT.to_string(),
+        )
+        .await
+        .unwrap();
+        (workspace_id, content)
+    }
+
+    #[tokio::test]
+    async fn test_sync_tasks() {
+        let db = setup_test_db().await;
+        let (workspace_id, content) = setup_todo(&db, NodeType::Todo).await;
+
+        assert_eq!(sync_tasks(&db, &content).await.unwrap(), 2);
+        let tasks = find_by_workspace(&db, &workspace_id).await.unwrap();
+        assert_eq!(tasks.len(), 2);
+        assert_eq!(tasks[0].id, "todo:0");
+        assert_eq!(tasks[0].due_date.as_deref(), Some("2026-01-09"));
+        assert!(tasks[1].checked);
+
+        // 重复同步不会产生重复任务
+        assert_eq!(rebuild_for_workspace(&db, &workspace_id).await.unwrap(), 2);
+
+        node_db_fn::delete(&db, "todo").await.unwrap();
+        assert!(find_by_workspace(&db, &workspace_id)
+            .await
+            .unwrap()
+            .is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_sync_tasks_ignores_other_node_types() {
+        let db = setup_test_db().await;
+        let (workspace_id, content) = setup_todo(&db, NodeType::File).await;
+
+        assert_eq!(sync_tasks(&db, &content).await.unwrap(), 0);
+        assert!(find_by_workspace(&db, &workspace_id)
+            .await
+            .unwrap()
+            .is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_toggle() {
+        let db = setup_test_db().await;
+        let (_, content) = setup_todo(&db, NodeType::Todo).await;
+        sync_tasks(&db, &content).await.unwrap();
+
+        let task = toggle(&db, "todo:0", None, Some(1)).await.unwrap();
+        assert!(task.checked);
+        assert_eq!(task.content_version, 2);
+
+        let saved = content_db_fn::find_by_node_id(&db, "todo")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(saved.version, 2);
+        assert!(extract_tasks(&saved.content).unwrap()[0].checked);
+
+        let result = toggle(&db, "todo:0", Some(false), Some(1)).await;
+        assert!(matches!(result, Err(AppError::ValidationError(_))));
+
+        let result = toggle(&db, "todo:9", None, None).await;
+        assert!(matches!(result, Err(AppError::NotFound(_))));
+    }
+}
@@ -18,6 +18,7 @@ pub async fn setup_test_db() -> DatabaseConnection {
         db_filename: format!("test-{}.db", uuid::Uuid::new_v4()),
         backup_dirname: "backups".to_string(),
         enable_encryption: false,
+        ..Default::default()
     };
 
     // 保持 temp_dir 不被 drop（通过 leak）
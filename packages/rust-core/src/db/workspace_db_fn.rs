@@ -4,7 +4,7 @@
 
 use crate::types::error::{AppError, AppResult};
 use crate::types::workspace::{workspace_entity as workspace, WorkspaceEntity as Workspace};
-use sea_orm::{ActiveModelTrait, DatabaseConnection, EntityTrait, Set};
+use sea_orm::{ActiveModelTrait, ConnectionTrait, DatabaseConnection, EntityTrait, Set};
 use tracing::info;
 
 // ============================================================================
@@ -12,7 +12,10 @@ use tracing::info;
 // ============================================================================
 
 /// 根据 ID 查询工作区
-pub async fn find_by_id(db: &DatabaseConnection, id: &str) -> AppResult<Option<workspace::Model>> {
+pub async fn find_by_id<C: ConnectionTrait>(
+    db: &C,
+    id: &str,
+) -> AppResult<Option<workspace::Model>> {
     let workspace = Workspace::find_by_id(id).one(db).await?;
     Ok(workspace)
 }
@@ -28,8 +31,8 @@ pub async fn find_all(db: &DatabaseConnection) -> AppResult<Vec<workspace::Model
 // ============================================================================
 
 /// 创建工作区
-pub async fn create(
-    db: &DatabaseConnection,
+pub async fn create<C: ConnectionTrait>(
+    db: &C,
     id: String,
     name: String,
     description: Option<String>,
@@ -54,8 +57,8 @@ pub async fn create(
 // ============================================================================
 
 /// 更新工作区
-pub async fn update(
-    db: &DatabaseConnection,
+pub async fn update<C: ConnectionTrait>(
+    db: &C,
     id: &str,
     name: Option<String>,
     description: Option<Option<String>>,
@@ -86,7 +89,7 @@ pub async fn update(
 // ============================================================================
 
 /// 删除工作区
-pub async fn delete(db: &DatabaseConnection, id: &str) -> AppResult<()> {
+pub async fn delete<C: ConnectionTrait>(db: &C, id: &str) -> AppResult<()> {
     let result = Workspace::delete_by_id(id).exec(db).await?;
 
     if result.rows_affected == 0 {
@@ -0,0 +1,236 @@
+//! WorkspaceMember 数据库函数
+//!
+//! 维护工作区成员及角色（`workspace_members` 表）
+
+use std::collections::HashSet;
+
+use crate::types::error::AppResult;
+use crate::types::member::{
+    WorkspaceMemberActiveModel, WorkspaceMemberColumn, WorkspaceMemberEntity, WorkspaceMemberModel,
+    WorkspaceRole,
+};
+use crate::types::user::{UserEntity, UserModel};
+use crate::types::workspace::{WorkspaceColumn, WorkspaceEntity};
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, ConnectionTrait, EntityTrait, Iterable, PaginatorTrait,
+    QueryFilter, QueryOrder, QuerySelect, Set,
+};
+use tracing::info;
+
+// ============================================================================
+// 查询函数
+// ============================================================================
+
+/// 查询用户在工作区中的角色（不是成员时为 None）
+pub async fn find_role<C: ConnectionTrait>(
+    db: &C,
+    workspace_id: &str,
+    user_id: &str,
+) -> AppResult<Option<WorkspaceRole>> {
+    let member = WorkspaceMemberEntity::find_by_id((workspace_id.to_string(), user_id.to_string()))
+        .one(db)
+        .await?;
+    Ok(member.map(|m| m.role))
+}
+
+/// 查询工作区的全部成员及对应用户（按加入时间升序）
+pub async fn find_by_workspace<C: ConnectionTrait>(
+    db: &C,
+    workspace_id: &str,
+) -> AppResult<Vec<(WorkspaceMemberModel, UserModel)>> {
+    let members = WorkspaceMemberEntity::find()
+        .filter(WorkspaceMemberColumn::WorkspaceId.eq(workspace_id))
+        .order_by_asc(WorkspaceMemberColumn::CreatedAt)
+        .find_also_related(UserEntity)
+        .all(db)
+        .await?;
+    Ok(members
+        .into_iter()
+        .filter_map(|(member, user)| user.map(|user| (member, user)))
+        .collect())
+}
+
+/// 查询用户具有 `role` 权限的全部工作区 ID
+pub async fn find_workspace_ids<C: ConnectionTrait>(
+    db: &C,
+    user_id: &str,
+    role: WorkspaceRole,
+) -> AppResult<HashSet<String>> {
+    let allowed: Vec<WorkspaceRole> = WorkspaceRole::iter().filter(|r| r.allows(role)).collect();
+    let ids: Vec<String> = WorkspaceMemberEntity::find()
+        .select_only()
+        .column(WorkspaceMemberColumn::WorkspaceId)
+        .filter(WorkspaceMemberColumn::UserId.eq(user_id))
+        .filter(WorkspaceMemberColumn::Role.is_in(allowed))
+        .into_tuple()
+        .all(db)
+        .await?;
+    Ok(ids.into_iter().collect())
+}
+
+/// 统计工作区的所有者人数
+pub async fn count_owners<C: ConnectionTrait>(db: &C, workspace_id: &str) -> AppResult<u64> {
+    let count = WorkspaceMemberEntity::find()
+        .filter(WorkspaceMemberColumn::WorkspaceId.eq(workspace_id))
+        .filter(WorkspaceMemberColumn::Role.eq(WorkspaceRole::Owner))
+        .count(db)
+        .await?;
+    Ok(count)
+}
+
+// ============================================================================
+// 写入函数
+// ============================================================================
+
+/// 设置成员角色（不是成员时加入工作区）
+pub async fn set_role<C: ConnectionTrait>(
+    db: &C,
+    workspace_id: &str,
+    user_id: &str,
+    role: WorkspaceRole,
+) -> AppResult<WorkspaceMemberModel> {
+    let existing =
+        WorkspaceMemberEntity::find_by_id((workspace_id.to_string(), user_id.to_string()))
+            .one(db)
+            .await?;
+    let member = match existing {
+        Some(member) => {
+            let mut model: WorkspaceMemberActiveModel = member.into();
+            model.role = Set(role);
+            model.update(db).await?
+        }
+        None => {
+            WorkspaceMemberActiveModel {
+                workspace_id: Set(workspace_id.to_string()),
+                user_id: Set(user_id.to_string()),
+                role: Set(role),
+                created_at: Set(chrono::Utc::now().timestamp_millis()),
+            }
+            .insert(db)
+            .await?
+        }
+    };
+    info!(
+        "设置工作区成员: workspace_id={}, user_id={}, role={:?}",
+        workspace_id, user_id, role
+    );
+    Ok(member)
+}
+
+/// 移除成员，返回是否存在该成员
+pub async fn remove<C: ConnectionTrait>(
+    db: &C,
+    workspace_id: &str,
+    user_id: &str,
+) -> AppResult<bool> {
+    let deleted =
+        WorkspaceMemberEntity::delete_by_id((workspace_id.to_string(), user_id.to_string()))
+            .exec(db)
+            .await?
+            .rows_affected;
+    Ok(deleted > 0)
+}
+
+/// 把没有成员的工作区设为用户所有，返回工作区个数
+///
+/// 用于启用认证前创建的工作区，以及同步推送到服务器的新工作区。
+pub async fn claim_unowned<C: ConnectionTrait>(db: &C, user_id: &str) -> AppResult<u64> {
+    let owned: HashSet<String> = WorkspaceMemberEntity::find()
+        .select_only()
+        .column(WorkspaceMemberColumn::WorkspaceId)
+        .distinct()
+        .into_tuple::<String>()
+        .all(db)
+        .await?
+        .into_iter()
+        .collect();
+    let workspace_ids: Vec<String> = WorkspaceEntity::find()
+        .select_only()
+        .column(WorkspaceColumn::Id)
+        .into_tuple()
+        .all(db)
+        .await?;
+
+    let mut claimed = 0;
+    for workspace_id in workspace_ids.iter().filter(|id| !owned.contains(*id)) {
+        set_role(db, workspace_id, user_id, WorkspaceRole::Owner).await?;
+        claimed += 1;
+    }
+    Ok(claimed)
+}
+
+// ============================================================================
+// 测试
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::test_utils::setup_test_db;
+    use crate::db::{auth_db_fn, user_db_fn, workspace_db_fn};
+
+    #[tokio::test]
+    async fn test_member_roles() {
+        let db = setup_test_db().await;
+        let alice = auth_db_fn::ensure_user(&db, "alice", "password-1")
+            .await
+            .unwrap();
+        let bob = auth_db_fn::ensure_user(&db, "bob", "password-1")
+            .await
+            .unwrap();
+        for id in ["ws1", "ws2"] {
+            workspace_db_fn::create(&db, id.to_string(), id.to_string(), None)
+                .await
+                .unwrap();
+        }
+
+        // 没有成员的工作区归第一个认领的用户
+        assert_eq!(claim_unowned(&db, &alice.id).await.unwrap(), 2);
+        assert_eq!(claim_unowned(&db, &bob.id).await.unwrap(), 0);
+        assert_eq!(
+            find_role(&db, "ws1", &alice.id).await.unwrap(),
+            Some(WorkspaceRole::Owner)
+        );
+
+        set_role(&db, "ws1", &bob.id, WorkspaceRole::Viewer)
+            .await
+            .unwrap();
+        set_role(&db, "ws1", &bob.id, WorkspaceRole::Editor)
+            .await
+            .unwrap();
+        let members = find_by_workspace(&db, "ws1").await.unwrap();
+        let roles: Vec<_> = members
+            .iter()
+            .map(|(m, u)| (u.username.as_str(), m.role))
+            .collect();
+        assert_eq!(
+            roles,
+            vec![
+                ("alice", WorkspaceRole::Owner),
+                ("bob", WorkspaceRole::Editor)
+            ]
+        );
+        assert_eq!(count_owners(&db, "ws1").await.unwrap(), 1);
+        assert_eq!(
+            find_workspace_ids(&db, &bob.id, WorkspaceRole::Editor)
+                .await
+                .unwrap(),
+            HashSet::from(["ws1".to_string()])
+        );
+        assert!(find_workspace_ids(&db, &bob.id, WorkspaceRole::Owner)
+            .await
+            .unwrap()
+            .is_empty());
+
+        assert!(remove(&db, "ws1", &bob.id).await.unwrap());
+        assert!(!remove(&db, "ws1", &bob.id).await.unwrap());
+
+        // 删除工作区或用户时级联删除成员
+        workspace_db_fn::delete(&db, "ws2").await.unwrap();
+        user_db_fn::delete(&db, &alice.id).await.unwrap();
+        assert!(find_workspace_ids(&db, &alice.id, WorkspaceRole::Viewer)
+            .await
+            .unwrap()
+            .is_empty());
+    }
+}
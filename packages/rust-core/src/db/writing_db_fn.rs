@@ -0,0 +1,429 @@
+//! Writing 数据库函数
+//!
+//! 维护写作统计缓存（`writing_stats` 表）、写作目标（`writing_goals` 表）
+//! 和每日字数变化（`writing_progress` 表）
+//!
+//! - 统计按内容版本号缓存，查询时只重新计算版本变化的内容
+//! - 保存内容时按本地日期累计字数变化
+//! - 节点删除时，其统计和节点目标通过外键级联删除；字数变化记录保留
+
+use std::collections::HashMap;
+
+use chrono::NaiveDate;
+
+use crate::r#fn::writing::{content_writing_stats, is_writing_node};
+use crate::types::content::{content_entity as content, ContentEntity as Content};
+use crate::types::error::{AppError, AppResult};
+use crate::types::node::{node_entity as node, NodeEntity as Node};
+use crate::types::writing::{
+    writing_goal_entity as writing_goal, writing_progress_entity as writing_progress,
+    writing_stats_entity as writing_stats, SetWritingGoalRequest, WritingGoalEntity,
+    WritingProgressEntity, WritingStats, WritingStatsEntity,
+};
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, ConnectionTrait, DatabaseConnection, EntityTrait, QueryFilter,
+    QueryOrder, QuerySelect, Set,
+};
+use tracing::info;
+
+// ============================================================================
+// 同步函数
+// ============================================================================
+
+/// 重新计算并缓存内容的写作统计
+///
+/// 接受任意连接类型，可在事务中调用。
+pub async fn refresh_stats<C: ConnectionTrait>(
+    db: &C,
+    workspace_id: &str,
+    content: &content::Model,
+) -> AppResult<WritingStats> {
+    let stats = content_writing_stats(&content.content);
+
+    WritingStatsEntity::delete_by_id(content.node_id.clone())
+        .exec(db)
+        .await?;
+    writing_stats::ActiveModel {
+        node_id: Set(content.node_id.clone()),
+        workspace_id: Set(workspace_id.to_string()),
+        content_version: Set(content.version),
+        cjk_characters: Set(stats.cjk_characters as i64),
+        latin_words: Set(stats.latin_words as i64),
+        characters: Set(stats.characters as i64),
+        paragraphs: Set(stats.paragraphs as i64),
+        updated_at: Set(chrono::Utc::now().timestamp_millis()),
+    }
+    .insert(db)
+    .await?;
+
+    Ok(stats)
+}
+
+/// 记录一次保存的字数变化
+///
+/// 比较保存前后的字数（保存前的字数优先取缓存），累计到节点当天的记录，同时更新统计缓存。
+/// 首次保存时保存前的字数为 0。只记录计入写作统计的节点类型。
+/// 接受任意连接类型，可在事务中调用。返回净增字数。
+pub async fn record_progress<C: ConnectionTrait>(
+    db: &C,
+    previous: Option<&content::Model>,
+    saved: &content::Model,
+    date: NaiveDate,
+) -> AppResult<i64> {
+    let Some(node) = Node::find_by_id(&saved.node_id).one(db).await? else {
+        return Ok(0);
+    };
+    if !is_writing_node(node.node_type) {
+        return Ok(0);
+    }
+
+    let before = match previous {
+        Some(prev) => match WritingStatsEntity::find_by_id(prev.node_id.clone())
+            .one(db)
+            .await?
+        {
+            Some(cached) if cached.content_version == prev.version => {
+                WritingStats::from(&cached).words
+            }
+            _ => content_writing_stats(&prev.content).words,
+        },
+        None => 0,
+    };
+    let after = refresh_stats(db, &node.workspace_id, saved).await?.words;
+    let delta = after as i64 - before as i64;
+    if delta == 0 {
+        return Ok(0);
+    }
+
+    let (added, removed) = if delta > 0 { (delta, 0) } else { (0, -delta) };
+    let date = date.format("%Y-%m-%d").to_string();
+    let id = format!("{}:{}", node.id, date);
+    let now = chrono::Utc::now().timestamp_millis();
+    match WritingProgressEntity::find_by_id(id.clone())
+        .one(db)
+        .await?
+    {
+        Some(existing) => {
+            let words_added = existing.words_added + added;
+            let words_removed = existing.words_removed + removed;
+            let mut model: writing_progress::ActiveModel = existing.into();
+            model.words_added = Set(words_added);
+            model.words_removed = Set(words_removed);
+            model.updated_at = Set(now);
+            model.update(db).await?;
+        }
+        None => {
+            writing_progress::ActiveModel {
+                id: Set(id),
+                workspace_id: Set(node.workspace_id.clone()),
+                node_id: Set(node.id.clone()),
+                date: Set(date),
+                words_added: Set(added),
+                words_removed: Set(removed),
+                updated_at: Set(now),
+            }
+            .insert(db)
+            .await?;
+        }
+    }
+
+    Ok(delta)
+}
+
+// ============================================================================
+// 查询函数
+// ============================================================================
+
+/// 查询工作区的所有字数变化记录（按日期排序）
+pub async fn find_progress(
+    db: &DatabaseConnection,
+    workspace_id: &str,
+) -> AppResult<Vec<writing_progress::Model>> {
+    let rows = WritingProgressEntity::find()
+        .filter(writing_progress::Column::WorkspaceId.eq(workspace_id))
+        .order_by_asc(writing_progress::Column::Date)
+        .all(db)
+        .await?;
+    Ok(rows)
+}
+
+/// 根据 ID 查询写作目标
+pub async fn find_goal_by_id(
+    db: &DatabaseConnection,
+    id: &str,
+) -> AppResult<Option<writing_goal::Model>> {
+    let goal = WritingGoalEntity::find_by_id(id).one(db).await?;
+    Ok(goal)
+}
+
+/// 查询工作区的所有写作目标（按创建时间排序）
+pub async fn find_goals(
+    db: &DatabaseConnection,
+    workspace_id: &str,
+) -> AppResult<Vec<writing_goal::Model>> {
+    let goals = WritingGoalEntity::find()
+        .filter(writing_goal::Column::WorkspaceId.eq(workspace_id))
+        .order_by_asc(writing_goal::Column::CreatedAt)
+        .all(db)
+        .await?;
+    Ok(goals)
+}
+
+/// 查询工作区的所有节点及各文档的写作统计
+///
+/// 缓存的版本号与内容版本号不一致（或没有缓存）时重新计算。
+/// 返回（节点列表，文档节点 ID -> 统计值）。
+pub async fn workspace_stats(
+    db: &DatabaseConnection,
+    workspace_id: &str,
+) -> AppResult<(Vec<node::Model>, HashMap<String, WritingStats>)> {
+    let nodes = Node::find()
+        .filter(node::Column::WorkspaceId.eq(workspace_id))
+        .all(db)
+        .await?;
+
+    let versions: Vec<(String, i32)> = Content::find()
+        .select_only()
+        .column(content::Column::NodeId)
+        .column(content::Column::Version)
+        .inner_join(Node)
+        .filter(node::Column::WorkspaceId.eq(workspace_id))
+        .into_tuple()
+        .all(db)
+        .await?;
+    let cached: HashMap<String, writing_stats::Model> = WritingStatsEntity::find()
+        .filter(writing_stats::Column::WorkspaceId.eq(workspace_id))
+        .all(db)
+        .await?
+        .into_iter()
+        .map(|m| (m.node_id.clone(), m))
+        .collect();
+
+    let writing_nodes: HashMap<&str, &node::Model> = nodes
+        .iter()
+        .filter(|n| is_writing_node(n.node_type))
+        .map(|n| (n.id.as_str(), n))
+        .collect();
+    let mut stats = HashMap::new();
+    let mut stale = Vec::new();
+    for (node_id, version) in versions {
+        if !writing_nodes.contains_key(node_id.as_str()) {
+            continue;
+        }
+        match cached.get(&node_id) {
+            Some(model) if model.content_version == version => {
+                stats.insert(node_id, WritingStats::from(model));
+            }
+            _ => stale.push(node_id),
+        }
+    }
+
+    if !stale.is_empty() {
+        let contents = Content::find()
+            .filter(content::Column::NodeId.is_in(stale))
+            .all(db)
+            .await?;
+        for item in &contents {
+            let computed = refresh_stats(db, workspace_id, item).await?;
+            stats.insert(item.node_id.clone(), computed);
+        }
+        info!(
+            "更新写作统计缓存: workspace={}, nodes={}",
+            workspace_id,
+            contents.len()
+        );
+    }
+
+    Ok((nodes, stats))
+}
+
+// ============================================================================
+// 目标函数
+// ============================================================================
+
+/// 设置写作目标
+///
+/// 同一范围（工作区或节点）的同类目标只保留一个，已存在时更新目标字数和截止日期。
+pub async fn set_goal(
+    db: &DatabaseConnection,
+    workspace_id: &str,
+    request: SetWritingGoalRequest,
+) -> AppResult<writing_goal::Model> {
+    let scope = match request.node_id.as_deref() {
+        Some(node_id) => writing_goal::Column::NodeId.eq(node_id),
+        None => writing_goal::Column::NodeId.is_null(),
+    };
+    let existing = WritingGoalEntity::find()
+        .filter(writing_goal::Column::WorkspaceId.eq(workspace_id))
+        .filter(writing_goal::Column::Kind.eq(request.kind))
+        .filter(scope)
+        .one(db)
+        .await?;
+
+    let now = chrono::Utc::now().timestamp_millis();
+    let goal = match existing {
+        Some(existing) => {
+            let mut model: writing_goal::ActiveModel = existing.into();
+            model.target = Set(request.target);
+            model.deadline = Set(request.deadline);
+            model.updated_at = Set(now);
+            model.update(db).await?
+        }
+        None => {
+            writing_goal::ActiveModel {
+                id: Set(uuid::Uuid::new_v4().to_string()),
+                workspace_id: Set(workspace_id.to_string()),
+                node_id: Set(request.node_id),
+                kind: Set(request.kind),
+                target: Set(request.target),
+                deadline: Set(request.deadline),
+                created_at: Set(now),
+                updated_at: Set(now),
+            }
+            .insert(db)
+            .await?
+        }
+    };
+
+    info!(
+        "设置写作目标: workspace={}, id={}, target={}",
+        workspace_id, goal.id, goal.target
+    );
+    Ok(goal)
+}
+
+/// 删除写作目标
+pub async fn delete_goal(db: &DatabaseConnection, id: &str) -> AppResult<()> {
+    let result = WritingGoalEntity::delete_by_id(id).exec(db).await?;
+    if result.rows_affected == 0 {
+        return Err(AppError::not_found(format!("WritingGoal {}", id)));
+    }
+    info!("删除写作目标: id={}", id);
+    Ok(())
+}
+
+// ============================================================================
+// 测试
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::test_utils::setup_test_db;
+    use crate::db::{content_db_fn, node_db_fn, workspace_db_fn};
+    use crate::types::node::NodeType;
+
+    #[tokio::test]
+    async fn test_workspace_stats_cache() {
+        let db = setup_test_db().await;
+        let workspace_id = uuid::Uuid::new_v4().to_string();
+        workspace_db_fn::create(&db, workspace_id.clone(), "测试工作区".to_string(), None)
+            .await
+            .unwrap();
+        for (id, node_type, text) in [
+            ("doc", NodeType::File, "今天写了三百字"),
+            ("chart", NodeType::Mermaid, "graph TD; A-->B"),
+        ] {
+            node_db_fn::create(
+                &db,
+                id.to_string(),
+                workspace_id.clone(),
+                None,
+                id.to_string(),
+                node_type,
+                None,
+            )
+            .await
+            .unwrap();
+            content_db_fn::create(&db, format!("c-{}", id), id.to_string(), text.to_string())
+                .await
+                .unwrap();
+        }
+
+        let (_, stats) = workspace_stats(&db, &workspace_id).await.unwrap();
+        assert_eq!(stats.len(), 1);
+        assert_eq!(stats["doc"].cjk_characters, 7);
+
+        let cached = WritingStatsEntity::find_by_id("doc")
+            .one(&db)
+            .await
+            .unwrap();
+        assert_eq!(cached.unwrap().content_version, 1);
+
+        content_db_fn::update(&db, "doc", "Hello world".to_string(), None)
+            .await
+            .unwrap();
+        let (_, stats) = workspace_stats(&db, &workspace_id).await.unwrap();
+        assert_eq!(stats["doc"].latin_words, 2);
+        assert_eq!(stats["doc"].cjk_characters, 0);
+
+        let cached = WritingStatsEntity::find_by_id("doc")
+            .one(&db)
+            .await
+            .unwrap();
+        assert_eq!(cached.unwrap().content_version, 2);
+    }
+
+    #[tokio::test]
+    async fn test_record_progress() {
+        let db = setup_test_db().await;
+        let workspace_id = uuid::Uuid::new_v4().to_string();
+        workspace_db_fn::create(&db, workspace_id.clone(), "测试工作区".to_string(), None)
+            .await
+            .unwrap();
+        node_db_fn::create(
+            &db,
+            "doc".to_string(),
+            workspace_id.clone(),
+            None,
+            "doc".to_string(),
+            NodeType::File,
+            None,
+        )
+        .await
+        .unwrap();
+        let day = NaiveDate::from_ymd_opt(2026, 3, 1).unwrap();
+
+        let first = content_db_fn::create(
+            &db,
+            "c-doc".to_string(),
+            "doc".to_string(),
+            "一二三四五".to_string(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(record_progress(&db, None, &first, day).await.unwrap(), 5);
+
+        let second = content_db_fn::update(&db, "doc", "一二三".to_string(), None)
+            .await
+            .unwrap();
+        assert_eq!(
+            record_progress(&db, Some(&first), &second, day)
+                .await
+                .unwrap(),
+            -2
+        );
+
+        let third = content_db_fn::update(&db, "doc", "一二三 four five".to_string(), None)
+            .await
+            .unwrap();
+        let next_day = day.succ_opt().unwrap();
+        assert_eq!(
+            record_progress(&db, Some(&second), &third, next_day)
+                .await
+                .unwrap(),
+            2
+        );
+
+        let rows = find_progress(&db, &workspace_id).await.unwrap();
+        let days: Vec<(&str, i64, i64)> = rows
+            .iter()
+            .map(|r| (r.date.as_str(), r.words_added, r.words_removed))
+            .collect();
+        assert_eq!(days, vec![("2026-03-01", 5, 2), ("2026-03-02", 2, 0)]);
+
+        // 节点删除后记录保留
+        node_db_fn::delete(&db, "doc").await.unwrap();
+        assert_eq!(find_progress(&db, &workspace_id).await.unwrap().len(), 2);
+    }
+}
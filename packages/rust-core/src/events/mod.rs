@@ -0,0 +1,150 @@
+//! 数据变更事件总线
+//!
+//! 进程内的广播总线：API 端点在写操作的事务中用 [`PendingEvents::record`]
+//! 把 [`ChangeEvent`] 追加到变更日志（增量同步使用），事务提交后再发布到总线。
+//! Warp 的 SSE 路由和 Tauri 的事件转发各自订阅。
+//! 没有订阅者时发布的事件直接丢弃。
+
+use std::sync::OnceLock;
+
+use sea_orm::ConnectionTrait;
+use tokio::sync::broadcast::{self, error::RecvError};
+use tracing::{debug, warn};
+
+use crate::db::change_log_db_fn;
+use crate::types::event::ChangeEvent;
+use crate::AppResult;
+
+/// 每个订阅者最多缓存的事件数量，超过后订阅者收到 `Resync`
+const EVENT_BUS_CAPACITY: usize = 1024;
+
+/// 事件总线
+pub struct EventBus {
+    sender: broadcast::Sender<ChangeEvent>,
+}
+
+impl EventBus {
+    /// 创建事件总线
+    pub fn new(capacity: usize) -> Self {
+        let (sender, _) = broadcast::channel(capacity);
+        Self { sender }
+    }
+
+    /// 发布事件
+    pub fn publish(&self, event: ChangeEvent) {
+        debug!("发布变更事件: {:?}", event);
+        // 没有订阅者时返回错误，忽略即可
+        let _ = self.sender.send(event);
+    }
+
+    /// 订阅事件（只接收订阅之后发布的事件）
+    pub fn subscribe(&self) -> EventSubscription {
+        EventSubscription {
+            receiver: self.sender.subscribe(),
+        }
+    }
+}
+
+/// 事件订阅
+pub struct EventSubscription {
+    receiver: broadcast::Receiver<ChangeEvent>,
+}
+
+impl EventSubscription {
+    /// 等待下一个事件
+    ///
+    /// 处理过慢丢失事件时返回 `ChangeEvent::Resync`；总线关闭时返回 None。
+    pub async fn recv(&mut self) -> Option<ChangeEvent> {
+        match self.receiver.recv().await {
+            Ok(event) => Some(event),
+            Err(RecvError::Lagged(skipped)) => {
+                warn!("事件订阅者落后，丢失 {} 个事件", skipped);
+                Some(ChangeEvent::Resync)
+            }
+            Err(RecvError::Closed) => None,
+        }
+    }
+}
+
+/// 全局事件总线
+pub fn event_bus() -> &'static EventBus {
+    static BUS: OnceLock<EventBus> = OnceLock::new();
+    BUS.get_or_init(|| EventBus::new(EVENT_BUS_CAPACITY))
+}
+
+/// 向全局事件总线发布事件
+pub fn publish(event: ChangeEvent) {
+    event_bus().publish(event);
+}
+
+/// 订阅全局事件总线
+pub fn subscribe() -> EventSubscription {
+    event_bus().subscribe()
+}
+
+/// 写操作中记录、等待事务提交后发布的事件
+///
+/// 变更日志与数据在同一事务中写入；事务回滚（提前返回错误）时事件随之丢弃，不会发布。
+#[derive(Debug, Default)]
+pub struct PendingEvents {
+    events: Vec<ChangeEvent>,
+}
+
+impl PendingEvents {
+    /// 创建空的事件列表
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 把事件追加到变更日志（在写操作的事务中调用）
+    pub async fn record<C: ConnectionTrait>(
+        &mut self,
+        db: &C,
+        event: ChangeEvent,
+    ) -> AppResult<()> {
+        change_log_db_fn::append(db, &event).await?;
+        self.events.push(event);
+        Ok(())
+    }
+
+    /// 发布到全局事件总线（事务提交后调用）
+    pub fn publish(self) {
+        for event in self.events {
+            publish(event);
+        }
+    }
+}
+
+// ============================================================================
+// 测试
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_publish_and_subscribe() {
+        let bus = EventBus::new(2);
+        // 没有订阅者时不报错
+        bus.publish(ChangeEvent::DataCleared);
+
+        let mut subscription = bus.subscribe();
+        bus.publish(ChangeEvent::TagsChanged {
+            workspace_id: "ws".to_string(),
+        });
+        assert_eq!(
+            subscription.recv().await,
+            Some(ChangeEvent::TagsChanged {
+                workspace_id: "ws".to_string()
+            })
+        );
+
+        // 超过容量时提示重新加载，然后继续接收最新的事件
+        for _ in 0..3 {
+            bus.publish(ChangeEvent::DataCleared);
+        }
+        assert_eq!(subscription.recv().await, Some(ChangeEvent::Resync));
+        assert_eq!(subscription.recv().await, Some(ChangeEvent::DataCleared));
+    }
+}
@@ -0,0 +1,198 @@
+//! 认证纯函数
+//!
+//! - 密码以 PBKDF2-HMAC-SHA256 加盐哈希保存，格式 `pbkdf2-sha256$轮数$盐$哈希`
+//! - 令牌是 256 位随机数（URL 安全的 Base64），数据库只保存它的 SHA-256 哈希
+//! - API 令牌带 `grain_pat_` 前缀，与登录会话的访问令牌区分
+//! - 请求头中的 Bearer 令牌解析和 CORS 来源校验
+
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use rand::Rng;
+use sha2::{Digest, Sha256};
+
+/// 密码哈希算法标识
+const PASSWORD_SCHEME: &str = "pbkdf2-sha256";
+
+/// PBKDF2 迭代轮数
+const PASSWORD_ROUNDS: u32 = 100_000;
+
+/// 密码哈希长度（字节）
+const PASSWORD_HASH_LEN: usize = 32;
+
+/// 密码最短长度（字符）
+pub const MIN_PASSWORD_LEN: usize = 8;
+
+/// 计算密码哈希（每次使用新的随机盐）
+pub fn hash_password(password: &str) -> String {
+    let salt: [u8; 16] = rand::thread_rng().r#gen();
+    let hash = derive_password(password, &salt, PASSWORD_ROUNDS);
+    format!(
+        "{}${}${}${}",
+        PASSWORD_SCHEME,
+        PASSWORD_ROUNDS,
+        URL_SAFE_NO_PAD.encode(salt),
+        URL_SAFE_NO_PAD.encode(hash)
+    )
+}
+
+/// 校验密码（格式无效的哈希视为不匹配）
+pub fn verify_password(password: &str, password_hash: &str) -> bool {
+    let mut parts = password_hash.split('$');
+    let (Some(PASSWORD_SCHEME), Some(rounds), Some(salt), Some(hash), None) = (
+        parts.next(),
+        parts.next(),
+        parts.next(),
+        parts.next(),
+        parts.next(),
+    ) else {
+        return false;
+    };
+    let (Ok(rounds), Ok(salt), Ok(hash)) = (
+        rounds.parse::<u32>(),
+        URL_SAFE_NO_PAD.decode(salt),
+        URL_SAFE_NO_PAD.decode(hash),
+    ) else {
+        return false;
+    };
+    if rounds == 0 {
+        return false;
+    }
+    constant_time_eq(&derive_password(password, &salt, rounds), &hash)
+}
+
+fn derive_password(password: &str, salt: &[u8], rounds: u32) -> [u8; PASSWORD_HASH_LEN] {
+    let mut hash = [0u8; PASSWORD_HASH_LEN];
+    pbkdf2::pbkdf2_hmac::<Sha256>(password.as_bytes(), salt, rounds, &mut hash);
+    hash
+}
+
+/// 比较两个字节串，耗时与内容无关
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+/// 生成随机令牌
+pub fn generate_token() -> String {
+    let token: [u8; 32] = rand::thread_rng().r#gen();
+    URL_SAFE_NO_PAD.encode(token)
+}
+
+/// API 令牌的前缀
+pub const API_TOKEN_PREFIX: &str = "grain_pat_";
+
+/// 列表中显示的 API 令牌开头长度（含前缀），用于辨认令牌
+const API_TOKEN_DISPLAY_LEN: usize = API_TOKEN_PREFIX.len() + 6;
+
+/// 生成随机 API 令牌
+pub fn generate_api_token() -> String {
+    format!("{}{}", API_TOKEN_PREFIX, generate_token())
+}
+
+/// 是否是 API 令牌（而不是登录会话的访问令牌）
+pub fn is_api_token(token: &str) -> bool {
+    token.starts_with(API_TOKEN_PREFIX)
+}
+
+/// API 令牌的开头部分（可以明文保存和显示）
+pub fn api_token_display_prefix(token: &str) -> String {
+    token.chars().take(API_TOKEN_DISPLAY_LEN).collect()
+}
+
+/// 令牌的哈希（十六进制 SHA-256），数据库按它查找令牌
+pub fn hash_token(token: &str) -> String {
+    Sha256::digest(token.as_bytes())
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
+
+/// 从 `Authorization` 请求头解析 Bearer 令牌
+pub fn parse_bearer(header: &str) -> Option<&str> {
+    let (scheme, token) = header.trim().split_once(' ')?;
+    let token = token.trim();
+    (scheme.eq_ignore_ascii_case("bearer") && !token.is_empty()).then_some(token)
+}
+
+/// 校验 CORS 来源（`scheme://host[:port]`，不带路径）
+pub fn is_valid_origin(origin: &str) -> bool {
+    let Some((scheme, host)) = origin.split_once("://") else {
+        return false;
+    };
+    let scheme_ok = !scheme.is_empty()
+        && scheme
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.'));
+    let (host, port) = match host.rsplit_once(':') {
+        Some((host, port)) => (host, Some(port)),
+        None => (host, None),
+    };
+    let host_ok = !host.is_empty()
+        && host
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '.'));
+    let port_ok = port.is_none_or(|p| p.parse::<u16>().is_ok());
+    scheme_ok && host_ok && port_ok
+}
+
+// ============================================================================
+// 测试
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_password_hash_round_trip() {
+        let hash = hash_password("correct horse");
+        assert!(hash.starts_with("pbkdf2-sha256$100000$"));
+        assert!(verify_password("correct horse", &hash));
+        assert!(!verify_password("wrong horse", &hash));
+        // 相同密码每次的盐不同
+        assert_ne!(hash, hash_password("correct horse"));
+
+        assert!(!verify_password("correct horse", "plain-text"));
+        assert!(!verify_password("correct horse", "pbkdf2-sha256$0$AA$AA"));
+    }
+
+    #[test]
+    fn test_token_hash() {
+        let token = generate_token();
+        assert_eq!(token.len(), 43);
+        assert_ne!(token, generate_token());
+        assert_eq!(hash_token(&token), hash_token(&token));
+        assert_eq!(
+            hash_token("abc"),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+    }
+
+    #[test]
+    fn test_api_token() {
+        let token = generate_api_token();
+        assert!(is_api_token(&token));
+        assert!(!is_api_token(&generate_token()));
+        let prefix = api_token_display_prefix(&token);
+        assert_eq!(prefix.len(), 16);
+        assert!(token.starts_with(&prefix));
+    }
+
+    #[test]
+    fn test_parse_bearer() {
+        assert_eq!(parse_bearer("Bearer abc"), Some("abc"));
+        assert_eq!(parse_bearer("bearer  abc "), Some("abc"));
+        assert_eq!(parse_bearer("Basic abc"), None);
+        assert_eq!(parse_bearer("Bearer "), None);
+        assert_eq!(parse_bearer("abc"), None);
+    }
+
+    #[test]
+    fn test_is_valid_origin() {
+        assert!(is_valid_origin("http://localhost:1420"));
+        assert!(is_valid_origin("https://grain.example.com"));
+        assert!(is_valid_origin("tauri://localhost"));
+        assert!(!is_valid_origin("localhost:1420"));
+        assert!(!is_valid_origin("https://grain.example.com/app"));
+        assert!(!is_valid_origin("http://localhost:99999"));
+        assert!(!is_valid_origin("*"));
+    }
+}
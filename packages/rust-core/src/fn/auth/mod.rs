@@ -0,0 +1,5 @@
+//! 认证纯函数模块
+
+pub mod auth_fn;
+
+pub use auth_fn::*;
@@ -14,7 +14,7 @@ use zip::write::SimpleFileOptions;
 use zip::{ZipArchive, ZipWriter};
 
 /// 备份信息
-#[derive(Debug, Clone, serde::Serialize)]
+#[derive(Debug, Clone, serde::Serialize, schemars::JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct BackupInfo {
     /// 备份文件名
@@ -276,6 +276,7 @@ mod tests {
             db_filename: "test.db".to_string(),
             backup_dirname: "backups".to_string(),
             enable_encryption: false,
+            ..Default::default()
         };
 
         // 创建一个假的数据库文件
@@ -304,6 +305,7 @@ mod tests {
             db_filename: "test.db".to_string(),
             backup_dirname: "backups".to_string(),
             enable_encryption: false,
+            ..Default::default()
         };
 
         // 不创建数据库文件
@@ -319,6 +321,7 @@ mod tests {
             db_filename: "test.db".to_string(),
             backup_dirname: "backups".to_string(),
             enable_encryption: false,
+            ..Default::default()
         };
 
         // 创建数据库和备份
@@ -344,6 +347,7 @@ mod tests {
             db_filename: "test.db".to_string(),
             backup_dirname: "backups".to_string(),
             enable_encryption: false,
+            ..Default::default()
         };
 
         // 创建数据库
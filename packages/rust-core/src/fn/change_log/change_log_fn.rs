@@ -0,0 +1,279 @@
+//! ChangeLog 纯函数
+//!
+//! 把变更事件映射为变更日志记录，并压缩过时的记录：
+//! - 同一实体只保留最新一条（内容以节点 ID 为实体 ID，节点删除后其内容记录也过时）
+//! - 工作区删除之前、该工作区内的记录都过时
+//! - 清除数据（reset）之前的记录都过时
+
+use std::cmp::Reverse;
+use std::collections::HashSet;
+
+use crate::types::change_log::{change_log_entity, ChangeEntity, ChangeOperation};
+use crate::types::event::ChangeEvent;
+
+/// 变更日志记录的目标（不含序号和时间）
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChangeTarget {
+    /// 所属工作区 ID
+    pub workspace_id: Option<String>,
+
+    /// 实体类型
+    pub entity_type: ChangeEntity,
+
+    /// 实体 ID
+    pub entity_id: String,
+
+    /// 变更操作
+    pub operation: ChangeOperation,
+
+    /// 内容版本号
+    pub version: Option<i32>,
+}
+
+impl ChangeTarget {
+    fn new(
+        workspace_id: &str,
+        entity_type: ChangeEntity,
+        entity_id: &str,
+        operation: ChangeOperation,
+    ) -> Self {
+        Self {
+            workspace_id: Some(workspace_id.to_string()),
+            entity_type,
+            entity_id: entity_id.to_string(),
+            operation,
+            version: None,
+        }
+    }
+}
+
+/// 把变更事件映射为变更日志记录（`Resync` 不记录）
+pub fn change_target(event: &ChangeEvent) -> Option<ChangeTarget> {
+    use ChangeEntity as E;
+    use ChangeOperation as Op;
+
+    let target = match event {
+        ChangeEvent::WorkspaceCreated { workspace_id }
+        | ChangeEvent::WorkspaceUpdated { workspace_id } => {
+            ChangeTarget::new(workspace_id, E::Workspace, workspace_id, Op::Upsert)
+        }
+        ChangeEvent::WorkspaceDeleted { workspace_id } => {
+            ChangeTarget::new(workspace_id, E::Workspace, workspace_id, Op::Delete)
+        }
+        ChangeEvent::NodeCreated {
+            workspace_id,
+            node_id,
+            ..
+        }
+        | ChangeEvent::NodeUpdated {
+            workspace_id,
+            node_id,
+        }
+        | ChangeEvent::NodeMoved {
+            workspace_id,
+            node_id,
+            ..
+        } => ChangeTarget::new(workspace_id, E::Node, node_id, Op::Upsert),
+        ChangeEvent::NodeDeleted {
+            workspace_id,
+            node_id,
+        } => ChangeTarget::new(workspace_id, E::Node, node_id, Op::Delete),
+        ChangeEvent::ContentSaved {
+            workspace_id,
+            node_id,
+            version,
+        } => ChangeTarget {
+            version: Some(*version),
+            ..ChangeTarget::new(workspace_id, E::Content, node_id, Op::Upsert)
+        },
+        ChangeEvent::TagsChanged { workspace_id } => {
+            ChangeTarget::new(workspace_id, E::Tags, workspace_id, Op::Upsert)
+        }
+        ChangeEvent::DataCleared => ChangeTarget {
+            workspace_id: None,
+            entity_type: E::Database,
+            entity_id: String::new(),
+            operation: Op::Reset,
+            version: None,
+        },
+        ChangeEvent::Resync => return None,
+    };
+    Some(target)
+}
+
+/// 逐条判断记录是否被取代
+///
+/// 记录必须按序号降序（从新到旧）传入，可以分多页传入同一个实例，
+/// 保留的状态只与实体数量有关。
+#[derive(Debug, Default)]
+pub struct ChangeCompactor {
+    seen: HashSet<(ChangeEntity, String)>,
+    deleted_workspaces: HashSet<String>,
+    reset: bool,
+}
+
+impl ChangeCompactor {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 记录是否被之前传入的（更新的）记录取代
+    pub fn is_superseded(&mut self, entry: &change_log_entity::Model) -> bool {
+        let key = (entry.entity_type, entry.entity_id.clone());
+        let workspace_deleted = entry
+            .workspace_id
+            .as_deref()
+            .is_some_and(|ws| self.deleted_workspaces.contains(ws));
+        if self.reset || workspace_deleted || self.seen.contains(&key) {
+            return true;
+        }
+
+        self.seen.insert(key);
+        match (entry.entity_type, entry.operation) {
+            (_, ChangeOperation::Reset) => self.reset = true,
+            (ChangeEntity::Workspace, ChangeOperation::Delete) => {
+                self.deleted_workspaces.insert(entry.entity_id.clone());
+            }
+            (ChangeEntity::Node, ChangeOperation::Delete) => {
+                self.seen
+                    .insert((ChangeEntity::Content, entry.entity_id.clone()));
+            }
+            _ => {}
+        }
+        false
+    }
+}
+
+/// 找出被后续记录取代的记录序号
+///
+/// `entries` 需要包含某个序号之后的全部记录（顺序不限）。
+pub fn superseded_seqs(entries: &[change_log_entity::Model]) -> HashSet<i64> {
+    let mut ordered: Vec<&change_log_entity::Model> = entries.iter().collect();
+    ordered.sort_by_key(|e| Reverse(e.seq));
+
+    let mut compactor = ChangeCompactor::new();
+    ordered
+        .into_iter()
+        .filter(|e| compactor.is_superseded(e))
+        .map(|e| e.seq)
+        .collect()
+}
+
+/// 压缩变更记录：去掉被取代的记录，按序号升序返回
+pub fn compact_changes(entries: Vec<change_log_entity::Model>) -> Vec<change_log_entity::Model> {
+    let superseded = superseded_seqs(&entries);
+    let mut kept: Vec<change_log_entity::Model> = entries
+        .into_iter()
+        .filter(|e| !superseded.contains(&e.seq))
+        .collect();
+    kept.sort_by_key(|e| e.seq);
+    kept
+}
+
+// ============================================================================
+// 测试
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(seq: i64, event: ChangeEvent) -> change_log_entity::Model {
+        let target = change_target(&event).unwrap();
+        change_log_entity::Model {
+            seq,
+            workspace_id: target.workspace_id,
+            entity_type: target.entity_type,
+            entity_id: target.entity_id,
+            operation: target.operation,
+            version: target.version,
+            created_at: seq,
+        }
+    }
+
+    fn node_updated(ws: &str, node: &str) -> ChangeEvent {
+        ChangeEvent::NodeUpdated {
+            workspace_id: ws.to_string(),
+            node_id: node.to_string(),
+        }
+    }
+
+    fn content_saved(ws: &str, node: &str, version: i32) -> ChangeEvent {
+        ChangeEvent::ContentSaved {
+            workspace_id: ws.to_string(),
+            node_id: node.to_string(),
+            version,
+        }
+    }
+
+    fn seqs(entries: &[change_log_entity::Model]) -> Vec<i64> {
+        entries.iter().map(|e| e.seq).collect()
+    }
+
+    #[test]
+    fn test_change_target() {
+        let target = change_target(&content_saved("ws", "n1", 4)).unwrap();
+        assert_eq!(target.entity_type, ChangeEntity::Content);
+        assert_eq!(target.entity_id, "n1");
+        assert_eq!(target.version, Some(4));
+
+        let target = change_target(&ChangeEvent::TagsChanged {
+            workspace_id: "ws".to_string(),
+        })
+        .unwrap();
+        assert_eq!(
+            (target.entity_type, target.entity_id.as_str()),
+            (ChangeEntity::Tags, "ws")
+        );
+
+        let target = change_target(&ChangeEvent::DataCleared).unwrap();
+        assert_eq!(target.operation, ChangeOperation::Reset);
+        assert_eq!(target.workspace_id, None);
+
+        assert!(change_target(&ChangeEvent::Resync).is_none());
+    }
+
+    #[test]
+    fn test_compact_keeps_latest_per_entity() {
+        let entries = vec![
+            entry(1, node_updated("ws", "n1")),
+            entry(2, content_saved("ws", "n1", 2)),
+            entry(3, node_updated("ws", "n2")),
+            entry(4, content_saved("ws", "n1", 3)),
+            entry(5, node_updated("ws", "n1")),
+        ];
+        let compacted = compact_changes(entries);
+        assert_eq!(seqs(&compacted), vec![3, 4, 5]);
+        assert_eq!(compacted[1].version, Some(3));
+    }
+
+    #[test]
+    fn test_compact_deletes() {
+        let entries = vec![
+            entry(1, node_updated("ws", "n1")),
+            entry(2, content_saved("ws", "n1", 2)),
+            entry(3, node_updated("other", "n2")),
+            entry(
+                4,
+                ChangeEvent::NodeDeleted {
+                    workspace_id: "ws".to_string(),
+                    node_id: "n1".to_string(),
+                },
+            ),
+            entry(5, node_updated("ws", "n3")),
+            entry(
+                6,
+                ChangeEvent::WorkspaceDeleted {
+                    workspace_id: "ws".to_string(),
+                },
+            ),
+        ];
+        // 节点删除取代其内容记录，工作区删除取代工作区内的所有记录
+        assert_eq!(seqs(&compact_changes(entries.clone())), vec![3, 6]);
+
+        let mut with_reset = entries;
+        with_reset.push(entry(7, ChangeEvent::DataCleared));
+        with_reset.push(entry(8, node_updated("other", "n4")));
+        assert_eq!(seqs(&compact_changes(with_reset)), vec![7, 8]);
+    }
+}
@@ -0,0 +1,5 @@
+//! ChangeLog 纯函数模块
+
+pub mod change_log_fn;
+
+pub use change_log_fn::*;
@@ -0,0 +1,746 @@
+//! Content CRDT 纯函数
+//!
+//! 协同编辑使用兼容 Yjs 的 CRDT 文档（yrs），Lexical 内容的结构与 `@lexical/yjs` 相同：
+//! - 名为 `root` 的 XmlText 对应根节点；节点属性以 `__` 开头保存（`__type`、`__format`、
+//!   `__indent` 等，`direction` 对应 `__dir`）
+//! - 元素节点是嵌入父节点的 XmlText；文本节点是嵌入的 Y.Map（属性）加上紧随其后的文本；
+//!   换行是 `__type` 为 `linebreak` 的 Y.Map；装饰节点是嵌入的 XmlElement
+//! - 不是 Lexical JSON 的内容（纯文本等）保存在名为 `content` 的 Y.Text 中
+//! - 编辑以 Yjs v1 编码的更新（update）交换和保存，全部更新合并后得到文档状态
+//! - 文本偏移按 UTF-16 计算，与 Yjs 客户端一致
+//!
+//! Lexical 内容总是由文档结构生成 JSON，并发编辑合并后不会得到无效的 JSON。
+
+use std::sync::Arc;
+
+use serde_json::{json, Map, Value};
+use yrs::types::text::YChange;
+use yrs::updates::decoder::Decode;
+use yrs::{
+    Any, Doc, GetString, In, Map as _, MapPrelim, MapRef, Number, OffsetKind, Options, Out,
+    ReadTxn, StateVector, Text, TextRef, Transact, TransactionMut, Update, Xml, XmlElementPrelim,
+    XmlElementRef, XmlTextPrelim, XmlTextRef,
+};
+
+use crate::{AppError, AppResult};
+
+/// Lexical 根节点的 XmlText 名称
+pub const CRDT_ROOT_NAME: &str = "root";
+
+/// 保存非 Lexical 内容的 Y.Text 名称
+pub const CRDT_TEXT_NAME: &str = "content";
+
+/// 装饰节点 XmlElement 的标签名（与 `@lexical/yjs` 相同）
+const DECORATOR_TAG: &str = "UNDEFINED";
+
+/// 元素节点的 `format`：JSON 中为名称，文档中为序号（与 Lexical 内部表示相同）
+const ELEMENT_FORMATS: [&str; 7] = ["", "left", "center", "right", "justify", "start", "end"];
+
+/// 文本节点的 `mode`：JSON 中为名称，文档中为序号
+const TEXT_MODES: [&str; 3] = ["normal", "token", "segmented"];
+
+// ============================================================================
+// 文档
+// ============================================================================
+
+/// 协同编辑文档
+pub struct CrdtDocument {
+    doc: Doc,
+}
+
+impl CrdtDocument {
+    /// 创建与 Yjs 偏移规则一致的空文档
+    fn empty() -> Self {
+        Self {
+            doc: Doc::with_options(Options {
+                offset_kind: OffsetKind::Utf16,
+                ..Options::default()
+            }),
+        }
+    }
+
+    /// 以内容创建文档（Lexical JSON 按节点结构写入，其他内容写入 Y.Text）
+    pub fn from_content(content: &str) -> Self {
+        let document = Self::empty();
+        match lexical_root(content) {
+            Some(root) => document.replace_root(&root),
+            None => document.replace_text(content),
+        }
+        document
+    }
+
+    /// 依次应用更新，得到文档
+    ///
+    /// 任一更新无法解码或应用时返回 ValidationError。
+    pub fn from_updates<B: AsRef<[u8]>>(updates: &[B]) -> AppResult<Self> {
+        let document = Self::empty();
+        for update in updates {
+            document.apply_update(update.as_ref())?;
+        }
+        Ok(document)
+    }
+
+    /// 应用一条更新（依赖的更新还没有到达时暂存，到达后自动应用）
+    pub fn apply_update(&self, update: &[u8]) -> AppResult<()> {
+        let update = Update::decode_v1(update)
+            .map_err(|e| AppError::validation(format!("无效的 CRDT 更新: {}", e)))?;
+        self.doc
+            .transact_mut()
+            .apply_update(update)
+            .map_err(|e| AppError::validation(format!("无效的 CRDT 更新: {}", e)))
+    }
+
+    /// 文档的完整状态（一条包含全部内容的更新，已删除的内容被清理）
+    pub fn state(&self) -> Vec<u8> {
+        self.doc
+            .transact()
+            .encode_state_as_update_v1(&StateVector::default())
+    }
+
+    /// 文档的状态向量
+    pub fn state_vector(&self) -> StateVector {
+        self.doc.transact().state_vector()
+    }
+
+    /// 对方（以 Yjs v1 编码的状态向量表示）还没有的更新
+    pub fn diff(&self, state_vector: &[u8]) -> AppResult<Vec<u8>> {
+        let state_vector = StateVector::decode_v1(state_vector)
+            .map_err(|e| AppError::validation(format!("无效的状态向量: {}", e)))?;
+        let diff = self.doc.transact().encode_diff_v1(&state_vector);
+        Ok(diff)
+    }
+
+    /// 文档内容：有 Lexical 根节点时为 Lexical JSON，否则为 Y.Text 的文本
+    pub fn content(&self) -> String {
+        let root = self.root();
+        let text = self.text();
+        let txn = self.doc.transact();
+        if root.len(&txn) == 0 && root.attributes(&txn).next().is_none() {
+            return text.get_string(&txn);
+        }
+
+        let mut node = read_properties(&txn, &root, NodeKind::Element);
+        node.entry("type").or_insert_with(|| json!("root"));
+        node.insert(
+            "children".to_string(),
+            Value::Array(
+                read_children(&txn, &root)
+                    .into_iter()
+                    .map(|(child, _)| child)
+                    .collect(),
+            ),
+        );
+        json!({ "root": node }).to_string()
+    }
+
+    /// 把文档内容替换为 `content`，返回这次修改的更新；内容相同时返回 None
+    ///
+    /// Lexical 内容只替换首尾相同的块之间的区域，其他文本只替换首尾相同的字符之间的区域，
+    /// 其他协作者在别处的编辑不受影响。
+    pub fn replace_content(&self, content: &str) -> Option<Vec<u8>> {
+        let before = self.state_vector();
+        match lexical_root(content) {
+            Some(root) => self.replace_root(&root),
+            None => self.replace_text(content),
+        }
+        if self.state_vector() == before {
+            return None;
+        }
+        let update = self.doc.transact().encode_diff_v1(&before);
+        Some(update)
+    }
+
+    fn root(&self) -> XmlTextRef {
+        let root = self.doc.get_or_insert_text(CRDT_ROOT_NAME);
+        AsRef::<XmlTextRef>::as_ref(&root).clone()
+    }
+
+    fn text(&self) -> TextRef {
+        self.doc.get_or_insert_text(CRDT_TEXT_NAME)
+    }
+
+    /// 替换根节点的属性和有变化的块
+    fn replace_root(&self, node: &Map<String, Value>) {
+        let root = self.root();
+        let mut txn = self.doc.transact_mut();
+
+        let properties = node_properties(node, NodeKind::Element);
+        let stale: Vec<String> = root
+            .attributes(&txn)
+            .map(|(name, _)| name.to_string())
+            .filter(|name| !properties.iter().any(|(n, _)| n == name))
+            .collect();
+        for name in stale {
+            root.remove_attribute(&mut txn, &name);
+        }
+        for (name, value) in properties {
+            if !matches!(root.get_attribute(&txn, &name), Some(Out::Any(ref v)) if *v == value) {
+                root.insert_attribute(&mut txn, name, In::Any(value));
+            }
+        }
+
+        let current = read_children(&txn, &root);
+        let children = node_children(node);
+        let prefix = current
+            .iter()
+            .zip(children)
+            .take_while(|((a, _), b)| a == *b)
+            .count();
+        let suffix = current[prefix..]
+            .iter()
+            .rev()
+            .zip(children[prefix..].iter().rev())
+            .take_while(|((a, _), b)| a == *b)
+            .count();
+
+        let index: u32 = current[..prefix].iter().map(|(_, len)| len).sum();
+        let removed: u32 = current[prefix..current.len() - suffix]
+            .iter()
+            .map(|(_, len)| len)
+            .sum();
+        if removed > 0 {
+            root.remove_range(&mut txn, index, removed);
+        }
+        write_children(
+            &mut txn,
+            &root,
+            index,
+            &children[prefix..children.len() - suffix],
+        );
+    }
+
+    /// 替换 Y.Text 中首尾相同部分之间的文本
+    fn replace_text(&self, text: &str) {
+        let content = self.text();
+        let mut txn = self.doc.transact_mut();
+        let current = content.get_string(&txn);
+        if current == text {
+            return;
+        }
+
+        let old: Vec<char> = current.chars().collect();
+        let new: Vec<char> = text.chars().collect();
+        let prefix = old.iter().zip(&new).take_while(|(a, b)| a == b).count();
+        let suffix = old[prefix..]
+            .iter()
+            .rev()
+            .zip(new[prefix..].iter().rev())
+            .take_while(|(a, b)| a == b)
+            .count();
+
+        let index = utf16_len(old[..prefix].iter().copied());
+        let removed = utf16_len(old[prefix..old.len() - suffix].iter().copied());
+        if removed > 0 {
+            content.remove_range(&mut txn, index, removed);
+        }
+        let inserted: String = new[prefix..new.len() - suffix].iter().collect();
+        if !inserted.is_empty() {
+            content.insert(&mut txn, index, &inserted);
+        }
+    }
+}
+
+/// 以内容创建文档，返回文档状态
+pub fn crdt_state_from_content(content: &str) -> Vec<u8> {
+    CrdtDocument::from_content(content).state()
+}
+
+/// 合并多条更新为一条文档状态，同时清理已删除的内容
+///
+/// 任一更新无法解码或应用时返回 ValidationError。
+pub fn merge_crdt_updates<B: AsRef<[u8]>>(updates: &[B]) -> AppResult<Vec<u8>> {
+    Ok(CrdtDocument::from_updates(updates)?.state())
+}
+
+/// 文档状态中的内容
+pub fn crdt_content(state: &[u8]) -> AppResult<String> {
+    Ok(CrdtDocument::from_updates(&[state])?.content())
+}
+
+// ============================================================================
+// Lexical 节点 <-> 文档结构
+// ============================================================================
+
+/// 节点在文档中的表示
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum NodeKind {
+    /// 元素节点（XmlText）
+    Element,
+    /// 文本节点（Y.Map + 文本）
+    Text,
+    /// 换行（Y.Map）
+    LineBreak,
+    /// 装饰节点（XmlElement）
+    Decorator,
+}
+
+impl NodeKind {
+    fn of(node: &Map<String, Value>) -> Self {
+        if node.get("children").is_some_and(Value::is_array) {
+            NodeKind::Element
+        } else if node.get("type").and_then(Value::as_str) == Some("linebreak") {
+            NodeKind::LineBreak
+        } else if node.get("text").is_some_and(Value::is_string) {
+            NodeKind::Text
+        } else {
+            NodeKind::Decorator
+        }
+    }
+}
+
+/// Lexical JSON 的根节点（不是带 `root` 对象的 JSON 时返回 None）
+fn lexical_root(content: &str) -> Option<Map<String, Value>> {
+    match serde_json::from_str::<Value>(content).ok()? {
+        Value::Object(mut document) => match document.remove("root")? {
+            Value::Object(root) => Some(root),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+fn node_children(node: &Map<String, Value>) -> &[Value] {
+    node.get("children")
+        .and_then(Value::as_array)
+        .map_or(&[], Vec::as_slice)
+}
+
+/// 节点保存在文档中的属性（子节点、文本和 `version` 不是属性）
+fn node_properties(node: &Map<String, Value>, kind: NodeKind) -> Vec<(String, Any)> {
+    node.iter()
+        .filter(|(key, _)| !matches!(key.as_str(), "children" | "text" | "version"))
+        .map(|(key, value)| {
+            let name = match key.as_str() {
+                "direction" => "__dir".to_string(),
+                _ => format!("__{}", key),
+            };
+            let value = match (kind, key.as_str(), value) {
+                (NodeKind::Element, "format", Value::String(format)) => {
+                    named_index(&ELEMENT_FORMATS, format)
+                }
+                (NodeKind::Text, "mode", Value::String(mode)) => named_index(&TEXT_MODES, mode),
+                _ => json_to_any(value),
+            };
+            (name, value)
+        })
+        .collect()
+}
+
+/// 从属性还原 JSON 字段
+fn property_field(name: &str, value: &Any, kind: NodeKind) -> Option<(String, Value)> {
+    let key = match name.strip_prefix("__")? {
+        "dir" => "direction",
+        key => key,
+    };
+    let value = match (kind, key, value) {
+        (NodeKind::Element, "format", Any::Number(Number::Int(index))) => {
+            json!(ELEMENT_FORMATS
+                .get(*index as usize)
+                .copied()
+                .unwrap_or_default())
+        }
+        (NodeKind::Text, "mode", Any::Number(Number::Int(index))) => {
+            json!(TEXT_MODES.get(*index as usize).copied().unwrap_or("normal"))
+        }
+        _ => any_to_json(value),
+    };
+    Some((key.to_string(), value))
+}
+
+fn named_index(names: &[&str], name: &str) -> Any {
+    let index = names.iter().position(|n| *n == name).unwrap_or_default();
+    Any::Number(Number::Int(index as i64))
+}
+
+/// 在 `index` 处依次写入子节点
+fn write_children(txn: &mut TransactionMut, parent: &XmlTextRef, index: u32, children: &[Value]) {
+    let mut index = index;
+    for node in children.iter().filter_map(Value::as_object) {
+        index += write_node(txn, parent, index, node);
+    }
+}
+
+/// 在 `index` 处写入节点，返回节点占用的长度
+fn write_node(
+    txn: &mut TransactionMut,
+    parent: &XmlTextRef,
+    index: u32,
+    node: &Map<String, Value>,
+) -> u32 {
+    let kind = NodeKind::of(node);
+    let properties = node_properties(node, kind);
+    match kind {
+        NodeKind::Element => {
+            let element = parent.insert_embed(txn, index, XmlTextPrelim::new(""));
+            for (name, value) in properties {
+                element.insert_attribute(txn, name, In::Any(value));
+            }
+            write_children(txn, &element, 0, node_children(node));
+            1
+        }
+        NodeKind::Text | NodeKind::LineBreak => {
+            let map: MapPrelim = properties
+                .into_iter()
+                .map(|(name, value)| (name, In::Any(value)))
+                .collect();
+            parent.insert_embed(txn, index, map);
+            let text = node.get("text").and_then(Value::as_str).unwrap_or_default();
+            if kind == NodeKind::Text && !text.is_empty() {
+                parent.insert(txn, index + 1, text);
+                1 + utf16_len(text.chars())
+            } else {
+                1
+            }
+        }
+        NodeKind::Decorator => {
+            let decorator = parent.insert_embed(txn, index, XmlElementPrelim::empty(DECORATOR_TAG));
+            for (name, value) in properties {
+                decorator.insert_attribute(txn, name, In::Any(value));
+            }
+            1
+        }
+    }
+}
+
+/// 读取元素或装饰节点的属性
+fn read_properties<T: ReadTxn, X: Xml>(txn: &T, node: &X, kind: NodeKind) -> Map<String, Value> {
+    let mut fields: Map<String, Value> = node
+        .attributes(txn)
+        .filter_map(|(name, value)| match value {
+            Out::Any(value) => property_field(name, &value, kind),
+            _ => None,
+        })
+        .collect();
+    fields.insert("version".to_string(), json!(1));
+    fields
+}
+
+/// 读取子节点和每个子节点占用的长度
+///
+/// 没有属性 Map 的文本（并发编辑时可能出现）作为默认格式的文本节点。
+fn read_children<T: ReadTxn>(txn: &T, parent: &XmlTextRef) -> Vec<(Value, u32)> {
+    let mut children: Vec<(Value, u32)> = Vec::new();
+    // 最后一个子节点是否是可以接上文本的文本节点
+    let mut text_open = false;
+    for chunk in parent.diff(txn, YChange::identity) {
+        match chunk.insert {
+            Out::Any(Any::String(text)) => {
+                if !text_open {
+                    children.push((default_text_node(), 0));
+                    text_open = true;
+                }
+                if let Some((node, len)) = children.last_mut() {
+                    let joined = format!("{}{}", node["text"].as_str().unwrap_or_default(), text);
+                    node["text"] = json!(joined);
+                    *len += utf16_len(text.chars());
+                }
+            }
+            Out::YMap(map) => {
+                let node = read_map(txn, &map);
+                text_open = node["type"] != "linebreak";
+                children.push((node, 1));
+            }
+            Out::YXmlText(element) => {
+                let mut node = read_properties(txn, &element, NodeKind::Element);
+                let nested = read_children(txn, &element);
+                node.insert(
+                    "children".to_string(),
+                    Value::Array(nested.into_iter().map(|(child, _)| child).collect()),
+                );
+                children.push((Value::Object(node), 1));
+                text_open = false;
+            }
+            Out::YXmlElement(decorator) => {
+                children.push((read_decorator(txn, &decorator), 1));
+                text_open = false;
+            }
+            _ => text_open = false,
+        }
+    }
+    children
+}
+
+/// 读取文本节点或换行的属性 Map
+fn read_map<T: ReadTxn>(txn: &T, map: &MapRef) -> Value {
+    let mut fields: Map<String, Value> = map
+        .iter(txn)
+        .filter_map(|(name, value)| match value {
+            Out::Any(value) => property_field(name, &value, NodeKind::Text),
+            _ => None,
+        })
+        .collect();
+    fields.insert("version".to_string(), json!(1));
+    if fields.get("type").and_then(Value::as_str) != Some("linebreak") {
+        fields.insert("text".to_string(), json!(""));
+    }
+    Value::Object(fields)
+}
+
+fn read_decorator<T: ReadTxn>(txn: &T, decorator: &XmlElementRef) -> Value {
+    Value::Object(read_properties(txn, decorator, NodeKind::Decorator))
+}
+
+fn default_text_node() -> Value {
+    json!({
+        "detail": 0,
+        "format": 0,
+        "mode": "normal",
+        "style": "",
+        "text": "",
+        "type": "text",
+        "version": 1
+    })
+}
+
+fn json_to_any(value: &Value) -> Any {
+    match value {
+        Value::Null => Any::Null,
+        Value::Bool(b) => Any::Bool(*b),
+        Value::Number(n) => match n.as_i64() {
+            Some(i) => Any::Number(Number::Int(i)),
+            None => Any::Number(Number::Float(n.as_f64().unwrap_or_default())),
+        },
+        Value::String(s) => Any::String(Arc::from(s.as_str())),
+        Value::Array(items) => Any::Array(items.iter().map(json_to_any).collect()),
+        Value::Object(fields) => Any::Map(Arc::new(
+            fields
+                .iter()
+                .map(|(key, value)| (key.clone(), json_to_any(value)))
+                .collect(),
+        )),
+    }
+}
+
+/// Yjs 的整数可能以浮点数编码，整数值还原为 JSON 整数
+fn any_to_json(value: &Any) -> Value {
+    match value {
+        Any::Null | Any::Undefined | Any::Buffer(_) => Value::Null,
+        Any::Bool(b) => Value::Bool(*b),
+        Any::Number(Number::Int(i)) => json!(i),
+        Any::Number(Number::Float(f)) if f.fract() == 0.0 && f.abs() < 9_007_199_254_740_992.0 => {
+            json!(*f as i64)
+        }
+        Any::Number(Number::Float(f)) => json!(f),
+        Any::String(s) => json!(s.as_ref()),
+        Any::Array(items) => Value::Array(items.iter().map(any_to_json).collect()),
+        Any::Map(fields) => Value::Object(
+            fields
+                .iter()
+                .map(|(key, value)| (key.clone(), any_to_json(value)))
+                .collect(),
+        ),
+    }
+}
+
+fn utf16_len(chars: impl Iterator<Item = char>) -> u32 {
+    chars.map(|c| c.len_utf16() as u32).sum()
+}
+
+// ============================================================================
+// 测试
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use yrs::updates::encoder::Encode;
+
+    /// 模拟一个客户端：在已有状态上编辑，返回这次编辑的更新
+    fn edit(state: &[u8], name: &str, f: impl FnOnce(&TextRef, &mut TransactionMut)) -> Vec<u8> {
+        let document = CrdtDocument::from_updates(&[state]).unwrap();
+        let text = document.doc.get_or_insert_text(name);
+        let before = document.state_vector();
+        f(&text, &mut document.doc.transact_mut());
+        let diff = document.doc.transact().encode_diff_v1(&before);
+        diff
+    }
+
+    fn lexical(paragraphs: &[&str]) -> String {
+        let children: Vec<Value> = paragraphs
+            .iter()
+            .map(|text| {
+                json!({
+                    "children": [{
+                        "detail": 0, "format": 1, "mode": "normal", "style": "",
+                        "text": text, "type": "text", "version": 1
+                    }],
+                    "direction": "ltr", "format": "center", "indent": 0,
+                    "type": "paragraph", "version": 1
+                })
+            })
+            .collect();
+        json!({
+            "root": {
+                "children": children, "direction": "ltr", "format": "", "indent": 0,
+                "type": "root", "version": 1
+            }
+        })
+        .to_string()
+    }
+
+    fn parse(content: &str) -> Value {
+        serde_json::from_str(content).unwrap()
+    }
+
+    #[test]
+    fn test_lexical_round_trip() {
+        let content = r##"{"root":{"children":[
+            {"children":[
+                {"detail":0,"format":0,"mode":"normal","style":"","text":"你好","type":"text","version":1},
+                {"type":"linebreak","version":1},
+                {"detail":0,"format":0,"mode":"token","style":"","tagName":"rust","text":"#[rust]","type":"tag","version":1}
+            ],"direction":null,"format":"","indent":0,"type":"heading","tag":"h1","version":1},
+            {"type":"image","src":"a.png","width":120,"version":1},
+            {"children":[],"direction":null,"format":"right","indent":1,"type":"paragraph","version":1}
+        ],"direction":"ltr","format":"","indent":0,"type":"root","version":1}}"##;
+
+        let state = crdt_state_from_content(content);
+        assert_eq!(parse(&crdt_content(&state).unwrap()), parse(content));
+    }
+
+    #[test]
+    fn test_lexical_yjs_structure() {
+        let document = CrdtDocument::from_content(&lexical(&["ab"]));
+        let root = document.root();
+        let txn = document.doc.transact();
+        assert_eq!(
+            root.get_attribute(&txn, "__type"),
+            Some(Out::Any(Any::from("root")))
+        );
+
+        // 段落是嵌入根节点的 XmlText，文本节点是 Y.Map 加上文本
+        let chunks = root.diff(&txn, YChange::identity);
+        let [paragraph] = chunks.as_slice() else {
+            panic!("根节点应只有一个段落");
+        };
+        let Out::YXmlText(paragraph) = &paragraph.insert else {
+            panic!("段落应为 XmlText");
+        };
+        assert_eq!(
+            paragraph.get_attribute(&txn, "__format"),
+            Some(Out::Any(Any::Number(Number::Int(2))))
+        );
+        let chunks = paragraph.diff(&txn, YChange::identity);
+        assert!(matches!(&chunks[0].insert, Out::YMap(_)));
+        assert_eq!(chunks[1].insert, Out::Any(Any::from("ab")));
+        assert_eq!(paragraph.len(&txn), 3);
+    }
+
+    #[test]
+    fn test_concurrent_lexical_edits_stay_valid() {
+        let base = crdt_state_from_content(&lexical(&["你好", "世界"]));
+
+        // 两个客户端并发编辑同一段落的文本，合并后仍是有效的 Lexical JSON
+        let edit_paragraph = |text: &'static str| {
+            let document = CrdtDocument::from_updates(&[&base]).unwrap();
+            let root = document.root();
+            let before = document.state_vector();
+            {
+                let mut txn = document.doc.transact_mut();
+                let Out::YXmlText(paragraph) = root.diff(&txn, YChange::identity).remove(0).insert
+                else {
+                    panic!("段落应为 XmlText");
+                };
+                paragraph.insert(&mut txn, 1, text);
+            }
+            let diff = document.doc.transact().encode_diff_v1(&before);
+            diff
+        };
+        let a = edit_paragraph("A");
+        let b = edit_paragraph("B");
+
+        let merged = merge_crdt_updates(&[base.clone(), b, a]).unwrap();
+        let content = parse(&crdt_content(&merged).unwrap());
+        let text = content["root"]["children"][0]["children"][0]["text"]
+            .as_str()
+            .unwrap();
+        assert!(text == "AB你好" || text == "BA你好");
+        assert_eq!(
+            content["root"]["children"][1],
+            parse(&lexical(&["世界"]))["root"]["children"][0]
+        );
+    }
+
+    #[test]
+    fn test_replace_lexical_keeps_concurrent_edit() {
+        let base = crdt_state_from_content(&lexical(&["一", "二", "三"]));
+        let document = CrdtDocument::from_updates(&[&base]).unwrap();
+        assert_eq!(
+            document.replace_content(&lexical(&["一", "二", "三"])),
+            None
+        );
+
+        // 服务器替换第三段，客户端同时编辑第一段
+        let replace = document
+            .replace_content(&lexical(&["一", "二", "3"]))
+            .unwrap();
+        let client = CrdtDocument::from_updates(&[&base]).unwrap();
+        let before = client.state_vector();
+        {
+            let root = client.root();
+            let mut txn = client.doc.transact_mut();
+            let Out::YXmlText(paragraph) = root.diff(&txn, YChange::identity).remove(0).insert
+            else {
+                panic!("段落应为 XmlText");
+            };
+            paragraph.insert(&mut txn, 2, "!");
+        }
+        let insert = client.doc.transact().encode_diff_v1(&before);
+
+        let merged = merge_crdt_updates(&[base, replace, insert]).unwrap();
+        assert_eq!(
+            parse(&crdt_content(&merged).unwrap()),
+            parse(&lexical(&["一!", "二", "3"]))
+        );
+    }
+
+    #[test]
+    fn test_plain_text_concurrent_edits_merge() {
+        let base = crdt_state_from_content("你好世界");
+        // 两个客户端基于同一状态并发编辑
+        let a = edit(&base, CRDT_TEXT_NAME, |text, txn| text.insert(txn, 0, "A"));
+        let b = edit(&base, CRDT_TEXT_NAME, |text, txn| text.insert(txn, 4, "B"));
+
+        let merged = merge_crdt_updates(&[base.clone(), b.clone(), a.clone()]).unwrap();
+        assert_eq!(crdt_content(&merged).unwrap(), "A你好世界B");
+        // 更新的顺序不影响结果
+        let merged = merge_crdt_updates(&[a, base, b]).unwrap();
+        assert_eq!(crdt_content(&merged).unwrap(), "A你好世界B");
+    }
+
+    #[test]
+    fn test_diff_contains_missing_updates() {
+        let base = crdt_state_from_content("abc");
+        let update = edit(&base, CRDT_TEXT_NAME, |text, txn| text.insert(txn, 3, "d"));
+        let document = CrdtDocument::from_updates(&[&base, &update]).unwrap();
+
+        // 只有基础状态的客户端拿到差异后与服务器一致
+        let client = CrdtDocument::from_updates(&[&base]).unwrap();
+        let diff = document.diff(&client.state_vector().encode_v1()).unwrap();
+        client.apply_update(&diff).unwrap();
+        assert_eq!(client.content(), "abcd");
+    }
+
+    #[test]
+    fn test_replace_text_keeps_concurrent_edit() {
+        let base = crdt_state_from_content("😀 一二三");
+        let document = CrdtDocument::from_updates(&[&base]).unwrap();
+        assert_eq!(document.replace_content("😀 一二三"), None);
+
+        // 服务器把“二”替换为“2”，客户端同时在开头插入
+        let replace = document.replace_content("😀 一2三").unwrap();
+        let insert = edit(&base, CRDT_TEXT_NAME, |text, txn| text.insert(txn, 0, ">"));
+
+        let merged = merge_crdt_updates(&[base, replace, insert]).unwrap();
+        assert_eq!(crdt_content(&merged).unwrap(), ">😀 一2三");
+    }
+
+    #[test]
+    fn test_invalid_update() {
+        assert!(matches!(
+            merge_crdt_updates(&[vec![0xff, 0xff, 0xff]]),
+            Err(AppError::ValidationError(_))
+        ));
+        assert!(CrdtDocument::from_content("").diff(&[0xff]).is_err());
+    }
+}
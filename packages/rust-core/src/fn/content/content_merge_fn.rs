@@ -0,0 +1,423 @@
+//! Content 合并纯函数
+//!
+//! Lexical JSON 的结构化三方合并：
+//! - 以基础版本为参照，分别对比本地版本（ours）和服务器当前版本（theirs）
+//! - 块级节点（`root.children`）按 diff3 对齐，只有一方修改的区域直接采用该方
+//! - 两方修改同一个块时逐层合并属性和子节点，仍然冲突时在文档中并列两个版本，
+//!   以 `<<<<<<<` / `=======` / `>>>>>>>` 段落标记
+
+use serde_json::{json, Map, Value};
+
+/// 冲突开始标记（其后是本地版本）
+pub const CONFLICT_OURS_MARKER: &str = "<<<<<<< 我的修改";
+
+/// 冲突分隔标记（其后是服务器版本）
+pub const CONFLICT_SEPARATOR: &str = "=======";
+
+/// 冲突结束标记
+pub const CONFLICT_THEIRS_MARKER: &str = ">>>>>>> 已保存的版本";
+
+/// 三方合并结果
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LexicalMerge {
+    /// 合并后的内容（有冲突时为标记了两个版本的冲突文档）
+    pub content: String,
+
+    /// 冲突数量
+    pub conflicts: usize,
+}
+
+/// 三方合并 Lexical JSON
+///
+/// `ours` 是本地基于 `base` 的修改，`theirs` 是服务器上基于 `base` 的修改。
+/// 任一内容不是带 `root` 的 Lexical JSON 时返回 None。
+pub fn merge_lexical(base: &str, ours: &str, theirs: &str) -> Option<LexicalMerge> {
+    let parse = |text: &str| {
+        serde_json::from_str::<Value>(text)
+            .ok()
+            .filter(|v| v.get("root").is_some_and(Value::is_object))
+    };
+    let (base, ours, theirs) = (parse(base)?, parse(ours)?, parse(theirs)?);
+
+    let mut conflicts = 0;
+    let root = merge_root(
+        &base["root"],
+        &ours["root"],
+        &theirs["root"],
+        &mut conflicts,
+    );
+
+    // root 之外的顶层字段按普通属性合并，冲突时采用本地版本
+    let (base, ours, theirs) = (
+        without(&base, "root"),
+        without(&ours, "root"),
+        without(&theirs, "root"),
+    );
+    let mut document = merge_value(&base, &ours, &theirs).unwrap_or(ours);
+    document["root"] = root;
+
+    Some(LexicalMerge {
+        content: serde_json::to_string(&document).ok()?,
+        conflicts,
+    })
+}
+
+/// 合并根节点：块级冲突在文档中标记，不会失败
+fn merge_root(base: &Value, ours: &Value, theirs: &Value, conflicts: &mut usize) -> Value {
+    let empty = Vec::new();
+    let children = |v: &Value| {
+        v.get("children")
+            .and_then(Value::as_array)
+            .unwrap_or(&empty)
+            .clone()
+    };
+
+    let merged_children = merge_list(
+        &children(base),
+        &children(ours),
+        &children(theirs),
+        Some(conflicts),
+    )
+    .unwrap_or_default();
+
+    let (base, ours_attrs, theirs) = (
+        without(base, "children"),
+        without(ours, "children"),
+        without(theirs, "children"),
+    );
+    let mut root = merge_value(&base, &ours_attrs, &theirs).unwrap_or(ours_attrs);
+    if let Some(map) = root.as_object_mut() {
+        map.insert("children".to_string(), Value::Array(merged_children));
+    }
+    root
+}
+
+/// 去掉对象的某个字段
+fn without(value: &Value, key: &str) -> Value {
+    let mut value = value.clone();
+    if let Some(map) = value.as_object_mut() {
+        map.remove(key);
+    }
+    value
+}
+
+/// 合并任意值：对象逐字段合并，`children` 数组按 diff3 合并，其余值两方都修改即冲突
+fn merge_value(base: &Value, ours: &Value, theirs: &Value) -> Option<Value> {
+    if ours == theirs || theirs == base {
+        return Some(ours.clone());
+    }
+    if ours == base {
+        return Some(theirs.clone());
+    }
+
+    let (Value::Object(ours_map), Value::Object(theirs_map)) = (ours, theirs) else {
+        return None;
+    };
+    // 节点类型不同时不能逐字段合并
+    if ours_map.get("type") != theirs_map.get("type") {
+        return None;
+    }
+    let empty = Map::new();
+    let base_map = base.as_object().unwrap_or(&empty);
+
+    let mut merged = Map::new();
+    let keys = ours_map
+        .keys()
+        .chain(theirs_map.keys().filter(|k| !ours_map.contains_key(*k)));
+    for key in keys {
+        let b = base_map.get(key).unwrap_or(&Value::Null);
+        let o = ours_map.get(key).unwrap_or(&Value::Null);
+        let t = theirs_map.get(key).unwrap_or(&Value::Null);
+        let value = match (key.as_str(), b, o, t) {
+            ("children", _, Value::Array(o_list), Value::Array(t_list)) => {
+                let empty = Vec::new();
+                let b_list = b.as_array().unwrap_or(&empty);
+                Value::Array(merge_list(b_list, o_list, t_list, None)?)
+            }
+            _ => merge_value(b, o, t)?,
+        };
+        // 一方删除的字段保持删除
+        if !value.is_null() || (ours_map.contains_key(key) && theirs_map.contains_key(key)) {
+            merged.insert(key.clone(), value);
+        }
+    }
+    Some(Value::Object(merged))
+}
+
+/// 按 diff3 合并节点列表
+///
+/// `conflicts` 为 Some 时（块级列表）冲突区域在结果中标记两个版本，
+/// 否则遇到冲突返回 None，由上层把整个块标记为冲突。
+fn merge_list(
+    base: &[Value],
+    ours: &[Value],
+    theirs: &[Value],
+    mut conflicts: Option<&mut usize>,
+) -> Option<Vec<Value>> {
+    let ours_match = match_indices(base, ours);
+    let theirs_match = match_indices(base, theirs);
+
+    let mut merged = Vec::new();
+    let (mut b, mut o, mut t) = (0, 0, 0);
+    loop {
+        // 下一个两方都未修改的基础节点
+        let stable =
+            (b..base.len()).find(|&i| ours_match[i].is_some() && theirs_match[i].is_some());
+        let (b_end, o_end, t_end) = match stable {
+            Some(i) => (i, ours_match[i].unwrap(), theirs_match[i].unwrap()),
+            None => (base.len(), ours.len(), theirs.len()),
+        };
+
+        let hunk = merge_hunk(&base[b..b_end], &ours[o..o_end], &theirs[t..t_end]);
+        match (hunk, conflicts.as_deref_mut()) {
+            (Some(values), _) => merged.extend(values),
+            (None, Some(count)) => {
+                *count += 1;
+                merged.push(marker_paragraph(CONFLICT_OURS_MARKER));
+                merged.extend_from_slice(&ours[o..o_end]);
+                merged.push(marker_paragraph(CONFLICT_SEPARATOR));
+                merged.extend_from_slice(&theirs[t..t_end]);
+                merged.push(marker_paragraph(CONFLICT_THEIRS_MARKER));
+            }
+            (None, None) => return None,
+        }
+
+        match stable {
+            Some(i) => {
+                merged.push(base[i].clone());
+                (b, o, t) = (i + 1, o_end + 1, t_end + 1);
+            }
+            None => break,
+        }
+    }
+    Some(merged)
+}
+
+/// 合并两个稳定节点之间的区域，两方都修改且无法逐个合并时返回 None
+fn merge_hunk(base: &[Value], ours: &[Value], theirs: &[Value]) -> Option<Vec<Value>> {
+    if ours == theirs || theirs == base {
+        return Some(ours.to_vec());
+    }
+    if ours == base {
+        return Some(theirs.to_vec());
+    }
+
+    // 两方修改了相同数量的节点：逐个合并（如两人修改同一段落的不同文本）
+    if !base.is_empty() && base.len() == ours.len() && base.len() == theirs.len() {
+        return base
+            .iter()
+            .zip(ours)
+            .zip(theirs)
+            .map(|((b, o), t)| merge_value(b, o, t))
+            .collect();
+    }
+
+    // 一方只删除了节点，另一方逐个修改：没有被删除节点上的修改即可合并
+    if base.len() == theirs.len() {
+        if let Some(values) = merge_deletions(base, ours, theirs) {
+            return Some(values);
+        }
+    }
+    if base.len() == ours.len() {
+        return merge_deletions(base, theirs, ours);
+    }
+    None
+}
+
+/// 合并“一方删除、一方逐个修改”的区域
+///
+/// `pruned` 必须是 `base` 的子序列，`edited` 与 `base` 一一对应；
+/// 被删除的节点在另一方未修改时删除，已修改则冲突。
+fn merge_deletions(base: &[Value], pruned: &[Value], edited: &[Value]) -> Option<Vec<Value>> {
+    let kept = match_indices(base, pruned);
+    if kept.iter().flatten().count() != pruned.len() {
+        return None;
+    }
+
+    let mut merged = Vec::new();
+    for (i, (b, e)) in base.iter().zip(edited).enumerate() {
+        match kept[i] {
+            Some(_) => merged.push(e.clone()),
+            None if e == b => {}
+            None => return None,
+        }
+    }
+    Some(merged)
+}
+
+/// 最长公共子序列对齐，返回每个基础节点在另一序列中的位置
+fn match_indices(base: &[Value], other: &[Value]) -> Vec<Option<usize>> {
+    let mut matched = vec![None; base.len()];
+
+    // 去掉相同的前缀和后缀，只对中间部分做 LCS
+    let prefix = base.iter().zip(other).take_while(|(a, b)| a == b).count();
+    let suffix = base[prefix..]
+        .iter()
+        .rev()
+        .zip(other[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+    for (i, slot) in matched.iter_mut().enumerate().take(prefix) {
+        *slot = Some(i);
+    }
+    for k in 0..suffix {
+        matched[base.len() - 1 - k] = Some(other.len() - 1 - k);
+    }
+
+    let a = &base[prefix..base.len() - suffix];
+    let b = &other[prefix..other.len() - suffix];
+    if a.is_empty() || b.is_empty() {
+        return matched;
+    }
+
+    // lengths[i][j] = a[i..] 与 b[j..] 的 LCS 长度
+    let mut lengths = vec![vec![0usize; b.len() + 1]; a.len() + 1];
+    for i in (0..a.len()).rev() {
+        for j in (0..b.len()).rev() {
+            lengths[i][j] = if a[i] == b[j] {
+                lengths[i + 1][j + 1] + 1
+            } else {
+                lengths[i + 1][j].max(lengths[i][j + 1])
+            };
+        }
+    }
+    let (mut i, mut j) = (0, 0);
+    while i < a.len() && j < b.len() {
+        if a[i] == b[j] {
+            matched[prefix + i] = Some(prefix + j);
+            i += 1;
+            j += 1;
+        } else if lengths[i + 1][j] >= lengths[i][j + 1] {
+            i += 1;
+        } else {
+            j += 1;
+        }
+    }
+    matched
+}
+
+/// 冲突标记段落
+fn marker_paragraph(text: &str) -> Value {
+    json!({
+        "children": [{
+            "detail": 0, "format": 0, "mode": "normal", "style": "",
+            "text": text, "type": "text", "version": 1
+        }],
+        "direction": "ltr", "format": "", "indent": 0,
+        "type": "paragraph", "version": 1
+    })
+}
+
+// ============================================================================
+// 测试
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn paragraph(text: &str) -> Value {
+        json!({ "type": "paragraph", "children": [{ "type": "text", "text": text }] })
+    }
+
+    fn doc(blocks: Vec<Value>) -> String {
+        json!({ "root": { "type": "root", "children": blocks } }).to_string()
+    }
+
+    fn texts(content: &str) -> Vec<String> {
+        let value: Value = serde_json::from_str(content).unwrap();
+        value["root"]["children"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|block| {
+                block["children"]
+                    .as_array()
+                    .unwrap()
+                    .iter()
+                    .map(|c| c["text"].as_str().unwrap_or(""))
+                    .collect::<Vec<_>>()
+                    .join("|")
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_merge_different_blocks() {
+        let base = doc(vec![paragraph("一"), paragraph("二"), paragraph("三")]);
+        let ours = doc(vec![
+            paragraph("一（改）"),
+            paragraph("二"),
+            paragraph("三"),
+        ]);
+        let theirs = doc(vec![
+            paragraph("一"),
+            paragraph("二"),
+            paragraph("三"),
+            paragraph("四"),
+        ]);
+
+        let merged = merge_lexical(&base, &ours, &theirs).unwrap();
+        assert_eq!(merged.conflicts, 0);
+        assert_eq!(texts(&merged.content), vec!["一（改）", "二", "三", "四"]);
+    }
+
+    #[test]
+    fn test_merge_same_block_different_children() {
+        let block = |a: &str, b: &str| {
+            json!({ "type": "paragraph", "children": [
+                { "type": "text", "text": a },
+                { "type": "text", "text": b, "format": 1 }
+            ]})
+        };
+        let base = doc(vec![block("前", "后")]);
+        let ours = doc(vec![block("前（我）", "后")]);
+        let theirs = doc(vec![block("前", "后（他）")]);
+
+        let merged = merge_lexical(&base, &ours, &theirs).unwrap();
+        assert_eq!(merged.conflicts, 0);
+        assert_eq!(texts(&merged.content), vec!["前（我）|后（他）"]);
+    }
+
+    #[test]
+    fn test_merge_deleted_block() {
+        let base = doc(vec![paragraph("一"), paragraph("二"), paragraph("三")]);
+        let ours = doc(vec![paragraph("一"), paragraph("三")]);
+        let theirs = doc(vec![
+            paragraph("一"),
+            paragraph("二"),
+            paragraph("三（改）"),
+        ]);
+
+        let merged = merge_lexical(&base, &ours, &theirs).unwrap();
+        assert_eq!(merged.conflicts, 0);
+        assert_eq!(texts(&merged.content), vec!["一", "三（改）"]);
+    }
+
+    #[test]
+    fn test_merge_conflict_marks_both_versions() {
+        let base = doc(vec![paragraph("一"), paragraph("二")]);
+        let ours = doc(vec![paragraph("一"), paragraph("我的二")]);
+        let theirs = doc(vec![paragraph("一"), paragraph("他的二")]);
+
+        let merged = merge_lexical(&base, &ours, &theirs).unwrap();
+        assert_eq!(merged.conflicts, 1);
+        assert_eq!(
+            texts(&merged.content),
+            vec![
+                "一",
+                CONFLICT_OURS_MARKER,
+                "我的二",
+                CONFLICT_SEPARATOR,
+                "他的二",
+                CONFLICT_THEIRS_MARKER
+            ]
+        );
+    }
+
+    #[test]
+    fn test_merge_rejects_non_lexical() {
+        assert!(merge_lexical("纯文本", "纯文本", "纯文本").is_none());
+        assert!(merge_lexical("{}", "{}", "{}").is_none());
+    }
+}
@@ -0,0 +1,7 @@
+//! Content 纯函数模块
+
+pub mod content_crdt_fn;
+pub mod content_merge_fn;
+
+pub use content_crdt_fn::*;
+pub use content_merge_fn::*;
@@ -0,0 +1,354 @@
+//! Diary 纯函数
+//!
+//! 日记的日期文件夹结构、模板渲染和日历聚合。
+//!
+//! 文件夹结构与桌面端一致：
+//! `Diary > year-2026-Horse > month-01-January > day-01-Thursday > 日记`
+
+use std::collections::{BTreeMap, HashMap};
+
+use chrono::{DateTime, Datelike, NaiveDate};
+use serde_json::json;
+
+use crate::types::diary::{DiaryCalendarDay, DiaryOnThisDayEntry};
+use crate::types::node::node_entity;
+
+/// 默认日记根文件夹名称
+pub const DEFAULT_DIARY_ROOT_FOLDER: &str = "Diary";
+
+/// 日期格式
+const DATE_FORMAT: &str = "%Y-%m-%d";
+
+const ZODIAC_ANIMALS: [&str; 12] = [
+    "Rat", "Ox", "Tiger", "Rabbit", "Dragon", "Snake", "Horse", "Goat", "Monkey", "Rooster", "Dog",
+    "Pig",
+];
+
+const MONTH_NAMES: [&str; 12] = [
+    "January",
+    "February",
+    "March",
+    "April",
+    "May",
+    "June",
+    "July",
+    "August",
+    "September",
+    "October",
+    "November",
+    "December",
+];
+
+const WEEKDAY_NAMES: [&str; 7] = [
+    "Sunday",
+    "Monday",
+    "Tuesday",
+    "Wednesday",
+    "Thursday",
+    "Friday",
+    "Saturday",
+];
+
+/// 某一天的日记文件夹名称
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DiaryFolders {
+    /// 年文件夹（如 `year-2026-Horse`）
+    pub year: String,
+    /// 月文件夹（如 `month-01-January`）
+    pub month: String,
+    /// 日文件夹（如 `day-01-Thursday`）
+    pub day: String,
+}
+
+// ============================================================================
+// 纯函数（数据转换）
+// ============================================================================
+
+/// 解析 `YYYY-MM-DD` 日期
+pub fn parse_diary_date(date: &str) -> Result<NaiveDate, String> {
+    NaiveDate::parse_from_str(date.trim(), DATE_FORMAT).map_err(|_| format!("无效的日期: {}", date))
+}
+
+/// 格式化为 `YYYY-MM-DD`
+pub fn format_diary_date(date: NaiveDate) -> String {
+    date.format(DATE_FORMAT).to_string()
+}
+
+/// 生成某一天的年/月/日文件夹名称
+pub fn diary_folders(date: NaiveDate) -> DiaryFolders {
+    let zodiac = ZODIAC_ANIMALS[(date.year() - 1900).rem_euclid(12) as usize];
+    DiaryFolders {
+        year: format!("year-{}-{}", date.year(), zodiac),
+        month: format!(
+            "month-{:02}-{}",
+            date.month(),
+            MONTH_NAMES[date.month0() as usize]
+        ),
+        day: format!("day-{:02}-{}", date.day(), weekday_name(date)),
+    }
+}
+
+/// 新建日记的标题
+pub fn diary_entry_title(date: NaiveDate) -> String {
+    format!("diary-{}.grain", format_diary_date(date))
+}
+
+/// 默认日记模板（Lexical JSON）
+///
+/// 与桌面端 `generateDiaryContent` 结构一致：标签行、空行、日期标题、空行
+pub fn default_diary_template() -> String {
+    let tag = |name: &str| {
+        json!({
+            "detail": 2, "format": 0, "mode": "segmented", "style": "",
+            "tagName": name, "text": format!("#[{}]", name), "type": "tag", "version": 1
+        })
+    };
+    let text = |text: &str| {
+        json!({
+            "detail": 0, "format": 0, "mode": "normal", "style": "",
+            "text": text, "type": "text", "version": 1
+        })
+    };
+    let paragraph = |children: Vec<serde_json::Value>| {
+        json!({
+            "children": children, "direction": "ltr", "format": "", "indent": 0,
+            "type": "paragraph", "version": 1
+        })
+    };
+
+    json!({
+        "root": {
+            "children": [
+                paragraph(vec![tag("diary"), text(" "), tag("notes")]),
+                paragraph(vec![]),
+                {
+                    "children": [text("{{date}} {{weekday}}")],
+                    "direction": "ltr", "format": "", "indent": 0,
+                    "tag": "h2", "type": "heading", "version": 1
+                },
+                paragraph(vec![]),
+            ],
+            "direction": "ltr", "format": "", "indent": 0, "type": "root", "version": 1
+        }
+    })
+    .to_string()
+}
+
+/// 渲染日记模板中的日期占位符
+pub fn render_diary_template(template: &str, date: NaiveDate) -> String {
+    let zodiac = ZODIAC_ANIMALS[(date.year() - 1900).rem_euclid(12) as usize];
+    [
+        ("{{date}}", format_diary_date(date)),
+        ("{{year}}", date.year().to_string()),
+        ("{{month}}", format!("{:02}", date.month())),
+        ("{{day}}", format!("{:02}", date.day())),
+        ("{{weekday}}", weekday_name(date).to_string()),
+        (
+            "{{monthName}}",
+            MONTH_NAMES[date.month0() as usize].to_string(),
+        ),
+        ("{{zodiac}}", zodiac.to_string()),
+    ]
+    .iter()
+    .fold(template.to_string(), |acc, (placeholder, value)| {
+        acc.replace(placeholder, value)
+    })
+}
+
+/// 确定日记节点所属的日期
+///
+/// 位于 `year-YYYY-*/month-MM-*/day-DD-*` 文件夹中时使用文件夹日期，
+/// 否则使用节点的创建日期（UTC）。
+pub fn diary_entry_date(
+    node: &node_entity::Model,
+    nodes: &HashMap<&str, &node_entity::Model>,
+) -> NaiveDate {
+    let parent =
+        |n: &node_entity::Model| n.parent_id.as_deref().and_then(|id| nodes.get(id).copied());
+
+    parent(node)
+        .and_then(|day| {
+            let month = parent(day)?;
+            let year = parent(month)?;
+            NaiveDate::from_ymd_opt(
+                folder_number(&year.title, "year-")? as i32,
+                folder_number(&month.title, "month-")?,
+                folder_number(&day.title, "day-")?,
+            )
+        })
+        .unwrap_or_else(|| {
+            DateTime::from_timestamp_millis(node.created_at)
+                .map(|dt| dt.date_naive())
+                .unwrap_or_default()
+        })
+}
+
+/// 按日期聚合日记，只返回 `[from, to]` 范围内有日记的日期（升序）
+///
+/// `entries` 为（日期，节点）列表，`word_counts` 为节点 ID 到字数的映射
+pub fn build_diary_calendar(
+    mut entries: Vec<(NaiveDate, node_entity::Model)>,
+    word_counts: &HashMap<String, u64>,
+    from: NaiveDate,
+    to: NaiveDate,
+) -> Vec<DiaryCalendarDay> {
+    entries.sort_by_key(|(_, node)| node.created_at);
+
+    let mut days: BTreeMap<NaiveDate, DiaryCalendarDay> = BTreeMap::new();
+    for (date, node) in entries {
+        if date < from || date > to {
+            continue;
+        }
+        let day = days.entry(date).or_insert_with(|| DiaryCalendarDay {
+            date: format_diary_date(date),
+            node_ids: Vec::new(),
+            word_count: 0,
+        });
+        day.word_count += word_counts.get(&node.id).copied().unwrap_or(0);
+        day.node_ids.push(node.id);
+    }
+    days.into_values().collect()
+}
+
+/// 查找往年同月同日的日记（最近的年份在前）
+pub fn diary_on_this_day(
+    entries: Vec<(NaiveDate, node_entity::Model)>,
+    date: NaiveDate,
+) -> Vec<DiaryOnThisDayEntry> {
+    let mut matched: Vec<(NaiveDate, node_entity::Model)> = entries
+        .into_iter()
+        .filter(|(d, _)| {
+            d.month() == date.month() && d.day() == date.day() && d.year() < date.year()
+        })
+        .collect();
+    matched.sort_by(|(a, x), (b, y)| b.cmp(a).then(x.created_at.cmp(&y.created_at)));
+
+    matched
+        .into_iter()
+        .map(|(d, node)| DiaryOnThisDayEntry {
+            date: format_diary_date(d),
+            years_ago: date.year() - d.year(),
+            node: node.into(),
+        })
+        .collect()
+}
+
+fn weekday_name(date: NaiveDate) -> &'static str {
+    WEEKDAY_NAMES[date.weekday().num_days_from_sunday() as usize]
+}
+
+/// 解析文件夹名称中的数字（`month-01-January` -> 1）
+fn folder_number(title: &str, prefix: &str) -> Option<u32> {
+    title.strip_prefix(prefix)?.split('-').next()?.parse().ok()
+}
+
+// ============================================================================
+// 测试
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::node::NodeType;
+
+    fn node(id: &str, parent_id: Option<&str>, title: &str, created_at: i64) -> node_entity::Model {
+        node_entity::Model {
+            id: id.to_string(),
+            workspace_id: "ws-1".to_string(),
+            parent_id: parent_id.map(str::to_string),
+            title: title.to_string(),
+            node_type: NodeType::Diary,
+            is_collapsed: false,
+            sort_order: 0,
+            tags: None,
+            created_at,
+            updated_at: created_at,
+        }
+    }
+
+    fn date(s: &str) -> NaiveDate {
+        parse_diary_date(s).unwrap()
+    }
+
+    #[test]
+    fn test_diary_folders() {
+        let folders = diary_folders(date("2026-01-01"));
+        assert_eq!(folders.year, "year-2026-Horse");
+        assert_eq!(folders.month, "month-01-January");
+        assert_eq!(folders.day, "day-01-Thursday");
+        assert_eq!(diary_folders(date("2024-12-16")).year, "year-2024-Dragon");
+        assert!(parse_diary_date("2026-13-01").is_err());
+    }
+
+    #[test]
+    fn test_render_diary_template() {
+        let rendered = render_diary_template(
+            "{{date}} {{weekday}} {{monthName}} {{zodiac}}",
+            date("2026-01-01"),
+        );
+        assert_eq!(rendered, "2026-01-01 Thursday January Horse");
+
+        let content = render_diary_template(&default_diary_template(), date("2026-01-01"));
+        let tags = crate::r#fn::tag::extract_content_tags(&content).unwrap();
+        assert_eq!(tags, vec!["diary", "notes"]);
+        assert!(content.contains("2026-01-01 Thursday"));
+    }
+
+    #[test]
+    fn test_diary_entry_date() {
+        let year = node("y", Some("root"), "year-2025-Snake", 0);
+        let month = node("m", Some("y"), "month-03-March", 0);
+        let day = node("d", Some("m"), "day-07-Friday", 0);
+        let entry = node("e", Some("d"), "diary", 0);
+        // 2026-01-01 12:00 UTC
+        let loose = node("loose", None, "diary", 1767268800000);
+
+        let nodes: HashMap<&str, &node_entity::Model> = [&year, &month, &day, &entry, &loose]
+            .into_iter()
+            .map(|n| (n.id.as_str(), n))
+            .collect();
+
+        assert_eq!(diary_entry_date(&entry, &nodes), date("2025-03-07"));
+        assert_eq!(diary_entry_date(&loose, &nodes), date("2026-01-01"));
+    }
+
+    #[test]
+    fn test_build_diary_calendar() {
+        let entries = vec![
+            (date("2026-01-02"), node("b", None, "b", 2)),
+            (date("2026-01-02"), node("a", None, "a", 1)),
+            (date("2026-01-05"), node("c", None, "c", 3)),
+            (date("2026-02-01"), node("d", None, "d", 4)),
+        ];
+        let word_counts = HashMap::from([("a".to_string(), 10), ("b".to_string(), 5)]);
+
+        let days = build_diary_calendar(
+            entries,
+            &word_counts,
+            date("2026-01-01"),
+            date("2026-01-31"),
+        );
+        assert_eq!(days.len(), 2);
+        assert_eq!(days[0].date, "2026-01-02");
+        assert_eq!(days[0].node_ids, vec!["a", "b"]);
+        assert_eq!(days[0].word_count, 15);
+        assert_eq!(days[1].date, "2026-01-05");
+        assert_eq!(days[1].word_count, 0);
+    }
+
+    #[test]
+    fn test_diary_on_this_day() {
+        let entries = vec![
+            (date("2024-03-07"), node("a", None, "a", 1)),
+            (date("2025-03-07"), node("b", None, "b", 2)),
+            (date("2026-03-07"), node("today", None, "today", 3)),
+            (date("2025-03-08"), node("c", None, "c", 4)),
+        ];
+
+        let result = diary_on_this_day(entries, date("2026-03-07"));
+        let ids: Vec<&str> = result.iter().map(|e| e.node.id.as_str()).collect();
+        assert_eq!(ids, vec!["b", "a"]);
+        assert_eq!(result[0].years_ago, 1);
+        assert_eq!(result[1].years_ago, 2);
+    }
+}
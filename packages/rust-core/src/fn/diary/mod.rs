@@ -0,0 +1,5 @@
+//! Diary 纯函数模块
+
+pub mod diary_fn;
+
+pub use diary_fn::*;
@@ -0,0 +1,371 @@
+//! Graph 纯函数
+//!
+//! 从节点和链接构建节点知识图谱
+
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
+
+use crate::r#fn::tag::tag_matches;
+use crate::types::graph::{
+    NodeGraphData, NodeGraphEdge, NodeGraphEdgeKind, NodeGraphNode, NodeGraphQuery,
+};
+use crate::types::link::link_entity;
+use crate::types::node::node_entity;
+
+/// 焦点节点的默认深度
+const DEFAULT_FOCUS_DEPTH: u32 = 1;
+
+/// 向上查找祖先时的最大层数（防止父子环）
+const MAX_ANCESTOR_DEPTH: usize = 1024;
+
+// ============================================================================
+// 纯函数（数据转换）
+// ============================================================================
+
+/// 构建节点知识图谱
+///
+/// 1. 按文件夹、节点类型、标签（含子标签）过滤节点
+/// 2. 在剩余节点之间生成引用、共同标签、父子关系三类边
+/// 3. 指定焦点节点时，只保留距离焦点不超过 depth 的部分
+/// 4. 根据最终的边计算每个节点的度数
+pub fn build_node_graph(
+    nodes: Vec<node_entity::Model>,
+    links: Vec<link_entity::Model>,
+    query: &NodeGraphQuery,
+) -> NodeGraphData {
+    let parents: HashMap<String, Option<String>> = nodes
+        .iter()
+        .map(|n| (n.id.clone(), n.parent_id.clone()))
+        .collect();
+
+    let candidates: Vec<(node_entity::Model, Vec<String>)> = nodes
+        .into_iter()
+        .map(|n| {
+            let tags = parse_tags(n.tags.as_deref());
+            (n, tags)
+        })
+        .filter(|(n, tags)| {
+            let in_folder = query
+                .folder_id
+                .as_deref()
+                .is_none_or(|folder_id| is_within(&parents, &n.id, folder_id));
+            let type_ok = query
+                .node_types
+                .as_ref()
+                .is_none_or(|types| types.contains(&n.node_type));
+            let tag_ok = query
+                .tag
+                .as_deref()
+                .is_none_or(|query_tag| tags.iter().any(|tag| tag_matches(tag, query_tag)));
+            in_folder && type_ok && tag_ok
+        })
+        .collect();
+
+    let ids: HashSet<&str> = candidates.iter().map(|(n, _)| n.id.as_str()).collect();
+    let kind_enabled = |kind: NodeGraphEdgeKind| {
+        query
+            .edge_kinds
+            .as_ref()
+            .is_none_or(|kinds| kinds.contains(&kind))
+    };
+
+    let mut edges = Vec::new();
+
+    // 引用边
+    if kind_enabled(NodeGraphEdgeKind::Link) {
+        for link in &links {
+            if ids.contains(link.source_id.as_str()) && ids.contains(link.target_id.as_str()) {
+                edges.push(NodeGraphEdge {
+                    source: link.source_id.clone(),
+                    target: link.target_id.clone(),
+                    kind: NodeGraphEdgeKind::Link,
+                    weight: 1,
+                });
+            }
+        }
+    }
+
+    // 父子关系边
+    if kind_enabled(NodeGraphEdgeKind::ParentChild) {
+        for (node, _) in &candidates {
+            if let Some(parent_id) = node.parent_id.as_deref().filter(|p| ids.contains(p)) {
+                edges.push(NodeGraphEdge {
+                    source: parent_id.to_string(),
+                    target: node.id.clone(),
+                    kind: NodeGraphEdgeKind::ParentChild,
+                    weight: 1,
+                });
+            }
+        }
+    }
+
+    // 共同标签边（权重为共有的标签数）
+    if kind_enabled(NodeGraphEdgeKind::SharedTag) {
+        let mut by_tag: HashMap<&str, Vec<&str>> = HashMap::new();
+        for (node, tags) in &candidates {
+            for tag in tags {
+                by_tag
+                    .entry(tag.as_str())
+                    .or_default()
+                    .push(node.id.as_str());
+            }
+        }
+
+        let mut weights: BTreeMap<(&str, &str), i32> = BTreeMap::new();
+        for members in by_tag.values() {
+            for i in 0..members.len() {
+                for j in (i + 1)..members.len() {
+                    let pair = if members[i] < members[j] {
+                        (members[i], members[j])
+                    } else {
+                        (members[j], members[i])
+                    };
+                    *weights.entry(pair).or_insert(0) += 1;
+                }
+            }
+        }
+
+        edges.extend(
+            weights
+                .into_iter()
+                .map(|((source, target), weight)| NodeGraphEdge {
+                    source: source.to_string(),
+                    target: target.to_string(),
+                    kind: NodeGraphEdgeKind::SharedTag,
+                    weight,
+                }),
+        );
+    }
+
+    // 焦点过滤（无向广度优先）
+    let reachable = query
+        .focus_id
+        .as_deref()
+        .map(|focus_id| reachable_from(focus_id, &ids, &edges, query.depth));
+
+    let (candidates, mut edges) = match reachable {
+        Some(reachable) => (
+            candidates
+                .into_iter()
+                .filter(|(n, _)| reachable.contains(&n.id))
+                .collect::<Vec<_>>(),
+            edges
+                .into_iter()
+                .filter(|e| reachable.contains(&e.source) && reachable.contains(&e.target))
+                .collect::<Vec<_>>(),
+        ),
+        None => (candidates, edges),
+    };
+    edges.sort_by(|a, b| (a.kind, &a.source, &a.target).cmp(&(b.kind, &b.source, &b.target)));
+
+    // 度数
+    let mut degrees: HashMap<&str, i32> = HashMap::new();
+    for edge in &edges {
+        *degrees.entry(edge.source.as_str()).or_insert(0) += 1;
+        *degrees.entry(edge.target.as_str()).or_insert(0) += 1;
+    }
+
+    let nodes = candidates
+        .into_iter()
+        .map(|(node, tags)| NodeGraphNode {
+            degree: degrees.get(node.id.as_str()).copied().unwrap_or(0),
+            id: node.id,
+            title: node.title,
+            node_type: node.node_type,
+            tags,
+        })
+        .collect();
+
+    NodeGraphData { nodes, edges }
+}
+
+/// 解析节点 tags JSON 字段
+fn parse_tags(tags_json: Option<&str>) -> Vec<String> {
+    tags_json
+        .and_then(|t| serde_json::from_str::<Vec<String>>(t).ok())
+        .unwrap_or_default()
+}
+
+/// 判断节点是否为文件夹本身或其后代
+fn is_within(parents: &HashMap<String, Option<String>>, node_id: &str, folder_id: &str) -> bool {
+    let mut current = Some(node_id);
+    for _ in 0..MAX_ANCESTOR_DEPTH {
+        match current {
+            Some(id) if id == folder_id => return true,
+            Some(id) => current = parents.get(id).and_then(|p| p.as_deref()),
+            None => return false,
+        }
+    }
+    false
+}
+
+/// 从焦点节点出发，沿边（忽略方向）查找 depth 步内可达的节点
+fn reachable_from(
+    focus_id: &str,
+    ids: &HashSet<&str>,
+    edges: &[NodeGraphEdge],
+    depth: Option<u32>,
+) -> HashSet<String> {
+    let mut reachable = HashSet::new();
+    if !ids.contains(focus_id) {
+        return reachable;
+    }
+
+    let mut adjacency: HashMap<&str, Vec<&str>> = HashMap::new();
+    for edge in edges {
+        adjacency
+            .entry(edge.source.as_str())
+            .or_default()
+            .push(edge.target.as_str());
+        adjacency
+            .entry(edge.target.as_str())
+            .or_default()
+            .push(edge.source.as_str());
+    }
+
+    let max_depth = depth.unwrap_or(DEFAULT_FOCUS_DEPTH);
+    let mut queue = VecDeque::from([(focus_id, 0u32)]);
+    reachable.insert(focus_id.to_string());
+    while let Some((id, d)) = queue.pop_front() {
+        if d >= max_depth {
+            continue;
+        }
+        for next in adjacency.get(id).into_iter().flatten() {
+            if reachable.insert(next.to_string()) {
+                queue.push_back((next, d + 1));
+            }
+        }
+    }
+    reachable
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::node::NodeType;
+
+    fn node(
+        id: &str,
+        parent: Option<&str>,
+        node_type: NodeType,
+        tags: &[&str],
+    ) -> node_entity::Model {
+        node_entity::Model {
+            id: id.to_string(),
+            workspace_id: "ws".to_string(),
+            parent_id: parent.map(str::to_string),
+            title: id.to_uppercase(),
+            node_type,
+            is_collapsed: false,
+            sort_order: 0,
+            tags: Some(serde_json::to_string(tags).unwrap()),
+            created_at: 0,
+            updated_at: 0,
+        }
+    }
+
+    fn link(source: &str, target: &str) -> link_entity::Model {
+        link_entity::Model {
+            id: format!("{}:{}", source, target),
+            source_id: source.to_string(),
+            target_id: target.to_string(),
+            target_title: target.to_string(),
+            created_at: 0,
+        }
+    }
+
+    /// folder
+    /// ├── a [rust, web]  --@--> b
+    /// ├── b [rust, web]
+    /// └── c (diary)      --@--> d
+    /// d [rust]（根级）
+    fn sample() -> (Vec<node_entity::Model>, Vec<link_entity::Model>) {
+        (
+            vec![
+                node("folder", None, NodeType::Folder, &[]),
+                node("a", Some("folder"), NodeType::File, &["rust", "web"]),
+                node("b", Some("folder"), NodeType::File, &["rust", "web"]),
+                node("c", Some("folder"), NodeType::Diary, &[]),
+                node("d", None, NodeType::File, &["rust"]),
+            ],
+            vec![link("a", "b"), link("c", "d")],
+        )
+    }
+
+    fn find<'a>(graph: &'a NodeGraphData, id: &str) -> &'a NodeGraphNode {
+        graph.nodes.iter().find(|n| n.id == id).unwrap()
+    }
+
+    #[test]
+    fn test_build_node_graph_all_edges() {
+        let (nodes, links) = sample();
+        let graph = build_node_graph(nodes, links, &NodeGraphQuery::default());
+
+        assert_eq!(graph.nodes.len(), 5);
+        let count = |kind| graph.edges.iter().filter(|e| e.kind == kind).count();
+        assert_eq!(count(NodeGraphEdgeKind::Link), 2);
+        assert_eq!(count(NodeGraphEdgeKind::ParentChild), 3);
+        // a-b 共享 2 个标签，a-d、b-d 共享 1 个
+        assert_eq!(count(NodeGraphEdgeKind::SharedTag), 3);
+        let ab = graph
+            .edges
+            .iter()
+            .find(|e| e.kind == NodeGraphEdgeKind::SharedTag && e.source == "a" && e.target == "b")
+            .unwrap();
+        assert_eq!(ab.weight, 2);
+
+        // a: link + parent + 2 shared
+        assert_eq!(find(&graph, "a").degree, 4);
+        assert_eq!(find(&graph, "folder").degree, 3);
+    }
+
+    #[test]
+    fn test_build_node_graph_filters() {
+        let (nodes, links) = sample();
+        let query = NodeGraphQuery {
+            folder_id: Some("folder".to_string()),
+            node_types: Some(vec![NodeType::File, NodeType::Diary]),
+            ..Default::default()
+        };
+        let graph = build_node_graph(nodes, links, &query);
+        let ids: Vec<&str> = graph.nodes.iter().map(|n| n.id.as_str()).collect();
+        assert_eq!(ids, vec!["a", "b", "c"]);
+        // c -> d 的引用被过滤（d 不在文件夹内）
+        assert_eq!(find(&graph, "c").degree, 0);
+
+        let (nodes, links) = sample();
+        let query = NodeGraphQuery {
+            tag: Some("web".to_string()),
+            edge_kinds: Some(vec![NodeGraphEdgeKind::Link]),
+            ..Default::default()
+        };
+        let graph = build_node_graph(nodes, links, &query);
+        assert_eq!(graph.nodes.len(), 2);
+        assert_eq!(graph.edges.len(), 1);
+        assert_eq!(graph.edges[0].kind, NodeGraphEdgeKind::Link);
+    }
+
+    #[test]
+    fn test_build_node_graph_focus_depth() {
+        let (nodes, links) = sample();
+        let query = NodeGraphQuery {
+            focus_id: Some("c".to_string()),
+            edge_kinds: Some(vec![
+                NodeGraphEdgeKind::Link,
+                NodeGraphEdgeKind::ParentChild,
+            ]),
+            ..Default::default()
+        };
+        let graph = build_node_graph(nodes, links, &query);
+        let mut ids: Vec<&str> = graph.nodes.iter().map(|n| n.id.as_str()).collect();
+        ids.sort();
+        assert_eq!(ids, vec!["c", "d", "folder"]);
+
+        let (nodes, links) = sample();
+        let query = NodeGraphQuery {
+            depth: Some(2),
+            ..query
+        };
+        let graph = build_node_graph(nodes, links, &query);
+        assert_eq!(graph.nodes.len(), 5);
+    }
+}
@@ -0,0 +1,5 @@
+//! Graph 纯函数模块
+
+pub mod graph_fn;
+
+pub use graph_fn::*;
@@ -0,0 +1,937 @@
+//! Ledger 纯函数
+//!
+//! 解析记账（ledger）节点内容中的复式记账分录，校验借贷平衡，并计算余额和报表。
+//!
+//! 支持两种账本格式：
+//!
+//! 纯文本（Lexical 内容按段落/列表项逐行提取后同样适用）：
+//!
+//! ```text
+//! 2026-01-05 * 超市购物
+//!     Expenses:Food        42.50 CNY
+//!     Assets:Bank         -42.50 CNY
+//!
+//! 2026-01-06 ! 工资
+//!     Assets:Bank        8000 CNY
+//!     Income:Salary              ; 省略金额时按平衡推算
+//! ```
+//!
+//! - 以日期（`YYYY-MM-DD` 或 `YYYY/MM/DD`）开头的行开始一笔分录，`*` 已确认、`!` 待确认
+//! - 随后首个词包含 `:` 的行为过账：`账户 金额 [币种]` 或 `账户 [币种] 金额`
+//! - 空行或其他文本结束分录；`;` 之后为注释
+//!
+//! 结构化 JSON：顶层为分录数组，或 `{"entries": [...]}`，每笔分录为
+//! `{"date", "description", "pending", "postings": [{"account", "amount", "commodity"}]}`。
+
+use std::collections::{BTreeMap, HashSet};
+
+use chrono::NaiveDate;
+use serde::Deserialize;
+use serde_json::Value;
+
+use crate::types::ledger::{
+    LedgerAmount, LedgerBalance, LedgerDiagnostic, LedgerDiagnosticSeverity, LedgerEntry,
+    LedgerGroupBy, LedgerPosting, LedgerReportQuery, LedgerReportRow,
+};
+
+/// 账户层级分隔符
+pub const LEDGER_ACCOUNT_SEPARATOR: char = ':';
+
+/// 分录日期格式
+const DATE_FORMAT: &str = "%Y-%m-%d";
+
+/// 账本解析结果
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ParsedLedger {
+    /// 所有分录（包含有错误的分录）
+    pub entries: Vec<LedgerEntry>,
+    /// 诊断信息
+    pub diagnostics: Vec<LedgerDiagnostic>,
+}
+
+impl ParsedLedger {
+    /// 没有错误的分录（计入余额和报表）
+    pub fn valid_entries(&self) -> impl Iterator<Item = &LedgerEntry> {
+        let invalid: HashSet<usize> = self
+            .diagnostics
+            .iter()
+            .filter(|d| d.severity == LedgerDiagnosticSeverity::Error)
+            .filter_map(|d| d.entry_index)
+            .collect();
+        self.entries
+            .iter()
+            .filter(move |entry| !invalid.contains(&entry.index))
+    }
+}
+
+/// 日期范围（两端包含，未指定的一端不限）
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct LedgerPeriod {
+    /// 起始日期
+    pub from: Option<NaiveDate>,
+    /// 结束日期
+    pub to: Option<NaiveDate>,
+}
+
+impl LedgerPeriod {
+    /// 分录日期（`YYYY-MM-DD`）是否在范围内
+    pub fn contains(&self, date: &str) -> bool {
+        let Ok(date) = NaiveDate::parse_from_str(date, DATE_FORMAT) else {
+            return false;
+        };
+        self.from.is_none_or(|from| date >= from) && self.to.is_none_or(|to| date <= to)
+    }
+}
+
+// ============================================================================
+// 解析
+// ============================================================================
+
+/// 解析前的分录
+struct RawEntry {
+    index: usize,
+    line: Option<usize>,
+    date: Option<NaiveDate>,
+    date_text: String,
+    description: String,
+    pending: bool,
+    postings: Vec<RawPosting>,
+    errors: Vec<(Option<usize>, String)>,
+    /// 有过账无法解析（不再检查过账数量和平衡）
+    incomplete: bool,
+}
+
+/// 解析前的过账（金额可省略）
+struct RawPosting {
+    account: String,
+    amount: Option<LedgerAmount>,
+    commodity: String,
+}
+
+/// 结构化 JSON 中的分录
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct JsonEntry {
+    date: String,
+    #[serde(default)]
+    description: String,
+    #[serde(default)]
+    pending: bool,
+    #[serde(default)]
+    postings: Vec<JsonPosting>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct JsonPosting {
+    account: String,
+    amount: Option<LedgerAmount>,
+    #[serde(default)]
+    commodity: String,
+}
+
+/// 解析账本内容
+///
+/// Lexical JSON 按块逐行提取文本后按纯文本语法解析；
+/// 顶层为数组或包含 `entries` 的 JSON 按结构化格式解析；其他内容按纯文本解析。
+pub fn parse_ledger(content: &str) -> ParsedLedger {
+    match serde_json::from_str::<Value>(content) {
+        Ok(Value::Object(map)) if map.contains_key("root") => {
+            let mut lines = Vec::new();
+            let mut current = String::new();
+            collect_lexical_lines(&map["root"], &mut current, &mut lines);
+            if !current.is_empty() {
+                lines.push(current);
+            }
+            parse_ledger_text(lines.iter().map(String::as_str))
+        }
+        Ok(Value::Object(mut map)) if map.contains_key("entries") => {
+            parse_ledger_json(map.remove("entries").unwrap_or_default())
+        }
+        Ok(value @ Value::Array(_)) => parse_ledger_json(value),
+        _ => parse_ledger_text(content.lines()),
+    }
+}
+
+/// 按块提取 Lexical 内容的文本行（空段落保留为空行）
+fn collect_lexical_lines(value: &Value, current: &mut String, lines: &mut Vec<String>) {
+    match value {
+        Value::Object(map) => {
+            if map.get("type").and_then(Value::as_str) == Some("linebreak") {
+                lines.push(std::mem::take(current));
+                return;
+            }
+            if let Some(text) = map.get("text").and_then(Value::as_str) {
+                current.push_str(text);
+            }
+            let Some(Value::Array(children)) = map.get("children") else {
+                return;
+            };
+            for child in children {
+                collect_lexical_lines(child, current, lines);
+            }
+            let inline = matches!(
+                map.get("type").and_then(Value::as_str),
+                Some("link" | "autolink")
+            );
+            if !inline && (!current.is_empty() || children.is_empty()) {
+                lines.push(std::mem::take(current));
+            }
+        }
+        Value::Array(items) => {
+            for item in items {
+                collect_lexical_lines(item, current, lines);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// 解析纯文本账本
+fn parse_ledger_text<'a>(lines: impl Iterator<Item = &'a str>) -> ParsedLedger {
+    let mut parsed = ParsedLedger::default();
+    let mut current: Option<RawEntry> = None;
+
+    for (i, raw_line) in lines.enumerate() {
+        let line_no = i + 1;
+        let is_comment = raw_line.trim_start().starts_with(';');
+        let line = raw_line.split(';').next().unwrap_or_default().trim();
+
+        if line.is_empty() {
+            if !is_comment {
+                if let Some(entry) = current.take() {
+                    finish_entry(entry, &mut parsed);
+                }
+            }
+            continue;
+        }
+
+        let first = line.split_whitespace().next().unwrap_or_default();
+        if looks_like_date(first) {
+            if let Some(entry) = current.take() {
+                finish_entry(entry, &mut parsed);
+            }
+            let index = parsed.entries.len();
+            current = Some(parse_header(index, line_no, first, &line[first.len()..]));
+            // 占位，保证序号连续
+            parsed.entries.push(placeholder_entry(index));
+            continue;
+        }
+
+        match current.as_mut() {
+            Some(entry) if is_ledger_account(first) => match parse_posting(line) {
+                Ok(posting) => entry.postings.push(posting),
+                Err(message) => {
+                    entry.errors.push((Some(line_no), message));
+                    entry.incomplete = true;
+                }
+            },
+            _ => {
+                if let Some(entry) = current.take() {
+                    finish_entry(entry, &mut parsed);
+                }
+            }
+        }
+    }
+
+    if let Some(entry) = current.take() {
+        finish_entry(entry, &mut parsed);
+    }
+    parsed
+}
+
+/// 解析结构化 JSON 账本
+fn parse_ledger_json(value: Value) -> ParsedLedger {
+    let mut parsed = ParsedLedger::default();
+    let Value::Array(items) = value else {
+        parsed.diagnostics.push(LedgerDiagnostic {
+            entry_index: None,
+            line: None,
+            severity: LedgerDiagnosticSeverity::Error,
+            message: "entries 必须是数组".to_string(),
+        });
+        return parsed;
+    };
+
+    for (index, item) in items.into_iter().enumerate() {
+        parsed.entries.push(placeholder_entry(index));
+        let entry = match serde_json::from_value::<JsonEntry>(item) {
+            Ok(json) => {
+                let date = parse_date(&json.date);
+                let mut errors = Vec::new();
+                if date.is_none() {
+                    errors.push((None, format!("无效的日期: {}", json.date)));
+                }
+                RawEntry {
+                    index,
+                    line: None,
+                    date,
+                    date_text: json.date,
+                    description: json.description.trim().to_string(),
+                    pending: json.pending,
+                    postings: json
+                        .postings
+                        .into_iter()
+                        .map(|p| RawPosting {
+                            account: p.account.trim().to_string(),
+                            amount: p.amount,
+                            commodity: p.commodity.trim().to_string(),
+                        })
+                        .collect(),
+                    errors,
+                    incomplete: false,
+                }
+            }
+            Err(e) => RawEntry {
+                index,
+                line: None,
+                date: None,
+                date_text: String::new(),
+                description: String::new(),
+                pending: false,
+                postings: Vec::new(),
+                errors: vec![(None, format!("无法解析的分录: {}", e))],
+                incomplete: true,
+            },
+        };
+        finish_entry(entry, &mut parsed);
+    }
+    parsed
+}
+
+fn placeholder_entry(index: usize) -> LedgerEntry {
+    LedgerEntry {
+        index,
+        line: None,
+        date: String::new(),
+        description: String::new(),
+        pending: false,
+        postings: Vec::new(),
+    }
+}
+
+/// 是否像日期（`2026-01-05`、`2026/1/5`）
+fn looks_like_date(token: &str) -> bool {
+    let bytes = token.as_bytes();
+    bytes.len() >= 8
+        && bytes[..4].iter().all(u8::is_ascii_digit)
+        && matches!(bytes[4], b'-' | b'/')
+        && token
+            .chars()
+            .all(|c| c.is_ascii_digit() || c == '-' || c == '/')
+}
+
+/// 是否为有效的账户名（包含 `:` 且没有空层级，纯文本账本据此识别过账行）
+pub fn is_ledger_account(token: &str) -> bool {
+    token.contains(LEDGER_ACCOUNT_SEPARATOR)
+        && token
+            .split(LEDGER_ACCOUNT_SEPARATOR)
+            .all(|segment| !segment.is_empty())
+}
+
+fn parse_date(text: &str) -> Option<NaiveDate> {
+    let text = text.trim();
+    NaiveDate::parse_from_str(text, DATE_FORMAT)
+        .or_else(|_| NaiveDate::parse_from_str(text, "%Y/%m/%d"))
+        .ok()
+}
+
+/// 解析分录首行（日期之后为 `[*|!] 摘要`）
+fn parse_header(index: usize, line_no: usize, date_text: &str, rest: &str) -> RawEntry {
+    let date = parse_date(date_text);
+    let mut errors = Vec::new();
+    if date.is_none() {
+        errors.push((Some(line_no), format!("无效的日期: {}", date_text)));
+    }
+
+    let rest = rest.trim();
+    let (pending, description) = match rest.chars().next() {
+        Some('!') => (true, rest[1..].trim()),
+        Some('*') => (false, rest[1..].trim()),
+        _ => (false, rest),
+    };
+
+    RawEntry {
+        index,
+        line: Some(line_no),
+        date,
+        date_text: date_text.to_string(),
+        description: description.to_string(),
+        pending,
+        postings: Vec::new(),
+        errors,
+        incomplete: false,
+    }
+}
+
+/// 解析过账行：`账户 [金额] [币种]`（金额和币种顺序不限）
+fn parse_posting(line: &str) -> Result<RawPosting, String> {
+    let tokens: Vec<&str> = line.split_whitespace().collect();
+    let account = tokens[0].to_string();
+    let (amount, commodity) = match tokens[1..] {
+        [] => (None, ""),
+        [amount] => (Some(amount.parse()?), ""),
+        [a, b] => match (a.parse::<LedgerAmount>(), b.parse::<LedgerAmount>()) {
+            (Ok(amount), _) => (Some(amount), b),
+            (Err(_), Ok(amount)) => (Some(amount), a),
+            (Err(e), Err(_)) => return Err(e),
+        },
+        _ => return Err(format!("无法解析的过账: {}", line)),
+    };
+    Ok(RawPosting {
+        account,
+        amount,
+        commodity: commodity.to_string(),
+    })
+}
+
+/// 校验分录（过账数量、省略金额推算、借贷平衡），写入结果
+fn finish_entry(mut raw: RawEntry, parsed: &mut ParsedLedger) {
+    let mut warnings = Vec::new();
+
+    if !raw.incomplete && raw.postings.len() < 2 {
+        raw.errors
+            .push((raw.line, "分录至少需要两条过账".to_string()));
+    }
+    if raw.postings.iter().any(|p| p.account.is_empty()) {
+        raw.errors.push((raw.line, "账户不能为空".to_string()));
+    }
+
+    let elided: Vec<usize> = (0..raw.postings.len())
+        .filter(|&i| raw.postings[i].amount.is_none())
+        .collect();
+    let mut sums: BTreeMap<&str, LedgerAmount> = BTreeMap::new();
+    let mut overflow = false;
+    for posting in &raw.postings {
+        if let Some(amount) = posting.amount {
+            let sum = sums.entry(posting.commodity.as_str()).or_default();
+            match sum.checked_add(amount) {
+                Some(total) => *sum = total,
+                None => overflow = true,
+            }
+        }
+    }
+    if overflow {
+        raw.errors.push((raw.line, "金额合计超出范围".to_string()));
+    }
+    let unbalanced: Vec<(String, LedgerAmount)> = sums
+        .into_iter()
+        .filter(|(_, sum)| !sum.is_zero())
+        .map(|(commodity, sum)| (commodity.to_string(), sum))
+        .collect();
+
+    let mut inferred = None;
+    match (elided.as_slice(), unbalanced.as_slice()) {
+        // 过账不完整或不足时不再检查平衡
+        _ if raw.incomplete || overflow || raw.postings.len() < 2 => {}
+        ([], []) => {}
+        ([], _) => {
+            let detail: Vec<String> = unbalanced
+                .iter()
+                .map(|(commodity, sum)| format_amount(*sum, commodity))
+                .collect();
+            raw.errors
+                .push((raw.line, format!("分录不平衡，差额 {}", detail.join(", "))));
+        }
+        ([i], []) => {
+            warnings.push(format!("{} 的金额推算为 0", raw.postings[*i].account));
+            let commodity = raw
+                .postings
+                .iter()
+                .find(|p| p.amount.is_some())
+                .map(|p| p.commodity.clone())
+                .unwrap_or_default();
+            inferred = Some((*i, LedgerAmount::ZERO, commodity));
+        }
+        ([i], [(commodity, sum)]) => match sum.checked_neg() {
+            Some(amount) => inferred = Some((*i, amount, commodity.clone())),
+            None => raw
+                .errors
+                .push((raw.line, "推算的金额超出范围".to_string())),
+        },
+        ([_], _) => raw
+            .errors
+            .push((raw.line, "多币种分录不能省略金额".to_string())),
+        _ => raw
+            .errors
+            .push((raw.line, "最多只能有一条过账省略金额".to_string())),
+    }
+
+    let postings = raw
+        .postings
+        .into_iter()
+        .enumerate()
+        .map(|(i, p)| match &inferred {
+            Some((index, amount, commodity)) if *index == i => LedgerPosting {
+                account: p.account,
+                amount: *amount,
+                commodity: commodity.clone(),
+                inferred: true,
+            },
+            _ => LedgerPosting {
+                account: p.account,
+                amount: p.amount.unwrap_or_default(),
+                commodity: p.commodity,
+                inferred: false,
+            },
+        })
+        .collect();
+
+    for (line, message) in raw.errors {
+        parsed.diagnostics.push(LedgerDiagnostic {
+            entry_index: Some(raw.index),
+            line,
+            severity: LedgerDiagnosticSeverity::Error,
+            message,
+        });
+    }
+    for message in warnings {
+        parsed.diagnostics.push(LedgerDiagnostic {
+            entry_index: Some(raw.index),
+            line: raw.line,
+            severity: LedgerDiagnosticSeverity::Warning,
+            message,
+        });
+    }
+
+    parsed.entries[raw.index] = LedgerEntry {
+        index: raw.index,
+        line: raw.line,
+        date: raw
+            .date
+            .map(|d| d.format(DATE_FORMAT).to_string())
+            .unwrap_or(raw.date_text),
+        description: raw.description,
+        pending: raw.pending,
+        postings,
+    };
+}
+
+/// 将分录格式化为纯文本账本行（首行 + 每条过账一行）
+pub fn format_ledger_entry(entry: &LedgerEntry) -> Vec<String> {
+    let flag = if entry.pending { "!" } else { "*" };
+    let header = if entry.description.is_empty() {
+        format!("{} {}", entry.date, flag)
+    } else {
+        format!("{} {} {}", entry.date, flag, entry.description)
+    };
+
+    std::iter::once(header)
+        .chain(entry.postings.iter().map(|posting| {
+            format!(
+                "    {}  {}",
+                posting.account,
+                format_amount(posting.amount, &posting.commodity)
+            )
+        }))
+        .collect()
+}
+
+/// 格式化带币种的金额（`42.50 CNY`）
+fn format_amount(amount: LedgerAmount, commodity: &str) -> String {
+    if commodity.is_empty() {
+        amount.to_string()
+    } else {
+        format!("{} {}", amount, commodity)
+    }
+}
+
+// ============================================================================
+// 余额与报表
+// ============================================================================
+
+/// 判断账户是否为查询账户或其子账户（`Expenses:Food` 匹配 `Expenses`）
+pub fn account_matches(account: &str, query: &str) -> bool {
+    match account.strip_prefix(query) {
+        Some(rest) => rest.is_empty() || rest.starts_with(LEDGER_ACCOUNT_SEPARATOR),
+        None => false,
+    }
+}
+
+/// 截断账户层级（`Expenses:Food:Lunch` 深度 1 -> `Expenses`）
+pub fn truncate_account(account: &str, depth: usize) -> String {
+    account
+        .split(LEDGER_ACCOUNT_SEPARATOR)
+        .take(depth)
+        .collect::<Vec<_>>()
+        .join(":")
+}
+
+/// 计算账户余额
+///
+/// 每个账户的余额同时累加到所有父账户（`Expenses:Food` 计入 `Expenses`）。
+/// 结果按账户、币种排序；余额超出金额范围时返回错误。
+pub fn ledger_balances<'a>(
+    entries: impl IntoIterator<Item = &'a LedgerEntry>,
+    period: &LedgerPeriod,
+) -> Result<Vec<LedgerBalance>, String> {
+    let mut totals: BTreeMap<(String, String), LedgerAmount> = BTreeMap::new();
+    for entry in entries.into_iter().filter(|e| period.contains(&e.date)) {
+        for posting in &entry.postings {
+            let segments: Vec<&str> = posting.account.split(LEDGER_ACCOUNT_SEPARATOR).collect();
+            for depth in 1..=segments.len() {
+                let account = segments[..depth].join(":");
+                let total = totals
+                    .entry((account, posting.commodity.clone()))
+                    .or_default();
+                *total = total.checked_add(posting.amount).ok_or_else(|| {
+                    format!("账户 {} 的余额超出范围", segments[..depth].join(":"))
+                })?;
+            }
+        }
+    }
+
+    Ok(totals
+        .into_iter()
+        .map(|((account, commodity), amount)| LedgerBalance {
+            account,
+            commodity,
+            amount,
+        })
+        .collect())
+}
+
+/// 生成报表
+///
+/// 按账户（或月份 + 账户）汇总范围内的过账；`account` 过滤包含子账户，
+/// `depth` 将账户截断到指定层级后合并。合计超出金额范围时返回错误。
+pub fn ledger_report<'a>(
+    entries: impl IntoIterator<Item = &'a LedgerEntry>,
+    query: &LedgerReportQuery,
+    period: &LedgerPeriod,
+) -> Result<Vec<LedgerReportRow>, String> {
+    type Key = (Option<String>, String, String);
+    let mut totals: BTreeMap<Key, (LedgerAmount, usize)> = BTreeMap::new();
+
+    for entry in entries.into_iter().filter(|e| period.contains(&e.date)) {
+        let period_key = match query.group_by {
+            LedgerGroupBy::Account => None,
+            LedgerGroupBy::Month => entry.date.get(..7).map(str::to_string),
+        };
+        for posting in &entry.postings {
+            if let Some(filter) = query.account.as_deref() {
+                if !account_matches(&posting.account, filter) {
+                    continue;
+                }
+            }
+            let account = match query.depth {
+                Some(depth) => truncate_account(&posting.account, depth),
+                None => posting.account.clone(),
+            };
+            let total = totals
+                .entry((
+                    period_key.clone(),
+                    account.clone(),
+                    posting.commodity.clone(),
+                ))
+                .or_default();
+            total.0 = total
+                .0
+                .checked_add(posting.amount)
+                .ok_or_else(|| format!("账户 {} 的合计超出范围", account))?;
+            total.1 += 1;
+        }
+    }
+
+    Ok(totals
+        .into_iter()
+        .map(
+            |((period, account, commodity), (amount, postings))| LedgerReportRow {
+                period,
+                account,
+                commodity,
+                amount,
+                postings,
+            },
+        )
+        .collect())
+}
+
+// ============================================================================
+// 测试
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEXT_LEDGER: &str = "\
+; 2026 年 1 月
+2026-01-05 * 超市购物
+    Expenses:Food        42.50 CNY
+    Expenses:Household   CNY 7.50
+    Assets:Bank         -50 CNY
+
+2026/01/20 ! 工资
+    Assets:Bank        8,000 CNY
+    Income:Salary              ; 推算
+
+2026-02-01 房租
+    Expenses:Rent      3000 CNY
+    Assets:Bank       -2900 CNY
+";
+
+    fn amount(text: &str) -> LedgerAmount {
+        text.parse().unwrap()
+    }
+
+    #[test]
+    fn test_parse_text_ledger() {
+        let parsed = parse_ledger(TEXT_LEDGER);
+        assert_eq!(parsed.entries.len(), 3);
+
+        let first = &parsed.entries[0];
+        assert_eq!(first.line, Some(2));
+        assert_eq!(first.date, "2026-01-05");
+        assert_eq!(first.description, "超市购物");
+        assert_eq!(first.postings[1].commodity, "CNY");
+        assert_eq!(first.postings[1].amount, amount("7.5"));
+
+        let salary = &parsed.entries[1];
+        assert_eq!(salary.date, "2026-01-20");
+        assert!(salary.pending);
+        assert!(salary.postings[1].inferred);
+        assert_eq!(salary.postings[1].amount, amount("-8000"));
+        assert_eq!(salary.postings[1].commodity, "CNY");
+
+        // 房租分录不平衡
+        assert_eq!(parsed.diagnostics.len(), 1);
+        let diagnostic = &parsed.diagnostics[0];
+        assert_eq!(diagnostic.entry_index, Some(2));
+        assert_eq!(diagnostic.line, Some(11));
+        assert_eq!(diagnostic.severity, LedgerDiagnosticSeverity::Error);
+        assert!(diagnostic.message.contains("100.00 CNY"));
+
+        assert_eq!(parsed.valid_entries().count(), 2);
+    }
+
+    #[test]
+    fn test_parse_diagnostics() {
+        let parsed = parse_ledger(
+            "2026-13-01 无效日期\n  A:B 1\n  C:D -1\n\n2026-01-02 单条\n  A:B 1\n\n\
+             2026-01-03 两条省略\n  A:B\n  C:D\n\n2026-01-04 坏金额\n  A:B abc\n  C:D 1\n\n\
+             2026-01-05 多币种\n  A:B 1 USD\n  A:B 1 CNY\n  C:D\n",
+        );
+        assert_eq!(parsed.entries.len(), 5);
+        let messages: Vec<(Option<usize>, &str)> = parsed
+            .diagnostics
+            .iter()
+            .map(|d| (d.entry_index, d.message.as_str()))
+            .collect();
+        assert_eq!(
+            messages,
+            vec![
+                (Some(0), "无效的日期: 2026-13-01"),
+                (Some(1), "分录至少需要两条过账"),
+                (Some(2), "最多只能有一条过账省略金额"),
+                (Some(3), "无效的金额: abc"),
+                (Some(4), "多币种分录不能省略金额"),
+            ]
+        );
+        assert_eq!(parsed.diagnostics[3].line, Some(13));
+        assert_eq!(parsed.diagnostics[0].line, Some(1));
+        assert_eq!(parsed.valid_entries().count(), 0);
+    }
+
+    #[test]
+    fn test_parse_lexical_ledger() {
+        let content = serde_json::json!({
+            "root": {
+                "children": [
+                    { "type": "paragraph", "children": [
+                        { "type": "tag", "tagName": "ledger", "text": "#[ledger]" }
+                    ]},
+                    { "type": "heading", "tag": "h2", "children": [
+                        { "type": "text", "text": "Expenses for Jan 5" }
+                    ]},
+                    { "type": "paragraph", "children": [
+                        { "type": "text", "text": "2026-01-05 午饭" },
+                        { "type": "linebreak" },
+                        { "type": "text", "text": "  Expenses:Food 30" }
+                    ]},
+                    { "type": "list", "listType": "bullet", "children": [
+                        { "type": "listitem", "children": [
+                            { "type": "text", "text": "Assets:Cash -30" }
+                        ]}
+                    ]},
+                    { "type": "paragraph", "children": [] },
+                    { "type": "paragraph", "children": [
+                        { "type": "text", "text": "Notes: 其他文本" }
+                    ]}
+                ]
+            }
+        })
+        .to_string();
+
+        let parsed = parse_ledger(&content);
+        assert!(parsed.diagnostics.is_empty());
+        assert_eq!(parsed.entries.len(), 1);
+        assert_eq!(parsed.entries[0].postings.len(), 2);
+        assert_eq!(parsed.entries[0].line, Some(3));
+    }
+
+    #[test]
+    fn test_parse_json_ledger() {
+        let content = r#"{"entries":[
+            {"date":"2026-01-05","description":"咖啡","postings":[
+                {"account":"Expenses:Coffee","amount":"3.5","commodity":"USD"},
+                {"account":"Assets:Cash","commodity":"USD"}
+            ]},
+            {"description":"缺少日期"}
+        ]}"#;
+        let parsed = parse_ledger(content);
+        assert_eq!(parsed.entries.len(), 2);
+        assert_eq!(parsed.entries[0].postings[1].amount, amount("-3.5"));
+        assert_eq!(parsed.diagnostics.len(), 1);
+        assert_eq!(parsed.diagnostics[0].entry_index, Some(1));
+        assert!(parsed.diagnostics[0].message.starts_with("无法解析的分录"));
+
+        let array = r#"[{"date":"2026-01-05","postings":[
+            {"account":"A:B","amount":1},{"account":"C:D","amount":-1}]}]"#;
+        assert_eq!(parse_ledger(array).valid_entries().count(), 1);
+    }
+
+    #[test]
+    fn test_ledger_balances() {
+        let parsed = parse_ledger(TEXT_LEDGER);
+        let balances = ledger_balances(parsed.valid_entries(), &LedgerPeriod::default()).unwrap();
+        let rows: Vec<(&str, String)> = balances
+            .iter()
+            .map(|b| (b.account.as_str(), b.amount.to_string()))
+            .collect();
+        assert_eq!(
+            rows,
+            vec![
+                ("Assets", "7950.00".to_string()),
+                ("Assets:Bank", "7950.00".to_string()),
+                ("Expenses", "50.00".to_string()),
+                ("Expenses:Food", "42.50".to_string()),
+                ("Expenses:Household", "7.50".to_string()),
+                ("Income", "-8000.00".to_string()),
+                ("Income:Salary", "-8000.00".to_string()),
+            ]
+        );
+
+        let period = LedgerPeriod {
+            from: None,
+            to: NaiveDate::from_ymd_opt(2026, 1, 10),
+        };
+        let balances = ledger_balances(parsed.valid_entries(), &period).unwrap();
+        assert!(balances.iter().all(|b| !b.account.starts_with("Income")));
+    }
+
+    #[test]
+    fn test_ledger_amount_overflow() {
+        let parsed = parse_ledger(
+            "2026-01-05 a\n  Assets:A 900000000000000\n  Assets:B 900000000000000\n  Income:C\n",
+        );
+        assert_eq!(parsed.valid_entries().count(), 0);
+        assert!(parsed
+            .diagnostics
+            .iter()
+            .any(|d| d.message == "金额合计超出范围"));
+
+        let parsed = parse_ledger(
+            "2026-01-05 a\n  Assets:A 900000000000000\n  Income:C\n\n\
+             2026-01-06 b\n  Assets:A 900000000000000\n  Income:C\n",
+        );
+        assert_eq!(parsed.valid_entries().count(), 2);
+        let error = ledger_balances(parsed.valid_entries(), &LedgerPeriod::default()).unwrap_err();
+        assert!(error.contains("Assets"));
+        let error = ledger_report(
+            parsed.valid_entries(),
+            &LedgerReportQuery::default(),
+            &LedgerPeriod::default(),
+        )
+        .unwrap_err();
+        assert!(error.contains("超出范围"));
+    }
+
+    #[test]
+    fn test_ledger_report() {
+        let parsed = parse_ledger(
+            "2026-01-05 a\n  Expenses:Food 10\n  Assets:Cash\n\n\
+             2026-01-20 b\n  Expenses:Rent 100\n  Assets:Cash\n\n\
+             2026-02-03 c\n  Expenses:Food:Lunch 5\n  Assets:Cash\n",
+        );
+
+        let by_month = ledger_report(
+            parsed.valid_entries(),
+            &LedgerReportQuery {
+                group_by: LedgerGroupBy::Month,
+                account: Some("Expenses".to_string()),
+                depth: Some(1),
+                ..Default::default()
+            },
+            &LedgerPeriod::default(),
+        )
+        .unwrap();
+        let rows: Vec<(Option<&str>, &str, String, usize)> = by_month
+            .iter()
+            .map(|r| {
+                (
+                    r.period.as_deref(),
+                    r.account.as_str(),
+                    r.amount.to_string(),
+                    r.postings,
+                )
+            })
+            .collect();
+        assert_eq!(
+            rows,
+            vec![
+                (Some("2026-01"), "Expenses", "110.00".to_string(), 2),
+                (Some("2026-02"), "Expenses", "5.00".to_string(), 1),
+            ]
+        );
+
+        let by_account = ledger_report(
+            parsed.valid_entries(),
+            &LedgerReportQuery {
+                account: Some("Expenses:Food".to_string()),
+                depth: Some(2),
+                ..Default::default()
+            },
+            &LedgerPeriod::default(),
+        )
+        .unwrap();
+        assert_eq!(by_account.len(), 1);
+        assert_eq!(by_account[0].period, None);
+        assert_eq!(by_account[0].amount.to_string(), "15.00");
+    }
+
+    #[test]
+    fn test_format_ledger_entry_round_trip() {
+        let parsed = parse_ledger(TEXT_LEDGER);
+        let text = parsed
+            .entries
+            .iter()
+            .take(2)
+            .map(|entry| format_ledger_entry(entry).join("\n"))
+            .collect::<Vec<_>>()
+            .join("\n\n");
+        assert!(text.starts_with("2026-01-05 * 超市购物\n    Expenses:Food  42.50 CNY"));
+
+        let reparsed = parse_ledger(&text);
+        assert!(reparsed.diagnostics.is_empty());
+        assert_eq!(reparsed.entries[1].postings, {
+            let mut postings = parsed.entries[1].postings.clone();
+            postings[1].inferred = false;
+            postings
+        });
+        assert!(reparsed.entries[1].pending);
+    }
+
+    #[test]
+    fn test_account_helpers() {
+        assert!(account_matches("Expenses:Food", "Expenses"));
+        assert!(account_matches("Expenses", "Expenses"));
+        assert!(!account_matches("ExpensesX", "Expenses"));
+        assert_eq!(truncate_account("A:B:C", 2), "A:B");
+        assert_eq!(truncate_account("A", 3), "A");
+        assert!(is_ledger_account("支出:餐饮"));
+        assert!(!is_ledger_account("Notes:"));
+        assert!(looks_like_date("2026/1/5"));
+        assert!(!looks_like_date("#[2026-01-05]"));
+    }
+}
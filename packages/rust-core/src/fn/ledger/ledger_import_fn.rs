@@ -0,0 +1,667 @@
+//! Ledger CSV 导入纯函数
+//!
+//! 将银行导出的 CSV 流水转换为复式记账分录：按列映射读取日期、摘要和金额，
+//! 按分类规则确定对方账户，检测与账本中已有分录重复的交易，并把新分录追加到记账内容。
+
+use std::collections::HashMap;
+
+use chrono::{NaiveDate, NaiveDateTime};
+use serde_json::{json, Value};
+
+use super::ledger_fn::{format_ledger_entry, is_ledger_account};
+use crate::types::ledger::{
+    ImportLedgerCsvRequest, LedgerAmount, LedgerAmountSign, LedgerCsvColumn, LedgerEntry,
+    LedgerImportIssue, LedgerPosting,
+};
+
+/// 未匹配规则的收入默认记入的账户
+pub const DEFAULT_INCOME_ACCOUNT: &str = "Income:Uncategorized";
+
+/// 未匹配规则的支出默认记入的账户
+pub const DEFAULT_EXPENSE_ACCOUNT: &str = "Expenses:Uncategorized";
+
+/// 从 CSV 行转换出的交易
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImportedTransaction {
+    /// CSV 行号（从 1 开始，包含表头）
+    pub row: usize,
+    /// 分录（第一条过账为银行账户）
+    pub entry: LedgerEntry,
+}
+
+// ============================================================================
+// CSV 解析
+// ============================================================================
+
+/// 解析 CSV 文本
+///
+/// 支持双引号包裹（字段内 `""` 表示一个引号，可包含分隔符和换行）、`\r\n` 换行和 UTF-8 BOM。
+/// 完全空白的行被忽略，但仍占用行号（返回值为（行号，字段））。
+pub fn parse_csv(text: &str, delimiter: char) -> Vec<(usize, Vec<String>)> {
+    let text = text.strip_prefix('\u{feff}').unwrap_or(text);
+    let mut records = Vec::new();
+    let mut record = Vec::new();
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut row = 1;
+    let mut record_row = 1;
+    let mut chars = text.chars().peekable();
+
+    let mut finish_record = |record: &mut Vec<String>, record_row: usize| {
+        let fields = std::mem::take(record);
+        if fields.iter().any(|f| !f.trim().is_empty()) {
+            records.push((record_row, fields));
+        }
+    };
+
+    while let Some(c) = chars.next() {
+        if in_quotes {
+            match c {
+                '"' if chars.next_if_eq(&'"').is_some() => field.push('"'),
+                '"' => in_quotes = false,
+                '\n' => {
+                    row += 1;
+                    field.push(c);
+                }
+                _ => field.push(c),
+            }
+            continue;
+        }
+        match c {
+            '"' if field.is_empty() => in_quotes = true,
+            '\r' if chars.peek() == Some(&'\n') => {}
+            '\n' | '\r' => {
+                record.push(std::mem::take(&mut field));
+                finish_record(&mut record, record_row);
+                row += 1;
+                record_row = row;
+            }
+            c if c == delimiter => record.push(std::mem::take(&mut field)),
+            _ => field.push(c),
+        }
+    }
+    if !field.is_empty() || !record.is_empty() {
+        record.push(field);
+        finish_record(&mut record, record_row);
+    }
+    records
+}
+
+/// 解析 CSV 中的金额
+///
+/// 去掉货币符号和空白，支持 `(12.00)` 和 `12.00-` 表示负数；
+/// `decimal_comma` 为 true 时 `1.234,56` 解析为 1234.56。空字段返回 None。
+fn parse_csv_amount(text: &str, decimal_comma: bool) -> Result<Option<LedgerAmount>, String> {
+    let trimmed = text.trim();
+    if trimmed.is_empty() {
+        return Ok(None);
+    }
+
+    let mut negative = false;
+    let mut body = trimmed;
+    if let Some(inner) = body.strip_prefix('(').and_then(|b| b.strip_suffix(')')) {
+        negative = true;
+        body = inner;
+    }
+    if let Some(inner) = body.strip_suffix('-') {
+        negative = !negative;
+        body = inner;
+    }
+
+    let mut cleaned: String = body
+        .chars()
+        .filter(|c| c.is_ascii_digit() || matches!(c, '.' | ',' | '-' | '+'))
+        .collect();
+    if decimal_comma {
+        cleaned = cleaned.replace('.', "").replace(',', ".");
+    }
+    let amount: LedgerAmount = cleaned
+        .parse()
+        .map_err(|_| format!("无效的金额: {}", trimmed))?;
+    if negative {
+        amount
+            .checked_neg()
+            .map(Some)
+            .ok_or_else(|| format!("金额超出范围: {}", trimmed))
+    } else {
+        Ok(Some(amount))
+    }
+}
+
+/// 解析 CSV 中的日期（指定格式时只用该格式，格式可以包含时间）
+fn parse_csv_date(text: &str, format: Option<&str>) -> Option<NaiveDate> {
+    let text = text.trim();
+    match format {
+        Some(format) => NaiveDate::parse_from_str(text, format).ok().or_else(|| {
+            NaiveDateTime::parse_from_str(text, format)
+                .ok()
+                .map(|dt| dt.date())
+        }),
+        None => ["%Y-%m-%d", "%Y/%m/%d"]
+            .iter()
+            .find_map(|f| NaiveDate::parse_from_str(text, f).ok()),
+    }
+}
+
+/// 将列映射解析为列序号
+fn resolve_column(column: &LedgerCsvColumn, headers: Option<&[String]>) -> Result<usize, String> {
+    match column {
+        LedgerCsvColumn::Index(index) => Ok(*index),
+        LedgerCsvColumn::Name(name) => {
+            let headers = headers.ok_or("没有表头时只能按列序号映射")?;
+            headers
+                .iter()
+                .position(|h| h.trim().eq_ignore_ascii_case(name.trim()))
+                .ok_or_else(|| format!("找不到列: {}", name))
+        }
+    }
+}
+
+// ============================================================================
+// 转换
+// ============================================================================
+
+/// 按分类规则确定对方账户
+///
+/// 依次匹配规则（摘要包含规则文本，不区分大小写）；都不匹配时收入记入 `income_account`，
+/// 支出记入 `expense_account`。
+pub fn categorize_transaction(
+    description: &str,
+    amount: LedgerAmount,
+    request: &ImportLedgerCsvRequest,
+) -> String {
+    let lower = description.to_lowercase();
+    request
+        .rules
+        .iter()
+        .find(|rule| lower.contains(&rule.contains.to_lowercase()))
+        .map(|rule| rule.account.clone())
+        .unwrap_or_else(|| {
+            let fallback = if amount.is_negative() {
+                (request.expense_account.as_deref(), DEFAULT_EXPENSE_ACCOUNT)
+            } else {
+                (request.income_account.as_deref(), DEFAULT_INCOME_ACCOUNT)
+            };
+            fallback.0.unwrap_or(fallback.1).to_string()
+        })
+}
+
+/// 校验导入请求中的账户和规则
+fn validate_request(request: &ImportLedgerCsvRequest) -> Result<(), String> {
+    let accounts = std::iter::once(request.account.as_str())
+        .chain(request.rules.iter().map(|rule| rule.account.as_str()))
+        .chain(request.income_account.as_deref())
+        .chain(request.expense_account.as_deref());
+    for account in accounts {
+        if !is_ledger_account(account) {
+            return Err(format!("无效的账户（需要 `:` 分隔的层级）: {}", account));
+        }
+    }
+    if request
+        .rules
+        .iter()
+        .any(|rule| rule.contains.trim().is_empty())
+    {
+        return Err("分类规则的匹配文本不能为空".to_string());
+    }
+    Ok(())
+}
+
+/// 将 CSV 流水转换为分录
+///
+/// 每行生成一笔两条过账的分录：银行账户（流入为正）和分类得到的对方账户。
+/// 列映射或账户配置有误时返回 Err；单行无法解析时跳过并记录在问题列表中。
+pub fn csv_to_ledger_entries(
+    request: &ImportLedgerCsvRequest,
+) -> Result<(Vec<ImportedTransaction>, Vec<LedgerImportIssue>), String> {
+    validate_request(request)?;
+
+    let mapping = &request.mapping;
+    let date_column = mapping.date.as_ref().ok_or("缺少日期列映射")?;
+    if mapping.amount.is_none() && mapping.debit.is_none() && mapping.credit.is_none() {
+        return Err("缺少金额列映射（amount 或 debit/credit）".to_string());
+    }
+
+    let mut records = parse_csv(&request.csv, request.delimiter.unwrap_or(',')).into_iter();
+    let headers = if request.has_header.unwrap_or(true) {
+        records.next().map(|(_, fields)| fields)
+    } else {
+        None
+    };
+    let resolve = |column: &Option<LedgerCsvColumn>| {
+        column
+            .as_ref()
+            .map(|c| resolve_column(c, headers.as_deref()))
+            .transpose()
+    };
+    let date_index = resolve_column(date_column, headers.as_deref())?;
+    let description_index = resolve(&mapping.description)?;
+    let amount_index = resolve(&mapping.amount)?;
+    let debit_index = resolve(&mapping.debit)?;
+    let credit_index = resolve(&mapping.credit)?;
+    let commodity_index = resolve(&mapping.commodity)?;
+
+    let mut transactions = Vec::new();
+    let mut issues = Vec::new();
+    for (row, fields) in records {
+        let field = |index: usize| fields.get(index).map(String::as_str);
+        let optional_field = |index: Option<usize>| index.and_then(field).unwrap_or_default();
+        let mut skip = |message: String| issues.push(LedgerImportIssue { row, message });
+
+        let Some(date_text) = field(date_index) else {
+            skip(format!("缺少第 {} 列", date_index + 1));
+            continue;
+        };
+        let Some(date) = parse_csv_date(date_text, request.date_format.as_deref()) else {
+            skip(format!("无效的日期: {}", date_text.trim()));
+            continue;
+        };
+
+        let amount = match amount_index {
+            Some(index) => parse_csv_amount(optional_field(Some(index)), request.decimal_comma),
+            None => {
+                let debit = parse_csv_amount(optional_field(debit_index), request.decimal_comma);
+                let credit = parse_csv_amount(optional_field(credit_index), request.decimal_comma);
+                match (debit, credit) {
+                    (Err(e), _) | (_, Err(e)) => Err(e),
+                    (Ok(None), Ok(None)) => Ok(None),
+                    (Ok(debit), Ok(credit)) => debit
+                        .unwrap_or_default()
+                        .checked_neg()
+                        .and_then(|debit| credit.unwrap_or_default().checked_add(debit))
+                        .map(Some)
+                        .ok_or_else(|| "金额超出范围".to_string()),
+                }
+            }
+        };
+        let amount = match amount {
+            Ok(Some(amount)) if request.sign == LedgerAmountSign::Inverted => {
+                match amount.checked_neg() {
+                    Some(amount) => amount,
+                    None => {
+                        skip("金额超出范围".to_string());
+                        continue;
+                    }
+                }
+            }
+            Ok(Some(amount)) => amount,
+            Ok(None) => {
+                skip("缺少金额".to_string());
+                continue;
+            }
+            Err(e) => {
+                skip(e);
+                continue;
+            }
+        };
+        if amount.is_zero() {
+            skip("金额为 0".to_string());
+            continue;
+        }
+        let Some(counter_amount) = amount.checked_neg() else {
+            skip("金额超出范围".to_string());
+            continue;
+        };
+
+        // `;` 在纯文本账本中表示注释
+        let description = optional_field(description_index)
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" ")
+            .replace(';', ",");
+        let commodity = match optional_field(commodity_index).trim() {
+            "" => request.commodity.clone().unwrap_or_default(),
+            commodity => commodity.to_string(),
+        };
+        let counter_account = categorize_transaction(&description, amount, request);
+
+        transactions.push(ImportedTransaction {
+            row,
+            entry: LedgerEntry {
+                index: transactions.len(),
+                line: None,
+                date: date.format("%Y-%m-%d").to_string(),
+                description,
+                pending: false,
+                postings: vec![
+                    LedgerPosting {
+                        account: request.account.clone(),
+                        amount,
+                        commodity: commodity.clone(),
+                        inferred: false,
+                    },
+                    LedgerPosting {
+                        account: counter_account,
+                        amount: counter_amount,
+                        commodity,
+                        inferred: false,
+                    },
+                ],
+            },
+        });
+    }
+    Ok((transactions, issues))
+}
+
+/// 标记与已有分录重复的交易
+///
+/// 日期、银行账户上的金额和币种都相同即视为重复。按次数匹配：
+/// 账本中已有一笔同样的交易时，CSV 中只有第一笔被视为重复（同一天两笔相同消费仍会导入一笔）。
+pub fn find_duplicate_transactions<'a>(
+    existing: impl IntoIterator<Item = &'a LedgerEntry>,
+    account: &str,
+    transactions: &[ImportedTransaction],
+) -> Vec<bool> {
+    let mut counts: HashMap<(String, LedgerAmount, String), usize> = HashMap::new();
+    for entry in existing {
+        for posting in entry.postings.iter().filter(|p| p.account == account) {
+            *counts
+                .entry((
+                    entry.date.clone(),
+                    posting.amount,
+                    posting.commodity.clone(),
+                ))
+                .or_default() += 1;
+        }
+    }
+
+    transactions
+        .iter()
+        .map(|transaction| {
+            let posting = &transaction.entry.postings[0];
+            let key = (
+                transaction.entry.date.clone(),
+                posting.amount,
+                posting.commodity.clone(),
+            );
+            match counts.get_mut(&key) {
+                Some(count) if *count > 0 => {
+                    *count -= 1;
+                    true
+                }
+                _ => false,
+            }
+        })
+        .collect()
+}
+
+// ============================================================================
+// 写入内容
+// ============================================================================
+
+/// 将分录追加到记账内容
+///
+/// - 没有内容：生成 Lexical 文档
+/// - Lexical JSON：每笔分录追加为一个段落（行之间用换行节点分隔）
+/// - 结构化 JSON：追加到分录数组
+/// - 纯文本：以空行分隔追加
+pub fn append_ledger_entries(content: Option<&str>, entries: &[LedgerEntry]) -> String {
+    let content = content.filter(|c| !c.trim().is_empty());
+    let parsed = content.map(serde_json::from_str::<Value>);
+
+    match (content, parsed) {
+        (None, _) => {
+            let mut root = json!({
+                "root": {
+                    "children": [],
+                    "direction": "ltr", "format": "", "indent": 0, "type": "root", "version": 1
+                }
+            });
+            push_lexical_entries(&mut root, entries);
+            root.to_string()
+        }
+        (Some(_), Some(Ok(mut value))) if value.get("root").is_some() => {
+            push_lexical_entries(&mut value, entries);
+            value.to_string()
+        }
+        (Some(_), Some(Ok(mut value))) if value.is_array() || value.get("entries").is_some() => {
+            let items = match value.get_mut("entries") {
+                Some(items) => items,
+                None => &mut value,
+            };
+            if let Some(items) = items.as_array_mut() {
+                items.extend(entries.iter().map(json_entry));
+            }
+            value.to_string()
+        }
+        (Some(text), _) => {
+            let appended: Vec<String> = entries
+                .iter()
+                .map(|entry| format_ledger_entry(entry).join("\n"))
+                .collect();
+            format!("{}\n\n{}\n", text.trim_end(), appended.join("\n\n"))
+        }
+    }
+}
+
+fn push_lexical_entries(document: &mut Value, entries: &[LedgerEntry]) {
+    let Some(children) = document
+        .get_mut("root")
+        .and_then(|root| root.get_mut("children"))
+        .and_then(Value::as_array_mut)
+    else {
+        return;
+    };
+
+    for entry in entries {
+        let mut nodes = Vec::new();
+        for (i, line) in format_ledger_entry(entry).into_iter().enumerate() {
+            if i > 0 {
+                nodes.push(json!({ "type": "linebreak", "version": 1 }));
+            }
+            nodes.push(json!({
+                "detail": 0, "format": 0, "mode": "normal", "style": "",
+                "text": line, "type": "text", "version": 1
+            }));
+        }
+        children.push(json!({
+            "children": nodes, "direction": "ltr", "format": "", "indent": 0,
+            "type": "paragraph", "version": 1
+        }));
+    }
+}
+
+fn json_entry(entry: &LedgerEntry) -> Value {
+    json!({
+        "date": entry.date,
+        "description": entry.description,
+        "pending": entry.pending,
+        "postings": entry.postings.iter().map(|p| json!({
+            "account": p.account,
+            "amount": p.amount,
+            "commodity": p.commodity,
+        })).collect::<Vec<_>>(),
+    })
+}
+
+// ============================================================================
+// 测试
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::r#fn::ledger::parse_ledger;
+    use crate::types::ledger::{LedgerCsvMapping, LedgerImportRule};
+
+    const BANK_CSV: &str = "\u{feff}Date,Description,Debit,Credit,Balance\r\n\
+        05/01/2026,\"STARBUCKS, KYOTO\",4.50,,995.50\r\n\
+        06/01/2026,Salary ACME,,3000.00,3995.50\r\n\
+        07/01/2026,Rent,\"1,200.00\",,2795.50\r\n\
+        \r\n\
+        08/01/2026,Broken,abc,,0\r\n\
+        31/02/2026,Bad date,1,,0\r\n";
+
+    fn request() -> ImportLedgerCsvRequest {
+        ImportLedgerCsvRequest {
+            csv: BANK_CSV.to_string(),
+            account: "Assets:Bank".to_string(),
+            mapping: LedgerCsvMapping {
+                date: Some(LedgerCsvColumn::Name("date".to_string())),
+                description: Some(LedgerCsvColumn::Name("Description".to_string())),
+                debit: Some(LedgerCsvColumn::Name("Debit".to_string())),
+                credit: Some(LedgerCsvColumn::Name("Credit".to_string())),
+                ..Default::default()
+            },
+            date_format: Some("%d/%m/%Y".to_string()),
+            commodity: Some("EUR".to_string()),
+            rules: vec![LedgerImportRule {
+                contains: "starbucks".to_string(),
+                account: "Expenses:Coffee".to_string(),
+            }],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_parse_csv() {
+        let records = parse_csv("a,\"b \"\"quoted\"\"\",\"multi\nline\"\n\n1;2,3", ',');
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].0, 1);
+        assert_eq!(records[0].1, vec!["a", "b \"quoted\"", "multi\nline"]);
+        assert_eq!(records[1].0, 4);
+        assert_eq!(records[1].1, vec!["1;2", "3"]);
+
+        let records = parse_csv("1;2\r\n3;4", ';');
+        assert_eq!(records[1], (2, vec!["3".to_string(), "4".to_string()]));
+    }
+
+    #[test]
+    fn test_parse_csv_amount() {
+        let parse = |text: &str, comma: bool| {
+            parse_csv_amount(text, comma)
+                .unwrap()
+                .map(|a| a.to_string())
+        };
+        assert_eq!(parse("$1,234.50", false).as_deref(), Some("1234.50"));
+        assert_eq!(parse("(12.00)", false).as_deref(), Some("-12.00"));
+        assert_eq!(parse("12.00-", false).as_deref(), Some("-12.00"));
+        assert_eq!(parse("-1.234,56 €", true).as_deref(), Some("-1234.56"));
+        assert_eq!(parse("  ", false), None);
+        assert!(parse_csv_amount("abc", false).is_err());
+    }
+
+    #[test]
+    fn test_csv_to_ledger_entries() {
+        let (transactions, issues) = csv_to_ledger_entries(&request()).unwrap();
+        assert_eq!(transactions.len(), 3);
+
+        let coffee = &transactions[0];
+        assert_eq!(coffee.row, 2);
+        assert_eq!(coffee.entry.date, "2026-01-05");
+        assert_eq!(coffee.entry.description, "STARBUCKS, KYOTO");
+        assert_eq!(coffee.entry.postings[0].amount.to_string(), "-4.50");
+        assert_eq!(coffee.entry.postings[0].commodity, "EUR");
+        assert_eq!(coffee.entry.postings[1].account, "Expenses:Coffee");
+
+        assert_eq!(
+            transactions[1].entry.postings[1].account,
+            DEFAULT_INCOME_ACCOUNT
+        );
+        assert_eq!(
+            transactions[1].entry.postings[0].amount.to_string(),
+            "3000.00"
+        );
+        assert_eq!(
+            transactions[2].entry.postings[1].account,
+            DEFAULT_EXPENSE_ACCOUNT
+        );
+        assert_eq!(
+            transactions[2].entry.postings[0].amount.to_string(),
+            "-1200.00"
+        );
+
+        let rows: Vec<(usize, &str)> = issues.iter().map(|i| (i.row, i.message.as_str())).collect();
+        assert_eq!(
+            rows,
+            vec![(6, "无效的金额: abc"), (7, "无效的日期: 31/02/2026")]
+        );
+    }
+
+    #[test]
+    fn test_csv_sign_and_index_mapping() {
+        let request = ImportLedgerCsvRequest {
+            csv: "2026-01-05;Card payment;12,50\n".to_string(),
+            account: "Liabilities:CreditCard".to_string(),
+            mapping: LedgerCsvMapping {
+                date: Some(LedgerCsvColumn::Index(0)),
+                description: Some(LedgerCsvColumn::Index(1)),
+                amount: Some(LedgerCsvColumn::Index(2)),
+                ..Default::default()
+            },
+            has_header: Some(false),
+            delimiter: Some(';'),
+            decimal_comma: true,
+            sign: LedgerAmountSign::Inverted,
+            expense_account: Some("Expenses:Card".to_string()),
+            ..Default::default()
+        };
+        let (transactions, issues) = csv_to_ledger_entries(&request).unwrap();
+        assert!(issues.is_empty());
+        assert_eq!(transactions[0].row, 1);
+        assert_eq!(
+            transactions[0].entry.postings[0].amount.to_string(),
+            "-12.50"
+        );
+        assert_eq!(transactions[0].entry.postings[1].account, "Expenses:Card");
+    }
+
+    #[test]
+    fn test_csv_config_errors() {
+        let mut bad = request();
+        bad.account = "Bank".to_string();
+        assert!(csv_to_ledger_entries(&bad).is_err());
+
+        let mut bad = request();
+        bad.mapping.description = Some(LedgerCsvColumn::Name("Memo".to_string()));
+        assert_eq!(csv_to_ledger_entries(&bad).unwrap_err(), "找不到列: Memo");
+
+        let mut bad = request();
+        bad.has_header = Some(false);
+        assert!(csv_to_ledger_entries(&bad).is_err());
+
+        let mut bad = request();
+        bad.mapping.debit = None;
+        bad.mapping.credit = None;
+        assert!(csv_to_ledger_entries(&bad).is_err());
+    }
+
+    #[test]
+    fn test_find_duplicate_transactions() {
+        let (transactions, _) = csv_to_ledger_entries(&request()).unwrap();
+        let existing = parse_ledger(
+            "2026-01-05 * Starbucks\n    Assets:Bank  -4.50 EUR\n    Expenses:Coffee\n\n\
+             2026-01-06 * Salary\n    Assets:Bank  3000 USD\n    Income:Salary\n",
+        );
+        let duplicates =
+            find_duplicate_transactions(existing.valid_entries(), "Assets:Bank", &transactions);
+        assert_eq!(duplicates, vec![true, false, false]);
+
+        // 同一 CSV 中两笔相同交易只有一笔与已有分录重复
+        let doubled = [transactions[0].clone(), transactions[0].clone()];
+        let duplicates =
+            find_duplicate_transactions(existing.valid_entries(), "Assets:Bank", &doubled);
+        assert_eq!(duplicates, vec![true, false]);
+    }
+
+    #[test]
+    fn test_append_ledger_entries() {
+        let (transactions, _) = csv_to_ledger_entries(&request()).unwrap();
+        let entries: Vec<LedgerEntry> = transactions.into_iter().map(|t| t.entry).collect();
+
+        for content in [
+            None,
+            Some(
+                r#"{"root":{"children":[{"type":"paragraph","children":[{"type":"text","text":"Ledger"}]}]}}"#,
+            ),
+            Some(r#"{"entries":[]}"#),
+            Some("; 2026\n"),
+        ] {
+            let appended = append_ledger_entries(content, &entries);
+            let parsed = parse_ledger(&appended);
+            assert!(parsed.diagnostics.is_empty(), "{:?}", parsed.diagnostics);
+            assert_eq!(parsed.entries.len(), 3, "{}", appended);
+            assert_eq!(parsed.entries[0].description, "STARBUCKS, KYOTO");
+        }
+    }
+}
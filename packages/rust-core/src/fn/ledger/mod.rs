@@ -0,0 +1,7 @@
+//! Ledger 纯函数模块
+
+pub mod ledger_fn;
+pub mod ledger_import_fn;
+
+pub use ledger_fn::*;
+pub use ledger_import_fn::*;
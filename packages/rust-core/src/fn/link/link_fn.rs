@@ -0,0 +1,187 @@
+//! Link 纯函数
+//!
+//! 从 Lexical 内容中解析和改写 `@` 引用（mention 节点）
+
+use serde_json::Value;
+use std::collections::HashSet;
+
+/// 内容中的一个 `@` 引用
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MentionRef {
+    /// 被引用的节点 ID（mention 节点的 roleId）
+    pub node_id: String,
+    /// 引用显示名称（mention 节点的 mentionName）
+    pub name: String,
+}
+
+// ============================================================================
+// 纯函数（数据转换）
+// ============================================================================
+
+/// 从 Lexical JSON 内容中提取所有 `@` 引用
+///
+/// 递归遍历编辑器状态，收集 `type == "mention"` 的节点。
+/// 同一目标只保留第一次出现，非 JSON 内容返回空列表。
+pub fn extract_mentions(content: &str) -> Vec<MentionRef> {
+    let Ok(root) = serde_json::from_str::<Value>(content) else {
+        return Vec::new();
+    };
+
+    let mut seen = HashSet::new();
+    let mut mentions = Vec::new();
+    collect_mentions(&root, &mut seen, &mut mentions);
+    mentions
+}
+
+/// 将内容中指向 `target_id` 的 `@` 引用改写为新名称
+///
+/// 同步更新 mention 节点的 `mentionName` 和显示文本 `text`。
+/// 返回改写后的内容和改写的引用数量；没有需要改写的引用或内容不是 JSON 时返回 None。
+pub fn rewrite_mentions(content: &str, target_id: &str, new_name: &str) -> Option<(String, usize)> {
+    let mut root = serde_json::from_str::<Value>(content).ok()?;
+
+    let rewritten = rewrite_mention_nodes(&mut root, target_id, new_name);
+    if rewritten == 0 {
+        return None;
+    }
+    serde_json::to_string(&root)
+        .ok()
+        .map(|text| (text, rewritten))
+}
+
+fn rewrite_mention_nodes(value: &mut Value, target_id: &str, new_name: &str) -> usize {
+    match value {
+        Value::Object(map) => {
+            let mut rewritten = 0;
+            let is_target = map.get("type").and_then(Value::as_str) == Some("mention")
+                && map.get("roleId").and_then(Value::as_str) == Some(target_id);
+            if is_target {
+                let text = format!("@{}", new_name);
+                let stale = map.get("mentionName").and_then(Value::as_str) != Some(new_name)
+                    || map.get("text").and_then(Value::as_str) != Some(text.as_str());
+                if stale {
+                    map.insert("mentionName".to_string(), Value::from(new_name));
+                    map.insert("text".to_string(), Value::from(text));
+                    rewritten += 1;
+                }
+            }
+            for child in map.values_mut() {
+                rewritten += rewrite_mention_nodes(child, target_id, new_name);
+            }
+            rewritten
+        }
+        Value::Array(items) => items
+            .iter_mut()
+            .map(|item| rewrite_mention_nodes(item, target_id, new_name))
+            .sum(),
+        _ => 0,
+    }
+}
+
+fn collect_mentions(value: &Value, seen: &mut HashSet<String>, out: &mut Vec<MentionRef>) {
+    match value {
+        Value::Object(map) => {
+            if map.get("type").and_then(Value::as_str) == Some("mention") {
+                if let Some(node_id) = map.get("roleId").and_then(Value::as_str) {
+                    if !node_id.is_empty() && seen.insert(node_id.to_string()) {
+                        let name = map
+                            .get("mentionName")
+                            .and_then(Value::as_str)
+                            .unwrap_or_default();
+                        out.push(MentionRef {
+                            node_id: node_id.to_string(),
+                            name: name.to_string(),
+                        });
+                    }
+                }
+            }
+            for child in map.values() {
+                collect_mentions(child, seen, out);
+            }
+        }
+        Value::Array(items) => {
+            for item in items {
+                collect_mentions(item, seen, out);
+            }
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mention(name: &str, id: &str) -> String {
+        format!(
+            r#"{{"type":"mention","mentionName":"{}","roleId":"{}","text":"@{}","version":1}}"#,
+            name, id, name
+        )
+    }
+
+    #[test]
+    fn test_extract_mentions_nested() {
+        let content = format!(
+            r#"{{"root":{{"children":[{{"type":"paragraph","children":[{},{{"type":"text","text":" 和 "}},{}]}}]}}}}"#,
+            mention("张三", "node-a"),
+            mention("李四", "node-b")
+        );
+
+        let mentions = extract_mentions(&content);
+        assert_eq!(
+            mentions,
+            vec![
+                MentionRef {
+                    node_id: "node-a".into(),
+                    name: "张三".into()
+                },
+                MentionRef {
+                    node_id: "node-b".into(),
+                    name: "李四".into()
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_extract_mentions_dedup() {
+        let content = format!(
+            r#"{{"root":{{"children":[{},{}]}}}}"#,
+            mention("张三", "node-a"),
+            mention("张三丰", "node-a")
+        );
+
+        let mentions = extract_mentions(&content);
+        assert_eq!(mentions.len(), 1);
+        assert_eq!(mentions[0].name, "张三");
+    }
+
+    #[test]
+    fn test_rewrite_mentions() {
+        let content = format!(
+            r#"{{"root":{{"children":[{},{},{}]}}}}"#,
+            mention("旧标题", "node-a"),
+            mention("其他", "node-b"),
+            mention("旧标题", "node-a")
+        );
+
+        let (rewritten, count) = rewrite_mentions(&content, "node-a", "新标题").unwrap();
+        assert_eq!(count, 2);
+        let mentions = extract_mentions(&rewritten);
+        assert_eq!(mentions[0].name, "新标题");
+        assert_eq!(mentions[1].name, "其他");
+        assert!(rewritten.contains("@新标题"));
+        assert!(!rewritten.contains("旧标题"));
+
+        // 已是最新名称时不需要改写
+        assert!(rewrite_mentions(&rewritten, "node-a", "新标题").is_none());
+        assert!(rewrite_mentions("纯文本", "node-a", "新标题").is_none());
+    }
+
+    #[test]
+    fn test_extract_mentions_invalid_content() {
+        assert!(extract_mentions("纯文本内容").is_empty());
+        assert!(extract_mentions("{}").is_empty());
+        assert!(extract_mentions(r#"{"type":"mention","roleId":""}"#).is_empty());
+    }
+}
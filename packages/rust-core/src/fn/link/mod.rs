@@ -0,0 +1,5 @@
+//! Link 纯函数模块
+
+pub mod link_fn;
+
+pub use link_fn::*;
@@ -3,10 +3,34 @@
 //! 包含所有业务逻辑的纯函数实现。
 //! 这些函数不包含副作用，只进行数据转换。
 
+pub mod auth;
 pub mod backup;
+pub mod change_log;
+pub mod content;
 pub mod crypto;
+pub mod diary;
+pub mod graph;
+pub mod ledger;
+pub mod link;
 pub mod node;
+pub mod search;
+pub mod sync;
+pub mod tag;
+pub mod task;
+pub mod writing;
 
+pub use auth::*;
 pub use backup::*;
+pub use change_log::*;
+pub use content::*;
 pub use crypto::*;
+pub use diary::*;
+pub use graph::*;
+pub use ledger::*;
+pub use link::*;
 pub use node::*;
+pub use search::*;
+pub use sync::*;
+pub use tag::*;
+pub use task::*;
+pub use writing::*;
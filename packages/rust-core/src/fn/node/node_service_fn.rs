@@ -6,7 +6,7 @@ use crate::db::{content_db_fn, node_db_fn};
 use crate::r#fn::node::node_transform_fn;
 use crate::types::error::{AppError, AppResult};
 use crate::types::node::{node_entity, NodeType};
-use sea_orm::DatabaseConnection;
+use sea_orm::{ConnectionTrait, DatabaseConnection};
 use tracing::info;
 
 // ============================================================================
@@ -54,25 +54,22 @@ pub async fn create_node_with_content(
 
 /// 删除节点及其所有子节点
 ///
-/// 递归删除所有子节点，然后删除节点本身
+/// 通过递归 CTE 一次性收集并删除所有后代和节点本身
 /// 内容会通过数据库外键级联删除
 pub async fn delete_node_recursive(db: &DatabaseConnection, id: &str) -> AppResult<()> {
-    // 先递归删除所有子节点
-    let children = node_db_fn::find_children(db, id).await?;
-    for child in children {
-        Box::pin(delete_node_recursive(db, &child.id)).await?;
+    if node_db_fn::find_by_id(db, id).await?.is_none() {
+        return Err(AppError::not_found(format!("Node {}", id)));
     }
 
-    // 删除节点本身（内容会级联删除）
-    node_db_fn::delete(db, id).await?;
+    node_db_fn::delete_batch(db, vec![id.to_string()]).await?;
 
     info!("递归删除节点: {}", id);
     Ok(())
 }
 
 /// 复制节点（包括内容）
-pub async fn duplicate_node(
-    db: &DatabaseConnection,
+pub async fn duplicate_node<C: ConnectionTrait>(
+    db: &C,
     source_id: &str,
     new_title: Option<String>,
 ) -> AppResult<node_entity::Model> {
@@ -2,7 +2,8 @@
 //!
 //! 包含节点数据转换的纯函数
 
-use crate::types::node::{node_entity, NodeType};
+use crate::types::node::{node_entity, NodeResponse, NodeTreeResponse, NodeType};
+use std::collections::HashMap;
 
 // ============================================================================
 // 转换函数
@@ -50,6 +51,43 @@ pub fn is_root_node(node: &node_entity::Model) -> bool {
     node.parent_id.is_none()
 }
 
+/// 将扁平的节点列表组装为嵌套树
+///
+/// - `root_parent_id`: 顶层节点的父节点 ID（None 表示工作区根级别）
+/// - 同级节点保持输入中的相对顺序
+/// - 父节点不在列表中的节点（除顶层外）会被忽略
+pub fn build_node_tree(
+    nodes: Vec<node_entity::Model>,
+    root_parent_id: Option<&str>,
+) -> Vec<NodeTreeResponse> {
+    let mut children_map: HashMap<Option<String>, Vec<node_entity::Model>> = HashMap::new();
+    for node in nodes {
+        children_map
+            .entry(node.parent_id.clone())
+            .or_default()
+            .push(node);
+    }
+
+    fn build(
+        parent_id: Option<String>,
+        children_map: &mut HashMap<Option<String>, Vec<node_entity::Model>>,
+    ) -> Vec<NodeTreeResponse> {
+        let children = children_map.remove(&parent_id).unwrap_or_default();
+        children
+            .into_iter()
+            .map(|child| {
+                let grandchildren = build(Some(child.id.clone()), children_map);
+                NodeTreeResponse {
+                    node: NodeResponse::from(child),
+                    children: grandchildren,
+                }
+            })
+            .collect()
+    }
+
+    build(root_parent_id.map(str::to_string), &mut children_map)
+}
+
 // ============================================================================
 // 测试
 // ============================================================================
@@ -216,6 +254,56 @@ mod tests {
         assert!(is_root_node(&model));
     }
 
+    fn tree_model(id: &str, parent_id: Option<&str>, sort_order: i32) -> node_entity::Model {
+        node_entity::Model {
+            id: id.to_string(),
+            workspace_id: "ws".to_string(),
+            parent_id: parent_id.map(str::to_string),
+            title: id.to_string(),
+            node_type: NodeType::File,
+            is_collapsed: false,
+            sort_order,
+            tags: None,
+            created_at: 0,
+            updated_at: 0,
+        }
+    }
+
+    #[test]
+    fn test_build_node_tree_nests_children() {
+        let nodes = vec![
+            tree_model("a", None, 0),
+            tree_model("b", None, 1),
+            tree_model("a1", Some("a"), 0),
+            tree_model("a2", Some("a"), 1),
+            tree_model("a1x", Some("a1"), 0),
+        ];
+
+        let tree = build_node_tree(nodes, None);
+
+        assert_eq!(tree.len(), 2);
+        assert_eq!(tree[0].node.id, "a");
+        assert_eq!(tree[1].node.id, "b");
+        assert_eq!(tree[0].children.len(), 2);
+        assert_eq!(tree[0].children[0].node.id, "a1");
+        assert_eq!(tree[0].children[1].node.id, "a2");
+        assert_eq!(tree[0].children[0].children[0].node.id, "a1x");
+        assert!(tree[1].children.is_empty());
+    }
+
+    #[test]
+    fn test_build_node_tree_from_subtree_root() {
+        let nodes = vec![
+            tree_model("c1", Some("root"), 0),
+            tree_model("c2", Some("root"), 1),
+        ];
+
+        let tree = build_node_tree(nodes, Some("root"));
+
+        assert_eq!(tree.len(), 2);
+        assert_eq!(tree[0].node.id, "c1");
+    }
+
     #[test]
     fn test_is_root_node_false() {
         let model = node_entity::Model {
@@ -0,0 +1,5 @@
+//! Search 纯函数模块
+
+pub mod search_fn;
+
+pub use search_fn::*;
@@ -0,0 +1,533 @@
+//! Search 纯函数
+//!
+//! 搜索查询语言的解析与求值。
+//!
+//! ## 语法
+//!
+//! 条件之间以空白分隔，全部满足时节点匹配；条件前加 `-` 表示取反。
+//!
+//! | 条件 | 说明 |
+//! |------|------|
+//! | `word` | 标题或正文包含该词（不区分大小写） |
+//! | `"exact phrase"` | 标题或正文包含该短语 |
+//! | `type:diary` | 节点类型 |
+//! | `tag:travel` | 标签（包含子标签，如 `travel/japan`） |
+//! | `title:plan` / `title:"trip plan"` | 标题包含 |
+//! | `created:>2026-01-01` | 创建日期，支持 `>` `>=` `<` `<=`、单日和 `a..b` 区间（UTC） |
+//! | `updated:2026-01-01..2026-01-31` | 更新日期，语法同 `created:` |
+//!
+//! 未知的 `key:` 前缀按普通文本处理（如 `http://`）。
+
+use std::collections::HashMap;
+
+use chrono::NaiveDate;
+use serde_json::Value;
+
+use crate::r#fn::tag::{normalize_tag_path, tag_matches};
+use crate::types::node::{node_entity, NodeType};
+
+/// 一天的毫秒数
+const DAY_MILLIS: i64 = 24 * 60 * 60 * 1000;
+
+/// 解析后的搜索查询（所有条件为 AND 关系）
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SearchQuery {
+    /// 查询条件
+    pub terms: Vec<SearchTerm>,
+}
+
+/// 单个查询条件
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SearchTerm {
+    /// 是否取反（`-tag:draft`）
+    pub negated: bool,
+    /// 条件内容
+    pub filter: SearchFilter,
+}
+
+/// 查询条件类型
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SearchFilter {
+    /// 标题或正文包含（已转为小写）
+    Text(String),
+    /// 标题包含（已转为小写）
+    Title(String),
+    /// 节点类型
+    Type(NodeType),
+    /// 标签（规范化后的路径，包含子标签）
+    Tag(String),
+    /// 创建时间范围
+    Created(DateRange),
+    /// 更新时间范围
+    Updated(DateRange),
+}
+
+/// 时间范围（毫秒时间戳，左闭右开），None 表示不限
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DateRange {
+    /// 起始时间（包含）
+    pub start: Option<i64>,
+    /// 结束时间（不包含）
+    pub end: Option<i64>,
+}
+
+impl DateRange {
+    /// 判断时间戳是否在范围内
+    pub fn contains(&self, timestamp: i64) -> bool {
+        self.start.is_none_or(|start| timestamp >= start)
+            && self.end.is_none_or(|end| timestamp < end)
+    }
+}
+
+impl SearchQuery {
+    /// 是否需要正文内容才能求值
+    pub fn needs_content(&self) -> bool {
+        self.terms
+            .iter()
+            .any(|term| matches!(term.filter, SearchFilter::Text(_)))
+    }
+
+    /// 判断节点是否满足所有条件
+    ///
+    /// `text` 为正文纯文本（见 [`content_plain_text`]），大小写不限
+    pub fn matches(&self, node: &node_entity::Model, tags: &[String], text: &str) -> bool {
+        let title = node.title.to_lowercase();
+        let text = text.to_lowercase();
+        self.terms.iter().all(|term| {
+            let hit = match &term.filter {
+                SearchFilter::Text(word) => title.contains(word) || text.contains(word),
+                SearchFilter::Title(word) => title.contains(word),
+                SearchFilter::Type(node_type) => &node.node_type == node_type,
+                SearchFilter::Tag(query) => tags.iter().any(|tag| tag_matches(tag, query)),
+                SearchFilter::Created(range) => range.contains(node.created_at),
+                SearchFilter::Updated(range) => range.contains(node.updated_at),
+            };
+            hit != term.negated
+        })
+    }
+}
+
+// ============================================================================
+// 纯函数（数据转换）
+// ============================================================================
+
+/// 解析查询语句
+///
+/// 语法错误（未闭合的引号、无效的类型或日期等）返回错误描述
+pub fn parse_search_query(input: &str) -> Result<SearchQuery, String> {
+    let mut terms = Vec::new();
+    for token in tokenize(input)? {
+        let filter = match token.key.as_deref() {
+            None => {
+                if token.value.is_empty() {
+                    continue;
+                }
+                SearchFilter::Text(token.value.to_lowercase())
+            }
+            Some(key) if token.value.is_empty() => {
+                return Err(format!("条件 {}: 缺少值", key));
+            }
+            Some("type") => SearchFilter::Type(token.value.to_lowercase().parse()?),
+            Some("tag") => {
+                let tag = normalize_tag_path(&token.value);
+                if tag.is_empty() {
+                    return Err(format!("无效的标签: {}", token.value));
+                }
+                SearchFilter::Tag(tag)
+            }
+            Some("title") => SearchFilter::Title(token.value.to_lowercase()),
+            Some("created") => SearchFilter::Created(parse_date_range(&token.value)?),
+            Some("updated") => SearchFilter::Updated(parse_date_range(&token.value)?),
+            Some(key) => SearchFilter::Text(format!("{}:{}", key, token.value).to_lowercase()),
+        };
+        terms.push(SearchTerm {
+            negated: token.negated,
+            filter,
+        });
+    }
+
+    if terms.is_empty() {
+        return Err("查询语句不能为空".to_string());
+    }
+    Ok(SearchQuery { terms })
+}
+
+/// 在节点列表上执行查询，结果按更新时间倒序
+///
+/// `contents` 为节点 ID 到内容的映射，查询不含正文条件时可以为空
+pub fn filter_nodes(
+    query: &SearchQuery,
+    nodes: Vec<node_entity::Model>,
+    contents: &HashMap<String, String>,
+) -> Vec<node_entity::Model> {
+    let mut matched: Vec<node_entity::Model> = nodes
+        .into_iter()
+        .filter(|node| {
+            let tags: Vec<String> = node
+                .tags
+                .as_deref()
+                .and_then(|t| serde_json::from_str(t).ok())
+                .unwrap_or_default();
+            let text = contents
+                .get(&node.id)
+                .map(|content| content_plain_text(content))
+                .unwrap_or_default();
+            query.matches(node, &tags, &text)
+        })
+        .collect();
+
+    matched.sort_by(|a, b| {
+        b.updated_at
+            .cmp(&a.updated_at)
+            .then_with(|| a.title.cmp(&b.title))
+    });
+    matched
+}
+
+/// 提取内容的纯文本
+///
+/// Lexical JSON 收集所有 `text` 字段，块级节点之间以换行分隔；
+/// 内容不是 JSON 时（代码、PlantUML 等）原样返回。
+pub fn content_plain_text(content: &str) -> String {
+    let Ok(root) = serde_json::from_str::<Value>(content) else {
+        return content.to_string();
+    };
+
+    let mut text = String::new();
+    collect_text(&root, &mut text);
+    text.trim_end().to_string()
+}
+
+fn collect_text(value: &Value, out: &mut String) {
+    match value {
+        Value::Object(map) => {
+            if let Some(text) = map.get("text").and_then(Value::as_str) {
+                out.push_str(text);
+            }
+            match map.get("children") {
+                Some(Value::Array(children)) => {
+                    for child in children {
+                        collect_text(child, out);
+                    }
+                    let inline = matches!(
+                        map.get("type").and_then(Value::as_str),
+                        Some("link" | "autolink")
+                    );
+                    if !inline && !out.is_empty() && !out.ends_with('\n') {
+                        out.push('\n');
+                    }
+                }
+                _ => {
+                    if let Some(root) = map.get("root") {
+                        collect_text(root, out);
+                    }
+                }
+            }
+        }
+        Value::Array(items) => {
+            for item in items {
+                collect_text(item, out);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// 查询语句中的一个词
+struct Token {
+    negated: bool,
+    key: Option<String>,
+    value: String,
+}
+
+/// 按空白切分查询语句，处理引号、`-` 前缀和 `key:` 前缀
+fn tokenize(input: &str) -> Result<Vec<Token>, String> {
+    let mut tokens = Vec::new();
+    let mut chars = input.chars().peekable();
+
+    loop {
+        while chars.next_if(|c| c.is_whitespace()).is_some() {}
+        if chars.peek().is_none() {
+            break;
+        }
+
+        let negated = chars.next_if_eq(&'-').is_some();
+        let mut key = None;
+        let mut value = String::new();
+        let mut quoted = false;
+
+        while let Some(c) = chars.next_if(|c| !c.is_whitespace()) {
+            match c {
+                '"' => {
+                    quoted = true;
+                    loop {
+                        match chars.next() {
+                            Some('"') => break,
+                            Some(c) => value.push(c),
+                            None => return Err("未闭合的引号".to_string()),
+                        }
+                    }
+                }
+                ':' if key.is_none() && !quoted && !value.is_empty() => {
+                    key = Some(std::mem::take(&mut value).to_lowercase());
+                }
+                c => value.push(c),
+            }
+        }
+
+        tokens.push(Token {
+            negated,
+            key,
+            value,
+        });
+    }
+    Ok(tokens)
+}
+
+/// 解析日期范围：`>d`、`>=d`、`<d`、`<=d`、`d`、`a..b`（两端包含，可省略一端）
+fn parse_date_range(value: &str) -> Result<DateRange, String> {
+    if let Some((from, to)) = value.split_once("..") {
+        if from.is_empty() && to.is_empty() {
+            return Err(format!("无效的日期范围: {}", value));
+        }
+        let start = (!from.is_empty()).then(|| day_start(from)).transpose()?;
+        let end = (!to.is_empty())
+            .then(|| day_start(to).map(|t| t + DAY_MILLIS))
+            .transpose()?;
+        return Ok(DateRange { start, end });
+    }
+
+    let range = if let Some(date) = value.strip_prefix(">=") {
+        DateRange {
+            start: Some(day_start(date)?),
+            end: None,
+        }
+    } else if let Some(date) = value.strip_prefix("<=") {
+        DateRange {
+            start: None,
+            end: Some(day_start(date)? + DAY_MILLIS),
+        }
+    } else if let Some(date) = value.strip_prefix('>') {
+        DateRange {
+            start: Some(day_start(date)? + DAY_MILLIS),
+            end: None,
+        }
+    } else if let Some(date) = value.strip_prefix('<') {
+        DateRange {
+            start: None,
+            end: Some(day_start(date)?),
+        }
+    } else {
+        let start = day_start(value)?;
+        DateRange {
+            start: Some(start),
+            end: Some(start + DAY_MILLIS),
+        }
+    };
+    Ok(range)
+}
+
+/// `YYYY-MM-DD` 当天 00:00（UTC）的毫秒时间戳
+fn day_start(date: &str) -> Result<i64, String> {
+    NaiveDate::parse_from_str(date, "%Y-%m-%d")
+        .ok()
+        .and_then(|d| d.and_hms_opt(0, 0, 0))
+        .map(|dt| dt.and_utc().timestamp_millis())
+        .ok_or_else(|| format!("无效的日期: {}", date))
+}
+
+// ============================================================================
+// 测试
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 2026-01-01 00:00:00 UTC
+    const JAN_1: i64 = 1767225600000;
+
+    fn node(title: &str, node_type: NodeType, updated_at: i64) -> node_entity::Model {
+        node_entity::Model {
+            id: title.to_string(),
+            workspace_id: "ws-1".to_string(),
+            parent_id: None,
+            title: title.to_string(),
+            node_type,
+            is_collapsed: false,
+            sort_order: 0,
+            tags: None,
+            created_at: updated_at,
+            updated_at,
+        }
+    }
+
+    #[test]
+    fn test_parse_search_query() {
+        let query = parse_search_query(
+            r#"type:diary tag:travel updated:>2026-01-01 "Exact Phrase" -tag:draft"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            query.terms,
+            vec![
+                SearchTerm {
+                    negated: false,
+                    filter: SearchFilter::Type(NodeType::Diary),
+                },
+                SearchTerm {
+                    negated: false,
+                    filter: SearchFilter::Tag("travel".to_string()),
+                },
+                SearchTerm {
+                    negated: false,
+                    filter: SearchFilter::Updated(DateRange {
+                        start: Some(JAN_1 + DAY_MILLIS),
+                        end: None,
+                    }),
+                },
+                SearchTerm {
+                    negated: false,
+                    filter: SearchFilter::Text("exact phrase".to_string()),
+                },
+                SearchTerm {
+                    negated: true,
+                    filter: SearchFilter::Tag("draft".to_string()),
+                },
+            ]
+        );
+        assert!(query.needs_content());
+    }
+
+    #[test]
+    fn test_parse_quoted_values_and_unknown_keys() {
+        let query = parse_search_query(r#"title:"Trip Plan" https://example.com"#).unwrap();
+        assert_eq!(
+            query.terms[0].filter,
+            SearchFilter::Title("trip plan".to_string())
+        );
+        assert_eq!(
+            query.terms[1].filter,
+            SearchFilter::Text("https://example.com".to_string())
+        );
+        assert!(!parse_search_query("title:x").unwrap().needs_content());
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(parse_search_query("").is_err());
+        assert!(parse_search_query("   ").is_err());
+        assert!(parse_search_query(r#""unclosed"#).is_err());
+        assert!(parse_search_query("type:unknown").is_err());
+        assert!(parse_search_query("tag:").is_err());
+        assert!(parse_search_query("tag:/").is_err());
+        assert!(parse_search_query("updated:>yesterday").is_err());
+        assert!(parse_search_query("created:..").is_err());
+    }
+
+    #[test]
+    fn test_parse_date_range() {
+        let day = DateRange {
+            start: Some(JAN_1),
+            end: Some(JAN_1 + DAY_MILLIS),
+        };
+        assert_eq!(parse_date_range("2026-01-01").unwrap(), day);
+        assert_eq!(
+            parse_date_range(">=2026-01-01").unwrap(),
+            DateRange {
+                start: Some(JAN_1),
+                end: None
+            }
+        );
+        assert_eq!(
+            parse_date_range("<2026-01-01").unwrap(),
+            DateRange {
+                start: None,
+                end: Some(JAN_1)
+            }
+        );
+        assert_eq!(
+            parse_date_range("<=2026-01-01").unwrap(),
+            DateRange {
+                start: None,
+                end: Some(JAN_1 + DAY_MILLIS)
+            }
+        );
+        assert_eq!(
+            parse_date_range("2026-01-01..2026-01-02").unwrap(),
+            DateRange {
+                start: Some(JAN_1),
+                end: Some(JAN_1 + 2 * DAY_MILLIS)
+            }
+        );
+        assert_eq!(
+            parse_date_range("..2026-01-01").unwrap(),
+            DateRange {
+                start: None,
+                end: Some(JAN_1 + DAY_MILLIS)
+            }
+        );
+        assert!(day.contains(JAN_1));
+        assert!(!day.contains(JAN_1 + DAY_MILLIS));
+    }
+
+    #[test]
+    fn test_query_matches() {
+        let query = parse_search_query("type:diary tag:travel -tag:travel/draft kyoto").unwrap();
+        let diary = node("京都之旅", NodeType::Diary, JAN_1);
+        let tags = vec!["travel/japan".to_string()];
+
+        assert!(query.matches(&diary, &tags, "Day one in Kyoto"));
+        assert!(!query.matches(&diary, &tags, "Day one in Osaka"));
+        assert!(!query.matches(&diary, &["travel/draft".to_string()], "kyoto"));
+        assert!(!query.matches(&diary, &["work".to_string()], "kyoto"));
+
+        let note = node("京都之旅", NodeType::Note, JAN_1);
+        assert!(!query.matches(&note, &tags, "kyoto"));
+
+        let title_query = parse_search_query("京都").unwrap();
+        assert!(title_query.matches(&note, &[], ""));
+    }
+
+    #[test]
+    fn test_filter_nodes() {
+        let mut a = node("a", NodeType::File, JAN_1);
+        a.tags = Some(r#"["travel"]"#.to_string());
+        let b = node("b", NodeType::File, JAN_1 + DAY_MILLIS);
+        let mut c = node("c", NodeType::File, JAN_1 + 2 * DAY_MILLIS);
+        c.tags = Some(r#"["travel"]"#.to_string());
+
+        let contents = HashMap::from([
+            (
+                "a".to_string(),
+                r#"{"root":{"children":[{"type":"paragraph","children":[{"type":"text","text":"hello world"}]}]}}"#.to_string(),
+            ),
+            ("b".to_string(), "hello plain".to_string()),
+        ]);
+
+        let query = parse_search_query("hello").unwrap();
+        let result = filter_nodes(&query, vec![a.clone(), b.clone(), c.clone()], &contents);
+        let ids: Vec<&str> = result.iter().map(|n| n.id.as_str()).collect();
+        assert_eq!(ids, vec!["b", "a"]);
+
+        let query = parse_search_query("tag:travel").unwrap();
+        let result = filter_nodes(&query, vec![a, b, c], &HashMap::new());
+        let ids: Vec<&str> = result.iter().map(|n| n.id.as_str()).collect();
+        assert_eq!(ids, vec!["c", "a"]);
+    }
+
+    #[test]
+    fn test_content_plain_text() {
+        let content = r#"{"root":{"children":[
+            {"type":"heading","children":[{"type":"text","text":"Title"}]},
+            {"type":"paragraph","children":[
+                {"type":"text","text":"see "},
+                {"type":"link","children":[{"type":"text","text":"docs"}]},
+                {"type":"text","text":" here"}
+            ]}
+        ]}}"#;
+        assert_eq!(content_plain_text(content), "Title\nsee docs here");
+        assert_eq!(content_plain_text("@startuml"), "@startuml");
+    }
+}
@@ -0,0 +1,5 @@
+//! Sync 纯函数模块
+
+pub mod sync_fn;
+
+pub use sync_fn::*;
@@ -0,0 +1,197 @@
+//! Sync 纯函数
+//!
+//! 推送/拉取同步中与数据库无关的部分：
+//! - 服务器地址规范化、同步版本记录 ID
+//! - 按依赖关系排序变更（工作区 → 父节点 → 子节点 → 内容 → 标签 → 删除）
+//! - 计算拉取游标（有依赖未满足的变更时，游标停在它之前，下次重新拉取）
+
+use std::collections::HashMap;
+
+use sea_orm::ActiveEnum;
+
+use crate::types::change_log::{ChangeEntity, ChangeOperation};
+use crate::types::error::{AppError, AppResult};
+use crate::types::sync::{SyncChange, SyncEntityData};
+
+/// 规范化服务器地址（去掉首尾空白和末尾的 `/`）
+pub fn normalize_server_url(url: &str) -> AppResult<String> {
+    let url = url.trim().trim_end_matches('/');
+    if !(url.starts_with("http://") || url.starts_with("https://")) {
+        return Err(AppError::validation(
+            "服务器地址必须以 http:// 或 https:// 开头",
+        ));
+    }
+    Ok(url.to_string())
+}
+
+/// 同步版本记录 ID（格式：remote_id|entity_type|entity_id）
+pub fn sync_version_id(remote_id: &str, entity_type: ChangeEntity, entity_id: &str) -> String {
+    format!("{}|{}|{}", remote_id, entity_type.to_value(), entity_id)
+}
+
+/// 实体类型是否参与同步（清除数据不同步）
+pub fn is_syncable(entity_type: ChangeEntity) -> bool {
+    entity_type != ChangeEntity::Database
+}
+
+/// 按依赖关系排序变更
+///
+/// 工作区和父节点先于子节点创建，删除放在最后（子节点先于工作区删除）；
+/// 同一类变更保持原有顺序。
+pub fn order_changes(changes: Vec<SyncChange>) -> Vec<SyncChange> {
+    // 本批次中每个节点的父节点，用于计算节点深度
+    let parents: HashMap<&str, Option<&str>> = changes
+        .iter()
+        .filter_map(|c| match &c.data {
+            Some(SyncEntityData::Node(node)) if c.operation == ChangeOperation::Upsert => {
+                Some((node.id.as_str(), node.parent_id.as_deref()))
+            }
+            _ => None,
+        })
+        .collect();
+
+    let depth = |id: &str| -> usize {
+        let mut depth = 0;
+        let mut current = parents.get(id).copied().flatten();
+        while let Some(parent) = current {
+            depth += 1;
+            // 防御环形引用
+            if depth > parents.len() {
+                break;
+            }
+            current = parents.get(parent).copied().flatten();
+        }
+        depth
+    };
+
+    let mut keyed: Vec<((u8, usize), SyncChange)> = changes
+        .iter()
+        .map(|c| {
+            let rank = match (c.entity_type, c.operation) {
+                (ChangeEntity::Workspace, ChangeOperation::Upsert) => 0,
+                (ChangeEntity::Node, ChangeOperation::Upsert) => 1,
+                (ChangeEntity::Content, _) => 2,
+                (ChangeEntity::Tags, _) => 3,
+                (ChangeEntity::Node, _) => 4,
+                _ => 5,
+            };
+            let depth = if rank == 1 { depth(&c.entity_id) } else { 0 };
+            ((rank, depth), c.clone())
+        })
+        .collect();
+    keyed.sort_by_key(|(key, _)| *key);
+    keyed.into_iter().map(|(_, c)| c).collect()
+}
+
+/// 计算应保存的拉取游标
+///
+/// `pending` 是依赖未满足、尚未应用的变更，游标停在其中最早的一条之前。
+pub fn pull_cursor(next_since: i64, pending: &[SyncChange]) -> i64 {
+    pending
+        .iter()
+        .map(|c| c.version - 1)
+        .fold(next_since, i64::min)
+}
+
+// ============================================================================
+// 测试
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::node::{NodeModel, NodeType};
+
+    fn node_change(id: &str, parent_id: Option<&str>, version: i64) -> SyncChange {
+        SyncChange {
+            entity_type: ChangeEntity::Node,
+            entity_id: id.to_string(),
+            workspace_id: Some("ws".to_string()),
+            operation: ChangeOperation::Upsert,
+            version,
+            data: Some(SyncEntityData::Node(NodeModel {
+                id: id.to_string(),
+                workspace_id: "ws".to_string(),
+                parent_id: parent_id.map(str::to_string),
+                title: id.to_string(),
+                node_type: NodeType::File,
+                is_collapsed: false,
+                sort_order: 0,
+                tags: None,
+                created_at: 0,
+                updated_at: 0,
+            })),
+        }
+    }
+
+    fn other_change(entity_type: ChangeEntity, operation: ChangeOperation) -> SyncChange {
+        SyncChange {
+            entity_type,
+            entity_id: "ws".to_string(),
+            workspace_id: Some("ws".to_string()),
+            operation,
+            version: 1,
+            data: None,
+        }
+    }
+
+    #[test]
+    fn test_normalize_server_url() {
+        assert_eq!(
+            normalize_server_url(" https://grain.example.com/ ").unwrap(),
+            "https://grain.example.com"
+        );
+        assert!(normalize_server_url("grain.example.com").is_err());
+    }
+
+    #[test]
+    fn test_sync_version_id() {
+        assert_eq!(
+            sync_version_id("https://a", ChangeEntity::Content, "n1"),
+            "https://a|content|n1"
+        );
+    }
+
+    #[test]
+    fn test_order_changes() {
+        let ordered = order_changes(vec![
+            other_change(ChangeEntity::Workspace, ChangeOperation::Delete),
+            other_change(ChangeEntity::Tags, ChangeOperation::Upsert),
+            node_change("child", Some("parent"), 3),
+            SyncChange {
+                operation: ChangeOperation::Delete,
+                data: None,
+                ..node_change("gone", None, 4)
+            },
+            node_change("parent", Some("root"), 2),
+            node_change("root", None, 5),
+            other_change(ChangeEntity::Workspace, ChangeOperation::Upsert),
+        ]);
+
+        let ids: Vec<(ChangeEntity, &str)> = ordered
+            .iter()
+            .map(|c| (c.entity_type, c.entity_id.as_str()))
+            .collect();
+        assert_eq!(
+            ids,
+            vec![
+                (ChangeEntity::Workspace, "ws"),
+                (ChangeEntity::Node, "root"),
+                (ChangeEntity::Node, "parent"),
+                (ChangeEntity::Node, "child"),
+                (ChangeEntity::Tags, "ws"),
+                (ChangeEntity::Node, "gone"),
+                (ChangeEntity::Workspace, "ws"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_pull_cursor() {
+        assert_eq!(pull_cursor(10, &[]), 10);
+        assert_eq!(
+            pull_cursor(10, &[node_change("a", None, 7), node_change("b", None, 4)]),
+            3
+        );
+    }
+}
@@ -0,0 +1,5 @@
+//! Tag 纯函数模块
+
+pub mod tag_fn;
+
+pub use tag_fn::*;
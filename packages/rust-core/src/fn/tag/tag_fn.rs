@@ -0,0 +1,574 @@
+//! Tag 纯函数
+//!
+//! 层级标签（`/` 分隔）的匹配、标签树构建，以及节点标签和内容中 `#[tag]` 的改写
+
+use serde_json::Value;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+
+use crate::types::tag::{TagModel, TagTreeNode};
+
+/// 标签层级分隔符
+pub const TAG_PATH_SEPARATOR: char = '/';
+
+/// 一次标签改名（`from` 及其子标签改为 `to` 下的同名路径）
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TagRename {
+    /// 原标签路径
+    pub from: String,
+    /// 新标签路径
+    pub to: String,
+}
+
+// ============================================================================
+// 纯函数（数据转换）
+// ============================================================================
+
+/// 判断标签是否匹配查询标签
+///
+/// 标签等于查询标签，或是其子标签（如 `project/alpha` 匹配 `project`）时返回 true。
+/// `projects` 不匹配 `project`。
+pub fn tag_matches(tag: &str, query: &str) -> bool {
+    match tag.strip_prefix(query) {
+        Some(rest) => rest.is_empty() || rest.starts_with(TAG_PATH_SEPARATOR),
+        None => false,
+    }
+}
+
+/// 返回标签路径的所有前缀（含自身），忽略空层级和层级两端的空白
+///
+/// `a/b/c` -> `["a", "a/b", "a/b/c"]`
+pub fn tag_path_prefixes(tag: &str) -> Vec<String> {
+    let mut prefixes = Vec::new();
+    let mut current = String::new();
+    for segment in tag
+        .split(TAG_PATH_SEPARATOR)
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+    {
+        if !current.is_empty() {
+            current.push(TAG_PATH_SEPARATOR);
+        }
+        current.push_str(segment);
+        prefixes.push(current.clone());
+    }
+    prefixes
+}
+
+/// 规范化标签路径（`/a// b/` -> `a/b`），无有效层级时返回空字符串
+pub fn normalize_tag_path(tag: &str) -> String {
+    tag_path_prefixes(tag).pop().unwrap_or_default()
+}
+
+/// 按改名规则计算标签的新路径
+///
+/// 多条规则都匹配时使用 `from` 最长（最具体）的一条；不匹配时返回 None。
+pub fn rename_tag_path(tag: &str, renames: &[TagRename]) -> Option<String> {
+    renames
+        .iter()
+        .filter(|rename| tag_matches(tag, &rename.from))
+        .max_by_key(|rename| rename.from.len())
+        .map(|rename| format!("{}{}", rename.to, &tag[rename.from.len()..]))
+}
+
+/// 将标签记录上的别名转换为改名规则（别名 -> 标签名）
+///
+/// 别名与标签名一样支持层级：别名 `js` 指向 `lang/javascript` 时，
+/// `js/react` 解析为 `lang/javascript/react`。
+pub fn alias_renames(tags: &[TagModel]) -> Vec<TagRename> {
+    tags.iter()
+        .flat_map(|tag| {
+            tag.alias_list().into_iter().map(|alias| TagRename {
+                from: alias,
+                to: tag.name.clone(),
+            })
+        })
+        .collect()
+}
+
+/// 设置标签别名
+///
+/// 别名经过规范化和去重，并去掉与标签名相同的别名；没有别名时存为 None。
+pub fn set_tag_aliases(tag: &mut TagModel, aliases: Vec<String>) {
+    let mut seen = HashSet::new();
+    let aliases: Vec<String> = aliases
+        .iter()
+        .map(|alias| normalize_tag_path(alias))
+        .filter(|alias| !alias.is_empty() && *alias != tag.name && seen.insert(alias.clone()))
+        .collect();
+    tag.aliases = if aliases.is_empty() {
+        None
+    } else {
+        serde_json::to_string(&aliases).ok()
+    };
+}
+
+/// 将 `source` 的元数据合并到 `target`
+///
+/// 颜色、图标、描述以 `target` 为准（为空时取 `source`），别名取并集，
+/// 最后使用时间取较晚者，创建时间取较早者。
+pub fn merge_tag_metadata(target: &mut TagModel, source: &TagModel) {
+    target.last_used = target.last_used.max(source.last_used);
+    target.created_at = target.created_at.min(source.created_at);
+    target.color = target.color.take().or_else(|| source.color.clone());
+    target.icon = target.icon.take().or_else(|| source.icon.clone());
+    target.description = target
+        .description
+        .take()
+        .or_else(|| source.description.clone());
+
+    let mut aliases = target.alias_list();
+    aliases.extend(source.alias_list());
+    set_tag_aliases(target, aliases);
+}
+
+/// 按改名规则改写节点的标签列表
+///
+/// 改名后出现的重复标签只保留第一个；没有变化时返回 None。
+pub fn rewrite_tag_list(tags: &[String], renames: &[TagRename]) -> Option<Vec<String>> {
+    let mut changed = false;
+    let mut seen = HashSet::new();
+    let mut result = Vec::with_capacity(tags.len());
+    for tag in tags {
+        let new_tag = match rename_tag_path(tag, renames) {
+            Some(renamed) => {
+                changed = true;
+                renamed
+            }
+            None => tag.clone(),
+        };
+        if seen.insert(new_tag.clone()) {
+            result.push(new_tag);
+        } else {
+            changed = true;
+        }
+    }
+    changed.then_some(result)
+}
+
+/// 从 Lexical 内容中提取标签
+///
+/// 来源包括 tag 节点的 `tagName`、普通文本中的 `#[tag]` 标记，
+/// 以及 `#+TAGS:` front-matter（逗号分隔）。
+/// 标签路径经过规范化并按首次出现的顺序去重；内容不是 JSON 时返回 None。
+pub fn extract_content_tags(content: &str) -> Option<Vec<String>> {
+    let root = serde_json::from_str::<Value>(content).ok()?;
+
+    let mut seen = HashSet::new();
+    let mut tags = Vec::new();
+    collect_content_tags(&root, &mut |raw: &str| {
+        let tag = normalize_tag_path(raw);
+        if !tag.is_empty() && seen.insert(tag.clone()) {
+            tags.push(tag);
+        }
+    });
+    Some(tags)
+}
+
+fn collect_content_tags(value: &Value, push: &mut impl FnMut(&str)) {
+    match value {
+        Value::Object(map) => {
+            let node_type = map.get("type").and_then(Value::as_str);
+            if node_type == Some("tag") {
+                if let Some(name) = map.get("tagName").and_then(Value::as_str) {
+                    push(name);
+                }
+                return;
+            }
+            if node_type == Some("front-matter")
+                && map
+                    .get("key")
+                    .and_then(Value::as_str)
+                    .is_some_and(|key| key.eq_ignore_ascii_case("TAGS"))
+            {
+                if let Some(value) = map.get("value").and_then(Value::as_str) {
+                    value.split(',').for_each(&mut *push);
+                }
+            }
+            if let Some(text) = map.get("text").and_then(Value::as_str) {
+                for name in inline_tag_names(text) {
+                    push(name);
+                }
+            }
+            for child in map.values() {
+                collect_content_tags(child, push);
+            }
+        }
+        Value::Array(items) => {
+            for item in items {
+                collect_content_tags(item, push);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// 提取普通文本中 `#[tag]` 标记的标签名
+pub(crate) fn inline_tag_names(text: &str) -> Vec<&str> {
+    let mut names = Vec::new();
+    let mut rest = text;
+    while let Some(start) = rest.find("#[") {
+        let after = &rest[start + 2..];
+        let Some(end) = after.find(']') else {
+            break;
+        };
+        names.push(&after[..end]);
+        rest = &after[end + 1..];
+    }
+    names
+}
+
+/// 按改名规则改写 Lexical 内容中的 `#[tag]`
+///
+/// 同时处理 tag 节点（`tagName` 与显示文本）和尚未转换为 tag 节点的普通文本。
+/// 返回改写后的内容和改写的标签数量；没有需要改写的标签或内容不是 JSON 时返回 None。
+pub fn rewrite_content_tags(content: &str, renames: &[TagRename]) -> Option<(String, usize)> {
+    let mut root = serde_json::from_str::<Value>(content).ok()?;
+
+    let rewritten = rewrite_tag_nodes(&mut root, renames);
+    if rewritten == 0 {
+        return None;
+    }
+    serde_json::to_string(&root)
+        .ok()
+        .map(|text| (text, rewritten))
+}
+
+fn rewrite_tag_nodes(value: &mut Value, renames: &[TagRename]) -> usize {
+    match value {
+        Value::Object(map) => {
+            if map.get("type").and_then(Value::as_str) == Some("tag") {
+                let renamed = map
+                    .get("tagName")
+                    .and_then(Value::as_str)
+                    .and_then(|name| rename_tag_path(name, renames));
+                return match renamed {
+                    Some(new_name) => {
+                        map.insert("text".to_string(), Value::from(format!("#[{}]", new_name)));
+                        map.insert("tagName".to_string(), Value::from(new_name));
+                        1
+                    }
+                    None => 0,
+                };
+            }
+
+            let mut rewritten = 0;
+            if let Some(Value::String(text)) = map.get_mut("text") {
+                if let Some((new_text, count)) = rewrite_inline_tags(text, renames) {
+                    *text = new_text;
+                    rewritten += count;
+                }
+            }
+            for child in map.values_mut() {
+                rewritten += rewrite_tag_nodes(child, renames);
+            }
+            rewritten
+        }
+        Value::Array(items) => items
+            .iter_mut()
+            .map(|item| rewrite_tag_nodes(item, renames))
+            .sum(),
+        _ => 0,
+    }
+}
+
+/// 改写普通文本中的 `#[tag]` 标记
+fn rewrite_inline_tags(text: &str, renames: &[TagRename]) -> Option<(String, usize)> {
+    let mut result = String::with_capacity(text.len());
+    let mut rest = text;
+    let mut rewritten = 0;
+    while let Some(start) = rest.find("#[") {
+        let after = &rest[start + 2..];
+        let Some(end) = after.find(']') else {
+            break;
+        };
+        result.push_str(&rest[..start]);
+        match rename_tag_path(&after[..end], renames) {
+            Some(new_name) => {
+                result.push_str("#[");
+                result.push_str(&new_name);
+                result.push(']');
+                rewritten += 1;
+            }
+            None => result.push_str(&rest[start..start + 2 + end + 1]),
+        }
+        rest = &after[end + 1..];
+    }
+    if rewritten == 0 {
+        return None;
+    }
+    result.push_str(rest);
+    Some((result, rewritten))
+}
+
+/// 构建层级标签树
+///
+/// - `tags`: 工作区的标签记录（提供 ID，也保证未被使用的标签出现在树中）
+/// - `node_tags`: 每个节点的标签列表，用于计算直接计数与汇总计数
+///
+/// 只作为前缀出现的中间层级会自动补齐；同级按名称排序。
+pub fn build_tag_tree(tags: Vec<TagModel>, node_tags: &[Vec<String>]) -> Vec<TagTreeNode> {
+    let mut ids: HashMap<String, String> = HashMap::new();
+    let mut paths: BTreeSet<String> = BTreeSet::new();
+    for tag in tags {
+        let prefixes = tag_path_prefixes(&tag.name);
+        if let Some(full) = prefixes.last() {
+            ids.insert(full.clone(), tag.id);
+        }
+        paths.extend(prefixes);
+    }
+
+    let mut own_counts: HashMap<String, i32> = HashMap::new();
+    let mut rollup_counts: HashMap<String, i32> = HashMap::new();
+    for tags in node_tags {
+        let mut own: HashSet<String> = HashSet::new();
+        let mut rollup: HashSet<String> = HashSet::new();
+        for tag in tags {
+            let prefixes = tag_path_prefixes(tag);
+            if let Some(full) = prefixes.last() {
+                own.insert(full.clone());
+            }
+            rollup.extend(prefixes);
+        }
+        for tag in own {
+            *own_counts.entry(tag).or_insert(0) += 1;
+        }
+        for tag in rollup {
+            paths.insert(tag.clone());
+            *rollup_counts.entry(tag).or_insert(0) += 1;
+        }
+    }
+
+    let mut children: BTreeMap<Option<&str>, Vec<&str>> = BTreeMap::new();
+    for path in &paths {
+        let parent = path.rsplit_once(TAG_PATH_SEPARATOR).map(|(p, _)| p);
+        children.entry(parent).or_default().push(path.as_str());
+    }
+
+    fn build(
+        parent: Option<&str>,
+        children: &BTreeMap<Option<&str>, Vec<&str>>,
+        ids: &HashMap<String, String>,
+        own_counts: &HashMap<String, i32>,
+        rollup_counts: &HashMap<String, i32>,
+    ) -> Vec<TagTreeNode> {
+        children
+            .get(&parent)
+            .map(|paths| {
+                paths
+                    .iter()
+                    .map(|path| TagTreeNode {
+                        id: ids.get(*path).cloned(),
+                        name: path.to_string(),
+                        label: path
+                            .rsplit(TAG_PATH_SEPARATOR)
+                            .next()
+                            .unwrap_or(path)
+                            .to_string(),
+                        count: own_counts.get(*path).copied().unwrap_or(0),
+                        rollup_count: rollup_counts.get(*path).copied().unwrap_or(0),
+                        children: build(Some(path), children, ids, own_counts, rollup_counts),
+                    })
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    build(None, &children, &ids, &own_counts, &rollup_counts)
+}
+
+// ============================================================================
+// 测试
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tag(name: &str) -> TagModel {
+        TagModel {
+            id: format!("ws-1:{}", name),
+            name: name.to_string(),
+            workspace_id: "ws-1".to_string(),
+            count: 0,
+            last_used: 0,
+            created_at: 0,
+            color: None,
+            icon: None,
+            description: None,
+            aliases: None,
+        }
+    }
+
+    fn tags(names: &[&str]) -> Vec<String> {
+        names.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_tag_matches() {
+        assert!(tag_matches("project", "project"));
+        assert!(tag_matches("project/alpha", "project"));
+        assert!(tag_matches("project/alpha/v1", "project/alpha"));
+        assert!(!tag_matches("projects", "project"));
+        assert!(!tag_matches("project", "project/alpha"));
+        assert!(!tag_matches("rust", "project"));
+    }
+
+    #[test]
+    fn test_tag_path_prefixes() {
+        assert_eq!(tag_path_prefixes("a/b/c"), vec!["a", "a/b", "a/b/c"]);
+        assert_eq!(tag_path_prefixes("/a//b/"), vec!["a", "a/b"]);
+        assert!(tag_path_prefixes("").is_empty());
+    }
+
+    fn rename(from: &str, to: &str) -> TagRename {
+        TagRename {
+            from: from.to_string(),
+            to: to.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_normalize_and_rename_tag_path() {
+        assert_eq!(normalize_tag_path(" /project// alpha /"), "project/alpha");
+        assert_eq!(normalize_tag_path("//"), "");
+
+        let renames = vec![rename("project", "work"), rename("project/alpha", "alpha")];
+        assert_eq!(
+            rename_tag_path("project", &renames).as_deref(),
+            Some("work")
+        );
+        assert_eq!(
+            rename_tag_path("project/beta", &renames).as_deref(),
+            Some("work/beta")
+        );
+        // 更具体的规则优先
+        assert_eq!(
+            rename_tag_path("project/alpha/v1", &renames).as_deref(),
+            Some("alpha/v1")
+        );
+        assert!(rename_tag_path("projects", &renames).is_none());
+    }
+
+    #[test]
+    fn test_alias_renames() {
+        let mut javascript = tag("lang/javascript");
+        javascript.aliases = Some(r#"["js", "ecmascript"]"#.to_string());
+        let renames = alias_renames(&[javascript, tag("rust")]);
+
+        assert_eq!(renames.len(), 2);
+        assert_eq!(
+            rename_tag_path("js/react", &renames).as_deref(),
+            Some("lang/javascript/react")
+        );
+        assert!(rename_tag_path("rust", &renames).is_none());
+    }
+
+    #[test]
+    fn test_merge_tag_metadata() {
+        let mut target = tag("wip");
+        target.color = Some("#f59e0b".to_string());
+        target.aliases = Some(r#"["doing"]"#.to_string());
+        let mut source = tag("draft");
+        source.color = Some("#64748b".to_string());
+        source.description = Some("草稿".to_string());
+        source.aliases = Some(r#"["todo", "wip", "doing"]"#.to_string());
+        source.created_at = -1;
+
+        merge_tag_metadata(&mut target, &source);
+        assert_eq!(target.color.as_deref(), Some("#f59e0b"));
+        assert_eq!(target.description.as_deref(), Some("草稿"));
+        assert_eq!(target.alias_list(), vec!["doing", "todo"]);
+        assert_eq!(target.created_at, -1);
+
+        set_tag_aliases(&mut target, vec![" ".to_string()]);
+        assert!(target.aliases.is_none());
+    }
+
+    #[test]
+    fn test_rewrite_tag_list() {
+        let renames = vec![rename("draft", "wip")];
+        assert_eq!(
+            rewrite_tag_list(&tags(&["draft", "rust", "wip"]), &renames),
+            Some(tags(&["wip", "rust"]))
+        );
+        assert!(rewrite_tag_list(&tags(&["rust"]), &renames).is_none());
+    }
+
+    #[test]
+    fn test_rewrite_content_tags() {
+        let content = r##"{"root":{"children":[{"type":"paragraph","children":[{"type":"tag","tagName":"draft","text":"#[draft]"},{"type":"text","text":" 见 #[draft/v1] 和 #[rust] #[未闭合"}]}]}}"##;
+        let renames = vec![rename("draft", "wip")];
+
+        let (rewritten, count) = rewrite_content_tags(content, &renames).unwrap();
+        assert_eq!(count, 2);
+        assert!(rewritten.contains(r#""tagName":"wip""#));
+        assert!(rewritten.contains(r##""text":"#[wip]""##));
+        assert!(rewritten.contains("见 #[wip/v1] 和 #[rust] #[未闭合"));
+        assert!(!rewritten.contains("draft"));
+
+        assert!(rewrite_content_tags(&rewritten, &renames).is_none());
+        assert!(rewrite_content_tags("纯文本 #[draft]", &renames).is_none());
+    }
+
+    #[test]
+    fn test_extract_content_tags() {
+        let content = r##"{"root":{"children":[
+            {"type":"front-matter","key":"tags","value":"diary, rust"},
+            {"type":"paragraph","children":[
+                {"type":"tag","tagName":"project/alpha","text":"#[project/alpha]"},
+                {"type":"text","text":"未转换的 #[ rust ] 和 #[ledger]，#[未闭合"}
+            ]}
+        ]}}"##;
+
+        assert_eq!(
+            extract_content_tags(content),
+            Some(tags(&["diary", "rust", "project/alpha", "ledger"]))
+        );
+        assert_eq!(extract_content_tags(r#"{"root":{}}"#), Some(Vec::new()));
+        assert!(extract_content_tags("纯文本 #[rust]").is_none());
+    }
+
+    #[test]
+    fn test_build_tag_tree() {
+        let node_tags = vec![
+            tags(&["project/alpha", "project/beta"]),
+            tags(&["project"]),
+            tags(&["project/alpha/v1"]),
+            tags(&["rust"]),
+        ];
+        let tree = build_tag_tree(
+            vec![
+                tag("project"),
+                tag("project/alpha"),
+                tag("project/beta"),
+                tag("rust"),
+                tag("unused"),
+            ],
+            &node_tags,
+        );
+
+        let names: Vec<&str> = tree.iter().map(|t| t.name.as_str()).collect();
+        assert_eq!(names, vec!["project", "rust", "unused"]);
+
+        let project = &tree[0];
+        assert_eq!(project.count, 1);
+        // 第一个文档同时有 alpha 和 beta，只计一次
+        assert_eq!(project.rollup_count, 3);
+        assert_eq!(project.children.len(), 2);
+
+        let alpha = &project.children[0];
+        assert_eq!(alpha.label, "alpha");
+        assert_eq!(alpha.id.as_deref(), Some("ws-1:project/alpha"));
+        assert_eq!(alpha.count, 1);
+        assert_eq!(alpha.rollup_count, 2);
+
+        // 只在节点上出现、没有标签记录的层级
+        let v1 = &alpha.children[0];
+        assert_eq!(v1.name, "project/alpha/v1");
+        assert!(v1.id.is_none());
+        assert_eq!(v1.count, 1);
+
+        assert_eq!(tree[2].count, 0);
+        assert_eq!(tree[2].rollup_count, 0);
+    }
+}
@@ -0,0 +1,5 @@
+//! Task 纯函数模块
+
+pub mod task_fn;
+
+pub use task_fn::*;
@@ -0,0 +1,431 @@
+//! Task 纯函数
+//!
+//! 从 Lexical 内容的勾选列表（`listType: "check"`）中提取任务，
+//! 解析任务文本中的截止日期、优先级和标签，以及按条件过滤任务。
+//!
+//! 任务文本中的标记：
+//! - 截止日期：`due:2026-01-05` 或 `📅 2026-01-05`
+//! - 优先级：`!high` / `!medium` / `!low`
+//! - 标签：`#[tag]`
+
+use std::cmp::Ordering;
+use std::collections::HashMap;
+
+use chrono::{Datelike, Duration, NaiveDate};
+use serde_json::{Map, Value};
+
+use crate::r#fn::tag::{inline_tag_names, normalize_tag_path, tag_matches};
+use crate::types::task::{TaskDueFilter, TaskModel, TaskPriority, TaskQuery, TaskStatus};
+
+/// 截止日期格式
+const DUE_DATE_FORMAT: &str = "%Y-%m-%d";
+
+/// 从内容中提取的任务
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExtractedTask {
+    /// 按文档顺序的位置（从 0 开始）
+    pub position: i32,
+    /// 任务文本
+    pub text: String,
+    /// 是否已勾选
+    pub checked: bool,
+    /// 截止日期（YYYY-MM-DD）
+    pub due_date: Option<String>,
+    /// 优先级
+    pub priority: Option<TaskPriority>,
+    /// 任务中的标签（规范化、去重）
+    pub tags: Vec<String>,
+}
+
+// ============================================================================
+// 纯函数（数据转换）
+// ============================================================================
+
+/// 提取 Lexical 内容中的所有勾选列表项
+///
+/// 按文档顺序（先序）编号，嵌套的子列表项排在父项之后。
+/// 只包含子列表的包装列表项不算任务。内容不是 JSON 时返回 None。
+pub fn extract_tasks(content: &str) -> Option<Vec<ExtractedTask>> {
+    let mut root = serde_json::from_str::<Value>(content).ok()?;
+
+    let mut tasks = Vec::new();
+    visit_check_items(&mut root, &mut |item| {
+        let mut text = String::new();
+        collect_item_text(item, &mut text);
+        let text = text.trim().to_string();
+        tasks.push(ExtractedTask {
+            position: tasks.len() as i32,
+            checked: item
+                .get("checked")
+                .and_then(Value::as_bool)
+                .unwrap_or(false),
+            due_date: parse_due_date(&text),
+            priority: parse_priority(&text),
+            tags: parse_task_tags(&text),
+            text,
+        });
+    });
+    Some(tasks)
+}
+
+/// 修改内容中指定位置任务的勾选状态
+///
+/// `checked` 为空时取反。返回改写后的内容和新的勾选状态；
+/// 内容不是 JSON 或该位置没有任务时返回 None。
+pub fn toggle_task_in_content(
+    content: &str,
+    position: i32,
+    checked: Option<bool>,
+) -> Option<(String, bool)> {
+    let mut root = serde_json::from_str::<Value>(content).ok()?;
+
+    let mut index = 0;
+    let mut toggled = None;
+    visit_check_items(&mut root, &mut |item| {
+        if index == position {
+            let current = item
+                .get("checked")
+                .and_then(Value::as_bool)
+                .unwrap_or(false);
+            let next = checked.unwrap_or(!current);
+            item.insert("checked".to_string(), Value::Bool(next));
+            toggled = Some(next);
+        }
+        index += 1;
+    });
+
+    let checked = toggled?;
+    serde_json::to_string(&root)
+        .ok()
+        .map(|text| (text, checked))
+}
+
+/// 按查询条件过滤任务
+///
+/// `node_tags` 为节点 ID 到节点标签的映射，标签条件匹配任务自身的标签或所在节点的标签。
+/// `tag` 应已解析别名。结果按截止日期升序（无截止日期在后）、优先级、节点和位置排序。
+pub fn filter_tasks(
+    tasks: Vec<TaskModel>,
+    node_tags: &HashMap<String, Vec<String>>,
+    query: &TaskQuery,
+    today: NaiveDate,
+) -> Vec<TaskModel> {
+    let week_start = today - Duration::days(today.weekday().num_days_from_monday() as i64);
+    let week_end = week_start + Duration::days(6);
+
+    let mut result: Vec<TaskModel> = tasks
+        .into_iter()
+        .filter(|task| match query.status {
+            Some(TaskStatus::Open) => !task.checked,
+            Some(TaskStatus::Done) => task.checked,
+            None => true,
+        })
+        .filter(|task| query.node_id.as_ref().is_none_or(|id| &task.node_id == id))
+        .filter(|task| {
+            let Some(due) = query.due else {
+                return true;
+            };
+            let Some(date) = task
+                .due_date
+                .as_deref()
+                .and_then(|d| NaiveDate::parse_from_str(d, DUE_DATE_FORMAT).ok())
+            else {
+                return false;
+            };
+            match due {
+                TaskDueFilter::Overdue => !task.checked && date < today,
+                TaskDueFilter::Today => date == today,
+                TaskDueFilter::ThisWeek => date >= week_start && date <= week_end,
+            }
+        })
+        .filter(|task| {
+            let Some(tag) = query.tag.as_deref() else {
+                return true;
+            };
+            task.tag_list().iter().any(|t| tag_matches(t, tag))
+                || node_tags
+                    .get(&task.node_id)
+                    .is_some_and(|tags| tags.iter().any(|t| tag_matches(t, tag)))
+        })
+        .collect();
+
+    result.sort_by(|a, b| {
+        compare_due(a.due_date.as_deref(), b.due_date.as_deref())
+            .then_with(|| {
+                let rank = |p: Option<TaskPriority>| p.map_or(u8::MAX, TaskPriority::rank);
+                rank(a.priority).cmp(&rank(b.priority))
+            })
+            .then_with(|| a.node_id.cmp(&b.node_id))
+            .then_with(|| a.position.cmp(&b.position))
+    });
+    result
+}
+
+/// 截止日期比较（无截止日期排在最后）
+fn compare_due(a: Option<&str>, b: Option<&str>) -> Ordering {
+    match (a, b) {
+        (Some(a), Some(b)) => a.cmp(b),
+        (Some(_), None) => Ordering::Less,
+        (None, Some(_)) => Ordering::Greater,
+        (None, None) => Ordering::Equal,
+    }
+}
+
+/// 按文档顺序访问所有勾选列表项
+fn visit_check_items<F: FnMut(&mut Map<String, Value>)>(value: &mut Value, visit: &mut F) {
+    match value {
+        Value::Object(map) => {
+            let is_check_list = map.get("type").and_then(Value::as_str) == Some("list")
+                && map.get("listType").and_then(Value::as_str) == Some("check");
+            if !is_check_list {
+                for child in map.values_mut() {
+                    visit_check_items(child, visit);
+                }
+                return;
+            }
+
+            let Some(Value::Array(items)) = map.get_mut("children") else {
+                return;
+            };
+            for item in items {
+                if let Value::Object(item) = &mut *item {
+                    if item.get("type").and_then(Value::as_str) == Some("listitem")
+                        && !is_wrapper_item(item)
+                    {
+                        visit(item);
+                    }
+                }
+                // 子列表（嵌套在列表项中）
+                if let Some(Value::Array(children)) = item.get_mut("children") {
+                    for child in children {
+                        if is_list(child) {
+                            visit_check_items(child, visit);
+                        }
+                    }
+                }
+            }
+        }
+        Value::Array(items) => {
+            for item in items {
+                visit_check_items(item, visit);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn is_list(value: &Value) -> bool {
+    value.get("type").and_then(Value::as_str) == Some("list")
+}
+
+/// 只包含子列表的列表项（Lexical 嵌套列表的包装节点）
+fn is_wrapper_item(item: &Map<String, Value>) -> bool {
+    match item.get("children").and_then(Value::as_array) {
+        Some(children) => !children.is_empty() && children.iter().all(is_list),
+        None => false,
+    }
+}
+
+/// 拼接列表项中的文本（不含子列表）
+fn collect_item_text(item: &Map<String, Value>, text: &mut String) {
+    if let Some(t) = item.get("text").and_then(Value::as_str) {
+        text.push_str(t);
+    }
+    if let Some(children) = item.get("children").and_then(Value::as_array) {
+        for child in children.iter().filter(|c| !is_list(c)) {
+            if let Value::Object(child) = child {
+                collect_item_text(child, text);
+            }
+        }
+    }
+}
+
+/// 解析截止日期（`due:YYYY-MM-DD` 或 `📅 YYYY-MM-DD`）
+fn parse_due_date(text: &str) -> Option<String> {
+    ["due:", "📅"].iter().find_map(|marker| {
+        text.match_indices(marker).find_map(|(start, _)| {
+            let candidate = text[start + marker.len()..].trim_start();
+            let date = candidate.get(..10)?;
+            NaiveDate::parse_from_str(date, DUE_DATE_FORMAT)
+                .ok()
+                .map(|d| d.format(DUE_DATE_FORMAT).to_string())
+        })
+    })
+}
+
+/// 解析优先级（`!high` / `!medium` / `!low`）
+fn parse_priority(text: &str) -> Option<TaskPriority> {
+    text.split_whitespace()
+        .filter_map(|word| word.strip_prefix('!'))
+        .find_map(|word| word.parse().ok())
+}
+
+/// 解析任务文本中的 `#[tag]` 标签
+fn parse_task_tags(text: &str) -> Vec<String> {
+    let mut tags: Vec<String> = Vec::new();
+    for name in inline_tag_names(text) {
+        let tag = normalize_tag_path(name);
+        if !tag.is_empty() && !tags.contains(&tag) {
+            tags.push(tag);
+        }
+    }
+    tags
+}
+
+// ============================================================================
+// 测试
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn item(text: &str, checked: bool) -> Value {
+        serde_json::json!({
+            "type": "listitem",
+            "checked": checked,
+            "children": [{ "type": "text", "text": text }]
+        })
+    }
+
+    fn check_list(items: Vec<Value>) -> Value {
+        serde_json::json!({ "type": "list", "listType": "check", "children": items })
+    }
+
+    fn content() -> String {
+        let nested = serde_json::json!({
+            "type": "listitem",
+            "children": [check_list(vec![item("子任务 due:2026-01-06", false)])]
+        });
+        serde_json::json!({
+            "root": {
+                "children": [
+                    { "type": "paragraph", "children": [{ "type": "text", "text": "本周" }] },
+                    check_list(vec![
+                        item("提交报告 !high 📅 2026-01-05 #[work/report]", false),
+                        nested,
+                        item("买牛奶", true),
+                    ]),
+                    {
+                        "type": "list",
+                        "listType": "bullet",
+                        "children": [item("不是任务", false)]
+                    }
+                ]
+            }
+        })
+        .to_string()
+    }
+
+    fn task(node_id: &str, position: i32, due: Option<&str>, checked: bool) -> TaskModel {
+        TaskModel {
+            id: format!("{}:{}", node_id, position),
+            node_id: node_id.to_string(),
+            workspace_id: "ws-1".to_string(),
+            position,
+            text: String::new(),
+            checked,
+            due_date: due.map(str::to_string),
+            priority: None,
+            tags: None,
+            content_version: 1,
+            updated_at: 0,
+        }
+    }
+
+    #[test]
+    fn test_extract_tasks() {
+        let tasks = extract_tasks(&content()).unwrap();
+        assert_eq!(tasks.len(), 3);
+
+        assert_eq!(tasks[0].position, 0);
+        assert_eq!(tasks[0].due_date.as_deref(), Some("2026-01-05"));
+        assert_eq!(tasks[0].priority, Some(TaskPriority::High));
+        assert_eq!(tasks[0].tags, vec!["work/report"]);
+        assert!(!tasks[0].checked);
+
+        assert_eq!(tasks[1].text, "子任务 due:2026-01-06");
+        assert_eq!(tasks[1].due_date.as_deref(), Some("2026-01-06"));
+
+        assert_eq!(tasks[2].text, "买牛奶");
+        assert!(tasks[2].checked);
+        assert_eq!(tasks[2].due_date, None);
+
+        assert!(extract_tasks("plain text").is_none());
+    }
+
+    #[test]
+    fn test_task_markers() {
+        assert_eq!(parse_due_date("due:2026-13-01"), None);
+        assert_eq!(
+            parse_due_date("due: 2026-02-03 later"),
+            Some("2026-02-03".to_string())
+        );
+        assert_eq!(parse_priority("fix !LOW"), Some(TaskPriority::Low));
+        assert_eq!(parse_priority("wow!high"), None);
+        assert_eq!(parse_task_tags("#[a] #[ a/ ] #[]"), vec!["a"]);
+    }
+
+    #[test]
+    fn test_toggle_task_in_content() {
+        let (toggled, checked) = toggle_task_in_content(&content(), 1, None).unwrap();
+        assert!(checked);
+        let tasks = extract_tasks(&toggled).unwrap();
+        assert!(tasks[1].checked);
+        assert!(!tasks[0].checked);
+
+        let (toggled, checked) = toggle_task_in_content(&toggled, 2, Some(true)).unwrap();
+        assert!(checked);
+        assert!(extract_tasks(&toggled).unwrap()[2].checked);
+
+        assert!(toggle_task_in_content(&content(), 3, None).is_none());
+    }
+
+    #[test]
+    fn test_filter_tasks() {
+        // 2026-01-07 是周三，本周为 01-05 至 01-11
+        let today = NaiveDate::from_ymd_opt(2026, 1, 7).unwrap();
+        let tasks = vec![
+            task("a", 0, Some("2026-01-06"), false),
+            task("a", 1, Some("2026-01-02"), true),
+            task("b", 0, None, false),
+            task("b", 1, Some("2026-01-11"), false),
+            task("b", 2, Some("2026-01-12"), false),
+        ];
+        let node_tags = HashMap::from([("b".to_string(), vec!["work/alpha".to_string()])]);
+        let ids = |query: TaskQuery| -> Vec<String> {
+            filter_tasks(tasks.clone(), &node_tags, &query, today)
+                .into_iter()
+                .map(|t| t.id)
+                .collect()
+        };
+
+        let all = ids(TaskQuery::default());
+        assert_eq!(all, vec!["a:1", "a:0", "b:1", "b:2", "b:0"]);
+
+        let overdue = ids(TaskQuery {
+            due: Some(TaskDueFilter::Overdue),
+            ..Default::default()
+        });
+        assert_eq!(overdue, vec!["a:0"]);
+
+        let this_week = ids(TaskQuery {
+            due: Some(TaskDueFilter::ThisWeek),
+            ..Default::default()
+        });
+        assert_eq!(this_week, vec!["a:0", "b:1"]);
+
+        let work = ids(TaskQuery {
+            status: Some(TaskStatus::Open),
+            tag: Some("work".to_string()),
+            ..Default::default()
+        });
+        assert_eq!(work, vec!["b:1", "b:2", "b:0"]);
+
+        let done = ids(TaskQuery {
+            status: Some(TaskStatus::Done),
+            ..Default::default()
+        });
+        assert_eq!(done, vec!["a:1"]);
+    }
+}
@@ -0,0 +1,5 @@
+//! Writing 纯函数模块
+
+pub mod writing_fn;
+
+pub use writing_fn::*;
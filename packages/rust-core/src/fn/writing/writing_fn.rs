@@ -0,0 +1,541 @@
+//! Writing 纯函数
+//!
+//! 写作统计：中日韩文字按字计数（不能按空白切分），其他文字按连续的字母数字计词，
+//! 段落按 Lexical 块级节点计数；并按节点树把文档统计汇总到文件夹。
+//!
+//! 写作进度：按天汇总字数变化，计算连续写作天数、热力图和目标完成情况。
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use chrono::{Duration, NaiveDate};
+
+use crate::r#fn::search::content_plain_text;
+use crate::types::node::{node_entity, NodeType};
+use crate::types::writing::{
+    writing_progress_entity, NodeWritingStats, WritingDay, WritingGoalKind, WritingGoalProgress,
+    WritingGoalResponse, WritingStats, WritingStatsResponse, WritingStreak,
+};
+
+/// 日期格式
+const DATE_FORMAT: &str = "%Y-%m-%d";
+
+/// 每日字数变化（日期 -> （增加，删除））
+pub type DailyWriting = BTreeMap<NaiveDate, (i64, i64)>;
+
+/// 是否为中日韩文字（汉字、假名、谚文）
+pub fn is_cjk(c: char) -> bool {
+    matches!(c as u32,
+        0x3040..=0x30FF   // 平假名、片假名
+        | 0x3400..=0x4DBF // 扩展 A
+        | 0x4E00..=0x9FFF // 基本汉字
+        | 0xAC00..=0xD7AF // 谚文音节
+        | 0xF900..=0xFAFF // 兼容汉字
+        | 0x20000..=0x2FFFF // 扩展 B 及以后
+    )
+}
+
+/// 分别统计中日韩字符数和其他文字的词数
+fn count_cjk_and_words(text: &str) -> (u64, u64) {
+    let mut cjk = 0;
+    let mut words = 0;
+    let mut in_word = false;
+    for c in text.chars() {
+        if is_cjk(c) {
+            cjk += 1;
+            in_word = false;
+        } else if c.is_alphanumeric() || (in_word && (c == '\'' || c == '-')) {
+            if !in_word {
+                words += 1;
+                in_word = true;
+            }
+        } else {
+            in_word = false;
+        }
+    }
+    (cjk, words)
+}
+
+/// 统计字数：中日韩字符每字计一，其他文字按连续的字母数字计词
+pub fn count_words(text: &str) -> u64 {
+    let (cjk, words) = count_cjk_and_words(text);
+    cjk + words
+}
+
+/// 是否计入写作统计的节点类型（图表、代码、记账等不计入）
+pub fn is_writing_node(node_type: NodeType) -> bool {
+    matches!(
+        node_type,
+        NodeType::File | NodeType::Diary | NodeType::Wiki | NodeType::Todo | NodeType::Note
+    )
+}
+
+/// 统计内容的字数、字符数、段落数和阅读时间
+///
+/// Lexical JSON 每个非空块级节点计一段；纯文本按空行分段。
+pub fn content_writing_stats(content: &str) -> WritingStats {
+    let text = content_plain_text(content);
+    let is_lexical = serde_json::from_str::<serde::de::IgnoredAny>(content).is_ok();
+
+    let paragraphs = if is_lexical {
+        text.lines().filter(|line| !line.trim().is_empty()).count()
+    } else {
+        text.split("\n\n")
+            .filter(|block| !block.trim().is_empty())
+            .count()
+    };
+    let (cjk, words) = count_cjk_and_words(&text);
+    let characters = text.chars().filter(|c| !c.is_whitespace()).count();
+
+    WritingStats::new(cjk, words, characters as u64, paragraphs as u64)
+}
+
+/// 汇总写作统计
+///
+/// `stats` 为文档节点 ID 到统计值的映射。文档的统计计入所有祖先文件夹；
+/// 指定 `scope` 时只统计该节点及其子孙节点。
+pub fn aggregate_writing_stats(
+    nodes: &[node_entity::Model],
+    stats: &HashMap<String, WritingStats>,
+    scope: Option<&str>,
+) -> WritingStatsResponse {
+    let by_id: HashMap<&str, &node_entity::Model> =
+        nodes.iter().map(|n| (n.id.as_str(), n)).collect();
+    let node_stats = |node: &node_entity::Model, stats: WritingStats| NodeWritingStats {
+        node_id: node.id.clone(),
+        title: node.title.clone(),
+        node_type: node.node_type,
+        parent_id: node.parent_id.clone(),
+        stats,
+    };
+
+    let mut response = WritingStatsResponse::default();
+    let mut folders: HashMap<&str, WritingStats> = HashMap::new();
+    for node in nodes {
+        let Some(&doc_stats) = stats.get(&node.id) else {
+            continue;
+        };
+
+        // 自身及祖先链（防御父节点环）
+        let mut chain = vec![node];
+        let mut seen = HashSet::from([node.id.as_str()]);
+        while let Some(parent) = chain
+            .last()
+            .and_then(|n| n.parent_id.as_deref())
+            .and_then(|id| by_id.get(id))
+        {
+            if !seen.insert(parent.id.as_str()) {
+                break;
+            }
+            chain.push(parent);
+        }
+
+        let ancestors = match scope {
+            Some(scope) => match chain.iter().position(|n| n.id == scope) {
+                Some(i) => &chain[..=i],
+                None => continue,
+            },
+            None => &chain[..],
+        };
+        for folder in ancestors.iter().filter(|n| n.node_type == NodeType::Folder) {
+            *folders.entry(folder.id.as_str()).or_default() += doc_stats;
+        }
+        response.total += doc_stats;
+        response.documents.push(node_stats(node, doc_stats));
+    }
+
+    response.folders = folders
+        .into_iter()
+        .map(|(id, stats)| node_stats(by_id[id], stats))
+        .collect();
+    for list in [&mut response.folders, &mut response.documents] {
+        list.sort_by(|a, b| {
+            b.stats
+                .words
+                .cmp(&a.stats.words)
+                .then_with(|| a.title.cmp(&b.title))
+        });
+    }
+    response
+}
+
+// ============================================================================
+// 写作进度
+// ============================================================================
+
+/// 节点及其所有子孙节点的 ID
+pub fn subtree_ids(nodes: &[node_entity::Model], root: &str) -> HashSet<String> {
+    let mut children: HashMap<&str, Vec<&str>> = HashMap::new();
+    for node in nodes {
+        if let Some(parent) = node.parent_id.as_deref() {
+            children.entry(parent).or_default().push(node.id.as_str());
+        }
+    }
+
+    let mut ids = HashSet::new();
+    let mut stack = vec![root];
+    while let Some(id) = stack.pop() {
+        if ids.insert(id.to_string()) {
+            stack.extend(children.get(id).into_iter().flatten());
+        }
+    }
+    ids
+}
+
+/// 按日期汇总字数变化
+///
+/// `scope` 为 None 时包含所有记录（包括已删除节点的记录）。
+pub fn daily_writing(
+    rows: &[writing_progress_entity::Model],
+    scope: Option<&HashSet<String>>,
+) -> DailyWriting {
+    let mut days = DailyWriting::new();
+    for row in rows {
+        if scope.is_some_and(|ids| !ids.contains(&row.node_id)) {
+            continue;
+        }
+        let Ok(date) = NaiveDate::parse_from_str(&row.date, DATE_FORMAT) else {
+            continue;
+        };
+        let day = days.entry(date).or_default();
+        day.0 += row.words_added;
+        day.1 += row.words_removed;
+    }
+    days
+}
+
+/// 当天是否达成目标（没有每日目标时净增字数大于 0 即可）
+fn day_goal_met(days: &DailyWriting, date: NaiveDate, daily_target: Option<i64>) -> bool {
+    let net = days
+        .get(&date)
+        .map_or(0, |(added, removed)| added - removed);
+    match daily_target {
+        Some(target) => net >= target,
+        None => net > 0,
+    }
+}
+
+/// 计算连续写作天数
+///
+/// 今天尚未达成目标时不中断连续记录，从昨天开始往前计算。
+pub fn writing_streak(
+    days: &DailyWriting,
+    daily_target: Option<i64>,
+    today: NaiveDate,
+) -> WritingStreak {
+    let mut longest = 0;
+    let mut run = 0;
+    let mut previous: Option<NaiveDate> = None;
+    for &date in days.keys() {
+        if !day_goal_met(days, date, daily_target) {
+            run = 0;
+            continue;
+        }
+        run = match previous {
+            Some(prev) if run > 0 && prev + Duration::days(1) == date => run + 1,
+            _ => 1,
+        };
+        previous = Some(date);
+        longest = longest.max(run);
+    }
+
+    let mut current = 0;
+    let mut date = today;
+    if !day_goal_met(days, date, daily_target) {
+        date -= Duration::days(1);
+    }
+    while day_goal_met(days, date, daily_target) {
+        current += 1;
+        date -= Duration::days(1);
+    }
+
+    WritingStreak { current, longest }
+}
+
+/// 生成 `[from, to]` 范围内的热力图（只包含有记录的日期）
+pub fn writing_heatmap(
+    days: &DailyWriting,
+    daily_target: Option<i64>,
+    from: NaiveDate,
+    to: NaiveDate,
+) -> Vec<WritingDay> {
+    days.range(from..=to)
+        .map(|(&date, &(added, removed))| WritingDay {
+            date: date.format(DATE_FORMAT).to_string(),
+            words_added: added,
+            words_removed: removed,
+            net_words: added - removed,
+            goal_met: day_goal_met(days, date, daily_target),
+        })
+        .collect()
+}
+
+/// 计算目标的完成情况
+///
+/// `current` 对总字数目标为范围内的字数，对每日目标为今天的净增字数。
+/// 只有总字数目标按截止日期计算剩余天数和每日需要的字数。
+pub fn writing_goal_progress(
+    goal: WritingGoalResponse,
+    current: i64,
+    today: NaiveDate,
+) -> WritingGoalProgress {
+    let remaining = (goal.target - current).max(0);
+    let percent = if goal.target > 0 {
+        (current.max(0) * 100 / goal.target).min(100) as u32
+    } else {
+        100
+    };
+
+    let days_left = match goal.kind {
+        WritingGoalKind::Total => goal
+            .deadline
+            .as_deref()
+            .and_then(|d| NaiveDate::parse_from_str(d, DATE_FORMAT).ok())
+            .map(|deadline| (deadline - today).num_days() + 1),
+        WritingGoalKind::Daily => None,
+    };
+    let daily_needed = days_left.map(|days| {
+        if days > 0 {
+            (remaining + days - 1) / days
+        } else {
+            remaining
+        }
+    });
+
+    WritingGoalProgress {
+        achieved: current >= goal.target,
+        goal,
+        current,
+        remaining,
+        percent,
+        days_left,
+        daily_needed,
+    }
+}
+
+// ============================================================================
+// 测试
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn node(id: &str, parent: Option<&str>, node_type: NodeType) -> node_entity::Model {
+        node_entity::Model {
+            id: id.to_string(),
+            workspace_id: "ws".to_string(),
+            parent_id: parent.map(str::to_string),
+            title: id.to_string(),
+            node_type,
+            is_collapsed: false,
+            sort_order: 0,
+            tags: None,
+            created_at: 0,
+            updated_at: 0,
+        }
+    }
+
+    #[test]
+    fn test_count_words() {
+        assert_eq!(count_words("Hello, world! It's fine."), 4);
+        assert_eq!(count_words("今天天气很好"), 6);
+        assert_eq!(count_words("去 Kyoto 旅行 3 天"), 6);
+        assert_eq!(count_words("  -- "), 0);
+    }
+
+    #[test]
+    fn test_content_writing_stats() {
+        let content = r#"{"root":{"children":[
+            {"type":"heading","children":[{"type":"text","text":"第一章"}]},
+            {"type":"paragraph","children":[]},
+            {"type":"paragraph","children":[
+                {"type":"text","text":"他说："},
+                {"type":"link","children":[{"type":"text","text":"hello world"}]},
+                {"type":"text","text":"。"}
+            ]}
+        ]}}"#;
+        let stats = content_writing_stats(content);
+        assert_eq!(stats.cjk_characters, 5);
+        assert_eq!(stats.latin_words, 2);
+        assert_eq!(stats.words, 7);
+        assert_eq!(stats.paragraphs, 2);
+        assert_eq!(stats.characters, 17);
+        assert_eq!(stats.reading_minutes, 1);
+
+        let stats = content_writing_stats("First paragraph.\nStill first.\n\nSecond one.");
+        assert_eq!(stats.latin_words, 6);
+        assert_eq!(stats.paragraphs, 2);
+
+        assert_eq!(content_writing_stats("").paragraphs, 0);
+    }
+
+    #[test]
+    fn test_aggregate_writing_stats() {
+        let nodes = vec![
+            node("book", None, NodeType::Folder),
+            node("part", Some("book"), NodeType::Folder),
+            node("ch1", Some("part"), NodeType::File),
+            node("ch2", Some("book"), NodeType::Note),
+            node("loose", None, NodeType::File),
+            node("empty", None, NodeType::Folder),
+        ];
+        let stats = HashMap::from([
+            ("ch1".to_string(), WritingStats::new(300, 0, 300, 3)),
+            ("ch2".to_string(), WritingStats::new(0, 100, 500, 2)),
+            ("loose".to_string(), WritingStats::new(10, 0, 10, 1)),
+        ]);
+
+        let all = aggregate_writing_stats(&nodes, &stats, None);
+        assert_eq!(all.total.documents, 3);
+        assert_eq!(all.total.words, 410);
+        let folders: Vec<(&str, u64)> = all
+            .folders
+            .iter()
+            .map(|f| (f.node_id.as_str(), f.stats.words))
+            .collect();
+        assert_eq!(folders, vec![("book", 400), ("part", 300)]);
+        assert_eq!(all.documents[0].node_id, "ch1");
+
+        let part = aggregate_writing_stats(&nodes, &stats, Some("part"));
+        assert_eq!(part.total.words, 300);
+        assert_eq!(part.folders.len(), 1);
+        assert_eq!(part.documents.len(), 1);
+
+        let single = aggregate_writing_stats(&nodes, &stats, Some("ch2"));
+        assert_eq!(single.total.documents, 1);
+        assert!(single.folders.is_empty());
+    }
+
+    fn date(s: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(s, DATE_FORMAT).unwrap()
+    }
+
+    fn progress_row(
+        node_id: &str,
+        day: &str,
+        added: i64,
+        removed: i64,
+    ) -> writing_progress_entity::Model {
+        writing_progress_entity::Model {
+            id: format!("{}:{}", node_id, day),
+            workspace_id: "ws".to_string(),
+            node_id: node_id.to_string(),
+            date: day.to_string(),
+            words_added: added,
+            words_removed: removed,
+            updated_at: 0,
+        }
+    }
+
+    #[test]
+    fn test_subtree_and_daily_writing() {
+        let nodes = vec![
+            node("book", None, NodeType::Folder),
+            node("ch1", Some("book"), NodeType::File),
+            node("other", None, NodeType::File),
+        ];
+        let scope = subtree_ids(&nodes, "book");
+        assert_eq!(scope.len(), 2);
+
+        let rows = vec![
+            progress_row("ch1", "2026-03-01", 500, 20),
+            progress_row("other", "2026-03-01", 100, 0),
+            progress_row("deleted", "2026-03-02", 50, 0),
+        ];
+        let all = daily_writing(&rows, None);
+        assert_eq!(all[&date("2026-03-01")], (600, 20));
+        assert_eq!(all[&date("2026-03-02")], (50, 0));
+
+        let book = daily_writing(&rows, Some(&scope));
+        assert_eq!(book.len(), 1);
+        assert_eq!(book[&date("2026-03-01")], (500, 20));
+    }
+
+    #[test]
+    fn test_writing_streak() {
+        let days: DailyWriting = [
+            ("2026-03-01", (1200, 0)),
+            ("2026-03-02", (1500, 100)),
+            ("2026-03-03", (300, 0)),
+            ("2026-03-05", (1000, 0)),
+            ("2026-03-06", (1100, 0)),
+            ("2026-03-07", (100, 0)),
+        ]
+        .into_iter()
+        .map(|(d, v)| (date(d), v))
+        .collect();
+
+        // 没有每日目标：有净增字数即可
+        let streak = writing_streak(&days, None, date("2026-03-07"));
+        assert_eq!(
+            streak,
+            WritingStreak {
+                current: 3,
+                longest: 3
+            }
+        );
+
+        // 每日 1000 字：今天未达成，从昨天往前算
+        let streak = writing_streak(&days, Some(1000), date("2026-03-07"));
+        assert_eq!(
+            streak,
+            WritingStreak {
+                current: 2,
+                longest: 2
+            }
+        );
+
+        // 中断一天后当前连续天数归零
+        let streak = writing_streak(&days, Some(1000), date("2026-03-09"));
+        assert_eq!(streak.current, 0);
+
+        let heatmap = writing_heatmap(&days, Some(1000), date("2026-03-02"), date("2026-03-05"));
+        let cells: Vec<(&str, i64, bool)> = heatmap
+            .iter()
+            .map(|d| (d.date.as_str(), d.net_words, d.goal_met))
+            .collect();
+        assert_eq!(
+            cells,
+            vec![
+                ("2026-03-02", 1400, true),
+                ("2026-03-03", 300, false),
+                ("2026-03-05", 1000, true)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_writing_goal_progress() {
+        let goal = WritingGoalResponse {
+            id: "g".to_string(),
+            workspace_id: "ws".to_string(),
+            node_id: None,
+            kind: WritingGoalKind::Total,
+            target: 80_000,
+            deadline: Some("2026-03-10".to_string()),
+            created_at: 0,
+            updated_at: 0,
+        };
+        let progress = writing_goal_progress(goal.clone(), 20_000, date("2026-03-01"));
+        assert_eq!(progress.percent, 25);
+        assert_eq!(progress.remaining, 60_000);
+        assert_eq!(progress.days_left, Some(10));
+        assert_eq!(progress.daily_needed, Some(6_000));
+        assert!(!progress.achieved);
+
+        let overdue = writing_goal_progress(goal.clone(), 90_000, date("2026-03-20"));
+        assert!(overdue.achieved);
+        assert_eq!(overdue.percent, 100);
+        assert_eq!(overdue.daily_needed, Some(0));
+
+        let daily = WritingGoalResponse {
+            kind: WritingGoalKind::Daily,
+            target: 1000,
+            ..goal
+        };
+        let progress = writing_goal_progress(daily, -50, date("2026-03-01"));
+        assert_eq!(progress.percent, 0);
+        assert_eq!(progress.remaining, 1050);
+        assert_eq!(progress.days_left, None);
+    }
+}
@@ -8,7 +8,10 @@
 //! - `types/` - 类型定义（Interface + Builder + Entity）
 //! - `db/` - 数据库操作函数
 //! - `fn/` - 纯函数
-//! - `macros/` - 声明式宏（Warp routes 和 Tauri commands）
+//! - `events/` - 数据变更事件总线（SSE 和 Tauri 事件的来源）
+//! - `sync/` - 与服务器推送/拉取同步的客户端
+//! - `collab/` - 协同编辑（WebSocket 上交换兼容 Yjs 的 CRDT 更新）
+//! - `macros/` - 声明式宏（由端点注册表生成 Warp routes、Tauri commands 和路由文档）
 //!
 //! ## 使用示例
 //!
@@ -21,19 +24,20 @@
 //! };
 //!
 //! // 直接调用端点
-//! let workspaces = GetWorkspaces::execute(&db, ()).await?;
+//! let workspaces = GetWorkspaces::execute(&db, None).await?;
 //!
-//! // 使用宏生成 Warp 路由
-//! let routes = warp_routes!(db,
-//!     GetWorkspaces => GET "/api/workspaces",
-//! );
+//! // 由端点注册表生成 Warp 路由
+//! let routes = macros::combine_routes(api_registry!(warp_routes, db, auth));
 //! ```
 
 pub mod api;
+pub mod collab;
 pub mod db;
+pub mod events;
 pub mod r#fn;
 pub mod macros;
 pub mod server;
+pub mod sync;
 pub mod tauri;
 pub mod types;
 
@@ -61,8 +65,9 @@ pub use types::workspace::{
 // ============================================
 
 pub use types::node::{
-    CreateNodeRequest, MoveNodeRequest, NodeActiveModel, NodeBuilder, NodeColumn, NodeEntity,
-    NodeModel, NodeRelation, NodeResponse, NodeType, UpdateNodeRequest,
+    CreateNodeRequest, DuplicateNodeRequest, MoveNodeRequest, NodeActiveModel, NodeBuilder,
+    NodeChangeResponse, NodeColumn, NodeEntity, NodeListQuery, NodeModel, NodeRelation,
+    NodeResponse, NodeTreeResponse, NodeType, UpdateNodeRequest,
 };
 
 // ============================================
@@ -70,9 +75,97 @@ pub use types::node::{
 // ============================================
 
 pub use types::content::{
-    ContentActiveModel, ContentBuilder, ContentColumn, ContentEntity, ContentModel,
-    ContentRelation, ContentResponse, ContentType, CreateContentRequest, SaveContentRequest,
-    UpdateContentRequest,
+    ContentActiveModel, ContentBuilder, ContentColumn, ContentEntity, ContentMergeResponse,
+    ContentMergeStatus, ContentModel, ContentRelation, ContentResponse, ContentRevisionActiveModel,
+    ContentRevisionColumn, ContentRevisionEntity, ContentRevisionModel, ContentType,
+    ContentUpdateActiveModel, ContentUpdateColumn, ContentUpdateEntity, ContentUpdateModel,
+    CreateContentRequest, SaveContentRequest, UpdateContentRequest,
+};
+
+// ============================================
+// 重新导出 Diary 类型
+// ============================================
+
+pub use types::diary::{
+    DiaryCalendarDay, DiaryCalendarQuery, DiaryEntryResponse, DiaryOnThisDayEntry,
+    DiaryOnThisDayQuery, GetOrCreateDiaryEntryRequest,
+};
+
+// ============================================
+// 重新导出 ChangeLog 类型
+// ============================================
+
+pub use types::change_log::{
+    ChangeEntity, ChangeLogActiveModel, ChangeLogColumn, ChangeLogEntity, ChangeLogEntry,
+    ChangeLogModel, ChangeLogRelation, ChangeOperation, ChangesSinceQuery, ChangesSinceResponse,
+    CompactChangeLogResponse,
+};
+
+// ============================================
+// 重新导出 Event 类型
+// ============================================
+
+pub use types::event::{ChangeEvent, ChangeEventQuery, CHANGE_EVENT_NAME};
+
+// ============================================
+// 重新导出 Graph 类型
+// ============================================
+
+pub use types::graph::{
+    NodeGraphData, NodeGraphEdge, NodeGraphEdgeKind, NodeGraphNode, NodeGraphQuery,
+};
+
+// ============================================
+// 重新导出 Ledger 类型
+// ============================================
+
+pub use types::ledger::{
+    ImportLedgerCsvRequest, LedgerAmount, LedgerAmountSign, LedgerBalance, LedgerBalancesQuery,
+    LedgerBalancesResponse, LedgerCheckResponse, LedgerCsvColumn, LedgerCsvMapping,
+    LedgerDiagnostic, LedgerDiagnosticSeverity, LedgerEntry, LedgerGroupBy, LedgerImportIssue,
+    LedgerImportResponse, LedgerImportRule, LedgerNodeDiagnostic, LedgerPosting, LedgerReportQuery,
+    LedgerReportResponse, LedgerReportRow,
+};
+
+// ============================================
+// 重新导出 Link 类型
+// ============================================
+
+pub use types::link::{
+    LinkRewriteReport, NodeLinkActiveModel, NodeLinkColumn, NodeLinkEntity, NodeLinkModel,
+    NodeLinkRelation, NodeLinkResponse, RewrittenDocument,
+};
+
+// ============================================
+// 重新导出 Member 类型
+// ============================================
+
+pub use types::member::{
+    SetWorkspaceMemberRequest, WorkspaceMemberActiveModel, WorkspaceMemberColumn,
+    WorkspaceMemberEntity, WorkspaceMemberModel, WorkspaceMemberRelation, WorkspaceMemberResponse,
+    WorkspaceRole,
+};
+
+// ============================================
+// 重新导出 Search 类型
+// ============================================
+
+pub use types::search::{
+    CreateSavedSearchRequest, SavedSearchActiveModel, SavedSearchColumn, SavedSearchEntity,
+    SavedSearchModel, SavedSearchRelation, SavedSearchResponse, SearchNodesQuery,
+    UpdateSavedSearchRequest,
+};
+
+// ============================================
+// 重新导出 Sync 类型
+// ============================================
+
+pub use types::sync::{
+    SyncChange, SyncConflict, SyncConflictStrategy, SyncEntityData, SyncPullQuery,
+    SyncPullResponse, SyncPushRequest, SyncPushResponse, SyncPushResult, SyncPushStatus,
+    SyncRemoteActiveModel, SyncRemoteColumn, SyncRemoteEntity, SyncRemoteModel, SyncRemoteRelation,
+    SyncReport, SyncRequest, SyncVersionActiveModel, SyncVersionColumn, SyncVersionEntity,
+    SyncVersionModel, SyncVersionRelation,
 };
 
 // ============================================
@@ -80,8 +173,33 @@ pub use types::content::{
 // ============================================
 
 pub use types::tag::{
-    CreateTagRequest, TagActiveModel, TagColumn, TagEntity, TagGraphData, TagGraphEdge,
-    TagGraphNode, TagModel, TagResponse, UpdateTagRequest,
+    CreateTagRequest, GetOrCreateTagRequest, MergeTagsRequest, RenameTagRequest, ResolveTagQuery,
+    SearchTagsQuery, TagActiveModel, TagColumn, TagEntity, TagGraphData, TagGraphEdge,
+    TagGraphNode, TagModel, TagNodesQuery, TagResponse, TagRewriteContent, TagRewriteReport,
+    TagTreeNode, TopTagsQuery, UpdateTagMetadataRequest, UpdateTagRequest,
+};
+
+// ============================================
+// 重新导出 Task 类型
+// ============================================
+
+pub use types::task::{
+    TaskActiveModel, TaskColumn, TaskDueFilter, TaskEntity, TaskModel, TaskPriority, TaskQuery,
+    TaskRelation, TaskResponse, TaskStatus, ToggleTaskRequest,
+};
+
+// ============================================
+// 重新导出 Writing 类型
+// ============================================
+
+pub use types::writing::{
+    NodeWritingStats, SetWritingGoalRequest, WritingDay, WritingGoalActiveModel, WritingGoalColumn,
+    WritingGoalEntity, WritingGoalKind, WritingGoalModel, WritingGoalProgress, WritingGoalRelation,
+    WritingGoalResponse, WritingProgressActiveModel, WritingProgressColumn, WritingProgressEntity,
+    WritingProgressModel, WritingProgressQuery, WritingProgressRelation, WritingProgressResponse,
+    WritingStats, WritingStatsActiveModel, WritingStatsColumn, WritingStatsEntity,
+    WritingStatsModel, WritingStatsQuery, WritingStatsRelation, WritingStatsResponse,
+    WritingStreak, CJK_CHARACTERS_PER_MINUTE, LATIN_WORDS_PER_MINUTE,
 };
 
 // ============================================
@@ -93,6 +211,18 @@ pub use types::user::{
     UserPlan, UserResponse,
 };
 
+// ============================================
+// 重新导出 Auth 类型
+// ============================================
+
+pub use types::auth::{
+    ApiTokenActiveModel, ApiTokenColumn, ApiTokenEntity, ApiTokenGrant, ApiTokenModel,
+    ApiTokenResponse, ApiTokenScope, AuthSessionActiveModel, AuthSessionColumn, AuthSessionEntity,
+    AuthSessionModel, AuthTokenResponse, AuthUser, CreateApiTokenRequest, CreatedApiTokenResponse,
+    LoginRequest, RefreshTokenRequest, UserCredentialActiveModel, UserCredentialColumn,
+    UserCredentialEntity, UserCredentialModel, TOKEN_TYPE_BEARER,
+};
+
 // ============================================
 // 重新导出 Attachment 类型
 // ============================================
@@ -121,18 +251,72 @@ pub use db::connection::DbConnection;
 // 重新导出纯函数
 // ============================================
 
+pub use r#fn::auth::{
+    generate_token, hash_password, hash_token, is_valid_origin, parse_bearer, verify_password,
+    MIN_PASSWORD_LEN,
+};
+
 pub use r#fn::backup::{
     cleanup_old_backups, create_backup, delete_backup, extract_backup_info,
     generate_backup_filename, is_valid_backup_filename, list_backups, restore_backup, BackupInfo,
 };
 
+pub use r#fn::change_log::{
+    change_target, compact_changes, superseded_seqs, ChangeCompactor, ChangeTarget,
+};
+
+pub use r#fn::content::{
+    crdt_content, crdt_state_from_content, merge_crdt_updates, merge_lexical, CrdtDocument,
+    LexicalMerge, CRDT_ROOT_NAME, CRDT_TEXT_NAME,
+};
+
 pub use r#fn::crypto::{delete_key, generate_key, get_or_create_key, key_exists};
 
 #[cfg(debug_assertions)]
 pub use r#fn::crypto::get_dev_key;
 
+pub use r#fn::diary::{
+    build_diary_calendar, default_diary_template, diary_entry_date, diary_entry_title,
+    diary_folders, diary_on_this_day, format_diary_date, parse_diary_date, render_diary_template,
+    DiaryFolders, DEFAULT_DIARY_ROOT_FOLDER,
+};
+
+pub use r#fn::graph::build_node_graph;
+
+pub use r#fn::ledger::{
+    account_matches, append_ledger_entries, categorize_transaction, csv_to_ledger_entries,
+    find_duplicate_transactions, format_ledger_entry, is_ledger_account, ledger_balances,
+    ledger_report, parse_csv, parse_ledger, truncate_account, ImportedTransaction, LedgerPeriod,
+    ParsedLedger, DEFAULT_EXPENSE_ACCOUNT, DEFAULT_INCOME_ACCOUNT, LEDGER_ACCOUNT_SEPARATOR,
+};
+
+pub use r#fn::link::{extract_mentions, rewrite_mentions, MentionRef};
+
 pub use r#fn::node::{
-    create_node_with_content, delete_node_recursive, duplicate_node, extract_tags,
+    build_node_tree, create_node_with_content, delete_node_recursive, duplicate_node, extract_tags,
     generate_copy_title, is_folder, is_root_node, node_type_needs_content, serialize_tags,
     transform_title,
 };
+
+pub use r#fn::search::{
+    content_plain_text, filter_nodes, parse_search_query, DateRange, SearchFilter, SearchQuery,
+    SearchTerm,
+};
+
+pub use r#fn::sync::{
+    is_syncable, normalize_server_url, order_changes, pull_cursor, sync_version_id,
+};
+
+pub use r#fn::tag::{
+    alias_renames, build_tag_tree, extract_content_tags, merge_tag_metadata, normalize_tag_path,
+    rename_tag_path, rewrite_content_tags, rewrite_tag_list, set_tag_aliases, tag_matches,
+    tag_path_prefixes, TagRename,
+};
+
+pub use r#fn::task::{extract_tasks, filter_tasks, toggle_task_in_content, ExtractedTask};
+
+pub use r#fn::writing::{
+    aggregate_writing_stats, content_writing_stats, count_words, daily_writing, is_cjk,
+    is_writing_node, subtree_ids, writing_goal_progress, writing_heatmap, writing_streak,
+    DailyWriting,
+};
@@ -1,27 +1,21 @@
 //! 宏模块
 //!
-//! 提供声明式宏用于自动生成 Warp handlers 和 Tauri commands。
+//! 由端点注册表（`api::registry` 中的 `api_registry!`）生成各个入口：
 //!
-//! ## 设计原则
-//!
-//! - **零手写边界代码**：所有 HTTP/IPC 处理代码由宏生成
-//! - **类型安全**：编译时验证端点定义正确性
-//! - **统一行为**：Warp 和 Tauri 使用相同的业务逻辑
+//! - `warp_routes!` - 每个有 HTTP 路由的条目生成一个 `EndpointRoute`
+//! - `tauri_commands!` - 每个条目生成一个 `#[tauri::command]` 函数
+//! - `tauri_handler!` - 生成包含所有命令的 `tauri::generate_handler!`
+//! - `registry_docs!` - 每个有 HTTP 路由的条目生成一个 `RouteDoc`
 //!
 //! ## 使用示例
 //!
 //! ```rust,ignore
 //! // Warp 服务器
-//! let routes = warp_routes!(db,
-//!     GetWorkspaces => GET "/api/workspaces",
-//!     CreateWorkspace => POST "/api/workspaces",
-//! );
+//! let routes = macros::combine_routes(api_registry!(warp_routes, db, auth));
 //!
 //! // Tauri 应用
-//! tauri_commands!(
-//!     GetWorkspaces => get_workspaces,
-//!     CreateWorkspace => create_workspace,
-//! );
+//! api_registry!(tauri_commands);
+//! builder.invoke_handler(api_registry!(tauri_handler, select_directory, save_file));
 //! ```
 
 pub mod rejection;
@@ -29,244 +23,534 @@ pub mod rejection;
 pub use rejection::{handle_rejection, AppRejection};
 
 use sea_orm::DatabaseConnection;
+use serde::de::{DeserializeOwned, Deserializer, Visitor};
+use serde_json::Value;
+use std::any::TypeId;
+use std::collections::HashMap;
 use std::sync::Arc;
-use warp::Filter;
+use warp::filters::BoxedFilter;
+use warp::http::Method;
+use warp::hyper::body::Bytes;
+use warp::reply::Response;
+use warp::{Filter, Reply};
+
+use crate::api::access;
+use crate::api::registry::{arg_key, ArgSource};
+use crate::api::ApiEndpoint;
+use crate::{AppError, AppResult, AuthUser};
 
 // ============================================================================
-// Warp 辅助函数
+// 请求参数
 // ============================================================================
 
-/// 注入数据库连接的 Filter
-///
-/// 将 Arc<DatabaseConnection> 注入到 Warp handler 中
-pub fn with_db(
-    db: Arc<DatabaseConnection>,
-) -> impl Filter<Extract = (Arc<DatabaseConnection>,), Error = std::convert::Infallible> + Clone {
-    warp::any().map(move || db.clone())
+/// 匹配到路由的请求：认证身份、路径参数、查询字符串和请求体
+pub struct RouteRequest {
+    auth: Option<AuthUser>,
+    params: HashMap<String, String>,
+    query: String,
+    body: Bytes,
 }
 
-/// 从 JSON body 提取输入的 Filter
-///
-/// 对于有请求体的端点，从 JSON body 反序列化输入
-pub fn json_body<T: serde::de::DeserializeOwned + Send>(
-) -> impl Filter<Extract = (T,), Error = warp::Rejection> + Clone {
-    warp::body::content_length_limit(1024 * 1024) // 1MB 限制
-        .and(warp::body::json())
+impl RouteRequest {
+    /// 按来源取出参数
+    ///
+    /// 缺少参数时按 null 反序列化，因此 `Option` 类型的参数可以省略。
+    pub fn arg<T: DeserializeOwned>(&self, source: ArgSource, name: &str) -> AppResult<T> {
+        match source {
+            ArgSource::Path => {
+                let value = self.params.get(name).cloned().unwrap_or_default();
+                serde_json::from_value(Value::String(value))
+                    .map_err(|e| AppError::validation(format!("无效的路径参数 {}: {}", name, e)))
+            }
+            ArgSource::Query => T::deserialize(QueryDeserializer::new(&self.query))
+                .map_err(|e| AppError::validation(format!("无效的查询参数: {}", e))),
+            ArgSource::Param => {
+                let key = arg_key(name);
+                let value = serde_urlencoded::from_str::<Vec<(String, String)>>(&self.query)
+                    .map_err(|e| AppError::validation(format!("无效的查询参数: {}", e)))?
+                    .into_iter()
+                    .find(|(k, _)| *k == key)
+                    .map(|(_, v)| v);
+                match value.as_deref() {
+                    None | Some("") | Some("null") => serde_json::from_value(Value::Null)
+                        .map_err(|_| AppError::validation(format!("缺少查询参数 {}", key))),
+                    // 字符串类型直接使用原值，数字和布尔值按 JSON 解析
+                    Some(value) => serde_json::from_value(Value::String(value.to_string()))
+                        .or_else(|_| serde_json::from_str(value))
+                        .map_err(|e| {
+                            AppError::validation(format!("无效的查询参数 {}: {}", key, e))
+                        }),
+                }
+            }
+            ArgSource::Body => {
+                let body = self.json_body()?;
+                serde_json::from_value(body)
+                    .map_err(|e| AppError::validation(format!("无效的请求体: {}", e)))
+            }
+            ArgSource::Field => {
+                let key = arg_key(name);
+                let value = match self.json_body()? {
+                    Value::Object(mut fields) => fields.remove(&key).unwrap_or(Value::Null),
+                    Value::Null => Value::Null,
+                    _ => return Err(AppError::validation("请求体必须是 JSON 对象")),
+                };
+                serde_json::from_value(value)
+                    .map_err(|e| AppError::validation(format!("无效的字段 {}: {}", key, e)))
+            }
+            ArgSource::Auth => serde_json::to_value(&self.auth)
+                .and_then(serde_json::from_value)
+                .map_err(|e| AppError::internal(format!("认证参数 {} 的类型无效: {}", name, e))),
+        }
+    }
+
+    /// 解析 JSON 请求体（空请求体为 null）
+    fn json_body(&self) -> AppResult<Value> {
+        if self.body.iter().all(u8::is_ascii_whitespace) {
+            return Ok(Value::Null);
+        }
+        serde_json::from_slice(&self.body)
+            .map_err(|e| AppError::validation(format!("无效的 JSON 格式: {}", e)))
+    }
 }
 
-/// 从 query 参数提取输入的 Filter
+/// 查询字符串的反序列化器
 ///
-/// 对于 GET 请求，从 query string 反序列化输入
-pub fn query_params<T: serde::de::DeserializeOwned + Send + 'static>(
-) -> impl Filter<Extract = (T,), Error = warp::Rejection> + Clone {
-    warp::query::<T>()
+/// 与 `serde_urlencoded` 相同，另外支持顶层的 `Option`（总是 Some），
+/// 使 Tauri 命令中可以省略的查询结构体（如 `Option<NodeGraphQuery>`）也能用于 HTTP。
+struct QueryDeserializer<'de>(serde_urlencoded::Deserializer<'de>);
+
+impl<'de> QueryDeserializer<'de> {
+    fn new(query: &'de str) -> Self {
+        Self(serde_urlencoded::Deserializer::new(form_urlencoded::parse(
+            query.as_bytes(),
+        )))
+    }
+}
+
+impl<'de> Deserializer<'de> for QueryDeserializer<'de> {
+    type Error = serde_urlencoded::de::Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        self.0.deserialize_any(visitor)
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_some(self.0)
+    }
+
+    fn deserialize_seq<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        self.0.deserialize_seq(visitor)
+    }
+
+    fn deserialize_unit<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        self.0.deserialize_unit(visitor)
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string bytes byte_buf
+        unit_struct newtype_struct tuple tuple_struct map struct enum identifier ignored_any
+    }
+}
+
+/// 按路由模式匹配请求路径，返回百分号解码后的路径参数
+fn match_path(pattern: &str, path: &str) -> Option<HashMap<String, String>> {
+    let pattern: Vec<&str> = pattern.trim_start_matches('/').split('/').collect();
+    let segments: Vec<&str> = path.trim_start_matches('/').split('/').collect();
+    if pattern.len() != segments.len() {
+        return None;
+    }
+
+    let mut params = HashMap::new();
+    for (expected, segment) in pattern.iter().zip(segments) {
+        match expected.strip_prefix(':') {
+            Some(name) => {
+                let value = percent_encoding::percent_decode_str(segment)
+                    .decode_utf8()
+                    .ok()?;
+                if value.is_empty() {
+                    return None;
+                }
+                params.insert(name.to_string(), value.into_owned());
+            }
+            None if *expected == segment => {}
+            None => return None,
+        }
+    }
+    Some(params)
 }
 
 // ============================================================================
-// Warp Routes 宏
+// 端点执行
 // ============================================================================
 
-/// 生成 Warp 路由
-///
-/// ## 语法
-///
-/// ```rust,ignore
-/// warp_routes!(db,
-///     EndpointType => METHOD "path",
-///     EndpointType => METHOD "path" with body,
-///     EndpointType => METHOD "path" with query,
-/// );
-/// ```
-///
-/// ## 参数
-///
-/// - `db`: DatabaseConnection 实例
-/// - `EndpointType`: 实现 ApiEndpoint trait 的类型
-/// - `METHOD`: HTTP 方法 (GET, POST, PUT, DELETE)
-/// - `"path"`: URL 路径
-/// - `with body`: 从 JSON body 提取输入（可选）
-/// - `with query`: 从 query string 提取输入（可选）
-///
-/// ## 示例
+/// 检查已认证用户的访问权限后执行端点（未启用认证时不检查）
+pub async fn authorize_and_execute<E: ApiEndpoint>(
+    db: &DatabaseConnection,
+    auth: Option<&AuthUser>,
+    input: E::Input,
+) -> AppResult<E::Output> {
+    if let Some(user) = auth {
+        access::authorize(db, user, &E::access(&input)).await?;
+    }
+    E::execute(db, input).await
+}
+
+/// 由请求构造端点 Input 的函数
+pub type InputFn<E> = fn(&RouteRequest) -> AppResult<<E as ApiEndpoint>::Input>;
+
+/// 注册表生成的一个 HTTP 路由
+pub struct EndpointRoute {
+    /// HTTP 方法
+    pub method: Method,
+    /// 路径，参数写作 `:name`
+    pub path: &'static str,
+    filter: BoxedFilter<(Response,)>,
+}
+
+/// 为端点生成 HTTP 路由
 ///
-/// ```rust,ignore
-/// let routes = warp_routes!(db,
-///     GetWorkspaces => GET "/api/workspaces",
-///     GetWorkspace => GET "/api/workspaces" / String with query,
-///     CreateWorkspace => POST "/api/workspaces" with body,
-///     UpdateWorkspace => PUT "/api/workspaces" / String with body,
-///     DeleteWorkspace => DELETE "/api/workspaces" / String,
-/// );
-/// ```
-#[macro_export]
-macro_rules! warp_routes {
-    // 基础路由（无输入）
-    ($db:expr, $($endpoint:ty => $method:ident $path:literal),* $(,)?) => {{
-        use warp::Filter;
-        use $crate::api::ApiEndpoint;
-        use $crate::macros::{with_db, handle_rejection, AppRejection};
-        use std::sync::Arc;
-
-        let db = Arc::new($db);
-        let mut routes = warp::any()
-            .and(warp::path::end())
-            .map(|| warp::reply::json(&serde_json::json!({"status": "ok"})))
-            .boxed();
+/// 路径和方法匹配后不再拒绝请求：认证、参数和执行的错误都在这里转换为错误响应，
+/// 这样字面路径（如 `/api/nodes/batch`）匹配后不会再落到参数路径（`/api/nodes/:id`）。
+/// 端点没有输出时响应 `{"success": true}`。
+pub fn endpoint_route<E: ApiEndpoint>(
+    method: Method,
+    path: &'static str,
+    input: InputFn<E>,
+    db: Arc<DatabaseConnection>,
+    auth: BoxedFilter<(Option<AuthUser>,)>,
+) -> EndpointRoute {
+    let matched = warp::path::full().and_then(move |full: warp::path::FullPath| async move {
+        match_path(path, full.as_str()).ok_or_else(warp::reject::not_found)
+    });
+    let method_filter = match method {
+        Method::GET => warp::get().boxed(),
+        Method::POST => warp::post().boxed(),
+        Method::PUT => warp::put().boxed(),
+        Method::DELETE => warp::delete().boxed(),
+        _ => unreachable!("注册表不支持的 HTTP 方法: {}", method),
+    };
+    let raw_query = warp::query::raw().or(warp::any().map(String::new)).unify();
+    // 认证和读取请求体的拒绝留到匹配之后转换为错误响应
+    let request = auth
+        .and(raw_query)
+        .and(warp::body::bytes())
+        .map(|auth, query, body| Ok((auth, query, body)))
+        .or_else(|rejection| async move { Ok::<_, warp::Rejection>((Err(rejection),)) });
 
-        $(
-            let db_clone = db.clone();
-            let route = warp::path!($path)
-                .and(warp::$method())
-                .and(with_db(db_clone))
-                .and_then(|db: Arc<sea_orm::DatabaseConnection>| async move {
-                    <$endpoint>::execute(&db, ())
-                        .await
-                        .map(|output| warp::reply::json(&output))
-                        .map_err(|e| warp::reject::custom(AppRejection(e)))
-                })
-                .boxed();
-            routes = routes.or(route).unify().boxed();
-        )*
+    let filter = matched
+        .and(method_filter)
+        .and(request)
+        .then(
+            move |params: HashMap<String, String>,
+                  request: Result<(Option<AuthUser>, String, Bytes), warp::Rejection>| {
+                let db = db.clone();
+                async move {
+                    let (auth, query, body) = match request {
+                        Ok(request) => request,
+                        Err(rejection) => return rejection_response(rejection).await,
+                    };
+                    let request = RouteRequest {
+                        auth,
+                        params,
+                        query,
+                        body,
+                    };
+                    let result = match input(&request) {
+                        Ok(input) => {
+                            authorize_and_execute::<E>(&db, request.auth.as_ref(), input).await
+                        }
+                        Err(e) => Err(e),
+                    };
+                    match result {
+                        Ok(_) if TypeId::of::<E::Output>() == TypeId::of::<()>() => {
+                            warp::reply::json(&serde_json::json!({"success": true})).into_response()
+                        }
+                        Ok(output) => warp::reply::json(&output).into_response(),
+                        Err(e) => {
+                            rejection_response(warp::reject::custom(AppRejection::from(e))).await
+                        }
+                    }
+                }
+            },
+        )
+        .boxed();
 
-        routes.recover(handle_rejection)
-    }};
+    EndpointRoute {
+        method,
+        path,
+        filter,
+    }
 }
 
-/// 生成带 JSON body 的 Warp 路由
-#[macro_export]
-macro_rules! warp_route_with_body {
-    ($db:expr, $endpoint:ty, $method:ident, $path:literal) => {{
-        use warp::Filter;
-        use $crate::api::ApiEndpoint;
-        use $crate::macros::{json_body, with_db, AppRejection};
-        use std::sync::Arc;
-
-        let db = Arc::new($db);
-        warp::path!($path)
-            .and(warp::$method())
-            .and(with_db(db))
-            .and(json_body::<<$endpoint as ApiEndpoint>::Input>())
-            .and_then(
-                |db: Arc<sea_orm::DatabaseConnection>,
-                 input: <$endpoint as ApiEndpoint>::Input| async move {
-                    <$endpoint>::execute(&db, input)
-                        .await
-                        .map(|output| warp::reply::json(&output))
-                        .map_err(|e| warp::reject::custom(AppRejection(e)))
-                },
-            )
-    }};
+/// 按 `handle_rejection` 的格式生成错误响应
+async fn rejection_response(rejection: warp::Rejection) -> Response {
+    let Ok(reply) = handle_rejection(rejection).await;
+    reply.into_response()
 }
 
-/// 生成带 query 参数的 Warp 路由
-#[macro_export]
-macro_rules! warp_route_with_query {
-    ($db:expr, $endpoint:ty, $method:ident, $path:literal) => {{
-        use warp::Filter;
-        use $crate::api::ApiEndpoint;
-        use $crate::macros::{query_params, with_db, AppRejection};
-        use std::sync::Arc;
-
-        let db = Arc::new($db);
-        warp::path!($path)
-            .and(warp::$method())
-            .and(with_db(db))
-            .and(query_params::<<$endpoint as ApiEndpoint>::Input>())
-            .and_then(
-                |db: Arc<sea_orm::DatabaseConnection>,
-                 input: <$endpoint as ApiEndpoint>::Input| async move {
-                    <$endpoint>::execute(&db, input)
-                        .await
-                        .map(|output| warp::reply::json(&output))
-                        .map_err(|e| warp::reject::custom(AppRejection(e)))
-                },
-            )
-    }};
+/// 合并注册表生成的路由
+///
+/// 逐段比较路径，字面段优先于参数段，使 `/api/users/current` 先于 `/api/users/:id` 匹配。
+pub fn combine_routes(mut routes: Vec<EndpointRoute>) -> BoxedFilter<(Response,)> {
+    routes.sort_by_key(|route| {
+        route
+            .path
+            .split('/')
+            .map(|segment| segment.starts_with(':'))
+            .collect::<Vec<_>>()
+    });
+    routes
+        .into_iter()
+        .map(|route| route.filter)
+        .reduce(|all, route| all.or(route).unify().boxed())
+        .unwrap_or_else(|| {
+            warp::any()
+                .and_then(|| async { Err(warp::reject::not_found()) })
+                .boxed()
+        })
 }
 
 // ============================================================================
-// Tauri Commands 宏
+// 注册表的生成宏
 // ============================================================================
 
-/// 生成 Tauri commands
-///
-/// ## 语法
-///
-/// ```rust,ignore
-/// tauri_commands!(
-///     EndpointType => command_name,
-///     EndpointType => command_name,
-/// );
-/// ```
-///
-/// ## 参数
-///
-/// - `EndpointType`: 实现 ApiEndpoint trait 的类型
-/// - `command_name`: 生成的 Tauri command 函数名（snake_case）
-///
-/// ## 生成的代码
-///
-/// 对于每个端点，生成一个 `#[tauri::command]` 标注的异步函数：
-///
-/// ```rust,ignore
-/// #[tauri::command]
-/// pub async fn command_name(
-///     db: tauri::State<'_, sea_orm::DatabaseConnection>,
-///     input: <EndpointType as ApiEndpoint>::Input,
-/// ) -> Result<<EndpointType as ApiEndpoint>::Output, String> {
-///     EndpointType::execute(&db, input)
-///         .await
-///         .map_err(|e| e.to_string())
-/// }
-/// ```
-///
-/// ## 示例
-///
-/// ```rust,ignore
-/// tauri_commands!(
-///     GetWorkspaces => get_workspaces,
-///     CreateWorkspace => create_workspace,
-///     DeleteWorkspace => delete_workspace,
-/// );
+/// 参数在 HTTP 请求中的名称：条目中给出的键，否则为参数名
+#[doc(hidden)]
+#[macro_export]
+macro_rules! registry_key {
+    ($arg:ident) => {
+        stringify!($arg)
+    };
+    ($arg:ident $key:literal) => {
+        $key
+    };
+}
+
+/// 生成 Warp 路由（`Vec<EndpointRoute>`）
 ///
-/// // 在 Tauri Builder 中使用
-/// tauri::Builder::default()
-///     .invoke_handler(tauri::generate_handler![
-///         get_workspaces,
-///         create_workspace,
-///         delete_workspace,
-///     ])
-/// ```
+/// 附加参数为数据库连接（`Arc<DatabaseConnection>`）和认证 Filter
+/// （`BoxedFilter<(Option<AuthUser>,)>`），只有 Tauri 命令的条目被跳过。
 #[macro_export]
-macro_rules! tauri_commands {
-    ($($endpoint:ty => $name:ident),* $(,)?) => {
+macro_rules! warp_routes {
+    (
+        [$db:expr, $auth:expr]
         $(
-            #[tauri::command]
-            pub async fn $name(
-                db: tauri::State<'_, sea_orm::DatabaseConnection>,
-                input: <$endpoint as $crate::api::ApiEndpoint>::Input,
-            ) -> Result<<$endpoint as $crate::api::ApiEndpoint>::Output, String> {
-                <$endpoint as $crate::api::ApiEndpoint>::execute(&db, input)
-                    .await
-                    .map_err(|e| e.to_string())
-            }
+            $summary:literal $ep:ident $(: $method:ident $path:literal)?
+                => $cmd:ident($($src:ident $(($key:literal))? $arg:ident: $ty:ty),* $(,)?)
+                -> $input:expr;
         )*
+    ) => {{
+        #[allow(unused_imports)]
+        use $crate::api::registry::prelude::*;
+        [$(
+            $crate::warp_routes!(
+                @route $db, $auth, [$($method $path)?] $ep ($($src [$($key)?] $arg: $ty),*) -> $input
+            ),
+        )*]
+        .into_iter()
+        .flatten()
+        .collect::<::std::vec::Vec<$crate::macros::EndpointRoute>>()
+    }};
+    (@route $db:expr, $auth:expr, [] $($rest:tt)*) => {
+        ::std::option::Option::None
     };
+    (
+        @route $db:expr, $auth:expr, [$method:ident $path:literal]
+        $ep:ident ($($src:ident [$($key:literal)?] $arg:ident: $ty:ty),*) -> $input:expr
+    ) => {{
+        #[allow(unused_variables)]
+        fn input(
+            req: &$crate::macros::RouteRequest,
+        ) -> $crate::AppResult<<$ep as $crate::api::ApiEndpoint>::Input> {
+            #[allow(unused_imports)]
+            use $crate::api::registry::prelude::*;
+            $(
+                let $arg: $ty = req.arg(
+                    $crate::api::registry::ArgSource::$src,
+                    $crate::registry_key!($arg $($key)?),
+                )?;
+            )*
+            Ok($input)
+        }
+        ::std::option::Option::Some($crate::macros::endpoint_route::<$ep>(
+            ::warp::http::Method::$method,
+            $path,
+            input,
+            $db.clone(),
+            $auth.clone(),
+        ))
+    }};
 }
 
-/// 生成无输入参数的 Tauri command
+/// 生成 Tauri 命令
 ///
-/// 用于 Input = () 的端点
+/// 每个条目生成一个同名的 `#[tauri::command]` 异步函数，参数即条目的参数；
+/// `Auth` 来源的参数不是命令参数，在函数中为 None。
 #[macro_export]
-macro_rules! tauri_command_no_input {
-    ($endpoint:ty => $name:ident) => {
-        #[tauri::command]
-        pub async fn $name(
-            db: tauri::State<'_, sea_orm::DatabaseConnection>,
-        ) -> Result<<$endpoint as $crate::api::ApiEndpoint>::Output, String> {
-            <$endpoint as $crate::api::ApiEndpoint>::execute(&db, ())
+macro_rules! tauri_commands {
+    (
+        []
+        $(
+            $summary:literal $ep:ident $(: $method:ident $path:literal)?
+                => $cmd:ident($($src:ident $(($key:literal))? $arg:ident: $ty:ty),* $(,)?)
+                -> $input:expr;
+        )*
+    ) => {
+        $(
+            $crate::tauri_commands!(
+                @command $summary $ep $cmd [$input] [] [] $($src $arg: $ty,)*
+            );
+        )*
+    };
+    (
+        @command $summary:literal $ep:ident $cmd:ident [$input:expr] [$($params:tt)*] [$($auth:tt)*]
+        Auth $arg:ident: $ty:ty, $($rest:tt)*
+    ) => {
+        $crate::tauri_commands!(
+            @command $summary $ep $cmd [$input] [$($params)*] [$($auth)* $arg: $ty,] $($rest)*
+        );
+    };
+    (
+        @command $summary:literal $ep:ident $cmd:ident [$input:expr] [$($params:tt)*] [$($auth:tt)*]
+        $src:ident $arg:ident: $ty:ty, $($rest:tt)*
+    ) => {
+        $crate::tauri_commands!(
+            @command $summary $ep $cmd [$input] [$($params)* $arg: $ty,] [$($auth)*] $($rest)*
+        );
+    };
+    (
+        @command $summary:literal $ep:ident $cmd:ident [$input:expr]
+        [$($arg:ident: $ty:ty,)*] [$($auth:ident: $auth_ty:ty,)*]
+    ) => {
+        #[doc = $summary]
+        #[::tauri::command]
+        pub async fn $cmd(
+            db: ::tauri::State<'_, ::sea_orm::DatabaseConnection>,
+            $($arg: $ty),*
+        ) -> ::std::result::Result<<$ep as $crate::api::ApiEndpoint>::Output, String> {
+            $(let $auth: $auth_ty = ::std::option::Option::None;)*
+            <$ep as $crate::api::ApiEndpoint>::execute(&db, $input)
                 .await
                 .map_err(|e| e.to_string())
         }
     };
 }
+
+/// 生成 `tauri::generate_handler!`
+///
+/// 附加参数为注册表以外的命令（文件、备份等）。
+#[macro_export]
+macro_rules! tauri_handler {
+    (
+        [$($extra:ident),* $(,)?]
+        $(
+            $summary:literal $ep:ident $(: $method:ident $path:literal)?
+                => $cmd:ident($($src:ident $(($key:literal))? $arg:ident: $ty:ty),* $(,)?)
+                -> $input:expr;
+        )*
+    ) => {
+        ::tauri::generate_handler![$($extra,)* $($cmd),*]
+    };
+}
+
+/// 生成路由文档（`Vec<RouteDoc>`），只有 Tauri 命令的条目被跳过
+#[macro_export]
+macro_rules! registry_docs {
+    (
+        []
+        $(
+            $summary:literal $ep:ident $(: $method:ident $path:literal)?
+                => $cmd:ident($($src:ident $(($key:literal))? $arg:ident: $ty:ty),* $(,)?)
+                -> $input:expr;
+        )*
+    ) => {{
+        #[allow(unused_imports)]
+        use $crate::api::registry::prelude::*;
+        [$(
+            $crate::registry_docs!(
+                @doc [$($method $path)?] $summary $ep ($($src [$($key)?] $arg: $ty),*)
+            ),
+        )*]
+        .into_iter()
+        .flatten()
+        .collect::<::std::vec::Vec<$crate::server::openapi::RouteDoc>>()
+    }};
+    (@doc [] $($rest:tt)*) => {
+        ::std::option::Option::None
+    };
+    (
+        @doc [$method:ident $path:literal]
+        $summary:literal $ep:ident ($($src:ident [$($key:literal)?] $arg:ident: $ty:ty),*)
+    ) => {
+        ::std::option::Option::Some(
+            $crate::server::openapi::RouteDoc::endpoint::<$ep>(
+                ::warp::http::Method::$method,
+                $path,
+                $summary,
+            )
+            $(.arg::<$ty>(
+                $crate::api::registry::ArgSource::$src,
+                $crate::registry_key!($arg $($key)?),
+            ))*
+        )
+    };
+}
+
+// ============================================================================
+// 测试
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_match_path() {
+        let params = match_path("/api/nodes/:id/move", "/api/nodes/a%20b/move").unwrap();
+        assert_eq!(params["id"], "a b");
+        assert!(match_path("/api/nodes/:id", "/api/nodes/a/move").is_none());
+        assert!(match_path("/api/nodes/batch", "/api/nodes/other").is_none());
+        assert!(match_path("/api/nodes/:id", "/api/nodes/").is_none());
+    }
+
+    #[test]
+    fn test_route_request_args() {
+        let request = RouteRequest {
+            auth: None,
+            params: HashMap::new(),
+            query: "parentId=&maxDepth=2&name=42".to_string(),
+            body: Bytes::from_static(br#"{"nodeIds":["a"]}"#),
+        };
+        let parent_id: Option<String> = request.arg(ArgSource::Param, "parent_id").unwrap();
+        assert_eq!(parent_id, None);
+        let max_depth: Option<u32> = request.arg(ArgSource::Param, "max_depth").unwrap();
+        assert_eq!(max_depth, Some(2));
+        let name: String = request.arg(ArgSource::Param, "name").unwrap();
+        assert_eq!(name, "42");
+        let node_ids: Vec<String> = request.arg(ArgSource::Field, "node_ids").unwrap();
+        assert_eq!(node_ids, vec!["a"]);
+        assert!(request.arg::<String>(ArgSource::Param, "missing").is_err());
+
+        // 查询结构体可以是 Option，查询字符串为空时也是 Some
+        let query: Option<crate::NodeListQuery> = request.arg(ArgSource::Query, "query").unwrap();
+        assert_eq!(query.unwrap().parent_id.as_deref(), Some(""));
+        let empty = RouteRequest {
+            auth: Some(AuthUser {
+                user_id: "u1".to_string(),
+                session_id: "s1".to_string(),
+                token: None,
+            }),
+            params: HashMap::new(),
+            query: String::new(),
+            body: Bytes::new(),
+        };
+        let query: Option<crate::NodeGraphQuery> = empty.arg(ArgSource::Query, "query").unwrap();
+        assert!(query.unwrap().depth.is_none());
+
+        // 认证身份
+        let auth: Option<AuthUser> = request.arg(ArgSource::Auth, "auth").unwrap();
+        assert!(auth.is_none());
+        let auth: Option<AuthUser> = empty.arg(ArgSource::Auth, "auth").unwrap();
+        assert_eq!(auth.unwrap().user_id, "u1");
+    }
+}
@@ -8,6 +8,7 @@
 //! - **正确的 HTTP 状态码**：根据错误类型返回对应状态码
 //! - **详细错误信息**：包含错误代码和消息
 
+use schemars::JsonSchema;
 use serde::Serialize;
 use std::convert::Infallible;
 use warp::{http::StatusCode, reject::Reject, Rejection, Reply};
@@ -39,8 +40,8 @@ impl From<AppError> for AppRejection {
 /// 错误响应 JSON 结构
 ///
 /// 统一的错误响应格式，包含错误代码和消息。
-#[derive(Serialize)]
-struct ErrorResponse {
+#[derive(Serialize, JsonSchema)]
+pub(crate) struct ErrorResponse {
     /// 错误代码（如 "NOT_FOUND", "VALIDATION_ERROR"）
     code: &'static str,
     /// 错误消息
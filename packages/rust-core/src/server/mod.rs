@@ -2,7 +2,9 @@
 //!
 //! 提供 Warp HTTP 服务器的完整实现，供 api-rust 调用。
 
+mod openapi;
 mod routes;
 mod warp_server;
 
+pub use routes::build_routes;
 pub use warp_server::run_server;
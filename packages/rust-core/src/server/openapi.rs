@@ -0,0 +1,660 @@
+//! OpenAPI 文档
+//!
+//! 由路由表和端点的 `Input` / `Output` 类型生成 OpenAPI 3 文档，在 `/api/openapi.json` 提供。
+//!
+//! - 端点注册表（`api_registry!`）中的路由由 `endpoint_docs` 生成，参数来源决定文档中的
+//!   查询参数和请求体；`routes.rs` 中手写的路由在 `route_docs` 中登记
+//! - 端点路由的 operationId 取 `ApiEndpoint::NAME`，tag 取端点所在的 `api` 子模块名
+//! - 类型的 schema 由 `schemars` 从 serde 定义生成，放在 `components.schemas`
+//! - 测试按结构检查路由表：注册表生成的 Warp 路由都有文档，登记的每个路由都由服务器提供
+
+use schemars::gen::{SchemaGenerator, SchemaSettings};
+use schemars::schema::{InstanceType, Schema, SchemaObject, SingleOrVec};
+use schemars::JsonSchema;
+use serde::Serialize;
+use serde_json::{json, Map, Value};
+use std::any::TypeId;
+use warp::http::Method;
+
+use crate::api::registry::{arg_key, ArgSource};
+use crate::api::{
+    api_token::{CreateApiToken, GetApiTokens, RevokeApiToken},
+    auth::{GetCurrentUser, Login, Logout, RefreshToken},
+    ApiEndpoint,
+};
+use crate::macros::rejection::ErrorResponse;
+use crate::{
+    BackupInfo, ChangeEvent, ChangeEventQuery, CreateApiTokenRequest, LoginRequest,
+    RefreshTokenRequest,
+};
+
+/// OpenAPI 文档的路径
+pub const OPENAPI_PATH: &str = "/api/openapi.json";
+
+/// 生成 schema 的函数
+type SchemaFn = fn(&mut SchemaGenerator) -> Schema;
+
+/// 引用类型的 schema（具名类型放入 `components.schemas`）
+fn schema_of<T: JsonSchema>(gen: &mut SchemaGenerator) -> Schema {
+    gen.subschema_for::<T>()
+}
+
+/// 类型本身的 schema（用于把查询参数结构体展开成参数列表）
+fn inline_schema_of<T: JsonSchema>(gen: &mut SchemaGenerator) -> Schema {
+    T::json_schema(gen)
+}
+
+/// 删除类路由的响应 `{"success": true}`
+#[derive(Serialize, JsonSchema)]
+pub struct SuccessResponse {
+    /// 固定为 true
+    pub success: bool,
+}
+
+/// 错误响应（所有路由失败时的响应体）
+#[derive(Serialize, JsonSchema)]
+pub(crate) struct ApiErrorResponse {
+    /// 固定为 false
+    success: bool,
+
+    /// 错误代码和消息
+    error: ErrorResponse,
+}
+
+/// 健康检查响应
+#[derive(Serialize, JsonSchema)]
+pub struct HealthResponse {
+    /// 固定为 `ok`
+    pub status: String,
+}
+
+/// `/api` 的响应
+#[derive(Serialize, JsonSchema)]
+pub struct ApiInfoResponse {
+    /// 服务名称
+    pub name: String,
+
+    /// 服务版本
+    pub version: String,
+
+    /// OpenAPI 文档的路径
+    pub openapi: String,
+
+    /// 所有路由（如 `GET /api/workspaces`）
+    pub endpoints: Vec<String>,
+}
+
+/// 路由的响应类型
+#[derive(Clone, Copy)]
+enum ResponseDoc {
+    /// JSON 响应
+    Json(SchemaFn),
+    /// 没有内容的成功响应
+    Empty,
+    /// Server-Sent Events，每个事件的 data 是该类型的 JSON
+    EventStream(SchemaFn),
+    /// WebSocket 升级
+    WebSocket,
+}
+
+/// 一个路由的文档信息
+#[derive(Clone)]
+pub struct RouteDoc {
+    /// HTTP 方法
+    pub method: Method,
+    /// 路径，参数写作 `:name`
+    pub path: &'static str,
+    operation_id: &'static str,
+    tag: &'static str,
+    summary: &'static str,
+    public: bool,
+    query: Option<SchemaFn>,
+    params: Vec<(String, SchemaFn)>,
+    body: Option<SchemaFn>,
+    fields: Vec<(String, SchemaFn)>,
+    response: ResponseDoc,
+}
+
+impl RouteDoc {
+    /// 不对应 `ApiEndpoint` 的路由
+    pub fn new(
+        method: Method,
+        path: &'static str,
+        operation_id: &'static str,
+        tag: &'static str,
+        summary: &'static str,
+    ) -> Self {
+        Self {
+            method,
+            path,
+            operation_id,
+            tag,
+            summary,
+            public: false,
+            query: None,
+            params: Vec::new(),
+            body: None,
+            fields: Vec::new(),
+            response: ResponseDoc::Empty,
+        }
+    }
+
+    /// 执行 `E` 的路由，响应为 `E::Output`（没有输出时为 `{"success": true}`）
+    pub fn endpoint<E>(method: Method, path: &'static str, summary: &'static str) -> Self
+    where
+        E: ApiEndpoint,
+        E::Output: JsonSchema,
+    {
+        let route = Self::new(method, path, E::NAME, endpoint_tag::<E>(), summary);
+        if TypeId::of::<E::Output>() == TypeId::of::<()>() {
+            route.success()
+        } else {
+            route.response::<E::Output>()
+        }
+    }
+
+    /// 不需要认证
+    pub fn public(mut self) -> Self {
+        self.public = true;
+        self
+    }
+
+    /// JSON 请求体
+    pub fn body<T: JsonSchema>(mut self) -> Self {
+        self.body = Some(schema_of::<T>);
+        self
+    }
+
+    /// 查询参数（结构体的每个字段是一个参数）
+    pub fn query<T: JsonSchema>(mut self) -> Self {
+        self.query = Some(inline_schema_of::<T>);
+        self
+    }
+
+    /// 注册表条目的参数
+    ///
+    /// 路径参数已由路径给出，认证身份来自令牌；单个查询参数不可为 null 时必填，请求体字段同理。
+    pub fn arg<T: JsonSchema>(mut self, source: ArgSource, name: &str) -> Self {
+        match source {
+            ArgSource::Path => {}
+            ArgSource::Query => self.query = Some(inline_schema_of::<T>),
+            ArgSource::Param => self.params.push((arg_key(name), schema_of::<T>)),
+            ArgSource::Body => self.body = Some(schema_of::<T>),
+            ArgSource::Field => self.fields.push((arg_key(name), schema_of::<T>)),
+            ArgSource::Auth => {}
+        }
+        self
+    }
+
+    /// JSON 响应
+    pub fn response<T: JsonSchema>(mut self) -> Self {
+        self.response = ResponseDoc::Json(schema_of::<T>);
+        self
+    }
+
+    /// 响应为 `{"success": true}`
+    pub fn success(self) -> Self {
+        self.response::<SuccessResponse>()
+    }
+
+    /// 响应为 Server-Sent Events
+    pub fn event_stream<T: JsonSchema>(mut self) -> Self {
+        self.response = ResponseDoc::EventStream(schema_of::<T>);
+        self
+    }
+
+    /// 响应为 WebSocket 升级
+    pub fn websocket(mut self) -> Self {
+        self.response = ResponseDoc::WebSocket;
+        self
+    }
+
+    /// `/api` 列表中的写法，如 `GET /api/workspaces/:id`
+    pub fn display(&self) -> String {
+        format!("{} {}", self.method, self.path)
+    }
+
+    /// OpenAPI 的路径写法，如 `/api/workspaces/{id}`
+    fn openapi_path(&self) -> String {
+        self.path
+            .split('/')
+            .map(|segment| match segment.strip_prefix(':') {
+                Some(name) => format!("{{{}}}", name),
+                None => segment.to_string(),
+            })
+            .collect::<Vec<_>>()
+            .join("/")
+    }
+
+    fn operation(&self, gen: &mut SchemaGenerator) -> Value {
+        let mut parameters: Vec<Value> = self
+            .path
+            .split('/')
+            .filter_map(|segment| segment.strip_prefix(':'))
+            .map(|name| {
+                json!({
+                    "name": name,
+                    "in": "path",
+                    "required": true,
+                    "schema": {"type": "string"},
+                })
+            })
+            .collect();
+        if let Some(query) = self.query {
+            // 可省略的查询结构体（`Option<T>`）的 schema 是对 T 的引用
+            let schema = query(gen);
+            let schema = gen.dereference(&schema).cloned().unwrap_or(schema);
+            parameters.extend(query_parameters(schema));
+        }
+        for (name, schema) in &self.params {
+            let schema = schema(gen);
+            parameters.push(json!({
+                "name": name,
+                "in": "query",
+                "required": !is_nullable(&schema),
+                "schema": schema,
+            }));
+        }
+
+        let mut operation = Map::new();
+        operation.insert("operationId".into(), json!(self.operation_id));
+        operation.insert("tags".into(), json!([self.tag]));
+        operation.insert("summary".into(), json!(self.summary));
+        if !parameters.is_empty() {
+            operation.insert("parameters".into(), Value::Array(parameters));
+        }
+        if let Some(body) = self.body {
+            operation.insert(
+                "requestBody".into(),
+                json!({
+                    "required": true,
+                    "content": {"application/json": {"schema": body(gen)}},
+                }),
+            );
+        }
+        if !self.fields.is_empty() {
+            let mut properties = Map::new();
+            let mut required = Vec::new();
+            for (name, schema) in &self.fields {
+                let schema = schema(gen);
+                if !is_nullable(&schema) {
+                    required.push(name.clone());
+                }
+                properties.insert(name.clone(), json!(schema));
+            }
+            operation.insert(
+                "requestBody".into(),
+                json!({
+                    "required": true,
+                    "content": {"application/json": {"schema": {
+                        "type": "object",
+                        "properties": properties,
+                        "required": required,
+                    }}},
+                }),
+            );
+        }
+        let success = match self.response {
+            ResponseDoc::Json(schema) => json!({
+                "200": {
+                    "description": "成功",
+                    "content": {"application/json": {"schema": schema(gen)}},
+                }
+            }),
+            ResponseDoc::Empty => json!({"200": {"description": "成功"}}),
+            ResponseDoc::EventStream(schema) => json!({
+                "200": {
+                    "description": "事件流，每个事件的 data 是一个 JSON 对象",
+                    "content": {"text/event-stream": {"schema": schema(gen)}},
+                }
+            }),
+            ResponseDoc::WebSocket => json!({"101": {"description": "升级为 WebSocket"}}),
+        };
+        let mut responses = success.as_object().cloned().unwrap_or_default();
+        responses.insert(
+            "default".into(),
+            json!({
+                "description": "错误",
+                "content": {"application/json": {"schema": schema_of::<ApiErrorResponse>(gen)}},
+            }),
+        );
+        operation.insert("responses".into(), Value::Object(responses));
+        if self.public {
+            operation.insert("security".into(), json!([]));
+        }
+        Value::Object(operation)
+    }
+}
+
+/// 端点所在的 `api` 子模块名，如 `GetWorkspaces` 为 `workspace`
+fn endpoint_tag<E>() -> &'static str {
+    std::any::type_name::<E>()
+        .rsplit("::")
+        .nth(1)
+        .unwrap_or("api")
+}
+
+/// `Option` 类型的 schema（openapi3 设置下带 `nullable: true`）
+fn is_nullable(schema: &Schema) -> bool {
+    matches!(
+        schema,
+        Schema::Object(SchemaObject { extensions, .. })
+            if extensions.get("nullable") == Some(&Value::Bool(true))
+    )
+}
+
+/// 把查询参数结构体的 schema 展开成参数列表（列表参数用逗号分隔）
+fn query_parameters(schema: Schema) -> Vec<Value> {
+    let Schema::Object(SchemaObject {
+        object: Some(object),
+        ..
+    }) = schema
+    else {
+        return Vec::new();
+    };
+    object
+        .properties
+        .into_iter()
+        .map(|(name, schema)| {
+            let is_array = matches!(
+                &schema,
+                Schema::Object(SchemaObject {
+                    instance_type: Some(SingleOrVec::Single(instance_type)),
+                    ..
+                }) if **instance_type == InstanceType::Array
+            );
+            let mut parameter = json!({
+                "name": name,
+                "in": "query",
+                "required": object.required.contains(&name),
+                "schema": schema,
+            });
+            if is_array {
+                parameter["style"] = json!("form");
+                parameter["explode"] = json!(false);
+            }
+            parameter
+        })
+        .collect()
+}
+
+// ============================================================================
+// 路由表
+// ============================================================================
+
+/// 注册表中有 HTTP 路由的端点
+pub fn endpoint_docs() -> Vec<RouteDoc> {
+    crate::api_registry!(registry_docs)
+}
+
+/// 服务器的全部路由
+///
+/// 注册表以外的路由（认证、令牌、事件流、协同编辑、备份和按认证身份分派的路由）
+/// 在 `routes.rs` 中手写，需要在这里登记。
+pub fn route_docs() -> Vec<RouteDoc> {
+    use Method as M;
+
+    let routes = vec![
+        // 系统
+        RouteDoc::new(M::GET, "/health", "health", "system", "健康检查")
+            .public()
+            .response::<HealthResponse>(),
+        RouteDoc::new(M::GET, "/api", "api_info", "system", "服务信息和路由列表")
+            .response::<ApiInfoResponse>(),
+        RouteDoc::new(M::GET, OPENAPI_PATH, "openapi", "system", "OpenAPI 文档").public(),
+        // 认证
+        RouteDoc::endpoint::<Login>(M::POST, "/api/auth/login", "用户名密码登录，签发令牌")
+            .public()
+            .body::<LoginRequest>(),
+        RouteDoc::endpoint::<RefreshToken>(M::POST, "/api/auth/refresh", "用刷新令牌换取新令牌")
+            .public()
+            .body::<RefreshTokenRequest>(),
+        RouteDoc::endpoint::<Logout>(M::POST, "/api/auth/logout", "使当前会话失效"),
+        RouteDoc::endpoint::<GetCurrentUser>(M::GET, "/api/auth/me", "当前登录的用户"),
+        // API 令牌
+        RouteDoc::endpoint::<GetApiTokens>(M::GET, "/api/tokens", "当前用户的 API 令牌"),
+        RouteDoc::endpoint::<CreateApiToken>(
+            M::POST,
+            "/api/tokens",
+            "创建 API 令牌（令牌只返回一次）",
+        )
+        .body::<CreateApiTokenRequest>(),
+        RouteDoc::endpoint::<RevokeApiToken>(M::DELETE, "/api/tokens/:id", "撤销 API 令牌"),
+        // 协同编辑
+        RouteDoc::new(
+            M::GET,
+            "/api/nodes/:node_id/collab",
+            "collab_content",
+            "content",
+            "协同编辑的 WebSocket（Yjs 协议）",
+        )
+        .websocket(),
+        // 事件
+        RouteDoc::new(
+            M::GET,
+            "/api/events",
+            "subscribe_events",
+            "event",
+            "订阅数据变更事件",
+        )
+        .query::<ChangeEventQuery>()
+        .event_stream::<ChangeEvent>(),
+        // 备份
+        RouteDoc::new(M::GET, "/api/backups", "list_backups", "backup", "列出备份")
+            .response::<Vec<BackupInfo>>(),
+        RouteDoc::new(
+            M::POST,
+            "/api/backups",
+            "create_backup",
+            "backup",
+            "创建备份",
+        )
+        .response::<BackupInfo>(),
+        RouteDoc::new(
+            M::DELETE,
+            "/api/backups/:filename",
+            "delete_backup",
+            "backup",
+            "删除备份",
+        )
+        .success(),
+    ];
+    routes.into_iter().chain(endpoint_docs()).collect()
+}
+
+/// 生成 OpenAPI 3 文档
+pub fn openapi_document() -> Value {
+    let mut gen = SchemaSettings::openapi3().into_generator();
+    let mut paths = Map::new();
+    for route in route_docs() {
+        let operation = route.operation(&mut gen);
+        let path = paths
+            .entry(route.openapi_path())
+            .or_insert_with(|| json!({}));
+        path[route.method.as_str().to_lowercase()] = operation;
+    }
+
+    json!({
+        "openapi": "3.0.3",
+        "info": {
+            "title": "Grain API",
+            "version": env!("CARGO_PKG_VERSION"),
+        },
+        "paths": paths,
+        "components": {
+            "schemas": gen.take_definitions(),
+            "securitySchemes": {
+                "bearerAuth": {
+                    "type": "http",
+                    "scheme": "bearer",
+                    "description": "登录获得的访问令牌或 API 令牌（grain_pat_ 开头）",
+                },
+            },
+        },
+        "security": [{"bearerAuth": []}],
+    })
+}
+
+// ============================================================================
+// 测试
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sea_orm::DatabaseConnection;
+    use std::collections::BTreeSet;
+    use std::sync::Arc;
+    use warp::http::StatusCode;
+    use warp::Filter;
+
+    use crate::db::test_utils::setup_test_db;
+    use crate::server::build_routes;
+    use crate::AppConfig;
+
+    /// 路径参数统一写作 `{}`
+    fn route_key(method: &Method, path: &str) -> String {
+        let path: Vec<_> = path
+            .split('/')
+            .map(|s| if s.starts_with(':') { "{}" } else { s })
+            .collect();
+        format!("{} {}", method, path.join("/"))
+    }
+
+    fn documented_routes() -> BTreeSet<String> {
+        route_docs()
+            .iter()
+            .map(|route| route_key(&route.method, route.path))
+            .collect()
+    }
+
+    #[test]
+    fn test_registry_routes_are_documented() {
+        let db = Arc::new(DatabaseConnection::Disconnected);
+        let auth = warp::any().map(|| None).boxed();
+        let registry: BTreeSet<String> = crate::api_registry!(warp_routes, db, auth)
+            .iter()
+            .map(|route| route_key(&route.method, route.path))
+            .collect();
+        let documented = documented_routes();
+        let undocumented: Vec<_> = registry.difference(&documented).collect();
+        assert!(
+            undocumented.is_empty(),
+            "以下注册表路由没有文档: {:?}",
+            undocumented
+        );
+        assert_eq!(
+            route_docs().len(),
+            documented.len(),
+            "路由重复登记（手写路由与注册表路由重复）"
+        );
+
+        // OpenAPI 文档中的操作与路由表一一对应
+        let document = openapi_document();
+        for route in route_docs() {
+            let operation =
+                &document["paths"][route.openapi_path()][route.method.as_str().to_lowercase()];
+            assert_eq!(operation["operationId"], route.operation_id);
+        }
+        let operations: usize = document["paths"]
+            .as_object()
+            .unwrap()
+            .values()
+            .map(|path| path.as_object().unwrap().len())
+            .sum();
+        assert_eq!(operations, route_docs().len());
+    }
+
+    /// 登记的每个路由都由服务器提供
+    ///
+    /// 启用认证且不带令牌时，匹配的路由返回 401 或其他错误，不会执行；
+    /// 只有没有匹配的路由返回 404 或 405。
+    #[tokio::test]
+    async fn test_documented_routes_are_served() {
+        let db = setup_test_db().await;
+        let config = AppConfig {
+            auth_enabled: true,
+            ..Default::default()
+        };
+        let routes = build_routes(Arc::new(db), Arc::new(config));
+        for route in route_docs() {
+            let path: Vec<_> = route
+                .path
+                .split('/')
+                .map(|s| if s.starts_with(':') { "x" } else { s })
+                .collect();
+            let response = warp::test::request()
+                .method(route.method.as_str())
+                .path(&path.join("/"))
+                .reply(&routes)
+                .await;
+            assert!(
+                !matches!(
+                    response.status(),
+                    StatusCode::NOT_FOUND | StatusCode::METHOD_NOT_ALLOWED
+                ),
+                "路由不存在: {}",
+                route.display()
+            );
+        }
+    }
+
+    #[test]
+    fn test_openapi_document() {
+        let document = openapi_document();
+        assert_eq!(document["openapi"], "3.0.3");
+
+        let workspace = &document["paths"]["/api/workspaces/{id}"];
+        assert_eq!(workspace["get"]["operationId"], "get_workspace");
+        assert_eq!(workspace["get"]["tags"], json!(["workspace"]));
+        assert_eq!(workspace["put"]["parameters"][0]["name"], "id");
+        assert_eq!(
+            workspace["put"]["requestBody"]["content"]["application/json"]["schema"]["$ref"],
+            "#/components/schemas/UpdateWorkspaceRequest"
+        );
+        assert!(document["paths"]["/health"]["get"]["security"] == json!([]));
+
+        // 查询参数结构体（包括可省略的）展开成参数，列表参数用逗号分隔
+        let graph = &document["paths"]["/api/workspaces/{workspace_id}/graph"]["get"];
+        let node_types = graph["parameters"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|p| p["name"] == "nodeTypes")
+            .unwrap();
+        assert_eq!(node_types["explode"], false);
+        let nodes = &document["paths"]["/api/workspaces/{workspace_id}/nodes"]["get"];
+        let names: Vec<_> = nodes["parameters"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|p| p["name"].as_str().unwrap())
+            .collect();
+        assert_eq!(names, ["workspace_id", "parentId", "type"]);
+
+        // 参数可以使用与 Tauri 参数名不同的键
+        let search = &document["paths"]["/api/workspaces/{workspace_id}/tags/search"]["get"];
+        assert_eq!(search["parameters"][1]["name"], "q");
+
+        // 单个查询参数和请求体字段按类型是否可为 null 决定是否必填
+        let tree = &document["paths"]["/api/workspaces/{workspace_id}/nodes/tree"]["get"];
+        assert_eq!(tree["parameters"][1]["name"], "rootId");
+        assert_eq!(tree["parameters"][1]["required"], false);
+        let batch = &document["paths"]["/api/nodes/batch"]["delete"];
+        let schema = &batch["requestBody"]["content"]["application/json"]["schema"];
+        assert_eq!(schema["required"], json!(["nodeIds"]));
+        assert_eq!(
+            document["paths"]["/api/tags/{id}"]["delete"]["responses"]["200"]["content"]
+                ["application/json"]["schema"]["$ref"],
+            "#/components/schemas/SuccessResponse"
+        );
+
+        // 所有引用的 schema 都有定义
+        let text = document.to_string();
+        let schemas = document["components"]["schemas"].as_object().unwrap();
+        for reference in text.split("\"#/components/schemas/").skip(1) {
+            let name = &reference[..reference.find('"').unwrap()];
+            assert!(schemas.contains_key(name), "缺少 schema: {}", name);
+        }
+    }
+}
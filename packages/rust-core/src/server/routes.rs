@@ -1,26 +1,27 @@
 //! 路由定义
 //!
-//! 所有 API 路由的统一定义
+//! API 路由由端点注册表（`api_registry!`）生成；这里手写认证、令牌、事件流、协同编辑和备份的路由。
 
 use sea_orm::DatabaseConnection;
 use std::sync::Arc;
 use warp::Filter;
 
+use super::openapi;
+
 use crate::api::{
-    clear_data::{ClearAllData, ClearDataKeepUsers},
-    content::{GetContent, SaveContent},
-    node::{
-        CreateNode, DeleteNode, GetChildNodes, GetNextSortOrder, GetNode, GetNodesByWorkspace, GetRootNodes,
-        MoveNode, UpdateNode,
-    },
-    transaction::{CreateNodeWithContent, CreateNodeWithContentRequest, DeleteNodeRecursive},
-    workspace::{CreateWorkspace, DeleteWorkspace, GetWorkspace, GetWorkspaces, UpdateWorkspace},
-    ApiEndpoint, IdInput, IdWithBodyInput, NextSortOrderInput, NodeIdInput, ParentIdInput, WorkspaceIdInput,
+    access,
+    api_token::{CreateApiToken, GetApiTokens, RevokeApiToken},
+    auth::{GetCurrentUser, Login, Logout, RefreshToken},
+    Access, ApiEndpoint, AuthWithBodyInput, IdInput,
 };
-use crate::macros::AppRejection;
+use crate::collab;
+use crate::db::{api_token_db_fn, auth_db_fn, node_db_fn};
+use crate::events;
+use crate::macros::{self, AppRejection};
+use crate::r#fn::auth::{is_api_token, is_valid_origin, parse_bearer};
 use crate::{
-    AppConfig, CreateNodeRequest, CreateWorkspaceRequest, MoveNodeRequest, SaveContentRequest,
-    UpdateNodeRequest, UpdateWorkspaceRequest,
+    ApiTokenGrant, AppConfig, AppError, AuthUser, ChangeEvent, ChangeEventQuery,
+    CreateApiTokenRequest, LoginRequest, RefreshTokenRequest, WorkspaceRole,
 };
 
 // ============================================================================
@@ -41,6 +42,104 @@ fn with_config(
     warp::any().map(move || config.clone())
 }
 
+/// 认证请求的 Filter
+///
+/// 关闭认证时提取 None；否则从 `Authorization: Bearer` 请求头取访问令牌或 API 令牌
+/// （浏览器的 WebSocket 不能设置请求头，也可以用 `access_token` 查询参数），
+/// 没有令牌、令牌无效或已过期时以 Unauthorized 拒绝。
+fn with_auth(
+    db: Arc<DatabaseConnection>,
+    config: Arc<AppConfig>,
+) -> impl Filter<Extract = (Option<AuthUser>,), Error = warp::Rejection> + Clone {
+    warp::header::optional::<String>("authorization")
+        .and(warp::query::<std::collections::HashMap<String, String>>())
+        .and(with_db(db))
+        .and(with_config(config))
+        .and_then(
+            |header: Option<String>,
+             query: std::collections::HashMap<String, String>,
+             db: Arc<DatabaseConnection>,
+             config: Arc<AppConfig>| async move {
+                if !config.auth_enabled {
+                    return Ok(None);
+                }
+                let token = match header.as_deref() {
+                    Some(header) => parse_bearer(header),
+                    None => query.get("access_token").map(String::as_str),
+                }
+                .ok_or_else(|| AppError::unauthorized("缺少访问令牌"))
+                .map_err(|e| warp::reject::custom(AppRejection::from(e)))?;
+                let user = if is_api_token(token) {
+                    api_token_db_fn::authenticate(db.as_ref(), token)
+                        .await
+                        .map(|model| {
+                            model.map(|model| AuthUser {
+                                user_id: model.user_id.clone(),
+                                session_id: String::new(),
+                                token: Some(ApiTokenGrant::from(&model)),
+                            })
+                        })
+                } else {
+                    auth_db_fn::authenticate(db.as_ref(), token)
+                        .await
+                        .map(|session| {
+                            session.map(|session| AuthUser {
+                                user_id: session.user_id,
+                                session_id: session.id,
+                                token: None,
+                            })
+                        })
+                };
+                match user {
+                    Ok(Some(user)) => Ok(Some(user)),
+                    Ok(None) => Err(warp::reject::custom(AppRejection::from(
+                        AppError::unauthorized("访问令牌无效或已过期"),
+                    ))),
+                    Err(e) => Err(warp::reject::custom(AppRejection::from(e))),
+                }
+            },
+        )
+}
+
+/// 要求已认证身份的 Filter（未启用认证时以 Unauthorized 拒绝）
+fn with_auth_user(
+    db: Arc<DatabaseConnection>,
+    config: Arc<AppConfig>,
+) -> impl Filter<Extract = (AuthUser,), Error = warp::Rejection> + Clone {
+    with_auth(db, config).and_then(|auth: Option<AuthUser>| async move {
+        auth.ok_or_else(|| {
+            warp::reject::custom(AppRejection::from(AppError::unauthorized(
+                "服务器未启用认证",
+            )))
+        })
+    })
+}
+
+/// 执行端点：已认证时先按 `ApiEndpoint::access` 检查工作区角色
+async fn run_endpoint<E: ApiEndpoint>(
+    db: &DatabaseConnection,
+    auth: Option<AuthUser>,
+    input: E::Input,
+) -> Result<E::Output, warp::Rejection> {
+    macros::authorize_and_execute::<E>(db, auth.as_ref(), input)
+        .await
+        .map_err(|e| warp::reject::custom(AppRejection::from(e)))
+}
+
+/// 检查已认证用户的访问权限（未启用认证时不检查）
+async fn check_access(
+    db: &DatabaseConnection,
+    auth: Option<&AuthUser>,
+    access: Access,
+) -> Result<(), warp::Rejection> {
+    match auth {
+        Some(user) => access::authorize(db, user, &access)
+            .await
+            .map_err(|e| warp::reject::custom(AppRejection::from(e))),
+        None => Ok(()),
+    }
+}
+
 // ============================================================================
 // 路由构建
 // ============================================================================
@@ -50,63 +149,75 @@ pub fn build_routes(
     db: Arc<DatabaseConnection>,
     config: Arc<AppConfig>,
 ) -> impl Filter<Extract = (impl warp::Reply,), Error = warp::Rejection> + Clone {
-    // API 路由
-    let api = workspace_routes(db.clone())
-        .or(node_routes(db.clone()))
-        .or(content_routes(db.clone()))
-        .or(transaction_routes(db.clone()))
-        .or(clear_data_routes(db.clone()))
-        .or(backup_routes(config.clone()));
+    // 注册表生成的 API 路由
+    let endpoint_auth = with_auth(db.clone(), config.clone()).boxed();
+    let endpoints = macros::combine_routes(crate::api_registry!(warp_routes, db, endpoint_auth));
+
+    // 手写的 API 路由：WebSocket、SSE、令牌和备份
+    let api = collab_content(db.clone(), config.clone())
+        .or(subscribe_events(db.clone(), config.clone()))
+        .or(api_token_routes(db.clone(), config.clone()))
+        .or(backup_routes(db.clone(), config.clone()))
+        .or(endpoints);
+
+    // 认证路由（登录和刷新令牌不需要访问令牌）
+    let auth = auth_routes(db.clone(), config.clone());
+
+    // 每个路由在路径匹配之后认证（未知路径不查询令牌，直接 404），并按端点声明的访问权限检查工作区角色
+    let require_auth = with_auth(db, config.clone())
+        .map(|_: Option<AuthUser>| ())
+        .untuple_one();
 
     // 健康检查
     let health = warp::path!("health")
         .and(warp::get())
         .map(|| warp::reply::json(&serde_json::json!({"status": "ok"})));
 
-    // API 信息
-    let api_info = warp::path!("api").and(warp::get()).map(|| {
-        warp::reply::json(&serde_json::json!({
-            "name": "Grain API",
-            "version": env!("CARGO_PKG_VERSION"),
-            "endpoints": [
-                "GET /api/workspaces",
-                "GET /api/workspaces/:id",
-                "POST /api/workspaces",
-                "PUT /api/workspaces/:id",
-                "DELETE /api/workspaces/:id",
-                "GET /api/workspaces/:workspace_id/nodes",
-                "GET /api/workspaces/:workspace_id/nodes/root",
-                "GET /api/workspaces/:workspace_id/nodes/next-sort-order",
-                "GET /api/nodes/:id",
-                "GET /api/nodes/:id/children",
-                "POST /api/nodes",
-                "PUT /api/nodes/:id",
-                "POST /api/nodes/:id/move",
-                "DELETE /api/nodes/:id",
-                "GET /api/nodes/:node_id/content",
-                "POST /api/contents",
-                "POST /api/nodes/with-content",
-                "DELETE /api/nodes/:id/recursive",
-                "GET /api/backups",
-                "POST /api/backups",
-                "DELETE /api/backups/:filename",
-                "DELETE /api/data/clear",
-                "GET /health"
-            ]
-        }))
-    });
-
-    // CORS 配置
+    // API 信息（路由列表来自 OpenAPI 文档的路由表）
+    let endpoints: Vec<String> = openapi::route_docs().iter().map(|r| r.display()).collect();
+    let api_info = warp::path!("api")
+        .and(warp::get())
+        .and(require_auth)
+        .map(move || {
+            warp::reply::json(&openapi::ApiInfoResponse {
+                name: "Grain API".to_string(),
+                version: env!("CARGO_PKG_VERSION").to_string(),
+                openapi: openapi::OPENAPI_PATH.to_string(),
+                endpoints: endpoints.clone(),
+            })
+        });
+
+    // OpenAPI 文档（不需要认证）
+    let document = Arc::new(openapi::openapi_document());
+    let openapi = warp::path!("api" / "openapi.json")
+        .and(warp::get())
+        .map(move || warp::reply::json(document.as_ref()));
+
+    // CORS 配置（只允许配置中的来源）
     let cors = warp::cors()
-        .allow_any_origin()
         .allow_methods(vec!["GET", "POST", "PUT", "DELETE", "OPTIONS"])
         .allow_headers(vec!["Content-Type", "Authorization"]);
+    let cors = if config.cors_origins.iter().any(|origin| origin == "*") {
+        cors.allow_any_origin()
+    } else {
+        let (origins, invalid): (Vec<_>, Vec<_>) = config
+            .cors_origins
+            .iter()
+            .map(String::as_str)
+            .partition(|origin| is_valid_origin(origin));
+        for origin in invalid {
+            tracing::warn!("忽略无效的 CORS 来源: {}", origin);
+        }
+        cors.allow_origins(origins)
+    };
 
     // 请求日志
     let log = warp::log("grain_api::request");
 
     // 组合所有路由
     health
+        .or(openapi)
+        .or(auth)
         .or(api_info)
         .or(api)
         .recover(crate::macros::handle_rejection)
@@ -115,57 +226,29 @@ pub fn build_routes(
 }
 
 // ============================================================================
-// Workspace 路由
+// Auth 路由
 // ============================================================================
 
-fn workspace_routes(
-    db: Arc<DatabaseConnection>,
-) -> impl Filter<Extract = (impl warp::Reply,), Error = warp::Rejection> + Clone {
-    get_workspaces(db.clone())
-        .or(get_workspace(db.clone()))
-        .or(create_workspace(db.clone()))
-        .or(update_workspace(db.clone()))
-        .or(delete_workspace(db))
-}
-
-fn get_workspaces(
-    db: Arc<DatabaseConnection>,
-) -> impl Filter<Extract = (impl warp::Reply,), Error = warp::Rejection> + Clone {
-    warp::path!("api" / "workspaces")
-        .and(warp::get())
-        .and(with_db(db))
-        .and_then(|db: Arc<DatabaseConnection>| async move {
-            GetWorkspaces::execute(&db, ())
-                .await
-                .map(|r| warp::reply::json(&r))
-                .map_err(|e| warp::reject::custom(AppRejection::from(e)))
-        })
-}
-
-fn get_workspace(
+fn auth_routes(
     db: Arc<DatabaseConnection>,
+    config: Arc<AppConfig>,
 ) -> impl Filter<Extract = (impl warp::Reply,), Error = warp::Rejection> + Clone {
-    warp::path!("api" / "workspaces" / String)
-        .and(warp::get())
-        .and(with_db(db))
-        .and_then(|id: String, db: Arc<DatabaseConnection>| async move {
-            GetWorkspace::execute(&db, IdInput::new(&id))
-                .await
-                .map(|r| warp::reply::json(&r))
-                .map_err(|e| warp::reject::custom(AppRejection::from(e)))
-        })
+    login(db.clone())
+        .or(refresh_token(db.clone()))
+        .or(logout(db.clone(), config.clone()))
+        .or(get_current_user(db, config))
 }
 
-fn create_workspace(
+fn login(
     db: Arc<DatabaseConnection>,
 ) -> impl Filter<Extract = (impl warp::Reply,), Error = warp::Rejection> + Clone {
-    warp::path!("api" / "workspaces")
+    warp::path!("api" / "auth" / "login")
         .and(warp::post())
         .and(warp::body::json())
         .and(with_db(db))
         .and_then(
-            |body: CreateWorkspaceRequest, db: Arc<DatabaseConnection>| async move {
-                CreateWorkspace::execute(&db, body)
+            |body: LoginRequest, db: Arc<DatabaseConnection>| async move {
+                Login::execute(&db, body)
                     .await
                     .map(|r| warp::reply::json(&r))
                     .map_err(|e| warp::reject::custom(AppRejection::from(e)))
@@ -173,16 +256,16 @@ fn create_workspace(
         )
 }
 
-fn update_workspace(
+fn refresh_token(
     db: Arc<DatabaseConnection>,
 ) -> impl Filter<Extract = (impl warp::Reply,), Error = warp::Rejection> + Clone {
-    warp::path!("api" / "workspaces" / String)
-        .and(warp::put())
+    warp::path!("api" / "auth" / "refresh")
+        .and(warp::post())
         .and(warp::body::json())
         .and(with_db(db))
         .and_then(
-            |id: String, body: UpdateWorkspaceRequest, db: Arc<DatabaseConnection>| async move {
-                UpdateWorkspace::execute(&db, IdWithBodyInput::new(&id, body))
+            |body: RefreshTokenRequest, db: Arc<DatabaseConnection>| async move {
+                RefreshToken::execute(&db, body)
                     .await
                     .map(|r| warp::reply::json(&r))
                     .map_err(|e| warp::reject::custom(AppRejection::from(e)))
@@ -190,288 +273,241 @@ fn update_workspace(
         )
 }
 
-fn delete_workspace(
+fn logout(
     db: Arc<DatabaseConnection>,
+    config: Arc<AppConfig>,
 ) -> impl Filter<Extract = (impl warp::Reply,), Error = warp::Rejection> + Clone {
-    warp::path!("api" / "workspaces" / String)
-        .and(warp::delete())
+    warp::path!("api" / "auth" / "logout")
+        .and(warp::post())
+        .and(with_auth_user(db.clone(), config))
         .and(with_db(db))
-        .and_then(|id: String, db: Arc<DatabaseConnection>| async move {
-            DeleteWorkspace::execute(&db, IdInput::new(&id))
+        .and_then(|auth: AuthUser, db: Arc<DatabaseConnection>| async move {
+            Logout::execute(&db, auth)
                 .await
-                .map(|_| warp::reply::json(&serde_json::json!({"success": true})))
+                .map(|r| warp::reply::json(&r))
                 .map_err(|e| warp::reject::custom(AppRejection::from(e)))
         })
 }
 
-// ============================================================================
-// Node 路由
-// ============================================================================
-
-fn node_routes(
-    db: Arc<DatabaseConnection>,
-) -> impl Filter<Extract = (impl warp::Reply,), Error = warp::Rejection> + Clone {
-    get_nodes_by_workspace(db.clone())
-        .or(get_root_nodes(db.clone()))
-        .or(get_next_sort_order(db.clone()))
-        .or(get_node(db.clone()))
-        .or(get_child_nodes(db.clone()))
-        .or(create_node(db.clone()))
-        .or(update_node(db.clone()))
-        .or(move_node(db.clone()))
-        .or(delete_node(db))
-}
-
-fn get_nodes_by_workspace(
-    db: Arc<DatabaseConnection>,
-) -> impl Filter<Extract = (impl warp::Reply,), Error = warp::Rejection> + Clone {
-    warp::path!("api" / "workspaces" / String / "nodes")
-        .and(warp::get())
-        .and(with_db(db))
-        .and_then(
-            |workspace_id: String, db: Arc<DatabaseConnection>| async move {
-                GetNodesByWorkspace::execute(&db, WorkspaceIdInput::new(&workspace_id))
-                    .await
-                    .map(|r| warp::reply::json(&r))
-                    .map_err(|e| warp::reject::custom(AppRejection::from(e)))
-            },
-        )
-}
-
-fn get_root_nodes(
+fn get_current_user(
     db: Arc<DatabaseConnection>,
+    config: Arc<AppConfig>,
 ) -> impl Filter<Extract = (impl warp::Reply,), Error = warp::Rejection> + Clone {
-    warp::path!("api" / "workspaces" / String / "nodes" / "root")
+    warp::path!("api" / "auth" / "me")
         .and(warp::get())
+        .and(with_auth_user(db.clone(), config))
         .and(with_db(db))
-        .and_then(
-            |workspace_id: String, db: Arc<DatabaseConnection>| async move {
-                GetRootNodes::execute(&db, WorkspaceIdInput::new(&workspace_id))
-                    .await
-                    .map(|r| warp::reply::json(&r))
-                    .map_err(|e| warp::reject::custom(AppRejection::from(e)))
-            },
-        )
+        .and_then(|auth: AuthUser, db: Arc<DatabaseConnection>| async move {
+            GetCurrentUser::execute(&db, auth)
+                .await
+                .map(|r| warp::reply::json(&r))
+                .map_err(|e| warp::reject::custom(AppRejection::from(e)))
+        })
 }
 
-fn get_next_sort_order(
-    db: Arc<DatabaseConnection>,
-) -> impl Filter<Extract = (impl warp::Reply,), Error = warp::Rejection> + Clone {
-    warp::path!("api" / "workspaces" / String / "nodes" / "next-sort-order")
-        .and(warp::get())
-        .and(warp::query::<std::collections::HashMap<String, String>>())
-        .and(with_db(db))
-        .and_then(
-            |workspace_id: String, query: std::collections::HashMap<String, String>, db: Arc<DatabaseConnection>| async move {
-                // 解析 parentId 查询参数，"null" 字符串转为 None
-                let parent_id = query.get("parentId").and_then(|v| {
-                    if v == "null" || v.is_empty() {
-                        None
-                    } else {
-                        Some(v.clone())
-                    }
-                });
-                
-                GetNextSortOrder::execute(&db, NextSortOrderInput::new(&workspace_id, parent_id))
-                    .await
-                    .map(|r| warp::reply::json(&r))
-                    .map_err(|e| warp::reject::custom(AppRejection::from(e)))
-            },
-        )
-}
+// ============================================================================
+// API Token 路由
+// ============================================================================
 
-fn get_node(
+fn api_token_routes(
     db: Arc<DatabaseConnection>,
+    config: Arc<AppConfig>,
 ) -> impl Filter<Extract = (impl warp::Reply,), Error = warp::Rejection> + Clone {
-    warp::path!("api" / "nodes" / String)
-        .and(warp::get())
-        .and(with_db(db))
-        .and_then(|id: String, db: Arc<DatabaseConnection>| async move {
-            GetNode::execute(&db, IdInput::new(&id))
-                .await
-                .map(|r| warp::reply::json(&r))
-                .map_err(|e| warp::reject::custom(AppRejection::from(e)))
-        })
+    get_api_tokens(db.clone(), config.clone())
+        .or(create_api_token(db.clone(), config.clone()))
+        .or(revoke_api_token(db, config))
 }
 
-fn get_child_nodes(
+fn get_api_tokens(
     db: Arc<DatabaseConnection>,
+    config: Arc<AppConfig>,
 ) -> impl Filter<Extract = (impl warp::Reply,), Error = warp::Rejection> + Clone {
-    warp::path!("api" / "nodes" / String / "children")
+    warp::path!("api" / "tokens")
         .and(warp::get())
+        .and(with_auth_user(db.clone(), config))
         .and(with_db(db))
-        .and_then(|parent_id: String, db: Arc<DatabaseConnection>| async move {
-            GetChildNodes::execute(&db, ParentIdInput::new(&parent_id))
+        .and_then(|auth: AuthUser, db: Arc<DatabaseConnection>| async move {
+            run_endpoint::<GetApiTokens>(&db, Some(auth.clone()), auth)
                 .await
                 .map(|r| warp::reply::json(&r))
-                .map_err(|e| warp::reject::custom(AppRejection::from(e)))
         })
 }
 
-fn create_node(
+fn create_api_token(
     db: Arc<DatabaseConnection>,
+    config: Arc<AppConfig>,
 ) -> impl Filter<Extract = (impl warp::Reply,), Error = warp::Rejection> + Clone {
-    warp::path!("api" / "nodes")
+    warp::path!("api" / "tokens")
         .and(warp::post())
+        .and(with_auth_user(db.clone(), config))
         .and(warp::body::json())
         .and(with_db(db))
         .and_then(
-            |body: CreateNodeRequest, db: Arc<DatabaseConnection>| async move {
-                CreateNode::execute(&db, body)
+            |auth: AuthUser, body: CreateApiTokenRequest, db: Arc<DatabaseConnection>| async move {
+                let input = AuthWithBodyInput::new(auth.clone(), body);
+                run_endpoint::<CreateApiToken>(&db, Some(auth), input)
                     .await
                     .map(|r| warp::reply::json(&r))
-                    .map_err(|e| warp::reject::custom(AppRejection::from(e)))
             },
         )
 }
 
-fn update_node(
+fn revoke_api_token(
     db: Arc<DatabaseConnection>,
+    config: Arc<AppConfig>,
 ) -> impl Filter<Extract = (impl warp::Reply,), Error = warp::Rejection> + Clone {
-    warp::path!("api" / "nodes" / String)
-        .and(warp::put())
-        .and(warp::body::json())
+    warp::path!("api" / "tokens" / String)
+        .and(warp::delete())
+        .and(with_auth_user(db.clone(), config))
         .and(with_db(db))
         .and_then(
-            |id: String, body: UpdateNodeRequest, db: Arc<DatabaseConnection>| async move {
-                UpdateNode::execute(&db, IdWithBodyInput::new(&id, body))
+            |id: String, auth: AuthUser, db: Arc<DatabaseConnection>| async move {
+                let input = AuthWithBodyInput::new(auth.clone(), IdInput::new(&id));
+                run_endpoint::<RevokeApiToken>(&db, Some(auth), input)
                     .await
-                    .map(|r| warp::reply::json(&r))
-                    .map_err(|e| warp::reject::custom(AppRejection::from(e)))
+                    .map(|_| warp::reply::json(&serde_json::json!({"success": true})))
             },
         )
 }
 
-fn move_node(
+// ============================================================================
+// Collab 路由
+// ============================================================================
+
+/// 协同编辑的 WebSocket，协议见 `collab` 模块（需要节点所在工作区的 editor 权限）
+fn collab_content(
     db: Arc<DatabaseConnection>,
+    config: Arc<AppConfig>,
 ) -> impl Filter<Extract = (impl warp::Reply,), Error = warp::Rejection> + Clone {
-    warp::path!("api" / "nodes" / String / "move")
-        .and(warp::post())
-        .and(warp::body::json())
+    warp::path!("api" / "nodes" / String / "collab")
+        .and(with_auth(db.clone(), config))
+        .and(warp::ws())
         .and(with_db(db))
         .and_then(
-            |id: String, body: MoveNodeRequest, db: Arc<DatabaseConnection>| async move {
-                MoveNode::execute(&db, IdWithBodyInput::new(&id, body))
-                    .await
-                    .map(|r| warp::reply::json(&r))
-                    .map_err(|e| warp::reject::custom(AppRejection::from(e)))
+            |node_id: String,
+             auth: Option<AuthUser>,
+             ws: warp::ws::Ws,
+             db: Arc<DatabaseConnection>| async move {
+                let access = Access::Node(node_id.clone(), WorkspaceRole::Editor);
+                check_access(&db, auth.as_ref(), access).await?;
+                match node_db_fn::find_by_id(db.as_ref(), &node_id).await {
+                    Ok(Some(_)) => {
+                        Ok(ws.on_upgrade(move |socket| collab::serve(db, node_id, socket)))
+                    }
+                    Ok(None) => Err(warp::reject::custom(AppRejection::from(
+                        AppError::not_found(format!("Node {}", node_id)),
+                    ))),
+                    Err(e) => Err(warp::reject::custom(AppRejection::from(e))),
+                }
             },
         )
 }
 
-fn delete_node(
-    db: Arc<DatabaseConnection>,
-) -> impl Filter<Extract = (impl warp::Reply,), Error = warp::Rejection> + Clone {
-    warp::path!("api" / "nodes" / String)
-        .and(warp::delete())
-        .and(with_db(db))
-        .and_then(|id: String, db: Arc<DatabaseConnection>| async move {
-            DeleteNode::execute(&db, IdInput::new(&id))
-                .await
-                .map(|_| warp::reply::json(&serde_json::json!({"success": true})))
-                .map_err(|e| warp::reject::custom(AppRejection::from(e)))
-        })
-}
-
 // ============================================================================
-// Content 路由
+// Event 路由
 // ============================================================================
 
-fn content_routes(
-    db: Arc<DatabaseConnection>,
-) -> impl Filter<Extract = (impl warp::Reply,), Error = warp::Rejection> + Clone {
-    get_content(db.clone()).or(save_content(db))
-}
-
-fn get_content(
+/// 以 SSE 推送数据变更事件
+///
+/// 每个事件的 data 是 `ChangeEvent` 的 JSON，`workspaceId` 参数只推送该工作区的事件
+/// （`dataCleared` 和 `resync` 总是推送）。指定工作区时需要该工作区的 viewer 权限；
+/// 启用认证时每个事件推送前都按订阅者当前的成员关系检查，被移除后不再收到该工作区的事件。
+fn subscribe_events(
     db: Arc<DatabaseConnection>,
+    config: Arc<AppConfig>,
 ) -> impl Filter<Extract = (impl warp::Reply,), Error = warp::Rejection> + Clone {
-    warp::path!("api" / "nodes" / String / "content")
+    warp::path!("api" / "events")
         .and(warp::get())
-        .and(with_db(db))
-        .and_then(|node_id: String, db: Arc<DatabaseConnection>| async move {
-            GetContent::execute(&db, NodeIdInput::new(&node_id))
-                .await
-                .map(|r| warp::reply::json(&r))
-                .map_err(|e| warp::reject::custom(AppRejection::from(e)))
-        })
-}
-
-fn save_content(
-    db: Arc<DatabaseConnection>,
-) -> impl Filter<Extract = (impl warp::Reply,), Error = warp::Rejection> + Clone {
-    warp::path!("api" / "contents")
-        .and(warp::post())
-        .and(warp::body::json())
+        .and(with_auth(db.clone(), config))
+        .and(warp::query::<ChangeEventQuery>())
         .and(with_db(db))
         .and_then(
-            |body: SaveContentRequest, db: Arc<DatabaseConnection>| async move {
-                SaveContent::execute(&db, body)
-                    .await
-                    .map(|r| warp::reply::json(&r))
-                    .map_err(|e| warp::reject::custom(AppRejection::from(e)))
+            |auth: Option<AuthUser>, query: ChangeEventQuery, db: Arc<DatabaseConnection>| async move {
+                let access = match &query.workspace_id {
+                    Some(id) => Access::Workspace(id.clone(), WorkspaceRole::Viewer),
+                    None => Access::Authenticated,
+                };
+                check_access(&db, auth.as_ref(), access).await?;
+                Ok::<_, warp::Rejection>((auth, query, db))
+            },
+        )
+        .untuple_one()
+        .map(
+            |auth: Option<AuthUser>, query: ChangeEventQuery, db: Arc<DatabaseConnection>| {
+                let stream = futures_util::stream::unfold(
+                    (events::subscribe(), query.workspace_id, auth, db),
+                    |(mut subscription, workspace_id, auth, db)| async move {
+                        loop {
+                            let event = subscription.recv().await?;
+                            if event.matches_workspace(workspace_id.as_deref())
+                                && can_receive(&db, auth.as_ref(), &event).await
+                            {
+                                let sse = warp::sse::Event::default().json_data(&event);
+                                return Some((sse, (subscription, workspace_id, auth, db)));
+                            }
+                        }
+                    },
+                );
+                warp::sse::reply(warp::sse::keep_alive().stream(stream))
             },
         )
 }
 
+/// 订阅者当前能否收到事件（全局事件总是可以；检查失败时不推送）
+async fn can_receive(
+    db: &DatabaseConnection,
+    auth: Option<&AuthUser>,
+    event: &ChangeEvent,
+) -> bool {
+    let Some(workspace_id) = event.workspace_id() else {
+        return true;
+    };
+    let access = Access::Workspace(workspace_id.to_string(), WorkspaceRole::Viewer);
+    match access::is_authorized(db, auth, &access).await {
+        Ok(allowed) => allowed,
+        Err(e) => {
+            tracing::warn!("检查事件订阅权限失败: {}", e);
+            false
+        }
+    }
+}
+
 // ============================================================================
-// Transaction 路由
+// Backup 路由
 // ============================================================================
 
-fn transaction_routes(
+/// 备份是整个数据库的副本，需要管理员（可以使用管理员创建的备份令牌）
+fn backup_routes(
     db: Arc<DatabaseConnection>,
+    config: Arc<AppConfig>,
 ) -> impl Filter<Extract = (impl warp::Reply,), Error = warp::Rejection> + Clone {
-    create_node_with_content(db.clone()).or(delete_node_recursive(db))
+    list_backups(db.clone(), config.clone())
+        .or(create_backup(db.clone(), config.clone()))
+        .or(delete_backup(db, config))
 }
 
-fn create_node_with_content(
+/// 需要备份权限的 Filter
+fn with_backup_access(
     db: Arc<DatabaseConnection>,
-) -> impl Filter<Extract = (impl warp::Reply,), Error = warp::Rejection> + Clone {
-    warp::path!("api" / "nodes" / "with-content")
-        .and(warp::post())
-        .and(warp::body::json())
+    config: Arc<AppConfig>,
+) -> impl Filter<Extract = (), Error = warp::Rejection> + Clone {
+    with_auth(db.clone(), config)
         .and(with_db(db))
         .and_then(
-            |body: CreateNodeWithContentRequest, db: Arc<DatabaseConnection>| async move {
-                CreateNodeWithContent::execute(&db, body)
-                    .await
-                    .map(|r| warp::reply::json(&r))
-                    .map_err(|e| warp::reject::custom(AppRejection::from(e)))
+            |auth: Option<AuthUser>, db: Arc<DatabaseConnection>| async move {
+                check_access(&db, auth.as_ref(), Access::Backup).await
             },
         )
-}
-
-fn delete_node_recursive(
-    db: Arc<DatabaseConnection>,
-) -> impl Filter<Extract = (impl warp::Reply,), Error = warp::Rejection> + Clone {
-    warp::path!("api" / "nodes" / String / "recursive")
-        .and(warp::delete())
-        .and(with_db(db))
-        .and_then(|id: String, db: Arc<DatabaseConnection>| async move {
-            DeleteNodeRecursive::execute(&db, IdInput::new(&id))
-                .await
-                .map(|r| warp::reply::json(&r))
-                .map_err(|e| warp::reject::custom(AppRejection::from(e)))
-        })
-}
-
-// ============================================================================
-// Backup 路由
-// ============================================================================
-
-fn backup_routes(
-    config: Arc<AppConfig>,
-) -> impl Filter<Extract = (impl warp::Reply,), Error = warp::Rejection> + Clone {
-    list_backups(config.clone())
-        .or(create_backup(config.clone()))
-        .or(delete_backup(config))
+        .untuple_one()
 }
 
 fn list_backups(
+    db: Arc<DatabaseConnection>,
     config: Arc<AppConfig>,
 ) -> impl Filter<Extract = (impl warp::Reply,), Error = warp::Rejection> + Clone {
     warp::path!("api" / "backups")
         .and(warp::get())
+        .and(with_backup_access(db, config.clone()))
         .and(with_config(config))
         .and_then(|config: Arc<AppConfig>| async move {
             crate::list_backups(&config)
@@ -481,10 +517,12 @@ fn list_backups(
 }
 
 fn create_backup(
+    db: Arc<DatabaseConnection>,
     config: Arc<AppConfig>,
 ) -> impl Filter<Extract = (impl warp::Reply,), Error = warp::Rejection> + Clone {
     warp::path!("api" / "backups")
         .and(warp::post())
+        .and(with_backup_access(db, config.clone()))
         .and(with_config(config))
         .and_then(|config: Arc<AppConfig>| async move {
             crate::create_backup(&config)
@@ -494,10 +532,12 @@ fn create_backup(
 }
 
 fn delete_backup(
+    db: Arc<DatabaseConnection>,
     config: Arc<AppConfig>,
 ) -> impl Filter<Extract = (impl warp::Reply,), Error = warp::Rejection> + Clone {
     warp::path!("api" / "backups" / String)
         .and(warp::delete())
+        .and(with_backup_access(db, config.clone()))
         .and(with_config(config))
         .and_then(|filename: String, config: Arc<AppConfig>| async move {
             let backup_path = config.backup_dir().join(&filename);
@@ -508,56 +548,498 @@ fn delete_backup(
 }
 
 // ============================================================================
-// Clear Data 路由
+// 测试
 // ============================================================================
 
-fn clear_data_routes(
-    db: Arc<DatabaseConnection>,
-) -> impl Filter<Extract = (impl warp::Reply,), Error = warp::Rejection> + Clone {
-    clear_all_data(db.clone()).or(clear_data_keep_users(db))
-}
-
-fn clear_all_data(
-    db: Arc<DatabaseConnection>,
-) -> impl Filter<Extract = (impl warp::Reply,), Error = warp::Rejection> + Clone {
-    warp::path!("api" / "data" / "clear")
-        .and(warp::delete())
-        .and(warp::query::<std::collections::HashMap<String, String>>())
-        .and(with_db(db))
-        .and_then(
-            |query: std::collections::HashMap<String, String>, db: Arc<DatabaseConnection>| async move {
-                // 检查是否有 keepUsers 参数
-                let keep_users = query.get("keepUsers")
-                    .map(|v| v == "true")
-                    .unwrap_or(false);
-
-                if keep_users {
-                    ClearDataKeepUsers::execute(&db, ())
-                        .await
-                        .map(|r| warp::reply::json(&r))
-                        .map_err(|e| warp::reject::custom(AppRejection::from(e)))
-                } else {
-                    ClearAllData::execute(&db, ())
-                        .await
-                        .map(|r| warp::reply::json(&r))
-                        .map_err(|e| warp::reject::custom(AppRejection::from(e)))
-                }
-            },
-        )
-}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::test_utils::setup_test_db;
+    use warp::http::StatusCode;
 
-fn clear_data_keep_users(
-    db: Arc<DatabaseConnection>,
-) -> impl Filter<Extract = (impl warp::Reply,), Error = warp::Rejection> + Clone {
-    // 这个路由实际上由上面的 clear_all_data 处理，通过查询参数区分
-    // 这里保留作为备用，但不会被匹配到
-    warp::path!("api" / "data" / "clear" / "keep-users")
-        .and(warp::delete())
-        .and(with_db(db))
-        .and_then(|db: Arc<DatabaseConnection>| async move {
-            ClearDataKeepUsers::execute(&db, ())
+    async fn setup_routes(
+        config: AppConfig,
+    ) -> impl Filter<Extract = (impl warp::Reply,), Error = warp::Rejection> + Clone {
+        let db = setup_test_db().await;
+        for username in ["alice", "bob"] {
+            auth_db_fn::ensure_user(&db, username, "correct horse")
                 .await
-                .map(|r| warp::reply::json(&r))
-                .map_err(|e| warp::reject::custom(AppRejection::from(e)))
+                .unwrap();
+        }
+        build_routes(Arc::new(db), Arc::new(config))
+    }
+
+    fn json(body: &[u8]) -> serde_json::Value {
+        serde_json::from_slice(body).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_api_requires_token() {
+        let routes = setup_routes(AppConfig {
+            auth_enabled: true,
+            ..Default::default()
+        })
+        .await;
+        let get = |path: &str, token: Option<&str>| {
+            let request = warp::test::request().method("GET").path(path);
+            match token {
+                Some(token) => request.header("authorization", format!("Bearer {}", token)),
+                None => request,
+            }
+        };
+
+        let health = get("/health", None).reply(&routes).await;
+        assert_eq!(health.status(), StatusCode::OK);
+        let document = get("/api/openapi.json", None).reply(&routes).await;
+        assert_eq!(document.status(), StatusCode::OK);
+        assert_eq!(json(document.body())["openapi"], "3.0.3");
+        assert_eq!(
+            get("/api", None).reply(&routes).await.status(),
+            StatusCode::UNAUTHORIZED
+        );
+        for token in [None, Some("invalid")] {
+            let response = get("/api/workspaces", token).reply(&routes).await;
+            assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+        }
+        // 未知路径不需要令牌
+        for path in ["/api/missing", "/missing"] {
+            let response = get(path, None).reply(&routes).await;
+            assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        }
+
+        let wrong = warp::test::request()
+            .method("POST")
+            .path("/api/auth/login")
+            .json(&serde_json::json!({"username": "alice", "password": "wrong horse"}))
+            .reply(&routes)
+            .await;
+        assert_eq!(wrong.status(), StatusCode::UNAUTHORIZED);
+
+        let login = warp::test::request()
+            .method("POST")
+            .path("/api/auth/login")
+            .json(&serde_json::json!({"username": "alice", "password": "correct horse"}))
+            .reply(&routes)
+            .await;
+        assert_eq!(login.status(), StatusCode::OK);
+        let tokens = json(login.body());
+        let access = tokens["accessToken"].as_str().unwrap().to_string();
+
+        let response = get("/api/workspaces", Some(&access)).reply(&routes).await;
+        assert_eq!(response.status(), StatusCode::OK);
+        let me = get("/api/auth/me", Some(&access)).reply(&routes).await;
+        assert_eq!(json(me.body())["username"], "alice");
+        let info = json(get("/api", Some(&access)).reply(&routes).await.body());
+        assert!(info["endpoints"]
+            .as_array()
+            .unwrap()
+            .contains(&serde_json::json!(
+                "GET /api/nodes/:node_id/content/version"
+            )));
+        // WebSocket 等不能设置请求头的客户端用查询参数携带令牌
+        let path = format!("/api/workspaces?access_token={}", access);
+        assert_eq!(
+            get(&path, None).reply(&routes).await.status(),
+            StatusCode::OK
+        );
+
+        let refresh = warp::test::request()
+            .method("POST")
+            .path("/api/auth/refresh")
+            .json(&serde_json::json!({"refreshToken": tokens["refreshToken"]}))
+            .reply(&routes)
+            .await;
+        assert_eq!(refresh.status(), StatusCode::OK);
+        let refreshed = json(refresh.body())["accessToken"]
+            .as_str()
+            .unwrap()
+            .to_string();
+        let stale = get("/api/workspaces", Some(&access)).reply(&routes).await;
+        assert_eq!(stale.status(), StatusCode::UNAUTHORIZED);
+
+        let logout = warp::test::request()
+            .method("POST")
+            .path("/api/auth/logout")
+            .header("authorization", format!("Bearer {}", refreshed))
+            .reply(&routes)
+            .await;
+        assert_eq!(logout.status(), StatusCode::OK);
+        let response = get("/api/workspaces", Some(&refreshed))
+            .reply(&routes)
+            .await;
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    }
+
+    async fn login(
+        routes: &(impl Filter<Extract = (impl warp::Reply,), Error = warp::Rejection> + 'static),
+        username: &str,
+    ) -> String {
+        let login = warp::test::request()
+            .method("POST")
+            .path("/api/auth/login")
+            .json(&serde_json::json!({"username": username, "password": "correct horse"}))
+            .reply(routes)
+            .await;
+        json(login.body())["accessToken"]
+            .as_str()
+            .unwrap()
+            .to_string()
+    }
+
+    #[tokio::test]
+    async fn test_workspace_roles() {
+        let routes = setup_routes(AppConfig {
+            auth_enabled: true,
+            ..Default::default()
+        })
+        .await;
+        let alice = login(&routes, "alice").await;
+        let bob = login(&routes, "bob").await;
+        let request = |method: &str, path: &str, token: &str| {
+            warp::test::request()
+                .method(method)
+                .path(path)
+                .header("authorization", format!("Bearer {}", token))
+        };
+
+        // 没有工作区时修改用户资料也需要管理员
+        let create_user = request("POST", "/api/users", &bob)
+            .json(&serde_json::json!({"username": "mallory"}))
+            .reply(&routes)
+            .await;
+        assert_eq!(create_user.status(), StatusCode::FORBIDDEN);
+
+        // 创建者成为 owner，其他用户看不到该工作区
+        let created = request("POST", "/api/workspaces", &alice)
+            .json(&serde_json::json!({"title": "共享"}))
+            .reply(&routes)
+            .await;
+        assert_eq!(created.status(), StatusCode::OK);
+        let workspace_id = json(created.body())["id"].as_str().unwrap().to_string();
+        let workspace_path = format!("/api/workspaces/{}", workspace_id);
+        let members_path = format!("{}/members", workspace_path);
+        let listed = request("GET", "/api/workspaces", &bob).reply(&routes).await;
+        assert_eq!(json(listed.body()), serde_json::json!([]));
+        let denied = request("GET", &workspace_path, &bob).reply(&routes).await;
+        assert_eq!(denied.status(), StatusCode::FORBIDDEN);
+        assert_eq!(json(denied.body())["error"]["code"], "FORBIDDEN");
+
+        // viewer 只能读
+        let added = request("PUT", &members_path, &alice)
+            .json(&serde_json::json!({"username": "bob", "role": "viewer"}))
+            .reply(&routes)
+            .await;
+        assert_eq!(added.status(), StatusCode::OK);
+        let listed = request("GET", "/api/workspaces", &bob).reply(&routes).await;
+        assert_eq!(json(listed.body()).as_array().unwrap().len(), 1);
+        let nodes = request("GET", &format!("{}/nodes", workspace_path), &bob)
+            .reply(&routes)
+            .await;
+        assert_eq!(nodes.status(), StatusCode::OK);
+        let create_node = request("POST", "/api/nodes", &bob)
+            .json(&serde_json::json!({"workspaceId": workspace_id, "title": "节点"}))
+            .reply(&routes)
+            .await;
+        assert_eq!(create_node.status(), StatusCode::FORBIDDEN);
+        let promote = request("PUT", &members_path, &bob)
+            .json(&serde_json::json!({"username": "bob", "role": "owner"}))
+            .reply(&routes)
+            .await;
+        assert_eq!(promote.status(), StatusCode::FORBIDDEN);
+        let backups = request("GET", "/api/backups", &bob).reply(&routes).await;
+        assert_eq!(backups.status(), StatusCode::FORBIDDEN);
+
+        let members = request("GET", &members_path, &bob).reply(&routes).await;
+        let members = json(members.body());
+        assert_eq!(members[0]["role"], "owner");
+        assert_eq!(members[1]["role"], "viewer");
+        let bob_id = members[1]["userId"].as_str().unwrap().to_string();
+        let removed = request("DELETE", &format!("{}/{}", members_path, bob_id), &alice)
+            .reply(&routes)
+            .await;
+        assert_eq!(removed.status(), StatusCode::OK);
+        let denied = request("GET", &members_path, &bob).reply(&routes).await;
+        assert_eq!(denied.status(), StatusCode::FORBIDDEN);
+    }
+
+    #[tokio::test]
+    async fn test_api_tokens() {
+        let routes = setup_routes(AppConfig {
+            auth_enabled: true,
+            ..Default::default()
+        })
+        .await;
+        let session = login(&routes, "alice").await;
+        let request = |method: &str, path: &str, token: &str| {
+            warp::test::request()
+                .method(method)
+                .path(path)
+                .header("authorization", format!("Bearer {}", token))
+        };
+        let create_workspace = |title: &str| {
+            request("POST", "/api/workspaces", &session)
+                .json(&serde_json::json!({"title": title}))
+                .reply(&routes)
+        };
+        let workspace = json(create_workspace("脚本").await.body());
+        let other = json(create_workspace("其他").await.body());
+
+        let created = request("POST", "/api/tokens", &session)
+            .json(&serde_json::json!({
+                "name": "导出",
+                "scope": "readonly",
+                "workspaceId": workspace["id"],
+            }))
+            .reply(&routes)
+            .await;
+        assert_eq!(created.status(), StatusCode::OK);
+        let created = json(created.body());
+        let token = created["token"].as_str().unwrap().to_string();
+        assert!(token.starts_with("grain_pat_"));
+
+        // 只读、限定工作区
+        let listed = request("GET", "/api/workspaces", &token)
+            .reply(&routes)
+            .await;
+        assert_eq!(json(listed.body()).as_array().unwrap().len(), 1);
+        let path = format!(
+            "/api/workspaces/{}/nodes",
+            workspace["id"].as_str().unwrap()
+        );
+        let nodes = request("GET", &path, &token).reply(&routes).await;
+        assert_eq!(nodes.status(), StatusCode::OK);
+        let path = format!("/api/workspaces/{}/nodes", other["id"].as_str().unwrap());
+        let nodes = request("GET", &path, &token).reply(&routes).await;
+        assert_eq!(nodes.status(), StatusCode::FORBIDDEN);
+        let create_node = request("POST", "/api/nodes", &token)
+            .json(&serde_json::json!({"workspaceId": workspace["id"], "title": "节点"}))
+            .reply(&routes)
+            .await;
+        assert_eq!(create_node.status(), StatusCode::FORBIDDEN);
+        // API 令牌不能管理令牌
+        let tokens = request("GET", "/api/tokens", &token).reply(&routes).await;
+        assert_eq!(tokens.status(), StatusCode::FORBIDDEN);
+
+        let tokens = request("GET", "/api/tokens", &session).reply(&routes).await;
+        let tokens = json(tokens.body());
+        assert!(tokens[0]["lastUsedAt"].is_i64());
+        assert!(tokens[0].get("token").is_none());
+
+        let path = format!("/api/tokens/{}", created["id"].as_str().unwrap());
+        let revoked = request("DELETE", &path, &session).reply(&routes).await;
+        assert_eq!(revoked.status(), StatusCode::OK);
+        let listed = request("GET", "/api/workspaces", &token)
+            .reply(&routes)
+            .await;
+        assert_eq!(listed.status(), StatusCode::UNAUTHORIZED);
+    }
+
+    #[tokio::test]
+    async fn test_event_subscription_follows_membership() {
+        let db = setup_test_db().await;
+        let bob = auth_db_fn::ensure_user(&db, "bob", "correct horse")
+            .await
+            .unwrap();
+        crate::db::workspace_db_fn::create(&db, "ws".to_string(), "ws".to_string(), None)
+            .await
+            .unwrap();
+        let bob = AuthUser {
+            user_id: bob.id,
+            session_id: String::new(),
+            token: None,
+        };
+        let saved = ChangeEvent::ContentSaved {
+            workspace_id: "ws".to_string(),
+            node_id: "n1".to_string(),
+            version: 1,
+        };
+
+        assert!(!can_receive(&db, Some(&bob), &saved).await);
+        assert!(can_receive(&db, Some(&bob), &ChangeEvent::DataCleared).await);
+        assert!(can_receive(&db, None, &saved).await);
+
+        // 每个事件都按当前的成员关系检查
+        crate::db::workspace_member_db_fn::set_role(&db, "ws", &bob.user_id, WorkspaceRole::Viewer)
+            .await
+            .unwrap();
+        assert!(can_receive(&db, Some(&bob), &saved).await);
+        crate::db::workspace_member_db_fn::remove(&db, "ws", &bob.user_id)
+            .await
+            .unwrap();
+        assert!(!can_receive(&db, Some(&bob), &saved).await);
+    }
+
+    #[tokio::test]
+    async fn test_auth_disabled() {
+        let routes = setup_routes(AppConfig {
+            auth_enabled: false,
+            ..Default::default()
+        })
+        .await;
+        let response = warp::test::request()
+            .path("/api/workspaces")
+            .reply(&routes)
+            .await;
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn test_registry_routes() {
+        let routes = setup_routes(AppConfig {
+            auth_enabled: false,
+            ..Default::default()
         })
+        .await;
+
+        // 每个注册表路由都能匹配到（参数无效时是业务错误，不是 404/405）
+        for route in openapi::endpoint_docs() {
+            let path = route
+                .path
+                .split('/')
+                .map(|s| if s.starts_with(':') { "missing" } else { s })
+                .collect::<Vec<_>>()
+                .join("/");
+            let response = warp::test::request()
+                .method(route.method.as_str())
+                .path(&path)
+                .reply(&routes)
+                .await;
+            assert_ne!(
+                response.status(),
+                StatusCode::METHOD_NOT_ALLOWED,
+                "{}",
+                route.display()
+            );
+            if response.status() == StatusCode::NOT_FOUND {
+                assert_ne!(
+                    json(response.body())["error"]["message"],
+                    "路由未找到",
+                    "{}",
+                    route.display()
+                );
+            }
+        }
+
+        // 字面路径先于参数路径匹配
+        let batch = warp::test::request()
+            .method("DELETE")
+            .path("/api/nodes/batch")
+            .json(&serde_json::json!({"nodeIds": []}))
+            .reply(&routes)
+            .await;
+        assert_eq!(batch.status(), StatusCode::OK);
+        assert_eq!(json(batch.body()), serde_json::json!({"success": true}));
+
+        let created = warp::test::request()
+            .method("POST")
+            .path("/api/workspaces")
+            .json(&serde_json::json!({"title": "注册表"}))
+            .reply(&routes)
+            .await;
+        let workspace_id = json(created.body())["id"].as_str().unwrap().to_string();
+        let tree = warp::test::request()
+            .path(&format!(
+                "/api/workspaces/{}/nodes/tree?maxDepth=1",
+                workspace_id
+            ))
+            .reply(&routes)
+            .await;
+        assert_eq!(tree.status(), StatusCode::OK);
+        let invalid = warp::test::request()
+            .path(&format!(
+                "/api/workspaces/{}/nodes?type=unknown",
+                workspace_id
+            ))
+            .reply(&routes)
+            .await;
+        assert_eq!(invalid.status(), StatusCode::BAD_REQUEST);
+
+        // 前端使用的查询参数名和方法
+        let node = warp::test::request()
+            .method("POST")
+            .path("/api/nodes")
+            .json(&serde_json::json!({"workspaceId": workspace_id, "title": "节点"}))
+            .reply(&routes)
+            .await;
+        let node_id = json(node.body())["id"].as_str().unwrap().to_string();
+        let moved = warp::test::request()
+            .method("PUT")
+            .path(&format!("/api/nodes/{}/move", node_id))
+            .json(&serde_json::json!({"newParentId": null, "newSortOrder": 2}))
+            .reply(&routes)
+            .await;
+        assert_eq!(json(moved.body())["sortOrder"], 2);
+        for (query, expected) in [("parentId=null", 1), ("type=folder", 0)] {
+            let nodes = warp::test::request()
+                .path(&format!("/api/workspaces/{}/nodes?{}", workspace_id, query))
+                .reply(&routes)
+                .await;
+            assert_eq!(json(nodes.body()).as_array().unwrap().len(), expected);
+        }
+        let tags = warp::test::request()
+            .path(&format!("/api/workspaces/{}/tags/search?q=x", workspace_id))
+            .reply(&routes)
+            .await;
+        assert_eq!(tags.status(), StatusCode::OK);
+
+        // 清除数据只有一个路由，保留用户由查询参数指定
+        let removed = warp::test::request()
+            .method("DELETE")
+            .path("/api/data/clear/keep-users")
+            .reply(&routes)
+            .await;
+        assert_eq!(removed.status(), StatusCode::NOT_FOUND);
+        let user = warp::test::request()
+            .method("POST")
+            .path("/api/users")
+            .json(&serde_json::json!({"username": "carol"}))
+            .reply(&routes)
+            .await;
+        assert_eq!(user.status(), StatusCode::OK);
+        let cleared = warp::test::request()
+            .method("DELETE")
+            .path("/api/data/clear?keepUsers=true")
+            .reply(&routes)
+            .await;
+        assert_eq!(cleared.status(), StatusCode::OK);
+        let users = warp::test::request()
+            .path("/api/users")
+            .reply(&routes)
+            .await;
+        assert_eq!(json(users.body())[0]["username"], "carol");
+        let workspaces = warp::test::request()
+            .path("/api/workspaces")
+            .reply(&routes)
+            .await;
+        assert_eq!(json(workspaces.body()), serde_json::json!([]));
+    }
+
+    #[tokio::test]
+    async fn test_cors_allowlist() {
+        let routes = setup_routes(AppConfig {
+            cors_origins: vec![
+                "https://grain.example.com".to_string(),
+                "not an origin".to_string(),
+            ],
+            ..Default::default()
+        })
+        .await;
+        let preflight = |origin: &str| {
+            warp::test::request()
+                .method("OPTIONS")
+                .path("/api/workspaces")
+                .header("origin", origin)
+                .header("access-control-request-method", "GET")
+                .header("access-control-request-headers", "authorization")
+        };
+
+        let allowed = preflight("https://grain.example.com").reply(&routes).await;
+        assert_eq!(allowed.status(), StatusCode::OK);
+        assert_eq!(
+            allowed.headers()["access-control-allow-origin"],
+            "https://grain.example.com"
+        );
+        let denied = preflight("https://evil.example.com").reply(&routes).await;
+        assert_eq!(denied.status(), StatusCode::FORBIDDEN);
+    }
 }
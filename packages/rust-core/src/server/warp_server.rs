@@ -6,6 +6,7 @@ use std::net::SocketAddr;
 use std::sync::Arc;
 
 use crate::db::connection::DbConnection;
+use crate::db::{auth_db_fn, workspace_member_db_fn};
 use crate::AppConfig;
 
 use super::routes::build_routes;
@@ -50,6 +51,8 @@ pub async fn run_server() {
         }
     };
 
+    init_auth(&db, &config).await;
+
     let config = Arc::new(config);
 
     // 构建路由树
@@ -72,6 +75,50 @@ pub async fn run_server() {
     warp::serve(routes).run(addr).await;
 }
 
+/// 初始化认证
+///
+/// 设置了 `GRAIN_ADMIN_USERNAME` 和 `GRAIN_ADMIN_PASSWORD` 时创建该用户（已存在则重置密码），
+/// 并设为管理员。
+async fn init_auth(db: &sea_orm::DatabaseConnection, config: &AppConfig) {
+    if !config.auth_enabled {
+        tracing::warn!("⚠️  未启用认证，所有 API 请求无需登录（GRAIN_AUTH_ENABLED=true 启用）");
+        return;
+    }
+
+    if let (Ok(username), Ok(password)) = (
+        std::env::var("GRAIN_ADMIN_USERNAME"),
+        std::env::var("GRAIN_ADMIN_PASSWORD"),
+    ) {
+        let user = match auth_db_fn::ensure_user(db, &username, &password).await {
+            Ok(user) => user,
+            Err(e) => {
+                tracing::error!("❌ 设置管理员失败: {}", e);
+                std::process::exit(1);
+            }
+        };
+        if let Err(e) = auth_db_fn::set_admin(db, &user.id, true).await {
+            tracing::error!("❌ 设置管理员失败: {}", e);
+            std::process::exit(1);
+        }
+        tracing::info!("🔑 已设置管理员 {} 的密码", username);
+
+        // 启用认证前创建的工作区没有成员，交给管理员
+        match workspace_member_db_fn::claim_unowned(db, &user.id).await {
+            Ok(0) => {}
+            Ok(count) => tracing::info!("👥 {} 个没有成员的工作区归 {} 所有", count, username),
+            Err(e) => tracing::error!("设置工作区所有者失败: {}", e),
+        }
+    }
+
+    match auth_db_fn::count_credentials(db).await {
+        Ok(0) => tracing::warn!(
+            "⚠️  没有可登录的用户，请设置 GRAIN_ADMIN_USERNAME 和 GRAIN_ADMIN_PASSWORD"
+        ),
+        Ok(_) => {}
+        Err(e) => tracing::error!("查询登录凭据失败: {}", e),
+    }
+}
+
 /// 初始化日志系统
 fn init_logging() {
     use tracing_subscriber::fmt::format::FmtSpan;
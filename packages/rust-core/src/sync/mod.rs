@@ -0,0 +1,805 @@
+//! 推送/拉取同步客户端
+//!
+//! 桌面端与 `grain-api` 服务器同步的流程：
+//!
+//! 1. 为变更日志出现之前的数据补记基线记录
+//! 2. 从保存的游标处分页拉取服务器变更并应用，每页提交后保存游标
+//! 3. 把游标之后的本地变更分批推送，按策略处理冲突
+//! 4. 保存推送游标
+//!
+//! 每个实体记录两个序号（见 `sync_versions` 表）：本地副本对应的服务器版本，
+//! 以及同步时的本地变更序号。本地变更日志中该实体有更新的记录，说明本地修改尚未推送。
+//! 中断后再次同步从保存的游标处继续，已同步的实体按版本跳过。
+
+use std::collections::HashMap;
+use std::future::Future;
+
+use sea_orm::{DatabaseConnection, TransactionTrait};
+use tracing::{info, warn};
+
+use crate::db::{change_log_db_fn, sync_db_fn};
+use crate::events;
+use crate::r#fn::change_log::compact_changes;
+use crate::r#fn::sync::{is_syncable, normalize_server_url, order_changes, pull_cursor};
+use crate::types::change_log::ChangeEntity;
+use crate::types::sync::{
+    SyncChange, SyncConflict, SyncConflictStrategy, SyncPullQuery, SyncPullResponse,
+    SyncPushRequest, SyncPushResponse, SyncPushResult, SyncPushStatus, SyncReport,
+};
+use crate::{AppError, AppResult};
+
+/// 默认每批拉取/推送的条数
+const DEFAULT_BATCH_SIZE: u64 = 200;
+
+/// 每批最多拉取/推送的条数
+const MAX_BATCH_SIZE: u64 = 1000;
+
+/// 推送时每页读取的本地变更记录数
+const PUSH_PAGE_SIZE: u64 = 500;
+
+// ============================================================================
+// 传输
+// ============================================================================
+
+/// 与同步服务器通信的方式
+pub trait SyncTransport: Send + Sync {
+    /// 拉取服务器变更
+    fn pull(
+        &self,
+        query: SyncPullQuery,
+    ) -> impl Future<Output = AppResult<SyncPullResponse>> + Send;
+
+    /// 推送本地变更
+    fn push(
+        &self,
+        request: SyncPushRequest,
+    ) -> impl Future<Output = AppResult<SyncPushResponse>> + Send;
+}
+
+/// 通过 HTTP 访问 `grain-api` 的同步端点
+pub struct HttpSyncTransport {
+    client: reqwest::Client,
+    server_url: String,
+    token: Option<String>,
+}
+
+impl HttpSyncTransport {
+    /// 创建 HTTP 传输（`token` 以 Bearer 方式发送）
+    pub fn new(server_url: &str, token: Option<String>) -> AppResult<Self> {
+        Ok(Self {
+            client: reqwest::Client::new(),
+            server_url: normalize_server_url(server_url)?,
+            token: token.filter(|t| !t.is_empty()),
+        })
+    }
+
+    fn request(&self, method: reqwest::Method, path: &str) -> reqwest::RequestBuilder {
+        let builder = self
+            .client
+            .request(method, format!("{}{}", self.server_url, path));
+        match &self.token {
+            Some(token) => builder.bearer_auth(token),
+            None => builder,
+        }
+    }
+
+    /// 解析响应，非 2xx 时转换为错误
+    async fn parse<T: serde::de::DeserializeOwned>(response: reqwest::Response) -> AppResult<T> {
+        let status = response.status();
+        if status.is_success() {
+            return Ok(response.json().await?);
+        }
+
+        let body: serde_json::Value = response.json().await.unwrap_or_default();
+        let message = body["error"]["message"]
+            .as_str()
+            .unwrap_or_else(|| status.canonical_reason().unwrap_or("未知错误"))
+            .to_string();
+        Err(match status.as_u16() {
+            401 => AppError::Unauthorized(message),
+            403 => AppError::Forbidden(message),
+            _ => AppError::InternalError(format!("同步服务器返回错误 ({}): {}", status, message)),
+        })
+    }
+}
+
+impl SyncTransport for HttpSyncTransport {
+    async fn pull(&self, query: SyncPullQuery) -> AppResult<SyncPullResponse> {
+        let response = self
+            .request(reqwest::Method::GET, "/api/sync/pull")
+            .query(&query)
+            .send()
+            .await?;
+        Self::parse(response).await
+    }
+
+    async fn push(&self, request: SyncPushRequest) -> AppResult<SyncPushResponse> {
+        let response = self
+            .request(reqwest::Method::POST, "/api/sync/push")
+            .json(&request)
+            .send()
+            .await?;
+        Self::parse(response).await
+    }
+}
+
+// ============================================================================
+// 同步流程
+// ============================================================================
+
+/// 与服务器完成一次同步（先拉取再推送）
+pub async fn run<T: SyncTransport>(
+    db: &DatabaseConnection,
+    transport: &T,
+    server_url: &str,
+    strategy: SyncConflictStrategy,
+    batch_size: Option<u64>,
+) -> AppResult<SyncReport> {
+    let remote_id = normalize_server_url(server_url)?;
+    let batch_size = match batch_size {
+        Some(0) => return Err(AppError::validation("batchSize 必须大于 0")),
+        Some(size) => size.min(MAX_BATCH_SIZE),
+        None => DEFAULT_BATCH_SIZE,
+    };
+
+    sync_db_fn::record_baseline(db).await?;
+    let remote = sync_db_fn::find_remote(db, &remote_id).await?;
+    let (pull_seq, push_seq) = remote.map_or((0, 0), |r| (r.pull_seq, r.push_seq));
+
+    let mut report = SyncReport::default();
+    let pull_seq = pull(
+        db,
+        transport,
+        &remote_id,
+        pull_seq,
+        push_seq,
+        strategy,
+        batch_size,
+        &mut report,
+    )
+    .await?;
+    let push_seq = push(
+        db,
+        transport,
+        &remote_id,
+        push_seq,
+        strategy,
+        batch_size,
+        &mut report,
+    )
+    .await?;
+
+    let synced_at = chrono::Utc::now().timestamp_millis();
+    sync_db_fn::save_remote(db, &remote_id, pull_seq, push_seq, Some(synced_at)).await?;
+
+    info!(
+        "同步完成 {}: 拉取 {} 条, 推送 {} 条, 冲突 {} 条, 拒绝 {} 条",
+        remote_id,
+        report.pulled,
+        report.pushed,
+        report.conflicts.len(),
+        report.rejected.len()
+    );
+    report.pull_seq = pull_seq;
+    report.push_seq = push_seq;
+    report.synced_at = synced_at;
+    Ok(report)
+}
+
+/// 分页拉取并应用服务器变更，返回新的拉取游标
+#[allow(clippy::too_many_arguments)]
+async fn pull<T: SyncTransport>(
+    db: &DatabaseConnection,
+    transport: &T,
+    remote_id: &str,
+    mut since: i64,
+    push_seq: i64,
+    strategy: SyncConflictStrategy,
+    batch_size: u64,
+    report: &mut SyncReport,
+) -> AppResult<i64> {
+    let mut pending: Vec<SyncChange> = Vec::new();
+
+    loop {
+        let page = transport
+            .pull(SyncPullQuery {
+                since: Some(since),
+                limit: Some(batch_size),
+            })
+            .await?;
+
+        let mut changes = std::mem::take(&mut pending);
+        changes.extend(page.changes);
+        pending = apply_pulled(db, remote_id, changes, strategy, report).await?;
+
+        since = page.next_since;
+        // 每页提交后保存游标，中断后从这里继续
+        let cursor = pull_cursor(since, &pending);
+        sync_db_fn::save_remote(db, remote_id, cursor, push_seq, None).await?;
+
+        if !page.has_more {
+            break;
+        }
+    }
+
+    if !pending.is_empty() {
+        warn!("{} 条服务器变更的依赖不存在，下次同步重试", pending.len());
+    }
+    Ok(pull_cursor(since, &pending))
+}
+
+/// 在一个事务中应用一页服务器变更，返回依赖不存在、需要稍后重试的变更
+async fn apply_pulled(
+    db: &DatabaseConnection,
+    remote_id: &str,
+    changes: Vec<SyncChange>,
+    strategy: SyncConflictStrategy,
+    report: &mut SyncReport,
+) -> AppResult<Vec<SyncChange>> {
+    let txn = db.begin().await?;
+    let mut pending = Vec::new();
+    let mut applied_events = Vec::new();
+
+    for change in order_changes(changes) {
+        let known =
+            sync_db_fn::find_version(&txn, remote_id, change.entity_type, &change.entity_id)
+                .await?;
+        if known.as_ref().is_some_and(|k| k.version >= change.version) {
+            continue;
+        }
+
+        let local_seq =
+            change_log_db_fn::latest_seq(&txn, change.entity_type, &change.entity_id).await?;
+        let local = sync_db_fn::load_entity(&txn, change.entity_type, &change.entity_id).await?;
+        let same = match (&local, &change.data) {
+            (Some(local), Some(remote)) => local.same_state(remote),
+            (None, None) => true,
+            _ => false,
+        };
+        if same {
+            sync_db_fn::save_version(
+                &txn,
+                remote_id,
+                change.entity_type,
+                &change.entity_id,
+                change.version,
+                local_seq,
+            )
+            .await?;
+            continue;
+        }
+
+        // 本地有未推送的修改：留给推送阶段检测冲突
+        let dirty = local_seq > known.as_ref().map_or(0, |k| k.local_seq);
+        if dirty && strategy != SyncConflictStrategy::PreferRemote {
+            continue;
+        }
+
+        if sync_db_fn::missing_dependency(&txn, &change)
+            .await?
+            .is_some()
+        {
+            pending.push(change);
+            continue;
+        }
+
+        let event = sync_db_fn::apply_change(&txn, &change).await?;
+        let applied_seq = match &event {
+            Some(event) => change_log_db_fn::append(&txn, event)
+                .await?
+                .map_or(local_seq, |e| e.seq),
+            None => local_seq,
+        };
+        sync_db_fn::save_version(
+            &txn,
+            remote_id,
+            change.entity_type,
+            &change.entity_id,
+            change.version,
+            applied_seq,
+        )
+        .await?;
+        applied_events.extend(event);
+        report.pulled += 1;
+    }
+
+    txn.commit().await?;
+    for event in applied_events {
+        events::publish(event);
+    }
+    Ok(pending)
+}
+
+/// 分页读取游标之后的本地变更并分批推送，返回新的推送游标
+///
+/// 未解决的冲突和被拒绝的变更不推进游标，下次同步重新推送。
+async fn push<T: SyncTransport>(
+    db: &DatabaseConnection,
+    transport: &T,
+    remote_id: &str,
+    push_seq: i64,
+    strategy: SyncConflictStrategy,
+    batch_size: u64,
+    report: &mut SyncReport,
+) -> AppResult<i64> {
+    let mut head = push_seq;
+    let mut unresolved: Option<i64> = None;
+    let mut mark_unresolved = |seq: i64| {
+        unresolved = Some(unresolved.map_or(seq, |s: i64| s.min(seq)));
+    };
+
+    loop {
+        let entries = change_log_db_fn::find_since(db, head, PUSH_PAGE_SIZE).await?;
+        let Some(last) = entries.last() else {
+            break;
+        };
+        head = last.seq;
+
+        // 每个实体待推送的本地变更序号
+        let mut local_seqs: HashMap<(ChangeEntity, String), i64> = HashMap::new();
+        let mut changes = Vec::new();
+        for entry in compact_changes(entries) {
+            if !is_syncable(entry.entity_type) {
+                continue;
+            }
+            let known =
+                sync_db_fn::find_version(db, remote_id, entry.entity_type, &entry.entity_id)
+                    .await?;
+            if known.as_ref().is_some_and(|k| k.local_seq >= entry.seq) {
+                continue;
+            }
+            let change = sync_db_fn::current_change(
+                db,
+                entry.entity_type,
+                &entry.entity_id,
+                entry.workspace_id.clone(),
+                known.map_or(0, |k| k.version),
+            )
+            .await?;
+            local_seqs.insert((entry.entity_type, entry.entity_id), entry.seq);
+            changes.push(change);
+        }
+
+        for batch in order_changes(changes).chunks(batch_size as usize) {
+            let response = transport
+                .push(SyncPushRequest {
+                    changes: batch.to_vec(),
+                    force: false,
+                })
+                .await?;
+
+            let mut forced = Vec::new();
+            for (change, result) in match_results(batch, response.results)? {
+                let seq = local_seqs[&(change.entity_type, change.entity_id.clone())];
+                match (result.status, strategy) {
+                    (SyncPushStatus::Applied, _) => {
+                        record_pushed(db, remote_id, change, &result, seq, report).await?;
+                    }
+                    (SyncPushStatus::Conflict, SyncConflictStrategy::PreferLocal) => {
+                        forced.push(SyncChange {
+                            version: result.version,
+                            ..change.clone()
+                        });
+                    }
+                    (SyncPushStatus::Conflict, SyncConflictStrategy::PreferRemote) => {
+                        let applied = match &result.remote {
+                            Some(remote) => apply_remote(db, remote_id, remote).await?,
+                            None => false,
+                        };
+                        if applied {
+                            report.pulled += 1;
+                        } else {
+                            mark_unresolved(seq);
+                        }
+                    }
+                    (SyncPushStatus::Conflict, SyncConflictStrategy::Report) => {
+                        if let Some(remote) = result.remote {
+                            report.conflicts.push(SyncConflict {
+                                local: change.clone(),
+                                remote,
+                            });
+                        }
+                        mark_unresolved(seq);
+                    }
+                    (SyncPushStatus::Rejected, _) => {
+                        report.rejected.push(result);
+                        mark_unresolved(seq);
+                    }
+                }
+            }
+
+            if forced.is_empty() {
+                continue;
+            }
+            let response = transport
+                .push(SyncPushRequest {
+                    changes: forced.clone(),
+                    force: true,
+                })
+                .await?;
+            for (change, result) in match_results(&forced, response.results)? {
+                let seq = local_seqs[&(change.entity_type, change.entity_id.clone())];
+                if result.status == SyncPushStatus::Applied {
+                    record_pushed(db, remote_id, change, &result, seq, report).await?;
+                } else {
+                    report.rejected.push(result);
+                    mark_unresolved(seq);
+                }
+            }
+        }
+    }
+
+    Ok(unresolved.map_or(head, |seq| seq - 1))
+}
+
+/// 按实体把推送结果与请求中的变更对应起来
+///
+/// 结果数量与请求不一致或缺少某个实体的结果时返回错误，本次同步不推进推送游标。
+fn match_results(
+    changes: &[SyncChange],
+    results: Vec<SyncPushResult>,
+) -> AppResult<Vec<(&SyncChange, SyncPushResult)>> {
+    if results.len() != changes.len() {
+        return Err(AppError::internal(format!(
+            "同步服务器返回 {} 条推送结果，请求中有 {} 条变更",
+            results.len(),
+            changes.len()
+        )));
+    }
+
+    let mut by_entity: HashMap<(ChangeEntity, String), SyncPushResult> = results
+        .into_iter()
+        .map(|r| ((r.entity_type, r.entity_id.clone()), r))
+        .collect();
+    changes
+        .iter()
+        .map(|change| {
+            by_entity
+                .remove(&(change.entity_type, change.entity_id.clone()))
+                .map(|result| (change, result))
+                .ok_or_else(|| {
+                    AppError::internal(format!(
+                        "同步服务器没有返回 {:?} {} 的推送结果",
+                        change.entity_type, change.entity_id
+                    ))
+                })
+        })
+        .collect()
+}
+
+/// 记录推送成功的实体版本
+async fn record_pushed(
+    db: &DatabaseConnection,
+    remote_id: &str,
+    change: &SyncChange,
+    result: &SyncPushResult,
+    local_seq: i64,
+    report: &mut SyncReport,
+) -> AppResult<()> {
+    sync_db_fn::save_version(
+        db,
+        remote_id,
+        change.entity_type,
+        &change.entity_id,
+        result.version,
+        local_seq,
+    )
+    .await?;
+    report.pushed += 1;
+    Ok(())
+}
+
+/// 用服务器上的状态覆盖本地（依赖不存在时返回 false）
+async fn apply_remote(
+    db: &DatabaseConnection,
+    remote_id: &str,
+    remote: &SyncChange,
+) -> AppResult<bool> {
+    let txn = db.begin().await?;
+    if sync_db_fn::missing_dependency(&txn, remote)
+        .await?
+        .is_some()
+    {
+        return Ok(false);
+    }
+
+    let event = sync_db_fn::apply_change(&txn, remote).await?;
+    let local_seq = match &event {
+        Some(event) => change_log_db_fn::append(&txn, event)
+            .await?
+            .map_or(0, |e| e.seq),
+        None => change_log_db_fn::latest_seq(&txn, remote.entity_type, &remote.entity_id).await?,
+    };
+    sync_db_fn::save_version(
+        &txn,
+        remote_id,
+        remote.entity_type,
+        &remote.entity_id,
+        remote.version,
+        local_seq,
+    )
+    .await?;
+    txn.commit().await?;
+
+    if let Some(event) = event {
+        events::publish(event);
+    }
+    Ok(true)
+}
+
+// ============================================================================
+// 测试
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    use crate::api::{ApiEndpoint, CreateWorkspace, OptionalAuthWithBodyInput, SaveContent};
+    use crate::db::content_db_fn;
+    use crate::db::node_db_fn;
+    use crate::db::test_utils::setup_test_db;
+    use crate::server::build_routes;
+    use crate::types::change_log::ChangeOperation;
+    use crate::types::config::AppConfig;
+    use crate::types::content::SaveContentRequest;
+    use crate::types::node::NodeType;
+    use crate::types::workspace::CreateWorkspaceRequest;
+
+    /// 在进程内启动服务器，返回地址和服务器数据库
+    async fn start_server() -> (String, DatabaseConnection) {
+        let db = setup_test_db().await;
+        let routes = build_routes(
+            Arc::new(db.clone()),
+            Arc::new(AppConfig {
+                auth_enabled: false,
+                ..Default::default()
+            }),
+        );
+        let (addr, server) = warp::serve(routes).bind_ephemeral(([127, 0, 0, 1], 0));
+        tokio::spawn(server);
+        (format!("http://{}", addr), db)
+    }
+
+    /// 拉取指定页数后中断的传输
+    struct InterruptedTransport {
+        inner: HttpSyncTransport,
+        pulls_left: AtomicUsize,
+    }
+
+    impl SyncTransport for InterruptedTransport {
+        async fn pull(&self, query: SyncPullQuery) -> AppResult<SyncPullResponse> {
+            if self.pulls_left.fetch_sub(1, Ordering::SeqCst) == 0 {
+                return Err(AppError::internal("连接中断"));
+            }
+            self.inner.pull(query).await
+        }
+
+        async fn push(&self, request: SyncPushRequest) -> AppResult<SyncPushResponse> {
+            self.inner.push(request).await
+        }
+    }
+
+    async fn sync(
+        db: &DatabaseConnection,
+        url: &str,
+        strategy: SyncConflictStrategy,
+    ) -> SyncReport {
+        let transport = HttpSyncTransport::new(url, None).unwrap();
+        run(db, &transport, url, strategy, Some(2)).await.unwrap()
+    }
+
+    async fn save(db: &DatabaseConnection, node_id: &str, text: &str) {
+        SaveContent::execute(
+            db,
+            SaveContentRequest {
+                node_id: node_id.to_string(),
+                content: text.to_string(),
+                expected_version: None,
+                content_type: None,
+            },
+        )
+        .await
+        .unwrap();
+    }
+
+    async fn content_of(db: &DatabaseConnection, node_id: &str) -> Option<String> {
+        content_db_fn::find_by_node_id(db, node_id)
+            .await
+            .unwrap()
+            .map(|c| c.content)
+    }
+
+    async fn create_node(db: &DatabaseConnection, workspace_id: &str, id: &str) {
+        node_db_fn::create(
+            db,
+            id.to_string(),
+            workspace_id.to_string(),
+            None,
+            id.to_string(),
+            NodeType::File,
+            None,
+        )
+        .await
+        .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_sync_between_two_desktops() {
+        let (url, server) = start_server().await;
+        let desktop_a = setup_test_db().await;
+        let desktop_b = setup_test_db().await;
+
+        // A 创建数据并推送（节点直接写库，由基线补记）
+        let workspace = CreateWorkspace::execute(
+            &desktop_a,
+            OptionalAuthWithBodyInput::anonymous(CreateWorkspaceRequest {
+                title: "同步".to_string(),
+                author: None,
+                description: None,
+                publisher: None,
+                language: None,
+                members: None,
+                owner: None,
+            }),
+        )
+        .await
+        .unwrap();
+        create_node(&desktop_a, &workspace.id, "doc").await;
+        save(&desktop_a, "doc", "初稿").await;
+
+        let report = sync(&desktop_a, &url, SyncConflictStrategy::Report).await;
+        assert_eq!(report.pushed, 3);
+        assert!(report.conflicts.is_empty());
+        assert_eq!(content_of(&server, "doc").await.as_deref(), Some("初稿"));
+
+        // B 拉取全部数据，再次同步没有变化
+        let report = sync(&desktop_b, &url, SyncConflictStrategy::Report).await;
+        assert_eq!((report.pulled, report.pushed), (3, 0));
+        assert_eq!(content_of(&desktop_b, "doc").await.as_deref(), Some("初稿"));
+        let report = sync(&desktop_b, &url, SyncConflictStrategy::Report).await;
+        assert_eq!((report.pulled, report.pushed), (0, 0));
+        let report = sync(&desktop_a, &url, SyncConflictStrategy::Report).await;
+        assert_eq!((report.pulled, report.pushed), (0, 0));
+
+        // 两边同时修改：后同步的一方报告冲突，两边都不修改
+        save(&desktop_a, "doc", "A 的修改").await;
+        save(&desktop_b, "doc", "B 的修改").await;
+        sync(&desktop_a, &url, SyncConflictStrategy::Report).await;
+        let report = sync(&desktop_b, &url, SyncConflictStrategy::Report).await;
+        assert_eq!(report.conflicts.len(), 1);
+        let conflict = &report.conflicts[0];
+        assert_eq!(conflict.local.entity_type, ChangeEntity::Content);
+        assert!(matches!(
+            &conflict.remote.data,
+            Some(crate::types::sync::SyncEntityData::Content(c)) if c.content == "A 的修改"
+        ));
+        assert_eq!(
+            content_of(&desktop_b, "doc").await.as_deref(),
+            Some("B 的修改")
+        );
+        assert_eq!(
+            content_of(&server, "doc").await.as_deref(),
+            Some("A 的修改")
+        );
+
+        // 冲突未解决时再次同步仍然报告
+        let report = sync(&desktop_b, &url, SyncConflictStrategy::Report).await;
+        assert_eq!(report.conflicts.len(), 1);
+
+        // 以本地为准覆盖服务器，A 拉取后与 B 一致
+        let report = sync(&desktop_b, &url, SyncConflictStrategy::PreferLocal).await;
+        assert!(report.conflicts.is_empty());
+        assert_eq!(report.pushed, 1);
+        assert_eq!(
+            content_of(&server, "doc").await.as_deref(),
+            Some("B 的修改")
+        );
+        sync(&desktop_a, &url, SyncConflictStrategy::Report).await;
+        assert_eq!(
+            content_of(&desktop_a, "doc").await.as_deref(),
+            Some("B 的修改")
+        );
+
+        // 以服务器为准
+        save(&desktop_a, "doc", "A 再改").await;
+        save(&desktop_b, "doc", "B 再改").await;
+        sync(&desktop_a, &url, SyncConflictStrategy::Report).await;
+        let report = sync(&desktop_b, &url, SyncConflictStrategy::PreferRemote).await;
+        assert!(report.conflicts.is_empty());
+        assert_eq!(
+            content_of(&desktop_b, "doc").await.as_deref(),
+            Some("A 再改")
+        );
+        let report = sync(&desktop_b, &url, SyncConflictStrategy::Report).await;
+        assert_eq!((report.pulled, report.pushed), (0, 0));
+    }
+
+    #[tokio::test]
+    async fn test_sync_resumes_after_interruption() {
+        let (url, _server) = start_server().await;
+        let desktop_a = setup_test_db().await;
+        let desktop_b = setup_test_db().await;
+
+        crate::db::workspace_db_fn::create(
+            &desktop_a,
+            "ws".to_string(),
+            "工作区".to_string(),
+            None,
+        )
+        .await
+        .unwrap();
+        for i in 0..5 {
+            create_node(&desktop_a, "ws", &format!("n{}", i)).await;
+        }
+        assert_eq!(
+            sync(&desktop_a, &url, SyncConflictStrategy::Report)
+                .await
+                .pushed,
+            6
+        );
+
+        // 拉取两页（4 条）后中断
+        let transport = InterruptedTransport {
+            inner: HttpSyncTransport::new(&url, None).unwrap(),
+            pulls_left: AtomicUsize::new(2),
+        };
+        let result = run(
+            &desktop_b,
+            &transport,
+            &url,
+            SyncConflictStrategy::Report,
+            Some(2),
+        )
+        .await;
+        assert!(result.is_err());
+        let remote = sync_db_fn::find_remote(&desktop_b, &url)
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(remote.pull_seq > 0);
+
+        // 从中断处继续，只拉取剩下的变更
+        let report = sync(&desktop_b, &url, SyncConflictStrategy::Report).await;
+        assert_eq!((report.pulled, report.pushed), (2, 0));
+        for i in 0..5 {
+            assert!(node_db_fn::find_by_id(&desktop_b, &format!("n{}", i))
+                .await
+                .unwrap()
+                .is_some());
+        }
+    }
+
+    #[test]
+    fn test_match_push_results() {
+        let change = |id: &str| SyncChange {
+            entity_type: ChangeEntity::Node,
+            entity_id: id.to_string(),
+            workspace_id: Some("ws".to_string()),
+            operation: ChangeOperation::Upsert,
+            version: 0,
+            data: None,
+        };
+        let result = |id: &str| SyncPushResult {
+            entity_type: ChangeEntity::Node,
+            entity_id: id.to_string(),
+            status: SyncPushStatus::Applied,
+            version: 1,
+            remote: None,
+            message: None,
+        };
+        let changes = vec![change("a"), change("b")];
+
+        // 按实体对应，与结果顺序无关
+        let matched = match_results(&changes, vec![result("b"), result("a")]).unwrap();
+        assert!(matched
+            .iter()
+            .all(|(change, result)| change.entity_id == result.entity_id));
+
+        // 数量不一致或实体对不上时报错
+        assert!(match_results(&changes, vec![result("a")]).is_err());
+        assert!(match_results(&changes, vec![result("a"), result("c")]).is_err());
+    }
+}
@@ -0,0 +1,7 @@
+//! API Tauri Commands
+//!
+//! 端点注册表（`api_registry!`）中的每个端点生成一个同名命令
+
+use crate::api::registry::prelude::*;
+
+crate::api_registry!(tauri_commands);
@@ -0,0 +1,33 @@
+//! 日志迁移相关的 Tauri 命令
+//!
+//! 日志的读写命令由端点注册表生成，这里只有从 IndexedDB 迁移的状态。
+//! 迁移完成后在数据目录中创建标记文件，之后不再迁移。
+
+use crate::AppConfig;
+use std::path::PathBuf;
+use tauri::State;
+
+/// 迁移完成的标记文件名（在数据目录中）
+const MIGRATION_MARKER: &str = ".log-migration-complete";
+
+fn marker_path(config: &AppConfig) -> PathBuf {
+    config.data_dir.join(MIGRATION_MARKER)
+}
+
+/// 检查是否需要从 IndexedDB 迁移
+///
+/// 没有迁移完成的标记文件时返回 true；IndexedDB 中是否有日志数据由前端检查。
+#[tauri::command]
+pub async fn check_needs_migration(config: State<'_, AppConfig>) -> Result<bool, String> {
+    marker_path(&config)
+        .try_exists()
+        .map(|exists| !exists)
+        .map_err(|e| e.to_string())
+}
+
+/// 标记迁移完成（创建标记文件）
+#[tauri::command]
+pub async fn mark_migration_complete(config: State<'_, AppConfig>) -> Result<(), String> {
+    std::fs::create_dir_all(&config.data_dir).map_err(|e| e.to_string())?;
+    std::fs::write(marker_path(&config), b"").map_err(|e| e.to_string())
+}
@@ -2,26 +2,15 @@
 //!
 //! 定义前端可调用的 Tauri Commands
 //!
-//! 所有命令都在 rust-core 中定义，desktop 只需调用 run() 函数
+//! 所有命令都在 rust-core 中定义，desktop 只需调用 run() 函数。
+//! API 命令由端点注册表生成，这里只手写文件、备份和迁移命令。
 
-mod attachment_commands;
+mod api_commands;
 mod backup_commands;
-mod clear_data_commands;
-mod content_commands;
 mod file_commands;
-mod log_commands;
-mod node_commands;
-mod tag_commands;
-mod user_commands;
-mod workspace_commands;
+mod migration_commands;
 
-pub use attachment_commands::*;
+pub use api_commands::*;
 pub use backup_commands::*;
-pub use clear_data_commands::*;
-pub use content_commands::*;
 pub use file_commands::*;
-pub use log_commands::*;
-pub use node_commands::*;
-pub use tag_commands::*;
-pub use user_commands::*;
-pub use workspace_commands::*;
+pub use migration_commands::*;
@@ -7,16 +7,16 @@
 //! 由于 `tauri::generate_context!()` 宏需要读取 `tauri.conf.json` 文件，
 //! 该宏必须在 desktop 应用中调用。因此 rust-core 提供：
 //! - `create_builder()` - 创建配置好的 Tauri Builder
-//! - 所有 Tauri commands 的实现
+//! - 所有 Tauri commands 的实现（API 命令由端点注册表生成）
 
 mod commands;
 
 pub use commands::*;
 
 use crate::db::connection::DbConnection;
-use crate::AppConfig;
-use tauri::Manager;
-use tracing::{error, info};
+use crate::{events, AppConfig, CHANGE_EVENT_NAME};
+use tauri::{AppHandle, Emitter, Manager};
+use tracing::{error, info, warn};
 use tracing_subscriber::{fmt, prelude::*, EnvFilter};
 
 /// 初始化日志系统
@@ -42,6 +42,18 @@ async fn init_database(config: &AppConfig) -> Result<sea_orm::DatabaseConnection
     Ok(db)
 }
 
+/// 将数据变更事件转发给前端（事件名 `grain:change`）
+fn forward_change_events(handle: AppHandle) {
+    let mut subscription = events::subscribe();
+    tauri::async_runtime::spawn(async move {
+        while let Some(event) = subscription.recv().await {
+            if let Err(e) = handle.emit(CHANGE_EVENT_NAME, &event) {
+                warn!("转发变更事件失败: {}", e);
+            }
+        }
+    });
+}
+
 /// 创建配置好的 Tauri Builder
 ///
 /// 返回一个已配置所有插件和命令的 Builder，调用者只需提供 context 并运行。
@@ -78,6 +90,7 @@ pub fn create_builder() -> tauri::Builder<tauri::Wry> {
                 Ok(db) => {
                     app.manage(db);
                     app.manage(config_clone);
+                    forward_change_events(app.handle().clone());
                     info!("应用初始化完成");
                 }
                 Err(e) => {
@@ -89,98 +102,20 @@ pub fn create_builder() -> tauri::Builder<tauri::Wry> {
 
             Ok(())
         })
-        .invoke_handler(tauri::generate_handler![
-            // 文件系统命令
+        // API 命令来自端点注册表，其余为文件、备份和迁移命令
+        .invoke_handler(crate::api_registry!(
+            tauri_handler,
             select_directory,
             save_file,
             get_downloads_dir,
             get_home_dir,
             ensure_directory_and_save,
-            // 工作区命令
-            get_workspaces,
-            get_workspace,
-            create_workspace,
-            update_workspace,
-            delete_workspace,
-            // 节点命令
-            get_nodes_by_workspace,
-            get_node,
-            get_child_nodes,
-            get_root_nodes,
-            get_nodes_by_parent,
-            get_nodes_by_type,
-            get_descendants,
-            get_next_sort_order,
-            create_node,
-            update_node,
-            move_node,
-            delete_node,
-            delete_nodes_batch,
-            reorder_nodes,
-            duplicate_node,
-            // 内容命令
-            get_content,
-            save_content,
-            get_content_version,
-            // 备份命令
             create_backup,
             restore_backup,
             list_backups,
             delete_backup,
             cleanup_old_backups,
-            // 清除数据命令
-            clear_sqlite_data,
-            clear_sqlite_data_keep_users,
-            // 标签命令
-            get_tags_by_workspace,
-            get_tag,
-            get_tag_by_name,
-            get_top_tags,
-            create_tag,
-            update_tag,
-            get_or_create_tag,
-            increment_tag_count,
-            decrement_tag_count,
-            delete_tag,
-            delete_tags_by_workspace,
-            search_tags,
-            get_nodes_by_tag,
-            get_tag_graph_data,
-            sync_tag_cache,
-            rebuild_tag_cache,
-            recalculate_tag_counts,
-            // 用户命令
-            get_users,
-            get_user,
-            get_user_by_username,
-            get_user_by_email,
-            get_current_user,
-            create_user,
-            update_user,
-            update_user_last_login,
-            delete_user,
-            // 附件命令
-            get_attachments,
-            get_attachments_by_project,
-            get_attachment,
-            get_attachments_by_type,
-            get_images_by_project,
-            get_audio_files_by_project,
-            get_attachment_by_path,
-            create_attachment,
-            update_attachment,
-            delete_attachment,
-            delete_attachments_by_project,
-            // 日志命令
-            init_log_database,
-            check_log_database_exists,
-            save_log_entry,
-            save_logs_batch,
-            query_logs,
-            get_log_stats,
-            clear_old_logs,
-            clear_all_logs,
             check_needs_migration,
             mark_migration_complete,
-        ])
+        ))
 }
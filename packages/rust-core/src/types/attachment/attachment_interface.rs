@@ -3,6 +3,7 @@
 //! 定义附件相关的数据传输对象（DTO）
 
 use super::attachment_entity::AttachmentType;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 // ============================================================================
@@ -10,7 +11,7 @@ use serde::{Deserialize, Serialize};
 // ============================================================================
 
 /// 创建附件请求
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct CreateAttachmentRequest {
     /// 关联的项目/工作区 ID
@@ -33,7 +34,7 @@ pub struct CreateAttachmentRequest {
 }
 
 /// 更新附件请求
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct UpdateAttachmentRequest {
     /// 原始文件名
@@ -54,7 +55,7 @@ pub struct UpdateAttachmentRequest {
 // ============================================================================
 
 /// 附件响应
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct AttachmentResponse {
     /// 附件 ID
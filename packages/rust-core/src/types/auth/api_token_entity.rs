@@ -0,0 +1,108 @@
+//! ApiToken 实体定义
+//!
+//! 个人 API 令牌：供脚本和集成长期使用，与登录会话分开，不会过期，可随时撤销。
+//! 只保存令牌的哈希和便于辨认的开头部分。
+//! SeaORM Entity 定义，对应数据库 `api_tokens` 表。
+
+use schemars::JsonSchema;
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// API 令牌的权限范围
+///
+/// 令牌的权限不会超过创建者本人在工作区中的角色。
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Hash,
+    EnumIter,
+    DeriveActiveEnum,
+    Serialize,
+    Deserialize,
+    JsonSchema,
+)]
+#[sea_orm(rs_type = "String", db_type = "Text")]
+#[serde(rename_all = "lowercase")]
+pub enum ApiTokenScope {
+    /// 与创建者相同的权限
+    #[sea_orm(string_value = "full")]
+    Full,
+
+    /// 只读
+    #[sea_orm(string_value = "readonly")]
+    ReadOnly,
+
+    /// 只能列出、创建和删除备份
+    #[sea_orm(string_value = "backup")]
+    Backup,
+}
+
+/// ApiToken 实体定义
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel)]
+#[sea_orm(table_name = "api_tokens")]
+pub struct Model {
+    /// 令牌 ID (UUID)
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: String,
+
+    /// 创建者的用户 ID
+    pub user_id: String,
+
+    /// 名称（说明令牌的用途）
+    pub name: String,
+
+    /// 令牌的开头部分（如 `grain_pat_Ab3xYz`），用于辨认令牌
+    pub token_prefix: String,
+
+    /// 令牌哈希
+    pub token_hash: String,
+
+    /// 权限范围
+    pub scope: ApiTokenScope,
+
+    /// 限定的工作区 ID（None 表示不限工作区）
+    pub workspace_id: Option<String>,
+
+    /// 创建时间戳 (毫秒)
+    pub created_at: i64,
+
+    /// 最近一次使用的时间戳 (毫秒)，从未使用时为 None
+    pub last_used_at: Option<i64>,
+}
+
+/// 关系定义
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    /// 令牌属于一个用户
+    #[sea_orm(
+        belongs_to = "crate::types::user::UserEntity",
+        from = "Column::UserId",
+        to = "crate::types::user::UserColumn::Id"
+    )]
+    User,
+
+    /// 令牌可以限定在一个工作区
+    #[sea_orm(
+        belongs_to = "crate::types::workspace::WorkspaceEntity",
+        from = "Column::WorkspaceId",
+        to = "crate::types::workspace::WorkspaceColumn::Id"
+    )]
+    Workspace,
+}
+
+impl Related<crate::types::user::UserEntity> for Entity {
+    fn to() -> RelationDef {
+        Relation::User.def()
+    }
+}
+
+impl Related<crate::types::workspace::WorkspaceEntity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Workspace.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,159 @@
+//! Auth 接口类型定义
+//!
+//! 登录、刷新令牌、API 令牌的请求和响应，以及通过认证的请求身份
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use super::api_token_entity::{ApiTokenScope, Model};
+use crate::types::user::UserResponse;
+
+/// 令牌类型（`Authorization: Bearer <token>`）
+pub const TOKEN_TYPE_BEARER: &str = "Bearer";
+
+/// 登录请求
+#[derive(Debug, Clone, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct LoginRequest {
+    /// 用户名
+    pub username: String,
+
+    /// 密码
+    pub password: String,
+}
+
+/// 刷新令牌请求
+#[derive(Debug, Clone, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct RefreshTokenRequest {
+    /// 刷新令牌
+    pub refresh_token: String,
+}
+
+/// 令牌响应（登录和刷新令牌）
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct AuthTokenResponse {
+    /// 访问令牌
+    pub access_token: String,
+
+    /// 刷新令牌（每次刷新后旧的刷新令牌失效）
+    pub refresh_token: String,
+
+    /// 令牌类型（固定为 `Bearer`）
+    pub token_type: String,
+
+    /// 访问令牌过期时间戳 (毫秒)
+    pub expires_at: i64,
+
+    /// 刷新令牌过期时间戳 (毫秒)
+    pub refresh_expires_at: i64,
+
+    /// 登录的用户
+    pub user: UserResponse,
+}
+
+/// 通过认证的请求身份
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct AuthUser {
+    /// 用户 ID
+    pub user_id: String,
+
+    /// 会话 ID（使用 API 令牌时为空）
+    pub session_id: String,
+
+    /// 使用 API 令牌认证时的令牌权限
+    #[serde(default)]
+    pub token: Option<ApiTokenGrant>,
+}
+
+/// API 令牌授予的权限
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiTokenGrant {
+    /// 令牌 ID
+    pub token_id: String,
+
+    /// 权限范围
+    pub scope: ApiTokenScope,
+
+    /// 限定的工作区 ID
+    pub workspace_id: Option<String>,
+}
+
+impl From<&Model> for ApiTokenGrant {
+    fn from(model: &Model) -> Self {
+        Self {
+            token_id: model.id.clone(),
+            scope: model.scope,
+            workspace_id: model.workspace_id.clone(),
+        }
+    }
+}
+
+/// 创建 API 令牌请求
+#[derive(Debug, Clone, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateApiTokenRequest {
+    /// 名称（说明令牌的用途）
+    pub name: String,
+
+    /// 权限范围
+    pub scope: ApiTokenScope,
+
+    /// 限定的工作区 ID（不填表示不限工作区，备份令牌不能限定工作区）
+    pub workspace_id: Option<String>,
+}
+
+/// API 令牌响应（不包含令牌本身）
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiTokenResponse {
+    /// 令牌 ID
+    pub id: String,
+
+    /// 名称
+    pub name: String,
+
+    /// 令牌的开头部分
+    pub token_prefix: String,
+
+    /// 权限范围
+    pub scope: ApiTokenScope,
+
+    /// 限定的工作区 ID
+    pub workspace_id: Option<String>,
+
+    /// 创建时间戳 (毫秒)
+    pub created_at: i64,
+
+    /// 最近一次使用的时间戳 (毫秒)
+    pub last_used_at: Option<i64>,
+}
+
+impl From<Model> for ApiTokenResponse {
+    fn from(model: Model) -> Self {
+        Self {
+            id: model.id,
+            name: model.name,
+            token_prefix: model.token_prefix,
+            scope: model.scope,
+            workspace_id: model.workspace_id,
+            created_at: model.created_at,
+            last_used_at: model.last_used_at,
+        }
+    }
+}
+
+/// 新建的 API 令牌（令牌只在创建时返回一次）
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct CreatedApiTokenResponse {
+    /// 令牌信息
+    #[serde(flatten)]
+    pub info: ApiTokenResponse,
+
+    /// 令牌
+    pub token: String,
+}
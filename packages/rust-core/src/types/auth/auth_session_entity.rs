@@ -0,0 +1,57 @@
+//! AuthSession 实体定义
+//!
+//! 登录会话：一对访问令牌和刷新令牌，只保存令牌的哈希。
+//! 刷新时轮换两个令牌，登出时删除会话。
+//! SeaORM Entity 定义，对应数据库 `auth_sessions` 表。
+
+use sea_orm::entity::prelude::*;
+
+/// AuthSession 实体定义
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel)]
+#[sea_orm(table_name = "auth_sessions")]
+pub struct Model {
+    /// 会话 ID (UUID)
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: String,
+
+    /// 用户 ID
+    pub user_id: String,
+
+    /// 访问令牌哈希
+    pub access_token_hash: String,
+
+    /// 刷新令牌哈希
+    pub refresh_token_hash: String,
+
+    /// 访问令牌过期时间戳 (毫秒)
+    pub access_expires_at: i64,
+
+    /// 刷新令牌过期时间戳 (毫秒)
+    pub refresh_expires_at: i64,
+
+    /// 创建时间戳 (毫秒)
+    pub created_at: i64,
+
+    /// 最近一次使用的时间戳 (毫秒)
+    pub last_used_at: i64,
+}
+
+/// 关系定义
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    /// 会话属于一个用户
+    #[sea_orm(
+        belongs_to = "crate::types::user::UserEntity",
+        from = "Column::UserId",
+        to = "crate::types::user::UserColumn::Id"
+    )]
+    User,
+}
+
+impl Related<crate::types::user::UserEntity> for Entity {
+    fn to() -> RelationDef {
+        Relation::User.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,31 @@
+//! Auth 类型模块
+//!
+//! 包含 API 认证相关的所有类型定义：
+//! - `user_credential_entity.rs` - SeaORM 数据库实体（登录凭据）
+//! - `auth_session_entity.rs` - SeaORM 数据库实体（登录会话）
+//! - `api_token_entity.rs` - SeaORM 数据库实体（API 令牌）
+//! - `auth_interface.rs` - DTO 结构体定义
+
+pub mod api_token_entity;
+pub mod auth_interface;
+pub mod auth_session_entity;
+pub mod user_credential_entity;
+
+// 重新导出所有公共类型
+pub use api_token_entity::{
+    ActiveModel as ApiTokenActiveModel, ApiTokenScope, Column as ApiTokenColumn,
+    Entity as ApiTokenEntity, Model as ApiTokenModel, Relation as ApiTokenRelation,
+};
+pub use auth_interface::{
+    ApiTokenGrant, ApiTokenResponse, AuthTokenResponse, AuthUser, CreateApiTokenRequest,
+    CreatedApiTokenResponse, LoginRequest, RefreshTokenRequest, TOKEN_TYPE_BEARER,
+};
+pub use auth_session_entity::{
+    ActiveModel as AuthSessionActiveModel, Column as AuthSessionColumn,
+    Entity as AuthSessionEntity, Model as AuthSessionModel, Relation as AuthSessionRelation,
+};
+pub use user_credential_entity::{
+    ActiveModel as UserCredentialActiveModel, Column as UserCredentialColumn,
+    Entity as UserCredentialEntity, Model as UserCredentialModel,
+    Relation as UserCredentialRelation,
+};
@@ -0,0 +1,44 @@
+//! UserCredential 实体定义
+//!
+//! 用户的登录凭据，密码只保存加盐哈希（格式见 `r#fn::auth`）。
+//! SeaORM Entity 定义，对应数据库 `user_credentials` 表。
+
+use sea_orm::entity::prelude::*;
+
+/// UserCredential 实体定义
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel)]
+#[sea_orm(table_name = "user_credentials")]
+pub struct Model {
+    /// 用户 ID
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub user_id: String,
+
+    /// 密码哈希
+    pub password_hash: String,
+
+    /// 是否为服务器管理员（可以管理用户资料）
+    pub is_admin: bool,
+
+    /// 更新时间戳 (毫秒)
+    pub updated_at: i64,
+}
+
+/// 关系定义
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    /// 凭据属于一个用户
+    #[sea_orm(
+        belongs_to = "crate::types::user::UserEntity",
+        from = "Column::UserId",
+        to = "crate::types::user::UserColumn::Id"
+    )]
+    User,
+}
+
+impl Related<crate::types::user::UserEntity> for Entity {
+    fn to() -> RelationDef {
+        Relation::User.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
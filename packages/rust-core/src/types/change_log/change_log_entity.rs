@@ -0,0 +1,43 @@
+//! ChangeLog 实体定义
+//!
+//! 只追加的变更日志，每次实体变更写入一条记录，序号单调递增。
+//! 不设外键，实体删除后记录保留。
+//! SeaORM Entity 定义，对应数据库 `change_log` 表。
+
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use super::change_log_interface::{ChangeEntity, ChangeOperation};
+
+/// ChangeLog 实体定义
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "change_log")]
+pub struct Model {
+    /// 序号（自增，单调递增）
+    #[sea_orm(primary_key)]
+    pub seq: i64,
+
+    /// 所属工作区 ID（清除数据等全局变更为空）
+    pub workspace_id: Option<String>,
+
+    /// 变更的实体类型
+    pub entity_type: ChangeEntity,
+
+    /// 变更的实体 ID（内容使用节点 ID，标签使用工作区 ID）
+    pub entity_id: String,
+
+    /// 变更操作
+    pub operation: ChangeOperation,
+
+    /// 变更后的内容版本号（仅内容）
+    pub version: Option<i32>,
+
+    /// 创建时间戳 (毫秒)
+    pub created_at: i64,
+}
+
+/// 关系定义
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
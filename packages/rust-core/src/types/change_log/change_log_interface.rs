@@ -0,0 +1,168 @@
+//! ChangeLog DTO 接口定义
+//!
+//! 定义变更日志相关的数据传输对象（DTO），用于增量同步。
+
+use schemars::JsonSchema;
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use super::change_log_entity::Model;
+
+// ============================================================================
+// 枚举
+// ============================================================================
+
+/// 变更的实体类型
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Hash,
+    EnumIter,
+    DeriveActiveEnum,
+    Serialize,
+    Deserialize,
+    JsonSchema,
+)]
+#[sea_orm(rs_type = "String", db_type = "Text")]
+#[serde(rename_all = "lowercase")]
+pub enum ChangeEntity {
+    /// 工作区
+    #[sea_orm(string_value = "workspace")]
+    Workspace,
+
+    /// 节点
+    #[sea_orm(string_value = "node")]
+    Node,
+
+    /// 节点内容
+    #[sea_orm(string_value = "content")]
+    Content,
+
+    /// 工作区的标签集合
+    #[sea_orm(string_value = "tags")]
+    Tags,
+
+    /// 整个数据库（清除数据）
+    #[sea_orm(string_value = "database")]
+    Database,
+}
+
+/// 变更操作
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Hash,
+    EnumIter,
+    DeriveActiveEnum,
+    Serialize,
+    Deserialize,
+    JsonSchema,
+)]
+#[sea_orm(rs_type = "String", db_type = "Text")]
+#[serde(rename_all = "lowercase")]
+pub enum ChangeOperation {
+    /// 创建或更新
+    #[sea_orm(string_value = "upsert")]
+    Upsert,
+
+    /// 删除
+    #[sea_orm(string_value = "delete")]
+    Delete,
+
+    /// 清除所有数据，客户端需要丢弃本地数据
+    #[sea_orm(string_value = "reset")]
+    Reset,
+}
+
+// ============================================================================
+// 请求 DTO
+// ============================================================================
+
+/// 增量变更查询参数
+#[derive(Debug, Clone, Default, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ChangesSinceQuery {
+    /// 上次同步到的序号（不含，默认 0 表示从头开始）
+    pub since: Option<i64>,
+
+    /// 只返回指定工作区的变更（全局变更总是返回）
+    pub workspace_id: Option<String>,
+
+    /// 最多扫描的记录条数（默认 500）
+    pub limit: Option<u64>,
+}
+
+// ============================================================================
+// 响应 DTO
+// ============================================================================
+
+/// 变更日志记录
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ChangeLogEntry {
+    /// 序号
+    pub seq: i64,
+
+    /// 所属工作区 ID
+    pub workspace_id: Option<String>,
+
+    /// 变更的实体类型
+    pub entity_type: ChangeEntity,
+
+    /// 变更的实体 ID
+    pub entity_id: String,
+
+    /// 变更操作
+    pub operation: ChangeOperation,
+
+    /// 变更后的内容版本号（仅内容）
+    pub version: Option<i32>,
+
+    /// 创建时间戳 (毫秒)
+    pub created_at: i64,
+}
+
+impl From<Model> for ChangeLogEntry {
+    fn from(model: Model) -> Self {
+        Self {
+            seq: model.seq,
+            workspace_id: model.workspace_id,
+            entity_type: model.entity_type,
+            entity_id: model.entity_id,
+            operation: model.operation,
+            version: model.version,
+            created_at: model.created_at,
+        }
+    }
+}
+
+/// 增量变更响应
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ChangesSinceResponse {
+    /// 压缩后的变更（按序号升序，每个实体只保留最新一条）
+    pub changes: Vec<ChangeLogEntry>,
+
+    /// 下次查询使用的 since
+    pub next_since: i64,
+
+    /// 是否还有更多变更（扫描的记录超过 limit 时为 true）
+    pub has_more: bool,
+}
+
+/// 压缩变更日志的结果
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct CompactChangeLogResponse {
+    /// 删除的过时记录数
+    pub removed: u64,
+
+    /// 保留的记录数
+    pub remaining: u64,
+}
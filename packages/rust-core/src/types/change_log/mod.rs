@@ -0,0 +1,18 @@
+//! ChangeLog 类型模块
+//!
+//! 包含变更日志相关的所有类型定义：
+//! - `change_log_entity.rs` - SeaORM 数据库实体
+//! - `change_log_interface.rs` - DTO 结构体定义
+
+pub mod change_log_entity;
+pub mod change_log_interface;
+
+// 重新导出所有公共类型
+pub use change_log_entity::{
+    ActiveModel as ChangeLogActiveModel, Column as ChangeLogColumn, Entity as ChangeLogEntity,
+    Model as ChangeLogModel, Relation as ChangeLogRelation,
+};
+pub use change_log_interface::{
+    ChangeEntity, ChangeLogEntry, ChangeOperation, ChangesSinceQuery, ChangesSinceResponse,
+    CompactChangeLogResponse,
+};
@@ -14,8 +14,19 @@ pub struct AppConfig {
     pub backup_dirname: String,
     /// 是否启用数据库加密
     pub enable_encryption: bool,
+    /// HTTP API 是否要求登录（默认关闭：Web 客户端还不支持登录，关闭时所有请求不经认证）
+    pub auth_enabled: bool,
+    /// 允许跨域访问的来源（`scheme://host[:port]`，`*` 表示任意来源）
+    pub cors_origins: Vec<String>,
 }
 
+/// 默认允许的跨域来源（本地开发服务器与 Tauri 窗口）
+const DEFAULT_CORS_ORIGINS: [&str; 3] = [
+    "http://localhost:1420",
+    "http://127.0.0.1:1420",
+    "tauri://localhost",
+];
+
 impl Default for AppConfig {
     fn default() -> Self {
         Self {
@@ -25,6 +36,8 @@ impl Default for AppConfig {
             db_filename: "grain.db".to_string(),
             backup_dirname: "backups".to_string(),
             enable_encryption: true,
+            auth_enabled: false,
+            cors_origins: DEFAULT_CORS_ORIGINS.map(String::from).to_vec(),
         }
     }
 }
@@ -60,11 +73,27 @@ impl AppConfig {
             .map(|v| v == "true" || v == "1")
             .unwrap_or(true);
 
+        let auth_enabled = std::env::var("GRAIN_AUTH_ENABLED")
+            .map(|v| v == "true" || v == "1")
+            .unwrap_or(false);
+
+        // 逗号分隔，如 `https://grain.example.com,http://localhost:1420`
+        let cors_origins = std::env::var("GRAIN_CORS_ORIGINS")
+            .map(|v| {
+                v.split(',')
+                    .map(|origin| origin.trim().trim_end_matches('/').to_string())
+                    .filter(|origin| !origin.is_empty())
+                    .collect()
+            })
+            .unwrap_or_else(|_| Self::default().cors_origins);
+
         Self {
             data_dir,
             db_filename,
             backup_dirname: "backups".to_string(),
             enable_encryption,
+            auth_enabled,
+            cors_origins,
         }
     }
 }
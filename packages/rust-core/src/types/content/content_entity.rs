@@ -3,12 +3,14 @@
 //! 存储节点的编辑器内容 (Lexical JSON 状态)。
 //! SeaORM Entity 定义，对应数据库 `contents` 表。
 
+use schemars::JsonSchema;
 use sea_orm::entity::prelude::*;
 use serde::{Deserialize, Serialize};
 
 /// Content 实体定义
-#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel, Serialize, Deserialize, JsonSchema)]
 #[sea_orm(table_name = "contents")]
+#[schemars(rename = "ContentModel")]
 pub struct Model {
     /// 内容 ID (UUID)
     #[sea_orm(primary_key, auto_increment = false)]
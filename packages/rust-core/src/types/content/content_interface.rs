@@ -14,6 +14,7 @@
 //! | lastEdit | updated_at | 更新时间 |
 //! | - | version | 版本号（后端独有，用于乐观锁） |
 
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 // ============================================================================
@@ -22,7 +23,7 @@ use serde::{Deserialize, Serialize};
 
 /// 内容类型
 /// 对应前端 ContentType: "lexical" | "excalidraw" | "text"
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "lowercase")]
 pub enum ContentType {
     /// Lexical 编辑器 JSON
@@ -67,7 +68,7 @@ impl std::str::FromStr for ContentType {
 // ============================================================================
 
 /// 保存内容请求（创建或更新）
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct SaveContentRequest {
     /// 关联的节点 ID
@@ -85,7 +86,7 @@ pub struct SaveContentRequest {
 
 /// 创建内容请求
 /// 对应前端 ContentCreateInput
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct CreateContentRequest {
     /// 关联的节点 ID
@@ -100,7 +101,7 @@ pub struct CreateContentRequest {
 
 /// 更新内容请求
 /// 对应前端 ContentUpdateInput
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct UpdateContentRequest {
     /// 内容字符串
@@ -119,7 +120,7 @@ pub struct UpdateContentRequest {
 
 /// 内容响应
 /// 对应前端 ContentInterface
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct ContentResponse {
     /// 内容 ID
@@ -144,6 +145,37 @@ pub struct ContentResponse {
     pub updated_at: i64,
 }
 
+/// 合并结果状态
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub enum ContentMergeStatus {
+    /// 版本一致，直接保存
+    Saved,
+
+    /// 自动合并后保存
+    Merged,
+
+    /// 存在冲突，未保存
+    Conflict,
+}
+
+/// 合并响应
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ContentMergeResponse {
+    /// 合并结果状态
+    pub status: ContentMergeStatus,
+
+    /// 保存后的内容；冲突时为标记了两个版本的冲突文档
+    pub content: String,
+
+    /// 冲突数量
+    pub conflicts: u32,
+
+    /// 保存后的版本号；冲突时为当前版本号（解决冲突后以此作为 expectedVersion 保存）
+    pub version: i32,
+}
+
 /// Entity -> DTO 转换
 /// 注意：当前 Entity 缺少 content_type 字段，使用默认值
 impl From<super::content_entity::Model> for ContentResponse {
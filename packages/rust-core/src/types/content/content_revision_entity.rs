@@ -0,0 +1,50 @@
+//! ContentRevision 实体定义
+//!
+//! 内容的历史版本，版本冲突时作为三方合并的基础版本。
+//! 每个节点只保留最近的若干个版本。
+//! SeaORM Entity 定义，对应数据库 `content_revisions` 表。
+
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// ContentRevision 实体定义
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "content_revisions")]
+pub struct Model {
+    /// 记录 ID（格式：node_id:version）
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: String,
+
+    /// 关联的节点 ID
+    pub node_id: String,
+
+    /// 内容版本号
+    pub version: i32,
+
+    /// 该版本的内容
+    #[sea_orm(column_type = "Text")]
+    pub content: String,
+
+    /// 创建时间戳 (毫秒)
+    pub created_at: i64,
+}
+
+/// 关系定义
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    /// 版本属于一个节点
+    #[sea_orm(
+        belongs_to = "crate::types::node::node_entity::Entity",
+        from = "Column::NodeId",
+        to = "crate::types::node::node_entity::Column::Id"
+    )]
+    Node,
+}
+
+impl Related<crate::types::node::node_entity::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Node.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
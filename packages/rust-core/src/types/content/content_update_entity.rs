@@ -0,0 +1,50 @@
+//! ContentUpdate 实体定义
+//!
+//! 协同编辑的 CRDT 更新（Yjs v1 编码），按写入顺序追加，
+//! 全部更新合并后就是节点的 CRDT 文档。压缩时合并为一条。
+//! SeaORM Entity 定义，对应数据库 `content_updates` 表。
+
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// ContentUpdate 实体定义
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "content_updates")]
+pub struct Model {
+    /// 序号（自增）
+    #[sea_orm(primary_key)]
+    pub id: i64,
+
+    /// 关联的节点 ID
+    pub node_id: String,
+
+    /// 更新数据（Yjs v1 编码）
+    #[sea_orm(column_type = "Blob")]
+    pub data: Vec<u8>,
+
+    /// 写入时 CRDT 文档对应的内容版本号
+    pub content_version: i32,
+
+    /// 创建时间戳 (毫秒)
+    pub created_at: i64,
+}
+
+/// 关系定义
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    /// 更新属于一个节点
+    #[sea_orm(
+        belongs_to = "crate::types::node::node_entity::Entity",
+        from = "Column::NodeId",
+        to = "crate::types::node::node_entity::Column::Id"
+    )]
+    Node,
+}
+
+impl Related<crate::types::node::node_entity::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Node.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
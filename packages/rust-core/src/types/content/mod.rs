@@ -2,12 +2,16 @@
 //!
 //! 包含 Content 相关的所有类型定义：
 //! - `content_entity.rs` - SeaORM 数据库实体
+//! - `content_revision_entity.rs` - SeaORM 数据库实体（历史版本）
+//! - `content_update_entity.rs` - SeaORM 数据库实体（协同编辑的 CRDT 更新）
 //! - `content_interface.rs` - DTO 结构体定义
 //! - `content_builder.rs` - Builder 模式实现
 
 pub mod content_builder;
 pub mod content_entity;
 pub mod content_interface;
+pub mod content_revision_entity;
+pub mod content_update_entity;
 
 // 重新导出所有公共类型
 pub use content_builder::ContentBuilder;
@@ -16,5 +20,15 @@ pub use content_entity::{
     Model as ContentModel, Relation as ContentRelation,
 };
 pub use content_interface::{
-    ContentResponse, ContentType, CreateContentRequest, SaveContentRequest, UpdateContentRequest,
+    ContentMergeResponse, ContentMergeStatus, ContentResponse, ContentType, CreateContentRequest,
+    SaveContentRequest, UpdateContentRequest,
+};
+pub use content_revision_entity::{
+    ActiveModel as ContentRevisionActiveModel, Column as ContentRevisionColumn,
+    Entity as ContentRevisionEntity, Model as ContentRevisionModel,
+    Relation as ContentRevisionRelation,
+};
+pub use content_update_entity::{
+    ActiveModel as ContentUpdateActiveModel, Column as ContentUpdateColumn,
+    Entity as ContentUpdateEntity, Model as ContentUpdateModel, Relation as ContentUpdateRelation,
 };
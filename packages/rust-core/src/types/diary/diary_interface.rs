@@ -0,0 +1,129 @@
+//! Diary DTO 接口定义
+//!
+//! 定义日记服务相关的数据传输对象（DTO）。
+//! 日期统一使用 `YYYY-MM-DD` 格式。
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::types::content::ContentResponse;
+use crate::types::node::NodeResponse;
+
+// ============================================================================
+// 请求 DTO
+// ============================================================================
+
+/// 获取或创建日记请求
+#[derive(Debug, Clone, Default, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct GetOrCreateDiaryEntryRequest {
+    /// 日期（`YYYY-MM-DD`）
+    pub date: String,
+
+    /// 新建日记时使用的内容模板（可选，默认与桌面端日记模板一致）
+    ///
+    /// 支持占位符：`{{date}}` `{{year}}` `{{month}}` `{{day}}`
+    /// `{{weekday}}` `{{monthName}}` `{{zodiac}}`
+    pub template: Option<String>,
+
+    /// 日记根文件夹名称（可选，默认 `Diary`）
+    pub root_folder: Option<String>,
+}
+
+/// 日记日历查询（日期范围，两端包含）
+#[derive(Debug, Clone, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct DiaryCalendarQuery {
+    /// 起始日期（`YYYY-MM-DD`）
+    pub from: String,
+
+    /// 结束日期（`YYYY-MM-DD`）
+    pub to: String,
+}
+
+/// "那年今日" 查询
+#[derive(Debug, Clone, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct DiaryOnThisDayQuery {
+    /// 日期（`YYYY-MM-DD`）
+    pub date: String,
+}
+
+// ============================================================================
+// 响应 DTO
+// ============================================================================
+
+/// 日记条目响应
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct DiaryEntryResponse {
+    /// 日记日期
+    pub date: String,
+
+    /// 是否为本次新建
+    pub created: bool,
+
+    /// 日记节点
+    pub node: NodeResponse,
+
+    /// 日记内容（旧数据可能没有内容记录）
+    pub content: Option<ContentResponse>,
+}
+
+/// 日历中有日记的一天
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct DiaryCalendarDay {
+    /// 日期
+    pub date: String,
+
+    /// 当天的日记节点 ID（按创建时间排序）
+    pub node_ids: Vec<String>,
+
+    /// 当天所有日记的字数合计
+    pub word_count: u64,
+}
+
+/// "那年今日" 条目
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct DiaryOnThisDayEntry {
+    /// 日记日期
+    pub date: String,
+
+    /// 距查询日期的年数
+    pub years_ago: i32,
+
+    /// 日记节点
+    pub node: NodeResponse,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_get_or_create_request_defaults() {
+        let request: GetOrCreateDiaryEntryRequest =
+            serde_json::from_str(r#"{"date":"2026-01-01"}"#).unwrap();
+        assert_eq!(request.date, "2026-01-01");
+        assert!(request.template.is_none());
+        assert!(request.root_folder.is_none());
+
+        let request: GetOrCreateDiaryEntryRequest =
+            serde_json::from_str(r#"{"date":"2026-01-01","rootFolder":"Journal"}"#).unwrap();
+        assert_eq!(request.root_folder.as_deref(), Some("Journal"));
+    }
+
+    #[test]
+    fn test_calendar_day_serialization() {
+        let day = DiaryCalendarDay {
+            date: "2026-01-01".into(),
+            node_ids: vec!["a".into()],
+            word_count: 42,
+        };
+        let json = serde_json::to_string(&day).unwrap();
+        assert!(json.contains("nodeIds"));
+        assert!(json.contains("wordCount"));
+    }
+}
@@ -0,0 +1,12 @@
+//! Diary 类型模块
+//!
+//! 包含日记服务相关的类型定义：
+//! - `diary_interface.rs` - DTO 结构体定义
+
+pub mod diary_interface;
+
+// 重新导出所有公共类型
+pub use diary_interface::{
+    DiaryCalendarDay, DiaryCalendarQuery, DiaryEntryResponse, DiaryOnThisDayEntry,
+    DiaryOnThisDayQuery, GetOrCreateDiaryEntryRequest,
+};
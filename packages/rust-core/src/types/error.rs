@@ -8,6 +8,7 @@
 //! - `ValidationError` - 验证失败 (400)
 //! - `DatabaseError` - 数据库错误 (500)
 //! - `Unauthorized` - 未授权 (401)
+//! - `Forbidden` - 无权访问 (403)
 //! - `InternalError` - 内部错误 (500)
 
 use thiserror::Error;
@@ -34,6 +35,10 @@ pub enum AppError {
     #[error("未授权: {0}")]
     Unauthorized(String),
 
+    /// 无权访问
+    #[error("无权访问: {0}")]
+    Forbidden(String),
+
     /// 内部错误
     #[error("内部错误: {0}")]
     InternalError(String),
@@ -67,6 +72,7 @@ impl AppError {
     /// - `ValidationError` → 400
     /// - `DatabaseError` → 500
     /// - `Unauthorized` → 401
+    /// - `Forbidden` → 403
     /// - `InternalError` → 500
     /// - `IoError` → 500
     /// - `SerializationError` → 400
@@ -76,6 +82,7 @@ impl AppError {
             AppError::ValidationError(_) => 400,
             AppError::DatabaseError(_) => 500,
             AppError::Unauthorized(_) => 401,
+            AppError::Forbidden(_) => 403,
             AppError::InternalError(_) => 500,
             AppError::IoError(_) => 500,
             AppError::SerializationError(_) => 400,
@@ -91,6 +98,7 @@ impl AppError {
             AppError::ValidationError(_) => "VALIDATION_ERROR",
             AppError::DatabaseError(_) => "DATABASE_ERROR",
             AppError::Unauthorized(_) => "UNAUTHORIZED",
+            AppError::Forbidden(_) => "FORBIDDEN",
             AppError::InternalError(_) => "INTERNAL_ERROR",
             AppError::IoError(_) => "IO_ERROR",
             AppError::SerializationError(_) => "SERIALIZATION_ERROR",
@@ -119,6 +127,11 @@ impl AppError {
         AppError::Unauthorized(msg.into())
     }
 
+    /// 创建无权访问错误
+    pub fn forbidden(msg: impl Into<String>) -> Self {
+        AppError::Forbidden(msg.into())
+    }
+
     /// 创建内部错误
     pub fn internal(msg: impl Into<String>) -> Self {
         AppError::InternalError(msg.into())
@@ -175,6 +188,12 @@ impl From<zip::result::ZipError> for AppError {
     }
 }
 
+impl From<reqwest::Error> for AppError {
+    fn from(err: reqwest::Error) -> Self {
+        AppError::InternalError(format!("请求同步服务器失败: {}", err))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -187,6 +206,7 @@ mod tests {
         assert_eq!(AppError::ValidationError("test".into()).status_code(), 400);
         assert_eq!(AppError::DatabaseError("test".into()).status_code(), 500);
         assert_eq!(AppError::Unauthorized("test".into()).status_code(), 401);
+        assert_eq!(AppError::Forbidden("test".into()).status_code(), 403);
         assert_eq!(AppError::InternalError("test".into()).status_code(), 500);
         assert_eq!(AppError::IoError("test".into()).status_code(), 500);
         assert_eq!(AppError::SerializationError("test".into()).status_code(), 400);
@@ -209,6 +229,7 @@ mod tests {
             AppError::Unauthorized("test".into()).error_code(),
             "UNAUTHORIZED"
         );
+        assert_eq!(AppError::Forbidden("test".into()).error_code(), "FORBIDDEN");
         assert_eq!(
             AppError::InternalError("test".into()).error_code(),
             "INTERNAL_ERROR"
@@ -240,6 +261,9 @@ mod tests {
         let err = AppError::unauthorized("需要登录");
         assert!(matches!(err, AppError::Unauthorized(_)));
 
+        let err = AppError::forbidden("需要编辑权限");
+        assert!(matches!(err, AppError::Forbidden(_)));
+
         let err = AppError::internal("未知错误");
         assert!(matches!(err, AppError::InternalError(_)));
     }
@@ -0,0 +1,154 @@
+//! Event DTO 接口定义
+//!
+//! 定义数据变更事件。所有写操作成功后发布事件，
+//! Warp 通过 SSE（`/api/events`）推送，Tauri 通过 `emit` 转发给前端。
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// Tauri 前端监听的事件名称
+pub const CHANGE_EVENT_NAME: &str = "grain:change";
+
+// ============================================================================
+// 事件
+// ============================================================================
+
+/// 数据变更事件
+///
+/// 序列化为带 `type` 字段的对象，如
+/// `{"type":"contentSaved","workspaceId":"..","nodeId":"..","version":3}`。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(
+    tag = "type",
+    rename_all = "camelCase",
+    rename_all_fields = "camelCase"
+)]
+pub enum ChangeEvent {
+    /// 创建了工作区
+    WorkspaceCreated { workspace_id: String },
+
+    /// 更新了工作区
+    WorkspaceUpdated { workspace_id: String },
+
+    /// 删除了工作区
+    WorkspaceDeleted { workspace_id: String },
+
+    /// 创建了节点
+    NodeCreated {
+        workspace_id: String,
+        node_id: String,
+        parent_id: Option<String>,
+    },
+
+    /// 更新了节点（标题、折叠状态、排序或标签）
+    NodeUpdated {
+        workspace_id: String,
+        node_id: String,
+    },
+
+    /// 移动了节点
+    NodeMoved {
+        workspace_id: String,
+        node_id: String,
+        parent_id: Option<String>,
+        sort_order: i32,
+    },
+
+    /// 删除了节点（子孙节点一并删除，不单独发布事件）
+    NodeDeleted {
+        workspace_id: String,
+        node_id: String,
+    },
+
+    /// 保存了内容
+    ContentSaved {
+        workspace_id: String,
+        node_id: String,
+        version: i32,
+    },
+
+    /// 工作区的标签发生变化（计数、元数据、重命名或合并）
+    TagsChanged { workspace_id: String },
+
+    /// 清除了所有数据
+    DataCleared,
+
+    /// 订阅方处理过慢丢失了事件，需要重新加载数据
+    Resync,
+}
+
+impl ChangeEvent {
+    /// 事件所属的工作区（全局事件返回 None）
+    pub fn workspace_id(&self) -> Option<&str> {
+        match self {
+            ChangeEvent::WorkspaceCreated { workspace_id }
+            | ChangeEvent::WorkspaceUpdated { workspace_id }
+            | ChangeEvent::WorkspaceDeleted { workspace_id }
+            | ChangeEvent::NodeCreated { workspace_id, .. }
+            | ChangeEvent::NodeUpdated { workspace_id, .. }
+            | ChangeEvent::NodeMoved { workspace_id, .. }
+            | ChangeEvent::NodeDeleted { workspace_id, .. }
+            | ChangeEvent::ContentSaved { workspace_id, .. }
+            | ChangeEvent::TagsChanged { workspace_id } => Some(workspace_id),
+            ChangeEvent::DataCleared | ChangeEvent::Resync => None,
+        }
+    }
+
+    /// 是否推送给只订阅了指定工作区的客户端（全局事件总是推送）
+    pub fn matches_workspace(&self, workspace_id: Option<&str>) -> bool {
+        match (workspace_id, self.workspace_id()) {
+            (Some(filter), Some(own)) => filter == own,
+            _ => true,
+        }
+    }
+}
+
+// ============================================================================
+// 请求 DTO
+// ============================================================================
+
+/// 事件订阅参数
+#[derive(Debug, Clone, Default, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ChangeEventQuery {
+    /// 只接收指定工作区的事件（可选）
+    pub workspace_id: Option<String>,
+}
+
+// ============================================================================
+// 测试
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_change_event_serialize() {
+        let event = ChangeEvent::ContentSaved {
+            workspace_id: "ws".to_string(),
+            node_id: "n1".to_string(),
+            version: 3,
+        };
+        let json = serde_json::to_value(&event).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({"type":"contentSaved","workspaceId":"ws","nodeId":"n1","version":3})
+        );
+        assert_eq!(serde_json::from_value::<ChangeEvent>(json).unwrap(), event);
+
+        let json = serde_json::to_string(&ChangeEvent::DataCleared).unwrap();
+        assert_eq!(json, r#"{"type":"dataCleared"}"#);
+    }
+
+    #[test]
+    fn test_matches_workspace() {
+        let event = ChangeEvent::TagsChanged {
+            workspace_id: "ws".to_string(),
+        };
+        assert!(event.matches_workspace(None));
+        assert!(event.matches_workspace(Some("ws")));
+        assert!(!event.matches_workspace(Some("other")));
+        assert!(ChangeEvent::DataCleared.matches_workspace(Some("other")));
+    }
+}
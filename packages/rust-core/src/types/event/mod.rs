@@ -0,0 +1,9 @@
+//! Event 类型模块
+//!
+//! 包含数据变更事件相关的类型定义：
+//! - `event_interface.rs` - 事件和订阅参数定义
+
+pub mod event_interface;
+
+// 重新导出所有公共类型
+pub use event_interface::{ChangeEvent, ChangeEventQuery, CHANGE_EVENT_NAME};
@@ -0,0 +1,203 @@
+//! Graph DTO 接口定义
+//!
+//! 定义节点知识图谱的数据传输对象（DTO）。
+//! 与标签图谱（TagGraphData）不同，图中的点是文档节点。
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Deserializer, Serialize};
+
+use crate::types::node::NodeType;
+
+// ============================================================================
+// 请求 DTO
+// ============================================================================
+
+/// 节点图谱查询条件
+///
+/// 所有条件均可选，同时指定时取交集
+#[derive(Debug, Clone, Default, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct NodeGraphQuery {
+    /// 只包含该文件夹（含自身）下的节点
+    #[serde(default)]
+    pub folder_id: Option<String>,
+
+    /// 只包含这些类型的节点
+    #[serde(default, deserialize_with = "list_or_csv")]
+    pub node_types: Option<Vec<NodeType>>,
+
+    /// 只包含带有该标签的节点
+    #[serde(default)]
+    pub tag: Option<String>,
+
+    /// 焦点节点，只返回与其距离不超过 depth 的节点
+    #[serde(default)]
+    pub focus_id: Option<String>,
+
+    /// 距焦点节点的最大深度（默认 1，仅在指定 focus_id 时生效）
+    #[serde(default)]
+    pub depth: Option<u32>,
+
+    /// 只包含这些类型的边（默认全部）
+    #[serde(default, deserialize_with = "list_or_csv")]
+    pub edge_kinds: Option<Vec<NodeGraphEdgeKind>>,
+}
+
+/// 列表或逗号分隔的字符串
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum ListOrCsv<T> {
+    List(Vec<T>),
+    Csv(String),
+}
+
+/// 反序列化列表条件：JSON 中是数组，查询参数中是逗号分隔的字符串（如 `nodeTypes=file,diary`）
+fn list_or_csv<'de, D, T>(deserializer: D) -> Result<Option<Vec<T>>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Deserialize<'de> + std::str::FromStr<Err = String>,
+{
+    match Option::<ListOrCsv<T>>::deserialize(deserializer)? {
+        Some(ListOrCsv::List(items)) => Ok(Some(items)),
+        Some(ListOrCsv::Csv(csv)) if csv.is_empty() => Ok(None),
+        Some(ListOrCsv::Csv(csv)) => csv
+            .split(',')
+            .map(|item| item.trim().parse::<T>())
+            .collect::<Result<Vec<T>, String>>()
+            .map(Some)
+            .map_err(serde::de::Error::custom),
+        None => Ok(None),
+    }
+}
+
+// ============================================================================
+// 响应 DTO
+// ============================================================================
+
+/// 节点图谱数据
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct NodeGraphData {
+    /// 图形节点（文档）
+    pub nodes: Vec<NodeGraphNode>,
+
+    /// 图形边（引用、共同标签、父子关系）
+    pub edges: Vec<NodeGraphEdge>,
+}
+
+/// 节点图谱中的点
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct NodeGraphNode {
+    /// 节点 ID
+    pub id: String,
+
+    /// 节点标题
+    pub title: String,
+
+    /// 节点类型
+    pub node_type: NodeType,
+
+    /// 标签数组
+    pub tags: Vec<String>,
+
+    /// 度数（与该节点相连的边数）
+    pub degree: i32,
+}
+
+/// 节点图谱中的边
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct NodeGraphEdge {
+    /// 源节点 ID
+    pub source: String,
+
+    /// 目标节点 ID
+    pub target: String,
+
+    /// 边类型
+    pub kind: NodeGraphEdgeKind,
+
+    /// 权重（引用/父子关系为 1，共同标签为共有的标签数）
+    pub weight: i32,
+}
+
+/// 节点图谱边类型
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize, JsonSchema,
+)]
+#[serde(rename_all = "camelCase")]
+pub enum NodeGraphEdgeKind {
+    /// `@` 引用（有向：引用方 -> 被引用方）
+    Link,
+    /// 共同标签（无向，source < target）
+    SharedTag,
+    /// 父子关系（有向：父 -> 子）
+    ParentChild,
+}
+
+impl std::str::FromStr for NodeGraphEdgeKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "link" => Ok(NodeGraphEdgeKind::Link),
+            "sharedTag" => Ok(NodeGraphEdgeKind::SharedTag),
+            "parentChild" => Ok(NodeGraphEdgeKind::ParentChild),
+            _ => Err(format!("未知的边类型: {}", s)),
+        }
+    }
+}
+
+impl NodeGraphData {
+    /// 创建空的图形数据
+    pub fn empty() -> Self {
+        Self {
+            nodes: Vec::new(),
+            edges: Vec::new(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_graph_query_list_or_csv() {
+        let json: NodeGraphQuery =
+            serde_json::from_str(r#"{"nodeTypes": ["file", "diary"]}"#).unwrap();
+        assert_eq!(json.node_types, Some(vec![NodeType::File, NodeType::Diary]));
+
+        let query: NodeGraphQuery =
+            serde_urlencoded::from_str("nodeTypes=file,%20diary&edgeKinds=&depth=2").unwrap();
+        assert_eq!(
+            query.node_types,
+            Some(vec![NodeType::File, NodeType::Diary])
+        );
+        assert_eq!(query.edge_kinds, None);
+        assert_eq!(query.depth, Some(2));
+        assert!(serde_urlencoded::from_str::<NodeGraphQuery>("edgeKinds=bogus").is_err());
+    }
+
+    #[test]
+    fn test_edge_kind_serialization() {
+        let json = serde_json::to_string(&NodeGraphEdgeKind::SharedTag).unwrap();
+        assert_eq!(json, "\"sharedTag\"");
+        assert_eq!(
+            "parentChild".parse::<NodeGraphEdgeKind>().unwrap(),
+            NodeGraphEdgeKind::ParentChild
+        );
+        assert!("unknown".parse::<NodeGraphEdgeKind>().is_err());
+    }
+
+    #[test]
+    fn test_node_graph_query_defaults() {
+        let query: NodeGraphQuery =
+            serde_json::from_str(r#"{"nodeTypes":["diary"],"focusId":"n-1"}"#).unwrap();
+        assert_eq!(query.node_types, Some(vec![NodeType::Diary]));
+        assert_eq!(query.focus_id.as_deref(), Some("n-1"));
+        assert!(query.depth.is_none());
+        assert!(query.edge_kinds.is_none());
+    }
+}
@@ -0,0 +1,11 @@
+//! Graph 类型模块
+//!
+//! 包含节点知识图谱相关的类型定义：
+//! - `graph_interface.rs` - DTO 结构体定义
+
+pub mod graph_interface;
+
+// 重新导出所有公共类型
+pub use graph_interface::{
+    NodeGraphData, NodeGraphEdge, NodeGraphEdgeKind, NodeGraphNode, NodeGraphQuery,
+};
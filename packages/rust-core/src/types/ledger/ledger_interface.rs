@@ -0,0 +1,568 @@
+//! Ledger DTO 接口定义
+//!
+//! 定义记账引擎相关的数据结构：定点金额、分录、过账、诊断信息以及查询/报表 DTO。
+//! 日期统一使用 `YYYY-MM-DD` 格式，金额在 JSON 中以十进制字符串表示（避免浮点误差）。
+
+use std::fmt;
+use std::str::FromStr;
+
+use schemars::gen::SchemaGenerator;
+use schemars::schema::{InstanceType, Metadata, Schema, SchemaObject};
+use schemars::JsonSchema;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::types::content::ContentResponse;
+
+// ============================================================================
+// 金额
+// ============================================================================
+
+/// 定点金额（最多 4 位小数）
+///
+/// 内部以万分之一为单位存储为整数，加减运算没有浮点误差。
+/// 不实现 `+`/`-` 运算符：合计大额余额可能溢出，运算一律通过 `checked_*` 进行。
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct LedgerAmount(i64);
+
+impl LedgerAmount {
+    /// 小数位数
+    pub const SCALE: u32 = 4;
+
+    const UNIT: i64 = 10_i64.pow(Self::SCALE);
+
+    /// 零
+    pub const ZERO: Self = Self(0);
+
+    /// 从万分之一单位构造
+    pub const fn from_units(units: i64) -> Self {
+        Self(units)
+    }
+
+    /// 万分之一单位的整数值
+    pub const fn units(self) -> i64 {
+        self.0
+    }
+
+    /// 是否为零
+    pub const fn is_zero(self) -> bool {
+        self.0 == 0
+    }
+
+    /// 是否为负数
+    pub const fn is_negative(self) -> bool {
+        self.0 < 0
+    }
+
+    /// 相加，溢出时返回 None
+    pub const fn checked_add(self, rhs: Self) -> Option<Self> {
+        match self.0.checked_add(rhs.0) {
+            Some(units) => Some(Self(units)),
+            None => None,
+        }
+    }
+
+    /// 取反，溢出时返回 None
+    pub const fn checked_neg(self) -> Option<Self> {
+        match self.0.checked_neg() {
+            Some(units) => Some(Self(units)),
+            None => None,
+        }
+    }
+}
+
+impl fmt::Display for LedgerAmount {
+    /// 至少保留 2 位小数，多余的尾随 0 去掉（`42.5` -> `42.50`，`0.125` -> `0.125`）
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let sign = if self.0 < 0 { "-" } else { "" };
+        let abs = self.0.unsigned_abs();
+        let unit = Self::UNIT as u64;
+        let mut fraction = format!("{:04}", abs % unit);
+        while fraction.len() > 2 && fraction.ends_with('0') {
+            fraction.pop();
+        }
+        write!(f, "{}{}.{}", sign, abs / unit, fraction)
+    }
+}
+
+impl FromStr for LedgerAmount {
+    type Err = String;
+
+    /// 解析十进制金额，允许正负号和千分位逗号（`-1,234.56`）
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || format!("无效的金额: {}", s);
+        let text = s.trim().replace(',', "");
+        let (negative, digits) = match text.strip_prefix('-') {
+            Some(rest) => (true, rest),
+            None => (false, text.strip_prefix('+').unwrap_or(&text)),
+        };
+        let (whole, fraction) = digits.split_once('.').unwrap_or((digits, ""));
+        if (whole.is_empty() && fraction.is_empty())
+            || !whole.chars().all(|c| c.is_ascii_digit())
+            || !fraction.chars().all(|c| c.is_ascii_digit())
+        {
+            return Err(invalid());
+        }
+        if fraction.len() > Self::SCALE as usize {
+            return Err(format!("金额最多 {} 位小数: {}", Self::SCALE, s));
+        }
+
+        let whole: i64 = if whole.is_empty() {
+            0
+        } else {
+            whole.parse().map_err(|_| invalid())?
+        };
+        let fraction: i64 = format!("{:0<4}", fraction).parse().map_err(|_| invalid())?;
+        let units = whole
+            .checked_mul(Self::UNIT)
+            .and_then(|w| w.checked_add(fraction))
+            .ok_or_else(invalid)?;
+        Ok(Self(if negative { -units } else { units }))
+    }
+}
+
+impl Serialize for LedgerAmount {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for LedgerAmount {
+    /// 接受十进制字符串或 JSON 数字
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = serde_json::Value::deserialize(deserializer)?;
+        let text = match value {
+            serde_json::Value::String(s) => s,
+            serde_json::Value::Number(n) => n.to_string(),
+            other => return Err(serde::de::Error::custom(format!("无效的金额: {}", other))),
+        };
+        text.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+impl JsonSchema for LedgerAmount {
+    fn schema_name() -> String {
+        "LedgerAmount".to_string()
+    }
+
+    /// 序列化为十进制字符串（如 `"-12.5"`），反序列化也接受 JSON 数字
+    fn json_schema(_gen: &mut SchemaGenerator) -> Schema {
+        SchemaObject {
+            instance_type: Some(InstanceType::String.into()),
+            metadata: Some(Box::new(Metadata {
+                description: Some("十进制金额，最多 4 位小数".to_string()),
+                ..Default::default()
+            })),
+            ..Default::default()
+        }
+        .into()
+    }
+}
+
+// ============================================================================
+// 分录
+// ============================================================================
+
+/// 过账（分录中的一行）
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct LedgerPosting {
+    /// 账户（`:` 分隔层级，如 `Expenses:Food`）
+    pub account: String,
+
+    /// 金额（正数借方，负数贷方）
+    pub amount: LedgerAmount,
+
+    /// 币种（未指定时为空字符串）
+    #[serde(default)]
+    pub commodity: String,
+
+    /// 金额是否由平衡推算（原文省略了金额）
+    #[serde(default)]
+    pub inferred: bool,
+}
+
+/// 记账分录
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct LedgerEntry {
+    /// 在账本中的序号（从 0 开始，包含无效分录）
+    pub index: usize,
+
+    /// 起始行号（从 1 开始；结构化 JSON 账本为空）
+    pub line: Option<usize>,
+
+    /// 日期（`YYYY-MM-DD`）
+    pub date: String,
+
+    /// 摘要
+    pub description: String,
+
+    /// 是否为待确认（`!` 标记）
+    pub pending: bool,
+
+    /// 过账列表
+    pub postings: Vec<LedgerPosting>,
+}
+
+/// 诊断级别
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub enum LedgerDiagnosticSeverity {
+    /// 错误（分录不计入余额）
+    Error,
+    /// 警告（分录仍计入余额）
+    Warning,
+}
+
+/// 账本诊断信息
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct LedgerDiagnostic {
+    /// 所属分录序号（与分录无关时为空）
+    pub entry_index: Option<usize>,
+
+    /// 行号（从 1 开始；结构化 JSON 账本为空）
+    pub line: Option<usize>,
+
+    /// 级别
+    pub severity: LedgerDiagnosticSeverity,
+
+    /// 说明
+    pub message: String,
+}
+
+// ============================================================================
+// 请求 DTO
+// ============================================================================
+
+/// 余额查询
+#[derive(Debug, Clone, Default, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct LedgerBalancesQuery {
+    /// 只统计指定记账节点（可选，默认工作区内所有记账节点）
+    pub node_id: Option<String>,
+
+    /// 截止日期（包含，可选）
+    pub to: Option<String>,
+}
+
+/// 报表分组方式
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub enum LedgerGroupBy {
+    /// 按账户
+    #[default]
+    Account,
+    /// 按月份和账户
+    Month,
+}
+
+/// 报表查询
+#[derive(Debug, Clone, Default, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct LedgerReportQuery {
+    /// 分组方式（默认按账户）
+    #[serde(default)]
+    pub group_by: LedgerGroupBy,
+
+    /// 账户过滤（包含子账户，如 `Expenses`）
+    pub account: Option<String>,
+
+    /// 账户层级深度（如 1 时 `Expenses:Food` 合并到 `Expenses`）
+    pub depth: Option<usize>,
+
+    /// 只统计指定记账节点（可选）
+    pub node_id: Option<String>,
+
+    /// 起始日期（包含，可选）
+    pub from: Option<String>,
+
+    /// 结束日期（包含，可选）
+    pub to: Option<String>,
+}
+
+/// CSV 列（列名或从 0 开始的列序号）
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(untagged)]
+pub enum LedgerCsvColumn {
+    /// 列序号
+    Index(usize),
+    /// 列名（需要表头）
+    Name(String),
+}
+
+/// CSV 列映射
+#[derive(Debug, Clone, Default, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct LedgerCsvMapping {
+    /// 日期列
+    pub date: Option<LedgerCsvColumn>,
+
+    /// 摘要列（可选）
+    pub description: Option<LedgerCsvColumn>,
+
+    /// 金额列（与 debit/credit 二选一）
+    pub amount: Option<LedgerCsvColumn>,
+
+    /// 支出列（从账户流出的金额）
+    pub debit: Option<LedgerCsvColumn>,
+
+    /// 收入列（流入账户的金额）
+    pub credit: Option<LedgerCsvColumn>,
+
+    /// 币种列（可选）
+    pub commodity: Option<LedgerCsvColumn>,
+}
+
+/// 金额列的符号约定
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub enum LedgerAmountSign {
+    /// 正数为流入账户（银行流水常见）
+    #[default]
+    Normal,
+    /// 正数为流出账户（信用卡账单常见）
+    Inverted,
+}
+
+/// 分类规则：摘要包含 `contains`（不区分大小写）时记入 `account`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct LedgerImportRule {
+    /// 摘要中包含的文本
+    pub contains: String,
+
+    /// 对方账户
+    pub account: String,
+}
+
+/// 导入银行 CSV 请求
+#[derive(Debug, Clone, Default, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportLedgerCsvRequest {
+    /// CSV 文本
+    pub csv: String,
+
+    /// 银行账户（如 `Assets:Bank:Checking`）
+    pub account: String,
+
+    /// 列映射
+    pub mapping: LedgerCsvMapping,
+
+    /// 第一行是否为表头（默认 true）
+    pub has_header: Option<bool>,
+
+    /// 分隔符（默认 `,`）
+    pub delimiter: Option<char>,
+
+    /// 日期格式（chrono 格式，默认依次尝试 `%Y-%m-%d`、`%Y/%m/%d`）
+    pub date_format: Option<String>,
+
+    /// 是否使用逗号作小数点（`1.234,56`）
+    #[serde(default)]
+    pub decimal_comma: bool,
+
+    /// 金额符号约定
+    #[serde(default)]
+    pub sign: LedgerAmountSign,
+
+    /// 币种（没有币种列时使用，可选）
+    pub commodity: Option<String>,
+
+    /// 分类规则（按顺序匹配第一条）
+    #[serde(default)]
+    pub rules: Vec<LedgerImportRule>,
+
+    /// 未匹配规则的收入记入的账户（默认 `Income:Uncategorized`）
+    pub income_account: Option<String>,
+
+    /// 未匹配规则的支出记入的账户（默认 `Expenses:Uncategorized`）
+    pub expense_account: Option<String>,
+
+    /// 只预览，不写入内容
+    #[serde(default)]
+    pub dry_run: bool,
+
+    /// 期望的内容版本号（乐观锁，可选）
+    pub expected_version: Option<i32>,
+}
+
+// ============================================================================
+// 响应 DTO
+// ============================================================================
+
+/// 账户余额
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct LedgerBalance {
+    /// 账户（包含父账户汇总行）
+    pub account: String,
+
+    /// 币种
+    pub commodity: String,
+
+    /// 余额
+    pub amount: LedgerAmount,
+}
+
+/// 报表行
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct LedgerReportRow {
+    /// 月份（`YYYY-MM`，按账户分组时为空）
+    pub period: Option<String>,
+
+    /// 账户
+    pub account: String,
+
+    /// 币种
+    pub commodity: String,
+
+    /// 合计金额
+    pub amount: LedgerAmount,
+
+    /// 过账数量
+    pub postings: usize,
+}
+
+/// 节点的诊断信息
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct LedgerNodeDiagnostic {
+    /// 记账节点 ID
+    pub node_id: String,
+
+    /// 记账节点标题
+    pub node_title: String,
+
+    /// 诊断信息
+    #[serde(flatten)]
+    pub diagnostic: LedgerDiagnostic,
+}
+
+/// 余额响应
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct LedgerBalancesResponse {
+    /// 账户余额（按账户、币种排序）
+    pub balances: Vec<LedgerBalance>,
+
+    /// 所有记账节点的诊断信息
+    pub diagnostics: Vec<LedgerNodeDiagnostic>,
+}
+
+/// 报表响应
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct LedgerReportResponse {
+    /// 报表行
+    pub rows: Vec<LedgerReportRow>,
+
+    /// 所有记账节点的诊断信息
+    pub diagnostics: Vec<LedgerNodeDiagnostic>,
+}
+
+/// 单个记账节点的解析结果
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct LedgerCheckResponse {
+    /// 记账节点 ID
+    pub node_id: String,
+
+    /// 解析出的分录（包含无效分录）
+    pub entries: Vec<LedgerEntry>,
+
+    /// 诊断信息
+    pub diagnostics: Vec<LedgerDiagnostic>,
+}
+
+/// 导入时跳过的 CSV 行
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct LedgerImportIssue {
+    /// 行号（从 1 开始，包含表头）
+    pub row: usize,
+
+    /// 原因
+    pub message: String,
+}
+
+/// 导入银行 CSV 响应
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct LedgerImportResponse {
+    /// 导入（预览时为将要导入）的分录
+    pub entries: Vec<LedgerEntry>,
+
+    /// 与账本中已有分录重复而跳过的行号
+    pub duplicate_rows: Vec<usize>,
+
+    /// 无法解析而跳过的行
+    pub issues: Vec<LedgerImportIssue>,
+
+    /// 写入后的内容（预览或没有新分录时为空）
+    pub content: Option<ContentResponse>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ledger_amount_parse_and_display() {
+        let amount: LedgerAmount = "-1,234.5".parse().unwrap();
+        assert_eq!(amount.units(), -12_345_000);
+        assert_eq!(amount.to_string(), "-1234.50");
+        assert_eq!(
+            "0.125".parse::<LedgerAmount>().unwrap().to_string(),
+            "0.125"
+        );
+        assert_eq!(".5".parse::<LedgerAmount>().unwrap().to_string(), "0.50");
+        assert_eq!("+3".parse::<LedgerAmount>().unwrap().to_string(), "3.00");
+
+        assert!("abc".parse::<LedgerAmount>().is_err());
+        assert!("1.23456".parse::<LedgerAmount>().is_err());
+        assert!("-".parse::<LedgerAmount>().is_err());
+        assert!("".parse::<LedgerAmount>().is_err());
+    }
+
+    #[test]
+    fn test_ledger_amount_checked_arithmetic() {
+        let one = LedgerAmount::from_units(1);
+        let max = LedgerAmount::from_units(i64::MAX);
+        let min = LedgerAmount::from_units(i64::MIN);
+        assert_eq!(one.checked_add(one), Some(LedgerAmount::from_units(2)));
+        assert_eq!(max.checked_add(one), None);
+        assert_eq!(min.checked_add(LedgerAmount::from_units(-1)), None);
+        assert_eq!(one.checked_neg(), Some(LedgerAmount::from_units(-1)));
+        assert_eq!(min.checked_neg(), None);
+        assert!("922337203685477.5807".parse::<LedgerAmount>().is_ok());
+        assert!("922337203685477.5808".parse::<LedgerAmount>().is_err());
+    }
+
+    #[test]
+    fn test_import_request_deserialize() {
+        let request: ImportLedgerCsvRequest = serde_json::from_str(
+            r#"{"csv":"","account":"Assets:Bank","mapping":{"date":0,"amount":"Betrag"},"sign":"inverted"}"#,
+        )
+        .unwrap();
+        assert_eq!(request.mapping.date, Some(LedgerCsvColumn::Index(0)));
+        assert_eq!(
+            request.mapping.amount,
+            Some(LedgerCsvColumn::Name("Betrag".to_string()))
+        );
+        assert_eq!(request.sign, LedgerAmountSign::Inverted);
+        assert!(request.rules.is_empty());
+    }
+
+    #[test]
+    fn test_ledger_amount_serde() {
+        let posting: LedgerPosting =
+            serde_json::from_str(r#"{"account":"Assets:Cash","amount":12.3}"#).unwrap();
+        assert_eq!(posting.amount.to_string(), "12.30");
+        assert_eq!(posting.commodity, "");
+
+        let json = serde_json::to_string(&posting).unwrap();
+        assert!(json.contains(r#""amount":"12.30""#));
+    }
+}
@@ -0,0 +1,15 @@
+//! Ledger 类型模块
+//!
+//! 包含记账引擎相关的类型定义：
+//! - `ledger_interface.rs` - 金额、分录、诊断、CSV 导入和 DTO 结构体定义
+
+pub mod ledger_interface;
+
+// 重新导出所有公共类型
+pub use ledger_interface::{
+    ImportLedgerCsvRequest, LedgerAmount, LedgerAmountSign, LedgerBalance, LedgerBalancesQuery,
+    LedgerBalancesResponse, LedgerCheckResponse, LedgerCsvColumn, LedgerCsvMapping,
+    LedgerDiagnostic, LedgerDiagnosticSeverity, LedgerEntry, LedgerGroupBy, LedgerImportIssue,
+    LedgerImportResponse, LedgerImportRule, LedgerNodeDiagnostic, LedgerPosting, LedgerReportQuery,
+    LedgerReportResponse, LedgerReportRow,
+};
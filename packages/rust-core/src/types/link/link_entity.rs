@@ -0,0 +1,48 @@
+//! NodeLink 实体定义
+//!
+//! 存储从内容中解析出的 `@` 引用（源节点 -> 目标节点）。
+//! SeaORM Entity 定义，对应数据库 `node_links` 表。
+
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// NodeLink 实体定义
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "node_links")]
+pub struct Model {
+    /// 链接 ID（格式：source_id:target_id）
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: String,
+
+    /// 源节点 ID（内容中包含引用的节点）
+    pub source_id: String,
+
+    /// 目标节点 ID（被引用的节点，可能已不存在）
+    pub target_id: String,
+
+    /// 引用时显示的名称（mentionName），目标被删除后用于展示断链
+    pub target_title: String,
+
+    /// 创建时间戳 (毫秒)
+    pub created_at: i64,
+}
+
+/// 关系定义
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    /// 链接属于源节点（源节点删除时级联删除）
+    #[sea_orm(
+        belongs_to = "crate::types::node::node_entity::Entity",
+        from = "Column::SourceId",
+        to = "crate::types::node::node_entity::Column::Id"
+    )]
+    Source,
+}
+
+impl Related<crate::types::node::node_entity::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Source.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,104 @@
+//! NodeLink DTO 接口定义
+//!
+//! 定义节点引用链接相关的数据传输对象（DTO）
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+// ============================================================================
+// 响应 DTO
+// ============================================================================
+
+/// 节点链接响应
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct NodeLinkResponse {
+    /// 链接 ID
+    pub id: String,
+
+    /// 源节点 ID
+    pub source_id: String,
+
+    /// 目标节点 ID
+    pub target_id: String,
+
+    /// 引用时显示的名称
+    pub target_title: String,
+
+    /// 创建时间戳（毫秒）
+    pub created_at: i64,
+}
+
+/// Entity -> DTO 转换
+impl From<super::link_entity::Model> for NodeLinkResponse {
+    fn from(model: super::link_entity::Model) -> Self {
+        Self {
+            id: model.id,
+            source_id: model.source_id,
+            target_id: model.target_id,
+            target_title: model.target_title,
+            created_at: model.created_at,
+        }
+    }
+}
+
+// ============================================================================
+// 链接改写报告
+// ============================================================================
+
+/// 重命名/移动节点时自动改写引用的报告
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct LinkRewriteReport {
+    /// 被引用的节点 ID
+    pub target_id: String,
+
+    /// 改写后的引用名称
+    pub new_title: String,
+
+    /// 被改写的文档
+    pub documents: Vec<RewrittenDocument>,
+}
+
+/// 被改写引用的文档
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct RewrittenDocument {
+    /// 文档节点 ID
+    pub node_id: String,
+
+    /// 文档标题
+    pub title: String,
+
+    /// 改写的引用数量
+    pub mentions: usize,
+
+    /// 改写后的内容版本号
+    pub version: i32,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_node_link_response_from_model() {
+        let model = super::super::link_entity::Model {
+            id: "a:b".into(),
+            source_id: "a".into(),
+            target_id: "b".into(),
+            target_title: "第一章".into(),
+            created_at: 1704067200000,
+        };
+
+        let response = NodeLinkResponse::from(model);
+        assert_eq!(response.id, "a:b");
+        assert_eq!(response.source_id, "a");
+        assert_eq!(response.target_id, "b");
+        assert_eq!(response.target_title, "第一章");
+
+        let json = serde_json::to_string(&response).unwrap();
+        assert!(json.contains("sourceId"));
+        assert!(json.contains("targetTitle"));
+    }
+}
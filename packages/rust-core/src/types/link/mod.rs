@@ -0,0 +1,15 @@
+//! Link 类型模块
+//!
+//! 包含节点 `@` 引用链接相关的所有类型定义：
+//! - `link_entity.rs` - SeaORM 数据库实体
+//! - `link_interface.rs` - DTO 结构体定义
+
+pub mod link_entity;
+pub mod link_interface;
+
+// 重新导出所有公共类型
+pub use link_entity::{
+    ActiveModel as NodeLinkActiveModel, Column as NodeLinkColumn, Entity as NodeLinkEntity,
+    Model as NodeLinkModel, Relation as NodeLinkRelation,
+};
+pub use link_interface::{LinkRewriteReport, NodeLinkResponse, RewrittenDocument};
@@ -2,13 +2,14 @@
 //!
 //! 定义日志相关的请求数据结构
 
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
 use super::entity::LogLevel;
 
 /// 创建日志条目请求
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct CreateLogEntryRequest {
     /// 日志唯一标识符（可选，如果不提供会自动生成）
     pub uuid: Option<String>,
@@ -37,7 +38,7 @@ pub struct CreateLogsBatchRequest {
 }
 
 /// 日志查询选项
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct LogQueryOptions {
     /// 限制返回条目数
     pub limit: Option<i32>,
@@ -76,7 +77,7 @@ impl Default for LogQueryOptions {
 }
 
 /// 清理旧日志请求
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct ClearOldLogsRequest {
     /// 清理此日期之前的日志（ISO 8601 格式）
     pub before_date: String,
@@ -2,13 +2,14 @@
 //!
 //! 定义日志相关的响应数据结构
 
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use std::collections::{BTreeMap, HashMap};
 
 use super::entity::LogLevel;
 
 /// 日志条目响应
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct LogEntryResponse {
     /// 日志 ID
     pub id: i32,
@@ -36,7 +37,7 @@ pub struct LogEntryResponse {
 }
 
 /// 日志查询结果
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct LogQueryResult {
     /// 日志条目列表
     pub entries: Vec<LogEntryResponse>,
@@ -49,7 +50,7 @@ pub struct LogQueryResult {
 }
 
 /// 日志统计信息
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct LogStats {
     /// 总日志条目数
     pub total_entries: i64,
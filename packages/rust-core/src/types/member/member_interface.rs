@@ -0,0 +1,57 @@
+//! Member 接口类型定义
+//!
+//! 工作区成员管理的请求和响应
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use super::workspace_member_entity::{Model, WorkspaceRole};
+use crate::types::user::UserModel;
+
+/// 添加成员或修改成员角色的请求
+#[derive(Debug, Clone, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SetWorkspaceMemberRequest {
+    /// 用户名
+    pub username: String,
+
+    /// 角色
+    pub role: WorkspaceRole,
+}
+
+/// 工作区成员响应
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkspaceMemberResponse {
+    /// 工作区 ID
+    pub workspace_id: String,
+
+    /// 用户 ID
+    pub user_id: String,
+
+    /// 用户名
+    pub username: String,
+
+    /// 显示名称
+    pub display_name: Option<String>,
+
+    /// 角色
+    pub role: WorkspaceRole,
+
+    /// 加入时间戳 (毫秒)
+    pub created_at: i64,
+}
+
+impl WorkspaceMemberResponse {
+    /// 由成员记录和对应的用户创建
+    pub fn new(member: Model, user: UserModel) -> Self {
+        Self {
+            workspace_id: member.workspace_id,
+            user_id: member.user_id,
+            username: user.username,
+            display_name: user.display_name,
+            role: member.role,
+            created_at: member.created_at,
+        }
+    }
+}
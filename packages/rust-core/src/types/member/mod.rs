@@ -0,0 +1,16 @@
+//! Member 类型模块
+//!
+//! 包含工作区成员和角色相关的所有类型定义：
+//! - `workspace_member_entity.rs` - SeaORM 数据库实体
+//! - `member_interface.rs` - DTO 结构体定义
+
+pub mod member_interface;
+pub mod workspace_member_entity;
+
+// 重新导出所有公共类型
+pub use member_interface::{SetWorkspaceMemberRequest, WorkspaceMemberResponse};
+pub use workspace_member_entity::{
+    ActiveModel as WorkspaceMemberActiveModel, Column as WorkspaceMemberColumn,
+    Entity as WorkspaceMemberEntity, Model as WorkspaceMemberModel,
+    Relation as WorkspaceMemberRelation, WorkspaceRole,
+};
@@ -0,0 +1,121 @@
+//! WorkspaceMember 实体定义
+//!
+//! 工作区成员及其角色。启用认证的 HTTP 服务器按成员角色检查访问权限，
+//! 没有成员记录的用户看不到工作区。
+//! SeaORM Entity 定义，对应数据库 `workspace_members` 表。
+
+use schemars::JsonSchema;
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// 工作区角色（权限从低到高）
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    EnumIter,
+    DeriveActiveEnum,
+    Serialize,
+    Deserialize,
+    JsonSchema,
+)]
+#[sea_orm(rs_type = "String", db_type = "Text")]
+#[serde(rename_all = "lowercase")]
+pub enum WorkspaceRole {
+    /// 只读
+    #[sea_orm(string_value = "viewer")]
+    Viewer,
+
+    /// 读写内容
+    #[sea_orm(string_value = "editor")]
+    Editor,
+
+    /// 读写内容，管理工作区设置和成员
+    #[sea_orm(string_value = "owner")]
+    Owner,
+}
+
+impl WorkspaceRole {
+    /// 是否具有 `required` 角色的权限
+    pub fn allows(self, required: WorkspaceRole) -> bool {
+        self >= required
+    }
+}
+
+/// WorkspaceMember 实体定义
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel)]
+#[sea_orm(table_name = "workspace_members")]
+pub struct Model {
+    /// 工作区 ID
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub workspace_id: String,
+
+    /// 用户 ID
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub user_id: String,
+
+    /// 角色
+    pub role: WorkspaceRole,
+
+    /// 加入时间戳 (毫秒)
+    pub created_at: i64,
+}
+
+/// 关系定义
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    /// 成员属于工作区（工作区删除时级联删除）
+    #[sea_orm(
+        belongs_to = "crate::types::workspace::workspace_entity::Entity",
+        from = "Column::WorkspaceId",
+        to = "crate::types::workspace::workspace_entity::Column::Id"
+    )]
+    Workspace,
+
+    /// 成员对应一个用户（用户删除时级联删除）
+    #[sea_orm(
+        belongs_to = "crate::types::user::UserEntity",
+        from = "Column::UserId",
+        to = "crate::types::user::UserColumn::Id"
+    )]
+    User,
+}
+
+impl Related<crate::types::workspace::workspace_entity::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Workspace.def()
+    }
+}
+
+impl Related<crate::types::user::UserEntity> for Entity {
+    fn to() -> RelationDef {
+        Relation::User.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+// ============================================================================
+// 测试
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_role_allows() {
+        assert!(WorkspaceRole::Owner.allows(WorkspaceRole::Editor));
+        assert!(WorkspaceRole::Editor.allows(WorkspaceRole::Editor));
+        assert!(!WorkspaceRole::Viewer.allows(WorkspaceRole::Editor));
+        assert_eq!(
+            serde_json::to_string(&WorkspaceRole::Owner).unwrap(),
+            r#""owner""#
+        );
+    }
+}
@@ -14,12 +14,24 @@ pub mod error;
 
 // DTO + Builder + Entity 模块（按实体分目录）
 pub mod attachment;
+pub mod auth;
+pub mod change_log;
 pub mod content;
+pub mod diary;
+pub mod event;
+pub mod graph;
+pub mod ledger;
+pub mod link;
 pub mod log;
+pub mod member;
 pub mod node;
+pub mod search;
+pub mod sync;
 pub mod tag;
+pub mod task;
 pub mod user;
 pub mod workspace;
+pub mod writing;
 
 // 重新导出核心类型
 pub use config::AppConfig;
@@ -27,8 +39,9 @@ pub use error::{AppError, AppResult};
 
 // 重新导出 Node 类型
 pub use node::{
-    CreateNodeRequest, MoveNodeRequest, NodeActiveModel, NodeBuilder, NodeColumn, NodeEntity,
-    NodeModel, NodeRelation, NodeResponse, NodeType, UpdateNodeRequest,
+    CreateNodeRequest, MoveNodeRequest, NodeActiveModel, NodeBuilder, NodeChangeResponse,
+    NodeColumn, NodeEntity, NodeModel, NodeRelation, NodeResponse, NodeTreeResponse, NodeType,
+    UpdateNodeRequest,
 };
 
 // 重新导出 Workspace 类型
@@ -39,15 +52,92 @@ pub use workspace::{
 
 // 重新导出 Content 类型
 pub use content::{
-    ContentActiveModel, ContentBuilder, ContentColumn, ContentEntity, ContentModel,
-    ContentRelation, ContentResponse, ContentType, CreateContentRequest, SaveContentRequest,
-    UpdateContentRequest,
+    ContentActiveModel, ContentBuilder, ContentColumn, ContentEntity, ContentMergeResponse,
+    ContentMergeStatus, ContentModel, ContentRelation, ContentResponse, ContentRevisionActiveModel,
+    ContentRevisionColumn, ContentRevisionEntity, ContentRevisionModel, ContentType,
+    ContentUpdateActiveModel, ContentUpdateColumn, ContentUpdateEntity, ContentUpdateModel,
+    CreateContentRequest, SaveContentRequest, UpdateContentRequest,
+};
+
+// 重新导出 Diary 类型
+pub use diary::{
+    DiaryCalendarDay, DiaryCalendarQuery, DiaryEntryResponse, DiaryOnThisDayEntry,
+    DiaryOnThisDayQuery, GetOrCreateDiaryEntryRequest,
+};
+
+// 重新导出 ChangeLog 类型
+pub use change_log::{
+    ChangeEntity, ChangeLogActiveModel, ChangeLogColumn, ChangeLogEntity, ChangeLogEntry,
+    ChangeLogModel, ChangeLogRelation, ChangeOperation, ChangesSinceQuery, ChangesSinceResponse,
+    CompactChangeLogResponse,
+};
+
+// 重新导出 Event 类型
+pub use event::{ChangeEvent, ChangeEventQuery, CHANGE_EVENT_NAME};
+
+// 重新导出 Graph 类型
+pub use graph::{NodeGraphData, NodeGraphEdge, NodeGraphEdgeKind, NodeGraphNode, NodeGraphQuery};
+
+// 重新导出 Ledger 类型
+pub use ledger::{
+    ImportLedgerCsvRequest, LedgerAmount, LedgerAmountSign, LedgerBalance, LedgerBalancesQuery,
+    LedgerBalancesResponse, LedgerCheckResponse, LedgerCsvColumn, LedgerCsvMapping,
+    LedgerDiagnostic, LedgerDiagnosticSeverity, LedgerEntry, LedgerGroupBy, LedgerImportIssue,
+    LedgerImportResponse, LedgerImportRule, LedgerNodeDiagnostic, LedgerPosting, LedgerReportQuery,
+    LedgerReportResponse, LedgerReportRow,
+};
+
+// 重新导出 Link 类型
+pub use link::{
+    LinkRewriteReport, NodeLinkActiveModel, NodeLinkColumn, NodeLinkEntity, NodeLinkModel,
+    NodeLinkRelation, NodeLinkResponse, RewrittenDocument,
+};
+
+// 重新导出 Member 类型
+pub use member::{
+    SetWorkspaceMemberRequest, WorkspaceMemberActiveModel, WorkspaceMemberColumn,
+    WorkspaceMemberEntity, WorkspaceMemberModel, WorkspaceMemberRelation, WorkspaceMemberResponse,
+    WorkspaceRole,
+};
+
+// 重新导出 Search 类型
+pub use search::{
+    CreateSavedSearchRequest, SavedSearchActiveModel, SavedSearchColumn, SavedSearchEntity,
+    SavedSearchModel, SavedSearchRelation, SavedSearchResponse, SearchNodesQuery,
+    UpdateSavedSearchRequest,
+};
+
+// 重新导出 Sync 类型
+pub use sync::{
+    SyncChange, SyncConflict, SyncConflictStrategy, SyncEntityData, SyncPullQuery,
+    SyncPullResponse, SyncPushRequest, SyncPushResponse, SyncPushResult, SyncPushStatus,
+    SyncRemoteActiveModel, SyncRemoteColumn, SyncRemoteEntity, SyncRemoteModel, SyncRemoteRelation,
+    SyncReport, SyncRequest, SyncVersionActiveModel, SyncVersionColumn, SyncVersionEntity,
+    SyncVersionModel, SyncVersionRelation,
 };
 
 // 重新导出 Tag 类型
 pub use tag::{
-    CreateTagRequest, TagActiveModel, TagColumn, TagEntity, TagGraphData, TagGraphEdge,
-    TagGraphNode, TagModel, TagResponse, UpdateTagRequest,
+    CreateTagRequest, MergeTagsRequest, RenameTagRequest, ResolveTagQuery, TagActiveModel,
+    TagColumn, TagEntity, TagGraphData, TagGraphEdge, TagGraphNode, TagModel, TagResponse,
+    TagRewriteContent, TagRewriteReport, TagTreeNode, UpdateTagMetadataRequest, UpdateTagRequest,
+};
+
+// 重新导出 Task 类型
+pub use task::{
+    TaskActiveModel, TaskColumn, TaskDueFilter, TaskEntity, TaskModel, TaskPriority, TaskQuery,
+    TaskRelation, TaskResponse, TaskStatus, ToggleTaskRequest,
+};
+
+// 重新导出 Writing 类型
+pub use writing::{
+    NodeWritingStats, SetWritingGoalRequest, WritingDay, WritingGoalActiveModel, WritingGoalColumn,
+    WritingGoalEntity, WritingGoalKind, WritingGoalModel, WritingGoalProgress, WritingGoalRelation,
+    WritingGoalResponse, WritingProgressActiveModel, WritingProgressColumn, WritingProgressEntity,
+    WritingProgressModel, WritingProgressQuery, WritingProgressRelation, WritingProgressResponse,
+    WritingStats, WritingStatsActiveModel, WritingStatsColumn, WritingStatsEntity,
+    WritingStatsModel, WritingStatsQuery, WritingStatsRelation, WritingStatsResponse,
+    WritingStreak, CJK_CHARACTERS_PER_MINUTE, LATIN_WORDS_PER_MINUTE,
 };
 
 // 重新导出 User 类型
@@ -56,6 +146,15 @@ pub use user::{
     UserPlan, UserResponse,
 };
 
+// 重新导出 Auth 类型
+pub use auth::{
+    ApiTokenActiveModel, ApiTokenColumn, ApiTokenEntity, ApiTokenGrant, ApiTokenModel,
+    ApiTokenResponse, ApiTokenScope, AuthSessionActiveModel, AuthSessionColumn, AuthSessionEntity,
+    AuthSessionModel, AuthTokenResponse, AuthUser, CreateApiTokenRequest, CreatedApiTokenResponse,
+    LoginRequest, RefreshTokenRequest, UserCredentialActiveModel, UserCredentialColumn,
+    UserCredentialEntity, UserCredentialModel, TOKEN_TYPE_BEARER,
+};
+
 // 重新导出 Attachment 类型
 pub use attachment::{
     AttachmentActiveModel, AttachmentColumn, AttachmentEntity, AttachmentModel, AttachmentResponse,
@@ -16,5 +16,6 @@ pub use node_entity::{
     Model as NodeModel, Relation as NodeRelation,
 };
 pub use node_interface::{
-    CreateNodeRequest, MoveNodeRequest, NodeResponse, NodeType, UpdateNodeRequest,
+    CreateNodeRequest, DuplicateNodeRequest, MoveNodeRequest, NodeChangeResponse, NodeListQuery,
+    NodeResponse, NodeTreeResponse, NodeType, UpdateNodeRequest,
 };
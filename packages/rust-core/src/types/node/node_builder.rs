@@ -104,6 +104,7 @@ impl NodeBuilder {
             sort_order: self.sort_order,
             is_collapsed: self.is_collapsed,
             tags: self.tags,
+            rewrite_links: None,
         }
     }
 
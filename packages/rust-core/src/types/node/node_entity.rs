@@ -3,14 +3,16 @@
 //! 节点是文件树的基本单元，可以是文件夹或各种类型的文件。
 //! SeaORM Entity 定义，对应数据库 `nodes` 表。
 
+use schemars::JsonSchema;
 use sea_orm::entity::prelude::*;
 use serde::{Deserialize, Serialize};
 
 use super::node_interface::NodeType;
 
 /// Node 实体定义
-#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel, Serialize, Deserialize, JsonSchema)]
 #[sea_orm(table_name = "nodes")]
+#[schemars(rename = "NodeModel")]
 pub struct Model {
     /// 节点 ID (UUID)
     #[sea_orm(primary_key, auto_increment = false)]
@@ -18,9 +18,12 @@
 //! | lastEdit | updated_at | 更新时间（毫秒时间戳） |
 //! | tags | tags | 标签数组（JSON） |
 
+use schemars::JsonSchema;
 use sea_orm::entity::prelude::*;
 use serde::{Deserialize, Serialize};
 
+use crate::types::link::LinkRewriteReport;
+
 // ============================================================================
 // 节点类型枚举（统一定义，同时用于 Entity 和 DTO）
 // ============================================================================
@@ -28,7 +31,18 @@ use serde::{Deserialize, Serialize};
 /// 节点类型
 /// 对应前端 NodeType: "folder" | "file" | "canvas" | "diary" | "drawing" | "wiki" | "todo" | "note" | "ledger" | "plantuml" | "mermaid" | "code"
 /// 同时用于 SeaORM Entity 和 DTO
-#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumIter, DeriveActiveEnum, Serialize, Deserialize)]
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    EnumIter,
+    DeriveActiveEnum,
+    Serialize,
+    Deserialize,
+    JsonSchema,
+)]
 #[sea_orm(rs_type = "String", db_type = "Text")]
 #[serde(rename_all = "lowercase")]
 pub enum NodeType {
@@ -134,7 +148,7 @@ impl std::str::FromStr for NodeType {
 
 /// 创建节点请求
 /// 对应前端 NodeCreateInput
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct CreateNodeRequest {
     /// 所属工作区 ID
@@ -165,7 +179,7 @@ pub struct CreateNodeRequest {
 
 /// 更新节点请求
 /// 对应前端 NodeUpdateInput
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct UpdateNodeRequest {
     /// 父节点 ID
@@ -185,10 +199,14 @@ pub struct UpdateNodeRequest {
 
     /// 标签数组
     pub tags: Option<Vec<String>>,
+
+    /// 标题变化时是否同步改写所有引用该节点的 `@` 提及（默认 false）
+    #[serde(default)]
+    pub rewrite_links: Option<bool>,
 }
 
 /// 移动节点请求
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct MoveNodeRequest {
     /// 新的父节点 ID（移动到根级别时为 null）
@@ -196,6 +214,43 @@ pub struct MoveNodeRequest {
 
     /// 新的排序顺序
     pub new_sort_order: i32,
+
+    /// 是否同步改写所有引用该节点的 `@` 提及（默认 false）
+    #[serde(default)]
+    pub rewrite_links: Option<bool>,
+}
+
+/// 复制节点请求
+#[derive(Debug, Clone, Default, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct DuplicateNodeRequest {
+    /// 副本的标题（默认为“原标题 (副本)”）
+    #[serde(default)]
+    pub new_title: Option<String>,
+}
+
+/// 工作区节点列表的筛选条件
+///
+/// 都省略时返回工作区所有节点，同时指定时取交集
+#[derive(Debug, Clone, Default, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct NodeListQuery {
+    /// 只返回该类型的节点
+    #[serde(default, rename = "type")]
+    pub node_type: Option<NodeType>,
+
+    /// 只返回该父节点下的节点（`null` 或空串表示根级别）
+    #[serde(default)]
+    pub parent_id: Option<String>,
+}
+
+impl NodeListQuery {
+    /// 父节点筛选：外层 None 表示不按父节点筛选，内层 None 表示根级别
+    pub fn parent_filter(&self) -> Option<Option<&str>> {
+        self.parent_id
+            .as_deref()
+            .map(|id| Some(id).filter(|id| !id.is_empty() && *id != "null"))
+    }
 }
 
 // ============================================================================
@@ -204,7 +259,7 @@ pub struct MoveNodeRequest {
 
 /// 节点响应
 /// 对应前端 NodeInterface
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct NodeResponse {
     /// 节点 ID
@@ -259,6 +314,36 @@ impl From<super::node_entity::Model> for NodeResponse {
     }
 }
 
+/// 节点树响应
+///
+/// 嵌套结构的节点，前端无需再从扁平列表重建树
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct NodeTreeResponse {
+    /// 节点本身（字段展开到同一层级）
+    #[serde(flatten)]
+    pub node: NodeResponse,
+
+    /// 子节点（按 sort_order 排序）
+    pub children: Vec<NodeTreeResponse>,
+}
+
+/// 节点变更响应（UpdateNode / MoveNode）
+///
+/// 节点字段展开到同一层级，与 NodeResponse 兼容；
+/// 请求了 `rewrite_links` 时附带引用改写报告
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct NodeChangeResponse {
+    /// 变更后的节点（字段展开到同一层级）
+    #[serde(flatten)]
+    pub node: NodeResponse,
+
+    /// 引用改写报告（未请求改写时省略）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub link_rewrite: Option<LinkRewriteReport>,
+}
+
 // ============================================================================
 // 不可变更新方法
 // ============================================================================
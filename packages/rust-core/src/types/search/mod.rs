@@ -0,0 +1,17 @@
+//! Search 类型模块
+//!
+//! 包含保存的搜索（虚拟文件夹）相关的所有类型定义：
+//! - `search_entity.rs` - SeaORM 数据库实体
+//! - `search_interface.rs` - DTO 结构体定义
+
+pub mod search_entity;
+pub mod search_interface;
+
+// 重新导出所有公共类型
+pub use search_entity::{
+    ActiveModel as SavedSearchActiveModel, Column as SavedSearchColumn,
+    Entity as SavedSearchEntity, Model as SavedSearchModel, Relation as SavedSearchRelation,
+};
+pub use search_interface::{
+    CreateSavedSearchRequest, SavedSearchResponse, SearchNodesQuery, UpdateSavedSearchRequest,
+};
@@ -0,0 +1,54 @@
+//! SavedSearch 实体定义
+//!
+//! 保存的搜索查询，在侧边栏中作为虚拟文件夹展示。
+//! SeaORM Entity 定义，对应数据库 `saved_searches` 表。
+
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// SavedSearch 实体定义
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "saved_searches")]
+pub struct Model {
+    /// 搜索 ID (UUID)
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: String,
+
+    /// 所属工作区 ID
+    pub workspace_id: String,
+
+    /// 显示名称
+    pub name: String,
+
+    /// 查询语句（如 `type:diary tag:travel -tag:draft`）
+    pub query: String,
+
+    /// 排序顺序
+    pub sort_order: i32,
+
+    /// 创建时间戳 (毫秒)
+    pub created_at: i64,
+
+    /// 更新时间戳 (毫秒)
+    pub updated_at: i64,
+}
+
+/// 关系定义
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    /// 搜索属于工作区（工作区删除时级联删除）
+    #[sea_orm(
+        belongs_to = "crate::types::workspace::workspace_entity::Entity",
+        from = "Column::WorkspaceId",
+        to = "crate::types::workspace::workspace_entity::Column::Id"
+    )]
+    Workspace,
+}
+
+impl Related<crate::types::workspace::workspace_entity::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Workspace.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
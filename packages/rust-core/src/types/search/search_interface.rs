@@ -0,0 +1,118 @@
+//! SavedSearch DTO 接口定义
+//!
+//! 定义保存的搜索相关的数据传输对象（DTO）
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+// ============================================================================
+// 请求 DTO
+// ============================================================================
+
+/// 创建保存的搜索请求
+#[derive(Debug, Clone, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateSavedSearchRequest {
+    /// 所属工作区 ID
+    pub workspace_id: String,
+
+    /// 显示名称
+    pub name: String,
+
+    /// 查询语句
+    pub query: String,
+}
+
+/// 更新保存的搜索请求
+#[derive(Debug, Clone, Default, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateSavedSearchRequest {
+    /// 显示名称
+    pub name: Option<String>,
+
+    /// 查询语句
+    pub query: Option<String>,
+
+    /// 排序顺序
+    pub sort_order: Option<i32>,
+}
+
+/// 临时搜索请求（不保存）
+#[derive(Debug, Clone, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SearchNodesQuery {
+    /// 查询语句
+    pub q: String,
+}
+
+// ============================================================================
+// 响应 DTO
+// ============================================================================
+
+/// 保存的搜索响应
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SavedSearchResponse {
+    /// 搜索 ID
+    pub id: String,
+
+    /// 所属工作区 ID
+    pub workspace_id: String,
+
+    /// 显示名称
+    pub name: String,
+
+    /// 查询语句
+    pub query: String,
+
+    /// 排序顺序
+    pub sort_order: i32,
+
+    /// 创建时间戳（毫秒）
+    pub created_at: i64,
+
+    /// 更新时间戳（毫秒）
+    pub updated_at: i64,
+}
+
+/// Entity -> DTO 转换
+impl From<super::search_entity::Model> for SavedSearchResponse {
+    fn from(model: super::search_entity::Model) -> Self {
+        Self {
+            id: model.id,
+            workspace_id: model.workspace_id,
+            name: model.name,
+            query: model.query,
+            sort_order: model.sort_order,
+            created_at: model.created_at,
+            updated_at: model.updated_at,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_saved_search_response_from_model() {
+        let model = super::super::search_entity::Model {
+            id: "search-1".into(),
+            workspace_id: "ws-1".into(),
+            name: "旅行日记".into(),
+            query: "type:diary tag:travel".into(),
+            sort_order: 2,
+            created_at: 1704067200000,
+            updated_at: 1704067200000,
+        };
+
+        let response = SavedSearchResponse::from(model);
+        assert_eq!(response.id, "search-1");
+        assert_eq!(response.query, "type:diary tag:travel");
+        assert_eq!(response.sort_order, 2);
+
+        let json = serde_json::to_string(&response).unwrap();
+        assert!(json.contains("workspaceId"));
+        assert!(json.contains("sortOrder"));
+    }
+}
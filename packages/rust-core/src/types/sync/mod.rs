@@ -0,0 +1,25 @@
+//! Sync 类型模块
+//!
+//! 包含推送/拉取同步相关的所有类型定义：
+//! - `sync_remote_entity.rs` - SeaORM 数据库实体（每个服务器的同步游标）
+//! - `sync_version_entity.rs` - SeaORM 数据库实体（每个实体的服务器版本）
+//! - `sync_interface.rs` - DTO 结构体定义
+
+pub mod sync_interface;
+pub mod sync_remote_entity;
+pub mod sync_version_entity;
+
+// 重新导出所有公共类型
+pub use sync_interface::{
+    SyncChange, SyncConflict, SyncConflictStrategy, SyncEntityData, SyncPullQuery,
+    SyncPullResponse, SyncPushRequest, SyncPushResponse, SyncPushResult, SyncPushStatus,
+    SyncReport, SyncRequest,
+};
+pub use sync_remote_entity::{
+    ActiveModel as SyncRemoteActiveModel, Column as SyncRemoteColumn, Entity as SyncRemoteEntity,
+    Model as SyncRemoteModel, Relation as SyncRemoteRelation,
+};
+pub use sync_version_entity::{
+    ActiveModel as SyncVersionActiveModel, Column as SyncVersionColumn,
+    Entity as SyncVersionEntity, Model as SyncVersionModel, Relation as SyncVersionRelation,
+};
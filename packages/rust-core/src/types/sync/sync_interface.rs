@@ -0,0 +1,235 @@
+//! Sync DTO 接口定义
+//!
+//! 定义桌面端与 `grain-api` 服务器之间推送/拉取同步的数据传输对象（DTO）。
+//!
+//! 每个实体的版本是它在服务器变更日志中最新一条记录的序号：
+//! 拉取时返回服务器的新版本，推送时携带本地副本所基于的版本，
+//! 服务器上的版本更新时说明期间有其他客户端修改过，判定为冲突。
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::types::change_log::{ChangeEntity, ChangeOperation};
+use crate::types::content::ContentModel;
+use crate::types::node::NodeModel;
+use crate::types::tag::TagModel;
+use crate::types::workspace::WorkspaceModel;
+
+// ============================================================================
+// 枚举
+// ============================================================================
+
+/// 冲突处理策略
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub enum SyncConflictStrategy {
+    /// 两边都不修改，返回冲突由用户处理
+    #[default]
+    Report,
+
+    /// 以本地为准，覆盖服务器
+    PreferLocal,
+
+    /// 以服务器为准，覆盖本地
+    PreferRemote,
+}
+
+/// 推送结果状态
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub enum SyncPushStatus {
+    /// 已应用（或服务器已是相同状态）
+    Applied,
+
+    /// 服务器版本比推送所基于的版本新
+    Conflict,
+
+    /// 无法应用（如父节点不存在）
+    Rejected,
+}
+
+// ============================================================================
+// 同步数据
+// ============================================================================
+
+/// 实体数据（数据库记录原样传输）
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(tag = "kind", content = "data", rename_all = "camelCase")]
+pub enum SyncEntityData {
+    /// 工作区
+    Workspace(WorkspaceModel),
+
+    /// 节点
+    Node(NodeModel),
+
+    /// 节点内容
+    Content(ContentModel),
+
+    /// 工作区的全部标签（按名称排序）
+    Tags(Vec<TagModel>),
+}
+
+impl SyncEntityData {
+    /// 两份数据是否表示相同的状态（内容记录的 ID 在两端可能不同，不参与比较）
+    pub fn same_state(&self, other: &SyncEntityData) -> bool {
+        match (self, other) {
+            (SyncEntityData::Content(a), SyncEntityData::Content(b)) => {
+                a.node_id == b.node_id && a.content == b.content && a.version == b.version
+            }
+            _ => self == other,
+        }
+    }
+}
+
+/// 一个实体的变更
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SyncChange {
+    /// 实体类型
+    pub entity_type: ChangeEntity,
+
+    /// 实体 ID（内容使用节点 ID，标签使用工作区 ID）
+    pub entity_id: String,
+
+    /// 所属工作区 ID
+    pub workspace_id: Option<String>,
+
+    /// 操作（upsert 或 delete）
+    pub operation: ChangeOperation,
+
+    /// 版本：拉取时为服务器的新版本，推送时为本地副本所基于的版本（0 表示从未同步）
+    pub version: i64,
+
+    /// 变更后的数据（删除时为空）
+    pub data: Option<SyncEntityData>,
+}
+
+// ============================================================================
+// 请求 DTO
+// ============================================================================
+
+/// 拉取参数
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SyncPullQuery {
+    /// 已拉取到的服务器变更序号（默认 0）
+    pub since: Option<i64>,
+
+    /// 最多扫描的记录条数（默认 500）
+    pub limit: Option<u64>,
+}
+
+/// 推送请求
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SyncPushRequest {
+    /// 本地变更（按依赖顺序排列：父节点在子节点之前）
+    pub changes: Vec<SyncChange>,
+
+    /// 忽略版本冲突，强制覆盖服务器
+    #[serde(default)]
+    pub force: bool,
+}
+
+/// 与服务器同步的请求
+#[derive(Debug, Clone, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SyncRequest {
+    /// 服务器地址（如 `https://grain.example.com`）
+    pub server_url: String,
+
+    /// 访问令牌（可选）
+    pub token: Option<String>,
+
+    /// 冲突处理策略（默认 report）
+    pub strategy: Option<SyncConflictStrategy>,
+
+    /// 每批拉取/推送的条数（默认 200）
+    pub batch_size: Option<u64>,
+}
+
+// ============================================================================
+// 响应 DTO
+// ============================================================================
+
+/// 拉取响应
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SyncPullResponse {
+    /// 变更（按序号升序，每个实体只保留最新一条）
+    pub changes: Vec<SyncChange>,
+
+    /// 下次拉取使用的 since
+    pub next_since: i64,
+
+    /// 是否还有更多变更
+    pub has_more: bool,
+}
+
+/// 单个变更的推送结果
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SyncPushResult {
+    /// 实体类型
+    pub entity_type: ChangeEntity,
+
+    /// 实体 ID
+    pub entity_id: String,
+
+    /// 状态
+    pub status: SyncPushStatus,
+
+    /// 服务器上的当前版本
+    pub version: i64,
+
+    /// 冲突时服务器上的当前状态
+    pub remote: Option<SyncChange>,
+
+    /// 拒绝原因
+    pub message: Option<String>,
+}
+
+/// 推送响应
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SyncPushResponse {
+    /// 与请求中的变更一一对应的结果
+    pub results: Vec<SyncPushResult>,
+}
+
+/// 同步冲突
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SyncConflict {
+    /// 本地变更
+    pub local: SyncChange,
+
+    /// 服务器上的当前状态
+    pub remote: SyncChange,
+}
+
+/// 同步报告
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SyncReport {
+    /// 从服务器应用的变更数
+    pub pulled: u64,
+
+    /// 推送并被服务器应用的变更数
+    pub pushed: u64,
+
+    /// 未解决的冲突
+    pub conflicts: Vec<SyncConflict>,
+
+    /// 被服务器拒绝的变更
+    pub rejected: Vec<SyncPushResult>,
+
+    /// 已拉取到的服务器变更序号
+    pub pull_seq: i64,
+
+    /// 已推送到的本地变更序号
+    pub push_seq: i64,
+
+    /// 完成时间戳 (毫秒)
+    pub synced_at: i64,
+}
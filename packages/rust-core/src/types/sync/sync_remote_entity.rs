@@ -0,0 +1,37 @@
+//! SyncRemote 实体定义
+//!
+//! 客户端与每个同步服务器的同步进度（拉取和推送的游标），中断后从游标处继续。
+//! SeaORM Entity 定义，对应数据库 `sync_remotes` 表。
+
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// SyncRemote 实体定义
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "sync_remotes")]
+pub struct Model {
+    /// 服务器标识（规范化的服务器地址）
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: String,
+
+    /// 服务器地址
+    pub server_url: String,
+
+    /// 已拉取到的服务器变更序号
+    pub pull_seq: i64,
+
+    /// 已推送到的本地变更序号
+    pub push_seq: i64,
+
+    /// 最近一次完成同步的时间戳 (毫秒)
+    pub last_synced_at: Option<i64>,
+
+    /// 更新时间戳 (毫秒)
+    pub updated_at: i64,
+}
+
+/// 关系定义
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,43 @@
+//! SyncVersion 实体定义
+//!
+//! 客户端记录的每个实体在服务器上的版本（服务器变更序号），
+//! 以及最近一次同步对应的本地变更序号，用于判断本地是否有未推送的修改和检测冲突。
+//! SeaORM Entity 定义，对应数据库 `sync_versions` 表。
+
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::types::change_log::ChangeEntity;
+
+/// SyncVersion 实体定义
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "sync_versions")]
+pub struct Model {
+    /// 记录 ID（格式：remote_id|entity_type|entity_id）
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: String,
+
+    /// 服务器标识
+    pub remote_id: String,
+
+    /// 实体类型
+    pub entity_type: ChangeEntity,
+
+    /// 实体 ID
+    pub entity_id: String,
+
+    /// 本地副本对应的服务器版本（服务器变更序号）
+    pub version: i64,
+
+    /// 已同步的本地变更序号（之后的本地变更尚未推送）
+    pub local_seq: i64,
+
+    /// 更新时间戳 (毫秒)
+    pub updated_at: i64,
+}
+
+/// 关系定义
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
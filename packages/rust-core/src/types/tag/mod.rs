@@ -9,5 +9,8 @@ pub use tag_entity::{
     ActiveModel as TagActiveModel, Column as TagColumn, Entity as TagEntity, Model as TagModel,
 };
 pub use tag_interface::{
-    CreateTagRequest, TagGraphData, TagGraphEdge, TagGraphNode, TagResponse, UpdateTagRequest,
+    CreateTagRequest, GetOrCreateTagRequest, MergeTagsRequest, RenameTagRequest, ResolveTagQuery,
+    SearchTagsQuery, TagGraphData, TagGraphEdge, TagGraphNode, TagNodesQuery, TagResponse,
+    TagRewriteContent, TagRewriteReport, TagTreeNode, TopTagsQuery, UpdateTagMetadataRequest,
+    UpdateTagRequest,
 };
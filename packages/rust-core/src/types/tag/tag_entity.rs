@@ -2,11 +2,14 @@
 //!
 //! 标签数据库实体定义
 
+use schemars::JsonSchema;
 use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
 
 /// 标签实体
-#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel)]
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel, Serialize, Deserialize, JsonSchema)]
 #[sea_orm(table_name = "tags")]
+#[schemars(rename = "TagModel")]
 pub struct Model {
     /// 标签 ID（格式：workspace_id:tag_name）
     #[sea_orm(primary_key, auto_increment = false)]
@@ -26,6 +29,28 @@ pub struct Model {
 
     /// 创建时间戳（毫秒）
     pub created_at: i64,
+
+    /// 显示颜色（如 `#e11d48`）
+    pub color: Option<String>,
+
+    /// 图标名称
+    pub icon: Option<String>,
+
+    /// 描述
+    pub description: Option<String>,
+
+    /// 别名列表（JSON 数组字符串）
+    pub aliases: Option<String>,
+}
+
+impl Model {
+    /// 解析别名列表（无别名或格式错误时返回空列表）
+    pub fn alias_list(&self) -> Vec<String> {
+        self.aliases
+            .as_deref()
+            .and_then(|a| serde_json::from_str(a).ok())
+            .unwrap_or_default()
+    }
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
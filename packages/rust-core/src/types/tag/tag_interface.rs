@@ -2,6 +2,7 @@
 //!
 //! 定义标签相关的数据传输对象（DTO）
 
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 // ============================================================================
@@ -9,7 +10,7 @@ use serde::{Deserialize, Serialize};
 // ============================================================================
 
 /// 创建标签请求
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct CreateTagRequest {
     /// 标签名称
@@ -20,7 +21,7 @@ pub struct CreateTagRequest {
 }
 
 /// 更新标签请求
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct UpdateTagRequest {
     /// 标签名称
@@ -33,12 +34,100 @@ pub struct UpdateTagRequest {
     pub last_used: Option<i64>,
 }
 
+/// 重命名标签请求
+///
+/// 子标签一同改名（`project/alpha` -> `work/alpha`）
+#[derive(Debug, Clone, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct RenameTagRequest {
+    /// 原标签名称
+    pub from: String,
+
+    /// 新标签名称（不能已存在，已存在时使用合并）
+    pub to: String,
+}
+
+/// 合并标签请求
+///
+/// 源标签（及其子标签）全部改为目标标签，目标标签可以不存在
+#[derive(Debug, Clone, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct MergeTagsRequest {
+    /// 源标签名称列表
+    pub sources: Vec<String>,
+
+    /// 目标标签名称
+    pub target: String,
+}
+
+/// 更新标签元数据请求
+///
+/// 字段为 None 时保持不变；颜色、图标、描述传空字符串时清除
+#[derive(Debug, Clone, Default, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateTagMetadataRequest {
+    /// 标签名称
+    pub name: String,
+
+    /// 显示颜色
+    pub color: Option<String>,
+
+    /// 图标名称
+    pub icon: Option<String>,
+
+    /// 描述
+    pub description: Option<String>,
+
+    /// 别名列表（整体替换）
+    pub aliases: Option<Vec<String>>,
+}
+
+/// 解析标签名称请求（名称可以是别名）
+#[derive(Debug, Clone, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ResolveTagQuery {
+    /// 标签名称或别名
+    pub name: String,
+}
+
+/// 获取或创建标签请求
+#[derive(Debug, Clone, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct GetOrCreateTagRequest {
+    /// 标签名称
+    pub name: String,
+}
+
+/// 获取最常用标签请求
+#[derive(Debug, Clone, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct TopTagsQuery {
+    /// 返回数量上限
+    pub limit: u64,
+}
+
+/// 搜索标签请求
+#[derive(Debug, Clone, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SearchTagsQuery {
+    /// 搜索关键词（匹配标签名称）
+    pub query: String,
+}
+
+/// 按标签查找节点请求
+#[derive(Debug, Clone, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct TagNodesQuery {
+    /// 标签名称
+    pub tag_name: String,
+}
+
 // ============================================================================
 // 响应 DTO
 // ============================================================================
 
 /// 标签响应
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct TagResponse {
     /// 标签 ID
@@ -58,11 +147,24 @@ pub struct TagResponse {
 
     /// 创建时间戳（毫秒）
     pub created_at: i64,
+
+    /// 显示颜色
+    pub color: Option<String>,
+
+    /// 图标名称
+    pub icon: Option<String>,
+
+    /// 描述
+    pub description: Option<String>,
+
+    /// 别名列表
+    pub aliases: Vec<String>,
 }
 
 /// Entity -> DTO 转换
 impl From<super::tag_entity::Model> for TagResponse {
     fn from(model: super::tag_entity::Model) -> Self {
+        let aliases = model.alias_list();
         Self {
             id: model.id,
             name: model.name,
@@ -70,10 +172,75 @@ impl From<super::tag_entity::Model> for TagResponse {
             count: model.count,
             last_used: model.last_used,
             created_at: model.created_at,
+            color: model.color,
+            icon: model.icon,
+            description: model.description,
+            aliases,
         }
     }
 }
 
+/// 标签重命名/合并结果
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct TagRewriteReport {
+    /// 改名后的标签记录（按名称排序）
+    pub tags: Vec<TagResponse>,
+
+    /// 改写了 tags 字段的节点数量
+    pub nodes_updated: u64,
+
+    /// 改写了 `#[tag]` 的内容数量
+    pub contents_updated: u64,
+
+    /// 改写了 tags 字段的节点 ID
+    pub updated_node_ids: Vec<String>,
+
+    /// 改写了 `#[tag]` 的内容
+    pub updated_contents: Vec<TagRewriteContent>,
+}
+
+/// 标签改写后的内容
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct TagRewriteContent {
+    /// 节点 ID
+    pub node_id: String,
+
+    /// 改写后的内容版本号
+    pub version: i32,
+}
+
+// ============================================================================
+// 层级标签 DTO
+// ============================================================================
+
+/// 标签树节点
+///
+/// 标签名使用 `/` 分隔层级（如 `project/alpha`）。
+/// 只作为前缀出现、本身没有标签记录的中间层级 `id` 为 None。
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct TagTreeNode {
+    /// 标签 ID（中间层级无对应标签时为 None）
+    pub id: Option<String>,
+
+    /// 完整标签路径（如 `project/alpha`）
+    pub name: String,
+
+    /// 当前层级名称（如 `alpha`）
+    pub label: String,
+
+    /// 直接使用此标签的文档数量
+    pub count: i32,
+
+    /// 使用此标签或其任一子标签的文档数量（同一文档只计一次）
+    pub rollup_count: i32,
+
+    /// 子标签
+    pub children: Vec<TagTreeNode>,
+}
+
 // ============================================================================
 // 图形数据 DTO
 // ============================================================================
@@ -81,7 +248,7 @@ impl From<super::tag_entity::Model> for TagResponse {
 /// 标签图形数据
 ///
 /// 用于可视化标签之间的关系
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct TagGraphData {
     /// 图形节点（标签）
@@ -94,7 +261,7 @@ pub struct TagGraphData {
 /// 标签图形节点
 ///
 /// 表示图形中的一个标签
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct TagGraphNode {
     /// 标签 ID
@@ -105,12 +272,38 @@ pub struct TagGraphNode {
 
     /// 使用此标签的文档数量
     pub count: i32,
+
+    /// 显示颜色
+    pub color: Option<String>,
+
+    /// 图标名称
+    pub icon: Option<String>,
+
+    /// 描述
+    pub description: Option<String>,
+
+    /// 别名列表
+    pub aliases: Vec<String>,
+}
+
+impl From<TagResponse> for TagGraphNode {
+    fn from(tag: TagResponse) -> Self {
+        Self {
+            id: tag.id,
+            name: tag.name,
+            count: tag.count,
+            color: tag.color,
+            icon: tag.icon,
+            description: tag.description,
+            aliases: tag.aliases,
+        }
+    }
 }
 
 /// 标签图形边
 ///
 /// 表示两个标签之间的关系（共同出现在同一文档中）
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct TagGraphEdge {
     /// 源标签 ID
@@ -134,14 +327,7 @@ impl TagGraphData {
 
     /// 从标签响应列表创建图形节点
     pub fn from_tags(tags: Vec<TagResponse>) -> Self {
-        let nodes = tags
-            .into_iter()
-            .map(|tag| TagGraphNode {
-                id: tag.id,
-                name: tag.name,
-                count: tag.count,
-            })
-            .collect();
+        let nodes = tags.into_iter().map(TagGraphNode::from).collect();
 
         Self {
             nodes,
@@ -163,12 +349,18 @@ mod tests {
             count: 5,
             last_used: 1704067200000,
             created_at: 1704067200000,
+            color: Some("#dea584".into()),
+            icon: None,
+            description: None,
+            aliases: Some(r#"["rs"]"#.into()),
         };
 
         let response = TagResponse::from(model);
         assert_eq!(response.id, "ws-1:rust");
         assert_eq!(response.name, "rust");
         assert_eq!(response.count, 5);
+        assert_eq!(response.color.as_deref(), Some("#dea584"));
+        assert_eq!(response.aliases, vec!["rs"]);
     }
 
     #[test]
@@ -188,6 +380,10 @@ mod tests {
                 count: 5,
                 last_used: 1704067200000,
                 created_at: 1704067200000,
+                color: None,
+                icon: None,
+                description: None,
+                aliases: Vec::new(),
             },
             TagResponse {
                 id: "ws-1:programming".into(),
@@ -196,6 +392,10 @@ mod tests {
                 count: 10,
                 last_used: 1704067200000,
                 created_at: 1704067200000,
+                color: None,
+                icon: None,
+                description: None,
+                aliases: Vec::new(),
             },
         ];
 
@@ -212,6 +412,10 @@ mod tests {
             id: "ws-1:rust".into(),
             name: "rust".into(),
             count: 5,
+            color: None,
+            icon: None,
+            description: None,
+            aliases: Vec::new(),
         };
         assert_eq!(node.id, "ws-1:rust");
         assert_eq!(node.name, "rust");
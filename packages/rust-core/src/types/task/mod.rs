@@ -0,0 +1,17 @@
+//! Task 类型模块
+//!
+//! 包含从待办内容中提取的任务相关的所有类型定义：
+//! - `task_entity.rs` - SeaORM 数据库实体
+//! - `task_interface.rs` - DTO 结构体定义
+
+pub mod task_entity;
+pub mod task_interface;
+
+// 重新导出所有公共类型
+pub use task_entity::{
+    ActiveModel as TaskActiveModel, Column as TaskColumn, Entity as TaskEntity, Model as TaskModel,
+    Relation as TaskRelation,
+};
+pub use task_interface::{
+    TaskDueFilter, TaskPriority, TaskQuery, TaskResponse, TaskStatus, ToggleTaskRequest,
+};
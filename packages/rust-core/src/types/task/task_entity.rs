@@ -0,0 +1,78 @@
+//! Task 实体定义
+//!
+//! 从待办（todo）节点内容的勾选列表中提取的任务索引。
+//! SeaORM Entity 定义，对应数据库 `tasks` 表。
+
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use super::task_interface::TaskPriority;
+
+/// Task 实体定义
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "tasks")]
+pub struct Model {
+    /// 任务 ID（格式：node_id:position）
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: String,
+
+    /// 所属节点 ID
+    pub node_id: String,
+
+    /// 所属工作区 ID
+    pub workspace_id: String,
+
+    /// 在内容中的位置（按文档顺序的第几个勾选项，从 0 开始）
+    pub position: i32,
+
+    /// 任务文本
+    pub text: String,
+
+    /// 是否已完成
+    pub checked: bool,
+
+    /// 截止日期（YYYY-MM-DD）
+    pub due_date: Option<String>,
+
+    /// 优先级
+    pub priority: Option<TaskPriority>,
+
+    /// 任务中的标签（JSON 数组字符串）
+    pub tags: Option<String>,
+
+    /// 提取时的内容版本号
+    pub content_version: i32,
+
+    /// 更新时间戳 (毫秒)
+    pub updated_at: i64,
+}
+
+/// 关系定义
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    /// 任务属于节点（节点删除时级联删除）
+    #[sea_orm(
+        belongs_to = "crate::types::node::node_entity::Entity",
+        from = "Column::NodeId",
+        to = "crate::types::node::node_entity::Column::Id"
+    )]
+    Node,
+}
+
+impl Related<crate::types::node::node_entity::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Node.def()
+    }
+}
+
+impl Model {
+    /// 解析标签列表
+    pub fn tag_list(&self) -> Vec<String> {
+        self.tags
+            .as_deref()
+            .and_then(|t| serde_json::from_str(t).ok())
+            .unwrap_or_default()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,223 @@
+//! Task DTO 接口定义
+//!
+//! 定义任务索引相关的数据传输对象（DTO）。
+//! 任务是待办（todo）节点内容中的勾选列表项，保存内容时提取。
+
+use schemars::JsonSchema;
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+// ============================================================================
+// 枚举
+// ============================================================================
+
+/// 任务优先级
+/// 在任务文本中以 `!high` / `!medium` / `!low` 标记
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    EnumIter,
+    DeriveActiveEnum,
+    Serialize,
+    Deserialize,
+    JsonSchema,
+)]
+#[sea_orm(rs_type = "String", db_type = "Text")]
+#[serde(rename_all = "lowercase")]
+pub enum TaskPriority {
+    /// 高
+    #[sea_orm(string_value = "high")]
+    High,
+
+    /// 中
+    #[sea_orm(string_value = "medium")]
+    Medium,
+
+    /// 低
+    #[sea_orm(string_value = "low")]
+    Low,
+}
+
+impl TaskPriority {
+    /// 排序权重（高优先级在前）
+    pub fn rank(self) -> u8 {
+        match self {
+            TaskPriority::High => 0,
+            TaskPriority::Medium => 1,
+            TaskPriority::Low => 2,
+        }
+    }
+}
+
+impl std::str::FromStr for TaskPriority {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "high" => Ok(TaskPriority::High),
+            "medium" => Ok(TaskPriority::Medium),
+            "low" => Ok(TaskPriority::Low),
+            _ => Err(format!("未知的任务优先级: {}", s)),
+        }
+    }
+}
+
+/// 任务完成状态过滤
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub enum TaskStatus {
+    /// 未完成
+    Open,
+    /// 已完成
+    Done,
+}
+
+/// 任务截止日期过滤
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub enum TaskDueFilter {
+    /// 已逾期（截止日期早于今天且未完成）
+    Overdue,
+    /// 今天到期
+    Today,
+    /// 本周到期（周一至周日）
+    ThisWeek,
+}
+
+// ============================================================================
+// 请求 DTO
+// ============================================================================
+
+/// 任务查询条件（所有条件可选，同时给出时取交集）
+#[derive(Debug, Clone, Default, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct TaskQuery {
+    /// 完成状态
+    pub status: Option<TaskStatus>,
+
+    /// 截止日期范围
+    pub due: Option<TaskDueFilter>,
+
+    /// 标签（匹配任务标签或所在节点的标签，包含子标签）
+    pub tag: Option<String>,
+
+    /// 只查询指定节点中的任务
+    pub node_id: Option<String>,
+
+    /// 作为"今天"的日期（`YYYY-MM-DD`，默认 UTC 当天）
+    pub today: Option<String>,
+}
+
+/// 切换任务勾选状态请求
+#[derive(Debug, Clone, Default, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ToggleTaskRequest {
+    /// 目标状态（为空时取反）
+    pub checked: Option<bool>,
+
+    /// 期望的内容版本号（乐观锁，可选）
+    pub expected_version: Option<i32>,
+}
+
+// ============================================================================
+// 响应 DTO
+// ============================================================================
+
+/// 任务响应
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct TaskResponse {
+    /// 任务 ID
+    pub id: String,
+
+    /// 所属节点 ID
+    pub node_id: String,
+
+    /// 所属工作区 ID
+    pub workspace_id: String,
+
+    /// 在内容中的位置
+    pub position: i32,
+
+    /// 任务文本
+    pub text: String,
+
+    /// 是否已完成
+    pub checked: bool,
+
+    /// 截止日期（YYYY-MM-DD）
+    pub due_date: Option<String>,
+
+    /// 优先级
+    pub priority: Option<TaskPriority>,
+
+    /// 任务中的标签
+    pub tags: Vec<String>,
+
+    /// 提取时的内容版本号
+    pub content_version: i32,
+
+    /// 更新时间戳（毫秒）
+    pub updated_at: i64,
+}
+
+/// Entity -> DTO 转换
+impl From<super::task_entity::Model> for TaskResponse {
+    fn from(model: super::task_entity::Model) -> Self {
+        let tags = model.tag_list();
+        Self {
+            id: model.id,
+            node_id: model.node_id,
+            workspace_id: model.workspace_id,
+            position: model.position,
+            text: model.text,
+            checked: model.checked,
+            due_date: model.due_date,
+            priority: model.priority,
+            tags,
+            content_version: model.content_version,
+            updated_at: model.updated_at,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_task_response_from_model() {
+        let model = super::super::task_entity::Model {
+            id: "node-1:0".into(),
+            node_id: "node-1".into(),
+            workspace_id: "ws-1".into(),
+            position: 0,
+            text: "提交报告 !high".into(),
+            checked: false,
+            due_date: Some("2026-01-05".into()),
+            priority: Some(TaskPriority::High),
+            tags: Some(r#"["work"]"#.into()),
+            content_version: 3,
+            updated_at: 1704067200000,
+        };
+
+        let response = TaskResponse::from(model);
+        assert_eq!(response.tags, vec!["work"]);
+
+        let json = serde_json::to_string(&response).unwrap();
+        assert!(json.contains("dueDate"));
+        assert!(json.contains(r#""priority":"high""#));
+    }
+
+    #[test]
+    fn test_task_query_deserialize() {
+        let query: TaskQuery =
+            serde_json::from_str(r#"{"status":"open","due":"thisWeek","tag":"work"}"#).unwrap();
+        assert_eq!(query.status, Some(TaskStatus::Open));
+        assert_eq!(query.due, Some(TaskDueFilter::ThisWeek));
+        assert_eq!(query.tag.as_deref(), Some("work"));
+    }
+}
@@ -3,6 +3,7 @@
 //! 定义用户相关的数据传输对象（DTO）
 
 use super::user_entity::UserPlan;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 // ============================================================================
@@ -10,7 +11,7 @@ use serde::{Deserialize, Serialize};
 // ============================================================================
 
 /// 用户功能权限
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, Default)]
 #[serde(rename_all = "camelCase")]
 pub struct UserFeatures {
     pub can_use_all_scenes: Option<bool>,
@@ -21,7 +22,7 @@ pub struct UserFeatures {
 }
 
 /// 用户应用状态
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, Default)]
 #[serde(rename_all = "camelCase")]
 pub struct UserState {
     pub last_location: Option<String>,
@@ -36,7 +37,7 @@ pub struct UserState {
 }
 
 /// 用户设置
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, Default)]
 #[serde(rename_all = "camelCase")]
 pub struct UserSettings {
     pub theme: Option<String>,
@@ -52,7 +53,7 @@ pub struct UserSettings {
 // ============================================================================
 
 /// 创建用户请求
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct CreateUserRequest {
     pub username: String,
@@ -65,7 +66,7 @@ pub struct CreateUserRequest {
 }
 
 /// 更新用户请求
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct UpdateUserRequest {
     pub username: Option<String>,
@@ -89,7 +90,7 @@ pub struct UpdateUserRequest {
 // ============================================================================
 
 /// 用户响应
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct UserResponse {
     pub id: String,
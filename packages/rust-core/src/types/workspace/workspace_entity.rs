@@ -3,12 +3,14 @@
 //! 工作区是节点的顶层容器。
 //! SeaORM Entity 定义，对应数据库 `workspaces` 表。
 
+use schemars::JsonSchema;
 use sea_orm::entity::prelude::*;
 use serde::{Deserialize, Serialize};
 
 /// Workspace 实体定义
-#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel, Serialize, Deserialize, JsonSchema)]
 #[sea_orm(table_name = "workspaces")]
+#[schemars(rename = "WorkspaceModel")]
 pub struct Model {
     /// 工作区 ID (UUID)
     #[sea_orm(primary_key, auto_increment = false)]
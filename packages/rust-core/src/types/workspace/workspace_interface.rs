@@ -18,6 +18,7 @@
 //! | members | members | 团队成员 |
 //! | owner | owner | 所有者 |
 
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 // ============================================================================
@@ -26,7 +27,7 @@ use serde::{Deserialize, Serialize};
 
 /// 创建工作区请求
 /// 对应前端 WorkspaceCreateInput
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct CreateWorkspaceRequest {
     /// 工作区标题
@@ -53,7 +54,7 @@ pub struct CreateWorkspaceRequest {
 
 /// 更新工作区请求
 /// 对应前端 WorkspaceUpdateInput
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct UpdateWorkspaceRequest {
     /// 工作区标题
@@ -87,7 +88,7 @@ pub struct UpdateWorkspaceRequest {
 
 /// 工作区响应
 /// 对应前端 WorkspaceInterface
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct WorkspaceResponse {
     /// 工作区 ID
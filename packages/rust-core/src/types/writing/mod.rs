@@ -0,0 +1,33 @@
+//! Writing 类型模块
+//!
+//! 包含写作统计和写作目标相关的所有类型定义：
+//! - `writing_stats_entity.rs` - SeaORM 数据库实体（统计缓存）
+//! - `writing_goal_entity.rs` - SeaORM 数据库实体（写作目标）
+//! - `writing_progress_entity.rs` - SeaORM 数据库实体（每日字数变化）
+//! - `writing_interface.rs` - DTO 结构体定义
+
+pub mod writing_goal_entity;
+pub mod writing_interface;
+pub mod writing_progress_entity;
+pub mod writing_stats_entity;
+
+// 重新导出所有公共类型
+pub use writing_goal_entity::{
+    ActiveModel as WritingGoalActiveModel, Column as WritingGoalColumn,
+    Entity as WritingGoalEntity, Model as WritingGoalModel, Relation as WritingGoalRelation,
+};
+pub use writing_interface::{
+    NodeWritingStats, SetWritingGoalRequest, WritingDay, WritingGoalKind, WritingGoalProgress,
+    WritingGoalResponse, WritingProgressQuery, WritingProgressResponse, WritingStats,
+    WritingStatsQuery, WritingStatsResponse, WritingStreak, CJK_CHARACTERS_PER_MINUTE,
+    LATIN_WORDS_PER_MINUTE,
+};
+pub use writing_progress_entity::{
+    ActiveModel as WritingProgressActiveModel, Column as WritingProgressColumn,
+    Entity as WritingProgressEntity, Model as WritingProgressModel,
+    Relation as WritingProgressRelation,
+};
+pub use writing_stats_entity::{
+    ActiveModel as WritingStatsActiveModel, Column as WritingStatsColumn,
+    Entity as WritingStatsEntity, Model as WritingStatsModel, Relation as WritingStatsRelation,
+};
@@ -0,0 +1,59 @@
+//! WritingGoal 实体定义
+//!
+//! 工作区或节点（文件夹/文档）的写作目标。
+//! SeaORM Entity 定义，对应数据库 `writing_goals` 表。
+
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use super::writing_interface::WritingGoalKind;
+
+/// WritingGoal 实体定义
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "writing_goals")]
+pub struct Model {
+    /// 目标 ID
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: String,
+
+    /// 所属工作区 ID
+    pub workspace_id: String,
+
+    /// 目标节点 ID（为空表示整个工作区）
+    pub node_id: Option<String>,
+
+    /// 目标类型
+    pub kind: WritingGoalKind,
+
+    /// 目标字数
+    pub target: i64,
+
+    /// 截止日期（YYYY-MM-DD，可选）
+    pub deadline: Option<String>,
+
+    /// 创建时间戳 (毫秒)
+    pub created_at: i64,
+
+    /// 更新时间戳 (毫秒)
+    pub updated_at: i64,
+}
+
+/// 关系定义
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    /// 目标属于工作区（工作区删除时级联删除）
+    #[sea_orm(
+        belongs_to = "crate::types::workspace::workspace_entity::Entity",
+        from = "Column::WorkspaceId",
+        to = "crate::types::workspace::workspace_entity::Column::Id"
+    )]
+    Workspace,
+}
+
+impl Related<crate::types::workspace::workspace_entity::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Workspace.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,388 @@
+//! Writing DTO 接口定义
+//!
+//! 定义写作统计、写作目标和每日进度相关的数据传输对象（DTO）。
+//! 中日韩文字按字计数，其他文字按词计数，阅读时间按两者分别估算。
+
+use std::ops::AddAssign;
+
+use schemars::JsonSchema;
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::types::node::NodeType;
+
+/// 中日韩文字每分钟阅读字数
+pub const CJK_CHARACTERS_PER_MINUTE: u64 = 400;
+
+/// 其他文字每分钟阅读词数
+pub const LATIN_WORDS_PER_MINUTE: u64 = 200;
+
+// ============================================================================
+// 枚举
+// ============================================================================
+
+/// 写作目标类型
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    EnumIter,
+    DeriveActiveEnum,
+    Serialize,
+    Deserialize,
+    JsonSchema,
+)]
+#[sea_orm(rs_type = "String", db_type = "Text")]
+#[serde(rename_all = "lowercase")]
+pub enum WritingGoalKind {
+    /// 总字数（如 8 万字的书稿）
+    #[sea_orm(string_value = "total")]
+    Total,
+
+    /// 每日字数（如每天 1000 字）
+    #[sea_orm(string_value = "daily")]
+    Daily,
+}
+
+// ============================================================================
+// 统计值
+// ============================================================================
+
+/// 写作统计
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct WritingStats {
+    /// 文档数
+    pub documents: u64,
+
+    /// 中日韩字符数（汉字、假名、谚文）
+    pub cjk_characters: u64,
+
+    /// 其他文字的词数（连续的字母数字）
+    pub latin_words: u64,
+
+    /// 字数（中日韩字符数 + 词数）
+    pub words: u64,
+
+    /// 非空白字符数
+    pub characters: u64,
+
+    /// 段落数
+    pub paragraphs: u64,
+
+    /// 预计阅读时间（分钟，向上取整）
+    pub reading_minutes: u64,
+}
+
+impl WritingStats {
+    /// 根据各项计数构造单个文档的统计，同时计算字数和阅读时间
+    pub fn new(cjk_characters: u64, latin_words: u64, characters: u64, paragraphs: u64) -> Self {
+        let mut stats = Self {
+            documents: 1,
+            cjk_characters,
+            latin_words,
+            characters,
+            paragraphs,
+            ..Default::default()
+        };
+        stats.update_derived();
+        stats
+    }
+
+    /// 重新计算字数和阅读时间
+    fn update_derived(&mut self) {
+        self.words = self.cjk_characters + self.latin_words;
+        // 通分后整体向上取整，避免两种文字分别取整
+        let numerator = self.cjk_characters * LATIN_WORDS_PER_MINUTE
+            + self.latin_words * CJK_CHARACTERS_PER_MINUTE;
+        self.reading_minutes =
+            numerator.div_ceil(CJK_CHARACTERS_PER_MINUTE * LATIN_WORDS_PER_MINUTE);
+    }
+}
+
+impl AddAssign for WritingStats {
+    fn add_assign(&mut self, other: Self) {
+        self.documents += other.documents;
+        self.cjk_characters += other.cjk_characters;
+        self.latin_words += other.latin_words;
+        self.characters += other.characters;
+        self.paragraphs += other.paragraphs;
+        self.update_derived();
+    }
+}
+
+/// Entity -> 统计值转换
+impl From<&super::writing_stats_entity::Model> for WritingStats {
+    fn from(model: &super::writing_stats_entity::Model) -> Self {
+        Self::new(
+            model.cjk_characters as u64,
+            model.latin_words as u64,
+            model.characters as u64,
+            model.paragraphs as u64,
+        )
+    }
+}
+
+// ============================================================================
+// 请求 DTO
+// ============================================================================
+
+/// 写作统计查询参数
+#[derive(Debug, Clone, Default, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct WritingStatsQuery {
+    /// 只统计指定节点（文件夹）及其子孙节点（可选，默认整个工作区）
+    pub node_id: Option<String>,
+}
+
+/// 设置写作目标请求
+///
+/// 同一范围（工作区或节点）的同类目标只有一个，再次设置时覆盖。
+#[derive(Debug, Clone, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SetWritingGoalRequest {
+    /// 目标节点 ID（为空表示整个工作区）
+    pub node_id: Option<String>,
+
+    /// 目标类型
+    pub kind: WritingGoalKind,
+
+    /// 目标字数
+    pub target: i64,
+
+    /// 截止日期（YYYY-MM-DD，可选）
+    pub deadline: Option<String>,
+}
+
+/// 写作进度查询参数
+#[derive(Debug, Clone, Default, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct WritingProgressQuery {
+    /// 只统计指定节点及其子孙节点的连续天数和热力图（可选）
+    pub node_id: Option<String>,
+
+    /// 热力图起始日期（YYYY-MM-DD，默认今天往前一年）
+    pub from: Option<String>,
+
+    /// 热力图结束日期（YYYY-MM-DD，默认今天）
+    pub to: Option<String>,
+
+    /// 作为"今天"的日期 `YYYY-MM-DD`（可选，默认本地当天）
+    pub today: Option<String>,
+}
+
+// ============================================================================
+// 响应 DTO
+// ============================================================================
+
+/// 单个节点的写作统计（文件夹为所有子孙文档的合计）
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct NodeWritingStats {
+    /// 节点 ID
+    pub node_id: String,
+
+    /// 节点标题
+    pub title: String,
+
+    /// 节点类型
+    pub node_type: NodeType,
+
+    /// 父节点 ID
+    pub parent_id: Option<String>,
+
+    /// 统计值
+    #[serde(flatten)]
+    pub stats: WritingStats,
+}
+
+/// 写作统计响应
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct WritingStatsResponse {
+    /// 范围内所有文档的合计
+    pub total: WritingStats,
+
+    /// 包含文档的文件夹（按字数降序）
+    pub folders: Vec<NodeWritingStats>,
+
+    /// 各文档的统计（按字数降序）
+    pub documents: Vec<NodeWritingStats>,
+}
+
+/// 写作目标响应
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct WritingGoalResponse {
+    /// 目标 ID
+    pub id: String,
+
+    /// 所属工作区 ID
+    pub workspace_id: String,
+
+    /// 目标节点 ID（为空表示整个工作区）
+    pub node_id: Option<String>,
+
+    /// 目标类型
+    pub kind: WritingGoalKind,
+
+    /// 目标字数
+    pub target: i64,
+
+    /// 截止日期（YYYY-MM-DD）
+    pub deadline: Option<String>,
+
+    /// 创建时间戳（毫秒）
+    pub created_at: i64,
+
+    /// 更新时间戳（毫秒）
+    pub updated_at: i64,
+}
+
+/// Entity -> DTO 转换
+impl From<super::writing_goal_entity::Model> for WritingGoalResponse {
+    fn from(model: super::writing_goal_entity::Model) -> Self {
+        Self {
+            id: model.id,
+            workspace_id: model.workspace_id,
+            node_id: model.node_id,
+            kind: model.kind,
+            target: model.target,
+            deadline: model.deadline,
+            created_at: model.created_at,
+            updated_at: model.updated_at,
+        }
+    }
+}
+
+/// 写作目标及其完成情况
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct WritingGoalProgress {
+    /// 目标
+    #[serde(flatten)]
+    pub goal: WritingGoalResponse,
+
+    /// 当前字数（总字数目标为范围内的字数，每日目标为今天的净增字数）
+    pub current: i64,
+
+    /// 剩余字数
+    pub remaining: i64,
+
+    /// 完成百分比（0-100）
+    pub percent: u32,
+
+    /// 是否已完成
+    pub achieved: bool,
+
+    /// 距截止日期的天数（包含今天，没有截止日期时为 None）
+    pub days_left: Option<i64>,
+
+    /// 按期完成每天需要写的字数（没有截止日期时为 None）
+    pub daily_needed: Option<i64>,
+}
+
+/// 某一天的写作进度（热力图单元）
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct WritingDay {
+    /// 日期（YYYY-MM-DD）
+    pub date: String,
+
+    /// 增加的字数
+    pub words_added: i64,
+
+    /// 删除的字数
+    pub words_removed: i64,
+
+    /// 净增字数
+    pub net_words: i64,
+
+    /// 是否达成当天目标（没有每日目标时为净增字数大于 0）
+    pub goal_met: bool,
+}
+
+/// 连续写作天数
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct WritingStreak {
+    /// 当前连续天数（今天尚未达成时从昨天往前计算）
+    pub current: u32,
+
+    /// 历史最长连续天数
+    pub longest: u32,
+}
+
+/// 写作进度响应
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct WritingProgressResponse {
+    /// 今天的日期（YYYY-MM-DD）
+    pub today: String,
+
+    /// 今天的净增字数
+    pub today_words: i64,
+
+    /// 连续写作天数
+    pub streak: WritingStreak,
+
+    /// 工作区所有目标的完成情况
+    pub goals: Vec<WritingGoalProgress>,
+
+    /// 热力图（只包含有记录的日期，升序）
+    pub heatmap: Vec<WritingDay>,
+}
+
+// ============================================================================
+// 测试
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_writing_stats_sum() {
+        let mut total = WritingStats::new(400, 0, 400, 3);
+        assert_eq!(total.reading_minutes, 1);
+
+        total += WritingStats::new(0, 110, 500, 2);
+        assert_eq!(total.documents, 2);
+        assert_eq!(total.words, 510);
+        assert_eq!(total.paragraphs, 5);
+        // 1 分钟 + 0.55 分钟
+        assert_eq!(total.reading_minutes, 2);
+
+        assert_eq!(WritingStats::default().reading_minutes, 0);
+    }
+
+    #[test]
+    fn test_set_goal_request_deserialize() {
+        let request: SetWritingGoalRequest =
+            serde_json::from_str(r#"{"kind":"daily","target":1000}"#).unwrap();
+        assert_eq!(request.kind, WritingGoalKind::Daily);
+        assert!(request.node_id.is_none());
+        assert!(
+            serde_json::from_str::<SetWritingGoalRequest>(r#"{"kind":"weekly","target":1}"#)
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_node_writing_stats_serialize() {
+        let stats = NodeWritingStats {
+            node_id: "n1".to_string(),
+            title: "草稿".to_string(),
+            node_type: NodeType::File,
+            parent_id: None,
+            stats: WritingStats::new(2, 1, 4, 1),
+        };
+        let json = serde_json::to_value(&stats).unwrap();
+        assert_eq!(json["nodeId"], "n1");
+        assert_eq!(json["cjkCharacters"], 2);
+        assert_eq!(json["words"], 3);
+        assert_eq!(json["readingMinutes"], 1);
+    }
+}
@@ -0,0 +1,55 @@
+//! WritingProgress 实体定义
+//!
+//! 每个节点每天的字数变化，保存内容时累计。
+//! 节点删除后历史记录保留（计入工作区的统计），工作区删除时级联删除。
+//! SeaORM Entity 定义，对应数据库 `writing_progress` 表。
+
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// WritingProgress 实体定义
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "writing_progress")]
+pub struct Model {
+    /// 记录 ID（格式：node_id:YYYY-MM-DD）
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: String,
+
+    /// 所属工作区 ID
+    pub workspace_id: String,
+
+    /// 节点 ID
+    pub node_id: String,
+
+    /// 日期（YYYY-MM-DD）
+    pub date: String,
+
+    /// 当天增加的字数
+    pub words_added: i64,
+
+    /// 当天删除的字数
+    pub words_removed: i64,
+
+    /// 更新时间戳 (毫秒)
+    pub updated_at: i64,
+}
+
+/// 关系定义
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    /// 记录属于工作区（工作区删除时级联删除）
+    #[sea_orm(
+        belongs_to = "crate::types::workspace::workspace_entity::Entity",
+        from = "Column::WorkspaceId",
+        to = "crate::types::workspace::workspace_entity::Column::Id"
+    )]
+    Workspace,
+}
+
+impl Related<crate::types::workspace::workspace_entity::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Workspace.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
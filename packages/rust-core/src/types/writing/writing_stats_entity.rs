@@ -0,0 +1,57 @@
+//! WritingStats 实体定义
+//!
+//! 节点内容的写作统计缓存，按内容版本号失效。
+//! SeaORM Entity 定义，对应数据库 `writing_stats` 表。
+
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// WritingStats 实体定义
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "writing_stats")]
+pub struct Model {
+    /// 节点 ID
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub node_id: String,
+
+    /// 所属工作区 ID
+    pub workspace_id: String,
+
+    /// 统计时的内容版本号
+    pub content_version: i32,
+
+    /// 中日韩字符数
+    pub cjk_characters: i64,
+
+    /// 其他文字的词数
+    pub latin_words: i64,
+
+    /// 非空白字符数
+    pub characters: i64,
+
+    /// 段落数
+    pub paragraphs: i64,
+
+    /// 更新时间戳 (毫秒)
+    pub updated_at: i64,
+}
+
+/// 关系定义
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    /// 统计属于节点（节点删除时级联删除）
+    #[sea_orm(
+        belongs_to = "crate::types::node::node_entity::Entity",
+        from = "Column::NodeId",
+        to = "crate::types::node::node_entity::Column::Id"
+    )]
+    Node,
+}
+
+impl Related<crate::types::node::node_entity::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Node.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}